@@ -0,0 +1,199 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! Resolution of the CSS `<gradient>` values of a box to the [`Gradient`]s
+//! painted by retina-gfx.
+//!
+//! # References
+//! * [CSS Images Module Level 3 § 3. Gradients](https://drafts.csswg.org/css-images-3/#gradients)
+
+use retina_gfx::{
+    Color,
+    euclid::default::{Point2D, Size2D, Vector2D},
+    Gradient,
+    GradientKind,
+    GradientStop,
+};
+use retina_layout::LayoutBox;
+use retina_style::{
+    CssColor,
+    CssColorStop,
+    CssDecimal,
+    CssGradient,
+    CssHorizontalSide,
+    CssLinearGradient,
+    CssLinearGradientDirection,
+    CssRadialGradient,
+    CssRadialGradientShape,
+    CssVerticalSide,
+};
+
+/// Resolve the gradient for painting it in a rect of the given `size`.
+pub(crate) fn resolve_gradient(gradient: &CssGradient, layout_box: &LayoutBox, size: Size2D<CssDecimal>) -> Gradient {
+    match gradient {
+        CssGradient::Linear(gradient) => resolve_linear_gradient(gradient, layout_box, size),
+        CssGradient::Radial(gradient) => resolve_radial_gradient(gradient, layout_box, size),
+    }
+}
+
+/// Compute the gradient line, which passes through the center of the rect,
+/// and of which the length is such that the corners of the rect are at `0%`
+/// and `100%`.
+///
+/// # References
+/// * [CSS Images Module Level 3 § 3.1.1. `linear-gradient()` syntax](https://drafts.csswg.org/css-images-3/#linear-gradient-syntax)
+fn resolve_linear_gradient(gradient: &CssLinearGradient, layout_box: &LayoutBox, size: Size2D<CssDecimal>) -> Gradient {
+    let direction = match gradient.direction {
+        // `0deg` points upwards, and positive angles rotate clockwise.
+        CssLinearGradientDirection::Angle(angle) => {
+            let (sin, cos) = angle.to_radians().sin_cos();
+            Vector2D::new(sin, -cos)
+        }
+
+        CssLinearGradientDirection::ToSideOrCorner { horizontal, vertical } => {
+            let x = match horizontal {
+                Some(CssHorizontalSide::Left) => -1.0,
+                Some(CssHorizontalSide::Right) => 1.0,
+                None => 0.0,
+            };
+            let y = match vertical {
+                Some(CssVerticalSide::Top) => -1.0,
+                Some(CssVerticalSide::Bottom) => 1.0,
+                None => 0.0,
+            };
+
+            // > If the argument instead specifies a corner of the box such as
+            // > to top left, the gradient line must be angled such that it
+            // > points into the same quadrant as the specified corner, and it
+            // > is perpendicular to a line intersecting the two neighboring
+            // > corners of the gradient box.
+            if x != 0.0 && y != 0.0 {
+                Vector2D::new(x * size.height, y * size.width).try_normalize().unwrap_or(Vector2D::new(x, y))
+            } else {
+                Vector2D::new(x, y)
+            }
+        }
+    };
+
+    let length = (size.width * direction.x).abs() + (size.height * direction.y).abs();
+    let center = Point2D::new(size.width / 2.0, size.height / 2.0);
+    let half_line = direction * (length / 2.0);
+
+    Gradient {
+        kind: GradientKind::Linear {
+            start: center - half_line,
+            end: center + half_line,
+        },
+        stops: resolve_color_stops(&gradient.color_stops, layout_box, length),
+    }
+}
+
+/// Compute the ending shape of the gradient, which is sized to pass through
+/// the `farthest-corner` of the rect.
+///
+/// # References
+/// * [CSS Images Module Level 3 § 3.2.1. `radial-gradient()` syntax](https://drafts.csswg.org/css-images-3/#radial-gradient-syntax)
+fn resolve_radial_gradient(gradient: &CssRadialGradient, layout_box: &LayoutBox, size: Size2D<CssDecimal>) -> Gradient {
+    let center = Point2D::new(
+        layout_box.resolve_length(gradient.center.0, Some(size.width)).unwrap_or(size.width / 2.0),
+        layout_box.resolve_length(gradient.center.1, Some(size.height)).unwrap_or(size.height / 2.0),
+    );
+
+    let farthest_side = Size2D::new(
+        center.x.max(size.width - center.x),
+        center.y.max(size.height - center.y),
+    );
+
+    let radii = match gradient.shape {
+        CssRadialGradientShape::Circle => {
+            let radius = farthest_side.width.hypot(farthest_side.height);
+            Size2D::new(radius, radius)
+        }
+
+        // > If the shape is an ellipse, the ending shape has the same aspect
+        // > ratio it would have for farthest-side.
+        CssRadialGradientShape::Ellipse => farthest_side * std::f64::consts::SQRT_2,
+    };
+
+    Gradient {
+        kind: GradientKind::Radial { center, radii },
+
+        // The positions of the stops are along the gradient ray, which
+        // extends horizontally from the center to the ending shape.
+        stops: resolve_color_stops(&gradient.color_stops, layout_box, radii.width),
+    }
+}
+
+/// Resolve the positions of the color stops to offsets along the gradient
+/// line of the given `length`, and fix up the positions that are missing or
+/// before the position of a previous stop.
+///
+/// # References
+/// * [CSS Images Module Level 4 § 3.5.3. Color Stop “Fixup”](https://drafts.csswg.org/css-images-4/#color-stop-fixup)
+fn resolve_color_stops(color_stops: &[CssColorStop], layout_box: &LayoutBox, length: CssDecimal) -> Vec<GradientStop> {
+    let mut offsets: Vec<Option<CssDecimal>> = color_stops.iter()
+        .map(|stop| {
+            let position = layout_box.resolve_length(stop.position?, Some(length))?;
+            Some(if length > 0.0 { position / length } else { 0.0 })
+        })
+        .collect();
+
+    // > If the first color stop does not have a position, set its position
+    // > to 0%. If the last color stop does not have a position, set its
+    // > position to 100%.
+    if let Some(first) = offsets.first_mut() {
+        first.get_or_insert(0.0);
+    }
+    if let Some(last) = offsets.last_mut() {
+        last.get_or_insert(1.0);
+    }
+
+    // > If a color stop has a position that is less than the specified
+    // > position of any color stop before it in the list, set its position to
+    // > be equal to the largest specified position of any color stop before
+    // > it.
+    let mut largest = CssDecimal::MIN;
+    for offset in offsets.iter_mut().flatten() {
+        largest = largest.max(*offset);
+        *offset = largest;
+    }
+
+    // > If any color stop still does not have a position, then, for each run
+    // > of adjacent color stops without positions, set their positions so
+    // > that they are evenly spaced between the preceding and following
+    // > color stops with positions.
+    let mut index = 0;
+    while index < offsets.len() {
+        if offsets[index].is_some() {
+            index += 1;
+            continue;
+        }
+
+        let run_start = index - 1;
+        let run_end = (index..offsets.len()).find(|index| offsets[*index].is_some()).unwrap();
+        let start = offsets[run_start].unwrap();
+        let end = offsets[run_end].unwrap();
+
+        let step = (end - start) / (run_end - run_start) as CssDecimal;
+        for (position, offset) in offsets[index..run_end].iter_mut().enumerate() {
+            *offset = Some(start + step * (position + 1) as CssDecimal);
+        }
+        index = run_end;
+    }
+
+    let text_color = layout_box.actual_values().text_color;
+    color_stops.iter()
+        .zip(offsets)
+        .map(|(stop, offset)| GradientStop {
+            offset: offset.unwrap_or_default(),
+            color: resolve_color(stop.color, text_color),
+        })
+        .collect()
+}
+
+fn resolve_color(color: CssColor, text_color: Color) -> Color {
+    match color {
+        CssColor::Color(color) => color,
+        CssColor::CurrentColor => text_color,
+    }
+}
@@ -1,6 +1,7 @@
 // Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
 // All Rights Reserved.
 
+mod gradient;
 mod painting;
 mod tile;
 mod trace;
@@ -21,19 +22,27 @@ use std::{
 
 use retina_gfx::{
     Context,
-    euclid::Rect,
+    euclid::{
+        default::Vector2D,
+        Rect,
+        Size2D,
+        UnknownUnit,
+    },
     Painter,
 };
 use retina_layout::LayoutBox;
+use retina_style::CssDecimal;
 use tracing::instrument;
 
+use self::painting::PaintInvoker;
 use self::tile::{
     Tile,
     TileSpace,
     tile_rect_by_coordinate,
-    TILE_SIZE,
 };
 
+pub use self::tile::DEFAULT_TILE_SIZE;
+
 /// The compositor is responsible for painting the page and putting it all into
 /// one texture. The term 'compositing' comes from the idea that the page is
 /// split into square sections (_tiles_) that need to be put together to form
@@ -42,23 +51,37 @@ use self::tile::{
 pub struct Compositor {
     context: Context,
 
+    /// The size of every tile. Larger tiles reduce the number of submissions
+    /// to the GPU, whereas smaller tiles reduce the amount of memory wasted on
+    /// tiles that are only partially visible.
+    tile_size: Size2D<u32, TileSpace>,
+
     /// The tiles are stored in a vector of rows and then columns, meaning the
     /// indexing order is __y__ first, and the __x__. They are also wrapped in
     /// a [`Mutex`] to allow for parallelization.
-    tiles: Vec<Vec<Mutex<Tile>>>,
+    ///
+    /// Tiles are only created once some part of the layout tree extends into
+    /// them, so a tile is `None` when nothing but the canvas background would
+    /// be painted on it (e.g. below the content of short pages).
+    tiles: Vec<Vec<Option<Mutex<Tile>>>>,
 
     /// This field has the same layout as [`tiles`](Compositor::tiles), and
     /// contains the cached versions of each tile.
-    tile_textures: Vec<Vec<wgpu::TextureView>>,
+    tile_textures: Vec<Vec<Option<wgpu::TextureView>>>,
 }
 
 impl Compositor {
     /// Creates a new [`Compositor`]. There really should only be one compositor
     /// per page, since it's job is also to be efficient and aggressively cache
     /// things.
-    pub fn new(context: Context) -> Self {
+    ///
+    /// See [`DEFAULT_TILE_SIZE`] for a sensible `tile_size`.
+    pub fn new(context: Context, tile_size: Size2D<u32, u32>) -> Self {
+        assert!(!tile_size.is_empty(), "tile size must not be empty: {tile_size:?}");
+
         Self {
             context,
+            tile_size: tile_size.cast_unit(),
             tiles: Vec::new(),
             tile_textures: Vec::new(),
         }
@@ -76,9 +99,10 @@ impl Compositor {
         let _guard = trace::CompositorTracingGuard::new();
         let _span = tracing::trace_span!("Composition").entered();
 
+        let tile_size = self.tile_size;
         let viewport = painter.viewport_rect().cast();
-        let vertical_tiles = divide_and_round_up(viewport.max_y() as _, TILE_SIZE.height);
-        let horizontal_tiles = divide_and_round_up(viewport.max_x(), TILE_SIZE.width);
+        let vertical_tiles = divide_and_round_up(viewport.max_y() as _, tile_size.height);
+        let horizontal_tiles = divide_and_round_up(viewport.max_x(), tile_size.width);
 
         if self.tiles.len() < vertical_tiles as _ {
             self.tiles.resize_with(vertical_tiles as _, || Vec::with_capacity(horizontal_tiles as _));
@@ -89,22 +113,34 @@ impl Compositor {
         log::trace!("Preparing tiles: {vertical_tiles} x {horizontal_tiles}");
         for y in 0..vertical_tiles {
             let row = &mut self.tiles[y as usize];
+            let texture_row = &mut self.tile_textures[y as usize];
 
             for x in 0..horizontal_tiles {
                 if row.len() == x as usize {
-                    let rect = tile_rect_by_coordinate(x, y);
-                    let tile = Tile::new(self.context.clone(), rect);
-                    self.tile_textures[y as usize].push(tile.canvas.create_view());
-                    row.push(Mutex::new(tile));
+                    row.push(None);
+                    texture_row.push(None);
+                }
+
+                if row[x as usize].is_some() {
+                    continue;
                 }
+
+                let rect = tile_rect_by_coordinate(x, y, tile_size);
+                if !layout_box_extends_into(layout_box, &rect.cast().cast_unit(), Vector2D::zero()) {
+                    continue;
+                }
+
+                let tile = Tile::new(self.context.clone(), rect);
+                texture_row[x as usize] = Some(tile.canvas.create_view());
+                row[x as usize] = Some(Mutex::new(tile));
             }
         }
 
         log::trace!("Tiles prepared in {} ms", begin.elapsed().as_millis());
         log::trace!("Initiating paint...");
 
-        let viewport_tile_vertical_range = (viewport.min_y() / TILE_SIZE.height)..divide_and_round_up(viewport.max_y(), TILE_SIZE.height);
-        let viewport_tile_horizontal_range = (viewport.min_x() / TILE_SIZE.width)..divide_and_round_up(viewport.max_x(), TILE_SIZE.width);
+        let viewport_tile_vertical_range = (viewport.min_y() / tile_size.height)..divide_and_round_up(viewport.max_y(), tile_size.height);
+        let viewport_tile_horizontal_range = (viewport.min_x() / tile_size.width)..divide_and_round_up(viewport.max_x(), tile_size.width);
 
         let (sender, receiver) = channel();
 
@@ -122,8 +158,12 @@ impl Compositor {
                 let _trace_guard = tracing::dispatcher::set_default(&dispatcher2);
                 for y in viewport_tile_vertical_range2.clone() {
                     for x in viewport_tile_horizontal_range2.clone() {
-                        let rect = tile_rect_by_coordinate(x, y).cast();
-                        painter.paint_rect_textured(rect, &tile_textures_ref[y as usize][x as usize]);
+                        let Some(texture) = &tile_textures_ref[y as usize][x as usize] else {
+                            continue;
+                        };
+
+                        let rect = tile_rect_by_coordinate(x, y, tile_size).cast();
+                        painter.paint_rect_textured(rect, texture);
                     }
                 }
                 let mut submission = Some(painter.submit_async_concurrently());
@@ -136,7 +176,7 @@ impl Compositor {
                         Ok((view, rect, y, x)) => {
                             let rect: Rect<u32, TileSpace> = rect;
                             painter.paint_rect_textured(rect.cast(), &view);
-                            tile_textures_ref[y as usize][x as usize] = view;
+                            tile_textures_ref[y as usize][x as usize] = Some(view);
                             has_new_images = true;
                         }
 
@@ -158,6 +198,14 @@ impl Compositor {
                         }
 
                         Err(RecvTimeoutError::Disconnected) => {
+                            // The fixed boxes aren't part of the tiles, since
+                            // they don't move along with the page when it
+                            // is scrolled, and are painted over the tiles.
+                            if !layout_box.fixed_boxes().is_empty() {
+                                PaintInvoker::new().paint_fixed_boxes(layout_box, painter);
+                                has_new_images = true;
+                            }
+
                             if has_new_images {
                                 painter.submit_async_concurrently().wait();
                                 upload_image_callback(painter);
@@ -181,7 +229,10 @@ impl Compositor {
 
             for y in viewport_tile_vertical_range.clone() {
                 for x in viewport_tile_horizontal_range.clone() {
-                    let tile = &self.tiles[y as usize][x as usize];
+                    let Some(tile) = &self.tiles[y as usize][x as usize] else {
+                        continue;
+                    };
+
                     let sender = sender.clone();
                     let dispatcher = dispatcher.clone();
                     s.spawn(move || {
@@ -223,13 +274,55 @@ impl Compositor {
     #[instrument]
     pub fn mark_tile_cache_dirty(&mut self) {
         for row in &mut self.tiles {
-            for tile in row {
+            for tile in row.iter_mut().flatten() {
                 tile.get_mut().unwrap().dirty = true;
             }
         }
     }
 }
 
+/// Checks whether any of the descendants of the `layout_box` covers the given
+/// `rect`. The box itself isn't checked, since the root box only paints the
+/// canvas background, with which the page is already cleared.
+fn layout_box_extends_into(
+    layout_box: &LayoutBox,
+    rect: &Rect<CssDecimal, UnknownUnit>,
+    translation: Vector2D<CssDecimal>,
+) -> bool {
+    let child_translation = translation - layout_box.scroll_offset();
+    layout_box.children().iter().any(|child| {
+        // The descendants of a transformed box are transformed along with it,
+        // so the transformed area of the whole subtree is checked instead.
+        if child.transform_matrix().is_some() {
+            return match transformed_ink_overflow(child) {
+                Some(bounds) => bounds.intersects(&rect.translate(-child_translation)),
+                None => true,
+            };
+        }
+
+        child.covers_rect(&rect.translate(-child_translation))
+            || layout_box_extends_into(child, rect, child_translation)
+    })
+}
+
+/// Get the area the `layout_box` and its descendants paint onto, with the
+/// transforms of the box and of its descendants applied. Returns `None` when
+/// the area can't be projected onto the page, e.g. when it is rotated behind
+/// the viewer.
+fn transformed_ink_overflow(layout_box: &LayoutBox) -> Option<Rect<CssDecimal, UnknownUnit>> {
+    let mut bounds = layout_box.rect_ink_overflow();
+
+    for child in layout_box.children() {
+        let child_bounds = transformed_ink_overflow(child)?;
+        bounds = bounds.union(&child_bounds.translate(-layout_box.scroll_offset()));
+    }
+
+    match layout_box.transform_matrix() {
+        Some(transform) => transform.outer_transformed_rect(&bounds),
+        None => Some(bounds),
+    }
+}
+
 /// The default integer division will inherently round down, so this function
 /// can be used to round up, and has the advantage of avoiding floating point
 /// arithmetic, and converting to and from float/integers.
@@ -1,9 +1,16 @@
 // Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
 // All Rights Reserved.
 
+use std::sync::Arc;
+
 use retina_dom::{
+    CanvasGraphics,
+    HtmlCanvasElement,
     HtmlElementKind,
+    HtmlImgElement,
+    HtmlInputElement,
     ImageData,
+    InputType,
 };
 use retina_gfx::{
     Color,
@@ -11,21 +18,37 @@ use retina_gfx::{
         Point2D,
         Rect,
         Size2D,
+        Transform3D,
         UnknownUnit,
+        Vector2D,
+        Vector3D,
     },
+    ImageRendering,
     Painter,
+    ShadowParams,
     Texture,
 };
-use retina_layout::LayoutBox;
+use retina_layout::{
+    LayoutBox,
+    StackingContext,
+    StackingLayer,
+};
 use retina_style::{
+    CssBoxShadow,
     CssColor,
     CssDecimal,
+    CssGradient,
+    CssImage,
+    CssImageRendering,
     CssLineStyle,
+    CssOutlineStyle,
     CssTextDecorationLine,
 };
 use retina_style_computation::BorderProperties;
 use tracing::instrument;
 
+use crate::gradient::resolve_gradient;
+
 #[derive(Debug)]
 pub struct PaintInvoker {
 
@@ -115,27 +138,156 @@ impl PaintInvoker {
 
     #[instrument(skip_all)]
     pub fn paint(&self, layout_box: &LayoutBox, painter: &mut Painter) {
-        self.paint_box(layout_box, painter);
+        let stacking_context = StackingContext::build(layout_box, Vector2D::zero());
+        self.paint_stacking_context(&stacking_context, painter);
     }
 
-    #[instrument(skip(painter))]
-    fn paint_box(&self, layout_box: &LayoutBox, painter: &mut Painter) {
-        if painter.is_rect_inside_viewport(layout_box.dimensions().rect_border_box().cast()) {
-            self.paint_background(layout_box, painter);
-            self.paint_border(layout_box, painter);
-            self.paint_text(layout_box, painter);
+    /// Paint the `position: fixed` boxes of the root box. Their positions are
+    /// relative to the viewport, so they're translated by the scroll position
+    /// of the painter, which keeps them stationary when the page scrolls.
+    #[instrument(skip_all)]
+    pub fn paint_fixed_boxes(&self, root: &LayoutBox, painter: &mut Painter) {
+        let translation = painter.viewport_rect().origin.to_vector();
+
+        // Fixed boxes always establish a stacking context, so they're painted
+        // in the order of their stack levels.
+        let mut stacking_contexts: Vec<_> = root.fixed_boxes()
+            .iter()
+            .map(|fixed_box| StackingContext::build(fixed_box, translation))
+            .collect();
+        stacking_contexts.sort_by_key(|context| context.z_index());
+
+        for stacking_context in &stacking_contexts {
+            self.paint_stacking_context(stacking_context, painter);
+        }
+    }
+
+    /// Paint the stacking context and the stacking contexts nested in it, in
+    /// the painting order of [CSS 2.2 Appendix E][spec].
+    ///
+    /// A stacking context with an `opacity` less than `1` is painted into an
+    /// intermediate texture first, such that the whole stacking context is
+    /// flattened before the opacity is applied, instead of the descendants
+    /// becoming translucent individually.
+    ///
+    /// [spec]: https://www.w3.org/TR/CSS22/zindex.html#painting-order
+    #[instrument(skip_all)]
+    fn paint_stacking_context(&self, stacking_context: &StackingContext, painter: &mut Painter) {
+        let opacity = stacking_context.layout_box().computed_style().opacity().value() as f32;
+
+        let Some(transform) = stacking_context.layout_box().transform_matrix() else {
+            painter.paint_with_opacity(stacking_context.bounds(), opacity, |painter| {
+                self.paint_stacking_context_layers(stacking_context, stacking_context.clip(), painter);
+            });
+            return;
+        };
+
+        // The matrix is in the coordinates of the layout, which are
+        // translated by the scroll offsets when painting.
+        let translation = stacking_context.translation();
+        let transform = Transform3D::translation(-translation.x, -translation.y, 0.0)
+            .then(&transform)
+            .then_translate(Vector3D::new(translation.x, translation.y, 0.0))
+            .cast();
+
+        // The clip of the ancestors applies to the transformed result, not to
+        // the contents of the stacking context.
+        self.paint_clipped(stacking_context.clip(), painter, |painter| {
+            painter.paint_transformed(stacking_context.bounds(), &transform, |painter| {
+                painter.paint_with_opacity(stacking_context.bounds(), opacity, |painter| {
+                    self.paint_stacking_context_layers(stacking_context, None, painter);
+                });
+            });
+        });
+    }
+
+    /// Paint the layers of the stacking context, where the box establishing
+    /// it is clipped to the `root_clip`.
+    fn paint_stacking_context_layers(
+        &self,
+        stacking_context: &StackingContext,
+        root_clip: Option<Rect<CssDecimal, UnknownUnit>>,
+        painter: &mut Painter,
+    ) {
+        let root = stacking_context.layout_box();
+        self.paint_clipped(root_clip, painter, |painter| {
+            self.paint_background_and_border(root, painter, stacking_context.translation());
+        });
+
+        for child in stacking_context.negative_children() {
+            self.paint_stacking_context(child, painter);
         }
 
-        self.paint_replaced_content(layout_box, painter);
+        for layer in [StackingLayer::Block, StackingLayer::Float, StackingLayer::Inline] {
+            for entry in stacking_context.descendants_in_layer(layer) {
+                self.paint_clipped(entry.clip, painter, |painter| {
+                    self.paint_background_and_border(entry.layout_box, painter, entry.translation);
+                });
+            }
+        }
+
+        self.paint_clipped(root_clip, painter, |painter| {
+            self.paint_content(root, painter, stacking_context.translation());
+        });
+        for entry in stacking_context.descendants() {
+            self.paint_clipped(entry.clip, painter, |painter| {
+                self.paint_content(entry.layout_box, painter, entry.translation);
+            });
+        }
 
-        for child in layout_box.children() {
-            self.paint_box(child, painter);
+        for child in stacking_context.non_negative_children() {
+            self.paint_stacking_context(child, painter);
         }
     }
 
+    /// Paint using the `paint` function, clipped to the `clip` rect if there
+    /// is one. The clip rects come from the ancestors with `overflow` other
+    /// than `visible`.
+    fn paint_clipped<F>(&self, clip: Option<Rect<CssDecimal, UnknownUnit>>, painter: &mut Painter, paint: F)
+            where F: FnOnce(&mut Painter) {
+        let Some(clip) = clip else {
+            paint(painter);
+            return;
+        };
+
+        painter.push_clip_rect(clip);
+        paint(painter);
+        painter.pop_clip_rect();
+    }
+
+    /// Paint the background and the borders of the box. The `translation` is
+    /// the sum of the negated scroll offsets of the ancestors that are scroll
+    /// containers.
+    fn paint_background_and_border(&self, layout_box: &LayoutBox, painter: &mut Painter, translation: Vector2D<CssDecimal, UnknownUnit>) {
+        let shadows = layout_box.computed_style().box_shadow();
+
+        // Outer shadows can be painted far outside of the border box, so
+        // those are checked separately by the painter.
+        self.paint_box_shadows(layout_box, painter, translation, shadows, false);
+
+        if painter.is_rect_inside_viewport(layout_box.dimensions().rect_border_box().translate(translation).cast()) {
+            self.paint_background(layout_box, painter, translation);
+            self.paint_box_shadows(layout_box, painter, translation, shadows, true);
+            self.paint_border(layout_box, painter, translation);
+        }
+
+        // The outline is drawn outside of the border box, so it is checked
+        // separately as well.
+        self.paint_outline(layout_box, painter, translation);
+    }
+
+    /// Paint the text and the replaced content (e.g. images) of the box.
+    fn paint_content(&self, layout_box: &LayoutBox, painter: &mut Painter, translation: Vector2D<CssDecimal, UnknownUnit>) {
+        if painter.is_rect_inside_viewport(layout_box.dimensions().rect_border_box().translate(translation).cast()) {
+            self.paint_text(layout_box, painter, translation);
+        }
+
+        self.paint_replaced_content(layout_box, painter, translation);
+    }
+
     #[instrument(skip_all)]
-    fn paint_background(&self, layout_box: &LayoutBox, painter: &mut Painter) {
-        let position = layout_box.dimensions().position_padding_box();
+    fn paint_background(&self, layout_box: &LayoutBox, painter: &mut Painter, translation: Vector2D<CssDecimal, UnknownUnit>) {
+        let position = layout_box.dimensions().position_padding_box() + translation;
 
         let size = layout_box.dimensions().size_padding_box();
 
@@ -148,6 +300,8 @@ impl PaintInvoker {
 
         if let Some(background_image) = layout_box.background_image() {
             self.paint_background_image(layout_box, painter, background_image, rect);
+        } else if let Some(CssImage::Gradient(gradient)) = &layout_box.computed_style().background_image {
+            self.paint_background_gradient(layout_box, painter, gradient, rect);
         }
     }
 
@@ -186,14 +340,75 @@ impl PaintInvoker {
         };
 
         // TODO background-repeat, background-size, etc.
-        _ = layout_box;
+        let rendering = image_rendering(layout_box.computed_style().image_rendering());
+        painter.paint_image(rect, texture.view(), rendering);
+    }
 
-        painter.paint_rect_textured(rect, texture.view());
+    /// Paint a `background-image` that is a gradient, which isn't loaded as
+    /// an image, but painted directly.
+    ///
+    /// TODO: `background-repeat`, `background-size`, etc.
+    #[instrument(skip_all)]
+    fn paint_background_gradient(
+        &self,
+        layout_box: &LayoutBox,
+        painter: &mut Painter,
+        gradient: &CssGradient,
+        rect: Rect<CssDecimal, UnknownUnit>
+    ) {
+        let gradient = resolve_gradient(gradient, layout_box, rect.size);
+        painter.paint_rect_gradient(rect, &gradient);
     }
 
+    /// Paint either the outer or the `inset` shadows of the `box-shadow`
+    /// property. Outer shadows are painted below the background, and inset
+    /// shadows above the background but below the border.
+    ///
+    /// # References
+    /// * [CSS Backgrounds and Borders Module Level 3 § 7.1. Drop Shadows: the `box-shadow` property](https://drafts.csswg.org/css-backgrounds/#box-shadow)
     #[instrument(skip_all)]
-    fn paint_border(&self, layout_box: &LayoutBox, painter: &mut Painter) {
-        let position = layout_box.dimensions().position_border_box();
+    fn paint_box_shadows(
+        &self,
+        layout_box: &LayoutBox,
+        painter: &mut Painter,
+        translation: Vector2D<CssDecimal, UnknownUnit>,
+        shadows: &[CssBoxShadow],
+        inset: bool,
+    ) {
+        let rect = if inset {
+            layout_box.dimensions().rect_padding_box()
+        } else {
+            layout_box.dimensions().rect_border_box()
+        }.translate(translation);
+
+        if rect.is_empty() {
+            return;
+        }
+
+        let text_color = layout_box.actual_values().text_color;
+        let resolve = |length| layout_box.resolve_length(length, None).unwrap_or_default();
+
+        // > The shadow effects are applied front-to-back: the first shadow is
+        // > on top and the others are layered behind.
+        for shadow in shadows.iter().rev().filter(|shadow| shadow.inset == inset) {
+            let params = ShadowParams {
+                color: match shadow.color {
+                    CssColor::Color(color) => color,
+                    CssColor::CurrentColor => text_color,
+                },
+                offset: Vector2D::new(resolve(shadow.offset_x), resolve(shadow.offset_y)),
+                blur_radius: resolve(shadow.blur_radius),
+                spread_radius: resolve(shadow.spread_radius),
+                inset,
+            };
+
+            painter.paint_shadow(rect, &params);
+        }
+    }
+
+    #[instrument(skip_all)]
+    fn paint_border(&self, layout_box: &LayoutBox, painter: &mut Painter, translation: Vector2D<CssDecimal, UnknownUnit>) {
+        let position = layout_box.dimensions().position_border_box() + translation;
         let text_color = layout_box.actual_values().text_color;
 
         self.paint_border_part(
@@ -243,16 +458,129 @@ impl PaintInvoker {
         }
     }
 
+    /// Paint the outline, which is drawn outside of the border box, expanded
+    /// by the `outline-offset`. Unlike borders, outlines don't take up space
+    /// in the layout.
+    ///
+    /// # References
+    /// * [CSS Basic User Interface Module Level 4 § 5. Outline Properties](https://drafts.csswg.org/css-ui/#outline-props)
+    #[instrument(skip_all)]
+    fn paint_outline(&self, layout_box: &LayoutBox, painter: &mut Painter, translation: Vector2D<CssDecimal, UnknownUnit>) {
+        let outline = layout_box.computed_style().outline;
+
+        // TODO: support the other line styles, like for borders.
+        match outline.style {
+            CssOutlineStyle::Auto | CssOutlineStyle::LineStyle(CssLineStyle::Solid) => (),
+            _ => return,
+        }
+
+        let resolve = |length| layout_box.resolve_length(length, None).unwrap_or_default();
+        let width = resolve(outline.width);
+        if width <= 0.0 {
+            return;
+        }
+
+        let offset = resolve(outline.offset);
+        let inner = layout_box.dimensions().rect_border_box()
+            .translate(translation)
+            .inflate(offset, offset);
+        if inner.size.width < 0.0 || inner.size.height < 0.0 {
+            return;
+        }
+
+        let outer = inner.inflate(width, width);
+        if !painter.is_rect_inside_viewport(outer.cast()) {
+            return;
+        }
+
+        let color = match outline.color {
+            CssColor::Color(color) => color,
+            CssColor::CurrentColor => layout_box.actual_values().text_color,
+        };
+
+        let horizontal = Size2D::new(outer.width(), width);
+        let vertical: Size2D<_, UnknownUnit> = Size2D::new(width, inner.height());
+
+        painter.paint_rect_colored(Rect::new(outer.origin, horizontal), color);
+        painter.paint_rect_colored(Rect::new(Point2D::new(outer.min_x(), inner.max_y()), horizontal), color);
+        painter.paint_rect_colored(Rect::new(Point2D::new(outer.min_x(), inner.min_y()), vertical), color);
+        painter.paint_rect_colored(Rect::new(Point2D::new(inner.max_x(), inner.min_y()), vertical), color);
+    }
+
     #[instrument(skip_all)]
     fn paint_replaced_content(
         &self,
         layout_box: &LayoutBox,
         painter: &mut Painter,
+        translation: Vector2D<CssDecimal, UnknownUnit>,
+    ) {
+        match layout_box.node.as_html_element_kind() {
+            Some(HtmlElementKind::Canvas(canvas)) => self.paint_canvas(layout_box, canvas, painter, translation),
+            Some(HtmlElementKind::Img(img)) => self.paint_image(layout_box, img, painter, translation),
+            Some(HtmlElementKind::Input(input)) => self.paint_input_control(layout_box, input, painter, translation),
+            _ => (),
+        }
+    }
+
+    /// Paint the bitmap of the canvas, which is uploaded to a texture again
+    /// when it was drawn onto since the last upload.
+    fn paint_canvas(
+        &self,
+        layout_box: &LayoutBox,
+        canvas: &HtmlCanvasElement,
+        painter: &mut Painter,
+        translation: Vector2D<CssDecimal, UnknownUnit>,
     ) {
-        let Some(HtmlElementKind::Img(img)) = layout_box.node.as_html_element_kind() else {
+        let rect = Rect::new(
+            layout_box.dimensions().position_content_box() + translation,
+            layout_box.dimensions().size_content_box(),
+        );
+
+        if rect.is_empty() || !painter.is_rect_inside_viewport(rect.cast()) {
+            return;
+        }
+
+        let bitmap = canvas.bitmap();
+        if bitmap.width() == 0 || bitmap.height() == 0 {
+            return;
+        }
+
+        let Ok(mut graphics) = bitmap.graphics().write() else {
+            log::warn!("No graphics was available for canvas");
             return;
         };
 
+        let generation = bitmap.generation();
+        if graphics.generation != generation || !graphics.graphics.is::<Texture>() {
+            let texture = Texture::create_from_image_bytes(
+                &painter.artwork().context,
+                bitmap.width(),
+                bitmap.height(),
+                wgpu::TextureFormat::Rgba8UnormSrgb,
+                &bitmap.to_rgba8(),
+            );
+
+            *graphics = CanvasGraphics {
+                generation,
+                graphics: Arc::new(texture),
+            };
+        }
+
+        let Some(texture) = graphics.graphics.downcast_ref::<Texture>() else {
+            return;
+        };
+
+        let rendering = image_rendering(layout_box.computed_style().image_rendering());
+        painter.paint_image(rect, texture.view(), rendering);
+    }
+
+    fn paint_image(
+        &self,
+        layout_box: &LayoutBox,
+        img: &HtmlImgElement,
+        painter: &mut Painter,
+        translation: Vector2D<CssDecimal, UnknownUnit>,
+    ) {
         let Ok(image) = img.data().read() else {
             return;
         };
@@ -270,9 +598,50 @@ impl PaintInvoker {
         };
 
         let size = Size2D::new(texture.width() as _, texture.height() as _);
-        let rect = Rect::new(layout_box.dimensions().position_content_box(), size);
+        let rect = Rect::new(layout_box.dimensions().position_content_box() + translation, size);
 
-        painter.paint_rect_textured(rect, texture.view());
+        let rendering = image_rendering(layout_box.computed_style().image_rendering());
+        painter.paint_image(rect, texture.view(), rendering);
+    }
+
+    /// Paint the mark of a checked checkbox or radio button. The box itself
+    /// is drawn using the border and background from the UA stylesheet, and
+    /// the text of text fields and buttons is laid out as an anonymous child.
+    fn paint_input_control(
+        &self,
+        layout_box: &LayoutBox,
+        input: &HtmlInputElement,
+        painter: &mut Painter,
+        translation: Vector2D<CssDecimal, UnknownUnit>,
+    ) {
+        if !input.is_checked() {
+            return;
+        }
+
+        let content_box = Rect::new(
+            layout_box.dimensions().position_content_box() + translation,
+            layout_box.dimensions().size_content_box(),
+        );
+
+        if content_box.is_empty() || !painter.is_rect_inside_viewport(content_box.cast()) {
+            return;
+        }
+
+        // A radio button has a smaller mark, to distinguish it from a
+        // checkbox, since we can't paint circles yet.
+        let inset_factor = match input.input_type() {
+            InputType::Radio => 0.3,
+            _ => 0.15,
+        };
+        let inset = content_box.width().min(content_box.height()) * inset_factor;
+        let mark = content_box.inflate(-inset, -inset);
+
+        let mut color = layout_box.actual_values().text_color;
+        if input.is_disabled() {
+            color = color.with_alpha(color.alpha() * 0.5);
+        }
+
+        painter.paint_rect_colored(mark, color);
     }
 
     #[instrument(skip_all)]
@@ -280,6 +649,7 @@ impl PaintInvoker {
         &self,
         layout_box: &LayoutBox,
         painter: &mut Painter,
+        translation: Vector2D<CssDecimal, UnknownUnit>,
     ) {
         let size = layout_box.font_size().as_abs().value() as f32;
         if size <= 0.0 {
@@ -309,20 +679,59 @@ impl PaintInvoker {
         let text_decoration_thickness = layout_box.font().underline_thickness(size) as CssDecimal;
 
         for line_box_fragment in layout_box.line_box_fragments() {
-            let position = line_box_fragment.position();
+            let position = line_box_fragment.position() + translation;
 
-            if !painter.viewport_rect().cast_unit().intersects(&Rect::new(line_box_fragment.position(), line_box_fragment.size())) {
+            if !painter.viewport_rect().cast_unit().intersects(&Rect::new(position, line_box_fragment.size())) {
                 continue;
             }
 
-            line_box_fragment.font().paint(
-                line_box_fragment.text(),
-                color,
-                position.cast(),
-                size,
-                layout_box.actual_values().text_hinting_options,
-                painter,
-            );
+            let actual_values = layout_box.actual_values();
+
+            // TODO: the text decorations of vertical text.
+            if layout_box.computed_style().writing_mode().is_vertical() {
+                line_box_fragment.font().paint_vertical(
+                    line_box_fragment.text(),
+                    color,
+                    position.cast(),
+                    size,
+                    actual_values.text_hinting_options,
+                    painter,
+                );
+                continue;
+            }
+
+            if actual_values.has_normal_spacing() {
+                line_box_fragment.font().paint(
+                    line_box_fragment.text(),
+                    color,
+                    position.cast(),
+                    size,
+                    actual_values.text_hinting_options,
+                    painter,
+                );
+            } else {
+                // The `letter-spacing` and `word-spacing` are added after
+                // each character, so they have to be painted one by one.
+                let text = line_box_fragment.text();
+                let mut character_position = position;
+                for (index, character) in text.char_indices() {
+                    let character_text = &text[index..index + character.len_utf8()];
+
+                    line_box_fragment.font().paint(
+                        character_text,
+                        color,
+                        character_position.cast(),
+                        size,
+                        actual_values.text_hinting_options,
+                        painter,
+                    );
+
+                    let advance = line_box_fragment.font()
+                        .calculate_size(size, character_text, actual_values.text_hinting_options)
+                        .width as CssDecimal;
+                    character_position.x += advance + actual_values.spacing_after(character);
+                }
+            }
 
             match line {
                 CssTextDecorationLine::None => (),
@@ -343,3 +752,13 @@ impl PaintInvoker {
         }
     }
 }
+
+/// Convert the computed value of the `image-rendering` property to the hint
+/// for the painter.
+fn image_rendering(value: CssImageRendering) -> ImageRendering {
+    match value {
+        CssImageRendering::Auto | CssImageRendering::Smooth | CssImageRendering::HighQuality => ImageRendering::Auto,
+        CssImageRendering::Pixelated => ImageRendering::Pixelated,
+        CssImageRendering::CrispEdges => ImageRendering::CrispEdges,
+    }
+}
@@ -13,15 +13,16 @@ use crate::painting::PaintInvoker;
 
 pub struct TileSpace;
 
-pub const TILE_SIZE: Size2D<u32, TileSpace> = Size2D::new(256, 256);
+/// The tile size that is used when the embedder doesn't have a preference.
+pub const DEFAULT_TILE_SIZE: Size2D<u32, u32> = Size2D::new(256, 256);
 
 #[inline]
-pub fn tile_rect_by_coordinate<T>(x: T, y: T) -> Rect<u32, TileSpace>
+pub fn tile_rect_by_coordinate<T>(x: T, y: T, tile_size: Size2D<u32, TileSpace>) -> Rect<u32, TileSpace>
         where T: Into<u32> {
     let x = x.into();
     let y = y.into();
-    let point = Point2D::new(x * TILE_SIZE.width, y * TILE_SIZE.height);
-    Rect::new(point, TILE_SIZE)
+    let point = Point2D::new(x * tile_size.width, y * tile_size.height);
+    Rect::new(point, tile_size)
 }
 
 #[derive(Debug)]
@@ -45,8 +46,8 @@ impl Tile {
     pub fn new(context: Context, rect: Rect<u32, TileSpace>) -> Self {
         let name = format!(
             "Compositor Tile (x={x}, y={y})",
-            x = rect.origin.x / TILE_SIZE.width,
-            y = rect.origin.y / TILE_SIZE.height,
+            x = rect.origin.x / rect.size.width,
+            y = rect.origin.y / rect.size.height,
         );
 
         Self {
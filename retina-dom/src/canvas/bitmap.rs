@@ -0,0 +1,189 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use std::{
+    any::Any,
+    fmt::Debug,
+    sync::{
+        Arc,
+        RwLock,
+        RwLockReadGuard,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+/// The [output bitmap][spec] of a `<canvas>` element, which is shared between
+/// the rendering contexts that draw onto it and the compositor that uploads
+/// it to the GPU.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/canvas.html#output-bitmap
+#[derive(Clone, Debug)]
+pub struct CanvasBitmap {
+    /// The pixels, or [`None`] when the canvas has no area.
+    pixels: Arc<RwLock<Option<CanvasPixels>>>,
+
+    /// Incremented each time the pixels are modified, such that the
+    /// compositor knows when the pixels must be uploaded again.
+    generation: Arc<AtomicU64>,
+
+    graphics: Arc<RwLock<CanvasGraphics>>,
+}
+
+impl PartialEq for CanvasBitmap {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.pixels, &other.pixels)
+    }
+}
+
+impl CanvasBitmap {
+    /// Create a bitmap of the given size, of which all the pixels are
+    /// transparent black.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            pixels: Arc::new(RwLock::new(CanvasPixels::new(width, height))),
+            generation: Arc::new(AtomicU64::new(0)),
+            graphics: Arc::new(RwLock::new(CanvasGraphics {
+                generation: u64::MAX,
+                graphics: Arc::new(()),
+            })),
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.pixels().as_ref().map_or(0, CanvasPixels::width)
+    }
+
+    pub fn height(&self) -> u32 {
+        self.pixels().as_ref().map_or(0, CanvasPixels::height)
+    }
+
+    /// The number of times the pixels were modified.
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    /// The graphics object (e.g. a texture) of the compositor, which isn't
+    /// known to the DOM.
+    pub fn graphics(&self) -> &Arc<RwLock<CanvasGraphics>> {
+        &self.graphics
+    }
+
+    pub fn pixels(&self) -> RwLockReadGuard<'_, Option<CanvasPixels>> {
+        self.pixels.read().unwrap()
+    }
+
+    /// Copy the pixels into non-premultiplied RGBA bytes, which is the
+    /// format the images are uploaded in.
+    pub fn to_rgba8(&self) -> Vec<u8> {
+        let pixels = self.pixels();
+        let Some(pixels) = pixels.as_ref() else {
+            return Vec::new();
+        };
+
+        pixels.data
+            .chunks_exact(4)
+            .flat_map(demultiply)
+            .collect()
+    }
+
+    /// Modify the pixels, if the bitmap has any. This is used by the
+    /// rendering contexts, which are implemented by the graphics library.
+    pub fn draw<F>(&self, f: F)
+            where F: FnOnce(&mut CanvasPixels) {
+        if let Some(pixels) = self.pixels.write().unwrap().as_mut() {
+            f(pixels);
+            self.generation.fetch_add(1, Ordering::AcqRel);
+        }
+    }
+}
+
+/// The graphics object of a [`CanvasBitmap`], tagged with the
+/// [generation][CanvasBitmap::generation] of the pixels it was created from.
+#[derive(Debug)]
+pub struct CanvasGraphics {
+    pub generation: u64,
+    pub graphics: Arc<dyn Any + Send + Sync>,
+}
+
+/// A rectangle of pixels in premultiplied RGBA, with 8 bits per channel.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CanvasPixels {
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
+impl CanvasPixels {
+    /// Create pixels that are transparent black, or [`None`] if the width or
+    /// the height is zero.
+    pub fn new(width: u32, height: u32) -> Option<Self> {
+        if width == 0 || height == 0 {
+            return None;
+        }
+
+        let length = (width as usize).checked_mul(height as usize)?.checked_mul(4)?;
+        Some(Self {
+            width,
+            height,
+            data: vec![0; length],
+        })
+    }
+
+    /// Create the pixels from non-premultiplied RGBA bytes, e.g. of a decoded
+    /// image.
+    pub fn from_rgba8(width: u32, height: u32, bytes: &[u8]) -> Option<Self> {
+        let mut pixels = Self::new(width, height)?;
+        for (pixel, bytes) in pixels.data.chunks_exact_mut(4).zip(bytes.chunks_exact(4)) {
+            let alpha = bytes[3];
+            pixel[0] = premultiply(bytes[0], alpha);
+            pixel[1] = premultiply(bytes[1], alpha);
+            pixel[2] = premultiply(bytes[2], alpha);
+            pixel[3] = alpha;
+        }
+        Some(pixels)
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The premultiplied RGBA bytes, row by row.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        &mut self.data
+    }
+
+    /// Get the non-premultiplied RGBA color of the pixel at the given
+    /// coordinates.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        let index = (y as usize * self.width as usize + x as usize) * 4;
+        Some(demultiply(&self.data[index..index + 4]))
+    }
+}
+
+fn premultiply(component: u8, alpha: u8) -> u8 {
+    let product = component as u32 * alpha as u32 + 128;
+    ((product + (product >> 8)) >> 8) as u8
+}
+
+fn demultiply(pixel: &[u8]) -> [u8; 4] {
+    let alpha = pixel[3] as u32;
+    let component = |value: u8| match alpha {
+        0 => 0,
+        255 => value,
+        _ => ((value as u32 * 255 + alpha / 2) / alpha).min(255) as u8,
+    };
+
+    [component(pixel[0]), component(pixel[1]), component(pixel[2]), pixel[3]]
+}
@@ -0,0 +1,77 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! The model of the `<canvas>` element: the [`CanvasBitmap`] the rendering
+//! contexts draw onto. The rendering contexts themselves are implemented by
+//! the graphics library, which rasterizes in software.
+//!
+//! # References
+//! * [HTML Living Standard § 4.12.5 The canvas element](https://html.spec.whatwg.org/multipage/canvas.html)
+
+pub mod bitmap;
+
+pub use self::bitmap::{CanvasBitmap, CanvasGraphics, CanvasPixels};
+
+use log::warn;
+
+use crate::{HtmlElementKind, ImageDataKind, Node};
+
+/// The pixels of an element that can be drawn with `drawImage()`, i.e. a
+/// [`CanvasImageSource`][spec].
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/canvas.html#canvasimagesource
+#[derive(Clone, Debug)]
+pub struct CanvasImageSource {
+    pixels: CanvasPixels,
+}
+
+impl CanvasImageSource {
+    /// Copy the pixels of the `<img>` or `<canvas>` element. This returns
+    /// [`None`] for other nodes, and for images of which the pixels aren't
+    /// available (anymore), e.g. because they are still loading.
+    pub fn from_node(node: &Node) -> Option<Self> {
+        let pixels = match node.as_html_element_kind()? {
+            HtmlElementKind::Canvas(canvas) => canvas.bitmap().pixels().clone()?,
+
+            HtmlElementKind::Img(img) => {
+                let data = img.data().read().unwrap();
+                let image = data.image().read().unwrap();
+                match &*image {
+                    ImageDataKind::Bitmap(image) => {
+                        let image = image.to_rgba8();
+                        CanvasPixels::from_rgba8(image.width(), image.height(), image.as_raw())?
+                    }
+
+                    // The first frame is used for animated images.
+                    ImageDataKind::Animated(image) => {
+                        let frame = image.frames().first()?.buffer();
+                        CanvasPixels::from_rgba8(frame.width(), frame.height(), frame.as_raw())?
+                    }
+
+                    ImageDataKind::Uploaded { .. } => {
+                        warn!("[canvas] The pixels of image \"{}\" were already uploaded to the GPU", img.src());
+                        return None;
+                    }
+
+                    ImageDataKind::None => return None,
+                }
+            }
+
+            _ => return None,
+        };
+
+        Some(Self { pixels })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.pixels.width()
+    }
+
+    pub fn height(&self) -> u32 {
+        self.pixels.height()
+    }
+
+    pub fn pixels(&self) -> &CanvasPixels {
+        &self.pixels
+    }
+}
@@ -0,0 +1,125 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! The [`cloneNode()`][spec] method of the `Node` interface.
+//!
+//! [spec]: https://dom.spec.whatwg.org/#dom-node-clonenode
+
+use crate::{
+    parse::create_element_for_qualified_name,
+    Comment,
+    Document,
+    Node,
+    NodeKind,
+    ProcessingInstruction,
+    Text,
+};
+
+impl Node {
+    /// [Clone][spec] this node, which creates a new node that doesn't share
+    /// its identity with this node. The descendants are cloned as well if
+    /// `deep` is set.
+    ///
+    /// The clone doesn't have a parent, nor does it have the registered
+    /// observers of this node.
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#concept-node-clone
+    pub fn clone_node(&self, deep: bool) -> Node {
+        // 2. If node is an element, then:
+        // 3. Otherwise, let copy be a node that implements the same
+        //    interfaces as node, and fulfills these additional requirements,
+        //    switching on the interface node implements:
+        let copy = match self.as_ref() {
+            NodeKind::Comment(comment) => Comment::new_handle(comment.data().clone()),
+
+            NodeKind::Document(document) => {
+                let copy = Document::new_handle();
+                let base_url = document.data().base_url().cloned();
+                copy.as_document().unwrap().data_mut().set_base_url(base_url);
+                copy
+            }
+
+            NodeKind::HtmlElement(element) => {
+                let element = element.as_dom_element();
+
+                // 2.2. Let copy be the result of creating an element, given
+                //      document, node’s local name, node’s namespace, node’s
+                //      namespace prefix, and node’s is value, with the
+                //      synchronous custom elements flag unset.
+                let mut copy = create_element_for_qualified_name(element.qualified_name().clone());
+
+                // 2.3. For each attribute in node’s attribute list:
+                let attributes = copy.as_dom_element_mut().unwrap().attributes_mut();
+                for (name, value) in element.attributes() {
+                    // 2.3.1. Let copyAttribute be a clone of attribute.
+                    // 2.3.2. Append copyAttribute to copy.
+                    attributes.append(name.clone(), value.clone());
+                }
+
+                Node::new(copy)
+            }
+
+            NodeKind::ProcessingInstruction(instruction) => {
+                ProcessingInstruction::new_handle(instruction.target().clone(), instruction.data().clone())
+            }
+
+            NodeKind::Text(text) => Text::new_handle(text.data().clone()),
+        };
+
+        // 6. If the clone children flag is set, then for each child child of
+        //    node, in tree order: append the result of cloning child with
+        //    document and the clone children flag set, to copy.
+        if deep {
+            if let Some(parent_node) = self.as_parent_node() {
+                let children = parent_node.children()
+                    .iter()
+                    .map(|child| child.clone_node(true))
+                    .collect();
+                copy.replace_all(children);
+            }
+        }
+
+        // 7. Return copy.
+        copy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Node, Parser};
+
+    #[test]
+    fn clone_node() {
+        let document = Parser::parse(concat!(
+            r#"<div id="target" class="a b">"#,
+            r#"<p title="x">text<!-- comment --></p><br>"#,
+            r#"</div>"#,
+        ));
+        let target = document.as_document().unwrap().get_element_by_id("target").unwrap();
+
+        let shallow = target.clone_node(false);
+        assert!(!Node::ptr_eq(&shallow, &target));
+        assert!(shallow.parent_node().is_none());
+        assert_eq!(shallow.tag_name(), Some("div"));
+        assert_eq!(shallow.as_dom_element().unwrap().attributes(), target.as_dom_element().unwrap().attributes());
+        assert_eq!(shallow.children_count(), 0);
+
+        let deep = target.clone_node(true);
+        assert!(!Node::ptr_eq(&deep, &target));
+        assert!(deep.parent_node().is_none());
+        assert_eq!(deep.inner_html(), target.inner_html());
+
+        let original_children = target.as_parent_node().unwrap().children().clone();
+        let cloned_children = deep.as_parent_node().unwrap().children().clone();
+        assert_eq!(cloned_children.len(), original_children.len());
+        for (original, clone) in original_children.iter().zip(&cloned_children) {
+            assert!(!Node::ptr_eq(original, clone));
+            assert!(Node::ptr_eq(&clone.parent_node().unwrap(), &deep));
+        }
+
+        // Mutating the clone doesn't affect the original.
+        cloned_children[0].remove_from_parent();
+        assert_eq!(target.children_count(), 2);
+        assert_eq!(deep.children_count(), 1);
+    }
+}
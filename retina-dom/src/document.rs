@@ -7,6 +7,7 @@
 use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 use retina_common::{StrTendril, DynamicSizeOf};
+use retina_fetch::Url;
 
 use crate::{
     Node,
@@ -63,6 +64,11 @@ impl Document {
         &mut self.mixin_parent_node
     }
 
+    /// [DOM Living Standard § 4.2.4 `getElementById()`](https://dom.spec.whatwg.org/#dom-nonelementparentnode-getelementbyid)
+    pub fn get_element_by_id(&self, id: &str) -> Option<Node> {
+        self.mixin_parent_node.get_element_by_id(id)
+    }
+
     pub fn data(&self) -> RwLockReadGuard<'_, DocumentData> {
         self.data.read().unwrap()
     }
@@ -88,6 +94,12 @@ impl DynamicSizeOf for Document {
 pub struct DocumentData {
     title: StrTendril,
     platform_messenger: Option<PlatformMessenger>,
+
+    /// The [frozen base URL][spec] of the first `<base>` element with an
+    /// `href` attribute, if there is one.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/semantics.html#frozen-base-url
+    base_url: Option<Url>,
 }
 
 impl DocumentData {
@@ -95,9 +107,18 @@ impl DocumentData {
         Self {
             title: StrTendril::new(),
             platform_messenger: None,
+            base_url: None,
         }
     }
 
+    pub fn base_url(&self) -> Option<&Url> {
+        self.base_url.as_ref()
+    }
+
+    pub fn set_base_url(&mut self, base_url: Option<Url>) {
+        self.base_url = base_url;
+    }
+
     pub fn platform_messenger(&self) -> &Option<PlatformMessenger> {
         &self.platform_messenger
     }
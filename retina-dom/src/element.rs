@@ -5,11 +5,12 @@
 //! implementation.
 
 use std::str::SplitAsciiWhitespace;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use html5ever::{QualName, local_name};
 use retina_common::DynamicSizeOf;
 
-use crate::{NodeInterface, ParentNode, AttributeList};
+use crate::{Node, NodeInterface, ParentNode, AttributeList};
 
 /// The [Interface `Element`](https://dom.spec.whatwg.org/#interface-element)
 /// implementation.
@@ -19,6 +20,14 @@ pub struct Element {
     mixin_parent_node: ParentNode,
     qualified_name: QualName,
     attribute_list: AttributeList,
+
+    /// Whether or not the pointing device hovers over this element, which is
+    /// matched by [`:hover`](https://drafts.csswg.org/selectors/#the-hover-pseudo).
+    hovered: AtomicBool,
+
+    /// Whether or not this element has the focus, which is matched by
+    /// [`:focus`](https://drafts.csswg.org/selectors/#the-focus-pseudo).
+    focused: AtomicBool,
 }
 
 impl Element {
@@ -28,6 +37,8 @@ impl Element {
             mixin_parent_node: ParentNode::new(),
             qualified_name,
             attribute_list: AttributeList::new(),
+            hovered: AtomicBool::new(false),
+            focused: AtomicBool::new(false),
         }
     }
 
@@ -63,6 +74,13 @@ impl Element {
         attribute_value.split_ascii_whitespace()
     }
 
+    /// Returns the first descendant element whose ID is `id`, like
+    /// [`Document::get_element_by_id`](crate::Document::get_element_by_id)
+    /// but scoped to the subtree of this element.
+    pub fn get_element_by_id(&self, id: &str) -> Option<Node> {
+        self.mixin_parent_node.get_element_by_id(id)
+    }
+
     pub fn id(&self) -> &str {
         self.attributes().find(&local_name!("id")).unwrap_or("")
     }
@@ -70,6 +88,22 @@ impl Element {
     pub fn qualified_name(&self) -> &QualName {
         &self.qualified_name
     }
+
+    pub fn is_hovered(&self) -> bool {
+        self.hovered.load(Ordering::Relaxed)
+    }
+
+    pub fn set_hovered(&self, hovered: bool) {
+        self.hovered.store(hovered, Ordering::Relaxed);
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.focused.load(Ordering::Relaxed)
+    }
+
+    pub fn set_focused(&self, focused: bool) {
+        self.focused.store(focused, Ordering::Relaxed);
+    }
 }
 
 impl DynamicSizeOf for Element {
@@ -78,5 +112,7 @@ impl DynamicSizeOf for Element {
             + self.mixin_parent_node.dynamic_size_of()
             + std::mem::size_of_val(&self.qualified_name)
             + self.attribute_list.dynamic_size_of()
+            + std::mem::size_of_val(&self.hovered)
+            + std::mem::size_of_val(&self.focused)
     }
 }
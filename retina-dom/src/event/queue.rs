@@ -1,11 +1,25 @@
 // Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
 // All Rights Reserved.
 
-use std::sync::{
-    Arc,
-    Mutex,
+use std::{
+    collections::VecDeque,
+    sync::{
+        Arc,
+        Mutex,
+    },
 };
 
+/// A task that runs as soon as the currently running script finishes, i.e.
+/// at the next [microtask checkpoint][spec].
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/webappapis.html#perform-a-microtask-checkpoint
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Microtask {
+    /// [Notify the mutation observers](https://dom.spec.whatwg.org/#notify-mutation-observers)
+    /// of the records in their queues.
+    NotifyMutationObservers,
+}
+
 #[derive(Clone)]
 pub struct EventQueue {
     inner: Arc<Mutex<EventQueueInner>>,
@@ -13,20 +27,60 @@ pub struct EventQueue {
 
 impl EventQueue {
     pub fn new() -> Self {
-        let s = Self {
+        Self {
             inner: Arc::new(Mutex::new(
                 EventQueueInner {
-
+                    microtasks: VecDeque::new(),
+                    mutation_observer_microtask_queued: false,
                 }
             ))
-        };
+        }
+    }
+
+    pub fn queue_microtask(&self, microtask: Microtask) {
+        self.inner.lock().unwrap().microtasks.push_back(microtask);
+    }
+
+    /// [DOM Living Standard § 4.3.2 Queue a mutation observer microtask](https://dom.spec.whatwg.org/#queue-a-mutation-observer-compound-microtask)
+    pub fn queue_mutation_observer_microtask(&self) {
+        let mut inner = self.inner.lock().unwrap();
+
+        // 1. If the surrounding agent’s mutation observer microtask queued is
+        //    true, then return.
+        if inner.mutation_observer_microtask_queued {
+            return;
+        }
 
-        _ = s.inner.as_ref();
+        // 2. Set the surrounding agent’s mutation observer microtask queued
+        //    to true.
+        inner.mutation_observer_microtask_queued = true;
 
-        s
+        // 3. Queue a microtask to notify mutation observers.
+        inner.microtasks.push_back(Microtask::NotifyMutationObservers);
+    }
+
+    /// Take the oldest microtask from the queue, which the caller must run.
+    pub fn pop_microtask(&self) -> Option<Microtask> {
+        let mut inner = self.inner.lock().unwrap();
+        let microtask = inner.microtasks.pop_front()?;
+
+        // The first step of notifying the mutation observers is setting the
+        // surrounding agent’s mutation observer microtask queued to false.
+        if microtask == Microtask::NotifyMutationObservers {
+            inner.mutation_observer_microtask_queued = false;
+        }
+
+        Some(microtask)
     }
 }
 
-struct EventQueueInner {
+impl std::fmt::Debug for EventQueue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventQueue").finish_non_exhaustive()
+    }
+}
 
+struct EventQueueInner {
+    microtasks: VecDeque<Microtask>,
+    mutation_observer_microtask_queued: bool,
 }
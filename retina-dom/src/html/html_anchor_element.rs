@@ -0,0 +1,193 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use html5ever::{LocalName, QualName, local_name};
+
+use crate::{
+    Element,
+    html::HtmlElement,
+    LinkRelationship,
+    LinkType,
+};
+
+/// The [`<a>`][spec] element, which is a hyperlink when it has a `href`
+/// attribute.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-a-element
+#[derive(Debug)]
+pub struct HtmlAnchorElement {
+    superclass_html_element: HtmlElement,
+}
+
+impl HtmlAnchorElement {
+    pub fn new(qualified_name: QualName) -> Self {
+        Self {
+            superclass_html_element: HtmlElement::new(qualified_name),
+        }
+    }
+
+    /// The [`href`][spec] attribute, which is the URL the hyperlink points
+    /// to. The element isn't a hyperlink if this attribute is missing.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/links.html#attr-hyperlink-href
+    pub fn href(&self) -> Option<&str> {
+        self.attribute(&local_name!("href"))
+    }
+
+    /// The [`target`][spec] attribute, which is the name of the navigable in
+    /// which the hyperlink is followed.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/links.html#attr-hyperlink-target
+    pub fn target(&self) -> &str {
+        self.attribute(&local_name!("target")).unwrap_or_default()
+    }
+
+    /// The [`rel`][spec] attribute, which is a space-separated list of link
+    /// types.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/links.html#attr-hyperlink-rel
+    pub fn rel(&self) -> &str {
+        self.attribute(&local_name!("rel")).unwrap_or_default()
+    }
+
+    pub fn relationship(&self) -> LinkRelationship<'_> {
+        LinkRelationship::for_anchor_element(self.rel())
+    }
+
+    /// The [`download`][spec] attribute, which means the resource should be
+    /// downloaded instead of navigated to, with the value as the suggested
+    /// file name.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/links.html#attr-hyperlink-download
+    pub fn download(&self) -> Option<&str> {
+        self.attribute(&local_name!("download"))
+    }
+
+    /// The [`hreflang`][spec] attribute, which is the language of the linked
+    /// resource.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/links.html#attr-hyperlink-hreflang
+    pub fn hreflang(&self) -> Option<&str> {
+        self.attribute(&local_name!("hreflang"))
+    }
+
+    /// Whether following the hyperlink opens a new top-level navigable,
+    /// according to the [rules for choosing a navigable][spec]. Named
+    /// navigables aren't tracked, so any name other than the keywords is
+    /// treated as a new one.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/document-sequences.html#the-rules-for-choosing-a-navigable
+    pub fn opens_new_browsing_context(&self) -> bool {
+        let target = self.target();
+        !target.is_empty()
+            && !target.eq_ignore_ascii_case("_self")
+            && !target.eq_ignore_ascii_case("_parent")
+            && !target.eq_ignore_ascii_case("_top")
+    }
+
+    /// [Get an element's noopener][spec], i.e. whether a new navigable
+    /// created by following this hyperlink must not have an opener.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/links.html#get-an-element's-noopener
+    pub fn no_opener(&self) -> bool {
+        let mut opener = false;
+        for link_type in self.relationship() {
+            match link_type {
+                LinkType::NoOpener | LinkType::NoReferrer => return true,
+                LinkType::Opener => opener = true,
+                _ => (),
+            }
+        }
+
+        !opener && self.target().eq_ignore_ascii_case("_blank")
+    }
+
+    /// Whether the `noreferrer` link type is specified, meaning the
+    /// `Referer` header must not be sent when following the hyperlink.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/links.html#link-type-noreferrer
+    pub fn no_referrer(&self) -> bool {
+        self.relationship().contains(LinkType::NoReferrer)
+    }
+
+    fn attribute(&self, name: &LocalName) -> Option<&str> {
+        self.superclass_html_element.as_ref()
+            .attributes()
+            .find(name)
+    }
+}
+
+impl AsRef<Element> for HtmlAnchorElement {
+    fn as_ref(&self) -> &Element {
+        self.superclass_html_element.as_ref()
+    }
+}
+
+impl AsMut<Element> for HtmlAnchorElement {
+    fn as_mut(&mut self) -> &mut Element {
+        self.superclass_html_element.as_mut()
+    }
+}
+
+impl AsRef<HtmlElement> for HtmlAnchorElement {
+    fn as_ref(&self) -> &HtmlElement {
+        &self.superclass_html_element
+    }
+}
+
+impl AsMut<HtmlElement> for HtmlAnchorElement {
+    fn as_mut(&mut self) -> &mut HtmlElement {
+        &mut self.superclass_html_element
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{HtmlElementKind, LinkType, Parser};
+
+    fn with_anchor(html: &str, f: impl FnOnce(&super::HtmlAnchorElement)) {
+        let document = Parser::parse(html);
+        let node = document.as_document().unwrap().get_element_by_id("a").unwrap();
+        let Some(HtmlElementKind::Anchor(anchor)) = node.as_html_element_kind() else {
+            panic!("not an anchor element: {node:?}");
+        };
+        f(anchor);
+    }
+
+    #[test]
+    fn attributes() {
+        with_anchor(r#"<a id="a" href="/x" download="x.txt" hreflang="nl" rel="nofollow stylesheet external">"#, |anchor| {
+            assert_eq!(anchor.href(), Some("/x"));
+            assert_eq!(anchor.target(), "");
+            assert_eq!(anchor.download(), Some("x.txt"));
+            assert_eq!(anchor.hreflang(), Some("nl"));
+
+            // `stylesheet` isn't allowed on <a> elements.
+            let link_types: Vec<_> = anchor.relationship().collect();
+            assert_eq!(link_types, [LinkType::NoFollow, LinkType::External]);
+        });
+    }
+
+    #[test]
+    fn navigation_context() {
+        // (target, rel, new browsing context, noopener, noreferrer)
+        let cases = [
+            ("", "", false, false, false),
+            ("_self", "", false, false, false),
+            ("_TOP", "", false, false, false),
+            ("_blank", "", true, true, false),
+            ("_blank", "opener", true, false, false),
+            ("some-window", "", true, false, false),
+            ("some-window", "noopener", true, true, false),
+            ("", "noreferrer", false, true, true),
+        ];
+
+        for (target, rel, new_browsing_context, no_opener, no_referrer) in cases {
+            with_anchor(&format!(r#"<a id="a" href="/" target="{target}" rel="{rel}">"#), |anchor| {
+                assert_eq!(anchor.opens_new_browsing_context(), new_browsing_context, "target={target:?} rel={rel:?}");
+                assert_eq!(anchor.no_opener(), no_opener, "target={target:?} rel={rel:?}");
+                assert_eq!(anchor.no_referrer(), no_referrer, "target={target:?} rel={rel:?}");
+            });
+        }
+    }
+}
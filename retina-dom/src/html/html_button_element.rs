@@ -0,0 +1,91 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use html5ever::{LocalName, QualName, local_name};
+
+use crate::{
+    Element,
+    html::HtmlElement,
+};
+
+/// The [`<button>`][spec] element, which represents a button labeled by its
+/// contents.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/form-elements.html#the-button-element
+#[derive(Debug)]
+pub struct HtmlButtonElement {
+    superclass_html_element: HtmlElement,
+}
+
+impl HtmlButtonElement {
+    pub fn new(qualified_name: QualName) -> Self {
+        Self {
+            superclass_html_element: HtmlElement::new(qualified_name),
+        }
+    }
+
+    /// The [`name`][spec] attribute, which is the name of the entry of the
+    /// button when it submits its form.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/form-control-infrastructure.html#attr-fe-name
+    pub fn name(&self) -> &str {
+        self.attribute(&local_name!("name")).unwrap_or_default()
+    }
+
+    /// The [`value`][spec] attribute, which is the value of the entry of the
+    /// button when it submits its form.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/form-elements.html#attr-button-value
+    pub fn value(&self) -> &str {
+        self.attribute(&local_name!("value")).unwrap_or_default()
+    }
+
+    /// Whether the button is in the [Submit Button][spec] state, which is the
+    /// default for a missing or invalid `type` attribute.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/form-elements.html#attr-button-type-submit-state
+    pub fn is_submit_button(&self) -> bool {
+        let ty = self.attribute(&local_name!("type")).unwrap_or_default();
+        !ty.eq_ignore_ascii_case("button") && !ty.eq_ignore_ascii_case("reset")
+    }
+
+    /// Whether the [`disabled`][spec] attribute is specified.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/form-control-infrastructure.html#attr-fe-disabled
+    pub fn is_disabled(&self) -> bool {
+        self.superclass_html_element.as_ref()
+            .attributes()
+            .find(&local_name!("disabled"))
+            .is_some()
+    }
+
+    fn attribute(&self, name: &LocalName) -> Option<&str> {
+        self.superclass_html_element.as_ref()
+            .attributes()
+            .find(name)
+    }
+}
+
+impl AsRef<Element> for HtmlButtonElement {
+    fn as_ref(&self) -> &Element {
+        self.superclass_html_element.as_ref()
+    }
+}
+
+impl AsMut<Element> for HtmlButtonElement {
+    fn as_mut(&mut self) -> &mut Element {
+        self.superclass_html_element.as_mut()
+    }
+}
+
+impl AsRef<HtmlElement> for HtmlButtonElement {
+    fn as_ref(&self) -> &HtmlElement {
+        &self.superclass_html_element
+    }
+}
+
+impl AsMut<HtmlElement> for HtmlButtonElement {
+    fn as_mut(&mut self) -> &mut HtmlElement {
+        &mut self.superclass_html_element
+    }
+}
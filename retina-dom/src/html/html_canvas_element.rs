@@ -0,0 +1,187 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use std::{
+    any::Any,
+    sync::{Arc, OnceLock},
+};
+
+use html5ever::{LocalName, QualName, local_name};
+
+use crate::{
+    CanvasBitmap,
+    Element,
+    html::HtmlElement,
+};
+
+use super::microsyntax::parse_non_negative_integer;
+
+/// The [`<canvas>`][spec] element, which is a bitmap that scripts can draw
+/// onto using a rendering context.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/canvas.html#the-canvas-element
+#[derive(Debug)]
+pub struct HtmlCanvasElement {
+    superclass_html_element: HtmlElement,
+
+    /// The [output bitmap][spec], which is created when it is first used,
+    /// since the attributes aren't known when the element is created.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/canvas.html#output-bitmap
+    bitmap: OnceLock<CanvasBitmap>,
+
+    /// The rendering context returned by `getContext()`, which is
+    /// implemented by the graphics library and therefore isn't known to the
+    /// DOM.
+    rendering_context: OnceLock<Arc<dyn Any + Send + Sync>>,
+}
+
+impl HtmlCanvasElement {
+    /// The width of the canvas if the `width` attribute is missing or
+    /// invalid.
+    pub const DEFAULT_WIDTH: u32 = 300;
+
+    /// The height of the canvas if the `height` attribute is missing or
+    /// invalid.
+    pub const DEFAULT_HEIGHT: u32 = 150;
+
+    pub fn new(qualified_name: QualName) -> Self {
+        Self {
+            superclass_html_element: HtmlElement::new(qualified_name),
+            bitmap: OnceLock::new(),
+            rendering_context: OnceLock::new(),
+        }
+    }
+
+    /// The width of the bitmap in pixels, as specified by the
+    /// [`width`][spec] attribute.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/canvas.html#attr-canvas-width
+    pub fn width(&self) -> u32 {
+        self.dimension_attribute(&local_name!("width"), Self::DEFAULT_WIDTH)
+    }
+
+    /// The height of the bitmap in pixels, as specified by the
+    /// [`height`][spec] attribute.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/canvas.html#attr-canvas-height
+    pub fn height(&self) -> u32 {
+        self.dimension_attribute(&local_name!("height"), Self::DEFAULT_HEIGHT)
+    }
+
+    pub fn bitmap(&self) -> &CanvasBitmap {
+        self.bitmap.get_or_init(|| CanvasBitmap::new(self.width(), self.height()))
+    }
+
+    /// The rendering context of this canvas, as returned by
+    /// [`getContext()`][spec], which is created using `create` when it is
+    /// first requested. Each call returns the same context.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/canvas.html#dom-canvas-getcontext
+    pub fn rendering_context<F>(&self, create: F) -> &Arc<dyn Any + Send + Sync>
+            where F: FnOnce(&CanvasBitmap) -> Arc<dyn Any + Send + Sync> {
+        self.rendering_context.get_or_init(|| create(self.bitmap()))
+    }
+
+    fn dimension_attribute(&self, name: &LocalName, default: u32) -> u32 {
+        self.superclass_html_element.as_ref()
+            .attributes()
+            .find(name)
+            .and_then(parse_non_negative_integer)
+            .and_then(|value| u32::try_from(value).ok())
+            .unwrap_or(default)
+    }
+}
+
+impl AsRef<Element> for HtmlCanvasElement {
+    fn as_ref(&self) -> &Element {
+        self.superclass_html_element.as_ref()
+    }
+}
+
+impl AsMut<Element> for HtmlCanvasElement {
+    fn as_mut(&mut self) -> &mut Element {
+        self.superclass_html_element.as_mut()
+    }
+}
+
+impl AsRef<HtmlElement> for HtmlCanvasElement {
+    fn as_ref(&self) -> &HtmlElement {
+        &self.superclass_html_element
+    }
+}
+
+impl AsMut<HtmlElement> for HtmlCanvasElement {
+    fn as_mut(&mut self) -> &mut HtmlElement {
+        &mut self.superclass_html_element
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CanvasImageSource, CanvasPixels, HtmlElementKind, Node, Parser};
+
+    fn find_canvas(node: &Node) -> Option<Node> {
+        if let Some(HtmlElementKind::Canvas(..)) = node.as_html_element_kind() {
+            return Some(node.clone());
+        }
+
+        node.as_parent_node()?
+            .children()
+            .iter()
+            .find_map(find_canvas)
+    }
+
+    #[test]
+    fn dimensions() {
+        let document = Parser::parse(r#"<canvas width="40" height="-2"></canvas>"#);
+        let node = find_canvas(&document).unwrap();
+        let Some(HtmlElementKind::Canvas(canvas)) = node.as_html_element_kind() else { unreachable!() };
+
+        assert_eq!(canvas.width(), 40);
+        assert_eq!(canvas.height(), 150);
+        assert_eq!(canvas.bitmap().width(), 40);
+        assert_eq!(canvas.bitmap().height(), 150);
+    }
+
+    #[test]
+    fn empty_bitmap() {
+        let document = Parser::parse(r#"<canvas width="0" height="10"></canvas>"#);
+        let node = find_canvas(&document).unwrap();
+        let Some(HtmlElementKind::Canvas(canvas)) = node.as_html_element_kind() else { unreachable!() };
+
+        assert!(canvas.bitmap().pixels().is_none());
+        assert_eq!(canvas.bitmap().to_rgba8(), Vec::<u8>::new());
+
+        canvas.bitmap().draw(|_| unreachable!("there are no pixels to draw onto"));
+        assert_eq!(canvas.bitmap().generation(), 0);
+        assert!(CanvasImageSource::from_node(&node).is_none());
+    }
+
+    #[test]
+    fn draw_and_copy_pixels() {
+        let document = Parser::parse(r#"<canvas width="2" height="1"></canvas>"#);
+        let node = find_canvas(&document).unwrap();
+        let Some(HtmlElementKind::Canvas(canvas)) = node.as_html_element_kind() else { unreachable!() };
+
+        // Half-transparent red, premultiplied.
+        canvas.bitmap().draw(|pixels| pixels.data_mut()[..4].copy_from_slice(&[128, 0, 0, 128]));
+        assert_eq!(canvas.bitmap().generation(), 1);
+        assert_eq!(canvas.bitmap().to_rgba8(), [255, 0, 0, 128, 0, 0, 0, 0]);
+
+        let image = CanvasImageSource::from_node(&node).unwrap();
+        assert_eq!((image.width(), image.height()), (2, 1));
+        assert_eq!(image.pixels().pixel(0, 0), Some([255, 0, 0, 128]));
+        assert_eq!(image.pixels().pixel(2, 0), None);
+
+        assert!(CanvasImageSource::from_node(&document).is_none());
+    }
+
+    #[test]
+    fn premultiplied_pixels() {
+        let pixels = CanvasPixels::from_rgba8(2, 1, &[255, 255, 255, 0, 200, 100, 50, 255]).unwrap();
+        assert_eq!(pixels.data(), [0, 0, 0, 0, 200, 100, 50, 255]);
+        assert_eq!(pixels.pixel(1, 0), Some([200, 100, 50, 255]));
+        assert!(CanvasPixels::new(0, 1).is_none());
+    }
+}
@@ -0,0 +1,223 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use std::sync::{RwLock, Weak};
+
+use html5ever::{LocalName, QualName, local_name};
+use strum::EnumString;
+
+use crate::{
+    Element,
+    Node,
+    NodeKind,
+    html::HtmlElement,
+};
+
+/// The [`<form>`][spec] element, which represents a collection of controls
+/// that can be submitted to a server.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/forms.html#the-form-element
+#[derive(Debug)]
+pub struct HtmlFormElement {
+    superclass_html_element: HtmlElement,
+
+    /// The form-associated elements of which this form is the [form
+    /// owner][spec], in tree order.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/form-control-infrastructure.html#form-owner
+    controls: RwLock<Vec<Weak<NodeKind>>>,
+}
+
+impl HtmlFormElement {
+    pub fn new(qualified_name: QualName) -> Self {
+        Self {
+            superclass_html_element: HtmlElement::new(qualified_name),
+            controls: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// The [`action`][spec] attribute, which is the URL to submit the form
+    /// to. If it is empty, the form is submitted to the URL of the document.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/form-control-infrastructure.html#attr-fs-action
+    pub fn action(&self) -> &str {
+        self.attribute(&local_name!("action")).unwrap_or_default().trim()
+    }
+
+    /// The state of the [`method`][spec] attribute.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/form-control-infrastructure.html#attr-fs-method
+    pub fn method(&self) -> FormMethod {
+        self.attribute(&local_name!("method"))
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_default()
+    }
+
+    /// The state of the [`enctype`][spec] attribute.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/form-control-infrastructure.html#attr-fs-enctype
+    pub fn enctype(&self) -> FormEnctype {
+        self.attribute(&local_name!("enctype"))
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_default()
+    }
+
+    /// Whether the [`novalidate`][spec] attribute is specified, which means
+    /// the constraints of the controls aren't validated on submission.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/form-control-infrastructure.html#attr-fs-novalidate
+    pub fn no_validate(&self) -> bool {
+        self.attribute(&local_name!("novalidate")).is_some()
+    }
+
+    /// The controls associated with this form, i.e. the [`elements`][spec],
+    /// excluding the ones that were removed from the tree.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/forms.html#dom-form-elements
+    pub fn controls(&self) -> Vec<Node> {
+        self.controls.read().unwrap()
+            .iter()
+            .filter_map(Weak::upgrade)
+            .map(Node::from)
+            .collect()
+    }
+
+    /// Associate the control with this form, i.e. make this form its form
+    /// owner.
+    pub(crate) fn add_control(&self, control: Weak<NodeKind>) {
+        self.controls.write().unwrap().push(control);
+    }
+
+    fn attribute(&self, name: &LocalName) -> Option<&str> {
+        self.superclass_html_element.as_ref()
+            .attributes()
+            .find(name)
+    }
+}
+
+impl AsRef<Element> for HtmlFormElement {
+    fn as_ref(&self) -> &Element {
+        self.superclass_html_element.as_ref()
+    }
+}
+
+impl AsMut<Element> for HtmlFormElement {
+    fn as_mut(&mut self) -> &mut Element {
+        self.superclass_html_element.as_mut()
+    }
+}
+
+impl AsRef<HtmlElement> for HtmlFormElement {
+    fn as_ref(&self) -> &HtmlElement {
+        &self.superclass_html_element
+    }
+}
+
+impl AsMut<HtmlElement> for HtmlFormElement {
+    fn as_mut(&mut self) -> &mut HtmlElement {
+        &mut self.superclass_html_element
+    }
+}
+
+/// The state of the [`method`][spec] attribute, which is [`FormMethod::Get`]
+/// if it is missing or invalid.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/form-control-infrastructure.html#attr-fs-method
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(EnumString, strum::Display)]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum FormMethod {
+    /// The entries are appended to the query of the action URL.
+    #[default]
+    Get,
+
+    /// The entries are sent as the body of the request.
+    Post,
+
+    /// The form closes the `<dialog>` it is in, without submitting.
+    Dialog,
+}
+
+/// The state of the [`enctype`][spec] attribute, which is
+/// [`FormEnctype::UrlEncoded`] if it is missing or invalid.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/form-control-infrastructure.html#attr-fs-enctype
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(EnumString, strum::Display, strum::AsRefStr)]
+#[strum(ascii_case_insensitive)]
+pub enum FormEnctype {
+    #[default]
+    #[strum(serialize = "application/x-www-form-urlencoded")]
+    UrlEncoded,
+
+    #[strum(serialize = "multipart/form-data")]
+    Multipart,
+
+    #[strum(serialize = "text/plain")]
+    TextPlain,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HtmlElementKind, Parser};
+
+    fn find_form(node: &Node) -> Option<Node> {
+        if let Some(HtmlElementKind::Form(..)) = node.as_html_element_kind() {
+            return Some(node.clone());
+        }
+
+        node.as_parent_node()?
+            .children()
+            .iter()
+            .find_map(find_form)
+    }
+
+    #[test]
+    fn attributes() {
+        let document = Parser::parse(r#"
+            <form action=" /search " method="POST" enctype="Multipart/Form-Data" novalidate></form>
+        "#);
+
+        let form = find_form(&document).unwrap();
+        let Some(HtmlElementKind::Form(form)) = form.as_html_element_kind() else { unreachable!() };
+
+        assert_eq!(form.action(), "/search");
+        assert_eq!(form.method(), FormMethod::Post);
+        assert_eq!(form.enctype(), FormEnctype::Multipart);
+        assert!(form.no_validate());
+    }
+
+    #[test]
+    fn defaults() {
+        let document = Parser::parse(r#"<form method="put" enctype="foo"></form>"#);
+
+        let form = find_form(&document).unwrap();
+        let Some(HtmlElementKind::Form(form)) = form.as_html_element_kind() else { unreachable!() };
+
+        assert_eq!(form.action(), "");
+        assert_eq!(form.method(), FormMethod::Get);
+        assert_eq!(form.enctype(), FormEnctype::UrlEncoded);
+        assert!(!form.no_validate());
+    }
+
+    #[test]
+    fn controls() {
+        let document = Parser::parse(r#"
+            <form>
+                <input name="a">
+                <div><input name="b"><button>Go</button></div>
+            </form>
+            <input name="c">
+        "#);
+
+        let form = find_form(&document).unwrap();
+        let Some(HtmlElementKind::Form(form)) = form.as_html_element_kind() else { unreachable!() };
+
+        let names: Vec<_> = form.controls()
+            .iter()
+            .map(|control| control.as_dom_element().unwrap().qualified_name().local.to_string())
+            .collect();
+        assert_eq!(names, ["input", "input", "button"]);
+    }
+}
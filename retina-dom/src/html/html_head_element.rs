@@ -0,0 +1,50 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use html5ever::QualName;
+
+use crate::{
+    Element,
+    html::HtmlElement,
+};
+
+/// The [`<head>`][spec] element, which contains the metadata of the document,
+/// such as the `<title>`, `<meta>` and `<link>` elements.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/semantics.html#the-head-element
+#[derive(Debug)]
+pub struct HtmlHeadElement {
+    superclass_html_element: HtmlElement,
+}
+
+impl HtmlHeadElement {
+    pub fn new(qualified_name: QualName) -> Self {
+        Self {
+            superclass_html_element: HtmlElement::new(qualified_name),
+        }
+    }
+}
+
+impl AsRef<Element> for HtmlHeadElement {
+    fn as_ref(&self) -> &Element {
+        self.superclass_html_element.as_ref()
+    }
+}
+
+impl AsMut<Element> for HtmlHeadElement {
+    fn as_mut(&mut self) -> &mut Element {
+        self.superclass_html_element.as_mut()
+    }
+}
+
+impl AsRef<HtmlElement> for HtmlHeadElement {
+    fn as_ref(&self) -> &HtmlElement {
+        &self.superclass_html_element
+    }
+}
+
+impl AsMut<HtmlElement> for HtmlHeadElement {
+    fn as_mut(&mut self) -> &mut HtmlElement {
+        &mut self.superclass_html_element
+    }
+}
@@ -0,0 +1,245 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use html5ever::{LocalName, QualName, local_name};
+
+use crate::{
+    Element,
+    html::{HtmlElement, InputType},
+};
+
+/// The [`<input>`][spec] element, which represents a typed data field, such
+/// as a text field or a checkbox.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/input.html#the-input-element
+#[derive(Debug)]
+pub struct HtmlInputElement {
+    superclass_html_element: HtmlElement,
+}
+
+impl HtmlInputElement {
+    pub fn new(qualified_name: QualName) -> Self {
+        Self {
+            superclass_html_element: HtmlElement::new(qualified_name),
+        }
+    }
+
+    /// The state of the [`type`][spec] attribute, which is
+    /// [`InputType::Text`] if it is missing or invalid.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/input.html#attr-input-type
+    pub fn input_type(&self) -> InputType {
+        InputType::parse(self.attribute(&local_name!("type")))
+    }
+
+    /// The [`name`][spec] attribute, which is the name of the entry of the
+    /// input when its form is submitted.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/form-control-infrastructure.html#attr-fe-name
+    pub fn name(&self) -> &str {
+        self.attribute(&local_name!("name")).unwrap_or_default()
+    }
+
+    /// The [`value`][spec] attribute, which is the default value of the
+    /// input, since the value can't be changed by the user yet.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/input.html#attr-input-value
+    pub fn value(&self) -> &str {
+        self.attribute(&local_name!("value")).unwrap_or_default()
+    }
+
+    /// The [`placeholder`][spec] attribute, which is a short hint shown when
+    /// the value of the input is empty.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/input.html#attr-input-placeholder
+    pub fn placeholder(&self) -> &str {
+        self.attribute(&local_name!("placeholder")).unwrap_or_default()
+    }
+
+    /// The [`min`][spec] attribute, if it is a valid floating-point number.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/input.html#attr-input-min
+    pub fn min(&self) -> Option<f64> {
+        self.attribute(&local_name!("min")).and_then(parse_floating_point_number)
+    }
+
+    /// The [`max`][spec] attribute, if it is a valid floating-point number.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/input.html#attr-input-max
+    pub fn max(&self) -> Option<f64> {
+        self.attribute(&local_name!("max")).and_then(parse_floating_point_number)
+    }
+
+    /// The [`step`][spec] attribute, if it is `any` or a valid floating-point
+    /// number greater than zero. Otherwise, the default step of the type
+    /// applies.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/input.html#attr-input-step
+    pub fn step(&self) -> Option<InputStep> {
+        let value = self.attribute(&local_name!("step"))?;
+        if value.eq_ignore_ascii_case("any") {
+            return Some(InputStep::Any);
+        }
+
+        parse_floating_point_number(value)
+            .filter(|step| *step > 0.0)
+            .map(InputStep::Value)
+    }
+
+    /// Whether the checkbox or radio button is checked. This is based on the
+    /// [`checked`][spec] attribute, since the checkedness can't be changed by
+    /// the user yet.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/input.html#attr-input-checked
+    pub fn is_checked(&self) -> bool {
+        self.input_type().is_checkable() && self.has_attribute(&local_name!("checked"))
+    }
+
+    /// Whether the [`disabled`][spec] attribute is specified.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/form-control-infrastructure.html#attr-fe-disabled
+    pub fn is_disabled(&self) -> bool {
+        self.has_attribute(&local_name!("disabled"))
+    }
+
+    /// Whether the [`required`][spec] attribute applies to the type of this
+    /// input, e.g. it doesn't for buttons.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/input.html#attr-input-required
+    pub fn supports_required(&self) -> bool {
+        self.input_type().supports_required()
+    }
+
+    /// Whether the [`required`][spec] attribute is specified and applies to
+    /// the type of this input.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/input.html#attr-input-required
+    pub fn is_required(&self) -> bool {
+        self.supports_required() && self.has_attribute(&local_name!("required"))
+    }
+
+    /// The [`pattern`][spec] attribute, which is a regular expression the
+    /// value must match.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/input.html#attr-input-pattern
+    pub fn pattern(&self) -> Option<&str> {
+        self.attribute(&local_name!("pattern"))
+    }
+
+    /// Whether the input satisfies its constraints, i.e. it isn't
+    /// [suffering from being missing][missing] or [from a pattern
+    /// mismatch][mismatch]. Disabled inputs and buttons are [barred from
+    /// constraint validation][barred].
+    ///
+    /// [barred]: https://html.spec.whatwg.org/multipage/form-control-infrastructure.html#barred-from-constraint-validation
+    /// [mismatch]: https://html.spec.whatwg.org/multipage/input.html#attr-input-pattern
+    /// [missing]: https://html.spec.whatwg.org/multipage/input.html#attr-input-required
+    pub fn is_valid(&self) -> bool {
+        let ty = self.input_type();
+        if self.is_disabled() || ty.is_button() || ty == InputType::Hidden {
+            return true;
+        }
+
+        !self.is_value_missing() && !self.has_pattern_mismatch()
+    }
+
+    /// Whether the input is required, but has no value.
+    ///
+    /// TODO: a radio button is also satisfied by another checked radio button
+    ///       in the same group.
+    pub fn is_value_missing(&self) -> bool {
+        if !self.is_required() {
+            return false;
+        }
+
+        match self.input_type() {
+            InputType::Checkbox | InputType::Radio => !self.is_checked(),
+
+            // Selecting files isn't supported yet.
+            InputType::File => true,
+
+            _ => self.value().is_empty(),
+        }
+    }
+
+    /// Whether the value doesn't match the `pattern` attribute. An invalid
+    /// pattern is ignored, and so is an empty value.
+    pub fn has_pattern_mismatch(&self) -> bool {
+        if !self.input_type().supports_pattern() || self.value().is_empty() {
+            return false;
+        }
+
+        let Some(pattern) = self.pattern() else {
+            return false;
+        };
+
+        // The pattern must match the entire value.
+        match regex::Regex::new(&format!("^(?:{pattern})$")) {
+            Ok(regex) => !regex.is_match(self.value()),
+            Err(e) => {
+                log::warn!("Ignoring invalid pattern \"{pattern}\": {e}");
+                false
+            }
+        }
+    }
+
+    fn attribute(&self, name: &LocalName) -> Option<&str> {
+        self.superclass_html_element.as_ref()
+            .attributes()
+            .find(name)
+    }
+
+    fn has_attribute(&self, name: &LocalName) -> bool {
+        self.attribute(name).is_some()
+    }
+}
+
+/// The value of the [`step`][spec] attribute.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/input.html#attr-input-step
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum InputStep {
+    /// Any value is allowed.
+    Any,
+
+    /// The value must be a multiple of this step, offset by the minimum.
+    Value(f64),
+}
+
+/// Parse a [valid floating-point number][spec], which is stricter than the
+/// Rust parser, e.g. it doesn't allow `inf` or a leading `+`.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#rules-for-parsing-floating-point-number-values
+fn parse_floating_point_number(value: &str) -> Option<f64> {
+    let value = value.trim_matches(|c: char| c.is_ascii_whitespace());
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    if !digits.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+        return None;
+    }
+
+    value.parse().ok().filter(|value: &f64| value.is_finite())
+}
+
+impl AsRef<Element> for HtmlInputElement {
+    fn as_ref(&self) -> &Element {
+        self.superclass_html_element.as_ref()
+    }
+}
+
+impl AsMut<Element> for HtmlInputElement {
+    fn as_mut(&mut self) -> &mut Element {
+        self.superclass_html_element.as_mut()
+    }
+}
+
+impl AsRef<HtmlElement> for HtmlInputElement {
+    fn as_ref(&self) -> &HtmlElement {
+        &self.superclass_html_element
+    }
+}
+
+impl AsMut<HtmlElement> for HtmlInputElement {
+    fn as_mut(&mut self) -> &mut HtmlElement {
+        &mut self.superclass_html_element
+    }
+}
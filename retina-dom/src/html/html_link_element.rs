@@ -10,6 +10,7 @@ use crate::{
     Element,
     HtmlElement,
     LinkRelationship,
+    LinkType,
 };
 
 #[derive(Debug)]
@@ -42,6 +43,19 @@ impl HtmlLinkElement {
         LinkRelationship::for_link_element(self.rel())
     }
 
+    /// The `href` of the link if it refers to a style sheet that applies by
+    /// default, i.e. [`rel="stylesheet"`][spec] without `alternate`, which
+    /// only applies when the user selects it.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/links.html#link-type-stylesheet
+    pub fn stylesheet_href(&self) -> Option<&str> {
+        if !self.relationship().contains(LinkType::Stylesheet) || self.relationship().contains(LinkType::Alternate) {
+            return None;
+        }
+
+        Some(self.href()).filter(|href| !href.is_empty())
+    }
+
     pub fn type_(&self) -> Mime {
         self.superclass_html_element.as_ref()
             .attributes()
@@ -0,0 +1,70 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use html5ever::{QualName, local_name};
+
+use crate::{
+    Element,
+    html::HtmlElement,
+};
+
+/// The [`<select>`][spec] element, which represents a control for selecting
+/// amongst a set of options.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/form-elements.html#the-select-element
+#[derive(Debug)]
+pub struct HtmlSelectElement {
+    superclass_html_element: HtmlElement,
+}
+
+impl HtmlSelectElement {
+    pub fn new(qualified_name: QualName) -> Self {
+        Self {
+            superclass_html_element: HtmlElement::new(qualified_name),
+        }
+    }
+
+    /// Whether the [`disabled`][spec] attribute is specified.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/form-control-infrastructure.html#attr-fe-disabled
+    pub fn is_disabled(&self) -> bool {
+        self.superclass_html_element.as_ref()
+            .attributes()
+            .find(&local_name!("disabled"))
+            .is_some()
+    }
+
+    /// Whether the [`required`][spec] attribute is specified.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/form-elements.html#attr-select-required
+    pub fn is_required(&self) -> bool {
+        self.superclass_html_element.as_ref()
+            .attributes()
+            .find(&local_name!("required"))
+            .is_some()
+    }
+}
+
+impl AsRef<Element> for HtmlSelectElement {
+    fn as_ref(&self) -> &Element {
+        self.superclass_html_element.as_ref()
+    }
+}
+
+impl AsMut<Element> for HtmlSelectElement {
+    fn as_mut(&mut self) -> &mut Element {
+        self.superclass_html_element.as_mut()
+    }
+}
+
+impl AsRef<HtmlElement> for HtmlSelectElement {
+    fn as_ref(&self) -> &HtmlElement {
+        &self.superclass_html_element
+    }
+}
+
+impl AsMut<HtmlElement> for HtmlSelectElement {
+    fn as_mut(&mut self) -> &mut HtmlElement {
+        &mut self.superclass_html_element
+    }
+}
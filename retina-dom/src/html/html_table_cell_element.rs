@@ -0,0 +1,148 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use html5ever::{LocalName, QualName, local_name};
+
+use crate::{
+    Element,
+    html::HtmlElement,
+};
+
+use super::microsyntax::parse_non_negative_integer;
+
+/// The [`<td>`][td] and [`<th>`][th] elements, which represent a data cell
+/// and a header cell of a table respectively.
+///
+/// [td]: https://html.spec.whatwg.org/multipage/tables.html#the-td-element
+/// [th]: https://html.spec.whatwg.org/multipage/tables.html#the-th-element
+#[derive(Debug)]
+pub struct HtmlTableCellElement {
+    superclass_html_element: HtmlElement,
+}
+
+impl HtmlTableCellElement {
+    /// The largest value of the `colspan` attribute.
+    pub const MAX_COLUMN_SPAN: usize = 1000;
+
+    /// The largest value of the `rowspan` attribute.
+    pub const MAX_ROW_SPAN: usize = 65534;
+
+    pub fn new(qualified_name: QualName) -> Self {
+        Self {
+            superclass_html_element: HtmlElement::new(qualified_name),
+        }
+    }
+
+    /// The number of columns this cell spans, as specified by the
+    /// [`colspan`][spec] attribute. It is at least one and at most
+    /// [`Self::MAX_COLUMN_SPAN`].
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/tables.html#attr-tdth-colspan
+    pub fn column_span(&self) -> usize {
+        self.attribute(&local_name!("colspan"))
+            .and_then(parse_non_negative_integer)
+            .filter(|span| *span != 0)
+            .map_or(1, |span| span.min(Self::MAX_COLUMN_SPAN))
+    }
+
+    /// The number of rows this cell spans, as specified by the
+    /// [`rowspan`][spec] attribute. A value of zero means the cell spans all
+    /// the remaining rows of its row group.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/tables.html#attr-tdth-rowspan
+    pub fn row_span(&self) -> usize {
+        self.attribute(&local_name!("rowspan"))
+            .and_then(parse_non_negative_integer)
+            .map_or(1, |span| span.min(Self::MAX_ROW_SPAN))
+    }
+
+    /// Whether this is a header cell, i.e. a `<th>` element.
+    pub fn is_header(&self) -> bool {
+        let element: &Element = self.superclass_html_element.as_ref();
+        element.qualified_name().local == local_name!("th")
+    }
+
+    fn attribute(&self, name: &LocalName) -> Option<&str> {
+        self.superclass_html_element.as_ref()
+            .attributes()
+            .find(name)
+    }
+}
+
+impl AsRef<Element> for HtmlTableCellElement {
+    fn as_ref(&self) -> &Element {
+        self.superclass_html_element.as_ref()
+    }
+}
+
+impl AsMut<Element> for HtmlTableCellElement {
+    fn as_mut(&mut self) -> &mut Element {
+        self.superclass_html_element.as_mut()
+    }
+}
+
+impl AsRef<HtmlElement> for HtmlTableCellElement {
+    fn as_ref(&self) -> &HtmlElement {
+        &self.superclass_html_element
+    }
+}
+
+impl AsMut<HtmlElement> for HtmlTableCellElement {
+    fn as_mut(&mut self) -> &mut HtmlElement {
+        &mut self.superclass_html_element
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{HtmlElementKind, Node, Parser};
+
+    fn find_cells(node: &Node, cells: &mut Vec<Node>) {
+        if let Some(HtmlElementKind::TableCell(..)) = node.as_html_element_kind() {
+            cells.push(node.clone());
+        }
+
+        if let Some(parent) = node.as_parent_node() {
+            for child in parent.children().iter() {
+                find_cells(child, cells);
+            }
+        }
+    }
+
+    fn spans(html: &str) -> Vec<(usize, usize, bool)> {
+        let mut cells = Vec::new();
+        find_cells(&Parser::parse(html), &mut cells);
+
+        cells.iter()
+            .map(|cell| {
+                let cell = cell.as_html_element_kind().unwrap().as_table_cell_element().unwrap();
+                (cell.column_span(), cell.row_span(), cell.is_header())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn column_and_row_span() {
+        let spans = spans(r#"
+            <table><tr>
+                <th>a</th>
+                <td colspan="2" rowspan="3">b</td>
+                <td colspan=" +4px" rowspan="0">c</td>
+            </tr></table>
+        "#);
+
+        assert_eq!(spans, [(1, 1, true), (2, 3, false), (4, 0, false)]);
+    }
+
+    #[test]
+    fn invalid_and_large_spans() {
+        let spans = spans(r#"
+            <table><tr>
+                <td colspan="0" rowspan="-1">a</td>
+                <td colspan="5000" rowspan="99999999999999999999999">b</td>
+            </tr></table>
+        "#);
+
+        assert_eq!(spans, [(1, 1, false), (1000, 65534, false)]);
+    }
+}
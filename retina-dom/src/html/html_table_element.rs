@@ -0,0 +1,50 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use html5ever::QualName;
+
+use crate::{
+    Element,
+    html::HtmlElement,
+};
+
+/// The [`<table>`][spec] element, which represents data with more than one
+/// dimension, in the form of a table.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/tables.html#the-table-element
+#[derive(Debug)]
+pub struct HtmlTableElement {
+    superclass_html_element: HtmlElement,
+}
+
+impl HtmlTableElement {
+    pub fn new(qualified_name: QualName) -> Self {
+        Self {
+            superclass_html_element: HtmlElement::new(qualified_name),
+        }
+    }
+}
+
+impl AsRef<Element> for HtmlTableElement {
+    fn as_ref(&self) -> &Element {
+        self.superclass_html_element.as_ref()
+    }
+}
+
+impl AsMut<Element> for HtmlTableElement {
+    fn as_mut(&mut self) -> &mut Element {
+        self.superclass_html_element.as_mut()
+    }
+}
+
+impl AsRef<HtmlElement> for HtmlTableElement {
+    fn as_ref(&self) -> &HtmlElement {
+        &self.superclass_html_element
+    }
+}
+
+impl AsMut<HtmlElement> for HtmlTableElement {
+    fn as_mut(&mut self) -> &mut HtmlElement {
+        &mut self.superclass_html_element
+    }
+}
@@ -0,0 +1,49 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use html5ever::QualName;
+
+use crate::{
+    Element,
+    html::HtmlElement,
+};
+
+/// The [`<tr>`][spec] element, which represents a row of cells in a table.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/tables.html#the-tr-element
+#[derive(Debug)]
+pub struct HtmlTableRowElement {
+    superclass_html_element: HtmlElement,
+}
+
+impl HtmlTableRowElement {
+    pub fn new(qualified_name: QualName) -> Self {
+        Self {
+            superclass_html_element: HtmlElement::new(qualified_name),
+        }
+    }
+}
+
+impl AsRef<Element> for HtmlTableRowElement {
+    fn as_ref(&self) -> &Element {
+        self.superclass_html_element.as_ref()
+    }
+}
+
+impl AsMut<Element> for HtmlTableRowElement {
+    fn as_mut(&mut self) -> &mut Element {
+        self.superclass_html_element.as_mut()
+    }
+}
+
+impl AsRef<HtmlElement> for HtmlTableRowElement {
+    fn as_ref(&self) -> &HtmlElement {
+        &self.superclass_html_element
+    }
+}
+
+impl AsMut<HtmlElement> for HtmlTableRowElement {
+    fn as_mut(&mut self) -> &mut HtmlElement {
+        &mut self.superclass_html_element
+    }
+}
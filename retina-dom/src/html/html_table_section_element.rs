@@ -0,0 +1,52 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use html5ever::QualName;
+
+use crate::{
+    Element,
+    html::HtmlElement,
+};
+
+/// The [`<thead>`][thead], [`<tbody>`][tbody] and [`<tfoot>`][tfoot]
+/// elements, which represent a group of rows of a table.
+///
+/// [thead]: https://html.spec.whatwg.org/multipage/tables.html#the-thead-element
+/// [tbody]: https://html.spec.whatwg.org/multipage/tables.html#the-tbody-element
+/// [tfoot]: https://html.spec.whatwg.org/multipage/tables.html#the-tfoot-element
+#[derive(Debug)]
+pub struct HtmlTableSectionElement {
+    superclass_html_element: HtmlElement,
+}
+
+impl HtmlTableSectionElement {
+    pub fn new(qualified_name: QualName) -> Self {
+        Self {
+            superclass_html_element: HtmlElement::new(qualified_name),
+        }
+    }
+}
+
+impl AsRef<Element> for HtmlTableSectionElement {
+    fn as_ref(&self) -> &Element {
+        self.superclass_html_element.as_ref()
+    }
+}
+
+impl AsMut<Element> for HtmlTableSectionElement {
+    fn as_mut(&mut self) -> &mut Element {
+        self.superclass_html_element.as_mut()
+    }
+}
+
+impl AsRef<HtmlElement> for HtmlTableSectionElement {
+    fn as_ref(&self) -> &HtmlElement {
+        &self.superclass_html_element
+    }
+}
+
+impl AsMut<HtmlElement> for HtmlTableSectionElement {
+    fn as_mut(&mut self) -> &mut HtmlElement {
+        &mut self.superclass_html_element
+    }
+}
@@ -0,0 +1,111 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use strum::EnumString;
+
+/// The state of the [`type`][spec] attribute of an `<input>` element.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/input.html#attr-input-type
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Default, EnumString, strum::Display, strum::AsRefStr)]
+#[strum(serialize_all = "kebab-case", ascii_case_insensitive)]
+pub enum InputType {
+    Button,
+
+    Checkbox,
+
+    Color,
+
+    Date,
+
+    DatetimeLocal,
+
+    Email,
+
+    File,
+
+    Hidden,
+
+    Image,
+
+    Month,
+
+    Number,
+
+    Password,
+
+    Radio,
+
+    Range,
+
+    Reset,
+
+    Search,
+
+    Submit,
+
+    Tel,
+
+    /// The default state, which is also used for missing and invalid values.
+    #[default]
+    Text,
+
+    Time,
+
+    Url,
+
+    Week,
+}
+
+impl InputType {
+    /// Parse the value of the `type` attribute, where missing and invalid
+    /// values map to the [Text][`InputType::Text`] state.
+    pub fn parse(value: Option<&str>) -> Self {
+        value.and_then(|value| value.parse().ok()).unwrap_or_default()
+    }
+
+    /// Whether the input is a checkbox or a radio button, which is painted as
+    /// a box with a checkmark instead of a text field.
+    pub fn is_checkable(&self) -> bool {
+        matches!(self, Self::Checkbox | Self::Radio)
+    }
+
+    /// Whether the input is painted as a button.
+    pub fn is_button(&self) -> bool {
+        matches!(self, Self::Button | Self::Reset | Self::Submit)
+    }
+
+    /// Whether the [`pattern`][spec] attribute applies to inputs of this
+    /// type.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/input.html#attr-input-pattern
+    pub fn supports_pattern(&self) -> bool {
+        matches!(self, Self::Email | Self::Password | Self::Search | Self::Tel | Self::Text | Self::Url)
+    }
+
+    /// Whether the [`required`][spec] attribute applies to inputs of this
+    /// type.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/input.html#attr-input-required
+    pub fn supports_required(&self) -> bool {
+        !matches!(self,
+            Self::Button | Self::Color | Self::Hidden | Self::Image | Self::Range | Self::Reset | Self::Submit
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(InputType::parse(None), InputType::Text);
+        assert_eq!(InputType::parse(Some("")), InputType::Text);
+        assert_eq!(InputType::parse(Some("foo")), InputType::Text);
+        assert_eq!(InputType::parse(Some("checkbox")), InputType::Checkbox);
+        assert_eq!(InputType::parse(Some("CheckBox")), InputType::Checkbox);
+        assert_eq!(InputType::parse(Some("datetime-local")), InputType::DatetimeLocal);
+        assert_eq!(InputType::parse(Some("submit")), InputType::Submit);
+    }
+}
@@ -25,6 +25,20 @@ impl<'attribute> LinkRelationship<'attribute> {
         self.find(|ty| *ty == link_type).is_some()
     }
 
+    pub fn for_anchor_element(attribute_value: &'attribute str) -> Self {
+        Self {
+            tokens: attribute_value.split_ascii_whitespace(),
+            kind: Kind::Anchor,
+        }
+    }
+
+    pub fn for_form_element(attribute_value: &'attribute str) -> Self {
+        Self {
+            tokens: attribute_value.split_ascii_whitespace(),
+            kind: Kind::Form,
+        }
+    }
+
     pub fn for_link_element(attribute_value: &'attribute str) -> Self {
         Self {
             tokens: attribute_value.split_ascii_whitespace(),
@@ -36,10 +50,33 @@ impl<'attribute> LinkRelationship<'attribute> {
 impl<'attribute> Iterator for LinkRelationship<'attribute> {
     type Item = LinkType;
     fn next(&mut self) -> Option<Self::Item> {
-        if self.kind != Kind::Link {
-            todo!();
+        match self.kind {
+            Kind::Anchor | Kind::Area => self.tokens.find_map(LinkType::from_str_anchor_element),
+            Kind::Link => self.tokens.find_map(LinkType::from_str_link_element),
+            Kind::Form => self.tokens.find_map(LinkType::from_str_form_element),
         }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn form_element() {
+        let types: Vec<_> = LinkRelationship::for_form_element("noopener stylesheet  help bogus").collect();
+        assert_eq!(types, [LinkType::NoOpener, LinkType::Help]);
+
+        assert!(LinkRelationship::for_form_element("external nofollow").contains(LinkType::NoFollow));
+        assert!(!LinkRelationship::for_form_element("bookmark icon").contains(LinkType::Bookmark));
+    }
+
+    #[test]
+    fn anchor_and_link_elements() {
+        let types: Vec<_> = LinkRelationship::for_anchor_element("stylesheet bookmark").collect();
+        assert_eq!(types, [LinkType::Bookmark]);
 
-        self.tokens.find_map(LinkType::from_str_link_element)
+        let types: Vec<_> = LinkRelationship::for_link_element("stylesheet bookmark").collect();
+        assert_eq!(types, [LinkType::Stylesheet]);
     }
 }
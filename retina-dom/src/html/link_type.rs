@@ -67,10 +67,63 @@ impl LinkType {
         std::str::FromStr::from_str(s).ok()
     }
 
+    pub fn from_str_anchor_element(s: &str) -> Option<Self> {
+        Self::from_str(s).filter(Self::is_allowed_on_anchor_element)
+    }
+
+    pub fn from_str_form_element(s: &str) -> Option<Self> {
+        Self::from_str(s).filter(Self::is_allowed_on_form_element)
+    }
+
     pub fn from_str_link_element(s: &str) -> Option<Self> {
         Self::from_str(s).filter(|ty| ty.link_element_effect().is_some())
     }
 
+    /// Whether this [`LinkType`] is allowed on `<a>` and `<area>` HTML
+    /// elements.
+    ///
+    /// ## References
+    /// * [HTML Living Standard § 4.6.7.][spec]
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/links.html#linkTypes
+    pub fn is_allowed_on_anchor_element(&self) -> bool {
+        !matches!(
+            self,
+            Self::Canonical
+                | Self::DnsPrefetch
+                | Self::Icon
+                | Self::Manifest
+                | Self::ModulePreload
+                | Self::Pingback
+                | Self::Preconnect
+                | Self::Prefetch
+                | Self::Preload
+                | Self::Stylesheet
+        )
+    }
+
+    /// Whether this [`LinkType`] is allowed on `<form>` HTML elements.
+    ///
+    /// ## References
+    /// * [HTML Living Standard § 4.6.7.][spec]
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/links.html#linkTypes
+    pub fn is_allowed_on_form_element(&self) -> bool {
+        matches!(
+            self,
+            Self::External
+                | Self::Help
+                | Self::License
+                | Self::Next
+                | Self::NoFollow
+                | Self::NoOpener
+                | Self::NoReferrer
+                | Self::Opener
+                | Self::Prev
+                | Self::Search
+        )
+    }
+
     /// Gives the effect of this [`LinkType`] for that it has on a `<link>`
     /// HTML element.
     ///
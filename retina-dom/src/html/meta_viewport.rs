@@ -0,0 +1,108 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! Parsing of the `content` attribute of the [`<meta name="viewport">`][spec]
+//! element.
+//!
+//! [spec]: https://drafts.csswg.org/css-viewport/#viewport-meta
+
+/// The width of the viewport that is requested by the document.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum MetaViewportWidth {
+    /// `width=device-width`, i.e. the width of the window.
+    DeviceWidth,
+
+    /// A width in CSS pixels.
+    Pixels(f64),
+}
+
+/// The properties of the [`<meta name="viewport">`][spec] element.
+///
+/// [spec]: https://drafts.csswg.org/css-viewport/#viewport-meta
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct MetaViewport {
+    pub width: Option<MetaViewportWidth>,
+
+    /// The zoom factor of the page when it is loaded, clamped to `0.1..=10`.
+    /// This is parsed, but not applied, since pages can't be zoomed yet.
+    pub initial_scale: Option<f64>,
+}
+
+impl MetaViewport {
+    /// Parse the `content` attribute, e.g.
+    /// `width=device-width, initial-scale=1`. Unknown and invalid properties
+    /// are ignored.
+    pub fn parse(content: &str) -> Self {
+        let mut viewport = Self::default();
+
+        for property in content.split([',', ';']) {
+            let Some((name, value)) = property.split_once('=') else {
+                continue;
+            };
+
+            let name = name.trim();
+            let value = value.trim();
+
+            if name.eq_ignore_ascii_case("width") {
+                viewport.width = parse_width(value);
+            } else if name.eq_ignore_ascii_case("initial-scale") {
+                viewport.initial_scale = parse_number(value).map(|scale| scale.clamp(0.1, 10.0));
+            }
+        }
+
+        viewport
+    }
+
+    /// Resolve the width of the layout viewport, given the width of the
+    /// window.
+    pub fn layout_width(&self, window_width: f64) -> f64 {
+        match self.width {
+            Some(MetaViewportWidth::DeviceWidth) | None => window_width,
+            Some(MetaViewportWidth::Pixels(width)) => width,
+        }
+    }
+}
+
+fn parse_width(value: &str) -> Option<MetaViewportWidth> {
+    if value.eq_ignore_ascii_case("device-width") {
+        return Some(MetaViewportWidth::DeviceWidth);
+    }
+
+    parse_number(value)
+        .filter(|width| *width > 0.0)
+        .map(|width| MetaViewportWidth::Pixels(width.clamp(1.0, 10000.0)))
+}
+
+fn parse_number(value: &str) -> Option<f64> {
+    value.parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn device_width() {
+        let viewport = MetaViewport::parse("width=device-width, initial-scale=1");
+        assert_eq!(viewport.width, Some(MetaViewportWidth::DeviceWidth));
+        assert_eq!(viewport.initial_scale, Some(1.0));
+        assert_eq!(viewport.layout_width(412.0), 412.0);
+    }
+
+    #[test]
+    fn fixed_width() {
+        let viewport = MetaViewport::parse(" WIDTH = 980 ;user-scalable=no");
+        assert_eq!(viewport.width, Some(MetaViewportWidth::Pixels(980.0)));
+        assert_eq!(viewport.initial_scale, None);
+        assert_eq!(viewport.layout_width(412.0), 980.0);
+    }
+
+    #[test]
+    fn invalid() {
+        let viewport = MetaViewport::parse("width=wide, initial-scale, foo=bar");
+        assert_eq!(viewport, MetaViewport::default());
+        assert_eq!(viewport.layout_width(412.0), 412.0);
+    }
+}
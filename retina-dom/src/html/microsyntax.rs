@@ -0,0 +1,23 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! The [common microsyntaxes][spec] used by the attributes of HTML elements.
+//!
+//! [spec]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html
+
+/// The [rules for parsing non-negative integers][spec], which ignore
+/// everything after the leading digits.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#rules-for-parsing-non-negative-integers
+pub(crate) fn parse_non_negative_integer(input: &str) -> Option<usize> {
+    let input = input.trim_start_matches(|c: char| c.is_ascii_whitespace());
+    let input = input.strip_prefix('+').unwrap_or(input);
+
+    let end = input.find(|c: char| !c.is_ascii_digit()).unwrap_or(input.len());
+    if end == 0 {
+        return None;
+    }
+
+    // Values too large to be represented are clamped by the callers anyway.
+    Some(input[..end].parse().unwrap_or(usize::MAX))
+}
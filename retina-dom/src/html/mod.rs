@@ -1,12 +1,26 @@
 // Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
 // All Rights Reserved.
 
+pub mod html_anchor_element;
+pub mod html_button_element;
+pub mod html_canvas_element;
 pub mod html_element;
+pub mod html_form_element;
+pub mod html_head_element;
 pub mod html_img_element;
+pub mod html_input_element;
 pub mod html_link_element;
+pub mod html_select_element;
 pub mod html_style_element;
+pub mod html_table_cell_element;
+pub mod html_table_element;
+pub mod html_table_row_element;
+pub mod html_table_section_element;
 pub mod html_unknown_element;
+pub mod input_type;
 mod lazy_loading;
+pub mod meta_viewport;
+mod microsyntax;
 pub mod link_kind;
 pub mod link_relationship;
 pub mod link_type;
@@ -14,12 +28,25 @@ pub mod link_type;
 use html5ever::{LocalName, Namespace, QualName};
 use retina_common::DynamicSizeOf;
 pub use self::{
+    html_anchor_element::HtmlAnchorElement,
+    html_button_element::HtmlButtonElement,
+    html_canvas_element::HtmlCanvasElement,
     html_element::HtmlElement,
+    html_form_element::{FormEnctype, FormMethod, HtmlFormElement},
+    html_head_element::HtmlHeadElement,
     html_img_element::HtmlImgElement,
+    html_input_element::{HtmlInputElement, InputStep},
     html_link_element::HtmlLinkElement,
+    html_select_element::HtmlSelectElement,
     html_style_element::HtmlStyleElement,
+    html_table_cell_element::HtmlTableCellElement,
+    html_table_element::HtmlTableElement,
+    html_table_row_element::HtmlTableRowElement,
+    html_table_section_element::HtmlTableSectionElement,
     html_unknown_element::HtmlUnknownElement,
+    input_type::InputType,
     lazy_loading::LazyLoadingKind,
+    meta_viewport::{MetaViewport, MetaViewportWidth},
     link_kind::LinkKind,
     link_relationship::LinkRelationship,
     link_type::LinkType,
@@ -29,40 +56,140 @@ use crate::{Element, NodeInterface};
 
 #[derive(Debug)]
 pub enum HtmlElementKind {
+    Anchor(HtmlAnchorElement),
+    Button(HtmlButtonElement),
+    Canvas(HtmlCanvasElement),
+    Form(HtmlFormElement),
+    Head(HtmlHeadElement),
     Img(HtmlImgElement),
+    Input(HtmlInputElement),
     Link(HtmlLinkElement),
+    Select(HtmlSelectElement),
     Style(HtmlStyleElement),
+    Table(HtmlTableElement),
+    TableCell(HtmlTableCellElement),
+    TableRow(HtmlTableRowElement),
+    TableSection(HtmlTableSectionElement),
     Unknown(HtmlUnknownElement),
 }
 
 impl HtmlElementKind {
     pub fn as_dom_element(&self) -> &Element {
         match self {
+            Self::Anchor(element) => element.as_ref(),
+            Self::Button(element) => element.as_ref(),
+            Self::Canvas(element) => element.as_ref(),
+            Self::Form(element) => element.as_ref(),
+            Self::Head(element) => element.as_ref(),
             Self::Img(element) => element.as_ref(),
+            Self::Input(element) => element.as_ref(),
             Self::Link(element) => element.as_ref(),
+            Self::Select(element) => element.as_ref(),
             Self::Style(element) => element.as_ref(),
+            Self::Table(element) => element.as_ref(),
+            Self::TableCell(element) => element.as_ref(),
+            Self::TableRow(element) => element.as_ref(),
+            Self::TableSection(element) => element.as_ref(),
             Self::Unknown(element) => element.as_ref(),
         }
     }
 
     pub fn as_dom_element_mut(&mut self) -> &mut Element {
         match self {
+            Self::Anchor(element) => element.as_mut(),
+            Self::Button(element) => element.as_mut(),
+            Self::Canvas(element) => element.as_mut(),
+            Self::Form(element) => element.as_mut(),
+            Self::Head(element) => element.as_mut(),
             Self::Img(element) => element.as_mut(),
+            Self::Input(element) => element.as_mut(),
             Self::Link(element) => element.as_mut(),
+            Self::Select(element) => element.as_mut(),
             Self::Style(element) => element.as_mut(),
+            Self::Table(element) => element.as_mut(),
+            Self::TableCell(element) => element.as_mut(),
+            Self::TableRow(element) => element.as_mut(),
+            Self::TableSection(element) => element.as_mut(),
             Self::Unknown(element) => element.as_mut(),
         }
     }
 
     pub fn as_html_element(&self) -> &HtmlElement {
         match self {
+            Self::Anchor(element) => element.as_ref(),
+            Self::Button(element) => element.as_ref(),
+            Self::Canvas(element) => element.as_ref(),
+            Self::Form(element) => element.as_ref(),
+            Self::Head(element) => element.as_ref(),
             Self::Img(element) => element.as_ref(),
+            Self::Input(element) => element.as_ref(),
             Self::Link(element) => element.as_ref(),
+            Self::Select(element) => element.as_ref(),
             Self::Style(element) => element.as_ref(),
+            Self::Table(element) => element.as_ref(),
+            Self::TableCell(element) => element.as_ref(),
+            Self::TableRow(element) => element.as_ref(),
+            Self::TableSection(element) => element.as_ref(),
             Self::Unknown(element) => element.as_ref(),
         }
     }
 
+    pub fn as_anchor_element(&self) -> Option<&HtmlAnchorElement> {
+        if let Self::Anchor(element) = self {
+            Some(element)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_button_element(&self) -> Option<&HtmlButtonElement> {
+        if let Self::Button(element) = self {
+            Some(element)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_canvas_element(&self) -> Option<&HtmlCanvasElement> {
+        if let Self::Canvas(element) = self {
+            Some(element)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_form_element(&self) -> Option<&HtmlFormElement> {
+        if let Self::Form(element) = self {
+            Some(element)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_head_element(&self) -> Option<&HtmlHeadElement> {
+        if let Self::Head(element) = self {
+            Some(element)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_input_element(&self) -> Option<&HtmlInputElement> {
+        if let Self::Input(element) = self {
+            Some(element)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_select_element(&self) -> Option<&HtmlSelectElement> {
+        if let Self::Select(element) = self {
+            Some(element)
+        } else {
+            None
+        }
+    }
+
     pub fn as_style_element(&self) -> Option<&HtmlStyleElement> {
         if let Self::Style(element) = self {
             Some(element)
@@ -71,6 +198,14 @@ impl HtmlElementKind {
         }
     }
 
+    pub fn as_table_cell_element(&self) -> Option<&HtmlTableCellElement> {
+        if let Self::TableCell(element) = self {
+            Some(element)
+        } else {
+            None
+        }
+    }
+
     pub fn as_node(&self) -> &NodeInterface {
         self.as_dom_element().as_node()
     }
@@ -0,0 +1,198 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! The [`innerHTML`][spec] getter and setter of the `Element` interface.
+//!
+//! [spec]: https://html.spec.whatwg.org/multipage/dynamic-markup-insertion.html#dom-element-innerhtml
+
+use std::fmt::Write;
+
+use html5ever::{local_name, namespace_url, ns, LocalName};
+
+use crate::{Node, NodeKind, Parser};
+
+impl Node {
+    /// Serialize the children of this node to HTML, which is the value of the
+    /// `innerHTML` getter.
+    ///
+    /// [HTML Living Standard § 8.5.2 `innerHTML`](https://html.spec.whatwg.org/multipage/dynamic-markup-insertion.html#dom-element-innerhtml)
+    pub fn inner_html(&self) -> String {
+        let mut output = String::new();
+        serialize_children(self, &mut output);
+        output
+    }
+
+    /// Parse the `html` as a fragment in the context of this element, and
+    /// replace the children of this element with the result. This does
+    /// nothing for nodes that aren't elements.
+    ///
+    /// [HTML Living Standard § 8.5.2 `innerHTML`](https://html.spec.whatwg.org/multipage/dynamic-markup-insertion.html#dom-element-innerhtml)
+    pub fn set_inner_html(&self, html: &str) {
+        let Some(element) = self.as_dom_element() else {
+            return;
+        };
+
+        // 2. Let context be this.
+        // 3. Let fragment be the result of invoking the fragment parsing
+        //    algorithm steps with context and compliantString.
+        let fragment = Parser::parse_fragment(html, element.qualified_name().clone());
+
+        // 5. Replace all with fragment within context.
+        self.replace_all(fragment);
+    }
+}
+
+/// The [HTML fragment serialization algorithm][spec] for the children of the
+/// `node`.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/parsing.html#serialising-html-fragments
+fn serialize_children(node: &Node, output: &mut String) {
+    let Some(parent_node) = node.as_parent_node() else {
+        return;
+    };
+
+    // The contents of these elements aren't escaped, since they would
+    // otherwise be parsed differently.
+    let is_raw_text_parent = node.as_dom_element().is_some_and(|element| {
+        let name = &element.qualified_name();
+        name.ns == ns!(html) && matches!(
+            name.local,
+            local_name!("style") | local_name!("script") | local_name!("xmp") | local_name!("iframe")
+                | local_name!("noembed") | local_name!("noframes") | local_name!("plaintext")
+        )
+    });
+
+    for child in parent_node.children().iter() {
+        match child.as_ref() {
+            NodeKind::HtmlElement(element) => {
+                let element = element.as_dom_element();
+                let name = element.qualified_name();
+                let tag_name = &name.local;
+
+                output.push('<');
+                output.push_str(tag_name);
+
+                // The attributes are stored unordered, so they are sorted to
+                // make the result stable.
+                let mut attributes: Vec<_> = element.attributes().into_iter().collect();
+                attributes.sort_by(|a, b| a.0.cmp(b.0));
+                for (name, value) in attributes {
+                    _ = write!(output, " {}=\"", name);
+                    escape(value, true, output);
+                    output.push('"');
+                }
+
+                output.push('>');
+
+                // Void elements don't have contents, nor an end tag.
+                if name.ns == ns!(html) && is_void_element(tag_name) {
+                    continue;
+                }
+
+                serialize_children(child, output);
+
+                output.push_str("</");
+                output.push_str(tag_name);
+                output.push('>');
+            }
+
+            NodeKind::Text(text) => {
+                if is_raw_text_parent {
+                    output.push_str(text.data_as_str());
+                } else {
+                    escape(text.data_as_str(), false, output);
+                }
+            }
+
+            NodeKind::Comment(comment) => {
+                _ = write!(output, "<!--{}-->", comment.data_as_str());
+            }
+
+            NodeKind::ProcessingInstruction(processing_instruction) => {
+                _ = write!(output, "<?{} {}>", processing_instruction.target(), processing_instruction.data_as_str());
+            }
+
+            NodeKind::Document(..) => (),
+        }
+    }
+}
+
+/// [Escape a string][spec], in the attribute mode if `attribute_mode` is set.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/parsing.html#escapingString
+fn escape(input: &str, attribute_mode: bool, output: &mut String) {
+    for character in input.chars() {
+        match character {
+            '&' => output.push_str("&amp;"),
+            '\u{a0}' => output.push_str("&nbsp;"),
+            '"' if attribute_mode => output.push_str("&quot;"),
+            '<' if !attribute_mode => output.push_str("&lt;"),
+            '>' if !attribute_mode => output.push_str("&gt;"),
+            _ => output.push(character),
+        }
+    }
+}
+
+/// Whether the element is a [void element][spec], or one of the legacy
+/// elements that are serialized as such.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/syntax.html#void-elements
+fn is_void_element(name: &LocalName) -> bool {
+    matches!(
+        *name,
+        local_name!("area") | local_name!("base") | local_name!("basefont") | local_name!("bgsound")
+            | local_name!("br") | local_name!("col") | local_name!("embed") | local_name!("frame")
+            | local_name!("hr") | local_name!("img") | local_name!("input") | local_name!("keygen")
+            | local_name!("link") | local_name!("meta") | local_name!("param") | local_name!("source")
+            | local_name!("track") | local_name!("wbr")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Parser;
+
+    fn element(html: &str) -> crate::Node {
+        let document = Parser::parse(html);
+        document.as_document().unwrap().get_element_by_id("target").unwrap()
+    }
+
+    #[test]
+    fn serialize() {
+        let target = element(concat!(
+            r#"<div id="target">"#,
+            r#"<p class="a" data-x='"&amp;'>1 &lt; 2 &amp;&nbsp;3</p>"#,
+            r#"<br><img src="x.png"><!-- comment -->"#,
+            r#"<style>a > b { content: "&"; }</style>"#,
+            r#"<svg><![CDATA[x < y]]></svg>"#,
+            r#"</div>"#,
+        ));
+
+        assert_eq!(target.inner_html(), concat!(
+            r#"<p class="a" data-x="&quot;&amp;">1 &lt; 2 &amp;&nbsp;3</p>"#,
+            r#"<br><img src="x.png"><!-- comment -->"#,
+            r#"<style>a > b { content: "&"; }</style>"#,
+            r#"<svg>x &lt; y</svg>"#,
+        ));
+    }
+
+    #[test]
+    fn set() {
+        let target = element(r#"<table><tbody><tr id="target"><td>old</td></tr></tbody></table>"#);
+
+        // The fragment is parsed in the context of the element, so the table
+        // cell isn't dropped, and no <html>, <head> or <body> is created.
+        target.set_inner_html("<td>a</td><td>b<br></td>");
+        assert_eq!(target.inner_html(), "<td>a</td><td>b<br></td>");
+
+        let children = target.as_parent_node().unwrap().children().clone();
+        assert_eq!(children.len(), 2);
+        for child in &children {
+            assert!(crate::Node::ptr_eq(&child.parent_node().unwrap(), &target));
+        }
+
+        target.set_inner_html("");
+        assert_eq!(target.inner_html(), "");
+        assert!(children[0].parent_node().is_none());
+    }
+}
@@ -0,0 +1,184 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! The [`insertAdjacentHTML()`][spec] method of the `Element` interface.
+//!
+//! [spec]: https://html.spec.whatwg.org/multipage/dynamic-markup-insertion.html#dom-element-insertadjacenthtml
+
+use html5ever::{local_name, namespace_url, ns, QualName};
+use strum::EnumString;
+
+use crate::{Node, Parser};
+
+/// The position relative to the element to insert the nodes at.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(EnumString, strum::Display, strum::AsRefStr)]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum InsertAdjacentPosition {
+    /// Before the element itself.
+    BeforeBegin,
+
+    /// Just inside the element, before its first child.
+    AfterBegin,
+
+    /// Just inside the element, after its last child.
+    BeforeEnd,
+
+    /// After the element itself.
+    AfterEnd,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum InsertAdjacentError {
+    /// The position isn't one of the [`InsertAdjacentPosition`] values, which
+    /// corresponds to a `SyntaxError` `DOMException`.
+    InvalidPosition,
+
+    /// The nodes should be inserted next to the element, but it doesn't have
+    /// a parent element, which corresponds to a `NoModificationAllowedError`
+    /// `DOMException`.
+    NoParentElement,
+
+    /// The method was invoked on a node that isn't an element.
+    NotAnElement,
+}
+
+impl Node {
+    /// Parse the `html` as a fragment and insert the resulting nodes at the
+    /// `position` relative to this element.
+    ///
+    /// [HTML Living Standard § 8.5.3 `insertAdjacentHTML()`](https://html.spec.whatwg.org/multipage/dynamic-markup-insertion.html#dom-element-insertadjacenthtml)
+    pub fn insert_adjacent_html(&self, position: &str, html: &str) -> Result<(), InsertAdjacentError> {
+        if !self.is_element() {
+            return Err(InsertAdjacentError::NotAnElement);
+        }
+
+        let position: InsertAdjacentPosition = position.parse()
+            .map_err(|_| InsertAdjacentError::InvalidPosition)?;
+
+        // 1. Let context be null.
+        // 2. Use the first matching item from this list:
+        let context = match position {
+            InsertAdjacentPosition::BeforeBegin | InsertAdjacentPosition::AfterEnd => {
+                // 1. Set context to this's parent.
+                // 2. If context is null or a Document, throw a
+                //    "NoModificationAllowedError" DOMException.
+                self.parent_node()
+                    .filter(|parent| !parent.is_document())
+                    .ok_or(InsertAdjacentError::NoParentElement)?
+            }
+
+            InsertAdjacentPosition::AfterBegin | InsertAdjacentPosition::BeforeEnd => self.clone(),
+        };
+
+        // 3. If context is not an Element or all of the following are true:
+        //    * context's node document is an HTML document;
+        //    * context's local name is "html"; and
+        //    * context's namespace is the HTML namespace,
+        //    then set context to the result of creating an element given this's
+        //    node document, body, and the HTML namespace.
+        let context_name = match context.as_dom_element() {
+            Some(element) if element.qualified_name().local != local_name!("html") => {
+                element.qualified_name().clone()
+            }
+            _ => QualName::new(None, ns!(html), local_name!("body")),
+        };
+
+        // 4. Let fragment be the result of invoking the fragment parsing
+        //    algorithm steps with context and string.
+        let fragment = Parser::parse_fragment(html, context_name);
+
+        // 5. Use the first matching item from this list:
+        match position {
+            // Insert fragment into this's parent before this.
+            InsertAdjacentPosition::BeforeBegin => {
+                for node in fragment {
+                    let index = self.index_in_parent().unwrap();
+                    context.insert_child(index, node);
+                }
+            }
+
+            // Insert fragment into this before its first child.
+            InsertAdjacentPosition::AfterBegin => {
+                for (index, node) in fragment.into_iter().enumerate() {
+                    self.insert_child(index, node);
+                }
+            }
+
+            // Append fragment to this.
+            InsertAdjacentPosition::BeforeEnd => {
+                for node in fragment {
+                    self.append_child(node);
+                }
+            }
+
+            // Insert fragment into this's parent before this's next sibling.
+            InsertAdjacentPosition::AfterEnd => {
+                let next_sibling_index = self.index_in_parent().unwrap() + 1;
+                for (offset, node) in fragment.into_iter().enumerate() {
+                    context.insert_child(next_sibling_index + offset, node);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find_by_id(document: &Node, id: &str) -> Node {
+        let mut found = None;
+        document.for_each_child_node_recursive_handle(&mut |node| {
+            if node.as_dom_element().is_some_and(|element| element.id() == id) {
+                found = Some(node.clone());
+            }
+        });
+        found.unwrap()
+    }
+
+    fn tag_names(node: &Node) -> Vec<String> {
+        node.as_parent_node()
+            .unwrap()
+            .children()
+            .iter()
+            .filter_map(|child| child.tag_name().map(ToString::to_string))
+            .collect()
+    }
+
+    #[test]
+    fn insert_at_all_positions() {
+        let document = Parser::parse("<div id=parent><p id=target><span></span></p></div>");
+        let parent = find_by_id(&document, "parent");
+        let target = find_by_id(&document, "target");
+
+        target.insert_adjacent_html("beforebegin", "<h1></h1>").unwrap();
+        target.insert_adjacent_html("AfterBegin", "<b></b><i></i>").unwrap();
+        target.insert_adjacent_html("beforeend", "<u></u>").unwrap();
+        target.insert_adjacent_html("afterend", "<h2></h2><h3></h3>").unwrap();
+
+        assert_eq!(tag_names(&parent), ["h1", "p", "h2", "h3"]);
+        assert_eq!(tag_names(&target), ["b", "i", "span", "u"]);
+
+        let inserted = parent.as_parent_node().unwrap().children()[0].clone();
+        assert!(inserted.parent_node().is_some_and(|node| node == parent));
+    }
+
+    #[test]
+    fn invalid_position() {
+        let document = Parser::parse("<p id=target></p>");
+        let target = find_by_id(&document, "target");
+
+        assert_eq!(target.insert_adjacent_html("middle", "<b></b>"), Err(InsertAdjacentError::InvalidPosition));
+    }
+
+    #[test]
+    fn next_to_root_element() {
+        let document = Parser::parse("<p></p>");
+        let html = document.as_parent_node().unwrap().children()[0].clone();
+
+        assert_eq!(html.insert_adjacent_html("afterend", "<b></b>"), Err(InsertAdjacentError::NoParentElement));
+    }
+}
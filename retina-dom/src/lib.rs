@@ -7,7 +7,9 @@
 //! * [DOM - Living Standard - WHATWG](https://dom.spec.whatwg.org/)
 
 pub mod attribute;
+pub mod canvas;
 pub mod character_data;
+pub mod clone_node;
 pub mod comment;
 pub mod data;
 pub mod document;
@@ -15,26 +17,42 @@ pub mod element;
 pub mod element_kind;
 pub mod event;
 pub mod html;
+pub mod inner_html;
+pub mod insert_adjacent;
+pub mod mutation;
+pub mod mutation_observer;
 pub mod node;
 pub mod parent_node;
 pub mod parse;
 pub mod platform_messenger;
+pub mod processing_instruction;
 pub mod text;
 pub mod wrapper;
 
 use std::{ops::Deref, sync::{Arc, Weak}};
 
 pub use attribute::AttributeList;
+pub use canvas::*;
 pub use character_data::CharacterData;
 pub use comment::Comment;
 pub use data::*;
 pub use document::Document;
 pub use element::Element;
 pub use html::*;
+pub use insert_adjacent::{InsertAdjacentError, InsertAdjacentPosition};
+pub use mutation_observer::{
+    Mutation,
+    MutationObserver,
+    MutationObserverError,
+    MutationObserverInit,
+    MutationRecord,
+    MutationRecordType,
+};
 pub use node::NodeInterface;
 pub use parent_node::ParentNode;
 pub use parse::Parser;
 pub use platform_messenger::{PlatformMessage, PlatformMessenger};
+pub use processing_instruction::ProcessingInstruction;
 pub use text::Text;
 pub use wrapper::*;
 
@@ -102,6 +120,7 @@ pub enum NodeKind {
     Comment(Comment),
     Document(Document),
     HtmlElement(HtmlElementKind),
+    ProcessingInstruction(ProcessingInstruction),
     Text(Text),
 }
 
@@ -159,6 +178,7 @@ impl NodeKind {
             Self::Comment(comment) => comment.as_node(),
             Self::Document(doc) => doc.as_node(),
             Self::HtmlElement(element) => element.as_node(),
+            Self::ProcessingInstruction(instruction) => instruction.as_node(),
             Self::Text(text) => text.as_node(),
         }
     }
@@ -168,6 +188,7 @@ impl NodeKind {
             Self::Comment(comment) => comment.as_node_mut(),
             Self::Document(doc) => doc.as_node_mut(),
             Self::HtmlElement(element) => element.as_node_mut(),
+            Self::ProcessingInstruction(instruction) => instruction.as_node_mut(),
             Self::Text(text) => text.as_node_mut(),
         }
     }
@@ -177,6 +198,7 @@ impl NodeKind {
             Self::Comment(..) => None,
             Self::Document(doc) => Some(doc.as_parent_node()),
             Self::HtmlElement(element) => Some(element.as_dom_element().as_parent_node()),
+            Self::ProcessingInstruction(..) => None,
             Self::Text(..) => None,
         }
     }
@@ -186,10 +208,19 @@ impl NodeKind {
             Self::Comment(..) => None,
             Self::Document(doc) => Some(doc.as_parent_node_mut()),
             Self::HtmlElement(element) => Some(element.as_dom_element_mut().as_parent_node_mut()),
+            Self::ProcessingInstruction(..) => None,
             Self::Text(..) => None,
         }
     }
 
+    pub fn as_processing_instruction(&self) -> Option<&ProcessingInstruction> {
+        if let Self::ProcessingInstruction(instruction) = self {
+            Some(instruction)
+        } else {
+            None
+        }
+    }
+
     pub fn as_text(&self) -> Option<&Text> {
         match self {
             Self::Text(text) => Some(text),
@@ -267,6 +298,7 @@ impl DynamicSizeOf for NodeKind {
             Self::Comment(value) => value.dynamic_size_of(),
             Self::Document(value) => value.dynamic_size_of(),
             Self::HtmlElement(element) => element.dynamic_size_of(),
+            Self::ProcessingInstruction(value) => value.dynamic_size_of(),
             Self::Text(value) => value.dynamic_size_of(),
         }
     }
@@ -299,6 +331,9 @@ impl DumpableNode for NodeKind {
                     write!(writer, ".{class}")?;
                 }
             }
+            Self::ProcessingInstruction(instruction) => {
+                write!(writer, "<?{} {}?>", instruction.target(), instruction.data_as_str())?;
+            }
             Self::Text(text) => {
                 let text = text.data_as_str().replace('\n', "\\n");
                 write!(writer, "\"{}\"", text)?
@@ -327,6 +362,7 @@ impl<'node_kind> core::fmt::Debug for ShortDumpable<'node_kind> {
             NodeKind::Comment(..) => f.write_str("#comment"),
             NodeKind::Document(..) => f.write_str("#document"),
             NodeKind::HtmlElement(..) => f.write_fmt(format_args!("<{}>", self.node_kind.tag_name().unwrap_or("element?"))),
+            NodeKind::ProcessingInstruction(instruction) => f.write_str(instruction.target()),
             NodeKind::Text(..) => f.write_str("#text"),
         }
     }
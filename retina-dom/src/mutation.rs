@@ -0,0 +1,119 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! Tree mutation primitives, used by the [mutation algorithms][spec] of the
+//! `Node` and `ParentNode` interfaces.
+//!
+//! [spec]: https://dom.spec.whatwg.org/#mutation-algorithms
+
+use std::sync::Weak;
+
+use crate::{Mutation, Node};
+
+impl Node {
+    /// Get the parent of this node, if it is attached to one.
+    pub fn parent_node(&self) -> Option<Node> {
+        self.as_node()
+            .parent()
+            .as_ref()
+            .and_then(Weak::upgrade)
+            .map(Node::from)
+    }
+
+    /// Get the position of this node in the children of its parent.
+    pub fn index_in_parent(&self) -> Option<usize> {
+        let parent = self.parent_node()?;
+        let index = parent.as_parent_node()?
+            .children()
+            .iter()
+            .position(|child| Node::ptr_eq(child, self));
+        index
+    }
+
+    /// Remove this node from the children of its parent, if it has one. The
+    /// observers of the parent are notified with a `childList` record.
+    pub fn remove_from_parent(&self) {
+        let Some(parent) = self.parent_node() else {
+            return;
+        };
+
+        let mut mutation = Mutation::child_list(&parent);
+        if let Some(parent_node) = parent.as_parent_node() {
+            let mut children = parent_node.children_mut();
+            if let Some(index) = children.iter().position(|child| Node::ptr_eq(child, self)) {
+                mutation.previous_sibling = index.checked_sub(1).map(|index| children[index].clone());
+                mutation.next_sibling = children.get(index + 1).cloned();
+                mutation.removed_nodes.push(children.remove(index));
+            }
+        }
+
+        self.as_node().set_parent(None);
+
+        if !mutation.removed_nodes.is_empty() {
+            mutation.queue();
+        }
+    }
+
+    /// Insert the `child` into the children of this node, such that it ends up
+    /// at `index`. The `child` is removed from its previous parent first.
+    ///
+    /// # Panics
+    /// This function panics when this node can't have children, or when the
+    /// `index` is larger than the number of children.
+    pub fn insert_child(&self, index: usize, child: Node) {
+        let parent_node = self.as_parent_node()
+            .expect("insert_child() called on a node that can't have children");
+
+        child.remove_from_parent();
+        child.as_node().set_parent(Some(Node::downgrade(self)));
+
+        let mut mutation = Mutation::child_list(self);
+        {
+            let mut children = parent_node.children_mut();
+            mutation.previous_sibling = index.checked_sub(1).map(|index| children[index].clone());
+            mutation.next_sibling = children.get(index).cloned();
+            children.insert(index, child.clone());
+        }
+
+        mutation.added_nodes.push(child);
+        mutation.queue();
+    }
+
+    /// [Replace all][spec] children of this node with the `nodes`, which are
+    /// removed from their previous parents first. The observers of this node
+    /// are notified with a single `childList` record.
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#concept-node-replace-all
+    ///
+    /// # Panics
+    /// This function panics when this node can't have children.
+    pub fn replace_all(&self, nodes: Vec<Node>) {
+        let parent_node = self.as_parent_node()
+            .expect("replace_all() called on a node that can't have children");
+
+        for node in &nodes {
+            node.remove_from_parent();
+            node.as_node().set_parent(Some(Node::downgrade(self)));
+        }
+
+        let removed_nodes = std::mem::replace(&mut *parent_node.children_mut(), nodes.clone());
+        for node in &removed_nodes {
+            node.as_node().set_parent(None);
+        }
+
+        if removed_nodes.is_empty() && nodes.is_empty() {
+            return;
+        }
+
+        let mut mutation = Mutation::child_list(self);
+        mutation.added_nodes = nodes;
+        mutation.removed_nodes = removed_nodes;
+        mutation.queue();
+    }
+
+    /// Insert the `child` as the last child of this node.
+    pub fn append_child(&self, child: Node) {
+        let index = self.children_count();
+        self.insert_child(index, child);
+    }
+}
@@ -0,0 +1,506 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! The [Interface `MutationObserver`][spec] implementation, which records the
+//! mutations of the observed nodes, so they can be delivered to the callback
+//! of the observer in a microtask.
+//!
+//! [spec]: https://dom.spec.whatwg.org/#interface-mutationobserver
+
+use std::sync::{Arc, Mutex, Weak};
+
+use crate::{
+    event::queue::EventQueue,
+    Node,
+    NodeKind,
+};
+
+/// The [`type`][spec] of a [`MutationRecord`].
+///
+/// [spec]: https://dom.spec.whatwg.org/#dom-mutationrecord-type
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(strum::Display, strum::AsRefStr)]
+pub enum MutationRecordType {
+    #[strum(serialize = "attributes")]
+    Attributes,
+
+    #[strum(serialize = "characterData")]
+    CharacterData,
+
+    #[strum(serialize = "childList")]
+    ChildList,
+}
+
+/// The [Interface `MutationRecord`][spec], which describes a single mutation
+/// of a node.
+///
+/// [spec]: https://dom.spec.whatwg.org/#interface-mutationrecord
+#[derive(Clone, Debug)]
+pub struct MutationRecord {
+    pub record_type: MutationRecordType,
+    pub target: Node,
+    pub added_nodes: Vec<Node>,
+    pub removed_nodes: Vec<Node>,
+    pub previous_sibling: Option<Node>,
+    pub next_sibling: Option<Node>,
+    pub attribute_name: Option<String>,
+    pub old_value: Option<String>,
+}
+
+/// The [Dictionary `MutationObserverInit`][spec], which are the options that
+/// are passed to `observe()`. The fields that are [`None`] were omitted.
+///
+/// [spec]: https://dom.spec.whatwg.org/#dictdef-mutationobserverinit
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MutationObserverInit {
+    pub child_list: bool,
+    pub attributes: Option<bool>,
+    pub character_data: Option<bool>,
+    pub subtree: bool,
+    pub attribute_old_value: Option<bool>,
+    pub character_data_old_value: Option<bool>,
+    pub attribute_filter: Option<Vec<String>>,
+}
+
+impl MutationObserverInit {
+    fn attributes(&self) -> bool {
+        self.attributes.unwrap_or(false)
+    }
+
+    fn character_data(&self) -> bool {
+        self.character_data.unwrap_or(false)
+    }
+}
+
+/// The reasons `observe()` throws a `TypeError`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MutationObserverError {
+    /// None of `childList`, `attributes` and `characterData` is true.
+    NothingToObserve,
+
+    /// `attributeOldValue` is true, but `attributes` is false.
+    AttributeOldValueWithoutAttributes,
+
+    /// `attributeFilter` is present, but `attributes` is false.
+    AttributeFilterWithoutAttributes,
+
+    /// `characterDataOldValue` is true, but `characterData` is false.
+    CharacterDataOldValueWithoutCharacterData,
+}
+
+impl std::fmt::Display for MutationObserverError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::NothingToObserve => "The options must set at least one of `childList`, `attributes` and `characterData` to true",
+            Self::AttributeOldValueWithoutAttributes => "The option `attributeOldValue` requires `attributes` to be true",
+            Self::AttributeFilterWithoutAttributes => "The option `attributeFilter` requires `attributes` to be true",
+            Self::CharacterDataOldValueWithoutCharacterData => "The option `characterDataOldValue` requires `characterData` to be true",
+        })
+    }
+}
+
+/// A [registered observer][spec] in the list of a node.
+///
+/// [spec]: https://dom.spec.whatwg.org/#registered-observer
+#[derive(Clone, Debug)]
+pub struct RegisteredObserver {
+    pub observer: MutationObserver,
+    pub options: MutationObserverInit,
+}
+
+/// The [Interface `MutationObserver`][spec]. The callback isn't stored here,
+/// but by the scripting engine, which is notified of the records using the
+/// [`EventQueue`].
+///
+/// [spec]: https://dom.spec.whatwg.org/#interface-mutationobserver
+#[derive(Clone, Debug)]
+pub struct MutationObserver {
+    inner: Arc<MutationObserverInner>,
+}
+
+#[derive(Debug)]
+struct MutationObserverInner {
+    event_queue: EventQueue,
+
+    /// The [node list](https://dom.spec.whatwg.org/#mutationobserver-node-list),
+    /// which are the nodes this observer is registered at.
+    node_list: Mutex<Vec<Weak<NodeKind>>>,
+
+    /// The [record queue](https://dom.spec.whatwg.org/#concept-mo-queue).
+    record_queue: Mutex<Vec<MutationRecord>>,
+}
+
+impl MutationObserver {
+    pub fn new(event_queue: EventQueue) -> Self {
+        Self {
+            inner: Arc::new(MutationObserverInner {
+                event_queue,
+                node_list: Mutex::new(Vec::new()),
+                record_queue: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Arc::ptr_eq(&this.inner, &other.inner)
+    }
+
+    /// [DOM Living Standard § 4.3.1 `observe()`](https://dom.spec.whatwg.org/#dom-mutationobserver-observe)
+    pub fn observe(&self, target: &Node, mut options: MutationObserverInit) -> Result<(), MutationObserverError> {
+        // 1. If either options["attributeOldValue"] or options["attributeFilter"]
+        //    exists, and options["attributes"] does not exist, then set
+        //    options["attributes"] to true.
+        if (options.attribute_old_value.is_some() || options.attribute_filter.is_some()) && options.attributes.is_none() {
+            options.attributes = Some(true);
+        }
+
+        // 2. If options["characterDataOldValue"] exists and
+        //    options["characterData"] does not exist, then set
+        //    options["characterData"] to true.
+        if options.character_data_old_value.is_some() && options.character_data.is_none() {
+            options.character_data = Some(true);
+        }
+
+        // 3. If none of options["childList"], options["attributes"], and
+        //    options["characterData"] is true, then throw a TypeError.
+        if !options.child_list && !options.attributes() && !options.character_data() {
+            return Err(MutationObserverError::NothingToObserve);
+        }
+
+        // 4. If options["attributeOldValue"] is true and options["attributes"]
+        //    is false, then throw a TypeError.
+        if options.attribute_old_value == Some(true) && !options.attributes() {
+            return Err(MutationObserverError::AttributeOldValueWithoutAttributes);
+        }
+
+        // 5. If options["attributeFilter"] is present and
+        //    options["attributes"] is false, then throw a TypeError.
+        if options.attribute_filter.is_some() && !options.attributes() {
+            return Err(MutationObserverError::AttributeFilterWithoutAttributes);
+        }
+
+        // 6. If options["characterDataOldValue"] is true and
+        //    options["characterData"] is false, then throw a TypeError.
+        if options.character_data_old_value == Some(true) && !options.character_data() {
+            return Err(MutationObserverError::CharacterDataOldValueWithoutCharacterData);
+        }
+
+        let mut registered_observers = target.as_node().registered_observers_mut();
+
+        // 7. For each registered of target’s registered observer list, if
+        //    registered’s observer is this:
+        if let Some(registered) = registered_observers.iter_mut().find(|registered| Self::ptr_eq(&registered.observer, self)) {
+            // 2. Set registered’s options to options.
+            registered.options = options;
+            return Ok(());
+        }
+
+        // 8. Otherwise:
+        // 1. Append a new registered observer whose observer is this and
+        //    options is options to target’s registered observer list.
+        registered_observers.push(RegisteredObserver {
+            observer: self.clone(),
+            options,
+        });
+
+        // 2. Append a weak reference to target to this’s node list.
+        self.inner.node_list.lock().unwrap().push(Node::downgrade(target));
+
+        Ok(())
+    }
+
+    /// [DOM Living Standard § 4.3.1 `disconnect()`](https://dom.spec.whatwg.org/#dom-mutationobserver-disconnect)
+    pub fn disconnect(&self) {
+        // 1. For each node of this’s node list, remove any registered observer
+        //    from node’s registered observer list for which this is the
+        //    observer.
+        for node in self.inner.node_list.lock().unwrap().drain(..) {
+            let Some(node) = node.upgrade().map(Node::from) else { continue };
+            node.as_node()
+                .registered_observers_mut()
+                .retain(|registered| !Self::ptr_eq(&registered.observer, self));
+        }
+
+        // 2. Empty this’s record queue.
+        self.inner.record_queue.lock().unwrap().clear();
+    }
+
+    /// [DOM Living Standard § 4.3.1 `takeRecords()`](https://dom.spec.whatwg.org/#dom-mutationobserver-takerecords)
+    pub fn take_records(&self) -> Vec<MutationRecord> {
+        std::mem::take(&mut *self.inner.record_queue.lock().unwrap())
+    }
+
+    fn enqueue_record(&self, record: MutationRecord) {
+        self.inner.record_queue.lock().unwrap().push(record);
+        self.inner.event_queue.queue_mutation_observer_microtask();
+    }
+}
+
+/// The description of a mutation, from which the [`MutationRecord`]s are
+/// created for the interested observers.
+#[derive(Clone, Debug)]
+pub struct Mutation<'a> {
+    pub record_type: MutationRecordType,
+    pub target: &'a Node,
+    pub attribute_name: Option<&'a str>,
+    pub old_value: Option<&'a str>,
+    pub added_nodes: Vec<Node>,
+    pub removed_nodes: Vec<Node>,
+    pub previous_sibling: Option<Node>,
+    pub next_sibling: Option<Node>,
+}
+
+impl<'a> Mutation<'a> {
+    /// A `childList` mutation of the `target`.
+    pub fn child_list(target: &'a Node) -> Self {
+        Self {
+            record_type: MutationRecordType::ChildList,
+            target,
+            attribute_name: None,
+            old_value: None,
+            added_nodes: Vec::new(),
+            removed_nodes: Vec::new(),
+            previous_sibling: None,
+            next_sibling: None,
+        }
+    }
+
+    /// An `attributes` mutation of the attribute called `name` of the
+    /// `target`.
+    pub fn attributes(target: &'a Node, name: &'a str, old_value: Option<&'a str>) -> Self {
+        Self {
+            record_type: MutationRecordType::Attributes,
+            attribute_name: Some(name),
+            old_value,
+            ..Self::child_list(target)
+        }
+    }
+
+    /// A `characterData` mutation of the `target`.
+    pub fn character_data(target: &'a Node, old_value: &'a str) -> Self {
+        Self {
+            record_type: MutationRecordType::CharacterData,
+            old_value: Some(old_value),
+            ..Self::child_list(target)
+        }
+    }
+
+    /// [Queue a mutation record][spec] of this mutation for the observers of
+    /// the target and its ancestors that are interested in it.
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#queue-a-mutation-record
+    pub fn queue(self) {
+        // 1. Let interestedObservers be an empty map.
+        let mut interested_observers: Vec<(MutationObserver, Option<&str>)> = Vec::new();
+
+        // 2. Let nodes be the inclusive ancestors of target.
+        let mut node = Some(self.target.clone());
+
+        // 3. For each node in nodes, and then for each registered of node’s
+        //    registered observer list:
+        while let Some(current) = node {
+            for registered in current.as_node().registered_observers().iter() {
+                // 1. Let options be registered’s options.
+                let options = &registered.options;
+
+                // 2. If none of the following are true
+                let uninterested =
+                    // node is not target and options["subtree"] is false
+                    (!Node::ptr_eq(&current, self.target) && !options.subtree)
+                    // type is "attributes" and options["attributes"] either
+                    // does not exist or is false
+                    || (self.record_type == MutationRecordType::Attributes && !options.attributes())
+                    // type is "attributes", options["attributeFilter"]
+                    // exists, and options["attributeFilter"] does not contain
+                    // name or namespace is non-null
+                    || (self.record_type == MutationRecordType::Attributes && options.attribute_filter.as_ref()
+                        .is_some_and(|filter| !filter.iter().any(|name| Some(name.as_str()) == self.attribute_name)))
+                    // type is "characterData" and options["characterData"]
+                    // either does not exist or is false
+                    || (self.record_type == MutationRecordType::CharacterData && !options.character_data())
+                    // type is "childList" and options["childList"] is false
+                    || (self.record_type == MutationRecordType::ChildList && !options.child_list);
+
+                if uninterested {
+                    continue;
+                }
+
+                // then:
+                // 1. Let mo be registered’s observer.
+                // 2. If interestedObservers[mo] does not exist, then set
+                //    interestedObservers[mo] to null.
+                let index = match interested_observers.iter().position(|(observer, _)| MutationObserver::ptr_eq(observer, &registered.observer)) {
+                    Some(index) => index,
+                    None => {
+                        interested_observers.push((registered.observer.clone(), None));
+                        interested_observers.len() - 1
+                    }
+                };
+
+                // 3. If either type is "attributes" and
+                //    options["attributeOldValue"] is true, or type is
+                //    "characterData" and options["characterDataOldValue"] is
+                //    true, then set interestedObservers[mo] to oldValue.
+                if (self.record_type == MutationRecordType::Attributes && options.attribute_old_value == Some(true))
+                    || (self.record_type == MutationRecordType::CharacterData && options.character_data_old_value == Some(true)) {
+                    interested_observers[index].1 = self.old_value;
+                }
+            }
+
+            node = current.parent_node();
+        }
+
+        // 4. For each observer → mappedOldValue of interestedObservers:
+        for (observer, mapped_old_value) in interested_observers {
+            // 1. Let record be a new MutationRecord object with its type set
+            //    to type, target set to target, attributeName set to name,
+            //    attributeNamespace set to namespace, oldValue set to
+            //    mappedOldValue, addedNodes set to addedNodes, removedNodes
+            //    set to removedNodes, previousSibling set to previousSibling,
+            //    and nextSibling set to nextSibling.
+            let record = MutationRecord {
+                record_type: self.record_type,
+                target: self.target.clone(),
+                added_nodes: self.added_nodes.clone(),
+                removed_nodes: self.removed_nodes.clone(),
+                previous_sibling: self.previous_sibling.clone(),
+                next_sibling: self.next_sibling.clone(),
+                attribute_name: self.attribute_name.map(String::from),
+                old_value: mapped_old_value.map(String::from),
+            };
+
+            // 2. Enqueue record to observer’s record queue.
+            // 3. Queue a mutation observer microtask.
+            observer.enqueue_record(record);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{event::queue::{EventQueue, Microtask}, Parser};
+
+    use super::*;
+
+    fn first_child(node: &Node) -> Node {
+        node.as_parent_node().unwrap().children()[0].clone()
+    }
+
+    fn observe(html: &str, options: MutationObserverInit) -> (Node, MutationObserver, EventQueue) {
+        let document = Parser::parse(html);
+        let target = document.as_document().unwrap().get_element_by_id("target").unwrap();
+
+        let event_queue = EventQueue::new();
+        let observer = MutationObserver::new(event_queue.clone());
+        observer.observe(&target, options).unwrap();
+        (target, observer, event_queue)
+    }
+
+    #[test]
+    fn child_list() {
+        let (target, observer, event_queue) = observe(r#"<div id="target"><p id="a"></p><p id="b"></p></div>"#, MutationObserverInit {
+            child_list: true,
+            ..Default::default()
+        });
+
+        let a = first_child(&target);
+        a.remove_from_parent();
+        target.append_child(a.clone());
+
+        // Only one microtask is queued for both records.
+        assert_eq!(event_queue.pop_microtask(), Some(Microtask::NotifyMutationObservers));
+        assert_eq!(event_queue.pop_microtask(), None);
+
+        let records = observer.take_records();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].record_type, MutationRecordType::ChildList);
+        assert!(Node::ptr_eq(&records[0].target, &target));
+        assert!(Node::ptr_eq(&records[0].removed_nodes[0], &a));
+        assert!(records[0].previous_sibling.is_none());
+        assert_eq!(records[0].next_sibling.as_ref().unwrap().as_dom_element().unwrap().id(), "b");
+
+        assert!(Node::ptr_eq(&records[1].added_nodes[0], &a));
+        assert_eq!(records[1].previous_sibling.as_ref().unwrap().as_dom_element().unwrap().id(), "b");
+        assert!(records[1].next_sibling.is_none());
+
+        assert!(observer.take_records().is_empty());
+    }
+
+    #[test]
+    fn subtree() {
+        let html = r#"<div id="target"><p id="child"></p></div>"#;
+        for (subtree, expected) in [(false, 0), (true, 1)] {
+            let (target, observer, _) = observe(html, MutationObserverInit {
+                child_list: true,
+                subtree,
+                ..Default::default()
+            });
+
+            let child = first_child(&target);
+            child.append_child(Parser::parse_fragment("<b></b>", child.as_dom_element().unwrap().qualified_name().clone()).remove(0));
+            assert_eq!(observer.take_records().len(), expected);
+        }
+    }
+
+    #[test]
+    fn attributes() {
+        let (target, observer, _) = observe(r#"<div id="target"></div>"#, MutationObserverInit {
+            attribute_old_value: Some(true),
+            attribute_filter: Some(vec![String::from("class")]),
+            ..Default::default()
+        });
+
+        Mutation::attributes(&target, "id", Some("target")).queue();
+        Mutation::attributes(&target, "class", Some("old")).queue();
+        Mutation::character_data(&target, "text").queue();
+
+        let records = observer.take_records();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].record_type.as_ref(), "attributes");
+        assert_eq!(records[0].attribute_name.as_deref(), Some("class"));
+        assert_eq!(records[0].old_value.as_deref(), Some("old"));
+    }
+
+    #[test]
+    fn disconnect() {
+        let (target, observer, _) = observe(r#"<div id="target"><p></p></div>"#, MutationObserverInit {
+            child_list: true,
+            ..Default::default()
+        });
+
+        first_child(&target).remove_from_parent();
+        observer.disconnect();
+        assert!(observer.take_records().is_empty());
+        assert!(target.as_node().registered_observers().is_empty());
+    }
+
+    #[test]
+    fn invalid_options() {
+        let document = Parser::parse("");
+        let observer = MutationObserver::new(EventQueue::new());
+
+        assert_eq!(
+            observer.observe(&document, MutationObserverInit::default()),
+            Err(MutationObserverError::NothingToObserve)
+        );
+
+        assert_eq!(
+            observer.observe(&document, MutationObserverInit {
+                child_list: true,
+                attributes: Some(false),
+                attribute_old_value: Some(true),
+                ..Default::default()
+            }),
+            Err(MutationObserverError::AttributeOldValueWithoutAttributes)
+        );
+
+        assert_eq!(
+            observer.observe(&document, MutationObserverInit {
+                character_data: Some(false),
+                character_data_old_value: Some(false),
+                ..Default::default()
+            }),
+            Err(MutationObserverError::NothingToObserve)
+        );
+    }
+}
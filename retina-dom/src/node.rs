@@ -5,25 +5,48 @@
 //! implementation.
 
 use core::fmt;
-use std::sync::{RwLock, Weak};
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    RwLock,
+    RwLockReadGuard,
+    RwLockWriteGuard,
+    Weak,
+};
 
 use retina_common::DynamicSizeOf;
 
-use crate::NodeKind;
+use crate::{NodeKind, mutation_observer::RegisteredObserver};
+
+/// The identifier of the next node that is created.
+static NEXT_NODE_ID: AtomicU64 = AtomicU64::new(0);
 
 /// The [Interface `Node`](https://dom.spec.whatwg.org/#interface-node)
 /// implementation.
 pub struct NodeInterface {
+    /// Uniquely identifies this node for the lifetime of the process, unlike
+    /// its address, which can be reused after the node is dropped.
+    id: u64,
+
     parent: RwLock<Option<Weak<NodeKind>>>,
+
+    /// The [registered observer list](https://dom.spec.whatwg.org/#registered-observer-list)
+    /// of the mutation observers that observe this node.
+    registered_observers: RwLock<Vec<RegisteredObserver>>,
 }
 
 impl NodeInterface {
     pub fn new() -> Self {
         Self {
-            parent: None.into()
+            id: NEXT_NODE_ID.fetch_add(1, Ordering::Relaxed),
+            parent: None.into(),
+            registered_observers: RwLock::new(Vec::new()),
         }
     }
 
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
     pub fn parent(&self) -> Option<Weak<NodeKind>> {
         if let Ok(parent) = self.parent.read() {
             Option::clone(&parent)
@@ -32,6 +55,14 @@ impl NodeInterface {
         }
     }
 
+    pub fn registered_observers(&self) -> RwLockReadGuard<'_, Vec<RegisteredObserver>> {
+        self.registered_observers.read().unwrap()
+    }
+
+    pub fn registered_observers_mut(&self) -> RwLockWriteGuard<'_, Vec<RegisteredObserver>> {
+        self.registered_observers.write().unwrap()
+    }
+
     pub fn set_parent(&self, parent: Option<Weak<NodeKind>>) {
         *self.parent.write().unwrap() = parent;
     }
@@ -52,6 +83,8 @@ impl DynamicSizeOf for NodeInterface {
         let reference = self.parent.read().unwrap();
         size += std::mem::size_of_val(&reference);
 
+        size += self.registered_observers.read().unwrap().capacity() * std::mem::size_of::<RegisteredObserver>();
+
         size
     }
 }
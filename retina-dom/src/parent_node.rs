@@ -31,6 +31,27 @@ impl ParentNode {
     pub fn children_mut(&self) -> RwLockWriteGuard<Vec<Node>> {
         self.children.write().unwrap()
     }
+
+    /// Returns the first descendant element in tree order whose ID is `id`.
+    /// An empty `id` never matches, since elements without an `id` attribute
+    /// also have an empty ID.
+    pub fn get_element_by_id(&self, id: &str) -> Option<Node> {
+        if id.is_empty() {
+            return None;
+        }
+
+        for child in self.children().iter() {
+            if child.as_dom_element().is_some_and(|element| element.id() == id) {
+                return Some(child.clone());
+            }
+
+            if let Some(found) = child.as_parent_node().and_then(|parent| parent.get_element_by_id(id)) {
+                return Some(found);
+            }
+        }
+
+        None
+    }
 }
 
 impl DynamicSizeOf for ParentNode {
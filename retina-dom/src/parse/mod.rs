@@ -4,19 +4,32 @@
 use std::borrow::Cow;
 use std::default::Default;
 
-use html5ever::local_name;
+use html5ever::{local_name, namespace_url, ns};
 use html5ever::parse_document;
 use html5ever::tendril::*;
 use html5ever::tree_builder::{ElementFlags, NodeOrText, QuirksMode, TreeSink};
 use html5ever::{Attribute, ExpandedName, QualName};
 use log::warn;
+use retina_fetch::Url;
 
 use crate::Comment;
+use crate::ProcessingInstruction;
 use crate::Document;
+use crate::HtmlAnchorElement;
+use crate::HtmlButtonElement;
 use crate::HtmlElementKind;
+use crate::HtmlCanvasElement;
+use crate::HtmlFormElement;
+use crate::HtmlHeadElement;
 use crate::HtmlImgElement;
+use crate::HtmlInputElement;
 use crate::HtmlLinkElement;
+use crate::HtmlSelectElement;
 use crate::HtmlStyleElement;
+use crate::HtmlTableCellElement;
+use crate::HtmlTableElement;
+use crate::HtmlTableRowElement;
+use crate::HtmlTableSectionElement;
 use crate::HtmlUnknownElement;
 use crate::Node;
 use crate::NodeKind;
@@ -35,8 +48,30 @@ impl Parser {
 
     #[must_use]
     pub fn parse_with_reader<R: std::io::Read>(reader: &mut R) -> Node {
+        Self::parse_with_reader_and_document_url(reader, None)
+    }
+
+    /// Parse the document, using the `document_url` to resolve the `href` of
+    /// the `<base>` element.
+    #[must_use]
+    pub fn parse_with_reader_and_document_url<R: std::io::Read>(reader: &mut R, document_url: Option<Url>) -> Node {
+        Self::parse_with_reader_and_stylesheet_observer(reader, document_url, &mut |_| ())
+    }
+
+    /// Parse the document, invoking the `stylesheet_observer` with the
+    /// resolved URL of every `<link rel="stylesheet">` as soon as the parser
+    /// encounters it, such that the style sheet can be fetched speculatively
+    /// before parsing has completed.
+    #[must_use]
+    pub fn parse_with_reader_and_stylesheet_observer<R: std::io::Read>(
+        reader: &mut R,
+        document_url: Option<Url>,
+        stylesheet_observer: &mut dyn FnMut(Url),
+    ) -> Node {
         let sink = Sink {
             document: Document::new_handle(),
+            document_url,
+            stylesheet_observer: Some(stylesheet_observer),
         };
 
         let sink = parse_document(sink, Default::default())
@@ -47,13 +82,131 @@ impl Parser {
         // sink.document.dump();
         sink.document
     }
+
+    /// Parse an XML document (e.g. `image/svg+xml` or `application/xhtml+xml`)
+    /// using the XML parser instead of the HTML parser.
+    ///
+    /// Note that the XML parser reports CDATA sections as regular character
+    /// data, so they end up as [`Text`] nodes.
+    #[must_use]
+    pub fn parse_xml_with_reader<R: std::io::Read>(reader: &mut R, document_url: Option<Url>) -> Node {
+        let sink = Sink {
+            document: Document::new_handle(),
+            document_url,
+            stylesheet_observer: None,
+        };
+
+        let sink = xml5ever::driver::parse_document(sink, Default::default())
+            .from_utf8()
+            .read_from(reader)
+            .unwrap();
+
+        sink.document
+    }
+
+    /// Parse the `input` using the [HTML fragment parsing algorithm][spec],
+    /// returning the parsed nodes, which aren't attached to any parent.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/parsing.html#html-fragment-parsing-algorithm
+    #[must_use]
+    pub fn parse_fragment(input: &str, context_name: QualName) -> Vec<Node> {
+        let sink = Sink {
+            document: Document::new_handle(),
+            document_url: None,
+            stylesheet_observer: None,
+        };
+
+        let sink = html5ever::parse_fragment(sink, Default::default(), context_name, Vec::new())
+            .from_utf8()
+            .read_from(&mut std::io::Cursor::new(input))
+            .unwrap();
+
+        // The fragment parsing algorithm puts the parsed nodes inside of an
+        // `<html>` root element.
+        let Some(root) = sink.document.as_parent_node().unwrap().children().first().cloned() else {
+            return Vec::new();
+        };
+
+        let Some(root_parent_node) = root.as_parent_node() else {
+            return Vec::new();
+        };
+
+        let nodes = std::mem::take(&mut *root_parent_node.children_mut());
+        for node in &nodes {
+            node.as_node().set_parent(None);
+        }
+
+        nodes
+    }
 }
 
-struct Sink {
+struct Sink<'observer> {
     document: Node,
+    document_url: Option<Url>,
+    stylesheet_observer: Option<&'observer mut dyn FnMut(Url)>,
 }
 
-impl TreeSink for Sink {
+impl<'observer> Sink<'observer> {
+    /// [Set the frozen base URL][spec] when the first `<base>` element with an
+    /// `href` attribute is created. An invalid, `data:` or `javascript:` URL
+    /// freezes the base URL to the URL of the document instead.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/semantics.html#set-the-frozen-base-url
+    fn set_frozen_base_url(&mut self, href: &str) {
+        let document = self.document.as_document().unwrap();
+        if document.data().base_url().is_some() {
+            return;
+        }
+
+        let url = match Url::options().base_url(self.document_url.as_ref()).parse(href.trim()) {
+            Ok(url) if url.scheme() != "data" && url.scheme() != "javascript" => Some(url),
+            Ok(url) => {
+                warn!("[Parser] Ignoring <base> with disallowed URL: {url}");
+                self.document_url.clone()
+            }
+            Err(err) => {
+                warn!("[Parser] Ignoring <base> with invalid href \"{href}\": {err}");
+                self.document_url.clone()
+            }
+        };
+
+        document.data_mut().set_base_url(url);
+    }
+
+    /// Notify the observer when a `<link rel="stylesheet">` was created.
+    /// Alternative style sheets aren't prefetched, since they don't apply by
+    /// default.
+    fn observe_stylesheet_link(&mut self, element: &HtmlElementKind) {
+        if self.stylesheet_observer.is_none() {
+            return;
+        }
+
+        let HtmlElementKind::Link(link) = element else {
+            return;
+        };
+
+        let Some(href) = link.stylesheet_href() else {
+            return;
+        };
+
+        let document = self.document.as_document().unwrap();
+        let base_url = document.data().base_url().cloned().or_else(|| self.document_url.clone());
+
+        let url = match Url::options().base_url(base_url.as_ref()).parse(href.trim()) {
+            Ok(url) => url,
+            Err(err) => {
+                warn!("[Parser] Not prefetching stylesheet with invalid href \"{href}\": {err}");
+                return;
+            }
+        };
+
+        if let Some(observer) = self.stylesheet_observer.as_mut() {
+            observer(url);
+        }
+    }
+}
+
+impl<'observer> TreeSink for Sink<'observer> {
     type Handle = Node;
     type Output = Self;
     fn finish(self) -> Self {
@@ -76,6 +229,7 @@ impl TreeSink for Sink {
         match target.as_ref() {
             NodeKind::Comment(..) => unimplemented!(),
             NodeKind::Document(..) => unimplemented!(),
+            NodeKind::ProcessingInstruction(..) => unimplemented!(),
             NodeKind::Text(..) => unimplemented!(),
 
             NodeKind::HtmlElement(html_element) => {
@@ -95,6 +249,16 @@ impl TreeSink for Sink {
         //    localName, given namespace, null, and is. If will execute script
         //    is true, set the synchronous custom elements flag; otherwise,
         //    leave it unset.
+        if qualified_name.local == local_name!("base") {
+            let href = attributes.iter()
+                .find(|attribute| attribute.name.local == local_name!("href"))
+                .map(|attribute| attribute.value.to_string());
+
+            if let Some(href) = href {
+                self.set_frozen_base_url(&href);
+            }
+        }
+
         let mut node = create_element_for_qualified_name(qualified_name);
         let element = node.as_html_element_kind_mut().unwrap();
 
@@ -105,6 +269,8 @@ impl TreeSink for Sink {
                 .append_attribute(attribute);
         }
 
+        self.observe_stylesheet_link(element);
+
         Node::new(node)
     }
 
@@ -112,9 +278,11 @@ impl TreeSink for Sink {
         Comment::new_handle(retina_common::StrTendril::from(_text.as_ref()))
     }
 
-    #[allow(unused_variables)]
     fn create_pi(&mut self, target: StrTendril, value: StrTendril) -> Self::Handle {
-        todo!()
+        ProcessingInstruction::new_handle(
+            retina_common::StrTendril::from(target.as_ref()),
+            retina_common::StrTendril::from(value.as_ref()),
+        )
     }
 
     fn append_before_sibling(&mut self, _sibling: &Self::Handle, _new_node: NodeOrText<Self::Handle>) {
@@ -130,6 +298,22 @@ impl TreeSink for Sink {
         todo!()
     }
 
+    /// Make the `form` the form owner of the `target` control, which is
+    /// called for controls that are inserted while the [form element
+    /// pointer][spec] is set.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/parsing.html#form-element-pointer
+    fn associate_with_form(
+        &mut self,
+        target: &Self::Handle,
+        form: &Self::Handle,
+        _nodes: (&Self::Handle, Option<&Self::Handle>),
+    ) {
+        if let Some(HtmlElementKind::Form(form)) = form.as_html_element_kind() {
+            form.add_control(Node::downgrade(target));
+        }
+    }
+
     fn parse_error(&mut self, msg: Cow<'static, str>) {
         warn!("[Parser] Parse Error: {msg}");
     }
@@ -179,8 +363,13 @@ impl TreeSink for Sink {
         // ignored
     }
 
-    fn add_attrs_if_missing(&mut self, _target: &Self::Handle, _attrs: Vec<Attribute>) {
-        todo!()
+    fn add_attrs_if_missing(&mut self, _target: &Self::Handle, attrs: Vec<Attribute>) {
+        // This happens for stray <html> and <body> tags, e.g. in fragments
+        // passed to `innerHTML`. The attributes of existing elements can't be
+        // modified yet, so they're dropped.
+        if !attrs.is_empty() {
+            warn!("[Parser] Ignoring {} attribute(s) of a stray <html> or <body> tag", attrs.len());
+        }
     }
 
     fn remove_from_parent(&mut self, _target: &Self::Handle) {
@@ -202,10 +391,14 @@ impl TreeSink for Sink {
 /// * [DOM Standard - **create an element**][concept]
 ///
 /// [concept]: https://dom.spec.whatwg.org/#concept-create-element
-fn create_element_for_qualified_name(
+pub(crate) fn create_element_for_qualified_name(
     qualified_name: QualName
 ) -> NodeKind {
     // In the future SVG, MathML, and custom elements can be constructed here.
+    if qualified_name.ns != ns!(html) {
+        return NodeKind::HtmlElement(HtmlElementKind::Unknown(HtmlUnknownElement::new(qualified_name)));
+    }
+
     NodeKind::HtmlElement(create_html_element_with_name(qualified_name))
 }
 
@@ -220,9 +413,22 @@ fn create_html_element_with_name(
     qualified_name: QualName,
 ) -> HtmlElementKind {
     match &qualified_name.local {
+        &local_name!("a") => HtmlElementKind::Anchor(HtmlAnchorElement::new(qualified_name)),
+        &local_name!("button") => HtmlElementKind::Button(HtmlButtonElement::new(qualified_name)),
+        &local_name!("canvas") => HtmlElementKind::Canvas(HtmlCanvasElement::new(qualified_name)),
+        &local_name!("form") => HtmlElementKind::Form(HtmlFormElement::new(qualified_name)),
+        &local_name!("head") => HtmlElementKind::Head(HtmlHeadElement::new(qualified_name)),
         &local_name!("img") => HtmlElementKind::Img(HtmlImgElement::new(qualified_name)),
+        &local_name!("input") => HtmlElementKind::Input(HtmlInputElement::new(qualified_name)),
         &local_name!("link") => HtmlElementKind::Link(HtmlLinkElement::new(qualified_name)),
+        &local_name!("select") => HtmlElementKind::Select(HtmlSelectElement::new(qualified_name)),
         &local_name!("style") => HtmlElementKind::Style(HtmlStyleElement::new(qualified_name)),
+        &local_name!("table") => HtmlElementKind::Table(HtmlTableElement::new(qualified_name)),
+        &local_name!("td") | &local_name!("th") => HtmlElementKind::TableCell(HtmlTableCellElement::new(qualified_name)),
+        &local_name!("tr") => HtmlElementKind::TableRow(HtmlTableRowElement::new(qualified_name)),
+        &local_name!("tbody") | &local_name!("tfoot") | &local_name!("thead") => {
+            HtmlElementKind::TableSection(HtmlTableSectionElement::new(qualified_name))
+        }
 
         _ => HtmlElementKind::Unknown(HtmlUnknownElement::new(qualified_name)),
     }
@@ -238,4 +444,72 @@ mod tests {
         _ = Parser::parse(TEXT);
     }
 
+    #[test]
+    fn parse_xml_processing_instruction() {
+        const TEXT: &str = "<?xml-stylesheet href=\"style.css\"?><svg xmlns=\"http://www.w3.org/2000/svg\"><style><![CDATA[rect { fill: red; }]]></style></svg>";
+        let document = Parser::parse_xml_with_reader(&mut std::io::Cursor::new(TEXT), None);
+
+        let children = document.as_parent_node().unwrap().children();
+        let instruction = children[0].as_processing_instruction().unwrap();
+        assert_eq!(instruction.target().as_ref(), "xml-stylesheet");
+        assert_eq!(instruction.data_as_str(), "href=\"style.css\"");
+
+        assert_eq!(children[1].tag_name(), Some("svg"));
+
+        // The CDATA section is reported as character data.
+        let style = children[1].as_parent_node().unwrap().children()[0].clone();
+        assert_eq!(style.tag_name(), Some("style"));
+        let text = style.as_parent_node().unwrap().children()[0].clone();
+        assert_eq!(text.as_text().unwrap().data_as_str(), "rect { fill: red; }");
+    }
+
+    #[test]
+    fn parse_observes_stylesheet_links() {
+        const TEXT: &str = "<head><link rel=icon href=favicon.ico><link rel=stylesheet href=style.css><base href=\"/assets/\"><link rel=\"alternate stylesheet\" href=dark.css><link rel=stylesheet href=main.css></head>";
+        let document_url = retina_fetch::Url::parse("https://example.com/page/index.html").unwrap();
+
+        let mut urls = Vec::new();
+        _ = Parser::parse_with_reader_and_stylesheet_observer(
+            &mut std::io::Cursor::new(TEXT),
+            Some(document_url),
+            &mut |url| urls.push(url.to_string()),
+        );
+
+        assert_eq!(urls, [
+            "https://example.com/page/style.css",
+            "https://example.com/assets/main.css",
+        ]);
+    }
+
+    fn parse_base_url(html: &str) -> Option<String> {
+        let document_url = retina_fetch::Url::parse("https://example.com/page/index.html").unwrap();
+        let document = Parser::parse_with_reader_and_document_url(&mut std::io::Cursor::new(html), Some(document_url));
+
+        let base_url = document.as_document().unwrap().data().base_url().map(ToString::to_string);
+        base_url
+    }
+
+    #[test]
+    fn parse_sets_frozen_base_url() {
+        assert_eq!(parse_base_url("<p></p>"), None);
+        assert_eq!(parse_base_url("<base target=_blank>"), None);
+
+        // Only the first `<base>` with an `href` sets the base URL, which is
+        // resolved against the URL of the document.
+        assert_eq!(
+            parse_base_url("<base target=_blank><base href=\"../assets/\"><base href=\"https://example.org/\">"),
+            Some("https://example.com/assets/".to_string()),
+        );
+
+        // `data:` and `javascript:` URLs are ignored, but still freeze the
+        // base URL, such that a later `<base>` doesn't apply either.
+        for href in ["data:text/html,hi", "javascript:void(0)", "http://[invalid"] {
+            assert_eq!(
+                parse_base_url(&format!("<base href=\"{href}\"><base href=\"/other/\">")),
+                Some("https://example.com/page/index.html".to_string()),
+                "href: {href}",
+            );
+        }
+    }
+
 }
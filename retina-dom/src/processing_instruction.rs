@@ -0,0 +1,64 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! The [Interface `ProcessingInstruction`][spec] implementation.
+//!
+//! [spec]: https://dom.spec.whatwg.org/#interface-processinginstruction
+
+use std::ops::{Deref, DerefMut};
+
+use retina_common::{StrTendril, DynamicSizeOf};
+
+use crate::{CharacterData, Node, NodeKind};
+
+/// The [Interface `ProcessingInstruction`][spec] implementation, e.g.
+/// `<?xml-stylesheet href="style.css"?>`.
+///
+/// [spec]: https://dom.spec.whatwg.org/#interface-processinginstruction
+#[derive(Debug)]
+pub struct ProcessingInstruction {
+    superclass_character_data: CharacterData,
+    target: StrTendril,
+}
+
+impl ProcessingInstruction {
+    pub fn new(target: StrTendril, data: StrTendril) -> Self {
+        Self {
+            superclass_character_data: CharacterData::new(data),
+            target,
+        }
+    }
+
+    pub fn new_handle(target: StrTendril, data: StrTendril) -> Node {
+        Node::new(
+            NodeKind::ProcessingInstruction(
+                Self::new(target, data)
+            )
+        )
+    }
+
+    pub fn target(&self) -> &StrTendril {
+        &self.target
+    }
+}
+
+impl DynamicSizeOf for ProcessingInstruction {
+    fn dynamic_size_of(&self) -> usize {
+        self.superclass_character_data.dynamic_size_of()
+            + self.target.len()
+    }
+}
+
+impl Deref for ProcessingInstruction {
+    type Target = CharacterData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.superclass_character_data
+    }
+}
+
+impl DerefMut for ProcessingInstruction {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.superclass_character_data
+    }
+}
@@ -10,6 +10,22 @@ pub struct DocumentWrapper(pub Node);
 
 impl DocumentWrapper {
     pub fn html(&self) -> Node {
+        self.find_html()
+            .unwrap_or_else(|| panic!("No <html> found in Document: {:#?}", self.0))
+    }
+
+    pub fn head(&self) -> Node {
+        self.find_head()
+            .unwrap_or_else(|| panic!("No <head> found in <html>: {:#?}", self.html()))
+    }
+
+    pub fn body(&self) -> Node {
+        self.find_body()
+            .unwrap_or_else(|| panic!("No <body> found in <html>: {:#?}", self.html()))
+    }
+
+    /// Find the `<html>` element, which isn't there for e.g. XML documents.
+    pub fn find_html(&self) -> Option<Node> {
         debug_assert!(matches!(self.0.as_ref(), crate::NodeKind::Document(..)));
 
         let children = self.0.as_ref()
@@ -17,30 +33,32 @@ impl DocumentWrapper {
             .expect("Expected Node to be Document, which is a parent node.")
             .children();
 
-        for child in children.iter() {
-            if child.tag_name() == Some("html") {
-                return child.clone();
-            }
-        }
+        let html = children.iter()
+            .find(|child| child.tag_name() == Some("html"))
+            .cloned();
+        html
+    }
 
-        panic!("No <html> found in Document: {:#?}", self.0);
+    pub fn find_head(&self) -> Option<Node> {
+        self.find_child_of_html("head")
     }
 
-    pub fn head(&self) -> Node {
-        let html = self.html();
+    pub fn find_body(&self) -> Option<Node> {
+        self.find_child_of_html("body")
+    }
+
+    fn find_child_of_html(&self, tag_name: &str) -> Option<Node> {
+        let html = self.find_html()?;
         let children = html
             .as_ref()
             .as_parent_node()
             .expect("Expected Node to be HTMLHtmlElement, which is a parent node.")
             .children();
 
-        for child in children.iter() {
-            if child.tag_name() == Some("head") {
-                return child.clone();
-            }
-        }
-
-        panic!("No <head> found in <html>: {html:#?}");
+        let child = children.iter()
+            .find(|child| child.tag_name() == Some(tag_name))
+            .cloned();
+        child
     }
 }
 
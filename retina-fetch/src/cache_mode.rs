@@ -0,0 +1,53 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use strum::AsRefStr;
+
+/// The request has an associated [cache mode][spec], which controls how the
+/// request interacts with the HTTP cache.
+///
+/// [spec]: https://fetch.spec.whatwg.org/#concept-request-cache-mode
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(AsRefStr)]
+#[strum(serialize_all = "kebab-case")]
+pub enum RequestCacheMode {
+    /// ["default"](https://fetch.spec.whatwg.org/#concept-request-cache-mode)
+    /// >   _Fetch will inspect the HTTP cache on the way to the network. If
+    /// >   the HTTP cache contains a matching fresh response it will be
+    /// >   returned. If the HTTP cache contains a matching stale-while-
+    /// >   revalidate response it will be returned, and a conditional network
+    /// >   fetch will be made to update the entry in the HTTP cache. If the
+    /// >   HTTP cache contains a matching stale response, a conditional
+    /// >   network fetch will be returned to update the entry in the HTTP
+    /// >   cache. Otherwise, a non-conditional network fetch will be returned
+    /// >   to update the entry in the HTTP cache._
+    #[default]
+    Default,
+
+    /// ["no-store"](https://fetch.spec.whatwg.org/#concept-request-cache-mode)
+    /// >   _Fetch behaves as if there is no HTTP cache at all._
+    NoStore,
+
+    /// ["reload"](https://fetch.spec.whatwg.org/#concept-request-cache-mode)
+    /// >   _Fetch behaves as if there is no HTTP cache on the way to the
+    /// >   network. Ergo, it creates a normal request and updates the HTTP
+    /// >   cache with the response._
+    Reload,
+}
+
+impl RequestCacheMode {
+    /// Get the normative string representation, as per [Fetch][spec].
+    ///
+    /// [spec]: https://fetch.spec.whatwg.org/#concept-request-cache-mode
+    pub fn as_str(&self) -> &str {
+        self.as_ref()
+    }
+
+    /// Whether or not the `Cache-Control: no-cache` and `Pragma: no-cache`
+    /// headers must be sent, as per [HTTP-network-or-cache fetch][spec].
+    ///
+    /// [spec]: https://fetch.spec.whatwg.org/#http-network-or-cache-fetch
+    pub fn bypasses_cache(&self) -> bool {
+        matches!(self, Self::NoStore | Self::Reload)
+    }
+}
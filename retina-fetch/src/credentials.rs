@@ -0,0 +1,42 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use strum::AsRefStr;
+
+/// The request has an associated [credentials mode][spec], which specifies
+/// whether credentials (cookies, HTTP authentication entries and TLS client
+/// certificates) are sent with the request.
+///
+/// [spec]: https://fetch.spec.whatwg.org/#concept-request-credentials-mode
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(AsRefStr)]
+pub enum RequestCredentialsMode {
+    /// ["omit"](https://fetch.spec.whatwg.org/#concept-request-credentials-mode)
+    /// >   _Excludes credentials from this request, and causes any
+    /// >   credentials sent back in the response to be ignored._
+    #[strum(serialize = "omit")]
+    Omit,
+
+    /// ["same-origin"](https://fetch.spec.whatwg.org/#concept-request-credentials-mode)
+    /// >   _Include credentials with requests made to same-origin URLs, and
+    /// >   use any credentials sent back in responses from same-origin
+    /// >   URLs._
+    #[default]
+    #[strum(serialize = "same-origin")]
+    SameOrigin,
+
+    /// ["include"](https://fetch.spec.whatwg.org/#concept-request-credentials-mode)
+    /// >   _Always includes credentials with this request, and always use any
+    /// >   credentials sent back in the response._
+    #[strum(serialize = "include")]
+    Include,
+}
+
+impl RequestCredentialsMode {
+    /// Get the normative string representation, as per [Fetch][spec].
+    ///
+    /// [spec]: https://fetch.spec.whatwg.org/#concept-request-credentials-mode
+    pub fn as_str(&self) -> &str {
+        self.as_ref()
+    }
+}
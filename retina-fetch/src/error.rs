@@ -48,6 +48,9 @@ pub enum NetworkError {
 
     /// A `file://` URL was not found.
     LocalFileNotFound,
+
+    /// A `file://` URL was found, but couldn't be read.
+    LocalFileUnreadable,
 }
 
 impl Display for NetworkError {
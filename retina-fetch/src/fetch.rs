@@ -1,10 +1,10 @@
 // Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
 // All Rights Reserved.
 
-use std::{sync::Arc, path::Path};
+use std::{future::Future, sync::Arc};
 
 use log::{warn, trace};
-use tokio::{runtime::Runtime, sync::mpsc::channel};
+use tokio::{runtime::Runtime, sync::mpsc::channel, task::JoinHandle};
 use url::Url;
 
 use crate::{
@@ -14,6 +14,7 @@ use crate::{
     InternalError,
     NetworkError,
     Request,
+    RequestCacheMode,
     RequestReferrer,
     Response,
 };
@@ -36,7 +37,7 @@ impl Fetch {
     pub fn create_instantaneous_response(&self, request: Arc<Request>, response: FetchResponse) -> FetchPromise {
         let (sender, receiver) = channel(1);
         self.runtime.spawn(async move {
-            sender.send(response).await.unwrap();
+            _ = sender.send(response).await;
         });
         FetchPromise {
             request,
@@ -83,9 +84,19 @@ impl Fetch {
         }
     }
 
-    /// Fetch a document, given the [`url`][Url] and optionally a
-    /// [referrer][RequestReferrer].
-    pub fn fetch_document(&self, url: Url, referrer: RequestReferrer) -> FetchPromise {
+    /// Run the `future` in the background, on the same runtime as the
+    /// fetches, e.g. to read the body of a [`Response`]. The returned
+    /// [`FetchTask`] can be used to cancel it.
+    pub fn spawn<F>(&self, future: F) -> FetchTask
+            where F: Future<Output = ()> + Send + 'static {
+        FetchTask {
+            handle: self.runtime.spawn(future),
+        }
+    }
+
+    /// Fetch a document, given the [`url`][Url], optionally a
+    /// [referrer][RequestReferrer] and the [cache mode][RequestCacheMode].
+    pub fn fetch_document(&self, url: Url, referrer: RequestReferrer, cache_mode: RequestCacheMode) -> FetchPromise {
         if url.scheme() == "about" {
             return self.fetch_document_about(url);
         }
@@ -94,7 +105,9 @@ impl Fetch {
             return self.fetch_document_file(url);
         }
 
-        self.fetch(Request::get_document(url, referrer))
+        let mut request = Request::get_document(url, referrer);
+        request.set_cache_mode(cache_mode);
+        self.fetch(request)
     }
 
     /// Fetch a `about` document.
@@ -120,23 +133,30 @@ impl Fetch {
         let task_request = Arc::clone(&request);
         self.runtime.spawn(async move {
             let request = task_request;
-            let mut path = request.url.path();
-
-            if cfg!(windows) && path.starts_with('/') {
-                path = &path[1..];
-            }
 
-            let path = Path::new(path);
-            if !path.exists() {
-                sender.send(Err(Error::NetworkError(NetworkError::LocalFileNotFound))).await.unwrap();
+            let Ok(path) = request.url.to_file_path() else {
+                warn!("Invalid file URL: {}", request.url.as_str());
+                _ = sender.send(Err(Error::NetworkError(NetworkError::LocalFileNotFound))).await;
                 return;
-            }
+            };
 
-            let file = tokio::fs::File::open(path).await.unwrap();
-            let decoder = tokio_util::codec::BytesCodec::new();
+            let response = match tokio::fs::read(&path).await {
+                Ok(contents) => {
+                    let content_type = mime_guess::from_path(&path).first_or_octet_stream();
+                    Ok(Response::new_file(request, content_type, contents))
+                }
 
-            let file = tokio_util::codec::FramedRead::new(file, decoder);
-            sender.send(Ok(Response::new_file(request, file))).await.unwrap();
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    Err(Error::NetworkError(NetworkError::LocalFileNotFound))
+                }
+
+                Err(e) => {
+                    warn!("Failed to read file \"{}\": {e}", path.display());
+                    Err(Error::NetworkError(NetworkError::LocalFileUnreadable))
+                }
+            };
+
+            _ = sender.send(response).await;
         });
 
         FetchPromise {
@@ -169,18 +189,44 @@ impl Fetch {
             ;
 
             // TODO follow <https://w3c.github.io/webappsec-referrer-policy/#determine-requests-referrer>
+            if request.cache_mode.bypasses_cache() {
+                hyper_request = hyper_request
+                    .header(http::header::CACHE_CONTROL, "no-cache")
+                    .header(http::header::PRAGMA, "no-cache");
+            }
+
             if let RequestReferrer::Url(url) = &request.referrer {
                 hyper_request = hyper_request.header(http::header::REFERER, url.to_string());
             }
 
+            let body = match &request.body {
+                Some(body) => {
+                    let (content_type, bytes) = body.encode();
+                    hyper_request = hyper_request.header(http::header::CONTENT_TYPE, content_type);
+                    hyper::Body::from(bytes)
+                }
+                None => hyper::Body::empty(),
+            };
+
+            // The headers of the request override the generated ones.
+            if let Some(headers) = hyper_request.headers_mut() {
+                for name in request.headers.keys() {
+                    headers.remove(name);
+                }
+
+                for (name, value) in &request.headers {
+                    headers.append(name, value.clone());
+                }
+            }
+
             let hyper_request = hyper_request
-                .body(hyper::Body::empty());
+                .body(body);
 
             let hyper_request = match hyper_request {
                 Ok(request) => request,
                 Err(e) => {
                     log::warn!("Failed to build request: {e}");
-                    sender.send(Err(Error::InternalError(InternalError::HyperError))).await.unwrap();
+                    _ = sender.send(Err(Error::InternalError(InternalError::HyperError))).await;
                     return;
                 }
             };
@@ -201,7 +247,7 @@ impl Fetch {
                 Err(e) => Err(e.into()),
             };
 
-            sender.send(response).await.unwrap();
+            _ = sender.send(response).await;
         });
 
         FetchPromise {
@@ -241,6 +287,21 @@ impl Fetch {
     }
 }
 
+/// A task that was started using [`Fetch::spawn()`].
+#[derive(Debug)]
+pub struct FetchTask {
+    handle: JoinHandle<()>,
+}
+
+impl FetchTask {
+    /// Cancel the task, e.g. because the fetch was aborted. Since the task
+    /// doesn't await the [`FetchPromise`] anymore, the response is discarded
+    /// when it arrives.
+    pub fn abort(&self) {
+        self.handle.abort();
+    }
+}
+
 fn spawn_runtime_stopper(runtime: Arc<Runtime>) {
     std::thread::spawn(move || {
         loop {
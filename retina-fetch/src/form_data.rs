@@ -0,0 +1,64 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+/// The [`FormData`][spec] class, which is an ordered list of entries, used
+/// as the body of e.g. a form submission.
+///
+/// [spec]: https://xhr.spec.whatwg.org/#interface-formdata
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FormData {
+    pub(crate) entries: Vec<(String, FormDataEntryValue)>,
+}
+
+impl FormData {
+    /// Create an empty [`FormData`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a string entry.
+    pub fn append(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.entries.push((name.into(), FormDataEntryValue::String(value.into())));
+    }
+
+    /// Append a file entry.
+    pub fn append_file(
+        &mut self,
+        name: impl Into<String>,
+        file_name: impl Into<String>,
+        content_type: mime::Mime,
+        contents: Vec<u8>,
+    ) {
+        self.entries.push((name.into(), FormDataEntryValue::File {
+            file_name: file_name.into(),
+            content_type,
+            contents,
+        }));
+    }
+
+    /// Get the entries, in the order they were appended.
+    pub fn entries(&self) -> &[(String, FormDataEntryValue)] {
+        &self.entries
+    }
+}
+
+/// The [`FormDataEntryValue`][spec] of a [`FormData`] entry.
+///
+/// [spec]: https://xhr.spec.whatwg.org/#formdataentryvalue
+#[derive(Clone, Debug, PartialEq)]
+pub enum FormDataEntryValue {
+    /// A regular string value, e.g. of an `<input type=text>`.
+    String(String),
+
+    /// A file, e.g. of an `<input type=file>`.
+    File {
+        /// The name of the file, without the directory.
+        file_name: String,
+
+        /// The type of the contents.
+        content_type: mime::Mime,
+
+        /// The binary contents of the file.
+        contents: Vec<u8>,
+    },
+}
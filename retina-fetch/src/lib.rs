@@ -12,28 +12,38 @@
 
 #![deny(missing_docs)]
 
+pub(crate) mod cache_mode;
+pub(crate) mod credentials;
 pub(crate) mod destination;
 pub(crate) mod error;
+pub(crate) mod form_data;
 pub(crate) mod initiator;
 pub(crate) mod fetch;
 pub(crate) mod mode;
+pub(crate) mod multipart;
 pub(crate) mod promise;
 pub(crate) mod referrer;
 pub(crate) mod request;
 pub(crate) mod response;
 pub(crate) mod status_code;
 
+pub use cache_mode::RequestCacheMode;
+pub use credentials::RequestCredentialsMode;
 pub use destination::RequestDestination;
 pub use error::{Error, InternalError, NetworkError};
-pub use fetch::Fetch;
+pub use form_data::{FormData, FormDataEntryValue};
+pub use fetch::{Fetch, FetchTask};
 pub use initiator::RequestInitiator;
 pub use mode::RequestMode;
+pub use multipart::MultipartEncoder;
 pub use promise::FetchPromise;
 pub use referrer::RequestReferrer;
-pub use request::Request;
+pub use request::{Request, RequestBody};
 pub use response::Response;
 pub use status_code::{StatusCode, StatusCodeClass};
 
+pub use hyper::{HeaderMap, Method};
+pub use hyper::header::{HeaderName, HeaderValue};
 pub use mime;
 pub use url::{
     self,
@@ -45,14 +55,15 @@ pub type FetchResponse = Result<Response, Error>;
 
 /// Parse a page URL. This function is laxer than the regular
 /// [`Url::parse()`][Url] parser, since the user can omit certain elements
-/// (like the scheme).
-pub fn parse_page_url(input: &str) -> Result<Url, url::ParseError> {
+/// (like the scheme). Relative URLs are resolved against the `base_url`, if
+/// one is given.
+pub fn parse_page_url(input: &str, base_url: Option<&Url>) -> Result<Url, url::ParseError> {
     if input.len() > 3 && &input[1..3] == ":\\" {
         let url = format!("file:///{}", input.replace('\\', "/"));
         return Url::parse(&url);
     }
 
-    let result = Url::parse(input);
+    let result = Url::options().base_url(base_url).parse(input);
 
     if result == Err(url::ParseError::RelativeUrlWithoutBase) && !input.starts_with("http") {
         if let Ok(url) = Url::parse(&format!("https://{input}")) {
@@ -16,6 +16,7 @@ pub enum RequestMode {
     /// >   _Used to ensure requests are made to same-origin URLs. Fetch will
     /// >   return a network error if the request is not made to a same-origin
     /// >   URL._
+    #[strum(serialize = "same-origin")]
     SameOrigin,
 
     /// ["cors"](https://fetch.spec.whatwg.org/#concept-request-mode)
@@ -31,6 +32,7 @@ pub enum RequestMode {
     /// >   CORS-safelisted request-headers. Upon success, fetch will return an
     /// >   opaque filtered response._
     #[default]
+    #[strum(serialize = "no-cors")]
     NoCors,
 
     /// ["navigate"](https://fetch.spec.whatwg.org/#concept-request-mode)
@@ -0,0 +1,124 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+};
+
+use crate::{FormData, FormDataEntryValue};
+
+/// Encodes a [`FormData`] using the [`multipart/form-data` encoding
+/// algorithm][spec], which is able to transfer binary files.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/form-control-infrastructure.html#multipart-form-data
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultipartEncoder {
+    boundary: String,
+}
+
+impl MultipartEncoder {
+    /// Create an encoder with a random boundary string.
+    pub fn new() -> Self {
+        let random = || RandomState::new().build_hasher().finish();
+        Self::with_boundary(format!("----RetinaFormBoundary{:016x}{:016x}", random(), random()))
+    }
+
+    /// Create an encoder with the given boundary string, which must not occur
+    /// in any of the entries.
+    pub fn with_boundary(boundary: impl Into<String>) -> Self {
+        Self {
+            boundary: boundary.into(),
+        }
+    }
+
+    /// Get the boundary string delimiting the entries.
+    pub fn boundary(&self) -> &str {
+        &self.boundary
+    }
+
+    /// Get the value of the `Content-Type` header of the encoded body.
+    pub fn content_type(&self) -> String {
+        format!("multipart/form-data; boundary={}", self.boundary)
+    }
+
+    /// Encode the entries of the form data into a body.
+    pub fn encode(&self, form_data: &FormData) -> Vec<u8> {
+        let mut body = Vec::new();
+
+        for (name, value) in form_data.entries() {
+            body.extend_from_slice(format!("--{}\r\n", self.boundary).as_bytes());
+            body.extend_from_slice(b"Content-Disposition: form-data; name=\"");
+            body.extend_from_slice(escape(name).as_bytes());
+            body.push(b'"');
+
+            match value {
+                FormDataEntryValue::String(value) => {
+                    body.extend_from_slice(b"\r\n\r\n");
+                    body.extend_from_slice(normalize_line_breaks(value).as_bytes());
+                }
+
+                FormDataEntryValue::File { file_name, content_type, contents } => {
+                    body.extend_from_slice(b"; filename=\"");
+                    body.extend_from_slice(escape(file_name).as_bytes());
+                    body.extend_from_slice(b"\"\r\nContent-Type: ");
+                    body.extend_from_slice(content_type.as_ref().as_bytes());
+                    body.extend_from_slice(b"\r\n\r\n");
+                    body.extend_from_slice(contents);
+                }
+            }
+
+            body.extend_from_slice(b"\r\n");
+        }
+
+        body.extend_from_slice(format!("--{}--\r\n", self.boundary).as_bytes());
+        body
+    }
+}
+
+impl Default for MultipartEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// > For field names and filenames for file fields, the result of the
+/// > encoding in the previous bullet point must be escaped by replacing any
+/// > 0x0A (LF) bytes with the byte sequence `%0A`, 0x0D (CR) with `%0D` and
+/// > 0x22 (") with `%22`.
+fn escape(value: &str) -> String {
+    value.replace('\n', "%0A")
+        .replace('\r', "%0D")
+        .replace('"', "%22")
+}
+
+/// Converts every lone CR and LF into a CRLF pair.
+fn normalize_line_breaks(value: &str) -> String {
+    value.replace("\r\n", "\n")
+        .replace('\r', "\n")
+        .replace('\n', "\r\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode() {
+        let mut form_data = FormData::new();
+        form_data.append("title", "Hello\nworld");
+        form_data.append_file("upload", "a \"b\".bin", mime::APPLICATION_OCTET_STREAM, vec![0, 159, 146, 150]);
+
+        let encoder = MultipartEncoder::with_boundary("XYZ");
+        assert_eq!(encoder.content_type(), "multipart/form-data; boundary=XYZ");
+
+        let mut expected = Vec::new();
+        expected.extend_from_slice(b"--XYZ\r\nContent-Disposition: form-data; name=\"title\"\r\n\r\nHello\r\nworld\r\n");
+        expected.extend_from_slice(b"--XYZ\r\nContent-Disposition: form-data; name=\"upload\"; filename=\"a %22b%22.bin\"\r\n");
+        expected.extend_from_slice(b"Content-Type: application/octet-stream\r\n\r\n");
+        expected.extend_from_slice(&[0, 159, 146, 150]);
+        expected.extend_from_slice(b"\r\n--XYZ--\r\n");
+
+        assert_eq!(encoder.encode(&form_data), expected);
+    }
+}
@@ -1,10 +1,14 @@
 // Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
 // All Rights Reserved.
 
-use hyper::Method;
+use hyper::{HeaderMap, Method};
 use url::Url;
 
 use crate::{
+    FormData,
+    MultipartEncoder,
+    RequestCacheMode,
+    RequestCredentialsMode,
     RequestDestination,
     RequestInitiator,
     RequestMode,
@@ -20,9 +24,13 @@ pub struct Request {
     pub(crate) destination: RequestDestination,
     pub(crate) mode: RequestMode,
     pub(crate) referrer: RequestReferrer,
+    pub(crate) cache_mode: RequestCacheMode,
+    pub(crate) credentials_mode: RequestCredentialsMode,
 
     pub(crate) method: hyper::Method,
     pub(crate) url: Url,
+    pub(crate) headers: HeaderMap,
+    pub(crate) body: Option<RequestBody>,
 }
 
 impl Request {
@@ -39,9 +47,13 @@ impl Request {
             destination,
             mode,
             referrer,
+            cache_mode: RequestCacheMode::default(),
+            credentials_mode: RequestCredentialsMode::default(),
 
             method: hyper::Method::GET,
             url,
+            headers: HeaderMap::new(),
+            body: None,
         }
     }
 
@@ -55,9 +67,13 @@ impl Request {
             destination: RequestDestination::Document,
             mode: RequestMode::Navigate,
             referrer,
+            cache_mode: RequestCacheMode::default(),
+            credentials_mode: RequestCredentialsMode::default(),
 
             method: Method::GET,
             url,
+            headers: HeaderMap::new(),
+            body: None,
         }
     }
 
@@ -73,8 +89,105 @@ impl Request {
         }
     }
 
+    /// Get the [body][RequestBody] of this request, if any.
+    pub fn body(&self) -> Option<&RequestBody> {
+        self.body.as_ref()
+    }
+
+    /// Set the [body][RequestBody] of this request.
+    pub fn set_body(&mut self, body: RequestBody) {
+        self.body = Some(body);
+    }
+
+    /// Get the [cache mode][RequestCacheMode] of this request.
+    pub fn cache_mode(&self) -> RequestCacheMode {
+        self.cache_mode
+    }
+
+    /// Set the [cache mode][RequestCacheMode] of this request.
+    pub fn set_cache_mode(&mut self, cache_mode: RequestCacheMode) {
+        self.cache_mode = cache_mode;
+    }
+
+    /// Get the [credentials mode][RequestCredentialsMode] of this request.
+    pub fn credentials_mode(&self) -> RequestCredentialsMode {
+        self.credentials_mode
+    }
+
+    /// Set the [credentials mode][RequestCredentialsMode] of this request.
+    pub fn set_credentials_mode(&mut self, credentials_mode: RequestCredentialsMode) {
+        self.credentials_mode = credentials_mode;
+    }
+
+    /// Get the [header list](https://fetch.spec.whatwg.org/#concept-request-header-list)
+    /// of this request, which are sent in addition to the headers that
+    /// [Fetch][crate::Fetch] generates itself, overriding them.
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// Get the mutable [header list](https://fetch.spec.whatwg.org/#concept-request-header-list)
+    /// of this request.
+    pub fn headers_mut(&mut self) -> &mut HeaderMap {
+        &mut self.headers
+    }
+
+    /// Get the [method](https://fetch.spec.whatwg.org/#concept-request-method)
+    /// of this request.
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    /// Set the [method](https://fetch.spec.whatwg.org/#concept-request-method)
+    /// of this request, e.g. `POST` for form submissions.
+    pub fn set_method(&mut self, method: Method) {
+        self.method = method;
+    }
+
+    /// Get the [mode][RequestMode] of this request.
+    pub fn mode(&self) -> RequestMode {
+        self.mode
+    }
+
+    /// Set the [mode][RequestMode] of this request.
+    pub fn set_mode(&mut self, mode: RequestMode) {
+        self.mode = mode;
+    }
+
     /// Get the [Url] that this request should retrieve.
     pub fn url(&self) -> &Url {
         &self.url
     }
 }
+
+/// The [body][spec] of a [`Request`].
+///
+/// [spec]: https://fetch.spec.whatwg.org/#concept-request-body
+#[derive(Clone, Debug, PartialEq)]
+pub enum RequestBody {
+    /// Raw bytes of the given type.
+    Bytes {
+        /// The value of the `Content-Type` header.
+        content_type: mime::Mime,
+
+        /// The contents of the body.
+        bytes: Vec<u8>,
+    },
+
+    /// Form data, encoded as `multipart/form-data`.
+    FormData(FormData),
+}
+
+impl RequestBody {
+    /// Encode the body, returning the value of the `Content-Type` header and
+    /// the bytes to send.
+    pub fn encode(&self) -> (String, Vec<u8>) {
+        match self {
+            Self::Bytes { content_type, bytes } => (content_type.to_string(), bytes.clone()),
+            Self::FormData(form_data) => {
+                let encoder = MultipartEncoder::new();
+                (encoder.content_type(), encoder.encode(form_data))
+            }
+        }
+    }
+}
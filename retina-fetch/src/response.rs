@@ -3,7 +3,6 @@
 
 use std::{sync::Arc, io::{BufRead, Read}};
 
-use futures_core::Stream;
 use hyper::body::{Bytes, Buf};
 use log::error;
 use url::Url;
@@ -27,17 +26,20 @@ impl Response {
         }
     }
 
-    pub(crate) fn new_file<S, O, E>(
+    pub(crate) fn new_file(
         request: Arc<Request>,
-        stream: S,
-    ) -> Self
-        where S: Stream<Item = Result<O, E>> + Send + 'static,
-            O: Into<Bytes> + 'static,
-            E: Into<Box<dyn std::error::Error + Send + Sync>> + 'static,
-        {
+        content_type: mime::Mime,
+        contents: Vec<u8>,
+    ) -> Self {
+        let inner = hyper::Response::builder()
+            .status(http::StatusCode::OK)
+            .header(hyper::header::CONTENT_TYPE, content_type.as_ref())
+            .body(contents.into())
+            .expect("file response should always be valid");
+
         Self {
             request,
-            inner: Inner::new(hyper::Body::wrap_stream(stream))
+            inner,
         }
     }
 
@@ -151,6 +153,19 @@ impl Response {
         self.status().is_successful()
     }
 
+    /// Get the [header list](https://fetch.spec.whatwg.org/#concept-response-header-list)
+    /// of this response.
+    pub fn headers(&self) -> &hyper::HeaderMap {
+        self.inner.headers()
+    }
+
+    /// Get the [status message](https://fetch.spec.whatwg.org/#concept-response-status-message)
+    /// of this response. Since HTTP/2 doesn't transmit the reason phrase,
+    /// this is the canonical reason phrase of the status code.
+    pub fn status_text(&self) -> &str {
+        self.inner.status().canonical_reason().unwrap_or_default()
+    }
+
     /// Get the [StatusCode] of this response.
     pub fn status(&self) -> StatusCode {
         self.inner.status().into()
@@ -118,21 +118,28 @@ impl FontKitFont {
         return_value
     }
 
+    /// Shape the `text` and invoke `f` for each glyph. When `vertical` is
+    /// set, the text is shaped top-to-bottom, such that the glyph positions
+    /// contain the vertical advances.
     #[instrument(skip(f))]
     fn glyph_iter<F>(
         &self,
         point_size: f32,
         text: &str,
         hints: TextHintingOptions,
+        vertical: bool,
         mut f: F
     )
             where F: FnMut(harfbuzz_rs::GlyphPosition, &Glyph) {
         self.with_size(point_size, |atlas| {
             let _harfbuzz_guard = trace_span!("with_harfbuzz_font").entered();
             self.font.with_harfbuzz_font(|font| {
-                let unicode_buffer = harfbuzz_rs::UnicodeBuffer::new()
-                    .add_str(text)
-                    .guess_segment_properties();
+                let mut unicode_buffer = harfbuzz_rs::UnicodeBuffer::new()
+                    .add_str(text);
+                if vertical {
+                    unicode_buffer = unicode_buffer.set_direction(harfbuzz_rs::Direction::Ttb);
+                }
+                let unicode_buffer = unicode_buffer.guess_segment_properties();
 
                 let features = resolve_hints_to_harfbuzz(hints);
                 let glyph_buffer = trace_span!("harfbuzz_rs::shape")
@@ -217,9 +224,11 @@ fn resolve_hints_to_harfbuzz(hints: TextHintingOptions) -> Vec<harfbuzz_rs::Feat
         EastAsianGlyphWidth::ProportionalWidth => features.push(Feature::new(TAG_PROPORTIONAL_WIDTHS, 1, ..)),
     }
 
-    if !hints.kerning {
-        features.push(Feature::new(TAG_KERN, 0, ..));
-    }
+    // https://drafts.csswg.org/css-fonts/#font-kerning-prop
+    // HarfBuzz enables kerning (both the GPOS and the legacy `kern` table) by
+    // default, but we're explicit about it, such that `font-kerning: none`
+    // is the only way to turn it off.
+    features.push(Feature::new(TAG_KERN, hints.kerning as u32, ..));
 
     match hints.ligatures {
         // https://drafts.csswg.org/css-fonts/#font-variant-ligatures-none-value
@@ -269,7 +278,7 @@ fn resolve_hints_to_harfbuzz(hints: TextHintingOptions) -> Vec<harfbuzz_rs::Feat
         }
     }
 
-    if !hints.kerning {
+    if hints.ruby {
         features.push(Feature::new(TAG_RUBY, 1, ..));
     }
 
@@ -294,13 +303,33 @@ impl retina_gfx_font::Font for FontKitFont {
             height,
         );
 
-        self.glyph_iter(point_size, text, hints, |position, _glyph| {
+        self.glyph_iter(point_size, text, hints, false, |position, _glyph| {
             size.width += position.x_advance as f32 / typographic_unit_conversion_factor;
         });
 
         size
     }
 
+    fn calculate_vertical_size(&self, point_size: f32, text: &str, hints: TextHintingOptions) -> Size2D<f32> {
+        let point_size = point_size * scale_factor() as f32;
+        let typographic_unit_conversion_factor = self.metrics.units_per_em as f32 / point_size;
+
+        let width = (self.metrics.ascent - self.metrics.descent)
+            / typographic_unit_conversion_factor;
+        let mut size = Size2D::new(
+            width,
+            0.0,
+        );
+
+        // The vertical advances point downwards, which is the negative
+        // y-direction in the coordinate system of HarfBuzz.
+        self.glyph_iter(point_size, text, hints, true, |position, _glyph| {
+            size.height -= position.y_advance as f32 / typographic_unit_conversion_factor;
+        });
+
+        size
+    }
+
     fn baseline_offset(&self, point_size: f32) -> f32 {
         let point_size = point_size * scale_factor() as f32;
         let typographic_unit_conversion_factor = self.metrics.units_per_em as f32 / point_size;
@@ -312,6 +341,38 @@ impl retina_gfx_font::Font for FontKitFont {
         &self.descriptor
     }
 
+    fn glyph_bounds(&self, codepoint: char, point_size: f32) -> Rect<f32> {
+        let point_size = point_size * scale_factor() as f32;
+        let typographic_unit_conversion_factor = self.metrics.units_per_em as f32 / point_size;
+
+        let font = self.font.borrow_font();
+        let Some(glyph_id) = font.glyph_for_char(codepoint) else {
+            return Rect::zero();
+        };
+
+        let bounds = match font.raster_bounds(
+            glyph_id,
+            point_size,
+            Transform2F::default(),
+            font_kit::hinting::HintingOptions::None,
+            font_kit::canvas::RasterizationOptions::SubpixelAa,
+        ) {
+            Ok(bounds) => bounds,
+            Err(e) => {
+                warn!("Failed to get raster bounds of glyph {glyph_id} for {codepoint:?}: {e:?}");
+                return Rect::zero();
+            }
+        };
+
+        // The raster bounds are relative to the baseline.
+        let baseline = self.metrics.ascent / typographic_unit_conversion_factor;
+
+        Rect::new(
+            Point2D::new(bounds.origin_x() as f32, baseline + bounds.origin_y() as f32),
+            Size2D::new(bounds.width() as f32, bounds.height() as f32),
+        )
+    }
+
     fn underline_position(&self, point_size: f32) -> f32 {
         let point_size = point_size * scale_factor() as f32;
         let typographic_unit_conversion_factor = self.metrics.units_per_em as f32 / point_size;
@@ -343,7 +404,7 @@ impl retina_gfx_font::Font for FontKitFont {
         let renderer = FontTextureMaterialRenderer::get(&painter.artwork().context);
         renderer.prepare(painter, color);
 
-        self.glyph_iter(font_size, text, hints, |glyph_position, glyph| {
+        self.glyph_iter(font_size, text, hints, false, |glyph_position, glyph| {
             let x_offset = glyph_position.x_offset as f32 / typographic_unit_conversion_factor;
             let y_offset = glyph_position.y_offset as f32 / typographic_unit_conversion_factor;
             let glyph_rect = Rect::new(
@@ -354,24 +415,50 @@ impl retina_gfx_font::Font for FontKitFont {
                 glyph.size.cast(),
             ).cast();
 
-            // If the texture is absent, this glyph is invisible (e.g. whitespace).
-            if let Some(texture_view) = glyph.texture_view.as_ref() {
-                let bind_group_entry = wgpu::BindGroupEntry {
-                    binding: 3,
-                    resource: renderer.uniform_buffer.as_entire_binding(),
-                };
-
-                painter.paint_rect_textured_with(
-                    glyph_rect,
-                    texture_view,
-                    Some(&renderer.renderer),
-                    Some(bind_group_entry),
-                );
-            }
+            paint_glyph(renderer, painter, glyph, glyph_rect);
 
             position.x += glyph_position.x_advance as f32 / typographic_unit_conversion_factor;
         });
     }
+
+    #[instrument(skip_all)]
+    fn paint_vertical(
+        &self,
+        text: &str,
+        color: retina_common::Color,
+        mut position: euclid::default::Point2D<f32>,
+        font_size: f32,
+        hints: TextHintingOptions,
+        painter: &mut retina_gfx::Painter,
+    ) {
+        let font_size = font_size * scale_factor() as f32;
+        let typographic_unit_conversion_factor = self.metrics.units_per_em as f32 / font_size;
+
+        // The vertical origin of the glyphs is at the center of the column.
+        position.x += (self.metrics.ascent - self.metrics.descent) / typographic_unit_conversion_factor / 2.0;
+
+        let renderer = FontTextureMaterialRenderer::get(&painter.artwork().context);
+        renderer.prepare(painter, color);
+
+        self.glyph_iter(font_size, text, hints, true, |glyph_position, glyph| {
+            // The offsets point from the vertical origin to the horizontal
+            // origin (on the baseline) of the glyph, of which the y-axis
+            // points upwards.
+            let x_offset = glyph_position.x_offset as f32 / typographic_unit_conversion_factor;
+            let y_offset = glyph_position.y_offset as f32 / typographic_unit_conversion_factor;
+            let glyph_rect = Rect::new(
+                Point2D::new(
+                    (position.x + glyph.origin.x() + x_offset).round(),
+                    (position.y - y_offset - glyph.typographic_bounds.max_y()).round(),
+                ),
+                glyph.size.cast(),
+            ).cast();
+
+            paint_glyph(renderer, painter, glyph, glyph_rect);
+
+            position.y -= glyph_position.y_advance as f32 / typographic_unit_conversion_factor;
+        });
+    }
 }
 
 impl Debug for FontKitFont {
@@ -384,6 +471,31 @@ impl Debug for FontKitFont {
     }
 }
 
+/// Paint the texture of the `glyph` in the `glyph_rect`. If the texture is
+/// absent, this glyph is invisible (e.g. whitespace).
+fn paint_glyph(
+    renderer: &FontTextureMaterialRenderer,
+    painter: &mut retina_gfx::Painter,
+    glyph: &Glyph,
+    glyph_rect: Rect<f64>,
+) {
+    let Some(texture_view) = glyph.texture_view.as_ref() else {
+        return;
+    };
+
+    let bind_group_entry = wgpu::BindGroupEntry {
+        binding: 3,
+        resource: renderer.uniform_buffer.as_entire_binding(),
+    };
+
+    painter.paint_rect_textured_with(
+        glyph_rect,
+        texture_view,
+        Some(&renderer.renderer),
+        Some(bind_group_entry),
+    );
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 struct GlyphId(pub u32);
 
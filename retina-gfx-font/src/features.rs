@@ -83,7 +83,8 @@ impl Default for TextHintingOptions {
             east_asian_form: EastAsianGlyphForm::default(),
             east_asian_width: EastAsianGlyphWidth::default(),
             ligatures: LigatureMode::default(),
-            kerning: false,
+            // `font-kerning: auto` and `normal` both enable kerning.
+            kerning: true,
             ruby: false,
             typographic_position: TypographicPositionMode::Normal,
         }
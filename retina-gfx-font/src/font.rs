@@ -1,7 +1,7 @@
 // Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
 // All Rights Reserved.
 
-use euclid::default::{Point2D, Size2D};
+use euclid::default::{Point2D, Rect, Size2D};
 use retina_common::Color;
 use retina_gfx::Painter;
 
@@ -13,8 +13,19 @@ use crate::{
 pub trait Font {
     fn calculate_size(&self, size: f32, text: &str, hints: TextHintingOptions) -> Size2D<f32>;
 
+    /// Calculate the size of the `text` when it is set vertically, with the
+    /// glyphs upright from top to bottom. The height is the sum of the
+    /// vertical advances, and the width that of the em box.
+    fn calculate_vertical_size(&self, size: f32, text: &str, hints: TextHintingOptions) -> Size2D<f32>;
+
     fn descriptor(&self) -> &FontDescriptor;
 
+    /// Get the ink bounding box of the glyph of `codepoint`, relative to the
+    /// top-left of the text run it's painted in (i.e. the same position as
+    /// given to [`Font::paint`]). Returns an empty rectangle if the font
+    /// doesn't have a glyph for this character.
+    fn glyph_bounds(&self, codepoint: char, point_size: f32) -> Rect<f32>;
+
     fn baseline_offset(&self, point_size: f32) -> f32;
     fn underline_position(&self, point_size: f32) -> f32;
     fn underline_thickness(&self, point_size: f32) -> f32;
@@ -28,4 +39,16 @@ pub trait Font {
         hints: TextHintingOptions,
         painter: &mut Painter
     );
+
+    /// Paint the `text` vertically, in the column of which the top-left is
+    /// at `position`. See [`Font::calculate_vertical_size`].
+    fn paint_vertical(
+        &self,
+        text: &str,
+        color: Color,
+        position: Point2D<f32>,
+        font_size: f32,
+        hints: TextHintingOptions,
+        painter: &mut Painter
+    );
 }
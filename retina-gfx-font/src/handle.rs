@@ -7,7 +7,12 @@ use std::{
     sync::Arc,
 };
 
-use crate::Font;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+    Font,
+    TextHintingOptions,
+};
 
 #[derive(Clone)]
 pub struct FontHandle {
@@ -21,6 +26,44 @@ impl FontHandle {
             font,
         }
     }
+
+    /// Finds the largest prefix of `text` that fits within `max_width` when
+    /// rendered, and returns its length in bytes. The text is only split at
+    /// grapheme cluster boundaries, and `0` is returned if not even the first
+    /// grapheme cluster fits.
+    ///
+    /// This uses a binary search, assuming that the width of a prefix never
+    /// exceeds the width of a longer prefix.
+    pub fn split_at_width(
+        &self,
+        text: &str,
+        max_width: f32,
+        size: f32,
+        hints: TextHintingOptions,
+    ) -> usize {
+        let boundaries: Vec<usize> = text.grapheme_indices(true)
+            .map(|(index, grapheme)| index + grapheme.len())
+            .collect();
+
+        // The amount of boundaries (i.e. grapheme clusters) that fit.
+        let mut low = 0;
+        let mut high = boundaries.len();
+        while low < high {
+            let middle = low + (high - low).div_ceil(2);
+            let prefix = &text[..boundaries[middle - 1]];
+
+            if self.calculate_size(size, prefix, hints).width <= max_width {
+                low = middle;
+            } else {
+                high = middle - 1;
+            }
+        }
+
+        match low {
+            0 => 0,
+            count => boundaries[count - 1],
+        }
+    }
 }
 
 impl Debug for FontHandle {
@@ -0,0 +1,40 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use euclid::default::{Point2D, Size2D};
+use retina_common::Color;
+
+/// A gradient painted by [`Painter::paint_rect_gradient()`](crate::Painter::paint_rect_gradient),
+/// of which the geometry is in pixels relative to the top left corner of the
+/// painted rect.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gradient {
+    pub kind: GradientKind,
+
+    /// The color stops, ordered by their offsets. Before the first and after
+    /// the last stop, the color of that stop is painted.
+    pub stops: Vec<GradientStop>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GradientKind {
+    /// The offsets of the stops are along the line from `start` (`0.0`) to
+    /// `end` (`1.0`), and the colors are constant perpendicular to it.
+    Linear {
+        start: Point2D<f64>,
+        end: Point2D<f64>,
+    },
+
+    /// The offsets of the stops are the distances from the `center`, where
+    /// `1.0` is the ellipse with the given `radii`.
+    Radial {
+        center: Point2D<f64>,
+        radii: Size2D<f64>,
+    },
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GradientStop {
+    pub offset: f64,
+    pub color: Color,
+}
@@ -0,0 +1,20 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+/// Hints how a texture should be sampled when it is painted at a different
+/// size than its own, mirroring the CSS [`image-rendering`][spec] property.
+///
+/// [spec]: https://drafts.csswg.org/css-images/#the-image-rendering
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ImageRendering {
+    /// Smoothly interpolate between the pixels.
+    #[default]
+    Auto,
+
+    /// Scale using the nearest neighbor, such that the pixels stay sharp.
+    Pixelated,
+
+    /// Preserve contrast and edges; this currently behaves like
+    /// [`ImageRendering::Pixelated`].
+    CrispEdges,
+}
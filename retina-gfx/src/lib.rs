@@ -4,9 +4,13 @@
 pub mod canvas;
 pub mod context;
 mod future;
+mod gradient;
+mod image_rendering;
 pub mod math;
 pub mod material;
 mod painter;
+pub mod raster;
+mod shadow;
 mod texture;
 pub mod vertex;
 pub mod window;
@@ -22,7 +26,14 @@ pub use winit::{
 pub use self::{
     context::Context,
     future::SubmissionFuture,
+    gradient::{
+        Gradient,
+        GradientKind,
+        GradientStop,
+    },
+    image_rendering::ImageRendering,
     painter::Painter,
+    shadow::ShadowParams,
     texture::Texture,
     window::{
         event_proxy::WindowEventProxy,
@@ -40,7 +51,11 @@ pub(crate) use self::{
     painter::Artwork,
     material::{
         ColorMaterialRenderer,
+        GradientMaterialRenderer,
+        OpacityMaterialRenderer,
+        ShadowMaterialRenderer,
         TextureMaterialRenderer,
+        TransformMaterialRenderer,
     },
 };
 
@@ -2,13 +2,27 @@
 // All Rights Reserved.
 
 mod color;
+mod gradient;
+mod opacity;
+mod shadow;
 mod texture;
+mod transform;
 
 use tracing::instrument;
 
+pub(crate) use self::{
+    gradient::{GRADIENT_UNIFORM_SIZE, MAX_GRADIENT_COLOR_STOPS},
+    opacity::OPACITY_UNIFORM_SIZE,
+    shadow::SHADOW_UNIFORM_SIZE,
+};
+
 pub use self::{
     color::ColorMaterialRenderer,
+    gradient::GradientMaterialRenderer,
+    opacity::OpacityMaterialRenderer,
+    shadow::ShadowMaterialRenderer,
     texture::TextureMaterialRenderer,
+    transform::TransformMaterialRenderer,
 };
 
 #[derive(Debug)]
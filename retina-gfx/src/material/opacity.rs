@@ -0,0 +1,175 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use std::sync::OnceLock;
+
+use wgpu::util::DeviceExt;
+
+use crate::vertex::textured_vertex;
+
+use super::{
+    MaterialRenderer,
+    MaterialRendererBase,
+};
+
+static INSTANCE: OnceLock<OpacityMaterialRenderer> = OnceLock::new();
+
+/// The size of the uniform of the opacity shader: the transform and the
+/// parameters.
+pub(crate) const OPACITY_UNIFORM_SIZE: usize = (4 + 1) * 16;
+
+/// The renderer that composites an intermediate texture (i.e. the contents
+/// of a box with the CSS `opacity` property) with a given opacity.
+///
+/// Since the intermediate texture is painted onto a transparent texture, its
+/// colors are already multiplied by their alpha, so it is blended using
+/// premultiplied alpha blending.
+#[derive(Debug)]
+pub struct OpacityMaterialRenderer {
+    pub(crate) base: MaterialRendererBase,
+    pub(crate) texture_bind_group_layout: wgpu::BindGroupLayout,
+    pub(crate) uniform_buffer: wgpu::Buffer,
+    pub(crate) sampler: wgpu::Sampler,
+}
+
+impl OpacityMaterialRenderer {
+    pub fn get(device: &wgpu::Device) -> &'static Self {
+        INSTANCE.get_or_init(|| {
+            Self::new(device)
+        })
+    }
+
+    fn new(device: &wgpu::Device) -> Self {
+        let texture_bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        multisampled: false,
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+            label: Some("Opacity Material Bind Group Layout"),
+        });
+
+        let render_pipeline_layout = device.create_pipeline_layout(
+            &wgpu::PipelineLayoutDescriptor {
+                label: Some("Opacity Material Render Pipeline Layout"),
+                bind_group_layouts: &[&texture_bind_group_layout],
+                push_constant_ranges: &[],
+            }
+        );
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Opacity Material Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../vertex/opacity.wgsl").into()),
+        });
+
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Opacity Material Render Pipeline"),
+            layout: Some(&render_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[textured_vertex::TexturedVertex::desc()],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: wgpu::TextureFormat::Bgra8UnormSrgb,
+                    blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: Some(wgpu::Face::Back),
+                // Setting this to anything other than Fill requires Features::POLYGON_MODE_LINE
+                // or Features::POLYGON_MODE_POINT
+                polygon_mode: wgpu::PolygonMode::Fill,
+                // Requires Features::DEPTH_CLIP_CONTROL
+                unclipped_depth: false,
+                // Requires Features::CONSERVATIVE_RASTERIZATION
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+        });
+
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Opacity Material Vertex Buffer"),
+            contents: bytemuck::cast_slice(textured_vertex::VERTICES),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Opacity Material Index Buffer"),
+            contents: bytemuck::cast_slice(textured_vertex::INDICES),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+        let num_indices = textured_vertex::INDICES.len() as u32;
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let uniform_buffer = device.create_buffer_init(
+            &wgpu::util::BufferInitDescriptor {
+                label: Some("Opacity Material Uniform Buffer"),
+                contents: &[0; OPACITY_UNIFORM_SIZE],
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            }
+        );
+
+        Self {
+            base: MaterialRendererBase {
+                render_pipeline,
+                vertex_buffer,
+                index_buffer,
+                num_indices,
+            },
+            texture_bind_group_layout,
+            uniform_buffer,
+            sampler,
+        }
+    }
+}
+
+impl MaterialRenderer for OpacityMaterialRenderer {
+    fn base(&self) -> &MaterialRendererBase {
+        &self.base
+    }
+}
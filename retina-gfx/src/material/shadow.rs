@@ -0,0 +1,154 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use std::sync::OnceLock;
+
+use wgpu::util::DeviceExt;
+
+use crate::vertex::textured_vertex;
+
+use super::{
+    MaterialRenderer,
+    MaterialRendererBase,
+};
+
+static INSTANCE: OnceLock<ShadowMaterialRenderer> = OnceLock::new();
+
+/// The size of the uniform of the shadow shader: the transform, the color,
+/// the shape, the clip and the parameters.
+pub(crate) const SHADOW_UNIFORM_SIZE: usize = (4 + 1 + 1 + 1 + 1) * 16;
+
+/// The renderer of the shadows of boxes, of which the blur is computed per
+/// pixel by the fragment shader, instead of blurring a texture.
+#[derive(Debug)]
+pub struct ShadowMaterialRenderer {
+    pub(crate) base: MaterialRendererBase,
+    pub(crate) uniform_buffer: wgpu::Buffer,
+    pub(crate) bind_group: wgpu::BindGroup,
+}
+
+impl ShadowMaterialRenderer {
+    pub fn get(device: &wgpu::Device) -> &'static Self {
+        INSTANCE.get_or_init(|| {
+            Self::new(device)
+        })
+    }
+
+    fn new(device: &wgpu::Device) -> Self {
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+            label: Some("Shadow Material Bind Group Layout"),
+        });
+
+        let render_pipeline_layout = device.create_pipeline_layout(
+            &wgpu::PipelineLayoutDescriptor {
+                label: Some("Shadow Material Render Pipeline Layout"),
+                bind_group_layouts: &[&bind_group_layout],
+                push_constant_ranges: &[],
+            }
+        );
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Shadow Material Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../vertex/shadow.wgsl").into()),
+        });
+
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Shadow Material Render Pipeline"),
+            layout: Some(&render_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[textured_vertex::TexturedVertex::desc()],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: wgpu::TextureFormat::Bgra8UnormSrgb,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: Some(wgpu::Face::Back),
+                // Setting this to anything other than Fill requires Features::POLYGON_MODE_LINE
+                // or Features::POLYGON_MODE_POINT
+                polygon_mode: wgpu::PolygonMode::Fill,
+                // Requires Features::DEPTH_CLIP_CONTROL
+                unclipped_depth: false,
+                // Requires Features::CONSERVATIVE_RASTERIZATION
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+        });
+
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Shadow Material Vertex Buffer"),
+            contents: bytemuck::cast_slice(textured_vertex::VERTICES),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Shadow Material Index Buffer"),
+            contents: bytemuck::cast_slice(textured_vertex::INDICES),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+        let num_indices = textured_vertex::INDICES.len() as u32;
+
+        let uniform_buffer = device.create_buffer_init(
+            &wgpu::util::BufferInitDescriptor {
+                label: Some("Shadow Material Uniform Buffer"),
+                contents: &[0; SHADOW_UNIFORM_SIZE],
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            }
+        );
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: uniform_buffer.as_entire_binding(),
+                }
+            ],
+            label: Some("Shadow Material Bind Group"),
+        });
+
+        Self {
+            base: MaterialRendererBase {
+                render_pipeline,
+                vertex_buffer,
+                index_buffer,
+                num_indices,
+            },
+            uniform_buffer,
+            bind_group,
+        }
+    }
+}
+
+impl MaterialRenderer for ShadowMaterialRenderer {
+    fn base(&self) -> &MaterialRendererBase {
+        &self.base
+    }
+}
@@ -5,7 +5,10 @@ use std::sync::OnceLock;
 
 use wgpu::util::DeviceExt;
 
-use crate::vertex::textured_vertex;
+use crate::{
+    ImageRendering,
+    vertex::textured_vertex,
+};
 
 use super::{
     MaterialRenderer,
@@ -20,6 +23,10 @@ pub struct TextureMaterialRenderer {
     pub(crate) texture_bind_group_layout: wgpu::BindGroupLayout,
     pub(crate) uniform_buffer: wgpu::Buffer,
     pub(crate) sampler: wgpu::Sampler,
+
+    /// The sampler used for [`ImageRendering::Pixelated`] and
+    /// [`ImageRendering::CrispEdges`].
+    pub(crate) nearest_sampler: wgpu::Sampler,
 }
 
 impl TextureMaterialRenderer {
@@ -43,8 +50,8 @@ impl TextureMaterialRenderer {
             address_mode_v: wgpu::AddressMode::ClampToEdge,
             address_mode_w: wgpu::AddressMode::ClampToEdge,
             mag_filter: wgpu::FilterMode::Linear,
-            min_filter: wgpu::FilterMode::Nearest,
-            mipmap_filter: wgpu::FilterMode::Nearest,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::FilterMode::Linear,
             ..Default::default()
         });
 
@@ -162,6 +169,16 @@ impl TextureMaterialRenderer {
         });
         let num_indices = textured_vertex::INDICES.len() as u32;
 
+        let nearest_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Nearest,
+            min_filter: wgpu::FilterMode::Nearest,
+            mipmap_filter: wgpu::FilterMode::Nearest,
+            ..Default::default()
+        });
+
         let uniform_buffer = device.create_buffer_init(
             &wgpu::util::BufferInitDescriptor {
                 label: Some("Texture Material Uniform Buffer"),
@@ -186,6 +203,15 @@ impl TextureMaterialRenderer {
             texture_bind_group_layout,
             uniform_buffer,
             sampler,
+            nearest_sampler,
+        }
+    }
+
+    /// Get the sampler that implements the [`ImageRendering`] hint.
+    pub(crate) fn sampler_for(&self, rendering: ImageRendering) -> &wgpu::Sampler {
+        match rendering {
+            ImageRendering::Auto => &self.sampler,
+            ImageRendering::Pixelated | ImageRendering::CrispEdges => &self.nearest_sampler,
         }
     }
 }
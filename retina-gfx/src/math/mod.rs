@@ -10,14 +10,32 @@ use euclid::{
     Size2D,
 };
 
+/// Compute the matrix that projects the unit quad onto the `rect` in the
+/// viewport. The `transform` (in the coordinates of the viewport) is applied
+/// to the rect before the orthographic projection.
 pub fn project<VU, RU>(
     viewport: Size2D<f32, VU>,
     rect: Rect<f64, RU>,
+    transform: Option<&Transform3D<f32>>,
 ) -> [[f32; 4]; 4] {
-    let model = Transform3D::identity()
+    let mut model = Transform3D::identity()
         .then_scale(rect.size.width as f32, rect.size.height as f32, 1.0)
         .then_translate(Vector3D::new(rect.origin.x as f32, viewport.height - rect.size.height as f32 - rect.origin.y as f32, 0.0));
 
+    if let Some(transform) = transform {
+        // The y-axis of the model points upwards, whilst it points downwards
+        // in the coordinates of the transform.
+        let flip = Transform3D::scale(1.0, -1.0, 1.0)
+            .then_translate(Vector3D::new(0.0, viewport.height, 0.0));
+
+        // The result is flattened onto the plane of the viewport, otherwise
+        // the parts rotated towards or away from the viewer would be clipped
+        // by the near and far planes of the projection.
+        let flatten = Transform3D::scale(1.0, 1.0, 0.0);
+
+        model = model.then(&flip).then(transform).then(&flatten).then(&flip);
+    }
+
     let projection = Transform3D::ortho(
         0.0,
         viewport.width as f32,
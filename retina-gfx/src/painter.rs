@@ -7,21 +7,42 @@ use euclid::default::{
     Point2D,
     Rect,
     Size2D,
+    Transform3D,
+    Vector3D,
 };
 
 use retina_common::Color;
 use tracing::{instrument, trace_span};
 
-use crate::material::MaterialRenderer;
+use crate::material::{
+    MaterialRenderer,
+    GRADIENT_UNIFORM_SIZE,
+    MAX_GRADIENT_COLOR_STOPS,
+    OPACITY_UNIFORM_SIZE,
+    SHADOW_UNIFORM_SIZE,
+};
 use crate::{
     ColorMaterialRenderer,
     Context,
+    Gradient,
+    GradientKind,
+    GradientMaterialRenderer,
+    ImageRendering,
+    OpacityMaterialRenderer,
+    ShadowMaterialRenderer,
+    ShadowParams,
     SubmissionFuture,
     TextureMaterialRenderer,
+    TransformMaterialRenderer,
 };
 
 use crate::math;
 
+/// The maximum width and height of the intermediate texture of
+/// [`Painter::paint_transformed()`], which is well below the limits of most
+/// GPUs.
+const MAX_INTERMEDIATE_TEXTURE_SIZE: u32 = 4096;
+
 #[derive(Debug)]
 pub struct Artwork {
     pub context: Context,
@@ -50,6 +71,10 @@ pub struct Painter<'art> {
     viewport_position: Point2D<f64>,
     texture: &'art wgpu::Texture,
 
+    /// The stack of rects the painting is clipped to, of which the last one
+    /// is the intersection of all of them.
+    clip_rects: Vec<Rect<f64>>,
+
     command_encoder: wgpu::CommandEncoder,
 }
 
@@ -65,6 +90,7 @@ impl<'art> Painter<'art> {
             viewport_size,
             viewport_position: Point2D::new(0.0, 0.0),
             texture,
+            clip_rects: Vec::new(),
 
             command_encoder,
         }
@@ -109,6 +135,41 @@ impl<'art> Painter<'art> {
         self.viewport_rect().intersects(&rect.cast().cast_unit())
     }
 
+    /// Clip the painting to the given rect, until the matching call to
+    /// [`Painter::pop_clip_rect()`]. The clip is intersected with the current
+    /// clip rect, if any.
+    pub fn push_clip_rect<Unit>(&mut self, rect: euclid::Rect<f64, Unit>) {
+        let rect = rect.cast_unit();
+        let rect = match self.clip_rects.last() {
+            Some(clip) => clip.intersection(&rect).unwrap_or_else(Rect::zero),
+            None => rect,
+        };
+        self.clip_rects.push(rect);
+    }
+
+    /// Remove the clip rect pushed last using [`Painter::push_clip_rect()`].
+    pub fn pop_clip_rect(&mut self) {
+        let popped = self.clip_rects.pop();
+        debug_assert!(popped.is_some(), "pop_clip_rect() called without a clip rect");
+    }
+
+    /// Get the scissor rect in the coordinates of the texture, or `None` if
+    /// everything is clipped away.
+    fn scissor_rect(&self) -> Option<Rect<u32>> {
+        let viewport: Rect<f64> = Rect::new(Point2D::zero(), self.viewport_size.cast());
+
+        let rect = match self.clip_rects.last() {
+            Some(clip) => self.offset_rect(*clip).round_out().intersection(&viewport)?,
+            None => viewport,
+        };
+
+        if rect.is_empty() {
+            return None;
+        }
+
+        Some(rect.cast())
+    }
+
     #[instrument]
     pub fn clear(&mut self, clear_color: Color) {
         self.command_encoder.begin_render_pass(
@@ -148,8 +209,10 @@ impl<'art> Painter<'art> {
 
     #[instrument(skip_all)]
     pub fn paint_rect_colored<Unit>(&mut self, rect: euclid::Rect<f64, Unit>, color: Color) {
+        let Some(scissor) = self.scissor_rect() else { return };
+
         let rect = self.offset_rect(rect);
-        let transformation = math::project(self.viewport_size.cast(), rect);
+        let transformation = math::project(self.viewport_size.cast(), rect, None);
 
         let uniform: [[f32; 4]; 5] = [
             [
@@ -195,6 +258,7 @@ impl<'art> Painter<'art> {
             },
         );
 
+        set_scissor_rect(&mut render_pass, scissor);
         color_material_renderer.base().bind_to_render_pass(&mut render_pass);
         render_pass.set_bind_group(0, &color_material_renderer.color_bind_group, &[]);
         color_material_renderer.base().draw_once(&mut render_pass);
@@ -202,25 +266,224 @@ impl<'art> Painter<'art> {
         drop(render_pass);
     }
 
+    /// Fill the rect with a linear or radial gradient, of which the geometry
+    /// is relative to the top left corner of the rect.
+    ///
+    /// Only the first 16 color stops are painted, which is the maximum the
+    /// shader supports.
+    #[instrument(skip_all)]
+    pub fn paint_rect_gradient<Unit>(&mut self, rect: euclid::Rect<f64, Unit>, gradient: &Gradient) {
+        if gradient.stops.is_empty() || !self.viewport_rect().cast_unit().intersects(&rect) {
+            return;
+        }
+
+        let Some(scissor) = self.scissor_rect() else { return };
+
+        if gradient.stops.len() > MAX_GRADIENT_COLOR_STOPS {
+            log::warn!("Gradient has {} color stops, only the first {MAX_GRADIENT_COLOR_STOPS} are painted", gradient.stops.len());
+        }
+
+        let rect = self.offset_rect(rect);
+        let transformation = math::project(self.viewport_size.cast(), rect, None);
+
+        let (kind, geometry) = match gradient.kind {
+            GradientKind::Linear { start, end } => (0.0, [start.x, start.y, end.x, end.y]),
+            GradientKind::Radial { center, radii } => (1.0, [center.x, center.y, radii.width, radii.height]),
+        };
+        let stops = &gradient.stops[..gradient.stops.len().min(MAX_GRADIENT_COLOR_STOPS)];
+
+        let mut uniform = [[0.0_f32; 4]; GRADIENT_UNIFORM_SIZE / 16];
+        uniform[0..4].copy_from_slice(&transformation);
+        uniform[4] = [rect.size.width as f32, rect.size.height as f32, kind, stops.len() as f32];
+        uniform[5] = geometry.map(|value| value as f32);
+        for (index, stop) in stops.iter().enumerate() {
+            uniform[6 + index] = [
+                stop.color.red() as f32,
+                stop.color.green() as f32,
+                stop.color.blue() as f32,
+                stop.color.alpha() as f32,
+            ];
+            uniform[6 + MAX_GRADIENT_COLOR_STOPS + index / 4][index % 4] = stop.offset as f32;
+        }
+
+        let uniform: &[u8] = bytemuck::cast_slice(&uniform);
+        let renderer = GradientMaterialRenderer::get(self.artwork.context.device());
+
+        trace_span!("upload buffer").in_scope(|| {
+            let mut uniform_buffer_view = self.artwork.staging_belt.write_buffer(
+                &mut self.command_encoder,
+                &renderer.uniform_buffer,
+                0,
+                NonZeroU64::new(uniform.len() as _).unwrap(),
+                self.artwork.context.device(),
+            );
+            uniform_buffer_view.copy_from_slice(uniform);
+        });
+
+        let mut render_pass = self.command_encoder.begin_render_pass(
+            &wgpu::RenderPassDescriptor {
+                label: Some("(retina-gfx) Gradient Material Render Pass"),
+                color_attachments: &[Some(
+                    wgpu::RenderPassColorAttachment {
+                        view: &self.artwork.texture_view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Load,
+                            store: true,
+                        },
+                    },
+                )],
+                depth_stencil_attachment: None,
+            },
+        );
+
+        set_scissor_rect(&mut render_pass, scissor);
+        renderer.base().bind_to_render_pass(&mut render_pass);
+        render_pass.set_bind_group(0, &renderer.bind_group, &[]);
+        renderer.base().draw_once(&mut render_pass);
+    }
+
+    /// Paint the shadow of a box, of which the `rect` is the border box for
+    /// outer shadows, and the padding box for inset shadows. Outer shadows
+    /// are only painted outside of the `rect`, and inset shadows only inside
+    /// of it.
+    ///
+    /// # References
+    /// * [CSS Backgrounds and Borders Module Level 3 § 7.2. Shadow Blurring](https://drafts.csswg.org/css-backgrounds/#shadow-blur)
+    #[instrument(skip_all)]
+    pub fn paint_shadow<Unit>(&mut self, rect: euclid::Rect<f64, Unit>, params: &ShadowParams) {
+        let rect: Rect<f64> = self.offset_rect(rect).cast_unit();
+
+        // > the resulting shadow must approximate the image that would be
+        // > generated by applying to the shadow a Gaussian blur with a
+        // > standard deviation equal to half the blur radius
+        let sigma = params.blur_radius.max(0.0) / 2.0;
+
+        let spread_radius = if params.inset { -params.spread_radius } else { params.spread_radius };
+        let shape = rect.translate(params.offset).inflate(spread_radius, spread_radius);
+        let shape = Rect::new(
+            shape.origin.min(shape.center()),
+            shape.size.max(Size2D::zero()),
+        );
+
+        // Beyond three standard deviations, the blur is practically invisible.
+        let painted_rect = if params.inset {
+            rect
+        } else {
+            shape.inflate(sigma * 3.0, sigma * 3.0)
+        };
+
+        if params.color.alpha() <= 0.0 || painted_rect.is_empty() || !Rect::from_size(self.viewport_size.cast()).intersects(&painted_rect) {
+            return;
+        }
+
+        let Some(scissor) = self.scissor_rect() else { return };
+
+        let transformation = math::project(self.viewport_size.cast(), painted_rect, None);
+        let relative = |rect: Rect<f64>| {
+            let rect = rect.translate(-painted_rect.origin.to_vector());
+            [rect.min_x() as f32, rect.min_y() as f32, rect.max_x() as f32, rect.max_y() as f32]
+        };
+
+        let mut uniform = [[0.0_f32; 4]; SHADOW_UNIFORM_SIZE / 16];
+        uniform[0..4].copy_from_slice(&transformation);
+        uniform[4] = [
+            params.color.red() as f32,
+            params.color.green() as f32,
+            params.color.blue() as f32,
+            params.color.alpha() as f32,
+        ];
+        uniform[5] = relative(shape);
+        uniform[6] = relative(rect);
+        uniform[7] = [
+            painted_rect.size.width as f32,
+            painted_rect.size.height as f32,
+            sigma as f32,
+            if params.inset { 1.0 } else { 0.0 },
+        ];
+
+        let uniform: &[u8] = bytemuck::cast_slice(&uniform);
+        let renderer = ShadowMaterialRenderer::get(self.artwork.context.device());
+
+        trace_span!("upload buffer").in_scope(|| {
+            let mut uniform_buffer_view = self.artwork.staging_belt.write_buffer(
+                &mut self.command_encoder,
+                &renderer.uniform_buffer,
+                0,
+                NonZeroU64::new(uniform.len() as _).unwrap(),
+                self.artwork.context.device(),
+            );
+            uniform_buffer_view.copy_from_slice(uniform);
+        });
+
+        let mut render_pass = self.command_encoder.begin_render_pass(
+            &wgpu::RenderPassDescriptor {
+                label: Some("(retina-gfx) Shadow Material Render Pass"),
+                color_attachments: &[Some(
+                    wgpu::RenderPassColorAttachment {
+                        view: &self.artwork.texture_view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Load,
+                            store: true,
+                        },
+                    },
+                )],
+                depth_stencil_attachment: None,
+            },
+        );
+
+        set_scissor_rect(&mut render_pass, scissor);
+        renderer.base().bind_to_render_pass(&mut render_pass);
+        render_pass.set_bind_group(0, &renderer.bind_group, &[]);
+        renderer.base().draw_once(&mut render_pass);
+    }
+
     #[inline]
     pub fn paint_rect_textured<Unit>(&mut self, rect: euclid::Rect<f64, Unit>, texture_view: &wgpu::TextureView) {
         self.paint_rect_textured_with(rect, texture_view, None, None)
     }
 
-    #[instrument]
+    /// Paint an image, sampling the texture according to the `rendering`
+    /// hint (e.g. from the CSS `image-rendering` property).
+    #[inline]
+    pub fn paint_image<Unit>(
+        &mut self,
+        rect: euclid::Rect<f64, Unit>,
+        texture_view: &wgpu::TextureView,
+        rendering: ImageRendering,
+    ) {
+        self.paint_rect_textured_impl(rect, texture_view, None, None, rendering)
+    }
+
+    #[inline]
     pub fn paint_rect_textured_with<Unit>(
         &mut self,
         rect: euclid::Rect<f64, Unit>,
         texture_view: &wgpu::TextureView,
         renderer: Option<&TextureMaterialRenderer>,
         extra_bind_group_entry: Option<wgpu::BindGroupEntry>,
+    ) {
+        self.paint_rect_textured_impl(rect, texture_view, renderer, extra_bind_group_entry, ImageRendering::Auto)
+    }
+
+    #[instrument]
+    fn paint_rect_textured_impl<Unit>(
+        &mut self,
+        rect: euclid::Rect<f64, Unit>,
+        texture_view: &wgpu::TextureView,
+        renderer: Option<&TextureMaterialRenderer>,
+        extra_bind_group_entry: Option<wgpu::BindGroupEntry>,
+        rendering: ImageRendering,
     ) {
         if !self.viewport_rect().cast_unit().intersects(&rect) {
             return;
         }
 
+        let Some(scissor) = self.scissor_rect() else { return };
+
         let rect = self.offset_rect(rect);
-        let transformation = math::project(self.viewport_size.cast(), rect);
+        let transformation = math::project(self.viewport_size.cast(), rect, None);
         let uniform: &[u8] = bytemuck::cast_slice(&transformation);
 
         let renderer = renderer.unwrap_or_else(|| {
@@ -245,7 +508,7 @@ impl<'art> Painter<'art> {
             },
             wgpu::BindGroupEntry {
                 binding: 1,
-                resource: wgpu::BindingResource::Sampler(&renderer.sampler),
+                resource: wgpu::BindingResource::Sampler(renderer.sampler_for(rendering)),
             },
             wgpu::BindGroupEntry {
                 binding: 2,
@@ -296,12 +559,246 @@ impl<'art> Painter<'art> {
         });
 
         trace_span!("bind and draw").in_scope(|| {
+            set_scissor_rect(&mut render_pass, scissor);
+            renderer.base().bind_to_render_pass(&mut render_pass);
+            render_pass.set_bind_group(0, &bind_group, &[]);
+            renderer.base().draw_once(&mut render_pass);
+        });
+    }
+
+    /// Paint the contents painted by `paint` in the `rect` (in the
+    /// coordinates of the page) into an intermediate texture, and composite
+    /// it using the given `transform`, which is also in the coordinates of
+    /// the page.
+    ///
+    /// The contents aren't clipped by the clip rects of this painter, but the
+    /// transformed result is.
+    #[instrument(skip_all)]
+    pub fn paint_transformed<F>(&mut self, rect: Rect<f64>, transform: &Transform3D<f32>, paint: F)
+            where F: FnOnce(&mut Painter) {
+        let Some(scissor) = self.scissor_rect() else { return };
+        let Some((artwork, rect)) = self.paint_intermediate_texture(rect, paint) else { return };
+        let context = self.artwork.context.clone();
+
+        // Convert the transform to the coordinates of the viewport.
+        let viewport_position = self.viewport_position.cast::<f32>();
+        let transform = Transform3D::translation(viewport_position.x, viewport_position.y, 0.0)
+            .then(transform)
+            .then_translate(Vector3D::new(-viewport_position.x, -viewport_position.y, 0.0));
+
+        let transformation = math::project(self.viewport_size.cast(), self.offset_rect(rect), Some(&transform));
+        let uniform: &[u8] = bytemuck::cast_slice(&transformation);
+
+        let renderer = TransformMaterialRenderer::get(context.device());
+
+        trace_span!("upload buffer").in_scope(|| {
+            let mut uniform_buffer_view = self.artwork.staging_belt.write_buffer(
+                &mut self.command_encoder,
+                &renderer.uniform_buffer,
+                0,
+                NonZeroU64::new(uniform.len() as _).unwrap(),
+                context.device(),
+            );
+            uniform_buffer_view.copy_from_slice(uniform);
+        });
+
+        let bind_group = trace_span!("create bind group").in_scope(|| {
+            context.device().create_bind_group(
+                &wgpu::BindGroupDescriptor {
+                    layout: &renderer.texture_bind_group_layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: wgpu::BindingResource::TextureView(&artwork.texture_view),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: wgpu::BindingResource::Sampler(&renderer.sampler),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 2,
+                            resource: renderer.uniform_buffer.as_entire_binding(),
+                        },
+                    ],
+                    label: Some("(retina-gfx) Transform Material Bind Group"),
+                }
+            )
+        });
+
+        let mut render_pass = trace_span!("create render pass").in_scope(|| {
+            self.command_encoder.begin_render_pass(
+                &wgpu::RenderPassDescriptor {
+                    label: Some("(retina-gfx) Transform Material Render Pass"),
+                    color_attachments: &[Some(
+                        wgpu::RenderPassColorAttachment {
+                            view: &self.artwork.texture_view,
+                            resolve_target: None,
+                            ops: wgpu::Operations {
+                                load: wgpu::LoadOp::Load,
+                                store: true,
+                            },
+                        },
+                    )],
+                    depth_stencil_attachment: None,
+                },
+            )
+        });
+
+        trace_span!("bind and draw").in_scope(|| {
+            set_scissor_rect(&mut render_pass, scissor);
+            renderer.base().bind_to_render_pass(&mut render_pass);
+            render_pass.set_bind_group(0, &bind_group, &[]);
+            renderer.base().draw_once(&mut render_pass);
+        });
+    }
+
+    /// Paint the contents painted by `paint` in the `rect` (in the
+    /// coordinates of the page) into an intermediate texture, such that they
+    /// are flattened and can be composited as a whole, and the opacity of
+    /// e.g. overlapping descendants doesn't compound.
+    ///
+    /// The contents aren't clipped by the clip rects of this painter, but the
+    /// composited result is.
+    #[instrument(skip_all)]
+    pub fn paint_with_opacity<F>(&mut self, rect: Rect<f64>, opacity: f32, paint: F)
+            where F: FnOnce(&mut Painter) {
+        if opacity >= 1.0 {
+            paint(self);
+            return;
+        }
+
+        if opacity <= 0.0 {
+            return;
+        }
+
+        // Without a transform, only the part inside the viewport is visible.
+        let Some(rect) = rect.intersection(&self.viewport_rect()) else { return };
+
+        let Some(scissor) = self.scissor_rect() else { return };
+        let Some((artwork, rect)) = self.paint_intermediate_texture(rect, paint) else { return };
+        let context = self.artwork.context.clone();
+
+        let transformation = math::project(self.viewport_size.cast(), self.offset_rect(rect), None);
+        let mut uniform = [[0.0_f32; 4]; OPACITY_UNIFORM_SIZE / 16];
+        uniform[0..4].copy_from_slice(&transformation);
+        uniform[4][0] = opacity;
+        let uniform: &[u8] = bytemuck::cast_slice(&uniform);
+
+        let renderer = OpacityMaterialRenderer::get(context.device());
+
+        trace_span!("upload buffer").in_scope(|| {
+            let mut uniform_buffer_view = self.artwork.staging_belt.write_buffer(
+                &mut self.command_encoder,
+                &renderer.uniform_buffer,
+                0,
+                NonZeroU64::new(uniform.len() as _).unwrap(),
+                context.device(),
+            );
+            uniform_buffer_view.copy_from_slice(uniform);
+        });
+
+        let bind_group = trace_span!("create bind group").in_scope(|| {
+            context.device().create_bind_group(
+                &wgpu::BindGroupDescriptor {
+                    layout: &renderer.texture_bind_group_layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: wgpu::BindingResource::TextureView(&artwork.texture_view),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: wgpu::BindingResource::Sampler(&renderer.sampler),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 2,
+                            resource: renderer.uniform_buffer.as_entire_binding(),
+                        },
+                    ],
+                    label: Some("(retina-gfx) Opacity Material Bind Group"),
+                }
+            )
+        });
+
+        let mut render_pass = trace_span!("create render pass").in_scope(|| {
+            self.command_encoder.begin_render_pass(
+                &wgpu::RenderPassDescriptor {
+                    label: Some("(retina-gfx) Opacity Material Render Pass"),
+                    color_attachments: &[Some(
+                        wgpu::RenderPassColorAttachment {
+                            view: &self.artwork.texture_view,
+                            resolve_target: None,
+                            ops: wgpu::Operations {
+                                load: wgpu::LoadOp::Load,
+                                store: true,
+                            },
+                        },
+                    )],
+                    depth_stencil_attachment: None,
+                },
+            )
+        });
+
+        trace_span!("bind and draw").in_scope(|| {
+            set_scissor_rect(&mut render_pass, scissor);
             renderer.base().bind_to_render_pass(&mut render_pass);
             render_pass.set_bind_group(0, &bind_group, &[]);
             renderer.base().draw_once(&mut render_pass);
         });
     }
 
+    /// Paint the contents painted by `paint` in the `rect` into a new
+    /// transparent texture, which is submitted before the commands recorded
+    /// by this painter. Returns the texture and the rect it covers, or `None`
+    /// if the rect is empty.
+    fn paint_intermediate_texture<F>(&self, rect: Rect<f64>, paint: F) -> Option<(Artwork, Rect<f64>)>
+            where F: FnOnce(&mut Painter) {
+        let rect = rect.round_out();
+        let size = Size2D::new(
+            (rect.size.width as u32).min(MAX_INTERMEDIATE_TEXTURE_SIZE),
+            (rect.size.height as u32).min(MAX_INTERMEDIATE_TEXTURE_SIZE),
+        );
+        if size.is_empty() {
+            return None;
+        }
+        let rect = Rect::new(rect.origin, size.cast());
+
+        let context = self.artwork.context.clone();
+        let texture = context.device().create_texture(&wgpu::TextureDescriptor {
+            label: Some("(retina-gfx) Intermediate Texture"),
+            dimension: wgpu::TextureDimension::D2,
+            size: wgpu::Extent3d {
+                width: size.width,
+                height: size.height,
+                ..Default::default()
+            },
+            format: wgpu::TextureFormat::Bgra8UnormSrgb,
+            mip_level_count: 1,
+            sample_count: 1,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[
+                wgpu::TextureFormat::Bgra8UnormSrgb,
+            ],
+        });
+        let mut artwork = Artwork::new(&context, texture.create_view(&wgpu::TextureViewDescriptor::default()));
+
+        // The intermediate texture is submitted before the commands recorded
+        // by this painter, which is fine since it doesn't depend on them.
+        trace_span!("paint intermediate texture").in_scope(|| {
+            let command_encoder = context.device().create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("(retina-gfx) Intermediate Command Encoder"),
+            });
+
+            let mut painter = Painter::new(&mut artwork, command_encoder, &texture, size)
+                .with_viewport_position(rect.origin);
+            painter.clear(Color::TRANSPARENT);
+            paint(&mut painter);
+            painter.submit_fast();
+        });
+
+        Some((artwork, rect))
+    }
+
     #[instrument]
     #[must_use]
     fn post_submissions(&mut self) -> wgpu::SubmissionIndex {
@@ -342,3 +839,7 @@ impl<'art> Painter<'art> {
         SubmissionFuture::new(self.artwork.context.clone(), submission_index)
     }
 }
+
+fn set_scissor_rect(render_pass: &mut wgpu::RenderPass, rect: Rect<u32>) {
+    render_pass.set_scissor_rect(rect.origin.x, rect.origin.y, rect.size.width, rect.size.height);
+}
@@ -0,0 +1,617 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use std::{
+    f32::consts::{FRAC_PI_2, TAU},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use font_kit::handle::Handle;
+use retina_common::Color;
+use strum::EnumString;
+use tiny_skia::{
+    BlendMode,
+    FillRule,
+    FilterQuality,
+    Paint,
+    Path,
+    PathBuilder,
+    Pattern,
+    PixmapMut,
+    PixmapRef,
+    Rect,
+    SpreadMode,
+    Stroke,
+    Transform,
+};
+
+use super::{CanvasFont, RasterImage, RasterSurface};
+
+/// The [`CanvasRenderingContext2D`][spec] of a `<canvas>` element, which
+/// draws onto a [`RasterSurface`], i.e. the output bitmap of the canvas. This
+/// is a handle, and clones refer to the same context.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/canvas.html#canvasrenderingcontext2d
+#[derive(Clone, Debug)]
+pub struct CanvasRenderingContext2D {
+    inner: Arc<Mutex<ContextInner>>,
+}
+
+#[derive(Debug)]
+struct ContextInner {
+    surface: Arc<dyn RasterSurface>,
+    state: DrawingState,
+
+    /// The states pushed by [`CanvasRenderingContext2D::save()`].
+    state_stack: Vec<DrawingState>,
+
+    /// The [current default path][spec], of which the points are already
+    /// transformed by the transformation matrix at the time they were added.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/canvas.html#current-default-path
+    path: PathBuilder,
+
+    /// The system font of the current [`CanvasFont`], which is looked up the
+    /// first time text is drawn.
+    font_handle: Option<Handle>,
+}
+
+/// The [drawing state][spec] that is saved and restored by `save()` and
+/// `restore()`.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/canvas.html#drawing-state
+#[derive(Clone, Debug)]
+struct DrawingState {
+    transform: Transform,
+    fill_style: Color,
+    stroke_style: Color,
+    line_width: f32,
+    font: CanvasFont,
+}
+
+impl Default for DrawingState {
+    fn default() -> Self {
+        Self {
+            transform: Transform::identity(),
+            fill_style: Color::BLACK,
+            stroke_style: Color::BLACK,
+            line_width: 1.0,
+            font: CanvasFont::default(),
+        }
+    }
+}
+
+/// The [fill rule][spec] of the `fill()` method.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/canvas.html#canvasfillrule
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[derive(EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum CanvasFillRule {
+    #[default]
+    NonZero,
+    EvenOdd,
+}
+
+/// The errors thrown by the methods of the [`CanvasRenderingContext2D`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CanvasError {
+    /// The radius of an arc is negative, which is an `IndexSizeError`.
+    NegativeRadius,
+}
+
+impl CanvasRenderingContext2D {
+    pub fn new(surface: Arc<dyn RasterSurface>) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(ContextInner {
+                surface,
+                state: DrawingState::default(),
+                state_stack: Vec::new(),
+                path: PathBuilder::new(),
+                font_handle: None,
+            })),
+        }
+    }
+
+    fn inner(&self) -> MutexGuard<'_, ContextInner> {
+        self.inner.lock().unwrap()
+    }
+
+    /// Push the current drawing state onto the stack.
+    pub fn save(&self) {
+        let mut inner = self.inner();
+        let state = inner.state.clone();
+        inner.state_stack.push(state);
+    }
+
+    /// Pop the top of the stack and make it the current drawing state. This
+    /// does nothing if the stack is empty.
+    pub fn restore(&self) {
+        let mut inner = self.inner();
+        if let Some(state) = inner.state_stack.pop() {
+            if state.font != inner.state.font {
+                inner.font_handle = None;
+            }
+            inner.state = state;
+        }
+    }
+
+    pub fn translate(&self, x: f32, y: f32) {
+        self.update_transform(|transform| transform.pre_translate(x, y));
+    }
+
+    pub fn rotate(&self, angle: f32) {
+        let (sin, cos) = angle.sin_cos();
+        self.transform(cos, sin, -sin, cos, 0.0, 0.0);
+    }
+
+    pub fn scale(&self, x: f32, y: f32) {
+        self.update_transform(|transform| transform.pre_scale(x, y));
+    }
+
+    /// Multiply the transformation matrix with the matrix
+    /// `[a c e; b d f; 0 0 1]`.
+    pub fn transform(&self, a: f32, b: f32, c: f32, d: f32, e: f32, f: f32) {
+        self.update_transform(|transform| transform.pre_concat(Transform::from_row(a, b, c, d, e, f)));
+    }
+
+    fn update_transform(&self, f: impl FnOnce(Transform) -> Transform) {
+        let mut inner = self.inner();
+        let transform = f(inner.state.transform);
+
+        // Non-finite arguments are ignored.
+        if transform.is_finite() {
+            inner.state.transform = transform;
+        }
+    }
+
+    pub fn fill_style(&self) -> Color {
+        self.inner().state.fill_style
+    }
+
+    pub fn set_fill_style(&self, color: Color) {
+        self.inner().state.fill_style = color;
+    }
+
+    pub fn stroke_style(&self) -> Color {
+        self.inner().state.stroke_style
+    }
+
+    pub fn set_stroke_style(&self, color: Color) {
+        self.inner().state.stroke_style = color;
+    }
+
+    pub fn line_width(&self) -> f32 {
+        self.inner().state.line_width
+    }
+
+    /// Set the line width, where zero, negative and non-finite values are
+    /// ignored.
+    pub fn set_line_width(&self, line_width: f32) {
+        if line_width.is_finite() && line_width > 0.0 {
+            self.inner().state.line_width = line_width;
+        }
+    }
+
+    pub fn font(&self) -> CanvasFont {
+        self.inner().state.font.clone()
+    }
+
+    pub fn set_font(&self, font: CanvasFont) {
+        let mut inner = self.inner();
+        if inner.state.font != font {
+            inner.state.font = font;
+            inner.font_handle = None;
+        }
+    }
+
+    /// Paint the rectangle with the `fillStyle`.
+    pub fn fill_rect(&self, x: f32, y: f32, width: f32, height: f32) {
+        let Some(rect) = normalized_rect(x, y, width, height) else { return };
+        let inner = self.inner();
+        let paint = solid_paint(inner.state.fill_style);
+        inner.draw(|pixmap| {
+            pixmap.fill_rect(rect, &paint, inner.state.transform, None);
+        });
+    }
+
+    /// Paint the outline of the rectangle with the `strokeStyle`.
+    pub fn stroke_rect(&self, x: f32, y: f32, width: f32, height: f32) {
+        let Some(rect) = normalized_rect(x, y, width, height) else { return };
+        let inner = self.inner();
+        inner.stroke_path_in_user_space(PathBuilder::from_rect(rect));
+    }
+
+    /// Make the pixels of the rectangle transparent black.
+    pub fn clear_rect(&self, x: f32, y: f32, width: f32, height: f32) {
+        let Some(rect) = normalized_rect(x, y, width, height) else { return };
+        let inner = self.inner();
+
+        let paint = Paint {
+            blend_mode: BlendMode::Clear,
+            ..Default::default()
+        };
+        inner.draw(|pixmap| {
+            pixmap.fill_rect(rect, &paint, inner.state.transform, None);
+        });
+    }
+
+    /// Paint the glyphs of the `text` with the `fillStyle`, where (`x`, `y`)
+    /// is the start of the alphabetic baseline.
+    pub fn fill_text(&self, text: &str, x: f32, y: f32) {
+        let mut inner = self.inner();
+        let Some(path) = inner.text_path(text, x, y).and_then(PathBuilder::finish) else { return };
+
+        let paint = solid_paint(inner.state.fill_style);
+        inner.draw(|pixmap| {
+            pixmap.fill_path(&path, &paint, FillRule::Winding, inner.state.transform, None);
+        });
+    }
+
+    /// Paint the outlines of the glyphs of the `text` with the
+    /// `strokeStyle`.
+    pub fn stroke_text(&self, text: &str, x: f32, y: f32) {
+        let mut inner = self.inner();
+        let Some(path) = inner.text_path(text, x, y).and_then(PathBuilder::finish) else { return };
+        inner.stroke_path_in_user_space(path);
+    }
+
+    /// Paint the `source` rectangle of the `image` into the `destination`
+    /// rectangle, where the rectangles are given as `[x, y, width, height]`.
+    pub fn draw_image(&self, image: RasterImage<'_>, source: [f32; 4], destination: [f32; 4]) {
+        let [sx, sy, sw, sh] = source;
+        let [dx, dy, dw, dh] = destination;
+        let (Some(source), Some(destination)) = (normalized_rect(sx, sy, sw, sh), normalized_rect(dx, dy, dw, dh)) else {
+            return;
+        };
+
+        if source.width() == 0.0 || source.height() == 0.0 {
+            return;
+        }
+
+        let Some(image) = PixmapRef::from_bytes(image.data, image.width, image.height) else { return };
+        let inner = self.inner();
+
+        // The pattern maps the source rectangle onto the destination.
+        let pattern_transform = Transform::from_translate(destination.x(), destination.y())
+            .pre_scale(destination.width() / source.width(), destination.height() / source.height())
+            .pre_translate(-source.x(), -source.y());
+
+        let paint = Paint {
+            shader: Pattern::new(
+                image,
+                SpreadMode::Pad,
+                FilterQuality::Bilinear,
+                1.0,
+                pattern_transform,
+            ),
+            ..Default::default()
+        };
+
+        inner.draw(|pixmap| {
+            pixmap.fill_rect(destination, &paint, inner.state.transform, None);
+        });
+    }
+
+    /// Empty the list of subpaths, such that there is a new path.
+    pub fn begin_path(&self) {
+        self.inner().path.clear();
+    }
+
+    pub fn move_to(&self, x: f32, y: f32) {
+        let mut inner = self.inner();
+        let (x, y) = inner.map_point(x, y);
+        inner.path.move_to(x, y);
+    }
+
+    /// Connect the last point of the subpath to the given point, or start a
+    /// new subpath if there is none.
+    pub fn line_to(&self, x: f32, y: f32) {
+        let mut inner = self.inner();
+        let (x, y) = inner.map_point(x, y);
+        inner.connect_to(x, y);
+    }
+
+    /// Add the arc of the circle at (`x`, `y`) from `start_angle` to
+    /// `end_angle` (in radians) to the path, connected to the previous point
+    /// with a straight line.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/canvas.html#dom-context-2d-arc
+    pub fn arc(
+        &self,
+        x: f32,
+        y: f32,
+        radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+        counterclockwise: bool,
+    ) -> Result<(), CanvasError> {
+        if ![x, y, radius, start_angle, end_angle].iter().all(|value| value.is_finite()) {
+            return Ok(());
+        }
+
+        if radius < 0.0 {
+            return Err(CanvasError::NegativeRadius);
+        }
+
+        let sweep = arc_sweep(start_angle, end_angle, counterclockwise);
+
+        let mut inner = self.inner();
+        let point_at = |angle: f32| (x + radius * angle.cos(), y + radius * angle.sin());
+
+        let (start_x, start_y) = inner.map_point_tuple(point_at(start_angle));
+        inner.connect_to(start_x, start_y);
+
+        // The arc is approximated with a cubic Bézier curve per quarter of the
+        // circle at most.
+        let segment_count = (sweep.abs() / FRAC_PI_2).ceil() as usize;
+        let segment_sweep = sweep / segment_count as f32;
+        let k = 4.0 / 3.0 * (segment_sweep / 4.0).tan() * radius;
+
+        let mut angle = start_angle;
+        for _ in 0..segment_count {
+            let next_angle = angle + segment_sweep;
+            let (x0, y0) = point_at(angle);
+            let (x3, y3) = point_at(next_angle);
+
+            let control1 = inner.map_point(x0 - k * angle.sin(), y0 + k * angle.cos());
+            let control2 = inner.map_point(x3 + k * next_angle.sin(), y3 - k * next_angle.cos());
+            let end = inner.map_point(x3, y3);
+
+            inner.path.cubic_to(control1.0, control1.1, control2.0, control2.1, end.0, end.1);
+            angle = next_angle;
+        }
+
+        Ok(())
+    }
+
+    /// Close the current subpath with a straight line to its first point.
+    pub fn close_path(&self) {
+        self.inner().path.close();
+    }
+
+    /// Paint the subpaths of the current path with the `fillStyle`.
+    pub fn fill(&self, fill_rule: CanvasFillRule) {
+        let inner = self.inner();
+        let Some(path) = inner.path.clone().finish() else { return };
+
+        let fill_rule = match fill_rule {
+            CanvasFillRule::NonZero => FillRule::Winding,
+            CanvasFillRule::EvenOdd => FillRule::EvenOdd,
+        };
+
+        let paint = solid_paint(inner.state.fill_style);
+        inner.draw(|pixmap| {
+            pixmap.fill_path(&path, &paint, fill_rule, Transform::identity(), None);
+        });
+    }
+
+    /// Paint the outlines of the subpaths of the current path with the
+    /// `strokeStyle`.
+    pub fn stroke(&self) {
+        let inner = self.inner();
+        let Some(path) = inner.path.clone().finish() else { return };
+
+        // The line width is in the coordinate space of the current
+        // transformation matrix, so the path is mapped back to that space.
+        let Some(inverse) = inner.state.transform.invert() else { return };
+        let Some(path) = path.transform(inverse) else { return };
+
+        let paint = solid_paint(inner.state.stroke_style);
+        let stroke = Stroke {
+            width: inner.state.line_width,
+            ..Default::default()
+        };
+
+        inner.draw(|pixmap| {
+            pixmap.stroke_path(&path, &paint, &stroke, inner.state.transform, None);
+        });
+    }
+}
+
+impl ContextInner {
+    /// Draw onto the pixels of the surface, if it has any.
+    fn draw(&self, f: impl FnOnce(&mut PixmapMut)) {
+        let mut f = Some(f);
+        self.surface.draw(&mut |data, width, height| {
+            let Some(mut pixmap) = PixmapMut::from_bytes(data, width, height) else { return };
+            if let Some(f) = f.take() {
+                f(&mut pixmap);
+            }
+        });
+    }
+
+    fn map_point(&self, x: f32, y: f32) -> (f32, f32) {
+        let transform = &self.state.transform;
+        (
+            transform.sx * x + transform.kx * y + transform.tx,
+            transform.ky * x + transform.sy * y + transform.ty,
+        )
+    }
+
+    fn map_point_tuple(&self, (x, y): (f32, f32)) -> (f32, f32) {
+        self.map_point(x, y)
+    }
+
+    /// Add a straight line to the given point, or move to it if the path
+    /// doesn't have any subpaths yet.
+    fn connect_to(&mut self, x: f32, y: f32) {
+        if self.path.is_empty() {
+            self.path.move_to(x, y);
+        } else {
+            self.path.line_to(x, y);
+        }
+    }
+
+    fn stroke_path_in_user_space(&self, path: Path) {
+        let paint = solid_paint(self.state.stroke_style);
+        let stroke = Stroke {
+            width: self.state.line_width,
+            ..Default::default()
+        };
+
+        self.draw(|pixmap| {
+            pixmap.stroke_path(&path, &paint, &stroke, self.state.transform, None);
+        });
+    }
+
+    /// The outlines of the glyphs of the `text` in the current font, in the
+    /// coordinate space of the current transformation matrix.
+    fn text_path(&mut self, text: &str, x: f32, y: f32) -> Option<PathBuilder> {
+        if !x.is_finite() || !y.is_finite() || text.is_empty() {
+            return None;
+        }
+
+        if self.font_handle.is_none() {
+            self.font_handle = self.state.font.select_handle();
+        }
+
+        let mut path = PathBuilder::new();
+        self.state.font.outline_text(self.font_handle.as_ref()?, text, x, y, &mut path);
+        Some(path)
+    }
+}
+
+/// The rectangle with the given origin and size, of which the size can be
+/// negative.
+fn normalized_rect(x: f32, y: f32, width: f32, height: f32) -> Option<Rect> {
+    Rect::from_ltrb(
+        x.min(x + width),
+        y.min(y + height),
+        x.max(x + width),
+        y.max(y + height),
+    )
+}
+
+fn solid_paint(color: Color) -> Paint<'static> {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(color.red_byte(), color.green_byte(), color.blue_byte(), color.alpha_byte());
+    paint
+}
+
+/// The signed angle the arc sweeps, which is at most a full circle.
+fn arc_sweep(start_angle: f32, end_angle: f32, counterclockwise: bool) -> f32 {
+    let sweep = end_angle - start_angle;
+
+    if counterclockwise {
+        if -sweep >= TAU {
+            -TAU
+        } else {
+            -(-sweep).rem_euclid(TAU)
+        }
+    } else if sweep >= TAU {
+        TAU
+    } else {
+        sweep.rem_euclid(TAU)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A surface of which the pixels are kept in memory.
+    #[derive(Debug)]
+    struct TestSurface {
+        width: u32,
+        height: u32,
+        data: Mutex<Vec<u8>>,
+    }
+
+    impl TestSurface {
+        fn new(width: u32, height: u32) -> Arc<Self> {
+            Arc::new(Self {
+                width,
+                height,
+                data: Mutex::new(vec![0; width as usize * height as usize * 4]),
+            })
+        }
+
+        fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+            let data = self.data.lock().unwrap();
+            let pixmap = PixmapRef::from_bytes(&data, self.width, self.height).unwrap();
+            let color = pixmap.pixel(x, y).unwrap().demultiply();
+            [color.red(), color.green(), color.blue(), color.alpha()]
+        }
+    }
+
+    impl RasterSurface for TestSurface {
+        fn draw(&self, f: &mut dyn FnMut(&mut [u8], u32, u32)) {
+            f(&mut self.data.lock().unwrap(), self.width, self.height);
+        }
+    }
+
+    #[test]
+    fn draw_rects_and_paths() {
+        let surface = TestSurface::new(20, 20);
+        let context = CanvasRenderingContext2D::new(surface.clone());
+
+        context.set_fill_style(Color::RED);
+        context.fill_rect(0.0, 0.0, 10.0, 10.0);
+        assert_eq!(surface.pixel(5, 5), [255, 0, 0, 255]);
+        assert_eq!(surface.pixel(15, 15), [0, 0, 0, 0]);
+
+        context.clear_rect(0.0, 0.0, 5.0, 5.0);
+        assert_eq!(surface.pixel(2, 2), [0, 0, 0, 0]);
+        assert_eq!(surface.pixel(7, 7), [255, 0, 0, 255]);
+
+        // The transformation is applied when the points are added, but the
+        // fill style when the path is filled.
+        context.save();
+        context.translate(10.0, 10.0);
+        context.set_fill_style(Color::BLUE);
+        context.begin_path();
+        context.move_to(0.0, 0.0);
+        context.line_to(10.0, 0.0);
+        context.line_to(10.0, 10.0);
+        context.line_to(0.0, 10.0);
+        context.close_path();
+        context.restore();
+        context.fill(CanvasFillRule::NonZero);
+
+        assert_eq!(surface.pixel(15, 15), [255, 0, 0, 255]);
+        assert_eq!(surface.pixel(5, 15), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn arc() {
+        let surface = TestSurface::new(20, 20);
+        let context = CanvasRenderingContext2D::new(surface.clone());
+
+        context.begin_path();
+        context.arc(10.0, 10.0, 8.0, 0.0, TAU, false).unwrap();
+        context.fill(CanvasFillRule::NonZero);
+
+        assert_eq!(surface.pixel(10, 10), [0, 0, 0, 255]);
+        assert_eq!(surface.pixel(1, 1), [0, 0, 0, 0]);
+        assert_eq!(context.arc(0.0, 0.0, -1.0, 0.0, 1.0, false), Err(CanvasError::NegativeRadius));
+    }
+
+    #[test]
+    fn draw_image() {
+        let source = TestSurface::new(2, 2);
+        let source_context = CanvasRenderingContext2D::new(source.clone());
+        source_context.set_fill_style(Color::GREEN);
+        source_context.fill_rect(1.0, 1.0, 1.0, 1.0);
+
+        let destination = TestSurface::new(10, 10);
+        let context = CanvasRenderingContext2D::new(destination.clone());
+
+        // The bottom-right quarter of the source is scaled to fill the whole
+        // destination, where the edges are filtered with the pixels outside
+        // of the source rectangle.
+        let data = source.data.lock().unwrap().clone();
+        let image = RasterImage { data: &data, width: 2, height: 2 };
+        context.draw_image(image, [1.0, 1.0, 1.0, 1.0], [0.0, 0.0, 10.0, 10.0]);
+
+        assert_eq!(destination.pixel(5, 5), [0, 255, 0, 255]);
+        assert_eq!(destination.pixel(9, 9), [0, 255, 0, 255]);
+    }
+
+    #[test]
+    fn fill_rule() {
+        assert_eq!("nonzero".parse(), Ok(CanvasFillRule::NonZero));
+        assert_eq!("evenodd".parse(), Ok(CanvasFillRule::EvenOdd));
+        assert!("even-odd".parse::<CanvasFillRule>().is_err());
+    }
+}
@@ -0,0 +1,262 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use font_kit::{
+    family_name::FamilyName,
+    font::Font,
+    handle::Handle,
+    outline::OutlineSink,
+    properties::{Properties, Style, Weight},
+    source::SystemSource,
+};
+use log::warn;
+use pathfinder_geometry::{line_segment::LineSegment2F, vector::Vector2F};
+use tiny_skia::PathBuilder;
+
+/// The font size of the `em` and `rem` units, which is the size of the
+/// default font, since the canvas isn't associated with a computed style yet.
+const DEFAULT_FONT_SIZE: f32 = 10.0;
+
+/// The value of the [`font`][spec] attribute of the 2D context, which is
+/// parsed as the CSS `font` shorthand.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/canvas.html#dom-context-2d-font
+#[derive(Clone, Debug, PartialEq)]
+pub struct CanvasFont {
+    serialized: String,
+    size: f32,
+    style: Style,
+    weight: Weight,
+    families: Vec<FamilyName>,
+}
+
+impl Default for CanvasFont {
+    fn default() -> Self {
+        Self::parse("10px sans-serif").unwrap()
+    }
+}
+
+impl CanvasFont {
+    /// Parse the value of the `font` shorthand, returning [`None`] if it is
+    /// invalid. Only the `font-style`, `font-weight`, `font-size` and
+    /// `font-family` components are used, and the other components are
+    /// ignored.
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+
+        let mut style = Style::Normal;
+        let mut weight = Weight::NORMAL;
+        let mut rest = input;
+
+        let size = loop {
+            let token_end = rest.find(|c: char| c.is_ascii_whitespace()).unwrap_or(rest.len());
+            let token = &rest[..token_end];
+            rest = rest[token_end..].trim_start();
+
+            if token.is_empty() {
+                return None;
+            }
+
+            match token.to_ascii_lowercase().as_str() {
+                "normal" | "small-caps" => (),
+                "italic" => style = Style::Italic,
+                "oblique" => style = Style::Oblique,
+                "bold" | "bolder" => weight = Weight::BOLD,
+                "lighter" => weight = Weight::LIGHT,
+                token => {
+                    if let Some(size) = parse_font_size(token) {
+                        break size;
+                    }
+
+                    match token.parse::<f32>() {
+                        Ok(value) if (1.0..=1000.0).contains(&value) => weight = Weight(value),
+                        _ => return None,
+                    }
+                }
+            }
+        };
+
+        let families: Vec<FamilyName> = rest.split(',')
+            .map(|family| family.trim().trim_matches(|c| c == '"' || c == '\''))
+            .filter(|family| !family.is_empty())
+            .map(parse_family_name)
+            .collect();
+
+        if families.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            serialized: input.to_string(),
+            size,
+            style,
+            weight,
+            families,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.serialized
+    }
+
+    /// The size of the font in CSS pixels.
+    pub fn size(&self) -> f32 {
+        self.size
+    }
+
+    /// Find the system font that matches this font best.
+    pub(crate) fn select_handle(&self) -> Option<Handle> {
+        let properties = Properties {
+            style: self.style,
+            weight: self.weight,
+            ..Default::default()
+        };
+
+        let mut families = self.families.clone();
+        families.push(FamilyName::SansSerif);
+
+        match SystemSource::new().select_best_match(&families, &properties) {
+            Ok(handle) => Some(handle),
+            Err(e) => {
+                warn!("[canvas] Failed to find a font for \"{}\": {e}", self.serialized);
+                None
+            }
+        }
+    }
+
+    /// Append the outlines of the glyphs of the `text` to the `path`, where
+    /// the alphabetic baseline of the text starts at (`x`, `y`).
+    pub(crate) fn outline_text(&self, handle: &Handle, text: &str, x: f32, y: f32, path: &mut PathBuilder) {
+        let font = match Font::from_handle(handle) {
+            Ok(font) => font,
+            Err(e) => {
+                warn!("[canvas] Failed to load the font for \"{}\": {e}", self.serialized);
+                return;
+            }
+        };
+
+        let scale = self.size / font.metrics().units_per_em as f32;
+        let mut pen_x = x;
+
+        for character in text.chars() {
+            let Some(glyph) = font.glyph_for_char(character) else {
+                continue;
+            };
+
+            let mut sink = PathSink {
+                path: &mut *path,
+                origin: Vector2F::new(pen_x, y),
+                scale,
+            };
+            _ = font.outline(glyph, font_kit::hinting::HintingOptions::None, &mut sink);
+
+            if let Ok(advance) = font.advance(glyph) {
+                pen_x += advance.x() * scale;
+            }
+        }
+    }
+}
+
+/// Parse the `font-size` component, in CSS pixels.
+fn parse_font_size(token: &str) -> Option<f32> {
+    // The `line-height` isn't used.
+    let token = token.split('/').next()?;
+
+    let (value, factor) = if let Some(value) = token.strip_suffix("px") {
+        (value, 1.0)
+    } else if let Some(value) = token.strip_suffix("pt") {
+        (value, 4.0 / 3.0)
+    } else if let Some(value) = token.strip_suffix("rem") {
+        (value, DEFAULT_FONT_SIZE)
+    } else if let Some(value) = token.strip_suffix("em") {
+        (value, DEFAULT_FONT_SIZE)
+    } else if let Some(value) = token.strip_suffix('%') {
+        (value, DEFAULT_FONT_SIZE / 100.0)
+    } else {
+        return None;
+    };
+
+    let value: f32 = value.parse().ok()?;
+    (value >= 0.0).then_some(value * factor)
+}
+
+fn parse_family_name(name: &str) -> FamilyName {
+    match name.to_ascii_lowercase().as_str() {
+        "serif" => FamilyName::Serif,
+        "sans-serif" => FamilyName::SansSerif,
+        "monospace" => FamilyName::Monospace,
+        "cursive" => FamilyName::Cursive,
+        "fantasy" => FamilyName::Fantasy,
+        _ => FamilyName::Title(name.to_string()),
+    }
+}
+
+/// Converts the outline of a glyph, of which the Y axis points up, to a path
+/// in the coordinate space of the canvas.
+struct PathSink<'path> {
+    path: &'path mut PathBuilder,
+    origin: Vector2F,
+    scale: f32,
+}
+
+impl<'path> PathSink<'path> {
+    fn convert(&self, point: Vector2F) -> (f32, f32) {
+        (
+            self.origin.x() + point.x() * self.scale,
+            self.origin.y() - point.y() * self.scale,
+        )
+    }
+}
+
+impl<'path> OutlineSink for PathSink<'path> {
+    fn move_to(&mut self, to: Vector2F) {
+        let (x, y) = self.convert(to);
+        self.path.move_to(x, y);
+    }
+
+    fn line_to(&mut self, to: Vector2F) {
+        let (x, y) = self.convert(to);
+        self.path.line_to(x, y);
+    }
+
+    fn quadratic_curve_to(&mut self, ctrl: Vector2F, to: Vector2F) {
+        let (x1, y1) = self.convert(ctrl);
+        let (x, y) = self.convert(to);
+        self.path.quad_to(x1, y1, x, y);
+    }
+
+    fn cubic_curve_to(&mut self, ctrl: LineSegment2F, to: Vector2F) {
+        let (x1, y1) = self.convert(ctrl.from());
+        let (x2, y2) = self.convert(ctrl.to());
+        let (x, y) = self.convert(to);
+        self.path.cubic_to(x1, y1, x2, y2, x, y);
+    }
+
+    fn close(&mut self) {
+        self.path.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_shorthand() {
+        let font = CanvasFont::parse("italic bold 12pt/1.5 \"Helvetica Neue\", serif").unwrap();
+        assert_eq!(font.as_str(), "italic bold 12pt/1.5 \"Helvetica Neue\", serif");
+        assert_eq!(font.size(), 16.0);
+        assert_eq!(font.style, Style::Italic);
+        assert_eq!(font.weight, Weight::BOLD);
+        assert_eq!(font.families, [FamilyName::Title("Helvetica Neue".into()), FamilyName::Serif]);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert_eq!(CanvasFont::parse(""), None);
+        assert_eq!(CanvasFont::parse("12px"), None);
+        assert_eq!(CanvasFont::parse("sans-serif"), None);
+        assert_eq!(CanvasFont::parse("big 12px sans-serif"), None);
+        assert_eq!(CanvasFont::default().size(), 10.0);
+    }
+}
@@ -0,0 +1,37 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! Software rasterization for the 2D rendering context of the HTML `<canvas>`
+//! element. Unlike the rest of the graphics library, the pixels are drawn on
+//! the CPU, after which they are uploaded to a texture as a whole.
+//!
+//! # References
+//! * [HTML Living Standard § 4.12.5 The canvas element](https://html.spec.whatwg.org/multipage/canvas.html)
+
+mod context_2d;
+mod font;
+
+pub use self::{
+    context_2d::{CanvasError, CanvasFillRule, CanvasRenderingContext2D},
+    font::CanvasFont,
+};
+
+use std::fmt::Debug;
+
+/// The pixels a [`CanvasRenderingContext2D`] draws onto, e.g. the output
+/// bitmap of a `<canvas>` element.
+pub trait RasterSurface: Debug + Send + Sync {
+    /// Modify the pixels of the surface, which are given in premultiplied
+    /// RGBA, row by row, together with the width and the height. Nothing is
+    /// drawn if the surface has no pixels.
+    fn draw(&self, f: &mut dyn FnMut(&mut [u8], u32, u32));
+}
+
+/// The pixels of an image drawn by
+/// [`CanvasRenderingContext2D::draw_image()`], in premultiplied RGBA.
+#[derive(Copy, Clone, Debug)]
+pub struct RasterImage<'pixels> {
+    pub data: &'pixels [u8],
+    pub width: u32,
+    pub height: u32,
+}
@@ -0,0 +1,26 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use euclid::default::Vector2D;
+use retina_common::Color;
+
+/// The parameters of a shadow painted by
+/// [`Painter::paint_shadow()`](crate::Painter::paint_shadow), with the
+/// lengths in pixels.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ShadowParams {
+    pub color: Color,
+    pub offset: Vector2D<f64>,
+
+    /// The blur radius, of which half is the standard deviation of the
+    /// gaussian blur.
+    pub blur_radius: f64,
+
+    /// How much the shape is expanded before it's blurred, or shrunk when
+    /// negative. For inset shadows, this is the other way around.
+    pub spread_radius: f64,
+
+    /// Whether or not the shadow is painted inside of the rect, instead of
+    /// outside of it.
+    pub inset: bool,
+}
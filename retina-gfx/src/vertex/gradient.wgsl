@@ -0,0 +1,88 @@
+struct GradientUniform {
+    transform: mat4x4<f32>,
+
+    // x, y: the size of the rect in pixels
+    // z: the kind of gradient, 0 for linear and 1 for radial
+    // w: the number of color stops
+    parameters: vec4<f32>,
+
+    // linear: the start (xy) and end (zw) point of the gradient line
+    // radial: the center (xy) and the radii (zw) of the ending shape
+    geometry: vec4<f32>,
+
+    colors: array<vec4<f32>, 16>,
+
+    // The offsets of the color stops, packed per four since the elements of
+    // uniform arrays are aligned to 16 bytes.
+    offsets: array<vec4<f32>, 4>,
+};
+
+@group(0)
+@binding(0)
+var<uniform> gradient: GradientUniform;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) tex_coords: vec2<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) position: vec2<f32>,
+}
+
+@vertex
+fn vs_main(
+    model: VertexInput,
+) -> VertexOutput {
+    var out: VertexOutput;
+    out.position = model.tex_coords * gradient.parameters.xy;
+    out.clip_position = gradient.transform * vec4<f32>(model.position, 1.0);
+    return out;
+}
+
+fn stop_offset(index: u32) -> f32 {
+    return gradient.offsets[index / 4u][index % 4u];
+}
+
+// Gradients are interpolated with premultiplied alpha, such that e.g. the
+// channels of `transparent` don't tint the colors halfway through.
+fn stop_color(index: u32) -> vec4<f32> {
+    let color = gradient.colors[index];
+    return vec4<f32>(color.rgb * color.a, color.a);
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    var t: f32;
+    if gradient.parameters.z < 0.5 {
+        let start = gradient.geometry.xy;
+        let line = gradient.geometry.zw - start;
+        t = dot(in.position - start, line) / max(dot(line, line), 0.0001);
+    } else {
+        let radii = max(gradient.geometry.zw, vec2<f32>(0.0001, 0.0001));
+        t = length((in.position - gradient.geometry.xy) / radii);
+    }
+
+    let count = u32(gradient.parameters.w);
+    var color = stop_color(count - 1u);
+    if t <= stop_offset(0u) {
+        color = stop_color(0u);
+    } else {
+        for (var i = 1u; i < count; i = i + 1u) {
+            let end = stop_offset(i);
+            if t < end {
+                let start = stop_offset(i - 1u);
+                let progress = (t - start) / max(end - start, 0.0001);
+                color = mix(stop_color(i - 1u), stop_color(i), progress);
+                break;
+            }
+        }
+    }
+
+    if color.a <= 0.0 {
+        return vec4<f32>(0.0, 0.0, 0.0, 0.0);
+    }
+
+    return vec4<f32>(color.rgb / color.a, color.a);
+}
@@ -0,0 +1,46 @@
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) tex_coords: vec2<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) tex_coords: vec2<f32>,
+}
+
+struct Uniform {
+    transform: mat4x4<f32>,
+
+    // x: the opacity, y, z, w: unused
+    parameters: vec4<f32>,
+};
+
+@group(0)
+@binding(2)
+var<uniform> t_uniform: Uniform;
+
+@vertex
+fn vs_main(
+    model: VertexInput,
+) -> VertexOutput {
+    var out: VertexOutput;
+    out.tex_coords = model.tex_coords;
+    out.clip_position = t_uniform.transform * vec4<f32>(model.position, 1.0);
+    return out;
+}
+
+@group(0)
+@binding(0)
+var t_diffuse: texture_2d<f32>;
+
+@group(0)
+@binding(1)
+var s_diffuse: sampler;
+
+// The intermediate texture is painted onto a transparent texture, so its
+// colors are premultiplied by their alpha, and the opacity applies to all
+// of the channels.
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return textureSample(t_diffuse, s_diffuse, in.tex_coords) * t_uniform.parameters.x;
+}
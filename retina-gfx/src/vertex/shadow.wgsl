@@ -0,0 +1,87 @@
+struct ShadowUniform {
+    transform: mat4x4<f32>,
+    color: vec4<f32>,
+
+    // The left (x), top (y), right (z) and bottom (w) edges of the shape
+    // casting the shadow, in pixels relative to the painted rect.
+    shape: vec4<f32>,
+
+    // The edges of the area that isn't painted, or the area that is painted
+    // for inset shadows.
+    clip: vec4<f32>,
+
+    // x, y: the size of the painted rect in pixels
+    // z: the standard deviation of the blur
+    // w: 1 for inset shadows, 0 otherwise
+    parameters: vec4<f32>,
+};
+
+@group(0)
+@binding(0)
+var<uniform> shadow: ShadowUniform;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) tex_coords: vec2<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) position: vec2<f32>,
+}
+
+@vertex
+fn vs_main(
+    model: VertexInput,
+) -> VertexOutput {
+    var out: VertexOutput;
+    out.position = model.tex_coords * shadow.parameters.xy;
+    out.clip_position = shadow.transform * vec4<f32>(model.position, 1.0);
+    return out;
+}
+
+// An approximation of the error function, with a maximum error of 5e-4.
+fn erf(x: vec2<f32>) -> vec2<f32> {
+    let s = sign(x);
+    let a = abs(x);
+    var y = 1.0 + (0.278393 + (0.230389 + 0.078108 * (a * a)) * a) * a;
+    y = y * y;
+    return s - s / (y * y);
+}
+
+fn is_inside(position: vec2<f32>, rect: vec4<f32>) -> bool {
+    return all(position >= rect.xy) && all(position < rect.zw);
+}
+
+// The coverage of the shape blurred with a gaussian, which is separable for
+// rectangles, i.e. the product of the horizontal and vertical coverage.
+fn coverage(position: vec2<f32>) -> f32 {
+    let sigma = shadow.parameters.z;
+    if sigma <= 0.0 {
+        return select(0.0, 1.0, is_inside(position, shadow.shape));
+    }
+
+    let scale = 1.0 / (sqrt(2.0) * sigma);
+    let start = erf((position - shadow.shape.xy) * scale);
+    let end = erf((position - shadow.shape.zw) * scale);
+    let range = 0.5 * (start - end);
+    return range.x * range.y;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let is_inset = shadow.parameters.w > 0.5;
+
+    // Outer shadows are only painted outside of the border box, and inset
+    // shadows only inside of the padding box.
+    if is_inside(in.position, shadow.clip) != is_inset {
+        discard;
+    }
+
+    var alpha = coverage(in.position);
+    if is_inset {
+        alpha = 1.0 - alpha;
+    }
+
+    return vec4<f32>(shadow.color.rgb, shadow.color.a * alpha);
+}
@@ -3,6 +3,7 @@
 
 use retina_common::Color;
 use retina_gfx_font::TextHintingOptions;
+use retina_style::{CssDecimal, CssReferencePixels, CssTextOverflow};
 
 use crate::LayoutBoxDimensions;
 
@@ -11,5 +12,125 @@ pub struct ActualValueMap {
     pub text_color: Color,
     pub background_color: Color,
     pub text_hinting_options: TextHintingOptions,
+
+    /// The font size of the root element, which `rem` units resolve against.
+    pub root_font_size: CssReferencePixels,
+
+    /// The size of the viewport, which the `vw`, `vh`, `vmin` and `vmax` units
+    /// resolve against.
+    pub viewport_width: CssReferencePixels,
+    pub viewport_height: CssReferencePixels,
+
     pub dimensions: LayoutBoxDimensions,
+
+    /// The `min-width`, `max-width`, `min-height` and `max-height`
+    /// constraints of the box.
+    pub size_constraints: SizeConstraints,
+
+    /// The `text-overflow` of the block container clipping this (inline)
+    /// content, or `clip` when the content isn't clipped.
+    pub text_overflow: CssTextOverflow,
+
+    /// The resolved `letter-spacing`, in CSS pixels.
+    pub letter_spacing: CssDecimal,
+
+    /// The resolved `word-spacing`, in CSS pixels.
+    pub word_spacing: CssDecimal,
+}
+
+impl ActualValueMap {
+    /// Whether or not the `letter-spacing` and `word-spacing` are both
+    /// `normal`, in which case the text can be measured and painted as-is.
+    pub fn has_normal_spacing(&self) -> bool {
+        self.letter_spacing == 0.0 && self.word_spacing == 0.0
+    }
+
+    /// The additional spacing that is added after the `character`. The
+    /// `word-spacing` only applies to the word-separator characters.
+    ///
+    /// # References
+    /// * [CSS Text Module Level 4 § 8.1. Word Spacing: the `word-spacing` property](https://drafts.csswg.org/css-text-4/#word-spacing-property)
+    /// * [CSS Text Module Level 4 § 8.2. Tracking: the `letter-spacing` property](https://drafts.csswg.org/css-text-4/#letter-spacing-property)
+    pub fn spacing_after(&self, character: char) -> CssDecimal {
+        match character {
+            ' ' | '\u{00A0}' => self.letter_spacing + self.word_spacing,
+            _ => self.letter_spacing,
+        }
+    }
+}
+
+/// The resolved value of one of the `min-*` and `max-*` sizing properties.
+///
+/// # References
+/// * [CSS Box Sizing Module Level 3 § 5.2. Min and Max Sizes](https://drafts.csswg.org/css-sizing-3/#min-size-properties)
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum SizeConstraint {
+    /// The box isn't constrained, i.e. `max-width: none` or `min-width: auto`.
+    #[default]
+    None,
+
+    /// The size of the content box.
+    Length(CssReferencePixels),
+
+    /// The `max-content` size, which is only known after a content-size pass
+    /// during layout.
+    MaxContent,
+
+    /// The `min-content` size, which is only known after a content-size pass
+    /// during layout.
+    MinContent,
+}
+
+impl SizeConstraint {
+    pub fn is_intrinsic(&self) -> bool {
+        matches!(self, Self::MaxContent | Self::MinContent)
+    }
+
+    /// Get the length, provided the constraint isn't an intrinsic size.
+    pub fn length(&self) -> Option<CssReferencePixels> {
+        match self {
+            Self::Length(length) => Some(*length),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct SizeConstraints {
+    pub min_width: SizeConstraint,
+    pub max_width: SizeConstraint,
+    pub min_height: SizeConstraint,
+    pub max_height: SizeConstraint,
+}
+
+impl SizeConstraints {
+    pub fn has_intrinsic_width(&self) -> bool {
+        self.min_width.is_intrinsic() || self.max_width.is_intrinsic()
+    }
+}
+
+/// Clamp the `value` between the `min` and `max`. When the `min` is greater
+/// than the `max`, the `min` wins.
+///
+/// # References
+/// * [CSS 2.2 § 10.4 Minimum and maximum widths](https://drafts.csswg.org/css2/#min-max-widths)
+/// * [CSS 2.2 § 10.7 Minimum and maximum heights](https://drafts.csswg.org/css2/#min-max-heights)
+pub(crate) fn clamp_size(
+    mut value: CssReferencePixels,
+    min: Option<CssReferencePixels>,
+    max: Option<CssReferencePixels>,
+) -> CssReferencePixels {
+    if let Some(max) = max {
+        if value > max {
+            value = max;
+        }
+    }
+
+    if let Some(min) = min {
+        if value < min {
+            value = min;
+        }
+    }
+
+    value
 }
@@ -133,6 +133,10 @@ impl LayoutBoxDimensions {
         Rect::new(self.position_border_box(), self.size_border_box())
     }
 
+    pub fn rect_padding_box(&self) -> Rect<CssDecimal> {
+        Rect::new(self.position_padding_box(), self.size_padding_box())
+    }
+
     pub fn width(&self) -> CssReferencePixels {
         self.width
     }
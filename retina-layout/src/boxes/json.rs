@@ -0,0 +1,77 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! Serialization of the layout tree to JSON, which is used for comparing the
+//! layout of a page with a golden file in integration tests.
+
+use serde_json::{json, Value};
+
+use super::{LayoutBox, LayoutBoxKind};
+
+impl LayoutBox {
+    /// Serialize this box and its descendants to JSON, e.g.:
+    /// ```json
+    /// {
+    ///     "kind": "Normal",
+    ///     "x": 10, "y": 20, "width": 200, "height": 40,
+    ///     "tag": "div",
+    ///     "children": []
+    /// }
+    /// ```
+    ///
+    /// The position and size are those of the border box. The `tag` is `null`
+    /// for boxes that aren't generated by an element, e.g. text. The root box
+    /// additionally has the `fixed_boxes` with `position: fixed`, since these
+    /// aren't part of its `children`.
+    pub fn to_json(&self) -> Value {
+        let kind = match self.kind {
+            LayoutBoxKind::Root => "Root",
+            LayoutBoxKind::Normal => "Normal",
+            LayoutBoxKind::Anonymous => "Anonymous",
+        };
+
+        let rect = self.dimensions.rect_border_box();
+
+        let mut value = json!({
+            "kind": kind,
+            "x": rect.origin.x,
+            "y": rect.origin.y,
+            "width": rect.size.width,
+            "height": rect.size.height,
+            "tag": self.node.tag_name(),
+            "children": self.children.iter().map(LayoutBox::to_json).collect::<Vec<_>>(),
+        });
+
+        if self.kind == LayoutBoxKind::Root {
+            value["fixed_boxes"] = self.fixed_boxes.iter().map(LayoutBox::to_json).collect();
+        }
+
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use rstest::rstest;
+    use serde_json::Value;
+
+    use crate::test_util::generate_layout;
+
+    /// Lay out the `index.html` and `style.css` in `test/layout/<name>`, and
+    /// compare the result to the golden `layout.json` in that directory.
+    #[rstest]
+    #[case("block")]
+    #[case("fixed")]
+    fn golden(#[case] name: &str) {
+        let directory = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../test/layout").join(name);
+        let read = |file_name: &str| std::fs::read_to_string(directory.join(file_name))
+            .unwrap_or_else(|e| panic!("failed to read {file_name} of {name}: {e}"));
+
+        let layout_root = generate_layout(&read("index.html"), &read("style.css"));
+        let expected: Value = serde_json::from_str(&read("layout.json")).unwrap();
+
+        assert_eq!(layout_root.to_json(), expected);
+    }
+}
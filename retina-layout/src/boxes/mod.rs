@@ -7,13 +7,14 @@
 
 mod dimensions;
 mod edge;
+mod json;
 mod line;
 
 use std::borrow::Cow;
 
 pub use dimensions::LayoutBoxDimensions;
 pub use edge::LayoutEdge;
-use euclid::default::Size2D;
+use euclid::default::{Point2D, Size2D, Vector2D};
 use log::warn;
 use retina_common::{
     Color,
@@ -24,18 +25,36 @@ use retina_common::{
 };
 use retina_dom::{ImageData, HtmlElementKind};
 use retina_gfx_font::{FontHandle, TextHintingOptions};
-use retina_style::{CssDecimal, CssReferencePixels, CssLength, CssWhiteSpace};
+use retina_style::{
+    CssDecimal,
+    CssDirection,
+    CssLength,
+    CssOverflowWrap,
+    CssReferencePixels,
+    CssTextOverflow,
+    CssWhiteSpace,
+    CssWordBreak,
+    CssWritingMode,
+};
 
 use crate::{
     ActualValueMap,
+    actual_values::clamp_size,
     formatting_context::{
         BlockFormattingContext,
+        FixedPositioning,
+        FlexFormattingContext,
         FormattingContext,
+        GridFormattingContext,
         FormattingContextKind,
         FormattingContextWhitespaceState,
         InlineFormattingContext,
         inline::InlineFormattingContextState,
+        LengthContext,
+        TableFormattingContext,
     },
+    SizeConstraint,
+    SizeConstraints,
     text::is_emoji,
 };
 
@@ -61,6 +80,26 @@ pub struct LayoutBox {
     pub(crate) font_size: CssReferencePixels,
     pub(crate) background_image: Option<ImageData>,
     pub(crate) line_box_fragments: Vec<LineBoxFragment>,
+
+    /// The distance the contents of this box are scrolled by. The positions
+    /// of the descendants aren't affected by this offset; it is subtracted
+    /// whilst painting and hit testing instead, such that scrolling doesn't
+    /// require a relayout.
+    pub(crate) scroll_offset: Vector2D<CssDecimal>,
+
+    /// The boxes with `position: fixed`, which are taken out of the normal
+    /// flow and positioned relative to the viewport. These are only stored
+    /// on the root box.
+    ///
+    /// [spec]: https://drafts.csswg.org/css-position/#fixed-position
+    pub(crate) fixed_boxes: Vec<LayoutBox>,
+
+    /// The stack level of the box when it establishes a stacking context, or
+    /// `None` when it's painted as part of the stacking context of its
+    /// ancestor.
+    ///
+    /// [spec]: https://www.w3.org/TR/CSS22/zindex.html
+    pub(crate) stacking_context_order: Option<i32>,
 }
 
 unsafe impl Sync for LayoutBox {}
@@ -90,6 +129,9 @@ impl LayoutBox {
             font_size,
             background_image: None,
             line_box_fragments: Vec::new(),
+            scroll_offset: Vector2D::zero(),
+            fixed_boxes: Vec::new(),
+            stacking_context_order: None,
         }
     }
 
@@ -114,10 +156,27 @@ impl LayoutBox {
         self.background_image.as_ref()
     }
 
+    /// Resolve a length of a property of this box to pixels, with
+    /// percentages relative to the `basis`. This is `None` for `auto`, and
+    /// for percentages without a `basis`.
+    pub fn resolve_length(&self, length: CssLength, basis: Option<CssDecimal>) -> Option<CssDecimal> {
+        LengthContext::new(self).resolve(length, basis)
+    }
+
     pub fn children(&self) -> &[LayoutBox] {
         &self.children
     }
 
+    pub fn children_mut(&mut self) -> &mut [LayoutBox] {
+        &mut self.children
+    }
+
+    /// Get the boxes with `position: fixed`, of which the positions are
+    /// relative to the viewport instead of the page.
+    pub fn fixed_boxes(&self) -> &[LayoutBox] {
+        &self.fixed_boxes
+    }
+
     pub fn computed_style(&self) -> &PropertyMap {
         &self.computed_style
     }
@@ -143,6 +202,76 @@ impl LayoutBox {
         &self.line_box_fragments
     }
 
+    /// Get the distance the contents of this box are scrolled by.
+    pub fn scroll_offset(&self) -> Vector2D<CssDecimal> {
+        self.scroll_offset
+    }
+
+    /// Whether or not the content overflowing the padding box is clipped,
+    /// i.e. `overflow` is not `visible`.
+    ///
+    /// [spec]: https://drafts.csswg.org/css-overflow/#overflow-properties
+    pub fn clips_overflow(&self) -> bool {
+        self.computed_style.overflow_x().clips() || self.computed_style.overflow_y().clips()
+    }
+
+    /// Whether or not the user can scroll the content of this box, which is
+    /// the case for `overflow: scroll` and `overflow: auto`.
+    pub fn is_user_scrollable(&self) -> bool {
+        self.computed_style.overflow_x().is_user_scrollable()
+            || self.computed_style.overflow_y().is_user_scrollable()
+    }
+
+    /// Get the furthest distance the contents of this box can be scrolled
+    /// by, which is the amount the [scrollable overflow][spec] extends past
+    /// the padding box.
+    ///
+    /// [spec]: https://drafts.csswg.org/css-overflow/#scrollable
+    pub fn max_scroll_offset(&self) -> Vector2D<CssDecimal> {
+        let padding_box = self.dimensions.rect_padding_box();
+
+        let mut extent = padding_box.max();
+        for child in &self.children {
+            child.extend_scrollable_overflow(&mut extent);
+        }
+
+        Vector2D::new(
+            (extent.x - padding_box.max_x()).max(0.0),
+            (extent.y - padding_box.max_y()).max(0.0),
+        )
+    }
+
+    fn extend_scrollable_overflow(&self, extent: &mut Point2D<CssDecimal>) {
+        let border_box = self.dimensions.rect_border_box();
+        extent.x = extent.x.max(border_box.max_x());
+        extent.y = extent.y.max(border_box.max_y());
+
+        for fragment in &self.line_box_fragments {
+            let position = fragment.position();
+            extent.x = extent.x.max(position.x + fragment.size().width);
+            extent.y = extent.y.max(position.y + fragment.size().height);
+        }
+
+        // The overflow of descendants that clip is contained in their own
+        // padding box, which is already included above.
+        if self.clips_overflow() {
+            return;
+        }
+
+        for child in &self.children {
+            child.extend_scrollable_overflow(extent);
+        }
+    }
+
+    pub fn set_scroll_offset(&mut self, offset: Vector2D<CssDecimal>) {
+        self.scroll_offset = offset;
+    }
+
+    /// Get the stack level of the box, if it establishes a stacking context.
+    pub fn stacking_context_order(&self) -> Option<i32> {
+        self.stacking_context_order
+    }
+
     pub fn kind(&self) -> &LayoutBoxKind {
         &self.kind
     }
@@ -192,8 +321,23 @@ impl LayoutBox {
         let hinting_options = self.actual_value_map.text_hinting_options;
         let text = StrTendril::from(text.as_ref());
 
+        if self.computed_style.writing_mode().is_vertical() {
+            self.run_anonymous_layout_vertical(parent, text, hinting_options);
+            self.run_anonymous_layout_calculate_size();
+            return;
+        }
+
         self.run_anonymous_layout_algorithm(parent, text, hinting_options);
 
+        if self.actual_value_map.text_overflow == CssTextOverflow::Ellipsis
+                && self.computed_style.white_space() == CssWhiteSpace::Nowrap {
+            if let Some(max_width) = parent.max_width {
+                self.run_anonymous_layout_text_overflow(max_width.value(), hinting_options);
+            }
+        }
+
+        self.run_anonymous_layout_bidi(parent, hinting_options);
+
         self.run_anonymous_layout_calculate_size();
     }
 
@@ -203,14 +347,43 @@ impl LayoutBox {
 
         let max_width = parent.max_width;
 
+        // The lines next to floats are shortened by them.
+        let floats = &parent.floats;
+
         let font_size = self.font_size().value() as f32;
         let mut fragment_begin_index: u32 = 0;
         let mut initial_begin_index: u32 = 0;
         let mut line_break_reason;
 
+        // With `word-break: break-all`, words that don't fit on the line are
+        // broken, and with `overflow-wrap: anywhere` only the words that are
+        // wider than the line by themselves.
+        //
+        // TODO: `word-break: keep-all` isn't supported yet.
+        let wraps = self.computed_style.white_space().wraps();
+        let break_all = wraps && self.computed_style.word_break() == CssWordBreak::BreakAll;
+        let break_anywhere = wraps && (self.computed_style.overflow_wrap() != CssOverflowWrap::Normal
+            || self.computed_style.word_break() == CssWordBreak::BreakWord);
+
+        // The pieces of the word that is broken, in reverse order.
+        let mut broken_word_pieces = Vec::new();
+
+        // Whether or not the broken word should start on a new line, since
+        // it should only be broken when there is no other break opportunity.
+        let mut must_wrap_broken_word = false;
+
         use unicode_segmentation::UnicodeSegmentation;
         let mut was_last_word_emoji = false;
-        for word in text.split_word_bounds() {
+        let mut words = text.split_word_bounds();
+        loop {
+            let (word, is_broken_piece) = match broken_word_pieces.pop() {
+                Some(piece) => (piece, true),
+                None => match words.next() {
+                    Some(word) => (word, false),
+                    None => break,
+                },
+            };
+
             if self.computed_style.white_space().collapses() && word.chars().all(char::is_whitespace) {
                 initial_begin_index += word.len() as u32;
                 continue;
@@ -237,8 +410,20 @@ impl LayoutBox {
                 word = without_spaces;
             }
 
+            // The pieces of a broken word are sized to fit the lines already.
+            let can_break_word = (break_all || break_anywhere)
+                && !is_broken_piece
+                && !is_word_emoji
+                && original_word.graphemes(true).nth(1).is_some();
+
             let Some(fragment) = self.line_box_fragments.last_mut() else {
-                let word_size = font.calculate_size(self.font_size().value() as _, word, hinting_options);
+                let word_size = calculate_text_size(&font, font_size, word, hinting_options, &self.actual_value_map);
+                if let Some(max_width) = max_width.filter(|max_width| can_break_word && word_size.width > max_width.value()) {
+                    let max_width = max_width.value() as f32;
+                    broken_word_pieces = split_word_into_lines(&font, original_word, max_width, max_width, font_size, hinting_options);
+                    continue;
+                }
+
                 if !honor_forced_line_breaks {
                     debug_assert!(fragment_begin_index == 0);
                 }
@@ -246,7 +431,7 @@ impl LayoutBox {
                 self.line_box_fragments.push(LineBoxFragment {
                     position: self.dimensions.content_position,
                     text: text.subtendril(initial_begin_index, word.len() as u32),
-                    size: word_size.cast(),
+                    size: word_size,
                     font,
                 });
                 continue;
@@ -264,13 +449,16 @@ impl LayoutBox {
                 new_fragment_text_length,
             );
 
-            let fragment_size = font.calculate_size(font_size, &fragment_text, hinting_options).cast();
+            let fragment_size = calculate_text_size(&font, font_size, &fragment_text, hinting_options, &self.actual_value_map);
 
             // Does this word already fit on the last fragment?
-            let is_wrap_line_break = self.computed_style.white_space() != CssWhiteSpace::Pre
-                && (is_word_emoji
+            let is_wrap_line_break = wraps
+                && (std::mem::take(&mut must_wrap_broken_word)
+                    || is_word_emoji
                     || was_last_word_emoji
                     || max_width.is_some_and(|max_width| fragment_size.width > max_width.value())
+                    || (!floats.is_empty()
+                        && fragment.position.x + fragment_size.width > floats.available_space(fragment.position.y, fragment_size.height).1)
                 );
 
             if !is_forced_line_break && !is_wrap_line_break {
@@ -279,6 +467,24 @@ impl LayoutBox {
                 continue;
             }
 
+            if is_wrap_line_break && !is_forced_line_break && can_break_word && !was_last_word_emoji {
+                if let Some(max_width) = max_width.filter(|_| break_all) {
+                    // The first piece fills the rest of the current line.
+                    let max_width = max_width.value() as f32;
+                    let first_line_width = max_width - fragment.size.width as f32;
+                    broken_word_pieces = split_word_into_lines(&font, original_word, first_line_width, max_width, font_size, hinting_options);
+                    continue;
+                }
+
+                let word_width = calculate_text_size(&font, font_size, word, hinting_options, &self.actual_value_map).width;
+                if let Some(max_width) = max_width.filter(|max_width| word_width > max_width.value()) {
+                    let max_width = max_width.value() as f32;
+                    broken_word_pieces = split_word_into_lines(&font, original_word, max_width, max_width, font_size, hinting_options);
+                    must_wrap_broken_word = true;
+                    continue;
+                }
+            }
+
             if is_word_emoji || was_last_word_emoji {
                 line_break_reason = LineBreakReason::Emoji;
             } else {
@@ -293,6 +499,10 @@ impl LayoutBox {
                 match line_break_reason {
                     LineBreakReason::Normal => {
                         position.y += last_fragment.size.height;
+
+                        if !floats.is_empty() {
+                            position.x = floats.available_space(position.y, last_fragment.size.height).0;
+                        }
                     }
                     LineBreakReason::Emoji => {
                         position.x += last_fragment.size.width;
@@ -334,12 +544,235 @@ impl LayoutBox {
             self.line_box_fragments.push(LineBoxFragment {
                 position,
                 text,
-                size: font.calculate_size(font_size, word, hinting_options).cast(),
+                size: calculate_text_size(&font, font_size, word, hinting_options, &self.actual_value_map),
                 font,
             });
         }
     }
 
+    /// Set the text in columns for the vertical writing modes. The inline
+    /// axis is vertical, so the `max_height` of the formatting context is
+    /// the available space of a column. The columns progress from the right
+    /// edge of the containing box with `vertical-rl`, and from the left edge
+    /// with `vertical-lr`.
+    ///
+    /// TODO: the glyphs are always upright, as with `text-orientation:
+    ///       upright`, and the block-level boxes of the formatting context are
+    ///       still stacked vertically.
+    ///
+    /// # References
+    /// * [CSS Writing Modes Level 3 § 3.1. Block Flow Direction: the `writing-mode` property](https://drafts.csswg.org/css-writing-modes/#block-flow)
+    fn run_anonymous_layout_vertical(&mut self, parent: &FormattingContext, text: StrTendril, hinting_options: TextHintingOptions) {
+        use unicode_segmentation::UnicodeSegmentation;
+
+        self.line_box_fragments.clear();
+
+        let max_inline_size = parent.max_height.map(|max_height| max_height.value());
+        let wraps = self.computed_style.white_space().wraps();
+        let font_size = self.font_size().value() as f32;
+
+        let measure = |text: &str| -> Size2D<CssDecimal> {
+            self.font.calculate_vertical_size(font_size, text, hinting_options).cast()
+        };
+
+        let mut columns = Vec::new();
+        let mut column_begin_index: u32 = 0;
+        let mut column_end_index: u32 = 0;
+        let mut column_size = Size2D::zero();
+
+        for word in text.split_word_bounds() {
+            let word_end_index = word.as_end_ptr() as u32 - text.as_ptr() as u32;
+            let size = measure(&text[column_begin_index as usize..word_end_index as usize]);
+
+            let is_wrap_line_break = wraps
+                && column_end_index != column_begin_index
+                && max_inline_size.is_some_and(|max_inline_size| size.height > max_inline_size);
+
+            if is_wrap_line_break {
+                columns.push((text.subtendril(column_begin_index, column_end_index - column_begin_index), column_size));
+                column_begin_index = column_end_index;
+                column_size = measure(&text[column_begin_index as usize..word_end_index as usize]);
+            } else {
+                column_size = size;
+            }
+
+            column_end_index = word_end_index;
+        }
+
+        if column_end_index != column_begin_index {
+            columns.push((text.subtendril(column_begin_index, column_end_index - column_begin_index), column_size));
+        }
+
+        let origin = self.dimensions.content_position;
+        let is_right_to_left = self.computed_style.writing_mode() == CssWritingMode::VerticalRl;
+        let mut x = if is_right_to_left {
+            parent.scroll_origin.x + parent.layout_box.dimensions().width().value()
+        } else {
+            origin.x
+        };
+
+        for (text, size) in columns {
+            if is_right_to_left {
+                x -= size.width;
+            }
+
+            let position = Point2D::new(x, origin.y);
+
+            if !is_right_to_left {
+                x += size.width;
+            }
+
+            self.line_box_fragments.push(LineBoxFragment {
+                position,
+                text,
+                size,
+                font: self.font.clone(),
+            });
+        }
+    }
+
+    /// Clips the line that overflows the available width, and renders an
+    /// ellipsis (`…`) at the end of the text that remains visible.
+    ///
+    /// # References
+    /// * [CSS Overflow Module Level 3 § 4.1. Inline Overflow Ellipsis: the `text-overflow` property][spec]
+    ///
+    /// [spec]: https://drafts.csswg.org/css-overflow/#text-overflow
+    fn run_anonymous_layout_text_overflow(&mut self, max_width: CssDecimal, hinting_options: TextHintingOptions) {
+        const ELLIPSIS: &str = "\u{2026}";
+
+        let Some(first_fragment) = self.line_box_fragments.first() else {
+            return;
+        };
+        let line_end = first_fragment.position.x + max_width;
+
+        let Some(index) = self.line_box_fragments.iter()
+            .position(|fragment| fragment.position.x + fragment.size.width > line_end) else {
+            return;
+        };
+
+        // The fragments after the one crossing the edge aren't visible at all.
+        self.line_box_fragments.truncate(index + 1);
+
+        let font_size = self.font_size().value() as f32;
+        let fragment = &mut self.line_box_fragments[index];
+
+        let ellipsis_width = calculate_text_size(&fragment.font, font_size, ELLIPSIS, hinting_options, &self.actual_value_map).width;
+        let available_width = line_end - fragment.position.x - ellipsis_width;
+
+        let mut text: &str = &fragment.text;
+        while !text.is_empty() {
+            let width = calculate_text_size(&fragment.font, font_size, text, hinting_options, &self.actual_value_map).width;
+            if width <= available_width {
+                break;
+            }
+
+            let last_char_index = text.char_indices().next_back().map_or(0, |(index, _)| index);
+            text = &text[..last_char_index];
+        }
+
+        let text = StrTendril::from(format!("{}{ELLIPSIS}", text.trim_end()));
+        fragment.size = calculate_text_size(&fragment.font, font_size, &text, hinting_options, &self.actual_value_map);
+        fragment.text = text;
+    }
+
+    /// Apply the [Unicode Bidirectional Algorithm][uba] to the lines, which
+    /// splits them into fragments of the same direction in visual order. The
+    /// text of right-to-left fragments is in logical order, since the shaper
+    /// already reverses it. With `direction: rtl`, the fragments of a line are
+    /// placed from the right edge of the containing box.
+    ///
+    /// # References
+    /// * [CSS Writing Modes Level 3 § 2.4. Applying the Bidirectional Reordering Algorithm][spec]
+    ///
+    /// [spec]: https://drafts.csswg.org/css-writing-modes/#bidi-algo
+    /// [uba]: https://unicode.org/reports/tr9/
+    fn run_anonymous_layout_bidi(&mut self, parent: &FormattingContext, hinting_options: TextHintingOptions) {
+        use unicode_bidi::{bidi_class, BidiClass, BidiInfo, Level};
+
+        let direction = self.computed_style.direction();
+        let base_level = match direction {
+            CssDirection::Ltr => Level::ltr(),
+            CssDirection::Rtl => Level::rtl(),
+        };
+
+        let is_right_to_left = |character| matches!(bidi_class(character), BidiClass::R | BidiClass::AL);
+        if direction == CssDirection::Ltr && !self.line_box_fragments.iter().any(|fragment| fragment.text.chars().any(is_right_to_left)) {
+            return;
+        }
+
+        let right_edge = parent.scroll_origin.x + parent.layout_box.dimensions().width().value();
+        let font_size = self.font_size().value() as f32;
+
+        let fragments = std::mem::take(&mut self.line_box_fragments);
+        let mut fragments = fragments.into_iter().peekable();
+        while let Some(first_fragment) = fragments.next() {
+            let line_start = first_fragment.position.x;
+            let line_y = first_fragment.position.y;
+
+            // An emoji starts a new fragment on the same line.
+            let mut line = vec![first_fragment];
+            while let Some(fragment) = fragments.next_if(|fragment| fragment.position.y == line_y) {
+                line.push(fragment);
+            }
+
+            if direction == CssDirection::Rtl {
+                line.reverse();
+            }
+
+            let mut x = line_start;
+            let line_begin_index = self.line_box_fragments.len();
+            for fragment in line {
+                let bidi_info = BidiInfo::new(&fragment.text, Some(base_level));
+                for paragraph in &bidi_info.paragraphs {
+                    let (_, runs) = bidi_info.visual_runs(paragraph, paragraph.range.clone());
+                    for run in runs {
+                        let text = fragment.text.subtendril(run.start as u32, run.len() as u32);
+                        let size = calculate_text_size(&fragment.font, font_size, &text, hinting_options, &self.actual_value_map);
+
+                        self.line_box_fragments.push(LineBoxFragment {
+                            position: Point2D::new(x, line_y),
+                            text,
+                            size,
+                            font: fragment.font.clone(),
+                        });
+                        x += size.width;
+                    }
+                }
+            }
+
+            if direction == CssDirection::Rtl {
+                let offset = right_edge - x;
+                for fragment in &mut self.line_box_fragments[line_begin_index..] {
+                    fragment.position.x += offset;
+                }
+            }
+        }
+    }
+
+    /// Indent the first line of this text by the `text-indent` of the block
+    /// container, which is a hanging indent when it is negative.
+    ///
+    /// # References
+    /// * [CSS Text Module Level 3 § 8.1. First Line Indentation: the `text-indent` property](https://drafts.csswg.org/css-text/#text-indent-property)
+    pub(crate) fn apply_text_indent(&mut self, indent: CssDecimal) {
+        let Some(first_line_y) = self.line_box_fragments.first().map(|fragment| fragment.position.y) else {
+            return;
+        };
+
+        // An emoji starts a new fragment on the same line, so all fragments
+        // of the first line are shifted.
+        for fragment in &mut self.line_box_fragments {
+            if fragment.position.y != first_line_y {
+                break;
+            }
+
+            fragment.position.x += indent;
+        }
+
+        self.run_anonymous_layout_calculate_size();
+    }
+
     fn run_anonymous_layout_calculate_size(&mut self) {
         let min_x = self.line_box_fragments.iter()
             .map(|fragment| fragment.position.x)
@@ -351,12 +784,14 @@ impl LayoutBox {
             .reduce(CssDecimal::max)
             .unwrap_or_default();
 
-        let min_y = self.line_box_fragments.first()
+        let min_y = self.line_box_fragments.iter()
             .map(|fragment| fragment.position.y)
+            .reduce(CssDecimal::min)
             .unwrap_or_default();
 
-        let max_y = self.line_box_fragments.last()
+        let max_y = self.line_box_fragments.iter()
             .map(|fragment| fragment.position.y + fragment.size.height)
+            .reduce(CssDecimal::max)
             .unwrap_or_default();
 
         self.dimensions.width = CssReferencePixels::new(max_x - min_x);
@@ -384,24 +819,140 @@ impl LayoutBox {
             return;
         }
 
-        if parent.is_none() {
-            self.dimensions = self.actual_value_map.dimensions;
+        match parent {
+            Some(parent) => self.run_layout_with_size_constraints(parent),
+            None => {
+                self.dimensions = self.actual_value_map.dimensions;
+                self.run_box_layout(None);
+            }
         }
+    }
 
-        if self.run_replaced_layout() {
+    /// Lay out the box and clamp its size to the `min-*` and `max-*`
+    /// constraints. When the width is clamped, the box is laid out again
+    /// with the clamped width, such that its contents are wrapped to it.
+    ///
+    /// # References
+    /// * [CSS 2.2 § 10.4 Minimum and maximum widths](https://drafts.csswg.org/css2/#min-max-widths)
+    /// * [CSS 2.2 § 10.7 Minimum and maximum heights](https://drafts.csswg.org/css2/#min-max-heights)
+    fn run_layout_with_size_constraints(&mut self, parent: &mut FormattingContext) {
+        let constraints = self.actual_value_map.size_constraints;
+        if constraints == SizeConstraints::default() {
+            self.run_box_layout(Some(&*parent));
             return;
         }
 
-        let parent = parent.map(|parent| &*parent);
+        let initial_dimensions = self.dimensions;
+        let edges = initial_dimensions.combined_horizontal_edges();
+
+        let mut resolve_width = |constraint| match constraint {
+            SizeConstraint::None => None,
+            SizeConstraint::Length(length) => Some(length),
+            SizeConstraint::MaxContent => Some(self.measure_intrinsic_width(parent, initial_dimensions, None)),
+            SizeConstraint::MinContent => Some(self.measure_intrinsic_width(parent, initial_dimensions, Some(edges))),
+        };
+
+        let min_width = resolve_width(constraints.min_width);
+        let max_width = resolve_width(constraints.max_width);
+
+        let previous_max_width = parent.max_width;
+        if let Some(max_width) = max_width {
+            let max_width = max_width + edges;
+            if previous_max_width.map_or(true, |previous| previous > max_width) {
+                parent.max_width = Some(max_width);
+            }
+        }
+
+        self.dimensions = initial_dimensions;
+        self.dimensions.width = clamp_size(initial_dimensions.width, min_width, max_width);
+        self.run_box_layout(Some(&*parent));
+
+        let width = clamp_size(self.dimensions.width, min_width, max_width);
+        if width != self.dimensions.width {
+            self.dimensions = initial_dimensions;
+            self.dimensions.width = width;
+            parent.max_width = Some(width + edges);
+            self.run_box_layout(Some(&*parent));
+
+            // The layout of `auto` sized boxes shrinks them to their
+            // contents, which mustn't undo the clamped size.
+            self.dimensions.width = width;
+        }
+
+        parent.max_width = previous_max_width;
+
+        // The contents aren't laid out again when the height is clamped, since
+        // they just overflow the box. The min-content and max-content heights
+        // of a box are the height of its laid-out contents.
+        //
+        // TODO: this is only true when the `height` is `auto`.
+        let content_height = self.dimensions.height;
+        let resolve_height = |constraint| match constraint {
+            SizeConstraint::None => None,
+            SizeConstraint::Length(length) => Some(length),
+            SizeConstraint::MaxContent | SizeConstraint::MinContent => Some(content_height),
+        };
+
+        self.dimensions.height = clamp_size(
+            content_height,
+            resolve_height(constraints.min_height),
+            resolve_height(constraints.max_height),
+        );
+    }
+
+    /// Lay out the box to find the width of its content box when its margin
+    /// box is given the `available_width`. An available width of only the
+    /// edges of the box gives the min-content width, and no available width
+    /// the max-content width.
+    fn measure_intrinsic_width(
+        &mut self,
+        parent: &mut FormattingContext,
+        initial_dimensions: LayoutBoxDimensions,
+        available_width: Option<CssReferencePixels>,
+    ) -> CssReferencePixels {
+        let previous_max_width = std::mem::replace(&mut parent.max_width, available_width);
+
+        self.dimensions = initial_dimensions;
+        self.run_box_layout(Some(&*parent));
+
+        parent.max_width = previous_max_width;
+        self.dimensions.width
+    }
+
+    /// Lay out the box and its descendants, without regard to the `min-*` and
+    /// `max-*` constraints.
+    fn run_box_layout(&mut self, parent: Option<&FormattingContext>) {
+        if self.run_replaced_layout() {
+            return;
+        }
 
         match self.formatting_context {
-            FormattingContextKind::Block => BlockFormattingContext::perform(self, parent),
+            FormattingContextKind::Block | FormattingContextKind::InlineBlock => {
+                BlockFormattingContext::perform(self, parent)
+            }
+
+            FormattingContextKind::Grid | FormattingContextKind::InlineGrid => {
+                GridFormattingContext::perform(self, parent)
+            }
+
+            FormattingContextKind::Flex | FormattingContextKind::InlineFlex => {
+                FlexFormattingContext::perform(self, parent)
+            }
+
+            FormattingContextKind::Table | FormattingContextKind::InlineTable => {
+                TableFormattingContext::perform(self, parent)
+            }
+
             FormattingContextKind::Inline => {
                 // TODO
                 _ = parent;
                 InlineFormattingContext::perform(self, parent)
             }
         }
+
+        if !self.fixed_boxes.is_empty() {
+            FixedPositioning::perform(self);
+        }
     }
 
     fn run_replaced_layout(&mut self) -> bool {
@@ -420,17 +971,45 @@ impl LayoutBox {
                 true
             }
 
+            HtmlElementKind::Canvas(canvas) => {
+                self.run_replaced_layout_for_image(Size2D::new(canvas.width(), canvas.height()));
+                true
+            }
+
             _ => false,
         }
     }
 
+    /// Size the image, of which the `auto` dimensions are its natural size,
+    /// or computed from the other dimension using the preferred aspect ratio.
+    ///
+    /// # References
+    /// * [CSS 2.2 § 10.3.2 Inline, replaced elements](https://drafts.csswg.org/css2/#inline-replaced-width)
+    /// * [CSS 2.2 § 10.6.2 Inline replaced elements, block-level replaced elements in normal flow, ...](https://drafts.csswg.org/css2/#inline-replaced-height)
+    /// * [CSS Box Sizing Module Level 4 § 2.1. Preferred Aspect Ratios](https://drafts.csswg.org/css-sizing-4/#aspect-ratio)
     fn run_replaced_layout_for_image(&mut self, image_size: Size2D<u32>) {
-        if let CssLength::Auto = self.computed_style.width() {
-            self.dimensions.width = CssReferencePixels::new(image_size.width as _);
-        }
-
-        if let CssLength::Auto = self.computed_style.height() {
-            self.dimensions.height = CssReferencePixels::new(image_size.height as _);
+        let natural_size = Size2D::new(image_size.width as CssDecimal, image_size.height as CssDecimal);
+        let natural_ratio = (natural_size.width != 0.0 && natural_size.height != 0.0)
+            .then(|| natural_size.width / natural_size.height);
+        let ratio = self.computed_style.aspect_ratio().preferred_ratio(natural_ratio);
+
+        let is_auto_width = self.computed_style.width() == CssLength::Auto;
+        let is_auto_height = self.computed_style.height() == CssLength::Auto;
+
+        let dimensions = &mut self.dimensions;
+        if is_auto_width && is_auto_height {
+            dimensions.width = CssReferencePixels::new(natural_size.width);
+            dimensions.height = CssReferencePixels::new(
+                ratio.map_or(natural_size.height, |ratio| natural_size.width / ratio)
+            );
+        } else if is_auto_width {
+            dimensions.width = CssReferencePixels::new(
+                ratio.map_or(natural_size.width, |ratio| dimensions.height.value() * ratio)
+            );
+        } else if is_auto_height {
+            dimensions.height = CssReferencePixels::new(
+                ratio.map_or(natural_size.height, |ratio| dimensions.width.value() / ratio)
+            );
         }
     }
 }
@@ -471,6 +1050,7 @@ impl DynamicSizeOf for LayoutBox {
         let mut size = std::mem::size_of_val(self);
 
         size += self.children.dynamic_size_of();
+        size += self.fixed_boxes.dynamic_size_of();
         size += self.line_box_fragments.dynamic_size_of();
 
         if let Some(image) = &self.background_image {
@@ -486,3 +1066,120 @@ pub enum LineBreakReason {
     Normal,
     Emoji,
 }
+
+/// Calculate the size of the `text` painted with the `font`, including the
+/// additional `letter-spacing` and `word-spacing`.
+fn calculate_text_size(
+    font: &FontHandle,
+    font_size: f32,
+    text: &str,
+    hinting_options: TextHintingOptions,
+    actual_values: &ActualValueMap,
+) -> Size2D<CssDecimal> {
+    let mut size: Size2D<CssDecimal> = font.calculate_size(font_size, text, hinting_options).cast();
+
+    if !actual_values.has_normal_spacing() {
+        let spacing: CssDecimal = text.chars()
+            .map(|character| actual_values.spacing_after(character))
+            .sum();
+
+        // Negative spacing makes the glyphs overlap, but a fragment can't
+        // have a negative width, which would place the next fragment on the
+        // line before it.
+        size.width = (size.width + spacing).max(0.0);
+    }
+
+    size
+}
+
+/// Split the `word` into the pieces that fit on consecutive lines, of which
+/// the first has `first_line_width` available and the others `max_width`.
+/// Every piece has at least one grapheme cluster, even if that doesn't fit
+/// on an empty line. The pieces are returned in reverse order, such that the
+/// next piece can be popped off.
+fn split_word_into_lines<'word>(
+    font: &FontHandle,
+    word: &'word str,
+    first_line_width: f32,
+    max_width: f32,
+    font_size: f32,
+    hinting_options: TextHintingOptions,
+) -> Vec<&'word str> {
+    use unicode_segmentation::UnicodeSegmentation;
+
+    let mut pieces = Vec::new();
+    let mut remainder = word;
+    let mut available_width = first_line_width;
+
+    while !remainder.is_empty() {
+        let mut length = font.split_at_width(remainder, available_width, font_size, hinting_options);
+        if length == 0 {
+            if available_width < max_width {
+                // Nothing fits on the rest of the first line, so the word
+                // starts on the next line.
+                available_width = max_width;
+                continue;
+            }
+
+            length = remainder.graphemes(true).next().map_or(remainder.len(), str::len);
+        }
+
+        let (piece, rest) = remainder.split_at(length);
+        pieces.push(piece);
+        remainder = rest;
+        available_width = max_width;
+    }
+
+    pieces.reverse();
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::test_util::{find_box_by_id, generate_layout};
+
+    use super::*;
+
+    fn line_texts(layout_box: &LayoutBox) -> Vec<String> {
+        let mut texts: Vec<String> = layout_box.line_box_fragments
+            .iter()
+            .map(|fragment| fragment.text.to_string())
+            .collect();
+
+        for child in layout_box.children() {
+            texts.extend(line_texts(child));
+        }
+
+        texts
+    }
+
+    /// Every character of the test font is 8px wide at the initial font size,
+    /// so six of them fit on a line of 50px.
+    #[rstest]
+    #[case("overflow-wrap: anywhere", &["abcdef", "ghijkl", "mnopqr", "st"])]
+    #[case("word-break: break-all", &["abcdef", "ghijkl", "mnopqr", "st"])]
+    #[case("", &["abcdefghijklmnopqrst"])]
+    fn break_word_wider_than_line(#[case] style: &str, #[case] expected: &[&str]) {
+        let css = format!("html, body, div {{ display: block }} #container {{ width: 50px; {style} }}");
+        let root = generate_layout("<div id=container>abcdefghijklmnopqrst</div>", &css);
+
+        let container = find_box_by_id(&root, "container").unwrap();
+        assert_eq!(line_texts(container), expected);
+    }
+
+    /// With `word-break: break-all`, the word that doesn't fit starts on the
+    /// current line, whereas with `overflow-wrap: anywhere` it is only broken
+    /// when it doesn't fit on a line by itself.
+    #[rstest]
+    #[case("word-break: break-all", &["ab cde", "fghijk", "lmnop"])]
+    #[case("overflow-wrap: anywhere", &["ab ", "cdefgh", "ijklmn", "op"])]
+    fn break_word_after_other_words(#[case] style: &str, #[case] expected: &[&str]) {
+        let css = format!("html, body, div {{ display: block }} #container {{ width: 50px; {style} }}");
+        let root = generate_layout("<div id=container>ab cdefghijklmnop</div>", &css);
+
+        let container = find_box_by_id(&root, "container").unwrap();
+        assert_eq!(line_texts(container), expected);
+    }
+}
@@ -1,16 +1,20 @@
 // Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
 // All Rights Reserved.
 
-use euclid::default::Point2D;
+use euclid::default::{Point2D, Size2D};
 use retina_style::{
     CssDecimal,
     CssLength,
     CssReferencePixels,
 };
 
-use crate::LayoutBox;
+use crate::{LayoutBox, LayoutBoxKind};
 
-use super::FormattingContext;
+use super::{
+    float::FloatSide,
+    FormattingContext,
+    LengthContext,
+};
 
 pub struct BlockFormattingContext<'bx> {
     base: FormattingContext<'bx>,
@@ -47,23 +51,59 @@ impl<'bx> BlockFormattingContext<'bx> {
         let mut max_container_width: f64 = 0.0;
         self.calculate_max_size();
 
-        let content_position_origin = self.layout_box().dimensions.content_position;
+        let content_position_origin = self.base.scroll_origin;
+
+        // Only the first line of the block container is indented.
+        let mut text_indent = Some(self.resolve_text_indent());
 
         for child in &mut children {
-            child.dimensions = child.actual_value_map.dimensions;
-            child.dimensions.set_margin_position(
-                Point2D::new(
-                    content_position_origin.x,
-                    content_position_origin.y + self.y_offset,
-                )
+            if child.kind != LayoutBoxKind::Anonymous {
+                let clearance = self.base.floats.clearance(child.computed_style().clear())
+                    .map(|clearance| clearance - content_position_origin.y);
+
+                if let Some(side) = FloatSide::from_float(child.computed_style().float()) {
+                    self.layout_float(child, side, clearance);
+                    continue;
+                }
+
+                if let Some(clearance) = clearance {
+                    self.y_offset = self.y_offset.max(clearance);
+                }
+            }
+
+            let position = Point2D::new(
+                content_position_origin.x,
+                content_position_origin.y + self.y_offset,
             );
 
-            child.run_layout(Some(&mut self.base), None);
+            let (start, end) = self.base.floats.available_space(position.y, 0.0);
+            if (start, end) == self.base.floats.content_edges() {
+                child.dimensions = child.actual_value_map.dimensions;
+                child.dimensions.set_margin_position(position);
+                child.run_layout(Some(&mut self.base), None);
+            } else {
+                self.layout_child_next_to_floats(child, Point2D::new(start, position.y), end - start);
+            }
+
+            if let Some(indent) = text_indent.take() {
+                if child.kind == LayoutBoxKind::Anonymous && indent != 0.0 {
+                    child.apply_text_indent(indent);
+                }
+            }
 
             let child_size = child.dimensions.size_margin_box();
 
             self.y_offset += child_size.height;
-            max_container_width = max_container_width.max(child_size.width);
+            max_container_width = max_container_width.max(child.dimensions.position_margin_box().x + child_size.width - content_position_origin.x);
+        }
+
+        // The floats are contained by the box, i.e. it grows to include them.
+        if let Some(bottom) = self.base.floats.bottom() {
+            self.y_offset = self.y_offset.max(bottom - content_position_origin.y);
+        }
+
+        if let Some(right_edge) = self.base.floats.right_edge() {
+            max_container_width = max_container_width.max(right_edge - content_position_origin.x);
         }
 
         if let CssLength::Auto = self.layout_box().computed_style.height() {
@@ -74,6 +114,88 @@ impl<'bx> BlockFormattingContext<'bx> {
             self.layout_box().dimensions.width = CssReferencePixels::new(max_container_width);
         }
 
+        self.apply_aspect_ratio();
+
         self.layout_box().children = children;
     }
+
+    /// Resolve the `text-indent`, of which percentages refer to the inline
+    /// size of the block container.
+    fn resolve_text_indent(&mut self) -> CssDecimal {
+        let layout_box = self.layout_box();
+        let inline_size = layout_box.dimensions().width().value();
+
+        LengthContext::new(layout_box)
+            .resolve(layout_box.computed_style().text_indent(), Some(inline_size))
+            .unwrap_or(0.0)
+    }
+
+    /// Compute the `auto` dimension of the box from the other one, when it
+    /// has a preferred aspect ratio. The width is the one the height is
+    /// computed from when both are `auto`.
+    ///
+    /// # References
+    /// * [CSS Box Sizing Module Level 4 § 2.1. Preferred Aspect Ratios](https://drafts.csswg.org/css-sizing-4/#aspect-ratio)
+    fn apply_aspect_ratio(&mut self) {
+        let content_height = self.y_offset;
+        let layout_box = self.layout_box();
+
+        let Some(ratio) = layout_box.computed_style().aspect_ratio().preferred_ratio(None) else {
+            return;
+        };
+
+        if layout_box.computed_style().height() == CssLength::Auto {
+            // > In order to avoid unintentional overflow, the automatic
+            // > minimum size in the ratio-dependent axis of a box with a
+            // > preferred aspect ratio that is neither a replaced element nor
+            // > a scroll container is its min-content size.
+            let minimum = if layout_box.clips_overflow() { 0.0 } else { content_height };
+            let height = (layout_box.dimensions.width.value() / ratio).max(minimum);
+            layout_box.dimensions.height = CssReferencePixels::new(height);
+        } else if layout_box.computed_style().width() == CssLength::Auto {
+            let width = layout_box.dimensions.height.value() * ratio;
+            layout_box.dimensions.width = CssReferencePixels::new(width);
+        }
+    }
+
+    /// Lay out a float, which is taken out of the normal flow and placed
+    /// against the `side` of the containing block, at the current y-offset or
+    /// below it if there isn't enough room or it has `clearance`. Its `auto`
+    /// width is shrunk to fit its contents.
+    ///
+    /// # References
+    /// * [CSS 2.2 § 9.5.1 Positioning the float: the `float` property](https://drafts.csswg.org/css2/#float-position)
+    /// * [CSS 2.2 § 10.3.5 Floating, non-replaced elements](https://drafts.csswg.org/css2/#float-width)
+    fn layout_float(&mut self, child: &mut LayoutBox, side: FloatSide, clearance: Option<CssDecimal>) {
+        let (start, end) = self.base.floats.content_edges();
+        let available_width = end - start;
+
+        let size = self.base.measure_child(child, None);
+        let width = size.width.min(available_width);
+
+        let y = self.base.scroll_origin.y + clearance.map_or(self.y_offset, |clearance| clearance.max(self.y_offset));
+        let position = self.base.floats.place(side, Size2D::new(width, size.height), y);
+
+        let width = (child.computed_style().width() == CssLength::Auto).then_some(width);
+        self.base.layout_child(child, position, width, None, Some(available_width));
+    }
+
+    /// Lay out an in-flow child of which the top is next to one or more
+    /// floats, such that it is shifted to the side of them, and is given the
+    /// remaining `available_width`. The lines of text are shortened by the
+    /// floats they are next to, and return to the full width below them.
+    ///
+    /// TODO: boxes that don't establish a block formatting context should
+    ///       overlap the floats, with only their line boxes shortened.
+    fn layout_child_next_to_floats(&mut self, child: &mut LayoutBox, position: Point2D<CssDecimal>, available_width: CssDecimal) {
+        if child.kind == LayoutBoxKind::Anonymous {
+            child.dimensions = child.actual_value_map.dimensions;
+            child.dimensions.set_margin_position(position);
+            child.run_layout(Some(&mut self.base), None);
+            return;
+        }
+
+        let width = (child.computed_style().width() == CssLength::Auto).then_some(available_width);
+        self.base.layout_child(child, position, width, None, Some(available_width));
+    }
 }
@@ -0,0 +1,105 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! Layout of boxes with `position: fixed`, which are taken out of the normal
+//! flow and positioned relative to the viewport.
+//!
+//! # References
+//! * [CSS Positioned Layout Module Level 3 § 3.6. Fixed positioning](https://drafts.csswg.org/css-position/#fixed-position)
+
+use euclid::default::{Point2D, Size2D};
+use retina_style::{CssDecimal, CssLength};
+
+use crate::LayoutBox;
+
+use super::{FormattingContext, LengthContext};
+
+pub struct FixedPositioning;
+
+impl FixedPositioning {
+    /// Lay out the fixed boxes of the root box, after the normal flow, with
+    /// the viewport (the dimensions of the initial containing block) as their
+    /// containing block.
+    pub fn perform(root: &mut LayoutBox) {
+        let mut fixed_boxes = std::mem::take(&mut root.fixed_boxes);
+
+        let viewport = Size2D::new(
+            root.actual_value_map.dimensions.width.value(),
+            root.actual_value_map.dimensions.height.value(),
+        );
+
+        let mut context = FormattingContext::new(None, root);
+
+        for fixed_box in &mut fixed_boxes {
+            let lengths = LengthContext::new(fixed_box);
+            let style = fixed_box.computed_style();
+            let left = lengths.resolve(style.inset_left(), Some(viewport.width));
+            let right = lengths.resolve(style.inset_right(), Some(viewport.width));
+            let top = lengths.resolve(style.inset_top(), Some(viewport.height));
+            let bottom = lengths.resolve(style.inset_bottom(), Some(viewport.height));
+            let is_auto_width = style.width() == CssLength::Auto;
+
+            let available_width = viewport.width - left.unwrap_or(0.0) - right.unwrap_or(0.0);
+            let available_width = available_width.max(0.0);
+
+            // An `auto` width with both insets specified stretches the box
+            // between the insets, otherwise it fills the available space.
+            let width = if is_auto_width && left.is_some() && right.is_some() {
+                Some(available_width)
+            } else {
+                None
+            };
+
+            let size = context.measure_child(fixed_box, Some(available_width));
+
+            let position = Point2D::new(
+                resolve_offset(left, right, width.unwrap_or(size.width), viewport.width),
+                resolve_offset(top, bottom, size.height, viewport.height),
+            );
+
+            context.layout_child(fixed_box, position, width, None, Some(available_width));
+        }
+
+        root.fixed_boxes = fixed_boxes;
+    }
+}
+
+/// Resolve the offset of the margin box along an axis, from the `start` and
+/// `end` insets (e.g. `left` and `right`).
+///
+/// TODO: the static position should be used when both insets are `auto`,
+///       but fixed boxes aren't laid out in the normal flow.
+fn resolve_offset(
+    start: Option<CssDecimal>,
+    end: Option<CssDecimal>,
+    size: CssDecimal,
+    viewport: CssDecimal,
+) -> CssDecimal {
+    match (start, end) {
+        (Some(start), _) => start,
+        (None, Some(end)) => viewport - end - size,
+        (None, None) => 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Some(10.0), None, 100.0, 800.0, 10.0)]
+    #[case(Some(10.0), Some(20.0), 100.0, 800.0, 10.0)]
+    #[case(None, Some(20.0), 100.0, 800.0, 680.0)]
+    #[case(None, Some(0.0), 800.0, 800.0, 0.0)]
+    #[case(None, None, 100.0, 800.0, 0.0)]
+    fn offset(
+        #[case] start: Option<CssDecimal>,
+        #[case] end: Option<CssDecimal>,
+        #[case] size: CssDecimal,
+        #[case] viewport: CssDecimal,
+        #[case] expected: CssDecimal,
+    ) {
+        assert_eq!(resolve_offset(start, end, size, viewport), expected);
+    }
+}
@@ -0,0 +1,699 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! The [flex formatting context][spec].
+//!
+//! [spec]: https://drafts.csswg.org/css-flexbox/
+
+use std::ops::Range;
+
+use euclid::default::Point2D;
+use retina_style::{
+    CssAlignContent,
+    CssAlignItems,
+    CssDecimal,
+    CssFlexBasis,
+    CssFlexWrap,
+    CssJustifyContent,
+    CssLength,
+    CssReferencePixels,
+};
+
+use crate::LayoutBox;
+
+use super::{FormattingContext, FormattingContextKind, LengthContext, is_collapsible_whitespace};
+
+pub struct FlexFormattingContext<'bx> {
+    base: FormattingContext<'bx>,
+}
+
+impl<'bx> FlexFormattingContext<'bx> {
+    pub fn perform(layout_box: &'bx mut LayoutBox, parent: Option<&FormattingContext>) {
+        let mut instance = Self {
+            base: FormattingContext::new(parent, layout_box),
+        };
+
+        instance.perform_inner()
+    }
+
+    fn layout_box(&mut self) -> &mut LayoutBox {
+        self.base.layout_box
+    }
+
+    fn calculate_max_size(&mut self) {
+        if self.layout_box().computed_style().width() != CssLength::Auto {
+            self.base.max_width = Some(self.layout_box().dimensions().width());
+        }
+
+        if self.layout_box().computed_style().height() != CssLength::Auto {
+            self.base.max_height = Some(self.layout_box().dimensions().height());
+        }
+    }
+
+    fn perform_inner(&mut self) {
+        let mut children = std::mem::take(&mut self.layout_box().children);
+        self.calculate_max_size();
+
+        let style = self.layout_box().computed_style().clone();
+        let lengths = LengthContext::new(self.layout_box());
+
+        let direction = style.flex_direction();
+        let is_row = direction.is_row();
+        let is_reverse = direction.is_reverse();
+        let is_multi_line = style.flex_wrap() != CssFlexWrap::Nowrap;
+        let is_wrap_reverse = style.flex_wrap() == CssFlexWrap::WrapReverse;
+
+        let is_shrink_to_fit = self.layout_box().formatting_context == FormattingContextKind::InlineFlex
+            && style.width() == CssLength::Auto;
+
+        let width = if is_shrink_to_fit {
+            None
+        } else {
+            Some(self.layout_box().dimensions().width().value())
+        };
+
+        let height = if style.height() == CssLength::Auto {
+            None
+        } else {
+            Some(self.layout_box().dimensions().height().value())
+        };
+
+        let axes = FlexAxes {
+            is_row,
+            main: if is_row { width } else { height },
+            cross: if is_row { height } else { width },
+        };
+
+        // Whitespace between the items doesn't generate flex items.
+        let mut item_indices = Vec::new();
+        for (index, child) in children.iter_mut().enumerate() {
+            if is_collapsible_whitespace(child) {
+                child.dimensions = child.actual_value_map.dimensions;
+                child.dimensions.width = CssReferencePixels::new(0.0);
+                child.dimensions.height = CssReferencePixels::new(0.0);
+                child.line_box_fragments.clear();
+                continue;
+            }
+
+            item_indices.push(index);
+        }
+
+        // The items are laid out in `order`-modified document order. This
+        // sort is stable, so items with the same `order` keep their document
+        // order.
+        item_indices.sort_by_key(|index| children[*index].computed_style().order());
+
+        let mut items: Vec<_> = item_indices.into_iter()
+            .map(|index| self.create_item(&mut children[index], index, axes, style.align_items(), lengths))
+            .collect();
+
+        let lines = collect_lines(&items, axes.main, is_multi_line);
+        for line in &lines {
+            resolve_flexible_lengths(&mut items[line.clone()], axes.main);
+        }
+
+        // The hypothetical cross size of the items in a row is their height
+        // at the resolved width. For columns, the width was already
+        // determined when creating the items.
+        if is_row {
+            let origin = self.base.scroll_origin;
+            for item in &mut items {
+                let child = &mut children[item.index];
+                self.base.layout_child(child, origin, Some(item.target), None, Some(item.target));
+                item.cross = child.dimensions.size_margin_box().height;
+            }
+        }
+
+        //
+        // Cross sizes of the lines
+        //
+        let mut line_cross_sizes: Vec<CssDecimal> = lines.iter()
+            .map(|line| items[line.clone()].iter().fold(0.0, |size, item| item.cross.max(size)))
+            .collect();
+
+        // > If the flex container is single-line and has a definite cross
+        // > size, the cross size of the flex line is the flex container’s
+        // > inner cross size.
+        if !is_multi_line {
+            if let Some(cross) = axes.cross {
+                line_cross_sizes[0] = cross;
+            }
+        }
+
+        let (mut line_position, line_gap) = match axes.cross {
+            Some(cross) if is_multi_line => {
+                align_lines(style.align_content(), is_wrap_reverse, cross, &mut line_cross_sizes)
+            }
+            _ => (0.0, 0.0),
+        };
+
+        let main_size = axes.main.unwrap_or_else(|| {
+            lines.iter()
+                .map(|line| items[line.clone()].iter().map(|item| item.target).sum())
+                .fold(0.0, CssDecimal::max)
+        });
+
+        let cross_size = axes.cross.unwrap_or_else(|| line_cross_sizes.iter().sum());
+
+        //
+        // Position the items.
+        //
+        let justification = Distribution::from_justify_content(style.justify_content(), is_reverse);
+        let origin = self.base.scroll_origin;
+
+        for (line, &line_cross_size) in lines.iter().zip(&line_cross_sizes) {
+            let line_items = &items[line.clone()];
+
+            let used_main_size: CssDecimal = line_items.iter().map(|item| item.target).sum();
+            let (mut main_position, main_gap) = justification.distribute(main_size - used_main_size, line_items.len());
+
+            for item in line_items {
+                let child = &mut children[item.index];
+
+                let item_cross_size = if item.is_stretched {
+                    line_cross_size
+                } else {
+                    item.cross
+                };
+
+                let offset_in_line = item.cross_offset(line_cross_size - item_cross_size, is_wrap_reverse);
+
+                let main = if is_reverse {
+                    main_size - main_position - item.target
+                } else {
+                    main_position
+                };
+
+                let cross = if is_wrap_reverse {
+                    cross_size - (line_position + offset_in_line) - item_cross_size
+                } else {
+                    line_position + offset_in_line
+                };
+
+                main_position += item.target + main_gap;
+
+                if is_row {
+                    let height = Some(item_cross_size).filter(|_| item.is_stretched);
+                    let position = Point2D::new(origin.x + main, origin.y + cross);
+                    self.base.layout_child(child, position, Some(item.target), height, Some(item.target));
+                } else {
+                    let position = Point2D::new(origin.x + cross, origin.y + main);
+                    self.base.layout_child(child, position, Some(item_cross_size), Some(item.target), Some(item_cross_size));
+                }
+            }
+
+            line_position += line_cross_size + line_gap;
+        }
+
+        let (width, height) = if is_row {
+            (main_size, cross_size)
+        } else {
+            (cross_size, main_size)
+        };
+
+        if is_shrink_to_fit {
+            self.layout_box().dimensions.width = CssReferencePixels::new(width);
+        }
+
+        if let CssLength::Auto = style.height() {
+            self.layout_box().dimensions.height = CssReferencePixels::new(height);
+        }
+
+        self.layout_box().children = children;
+    }
+
+    /// Determine the [flex base size and hypothetical main size][spec] of the
+    /// item, with all sizes being outer sizes.
+    ///
+    /// [spec]: https://drafts.csswg.org/css-flexbox/#algo-main-item
+    fn create_item(
+        &mut self,
+        child: &mut LayoutBox,
+        index: usize,
+        axes: FlexAxes,
+        align_items: CssAlignItems,
+        lengths: LengthContext,
+    ) -> FlexItem {
+        let style = child.computed_style();
+        let dimensions = child.actual_value_map.dimensions;
+
+        let align = style.align_self().resolve(align_items);
+        let grow = style.flex_grow();
+        let shrink = style.flex_shrink();
+        let basis = style.flex_basis();
+
+        let horizontal_edges = dimensions.combined_horizontal_edges().value();
+        let vertical_edges = dimensions.combined_vertical_edges().value();
+
+        let specified_width = (style.width() != CssLength::Auto)
+            .then(|| dimensions.width().value() + horizontal_edges);
+        let specified_height = (style.height() != CssLength::Auto)
+            .then(|| dimensions.height().value() + vertical_edges);
+
+        let (specified_main_size, specified_cross_size, main_edges) = if axes.is_row {
+            (specified_width, specified_height, horizontal_edges)
+        } else {
+            (specified_height, specified_width, vertical_edges)
+        };
+
+        let is_stretched = specified_cross_size.is_none()
+            && matches!(align, CssAlignItems::Normal | CssAlignItems::Stretch);
+
+        let (min_content_size, max_content_size, cross) = if axes.is_row {
+            let min_content = self.base.measure_child(child, Some(0.0)).width;
+            let max_content = self.base.measure_child(child, None).width;
+            (min_content, max_content, 0.0)
+        } else {
+            // The width of an item in a column is the width of the container
+            // when stretched, and otherwise its fit-content width.
+            let cross = specified_cross_size.unwrap_or_else(|| {
+                let max_content = self.base.measure_child(child, None).width;
+                match axes.cross {
+                    Some(container) if is_stretched => container,
+                    Some(container) => max_content.min(container),
+                    None => max_content,
+                }
+            });
+
+            let origin = self.base.scroll_origin;
+            self.base.layout_child(child, origin, Some(cross), None, Some(cross));
+            let content_height = child.dimensions.size_margin_box().height;
+            (content_height, content_height, cross)
+        };
+
+        // > If the used flex basis is content or depends on its available
+        // > space, [...] the flex base size is the item’s resulting main size.
+        let base_size = match basis {
+            CssFlexBasis::Auto => specified_main_size,
+            CssFlexBasis::Content => None,
+            CssFlexBasis::Length(length) => lengths.resolve(length, axes.main).map(|size| size + main_edges),
+        }.unwrap_or(max_content_size);
+
+        // The automatic minimum size of flex items, which is the smaller of
+        // the content size and the specified size suggestions.
+        let min_size = match specified_main_size {
+            Some(specified) => specified.min(min_content_size),
+            None => min_content_size,
+        };
+
+        FlexItem {
+            index,
+            base_size,
+            min_size,
+            grow,
+            shrink,
+            target: base_size.max(min_size),
+            is_frozen: false,
+            cross,
+            align,
+            is_stretched,
+        }
+    }
+}
+
+/// The directions of the axes, and the inner sizes of the container along
+/// them, if they are definite.
+#[derive(Copy, Clone, Debug)]
+struct FlexAxes {
+    is_row: bool,
+    main: Option<CssDecimal>,
+    cross: Option<CssDecimal>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct FlexItem {
+    /// The index of the box in the children of the container.
+    index: usize,
+
+    base_size: CssDecimal,
+    min_size: CssDecimal,
+    grow: CssDecimal,
+    shrink: CssDecimal,
+
+    /// The target main size, which is the used main size after resolving the
+    /// flexible lengths.
+    target: CssDecimal,
+    is_frozen: bool,
+
+    /// The hypothetical cross size.
+    cross: CssDecimal,
+
+    /// The `align-self` value, with `auto` resolved.
+    align: CssAlignItems,
+    is_stretched: bool,
+}
+
+impl FlexItem {
+    /// The flex base size clamped by the minimum size.
+    fn hypothetical_size(&self) -> CssDecimal {
+        self.base_size.max(self.min_size)
+    }
+
+    /// The offset of the item in its line, given the free space in the cross
+    /// axis of the line.
+    ///
+    /// TODO: `baseline` alignment is treated as `flex-start`.
+    fn cross_offset(&self, free_space: CssDecimal, is_wrap_reverse: bool) -> CssDecimal {
+        match self.align {
+            CssAlignItems::Normal
+                | CssAlignItems::Stretch
+                | CssAlignItems::FlexStart
+                | CssAlignItems::Baseline => 0.0,
+            CssAlignItems::FlexEnd => free_space,
+            CssAlignItems::Center => free_space / 2.0,
+
+            // `start` and `end` are relative to the writing mode, which
+            // `wrap-reverse` flips in relation to the cross axis.
+            CssAlignItems::Start if is_wrap_reverse => free_space,
+            CssAlignItems::Start => 0.0,
+            CssAlignItems::End if is_wrap_reverse => 0.0,
+            CssAlignItems::End => free_space,
+        }
+    }
+}
+
+/// [Collect the flex items into flex lines][spec]. A single-line container,
+/// or one with an indefinite main size, has all its items in one line.
+///
+/// [spec]: https://drafts.csswg.org/css-flexbox/#algo-line-break
+fn collect_lines(items: &[FlexItem], available: Option<CssDecimal>, is_multi_line: bool) -> Vec<Range<usize>> {
+    let available = match available {
+        Some(available) if is_multi_line => available,
+        _ => return vec![0..items.len()],
+    };
+
+    let mut lines = Vec::new();
+    let mut start = 0;
+    let mut used = 0.0;
+
+    for (index, item) in items.iter().enumerate() {
+        let size = item.hypothetical_size();
+        if index > start && used + size > available {
+            lines.push(start..index);
+            start = index;
+            used = 0.0;
+        }
+
+        used += size;
+    }
+
+    lines.push(start..items.len());
+    lines
+}
+
+/// [Resolve the flexible lengths][spec] of the items in a line, setting their
+/// target main sizes.
+///
+/// [spec]: https://drafts.csswg.org/css-flexbox/#resolve-flexible-lengths
+fn resolve_flexible_lengths(items: &mut [FlexItem], available: Option<CssDecimal>) {
+    for item in items.iter_mut() {
+        item.target = item.hypothetical_size();
+        item.is_frozen = false;
+    }
+
+    // Without a definite main size, the items are sized as their
+    // hypothetical main sizes, which then determine the container size.
+    let Some(available) = available else {
+        return;
+    };
+
+    // > 1. Determine the used flex factor.
+    let hypothetical_sum: CssDecimal = items.iter().map(FlexItem::hypothetical_size).sum();
+    let is_growing = hypothetical_sum < available;
+
+    let flex_factor = |item: &FlexItem| if is_growing { item.grow } else { item.shrink };
+
+    // > 2. Size inflexible items.
+    for item in items.iter_mut() {
+        item.is_frozen = flex_factor(&*item) == 0.0
+            || (is_growing && item.base_size > item.target)
+            || (!is_growing && item.base_size < item.target);
+    }
+
+    // > 3. Calculate initial free space.
+    let free_space = |items: &[FlexItem]| -> CssDecimal {
+        available - items.iter()
+            .map(|item| if item.is_frozen { item.target } else { item.base_size })
+            .sum::<CssDecimal>()
+    };
+
+    let initial_free_space = free_space(items);
+
+    // > 4. Loop
+    while items.iter().any(|item| !item.is_frozen) {
+        // > b. Calculate the remaining free space.
+        let mut remaining_free_space = free_space(items);
+
+        let factor_sum: CssDecimal = items.iter()
+            .filter(|item| !item.is_frozen)
+            .map(flex_factor)
+            .sum();
+
+        if factor_sum < 1.0 {
+            let scaled = initial_free_space * factor_sum;
+            if scaled.abs() < remaining_free_space.abs() {
+                remaining_free_space = scaled;
+            }
+        }
+
+        // > c. Distribute free space proportional to the flex factors.
+        let scaled_shrink_sum: CssDecimal = items.iter()
+            .filter(|item| !item.is_frozen)
+            .map(|item| item.shrink * item.base_size)
+            .sum();
+
+        for item in items.iter_mut().filter(|item| !item.is_frozen) {
+            item.target = item.base_size;
+
+            if remaining_free_space == 0.0 {
+                continue;
+            }
+
+            if is_growing {
+                item.target += remaining_free_space * item.grow / factor_sum;
+            } else if scaled_shrink_sum > 0.0 {
+                let ratio = item.shrink * item.base_size / scaled_shrink_sum;
+                item.target -= remaining_free_space.abs() * ratio;
+            }
+        }
+
+        // > d. Fix min/max violations.
+        let mut total_violation = 0.0;
+        let mut violations = vec![0.0; items.len()];
+        for (item, violation) in items.iter_mut().zip(&mut violations) {
+            if item.is_frozen {
+                continue;
+            }
+
+            let clamped = item.target.max(item.min_size);
+            *violation = clamped - item.target;
+            total_violation += *violation;
+            item.target = clamped;
+        }
+
+        // > e. Freeze over-flexed items.
+        for (item, violation) in items.iter_mut().zip(violations) {
+            if total_violation == 0.0 || violation > 0.0 {
+                item.is_frozen = true;
+            }
+        }
+    }
+}
+
+/// Distribution of free space along an axis, for `justify-content` and
+/// `align-content`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Distribution {
+    Start,
+    End,
+    Center,
+    SpaceBetween,
+    SpaceAround,
+    SpaceEvenly,
+}
+
+impl Distribution {
+    /// `start` and `end` are relative to the writing mode, so they are
+    /// swapped when the axis is reversed.
+    fn from_justify_content(value: CssJustifyContent, is_reverse: bool) -> Self {
+        match value {
+            CssJustifyContent::Normal | CssJustifyContent::FlexStart => Self::Start,
+            CssJustifyContent::FlexEnd => Self::End,
+            CssJustifyContent::Start if is_reverse => Self::End,
+            CssJustifyContent::Start => Self::Start,
+            CssJustifyContent::End if is_reverse => Self::Start,
+            CssJustifyContent::End => Self::End,
+            CssJustifyContent::Center => Self::Center,
+            CssJustifyContent::SpaceBetween => Self::SpaceBetween,
+            CssJustifyContent::SpaceAround => Self::SpaceAround,
+            CssJustifyContent::SpaceEvenly => Self::SpaceEvenly,
+        }
+    }
+
+    /// Returns the offset of the first box and the gap between the boxes.
+    /// The space distributions fall back to `flex-start` or `center` when
+    /// there is no free space to distribute.
+    fn distribute(self, free_space: CssDecimal, count: usize) -> (CssDecimal, CssDecimal) {
+        let count = count as CssDecimal;
+
+        match self {
+            Self::Start => (0.0, 0.0),
+            Self::End => (free_space, 0.0),
+            Self::Center => (free_space / 2.0, 0.0),
+
+            Self::SpaceBetween if free_space > 0.0 && count > 1.0 => (0.0, free_space / (count - 1.0)),
+            Self::SpaceBetween => (0.0, 0.0),
+
+            Self::SpaceAround if free_space > 0.0 => {
+                let gap = free_space / count;
+                (gap / 2.0, gap)
+            }
+
+            Self::SpaceEvenly if free_space > 0.0 => {
+                let gap = free_space / (count + 1.0);
+                (gap, gap)
+            }
+
+            Self::SpaceAround | Self::SpaceEvenly => (free_space / 2.0, 0.0),
+        }
+    }
+}
+
+/// [Align the flex lines][spec] of a multi-line container within its cross
+/// size, returning the offset of the first line and the gap between the
+/// lines. `stretch` grows the cross sizes of the lines instead.
+///
+/// [spec]: https://drafts.csswg.org/css-flexbox/#algo-line-align
+fn align_lines(
+    align_content: CssAlignContent,
+    is_wrap_reverse: bool,
+    container_cross_size: CssDecimal,
+    line_cross_sizes: &mut [CssDecimal],
+) -> (CssDecimal, CssDecimal) {
+    let free_space = container_cross_size - line_cross_sizes.iter().sum::<CssDecimal>();
+
+    let distribution = match align_content {
+        CssAlignContent::Normal | CssAlignContent::Stretch => {
+            if free_space > 0.0 && !line_cross_sizes.is_empty() {
+                let extra = free_space / line_cross_sizes.len() as CssDecimal;
+                for size in line_cross_sizes.iter_mut() {
+                    *size += extra;
+                }
+            }
+
+            return (0.0, 0.0);
+        }
+
+        CssAlignContent::FlexStart => Distribution::Start,
+        CssAlignContent::FlexEnd => Distribution::End,
+        CssAlignContent::Start if is_wrap_reverse => Distribution::End,
+        CssAlignContent::Start => Distribution::Start,
+        CssAlignContent::End if is_wrap_reverse => Distribution::Start,
+        CssAlignContent::End => Distribution::End,
+        CssAlignContent::Center => Distribution::Center,
+        CssAlignContent::SpaceBetween => Distribution::SpaceBetween,
+        CssAlignContent::SpaceAround => Distribution::SpaceAround,
+        CssAlignContent::SpaceEvenly => Distribution::SpaceEvenly,
+    };
+
+    distribution.distribute(free_space, line_cross_sizes.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn item(base_size: CssDecimal, min_size: CssDecimal, grow: CssDecimal, shrink: CssDecimal) -> FlexItem {
+        FlexItem {
+            index: 0,
+            base_size,
+            min_size,
+            grow,
+            shrink,
+            target: 0.0,
+            is_frozen: false,
+            cross: 0.0,
+            align: CssAlignItems::Normal,
+            is_stretched: true,
+        }
+    }
+
+    fn targets(items: &[FlexItem]) -> Vec<CssDecimal> {
+        items.iter().map(|item| item.target).collect()
+    }
+
+    #[test]
+    fn grow_distributes_free_space_by_factor() {
+        let mut items = vec![
+            item(0.0, 0.0, 1.0, 1.0),
+            item(0.0, 0.0, 3.0, 1.0),
+            item(100.0, 0.0, 0.0, 1.0),
+        ];
+
+        resolve_flexible_lengths(&mut items, Some(500.0));
+        assert_eq!(targets(&items), vec![100.0, 300.0, 100.0]);
+    }
+
+    #[test]
+    fn grow_factors_below_one_leave_free_space() {
+        let mut items = vec![item(100.0, 0.0, 0.25, 1.0)];
+
+        resolve_flexible_lengths(&mut items, Some(500.0));
+        assert_eq!(targets(&items), vec![200.0]);
+    }
+
+    #[test]
+    fn shrink_is_weighted_by_base_size() {
+        let mut items = vec![
+            item(300.0, 0.0, 0.0, 1.0),
+            item(100.0, 0.0, 0.0, 1.0),
+        ];
+
+        resolve_flexible_lengths(&mut items, Some(200.0));
+        assert_eq!(targets(&items), vec![150.0, 50.0]);
+    }
+
+    #[test]
+    fn shrink_freezes_items_at_their_minimum_size() {
+        let mut items = vec![
+            item(200.0, 150.0, 0.0, 1.0),
+            item(200.0, 0.0, 0.0, 1.0),
+        ];
+
+        resolve_flexible_lengths(&mut items, Some(200.0));
+        assert_eq!(targets(&items), vec![150.0, 50.0]);
+    }
+
+    #[test]
+    fn lines_break_before_overflowing_items() {
+        let items = vec![
+            item(100.0, 0.0, 0.0, 1.0),
+            item(100.0, 0.0, 0.0, 1.0),
+            item(250.0, 0.0, 0.0, 1.0),
+            item(50.0, 0.0, 0.0, 1.0),
+        ];
+
+        assert_eq!(collect_lines(&items, Some(250.0), true), vec![0..2, 2..3, 3..4]);
+        assert_eq!(collect_lines(&items, Some(250.0), false), vec![0..4]);
+        assert_eq!(collect_lines(&items, None, true), vec![0..4]);
+    }
+
+    #[rstest]
+    #[case(Distribution::Start, 120.0, 3, (0.0, 0.0))]
+    #[case(Distribution::End, 120.0, 3, (120.0, 0.0))]
+    #[case(Distribution::Center, 120.0, 3, (60.0, 0.0))]
+    #[case(Distribution::SpaceBetween, 120.0, 3, (0.0, 60.0))]
+    #[case(Distribution::SpaceBetween, 120.0, 1, (0.0, 0.0))]
+    #[case(Distribution::SpaceAround, 120.0, 3, (20.0, 40.0))]
+    #[case(Distribution::SpaceEvenly, 120.0, 3, (30.0, 30.0))]
+    #[case(Distribution::SpaceEvenly, -40.0, 3, (-20.0, 0.0))]
+    fn distribute(
+        #[case] distribution: Distribution,
+        #[case] free_space: CssDecimal,
+        #[case] count: usize,
+        #[case] expected: (CssDecimal, CssDecimal),
+    ) {
+        assert_eq!(distribution.distribute(free_space, count), expected);
+    }
+}
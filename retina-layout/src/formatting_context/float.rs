@@ -0,0 +1,256 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! The bookkeeping of the floats of a block container, which are taken out of
+//! the normal flow and shift the content following them to the side.
+//!
+//! # References
+//! * [CSS 2.2 § 9.5 Floats](https://drafts.csswg.org/css2/#floats)
+
+use euclid::default::{Point2D, Rect, Size2D};
+use retina_style::{
+    CssClear,
+    CssDecimal,
+    CssFloatValue,
+};
+
+/// The side of the containing block a float is placed against.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum FloatSide {
+    Left,
+    Right,
+}
+
+impl FloatSide {
+    /// Get the side for the computed value of `float`, or `None` if the box
+    /// isn't floated.
+    ///
+    /// TODO: the logical values assume a left-to-right direction.
+    pub(crate) fn from_float(float: CssFloatValue) -> Option<Self> {
+        match float {
+            CssFloatValue::None => None,
+            CssFloatValue::Left | CssFloatValue::InlineStart => Some(Self::Left),
+            CssFloatValue::Right | CssFloatValue::InlineEnd => Some(Self::Right),
+        }
+    }
+}
+
+/// The floats placed in a block container so far, which are stored as their
+/// margin boxes in the coordinates of the layout. These determine the space
+/// that is available to the content following them at a given y-offset.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct FloatList {
+    /// The x-coordinate of the left content edge of the containing block.
+    start: CssDecimal,
+
+    /// The x-coordinate of the right content edge of the containing block.
+    end: CssDecimal,
+
+    left: Vec<Rect<CssDecimal>>,
+    right: Vec<Rect<CssDecimal>>,
+}
+
+impl FloatList {
+    pub(crate) fn new(start: CssDecimal, end: CssDecimal) -> Self {
+        Self {
+            start,
+            end: end.max(start),
+            left: Vec::new(),
+            right: Vec::new(),
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.left.is_empty() && self.right.is_empty()
+    }
+
+    /// Get the x-coordinates of the left and right content edges of the
+    /// containing block, i.e. the available space without any floats.
+    pub(crate) fn content_edges(&self) -> (CssDecimal, CssDecimal) {
+        (self.start, self.end)
+    }
+
+    /// Get the x-coordinates of the left and right edges of the space that is
+    /// available to content in the band from `y` to `y + height`, which is
+    /// the content box of the containing block, shortened by the floats that
+    /// intersect the band.
+    pub(crate) fn available_space(&self, y: CssDecimal, height: CssDecimal) -> (CssDecimal, CssDecimal) {
+        let start = self.left.iter()
+            .filter(|float| intersects_band(float, y, height))
+            .map(|float| float.max_x())
+            .fold(self.start, CssDecimal::max);
+
+        let end = self.right.iter()
+            .filter(|float| intersects_band(float, y, height))
+            .map(|float| float.min_x())
+            .fold(self.end, CssDecimal::min);
+
+        (start, end.max(start))
+    }
+
+    /// Place a float with the given margin box `size` against the `side` of
+    /// the containing block, at or below `y`, and return the position of its
+    /// margin box.
+    ///
+    /// # References
+    /// * [CSS 2.2 § 9.5.1 Positioning the float: the `float` property](https://drafts.csswg.org/css2/#float-position)
+    pub(crate) fn place(&mut self, side: FloatSide, size: Size2D<CssDecimal>, y: CssDecimal) -> Point2D<CssDecimal> {
+        // > The outer top of a floating box may not be higher than the outer
+        // > top of any block or floated box generated by an element earlier
+        // > in the source document.
+        let mut y = self.left.iter()
+            .chain(&self.right)
+            .map(|float| float.min_y())
+            .fold(y, CssDecimal::max);
+
+        // > If there isn't enough horizontal room for the float, it is
+        // > shifted downward until either it fits or there are no more floats
+        // > present.
+        let (start, end) = loop {
+            let (start, end) = self.available_space(y, size.height);
+            if end - start >= size.width {
+                break (start, end);
+            }
+
+            let next_y = self.left.iter()
+                .chain(&self.right)
+                .filter(|float| intersects_band(float, y, size.height))
+                .map(|float| float.max_y())
+                .reduce(CssDecimal::min);
+
+            match next_y {
+                Some(next_y) => y = next_y,
+                None => break (start, end),
+            }
+        };
+
+        let position = match side {
+            FloatSide::Left => Point2D::new(start, y),
+            FloatSide::Right => Point2D::new(end - size.width, y),
+        };
+
+        let rect = Rect::new(position, size);
+        match side {
+            FloatSide::Left => self.left.push(rect),
+            FloatSide::Right => self.right.push(rect),
+        }
+
+        position
+    }
+
+    /// Get the y-coordinate below the floats on the sides specified by the
+    /// `clear` value, or `None` if the box doesn't need clearance.
+    ///
+    /// # References
+    /// * [CSS 2.2 § 9.5.2 Controlling flow next to floats: the `clear` property](https://drafts.csswg.org/css2/#flow-control)
+    pub(crate) fn clearance(&self, clear: CssClear) -> Option<CssDecimal> {
+        let floats: &[&[Rect<CssDecimal>]] = match clear {
+            CssClear::None => return None,
+            CssClear::Left | CssClear::InlineStart => &[&self.left],
+            CssClear::Right | CssClear::InlineEnd => &[&self.right],
+            CssClear::Both => &[&self.left, &self.right],
+        };
+
+        floats.iter()
+            .flat_map(|floats| floats.iter())
+            .map(|float| float.max_y())
+            .reduce(CssDecimal::max)
+    }
+
+    /// Get the bottom edge of the lowest float, which a block container with
+    /// an `auto` height grows to contain.
+    pub(crate) fn bottom(&self) -> Option<CssDecimal> {
+        self.clearance(CssClear::Both)
+    }
+
+    /// Get the right edge of the rightmost float.
+    pub(crate) fn right_edge(&self) -> Option<CssDecimal> {
+        self.left.iter()
+            .chain(&self.right)
+            .map(|float| float.max_x())
+            .reduce(CssDecimal::max)
+    }
+}
+
+/// Whether or not the float intersects the horizontal band from `y` to
+/// `y + height`. An empty band intersects the floats it is inside of.
+fn intersects_band(float: &Rect<CssDecimal>, y: CssDecimal, height: CssDecimal) -> bool {
+    if float.max_y() <= y {
+        return false;
+    }
+
+    if height > 0.0 {
+        float.min_y() < y + height
+    } else {
+        float.min_y() <= y
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn size(width: CssDecimal, height: CssDecimal) -> Size2D<CssDecimal> {
+        Size2D::new(width, height)
+    }
+
+    #[test]
+    fn place_left_floats_next_to_each_other() {
+        let mut floats = FloatList::new(0.0, 500.0);
+
+        assert_eq!(floats.place(FloatSide::Left, size(100.0, 50.0), 0.0), Point2D::new(0.0, 0.0));
+        assert_eq!(floats.place(FloatSide::Left, size(100.0, 80.0), 0.0), Point2D::new(100.0, 0.0));
+        assert_eq!(floats.available_space(10.0, 20.0), (200.0, 500.0));
+        assert_eq!(floats.available_space(60.0, 10.0), (200.0, 500.0));
+        assert_eq!(floats.available_space(80.0, 10.0), (0.0, 500.0));
+    }
+
+    #[test]
+    fn place_right_float() {
+        let mut floats = FloatList::new(10.0, 510.0);
+
+        assert_eq!(floats.place(FloatSide::Right, size(100.0, 50.0), 20.0), Point2D::new(410.0, 20.0));
+        assert_eq!(floats.available_space(0.0, 10.0), (10.0, 510.0));
+        assert_eq!(floats.available_space(0.0, 30.0), (10.0, 410.0));
+    }
+
+    #[test]
+    fn place_float_below_when_it_does_not_fit() {
+        let mut floats = FloatList::new(0.0, 300.0);
+
+        floats.place(FloatSide::Left, size(200.0, 50.0), 0.0);
+        floats.place(FloatSide::Right, size(50.0, 100.0), 0.0);
+
+        // Beside the left float, there is only 50px of room.
+        assert_eq!(floats.place(FloatSide::Left, size(100.0, 10.0), 0.0), Point2D::new(0.0, 50.0));
+    }
+
+    #[test]
+    fn place_float_not_above_earlier_float() {
+        let mut floats = FloatList::new(0.0, 300.0);
+
+        floats.place(FloatSide::Left, size(100.0, 10.0), 40.0);
+        assert_eq!(floats.place(FloatSide::Right, size(100.0, 10.0), 0.0), Point2D::new(200.0, 40.0));
+    }
+
+    #[test]
+    fn place_float_wider_than_container() {
+        let mut floats = FloatList::new(0.0, 100.0);
+
+        assert_eq!(floats.place(FloatSide::Left, size(200.0, 10.0), 0.0), Point2D::new(0.0, 0.0));
+    }
+
+    #[rstest]
+    #[case(CssClear::None, None)]
+    #[case(CssClear::Left, Some(50.0))]
+    #[case(CssClear::Right, Some(120.0))]
+    #[case(CssClear::Both, Some(120.0))]
+    fn clearance(#[case] clear: CssClear, #[case] expected: Option<CssDecimal>) {
+        let mut floats = FloatList::new(0.0, 500.0);
+        floats.place(FloatSide::Left, size(100.0, 50.0), 0.0);
+        floats.place(FloatSide::Right, size(100.0, 100.0), 20.0);
+
+        assert_eq!(floats.clearance(clear), expected);
+    }
+}
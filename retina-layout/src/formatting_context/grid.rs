@@ -0,0 +1,880 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! The [grid formatting context][spec].
+//!
+//! [spec]: https://drafts.csswg.org/css-grid/
+
+use euclid::default::{Point2D, Size2D};
+use retina_style::{
+    CssDecimal,
+    CssGridLine,
+    CssGridPlacement,
+    CssGridTemplateAreas,
+    CssGridTrackBreadth,
+    CssGridTrackList,
+    CssGridTrackSize,
+    CssLength,
+    CssReferencePixels,
+};
+
+use crate::LayoutBox;
+
+use super::{FormattingContext, FormattingContextKind, LengthContext, is_collapsible_whitespace};
+
+pub struct GridFormattingContext<'bx> {
+    base: FormattingContext<'bx>,
+}
+
+impl<'bx> GridFormattingContext<'bx> {
+    pub fn perform(layout_box: &'bx mut LayoutBox, parent: Option<&FormattingContext>) {
+        let mut instance = Self {
+            base: FormattingContext::new(parent, layout_box),
+        };
+
+        instance.perform_inner()
+    }
+
+    fn layout_box(&mut self) -> &mut LayoutBox {
+        self.base.layout_box
+    }
+
+    fn calculate_max_size(&mut self) {
+        if self.layout_box().computed_style().width() != CssLength::Auto {
+            self.base.max_width = Some(self.layout_box().dimensions().width());
+        }
+
+        if self.layout_box().computed_style().height() != CssLength::Auto {
+            self.base.max_height = Some(self.layout_box().dimensions().height());
+        }
+    }
+
+    fn perform_inner(&mut self) {
+        let mut children = std::mem::take(&mut self.layout_box().children);
+        self.calculate_max_size();
+
+        let style = self.layout_box().computed_style().clone();
+        let lengths = LengthContext::new(self.layout_box());
+
+        // Whitespace between the items doesn't generate grid items.
+        let mut item_indices = Vec::new();
+        for (index, child) in children.iter_mut().enumerate() {
+            if is_collapsible_whitespace(child) {
+                child.dimensions = child.actual_value_map.dimensions;
+                child.dimensions.width = CssReferencePixels::new(0.0);
+                child.dimensions.height = CssReferencePixels::new(0.0);
+                child.line_box_fragments.clear();
+                continue;
+            }
+
+            item_indices.push(index);
+        }
+
+        let areas = style.grid_template_areas();
+        let (area_rows, area_columns) = areas.size();
+
+        let template_columns = explicit_tracks(style.grid_template_columns());
+        let template_rows = explicit_tracks(style.grid_template_rows());
+
+        let explicit_columns = template_columns.len().max(area_columns);
+        let explicit_rows = template_rows.len().max(area_rows);
+
+        let placements: Vec<_> = item_indices.iter()
+            .map(|index| {
+                let style = children[*index].computed_style();
+                (style.grid_row().clone(), style.grid_column().clone())
+            })
+            .collect();
+
+        let grid = place_items(&placements, explicit_rows, explicit_columns, areas);
+
+        //
+        // Columns
+        //
+        let is_shrink_to_fit = self.layout_box().formatting_context == FormattingContextKind::InlineGrid
+            && style.width() == CssLength::Auto;
+
+        let available_width = if is_shrink_to_fit {
+            None
+        } else {
+            Some(self.layout_box().dimensions().width().value())
+        };
+
+        let mut columns = create_tracks(&template_columns, grid.columns, available_width, lengths);
+
+        let column_items: Vec<_> = item_indices.iter()
+            .zip(&grid.items)
+            .map(|(index, (_, column))| {
+                let child = &mut children[*index];
+                TrackSizingItem {
+                    span: *column,
+                    min_content: self.base.measure_child(child, Some(0.0)).width,
+                    max_content: self.base.measure_child(child, None).width,
+                }
+            })
+            .collect();
+
+        size_tracks(&mut columns, &column_items, available_width);
+        let column_offsets = track_offsets(&columns);
+
+        //
+        // Rows
+        //
+        let available_height = if style.height() == CssLength::Auto {
+            None
+        } else {
+            Some(self.layout_box().dimensions().height().value())
+        };
+
+        let mut rows = create_tracks(&template_rows, grid.rows, available_height, lengths);
+
+        let row_items: Vec<_> = item_indices.iter()
+            .zip(&grid.items)
+            .map(|(index, (row, column))| {
+                let width = column_offsets[column.end()] - column_offsets[column.start];
+                let height = self.base.measure_child(&mut children[*index], Some(width)).height;
+                TrackSizingItem {
+                    span: *row,
+                    min_content: height,
+                    max_content: height,
+                }
+            })
+            .collect();
+
+        size_tracks(&mut rows, &row_items, available_height);
+        let row_offsets = track_offsets(&rows);
+
+        //
+        // Position the items in their grid areas.
+        //
+        let origin = self.base.scroll_origin;
+        for (index, (row, column)) in item_indices.iter().zip(&grid.items) {
+            let child = &mut children[*index];
+
+            let area_position = Point2D::new(
+                origin.x + column_offsets[column.start],
+                origin.y + row_offsets[row.start],
+            );
+
+            let area_size = Size2D::new(
+                column_offsets[column.end()] - column_offsets[column.start],
+                row_offsets[row.end()] - row_offsets[row.start],
+            );
+
+            self.layout_item(child, area_position, area_size);
+        }
+
+        if let CssLength::Auto = style.height() {
+            self.layout_box().dimensions.height = CssReferencePixels::new(row_offsets.last().copied().unwrap_or_default());
+        }
+
+        if is_shrink_to_fit {
+            self.layout_box().dimensions.width = CssReferencePixels::new(column_offsets.last().copied().unwrap_or_default());
+        }
+
+        self.layout_box().children = children;
+    }
+
+    /// Lay out the item in its grid area, stretching it when its size is
+    /// `auto`, which is the behavior of `justify-self` and `align-self` for
+    /// grid items by default.
+    fn layout_item(&mut self, child: &mut LayoutBox, position: Point2D<CssDecimal>, size: Size2D<CssDecimal>) {
+        let width = Some(size.width).filter(|_| child.computed_style().width() == CssLength::Auto);
+        let height = Some(size.height).filter(|_| child.computed_style().height() == CssLength::Auto);
+
+        self.base.layout_child(child, position, width, height, Some(size.width));
+    }
+}
+
+/// The explicit tracks of the grid.
+///
+/// TODO: support `subgrid`, which uses the tracks of the parent grid. It is
+///       treated like `none` for now, so the items are placed in implicit
+///       tracks.
+fn explicit_tracks(list: &CssGridTrackList) -> Vec<CssGridTrackSize> {
+    list.tracks().to_vec()
+}
+
+/// A track sizing function, with its lengths resolved.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum TrackBreadth {
+    Fixed(CssDecimal),
+    Auto,
+    MinContent,
+    MaxContent,
+    Flex(CssDecimal),
+}
+
+impl TrackBreadth {
+    fn resolve(breadth: CssGridTrackBreadth, basis: Option<CssDecimal>, lengths: LengthContext) -> Self {
+        let length = match breadth {
+            CssGridTrackBreadth::Auto => return Self::Auto,
+            CssGridTrackBreadth::Flex(factor) => return Self::Flex(factor),
+            CssGridTrackBreadth::MaxContent => return Self::MaxContent,
+            CssGridTrackBreadth::MinContent => return Self::MinContent,
+            CssGridTrackBreadth::Length(length) => length,
+        };
+
+        // Percentages against an indefinite size behave as `auto`.
+        lengths.resolve(length, basis).map_or(Self::Auto, Self::Fixed)
+    }
+
+    fn is_intrinsic(&self) -> bool {
+        matches!(self, Self::Auto | Self::MinContent | Self::MaxContent)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct GridTrack {
+    min: TrackBreadth,
+    max: TrackBreadth,
+    base: CssDecimal,
+    growth_limit: CssDecimal,
+}
+
+impl GridTrack {
+    fn new(size: CssGridTrackSize, basis: Option<CssDecimal>, lengths: LengthContext) -> Self {
+        let min = TrackBreadth::resolve(size.min_sizing_function(), basis, lengths);
+        let max = TrackBreadth::resolve(size.max_sizing_function(), basis, lengths);
+
+        let base = match min {
+            TrackBreadth::Fixed(value) => value,
+            _ => 0.0,
+        };
+
+        let growth_limit = match max {
+            TrackBreadth::Fixed(value) => value.max(base),
+            _ => CssDecimal::INFINITY,
+        };
+
+        Self { min, max, base, growth_limit }
+    }
+
+    fn flex_factor(&self) -> Option<CssDecimal> {
+        match self.max {
+            TrackBreadth::Flex(factor) => Some(factor),
+            _ => None,
+        }
+    }
+}
+
+/// Create the explicit tracks, followed by the implicit tracks, which are
+/// sized `auto` (the initial value of `grid-auto-rows` and
+/// `grid-auto-columns`).
+fn create_tracks(
+    template: &[CssGridTrackSize],
+    count: usize,
+    basis: Option<CssDecimal>,
+    lengths: LengthContext,
+) -> Vec<GridTrack> {
+    (0..count)
+        .map(|index| {
+            let size = template.get(index).copied().unwrap_or_default();
+            GridTrack::new(size, basis, lengths)
+        })
+        .collect()
+}
+
+/// The offsets of the grid lines, relative to the start of the grid.
+fn track_offsets(tracks: &[GridTrack]) -> Vec<CssDecimal> {
+    let mut offsets = Vec::with_capacity(tracks.len() + 1);
+    let mut offset = 0.0;
+    offsets.push(offset);
+
+    for track in tracks {
+        offset += track.base;
+        offsets.push(offset);
+    }
+
+    offsets
+}
+
+/// The lines an item occupies in a single axis.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct GridSpan {
+    start: usize,
+    span: usize,
+}
+
+impl GridSpan {
+    fn end(&self) -> usize {
+        self.start + self.span
+    }
+
+    fn tracks(&self) -> std::ops::Range<usize> {
+        self.start..self.end()
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct TrackSizingItem {
+    span: GridSpan,
+    min_content: CssDecimal,
+    max_content: CssDecimal,
+}
+
+/// Run a simplified version of the track sizing algorithm.
+///
+/// # References
+/// * [CSS Grid Layout Module Level 2 § 12. Grid Layout Algorithm](https://drafts.csswg.org/css-grid/#algo-track-sizing)
+fn size_tracks(tracks: &mut [GridTrack], items: &[TrackSizingItem], available: Option<CssDecimal>) {
+    resolve_intrinsic_track_sizes(tracks, items);
+
+    let has_flexible_tracks = tracks.iter().any(|track| track.flex_factor().is_some());
+
+    // Maximize the tracks.
+    match available {
+        Some(available) => {
+            let free_space = available - tracks.iter().map(|track| track.base).sum::<CssDecimal>();
+            distribute_up_to_growth_limits(tracks, free_space);
+        }
+        None => {
+            for track in tracks.iter_mut().filter(|track| track.flex_factor().is_none()) {
+                track.base = track.growth_limit;
+            }
+        }
+    }
+
+    if has_flexible_tracks {
+        expand_flexible_tracks(tracks, items, available);
+    }
+
+    // Stretch the `auto` tracks to fill the remaining space.
+    if let Some(available) = available {
+        let free_space = available - tracks.iter().map(|track| track.base).sum::<CssDecimal>();
+        let auto_tracks = tracks.iter().filter(|track| track.max == TrackBreadth::Auto).count();
+        if free_space > 0.0 && auto_tracks != 0 {
+            let share = free_space / auto_tracks as CssDecimal;
+            for track in tracks.iter_mut().filter(|track| track.max == TrackBreadth::Auto) {
+                track.base += share;
+            }
+        }
+    }
+}
+
+/// [§ 12.5. Resolve Intrinsic Track Sizes](https://drafts.csswg.org/css-grid/#algo-content)
+fn resolve_intrinsic_track_sizes(tracks: &mut [GridTrack], items: &[TrackSizingItem]) {
+    let mut spanning_items = Vec::new();
+
+    for item in items {
+        if item.span.span != 1 {
+            spanning_items.push(item);
+            continue;
+        }
+
+        let track = &mut tracks[item.span.start];
+        match track.min {
+            TrackBreadth::Auto | TrackBreadth::MinContent => track.base = track.base.max(item.min_content),
+            TrackBreadth::MaxContent => track.base = track.base.max(item.max_content),
+            TrackBreadth::Fixed(..) | TrackBreadth::Flex(..) => (),
+        }
+
+        let contribution = match track.max {
+            TrackBreadth::MinContent => item.min_content,
+            TrackBreadth::Auto | TrackBreadth::MaxContent => item.max_content,
+            TrackBreadth::Fixed(..) | TrackBreadth::Flex(..) => continue,
+        };
+
+        track.growth_limit = if track.growth_limit.is_infinite() {
+            contribution
+        } else {
+            track.growth_limit.max(contribution)
+        };
+    }
+
+    // Items spanning multiple tracks distribute the space they need over
+    // the intrinsically sized tracks they span, in order of their span.
+    spanning_items.sort_by_key(|item| item.span.span);
+    for item in spanning_items {
+        let range = item.span.tracks();
+
+        let intrinsic_min: Vec<usize> = range.clone().filter(|index| tracks[*index].min.is_intrinsic()).collect();
+        let spanned_base: CssDecimal = tracks[range.clone()].iter().map(|track| track.base).sum();
+        let extra = item.min_content - spanned_base;
+        if extra > 0.0 && !intrinsic_min.is_empty() {
+            let share = extra / intrinsic_min.len() as CssDecimal;
+            for index in &intrinsic_min {
+                tracks[*index].base += share;
+            }
+        }
+
+        let intrinsic_max: Vec<usize> = range.clone().filter(|index| tracks[*index].max.is_intrinsic()).collect();
+        let spanned_limit: CssDecimal = tracks[range]
+            .iter()
+            .map(|track| if track.growth_limit.is_infinite() { track.base } else { track.growth_limit })
+            .sum();
+        let extra = item.max_content - spanned_limit;
+        if extra > 0.0 && !intrinsic_max.is_empty() {
+            let share = extra / intrinsic_max.len() as CssDecimal;
+            for index in intrinsic_max {
+                let track = &mut tracks[index];
+                let limit = if track.growth_limit.is_infinite() { track.base } else { track.growth_limit };
+                track.growth_limit = limit + share;
+            }
+        }
+    }
+
+    for track in tracks.iter_mut() {
+        if track.growth_limit.is_infinite() && track.flex_factor().is_none() {
+            track.growth_limit = track.base;
+        }
+
+        track.growth_limit = track.growth_limit.max(track.base);
+    }
+}
+
+/// [§ 12.6. Maximize Tracks](https://drafts.csswg.org/css-grid/#algo-grow-tracks)
+fn distribute_up_to_growth_limits(tracks: &mut [GridTrack], mut free_space: CssDecimal) {
+    while free_space > CssDecimal::EPSILON {
+        let growable: Vec<usize> = (0..tracks.len())
+            .filter(|index| {
+                let track = &tracks[*index];
+                track.flex_factor().is_none() && track.base < track.growth_limit
+            })
+            .collect();
+
+        if growable.is_empty() {
+            break;
+        }
+
+        let share = free_space / growable.len() as CssDecimal;
+        for index in growable {
+            let track = &mut tracks[index];
+            let growth = share.min(track.growth_limit - track.base);
+            track.base += growth;
+            free_space -= growth;
+        }
+    }
+}
+
+/// [§ 12.7. Expand Flexible Tracks](https://drafts.csswg.org/css-grid/#algo-flex-tracks)
+fn expand_flexible_tracks(tracks: &mut [GridTrack], items: &[TrackSizingItem], available: Option<CssDecimal>) {
+    let flex_fraction = match available {
+        Some(available) => find_size_of_fr(tracks, available),
+
+        // With an indefinite size, the flexible tracks are sized to fit
+        // their contents, whilst keeping the ratio of their flex factors.
+        None => {
+            let track_fraction = tracks.iter()
+                .filter_map(|track| track.flex_factor().map(|factor| {
+                    if factor > 1.0 { track.base / factor } else { track.base }
+                }))
+                .fold(0.0, CssDecimal::max);
+
+            items.iter()
+                .filter_map(|item| {
+                    let factors: CssDecimal = tracks[item.span.tracks()]
+                        .iter()
+                        .filter_map(GridTrack::flex_factor)
+                        .sum();
+
+                    if factors == 0.0 {
+                        return None;
+                    }
+
+                    let inflexible: CssDecimal = tracks[item.span.tracks()]
+                        .iter()
+                        .filter(|track| track.flex_factor().is_none())
+                        .map(|track| track.base)
+                        .sum();
+
+                    Some((item.max_content - inflexible).max(0.0) / factors.max(1.0))
+                })
+                .fold(track_fraction, CssDecimal::max)
+        }
+    };
+
+    for track in tracks.iter_mut() {
+        if let Some(factor) = track.flex_factor() {
+            track.base = track.base.max(flex_fraction * factor);
+        }
+    }
+}
+
+/// [§ 12.7.1. Find the Size of an `fr`](https://drafts.csswg.org/css-grid/#algo-find-fr-size)
+fn find_size_of_fr(tracks: &[GridTrack], available: CssDecimal) -> CssDecimal {
+    let mut inflexible: Vec<bool> = tracks.iter().map(|track| track.flex_factor().is_none()).collect();
+
+    loop {
+        let leftover_space = available - tracks.iter()
+            .zip(&inflexible)
+            .filter(|(_, inflexible)| **inflexible)
+            .map(|(track, _)| track.base)
+            .sum::<CssDecimal>();
+
+        let flex_factor_sum: CssDecimal = tracks.iter()
+            .zip(&inflexible)
+            .filter(|(_, inflexible)| !**inflexible)
+            .filter_map(|(track, _)| track.flex_factor())
+            .sum();
+
+        let hypothetical_fr_size = leftover_space.max(0.0) / flex_factor_sum.max(1.0);
+
+        // Tracks whose base size is larger than their share are treated as
+        // inflexible, after which the size is determined again.
+        let mut changed = false;
+        for (track, inflexible) in tracks.iter().zip(inflexible.iter_mut()) {
+            if *inflexible {
+                continue;
+            }
+
+            let factor = track.flex_factor().unwrap_or_default();
+            if hypothetical_fr_size * factor < track.base {
+                *inflexible = true;
+                changed = true;
+            }
+        }
+
+        if !changed {
+            return hypothetical_fr_size;
+        }
+    }
+}
+
+/// The result of placing the items in the grid.
+#[derive(Debug, PartialEq)]
+struct GridPlacement {
+    rows: usize,
+    columns: usize,
+
+    /// The row and column span of each item.
+    items: Vec<(GridSpan, GridSpan)>,
+}
+
+/// The lines of an item in a single axis, before auto-placement.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct AxisPlacement {
+    start: Option<usize>,
+    span: usize,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Axis {
+    Row,
+    Column,
+}
+
+/// [§ 8.3. Line-based Placement](https://drafts.csswg.org/css-grid/#line-placement)
+fn resolve_axis_placement(
+    placement: &CssGridPlacement,
+    explicit_count: usize,
+    areas: &CssGridTemplateAreas,
+    axis: Axis,
+) -> AxisPlacement {
+    let line_index = |line: &CssGridLine, is_start: bool| -> Option<usize> {
+        match line {
+            CssGridLine::Line(line) if *line > 0 => Some(*line as usize - 1),
+
+            // Negative lines count from the end of the explicit grid.
+            // TODO: lines before the explicit grid are clamped to its start.
+            CssGridLine::Line(line) => Some((explicit_count as i64 + 1 + *line as i64).max(0) as usize),
+
+            CssGridLine::Area(name) => {
+                let area = areas.find_area(name)?;
+                Some(match (axis, is_start) {
+                    (Axis::Row, true) => area.row_start,
+                    (Axis::Row, false) => area.row_end,
+                    (Axis::Column, true) => area.column_start,
+                    (Axis::Column, false) => area.column_end,
+                })
+            }
+
+            CssGridLine::Auto | CssGridLine::Span(..) => None,
+        }
+    };
+
+    let span_of = |line: &CssGridLine| match line {
+        CssGridLine::Span(span) => Some(*span as usize),
+        _ => None,
+    };
+
+    let start = line_index(&placement.start, true);
+    let end = line_index(&placement.end, false);
+
+    match (start, end) {
+        (Some(start), Some(end)) if start == end => AxisPlacement { start: Some(start), span: 1 },
+        (Some(start), Some(end)) => AxisPlacement {
+            start: Some(start.min(end)),
+            span: start.abs_diff(end),
+        },
+        (Some(start), None) => AxisPlacement {
+            start: Some(start),
+            span: span_of(&placement.end).unwrap_or(1),
+        },
+        (None, Some(end)) => {
+            let span = span_of(&placement.start).unwrap_or(1);
+            AxisPlacement {
+                start: Some(end.saturating_sub(span)),
+                span: span.min(end).max(1),
+            }
+        }
+        (None, None) => AxisPlacement {
+            start: None,
+            span: span_of(&placement.start).or_else(|| span_of(&placement.end)).unwrap_or(1),
+        },
+    }
+}
+
+/// Keeps track of the cells of the grid that are occupied by items.
+struct OccupancyGrid {
+    cells: Vec<Vec<bool>>,
+    columns: usize,
+}
+
+impl OccupancyGrid {
+    fn is_free(&self, row: GridSpan, column: GridSpan) -> bool {
+        row.tracks().all(|row| {
+            let Some(cells) = self.cells.get(row) else { return true };
+            column.tracks().all(|column| !cells.get(column).copied().unwrap_or(false))
+        })
+    }
+
+    fn occupy(&mut self, row: GridSpan, column: GridSpan) {
+        self.columns = self.columns.max(column.end());
+        if self.cells.len() < row.end() {
+            self.cells.resize(row.end(), Vec::new());
+        }
+
+        for cells in &mut self.cells[row.tracks()] {
+            if cells.len() < column.end() {
+                cells.resize(column.end(), false);
+            }
+
+            cells[column.tracks()].fill(true);
+        }
+    }
+}
+
+/// Place the items in the grid, using the `grid-row` and `grid-column` of
+/// each item (in that order), and auto-place the remaining items in
+/// row-major order (i.e. `grid-auto-flow: row`).
+///
+/// # References
+/// * [CSS Grid Layout Module Level 2 § 8.5. Grid Item Placement Algorithm](https://drafts.csswg.org/css-grid/#auto-placement-algo)
+fn place_items(
+    items: &[(CssGridPlacement, CssGridPlacement)],
+    explicit_rows: usize,
+    explicit_columns: usize,
+    areas: &CssGridTemplateAreas,
+) -> GridPlacement {
+    let placements: Vec<(AxisPlacement, AxisPlacement)> = items.iter()
+        .map(|(row, column)| (
+            resolve_axis_placement(row, explicit_rows, areas, Axis::Row),
+            resolve_axis_placement(column, explicit_columns, areas, Axis::Column),
+        ))
+        .collect();
+
+    let columns = placements.iter()
+        .map(|(_, column)| match column.start {
+            Some(start) => start + column.span,
+            None => column.span,
+        })
+        .fold(explicit_columns, usize::max);
+
+    let mut grid = OccupancyGrid {
+        cells: Vec::new(),
+        columns,
+    };
+
+    let mut result: Vec<Option<(GridSpan, GridSpan)>> = vec![None; placements.len()];
+
+    // 1. Position anything that's not auto-positioned.
+    for (index, (row, column)) in placements.iter().enumerate() {
+        let (Some(row_start), Some(column_start)) = (row.start, column.start) else {
+            continue;
+        };
+
+        let row = GridSpan { start: row_start, span: row.span };
+        let column = GridSpan { start: column_start, span: column.span };
+        grid.occupy(row, column);
+        result[index] = Some((row, column));
+    }
+
+    // 2. Process the items locked to a given row.
+    let mut row_cursors = std::collections::HashMap::new();
+    for (index, (row, column)) in placements.iter().enumerate() {
+        let (Some(row_start), None) = (row.start, column.start) else {
+            continue;
+        };
+
+        let row = GridSpan { start: row_start, span: row.span };
+        let cursor: &mut usize = row_cursors.entry(row_start).or_default();
+
+        let mut column = GridSpan { start: *cursor, span: column.span };
+        while !grid.is_free(row, column) {
+            column.start += 1;
+        }
+
+        *cursor = column.end();
+        grid.occupy(row, column);
+        result[index] = Some((row, column));
+    }
+
+    // 4. Position the remaining grid items.
+    let mut cursor_row = 0;
+    let mut cursor_column = 0;
+    for (index, (row, column)) in placements.iter().enumerate() {
+        if result[index].is_some() {
+            continue;
+        }
+
+        let placed = match column.start {
+            Some(column_start) => {
+                if column_start < cursor_column {
+                    cursor_row += 1;
+                }
+
+                let column = GridSpan { start: column_start, span: column.span };
+                let mut row = GridSpan { start: cursor_row, span: row.span };
+                while !grid.is_free(row, column) {
+                    row.start += 1;
+                }
+
+                (row, column)
+            }
+
+            None => {
+                let column_span = column.span.min(grid.columns.max(1));
+                loop {
+                    if cursor_column + column_span > grid.columns.max(1) {
+                        cursor_row += 1;
+                        cursor_column = 0;
+                        continue;
+                    }
+
+                    let row = GridSpan { start: cursor_row, span: row.span };
+                    let column = GridSpan { start: cursor_column, span: column_span };
+                    if grid.is_free(row, column) {
+                        break (row, column);
+                    }
+
+                    cursor_column += 1;
+                }
+            }
+        };
+
+        cursor_row = placed.0.start;
+        cursor_column = placed.1.end();
+
+        grid.occupy(placed.0, placed.1);
+        result[index] = Some(placed);
+    }
+
+    GridPlacement {
+        rows: grid.cells.len().max(explicit_rows),
+        columns: grid.columns,
+        items: result.into_iter().map(Option::unwrap).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use retina_common::StrTendril;
+
+    fn placement(start: CssGridLine, end: CssGridLine) -> CssGridPlacement {
+        CssGridPlacement { start, end }
+    }
+
+    fn auto() -> CssGridPlacement {
+        CssGridPlacement::default()
+    }
+
+    fn span(start: usize, span: usize) -> GridSpan {
+        GridSpan { start, span }
+    }
+
+    fn track(size: CssGridTrackSize) -> GridTrack {
+        GridTrack::new(size, None, LengthContext { font_size: 16.0, root_font_size: 16.0, viewport_width: 800.0, viewport_height: 600.0 })
+    }
+
+    #[test]
+    fn auto_placement_wraps_rows() {
+        let items = vec![(auto(), auto()); 5];
+        let grid = place_items(&items, 0, 2, &CssGridTemplateAreas::None);
+
+        assert_eq!(grid.rows, 3);
+        assert_eq!(grid.columns, 2);
+        assert_eq!(grid.items, vec![
+            (span(0, 1), span(0, 1)),
+            (span(0, 1), span(1, 1)),
+            (span(1, 1), span(0, 1)),
+            (span(1, 1), span(1, 1)),
+            (span(2, 1), span(0, 1)),
+        ]);
+    }
+
+    #[test]
+    fn auto_placement_skips_explicitly_placed_items() {
+        let items = vec![
+            (auto(), auto()),
+            (placement(CssGridLine::Line(1), CssGridLine::Auto), placement(CssGridLine::Line(2), CssGridLine::Span(2))),
+            (auto(), auto()),
+        ];
+        let grid = place_items(&items, 0, 3, &CssGridTemplateAreas::None);
+
+        assert_eq!(grid.items, vec![
+            (span(0, 1), span(0, 1)),
+            (span(0, 1), span(1, 2)),
+            (span(1, 1), span(0, 1)),
+        ]);
+    }
+
+    #[test]
+    fn placement_by_area_name() {
+        let areas = CssGridTemplateAreas::Areas(vec![
+            vec![Some(StrTendril::from("head")), Some(StrTendril::from("head"))],
+            vec![None, Some(StrTendril::from("main"))],
+        ]);
+
+        let main = placement(CssGridLine::Area("main".into()), CssGridLine::Area("main".into()));
+        let head = placement(CssGridLine::Area("head".into()), CssGridLine::Area("head".into()));
+
+        let grid = place_items(&[(main.clone(), main), (head.clone(), head)], 2, 2, &areas);
+        assert_eq!(grid.items, vec![
+            (span(1, 1), span(1, 1)),
+            (span(0, 1), span(0, 2)),
+        ]);
+    }
+
+    #[test]
+    fn negative_lines_count_from_the_end() {
+        let column = placement(CssGridLine::Line(1), CssGridLine::Line(-1));
+        let grid = place_items(&[(auto(), column)], 0, 3, &CssGridTemplateAreas::None);
+        assert_eq!(grid.items, vec![(span(0, 1), span(0, 3))]);
+    }
+
+    #[test]
+    fn flexible_tracks_share_the_free_space() {
+        let mut tracks = vec![
+            track(CssGridTrackSize::Breadth(CssGridTrackBreadth::Length(CssLength::Pixels(100.0)))),
+            track(CssGridTrackSize::Breadth(CssGridTrackBreadth::Flex(1.0))),
+            track(CssGridTrackSize::Breadth(CssGridTrackBreadth::Flex(3.0))),
+        ];
+
+        size_tracks(&mut tracks, &[], Some(500.0));
+
+        let sizes: Vec<_> = tracks.iter().map(|track| track.base).collect();
+        assert_eq!(sizes, vec![100.0, 100.0, 300.0]);
+    }
+
+    #[test]
+    fn minmax_grows_up_to_its_maximum() {
+        let mut tracks = vec![
+            track(CssGridTrackSize::MinMax {
+                min: CssGridTrackBreadth::Length(CssLength::Pixels(50.0)),
+                max: CssGridTrackBreadth::Length(CssLength::Pixels(150.0)),
+            }),
+            track(CssGridTrackSize::Breadth(CssGridTrackBreadth::Auto)),
+        ];
+
+        let items = [
+            TrackSizingItem { span: span(1, 1), min_content: 20.0, max_content: 80.0 },
+        ];
+
+        size_tracks(&mut tracks, &items, Some(400.0));
+
+        let sizes: Vec<_> = tracks.iter().map(|track| track.base).collect();
+        assert_eq!(sizes, vec![150.0, 250.0]);
+    }
+}
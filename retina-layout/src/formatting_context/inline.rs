@@ -35,11 +35,12 @@ impl InlineFormattingContextState {
 
 impl<'bx> InlineFormattingContext<'bx> {
     pub fn perform(layout_box: &'bx mut LayoutBox, parent: Option<&FormattingContext>) {
-        let content_position_origin = layout_box.dimensions().content_position;
+        let base = FormattingContext::new(parent, layout_box);
+        let state = InlineFormattingContextState::new(base.scroll_origin);
 
         let mut instance = Self {
-            base: FormattingContext::new(parent, layout_box),
-            state: InlineFormattingContextState::new(content_position_origin),
+            base,
+            state,
         };
 
         instance.perform_inner()
@@ -98,7 +99,13 @@ impl<'bx> InlineFormattingContext<'bx> {
             self.state.content_position_origin.y,
         ));
 
-        child.run_layout(Some(&mut self.base), Some(&mut self.state));
+        // Atomic inlines are laid out by their own formatting context, and
+        // their margin box is placed on the line as a whole.
+        if child.formatting_context.is_atomic_inline() {
+            child.run_layout(Some(&mut self.base), None);
+        } else {
+            child.run_layout(Some(&mut self.base), Some(&mut self.state));
+        }
 
         let child_size = child.dimensions.size_margin_box();
 
@@ -3,12 +3,24 @@
 
 pub mod inline;
 pub mod block;
+pub mod fixed;
+pub mod flex;
+pub(crate) mod float;
+pub mod grid;
+pub mod table;
 
 pub use block::BlockFormattingContext;
+pub use fixed::FixedPositioning;
+pub use flex::FlexFormattingContext;
+pub use grid::GridFormattingContext;
 pub use inline::InlineFormattingContext;
-use retina_style::CssReferencePixels;
+pub use table::TableFormattingContext;
+use euclid::default::{Point2D, Size2D};
+use retina_style::{CssDecimal, CssLength, CssReferencePixels};
 
-use crate::LayoutBox;
+use crate::{LayoutBox, LayoutBoxKind};
+
+use self::float::FloatList;
 
 #[derive(Debug)]
 pub struct FormattingContext<'bx> {
@@ -24,6 +36,17 @@ pub struct FormattingContext<'bx> {
 
     pub(crate) max_width: Option<CssReferencePixels>,
     pub(crate) max_height: Option<CssReferencePixels>,
+
+    /// The origin the children of this formatting context are positioned
+    /// against, which is the content position of the container. For scroll
+    /// containers, the positions of the children are thereby relative to the
+    /// unscrolled content, and the `scroll_offset` of the container is
+    /// subtracted when painting.
+    pub(crate) scroll_origin: Point2D<CssDecimal>,
+
+    /// The floats placed in this formatting context so far, which the line
+    /// boxes of the text following them are shortened by.
+    pub(crate) floats: FloatList,
 }
 
 impl<'bx> FormattingContext<'bx> {
@@ -39,15 +62,136 @@ impl<'bx> FormattingContext<'bx> {
             .flatten()
             .map(|value| value - layout_box.dimensions().combined_vertical_edges());
 
+        let scroll_origin = layout_box.dimensions().content_position;
+        let floats = FloatList::new(scroll_origin.x, scroll_origin.x + layout_box.dimensions().width().value());
+
         Self {
             layout_box,
             whitespace_state: FormattingContextWhitespaceState::Initial,
             max_width,
             max_height,
+            scroll_origin,
+            floats,
+        }
+    }
+
+    /// Lay out the child at the origin of this formatting context, within the
+    /// available width, and return its margin box size. An available width of
+    /// zero gives the min-content size, and no available width the
+    /// max-content size.
+    pub(crate) fn measure_child(&mut self, child: &mut LayoutBox, available_width: Option<CssDecimal>) -> Size2D<CssDecimal> {
+        let width = available_width.filter(|_| child.computed_style().width() == CssLength::Auto);
+        self.layout_child(child, self.scroll_origin, width, None, available_width);
+        child.dimensions.size_margin_box()
+    }
+
+    /// Lay out the child with its margin box at the given position. The
+    /// margin box is given the `width` and `height` when specified, e.g. when
+    /// it is stretched or flexed, and otherwise keeps the size the layout
+    /// gave it.
+    pub(crate) fn layout_child(
+        &mut self,
+        child: &mut LayoutBox,
+        position: Point2D<CssDecimal>,
+        width: Option<CssDecimal>,
+        height: Option<CssDecimal>,
+        available_width: Option<CssDecimal>,
+    ) {
+        let is_anonymous = child.kind == LayoutBoxKind::Anonymous;
+
+        child.dimensions = child.actual_value_map.dimensions;
+        child.dimensions.set_margin_position(position);
+
+        if let Some(width) = width.filter(|_| !is_anonymous) {
+            child.dimensions.width = CssReferencePixels::new(width) - child.dimensions.combined_horizontal_edges();
+            child.dimensions.width.ensure_abs();
+        }
+
+        if let Some(height) = height.filter(|_| !is_anonymous) {
+            child.dimensions.height = CssReferencePixels::new(height) - child.dimensions.combined_vertical_edges();
+            child.dimensions.height.ensure_abs();
+        }
+
+        let size = Size2D::new(child.dimensions.width, child.dimensions.height);
+
+        let previous_max_width = std::mem::replace(
+            &mut self.max_width,
+            available_width.map(CssReferencePixels::new),
+        );
+
+        child.run_layout(Some(self), None);
+
+        self.max_width = previous_max_width;
+
+        // The layout of `auto` sized boxes shrinks them to their contents,
+        // which mustn't undo the given size.
+        if !is_anonymous {
+            if width.is_some() {
+                child.dimensions.width = size.width;
+            }
+
+            if height.is_some() {
+                child.dimensions.height = size.height;
+            }
         }
     }
 }
 
+/// The font sizes and viewport size needed to resolve font-relative and
+/// viewport-relative lengths of the children of a box.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct LengthContext {
+    pub(crate) font_size: CssDecimal,
+    pub(crate) root_font_size: CssDecimal,
+    pub(crate) viewport_width: CssDecimal,
+    pub(crate) viewport_height: CssDecimal,
+}
+
+impl LengthContext {
+    pub(crate) fn new(layout_box: &LayoutBox) -> Self {
+        let actual_values = layout_box.actual_values();
+        Self {
+            font_size: layout_box.font_size().value(),
+            root_font_size: actual_values.root_font_size.value(),
+            viewport_width: actual_values.viewport_width.value(),
+            viewport_height: actual_values.viewport_height.value(),
+        }
+    }
+
+    /// Resolve the length to pixels, with percentages relative to the
+    /// `basis`. This is `None` for `auto`, the intrinsic size keywords, and
+    /// for percentages of an indefinite size.
+    pub(crate) fn resolve(&self, length: CssLength, basis: Option<CssDecimal>) -> Option<CssDecimal> {
+        match length {
+            CssLength::Auto | CssLength::MaxContent | CssLength::MinContent => None,
+            CssLength::Calc(calc) => calc.terms().map(|term| self.resolve(term, basis)).sum(),
+            CssLength::FontSize(value) => Some(value * self.font_size),
+            CssLength::FontSizeOfRootElement(value) => Some(value * self.root_font_size),
+            CssLength::Percentage(value) => basis.map(|basis| value * basis),
+            CssLength::Pixels(value) => Some(value),
+            CssLength::UaDefaultViewportHeightPercentage(value) => Some(value / 100.0 * self.viewport_height),
+            CssLength::UaDefaultViewportMaxPercentage(value) => Some(value / 100.0 * self.viewport_width.max(self.viewport_height)),
+            CssLength::UaDefaultViewportMinPercentage(value) => Some(value / 100.0 * self.viewport_width.min(self.viewport_height)),
+            CssLength::UaDefaultViewportWidthPercentage(value) => Some(value / 100.0 * self.viewport_width),
+        }
+    }
+}
+
+/// Whether or not the box is a text run consisting of only collapsible
+/// whitespace, which doesn't generate a flex or grid item.
+pub(crate) fn is_collapsible_whitespace(layout_box: &LayoutBox) -> bool {
+    if layout_box.kind != LayoutBoxKind::Anonymous {
+        return false;
+    }
+
+    let Some(text) = layout_box.node.as_text() else {
+        return false;
+    };
+
+    layout_box.computed_style().white_space().collapses()
+        && text.data_as_str().chars().all(|c| c.is_ascii_whitespace())
+}
+
 /// This state determines the state of whitespace in the formatting context,
 /// which is necessary for following the [White Space Processing Rules][spec].
 ///
@@ -67,5 +211,47 @@ pub enum FormattingContextWhitespaceState {
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum FormattingContextKind {
     Block,
+
+    /// The box of a `display: flex` element, which establishes a flex
+    /// formatting context for its contents.
+    Flex,
+
+    /// The box of a `display: grid` element, which establishes a grid
+    /// formatting context for its contents.
+    Grid,
+
     Inline,
+
+    /// The box of a `display: inline-block` element, which establishes a
+    /// block formatting context for its contents, but participates in the
+    /// inline formatting context of its parent as a single atomic box.
+    InlineBlock,
+
+    /// The box of a `display: inline-flex` element, which establishes a flex
+    /// formatting context, but is an atomic inline like [`Self::InlineBlock`].
+    InlineFlex,
+
+    /// The box of a `display: inline-grid` element, which establishes a grid
+    /// formatting context, but is an atomic inline like [`Self::InlineBlock`].
+    InlineGrid,
+
+    /// The box of a `display: inline-table` element, which establishes a
+    /// table formatting context, but is an atomic inline like
+    /// [`Self::InlineBlock`].
+    InlineTable,
+
+    /// The box of a `display: table` element, which establishes a table
+    /// formatting context for its rows, cells and captions.
+    Table,
+}
+
+impl FormattingContextKind {
+    /// Whether or not the box is an [atomic inline][spec], meaning the inline
+    /// layout algorithm treats it as a single opaque box, much like a replaced
+    /// element.
+    ///
+    /// [spec]: https://drafts.csswg.org/css-display/#atomic-inline
+    pub fn is_atomic_inline(&self) -> bool {
+        matches!(self, Self::InlineBlock | Self::InlineFlex | Self::InlineGrid | Self::InlineTable)
+    }
 }
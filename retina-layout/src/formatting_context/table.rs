@@ -0,0 +1,969 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! The [table formatting context][spec].
+//!
+//! [spec]: https://drafts.csswg.org/css-tables/
+
+use std::ops::Range;
+
+use euclid::default::{Point2D, Vector2D};
+use retina_dom::HtmlElementKind;
+use retina_style::{
+    CssBorderCollapse,
+    CssCaptionSide,
+    CssDecimal,
+    CssDisplay,
+    CssDisplayInternal,
+    CssLength,
+    CssLineStyle,
+    CssReferencePixels,
+    CssTableLayout,
+};
+use retina_style_computation::BorderProperties;
+
+use crate::{LayoutBox, LayoutEdge};
+
+use super::{FormattingContext, FormattingContextKind, LengthContext};
+
+pub struct TableFormattingContext<'bx> {
+    base: FormattingContext<'bx>,
+}
+
+impl<'bx> TableFormattingContext<'bx> {
+    pub fn perform(layout_box: &'bx mut LayoutBox, parent: Option<&FormattingContext>) {
+        let mut instance = Self {
+            base: FormattingContext::new(parent, layout_box),
+        };
+
+        instance.perform_inner()
+    }
+
+    fn layout_box(&mut self) -> &mut LayoutBox {
+        self.base.layout_box
+    }
+
+    fn calculate_max_size(&mut self) {
+        if self.layout_box().computed_style().width() != CssLength::Auto {
+            self.base.max_width = Some(self.layout_box().dimensions().width());
+        }
+
+        if self.layout_box().computed_style().height() != CssLength::Auto {
+            self.base.max_height = Some(self.layout_box().dimensions().height());
+        }
+    }
+
+    fn perform_inner(&mut self) {
+        let mut children = std::mem::take(&mut self.layout_box().children);
+        self.calculate_max_size();
+
+        let style = self.layout_box().computed_style().clone();
+        let lengths = LengthContext::new(self.layout_box());
+
+        let structure = TableStructure::new(&mut children);
+        let (placements, column_count) = place_cells(&structure.cell_spans(&children), &structure.row_group_ends());
+
+        let is_collapsed = style.border_collapse() == CssBorderCollapse::Collapse;
+        let spacing = if is_collapsed {
+            Vector2D::zero()
+        } else {
+            let spacing = style.border_spacing();
+            Vector2D::new(
+                lengths.resolve(spacing.horizontal, None).unwrap_or_default(),
+                lengths.resolve(spacing.vertical, None).unwrap_or_default(),
+            )
+        };
+
+        // The content box grows by the width of the borders the table loses
+        // when collapsing them.
+        let mut available_width = match self.layout_box().formatting_context {
+            FormattingContextKind::InlineTable if style.width() == CssLength::Auto => {
+                self.base.max_width.map(|width| width.value())
+            }
+            _ => Some(self.layout_box().dimensions().width().value()),
+        };
+
+        if is_collapsed {
+            let grown = self.collapse_borders(&mut children, &structure, &placements, column_count);
+            available_width = available_width.map(|width| width + grown);
+        }
+
+        //
+        // Columns
+        //
+        let cells: Vec<(&TableCell, &CellPlacement)> = structure.cells.iter().zip(placements.iter().flatten()).collect();
+
+        let total_spacing = if column_count == 0 { 0.0 } else { (column_count + 1) as CssDecimal * spacing.x };
+        let is_fixed_layout = style.table_layout() == CssTableLayout::Fixed && style.width() != CssLength::Auto;
+
+        let (table_width, columns) = if is_fixed_layout {
+            let table_width = available_width.unwrap_or_default();
+            let available = (table_width - total_spacing).max(0.0);
+
+            let first_row: Vec<_> = cells.iter()
+                .filter(|(_, placement)| placement.row == 0)
+                .map(|(cell, placement)| {
+                    let cell = cell.layout_box(&children);
+                    let width = LengthContext::new(cell)
+                        .resolve(cell.computed_style().width(), Some(available))
+                        .map(|width| width + cell.actual_value_map.dimensions.combined_horizontal_edges().value());
+                    (placement.columns(), width)
+                })
+                .collect();
+
+            (table_width, fixed_column_widths(column_count, &first_row, available, spacing.x))
+        } else {
+            let items: Vec<_> = cells.iter()
+                .map(|(cell, placement)| {
+                    let cell = cell.layout_box_mut(&mut children);
+                    ColumnItem {
+                        columns: placement.columns(),
+                        min: self.base.measure_child(cell, Some(0.0)).width,
+                        max: self.base.measure_child(cell, None).width,
+                    }
+                })
+                .collect();
+
+            let widths = column_widths(column_count, &items, spacing.x);
+            let min_width = widths.iter().map(|width| width.min).sum::<CssDecimal>() + total_spacing;
+            let max_width = widths.iter().map(|width| width.max).sum::<CssDecimal>() + total_spacing;
+
+            let table_width = if style.width() == CssLength::Auto {
+                available_width.map_or(max_width, |available| max_width.min(available)).max(min_width)
+            } else {
+                available_width.unwrap_or_default().max(min_width)
+            };
+
+            (table_width, distribute_width(&widths, (table_width - total_spacing).max(0.0)))
+        };
+
+        let mut column_offsets = Vec::with_capacity(column_count + 1);
+        let mut offset = spacing.x;
+        for width in &columns {
+            column_offsets.push(offset);
+            offset += width + spacing.x;
+        }
+        column_offsets.push(offset);
+
+        let span_width = |columns: &Range<usize>| {
+            column_offsets[columns.end] - column_offsets[columns.start] - spacing.x
+        };
+
+        //
+        // Rows
+        //
+        let row_count = structure.rows.len();
+        let mut rows = vec![TableRowMetrics::default(); row_count];
+        for (row, metrics) in structure.rows.iter().zip(rows.iter_mut()) {
+            let row = row.path.layout_box(&children);
+            metrics.height = LengthContext::new(row)
+                .resolve(row.computed_style().height(), None)
+                .unwrap_or_default();
+        }
+
+        let mut cell_metrics = Vec::with_capacity(cells.len());
+        for (cell, placement) in &cells {
+            let width = span_width(&placement.columns());
+            let cell = cell.layout_box_mut(&mut children);
+            self.base.layout_child(cell, self.base.scroll_origin, Some(width), None, Some(width));
+
+            let height = cell.dimensions.size_margin_box().height;
+            let baseline = cell_baseline(cell);
+            cell_metrics.push((height, baseline));
+
+            let row = &mut rows[placement.row];
+            row.baseline = row.baseline.max(baseline);
+        }
+
+        // Cells spanning a single row are aligned on the baseline of the row,
+        // after which the rows are grown to fit the cells spanning multiple
+        // rows, in order of their span.
+        let mut row_items: Vec<_> = cells.iter()
+            .zip(&cell_metrics)
+            .map(|((_, placement), (height, baseline))| (placement.rows(), rows[placement.row].baseline - baseline + height))
+            .collect();
+        row_items.sort_by_key(|(rows, _)| rows.len());
+
+        for (span, height) in row_items {
+            let spanned = rows[span.clone()].iter().map(|row| row.height).sum::<CssDecimal>()
+                + (span.len() - 1) as CssDecimal * spacing.y;
+
+            let extra = height - spanned;
+            if extra > 0.0 {
+                let share = extra / span.len() as CssDecimal;
+                for row in &mut rows[span] {
+                    row.height += share;
+                }
+            }
+        }
+
+        //
+        // Captions
+        //
+        let caption_width = table_width;
+        let mut caption_heights = [0.0, 0.0];
+        for index in &structure.captions {
+            let caption = &mut children[*index];
+            let side = caption.computed_style().caption_side();
+            let height = self.base.measure_child(caption, Some(caption_width)).height;
+            caption_heights[side as usize] += height;
+        }
+
+        let grid_height = if row_count == 0 {
+            0.0
+        } else {
+            rows.iter().map(|row| row.height).sum::<CssDecimal>() + (row_count + 1) as CssDecimal * spacing.y
+        };
+
+        // A specified height larger than the contents is distributed over
+        // the rows.
+        if style.height() != CssLength::Auto && row_count != 0 {
+            let extra = self.layout_box().dimensions().height().value() - grid_height - caption_heights.iter().sum::<CssDecimal>();
+            if extra > 0.0 {
+                let share = extra / row_count as CssDecimal;
+                for row in &mut rows {
+                    row.height += share;
+                }
+            }
+        }
+
+        let origin = self.base.scroll_origin;
+        let mut y = 0.0;
+        y += self.layout_captions(&mut children, &structure.captions, CssCaptionSide::Top, origin.y + y, caption_width);
+
+        let grid_top = y;
+        if row_count != 0 {
+            y += spacing.y;
+        }
+
+        for row in &mut rows {
+            row.offset = y;
+            y += row.height + spacing.y;
+        }
+
+        let row_bottom = |span: &Range<usize>| rows[span.end - 1].offset + rows[span.end - 1].height;
+
+        //
+        // Position the cells, rows and row groups.
+        //
+        for ((cell, placement), (_, baseline)) in cells.iter().zip(&cell_metrics) {
+            let columns = placement.columns();
+            let span = placement.rows();
+
+            let position = Point2D::new(
+                origin.x + column_offsets[columns.start],
+                origin.y + rows[placement.row].offset,
+            );
+
+            let width = span_width(&columns);
+            let height = row_bottom(&span) - rows[placement.row].offset;
+
+            let cell = cell.layout_box_mut(&mut children);
+            self.base.layout_child(cell, position, Some(width), Some(height), Some(width));
+
+            let shift = rows[placement.row].baseline - baseline;
+            if shift > 0.0 {
+                for child in &mut cell.children {
+                    translate_vertically(child, shift);
+                }
+            }
+        }
+
+        let row_width = (table_width - 2.0 * spacing.x).max(0.0);
+        for (index, row) in structure.rows.iter().enumerate() {
+            let metrics = rows[index];
+            let position = Point2D::new(origin.x + spacing.x, origin.y + metrics.offset);
+            place_box(row.path.layout_box_mut(&mut children), position, row_width, metrics.height);
+        }
+
+        for group in &structure.row_groups {
+            let Some(index) = group.index else { continue };
+
+            let (top, height) = if group.rows.is_empty() {
+                (grid_top, 0.0)
+            } else {
+                (rows[group.rows.start].offset, row_bottom(&group.rows) - rows[group.rows.start].offset)
+            };
+
+            let position = Point2D::new(origin.x + spacing.x, origin.y + top);
+            place_box(&mut children[index], position, row_width, height);
+        }
+
+        y = y.max(grid_top + grid_height);
+        y += self.layout_captions(&mut children, &structure.captions, CssCaptionSide::Bottom, origin.y + y, caption_width);
+
+        if style.height() == CssLength::Auto {
+            self.layout_box().dimensions.height = CssReferencePixels::new(y);
+        }
+
+        self.layout_box().dimensions.width = CssReferencePixels::new(table_width);
+        self.layout_box().children = children;
+    }
+
+    /// Lay out the captions on the given `side` of the table, starting at
+    /// `y`, and return their combined height.
+    ///
+    /// TODO: the table wrapper box isn't generated, so the captions are
+    ///       placed inside the borders of the table.
+    fn layout_captions(
+        &mut self,
+        children: &mut [LayoutBox],
+        captions: &[usize],
+        side: CssCaptionSide,
+        y: CssDecimal,
+        width: CssDecimal,
+    ) -> CssDecimal {
+        let mut height = 0.0;
+        for index in captions {
+            let caption = &mut children[*index];
+            if caption.computed_style().caption_side() != side {
+                continue;
+            }
+
+            let position = Point2D::new(self.base.scroll_origin.x, y + height);
+            let auto_width = (caption.computed_style().width() == CssLength::Auto).then_some(width);
+            self.base.layout_child(caption, position, auto_width, None, Some(width));
+            height += caption.dimensions.size_margin_box().height;
+        }
+        height
+    }
+
+    /// Resolve the borders of the cells and the table in the [collapsing
+    /// border model][spec], where adjacent cells share a single border, which
+    /// is the widest of the borders of both. Each cell gets half of the
+    /// borders on its edges, and the table half of the borders on the edges
+    /// of the grid. Returns the width the content box of the table grows by.
+    ///
+    /// TODO: the style and color of the widest border should be used as well.
+    ///
+    /// [spec]: https://drafts.csswg.org/css2/#collapsing-borders
+    fn collapse_borders(
+        &mut self,
+        children: &mut [LayoutBox],
+        structure: &TableStructure,
+        placements: &[Vec<CellPlacement>],
+        column_count: usize,
+    ) -> CssDecimal {
+        let placements: Vec<CellPlacement> = placements.iter().flatten().copied().collect();
+        let widths: Vec<[CssDecimal; 4]> = structure.cells.iter()
+            .map(|cell| border_widths(cell.layout_box(children)))
+            .collect();
+
+        let table_widths = border_widths(self.layout_box());
+        let row_count = structure.rows.len();
+
+        // The index of the cell occupying each slot of the grid.
+        let mut slots = vec![vec![None; column_count]; row_count];
+        for (index, placement) in placements.iter().enumerate() {
+            for row in placement.rows() {
+                for column in placement.columns() {
+                    slots[row][column] = Some(index);
+                }
+            }
+        }
+
+        let mut table_edges = [0.0 as CssDecimal; 4];
+        for (index, placement) in placements.iter().enumerate() {
+            let mut edges = widths[index];
+
+            let neighbors = [
+                placement.columns().map(|column| placement.row.checked_sub(1).map(|row| (row, column))).collect::<Vec<_>>(),
+                placement.rows().map(|row| Some((row, placement.column + placement.column_span)).filter(|_| placement.column + placement.column_span < column_count)).collect(),
+                placement.columns().map(|column| Some((placement.row + placement.row_span, column)).filter(|_| placement.row + placement.row_span < row_count)).collect(),
+                placement.rows().map(|row| placement.column.checked_sub(1).map(|column| (row, column))).collect(),
+            ];
+
+            for (side, neighbors) in neighbors.iter().enumerate() {
+                let opposite = (side + 2) % 4;
+                for neighbor in neighbors {
+                    let width = match neighbor {
+                        Some((row, column)) => match slots[*row][*column] {
+                            Some(neighbor) => widths[neighbor][opposite],
+                            None => 0.0,
+                        },
+                        None => {
+                            table_edges[side] = table_edges[side].max(edges[side].max(table_widths[side]));
+                            table_widths[side]
+                        }
+                    };
+                    edges[side] = edges[side].max(width);
+                }
+            }
+
+            let cell = structure.cells[index].layout_box_mut(children);
+            cell.actual_value_map.dimensions.border = LayoutEdge {
+                top: CssReferencePixels::new(edges[0] / 2.0),
+                right: CssReferencePixels::new(edges[1] / 2.0),
+                bottom: CssReferencePixels::new(edges[2] / 2.0),
+                left: CssReferencePixels::new(edges[3] / 2.0),
+            };
+        }
+
+        let border = LayoutEdge {
+            top: CssReferencePixels::new(table_edges[0] / 2.0),
+            right: CssReferencePixels::new(table_edges[1] / 2.0),
+            bottom: CssReferencePixels::new(table_edges[2] / 2.0),
+            left: CssReferencePixels::new(table_edges[3] / 2.0),
+        };
+
+        let dimensions = &mut self.layout_box().dimensions;
+        let previous = dimensions.border;
+        dimensions.content_position.x += (border.left - previous.left).value();
+        dimensions.content_position.y += (border.top - previous.top).value();
+        dimensions.border = border;
+        self.base.scroll_origin = self.layout_box().dimensions.content_position;
+
+        (previous.left + previous.right - border.left - border.right).value()
+    }
+}
+
+/// The location of a row, row group or cell in the children of the table
+/// box.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct BoxPath {
+    /// The index of the row group, or [`None`] if the row is a child of the
+    /// table itself.
+    group: Option<usize>,
+    row: usize,
+}
+
+impl BoxPath {
+    fn layout_box<'a>(&self, children: &'a [LayoutBox]) -> &'a LayoutBox {
+        match self.group {
+            Some(group) => &children[group].children[self.row],
+            None => &children[self.row],
+        }
+    }
+
+    fn layout_box_mut<'a>(&self, children: &'a mut [LayoutBox]) -> &'a mut LayoutBox {
+        match self.group {
+            Some(group) => &mut children[group].children[self.row],
+            None => &mut children[self.row],
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct TableRow {
+    path: BoxPath,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct TableCell {
+    row: BoxPath,
+    index: usize,
+}
+
+impl TableCell {
+    fn layout_box<'a>(&self, children: &'a [LayoutBox]) -> &'a LayoutBox {
+        &self.row.layout_box(children).children[self.index]
+    }
+
+    fn layout_box_mut<'a>(&self, children: &'a mut [LayoutBox]) -> &'a mut LayoutBox {
+        &mut self.row.layout_box_mut(children).children[self.index]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct TableRowGroup {
+    /// The index of the row group box, or [`None`] for the rows that are
+    /// children of the table itself.
+    index: Option<usize>,
+    rows: Range<usize>,
+}
+
+/// The boxes of the table, in the order they are laid out.
+#[derive(Debug, Default)]
+struct TableStructure {
+    captions: Vec<usize>,
+    rows: Vec<TableRow>,
+    row_groups: Vec<TableRowGroup>,
+
+    /// The cells, in row-major order.
+    cells: Vec<TableCell>,
+}
+
+impl TableStructure {
+    /// Find the captions, rows and cells of the table. The header groups are
+    /// placed before the other rows and the footer groups after them.
+    ///
+    /// TODO: the [anonymous table objects][spec] aren't generated, so boxes
+    ///       that aren't in a proper table structure aren't displayed.
+    ///
+    /// [spec]: https://drafts.csswg.org/css-tables/#fixup-algorithm
+    fn new(children: &mut [LayoutBox]) -> Self {
+        let mut structure = Self::default();
+
+        let mut headers = Vec::new();
+        let mut bodies = Vec::new();
+        let mut footers = Vec::new();
+
+        for (index, child) in children.iter_mut().enumerate() {
+            match internal_display(child) {
+                Some(CssDisplayInternal::TableCaption) => structure.captions.push(index),
+                Some(CssDisplayInternal::TableHeaderGroup) => headers.push((Some(index), index)),
+                Some(CssDisplayInternal::TableFooterGroup) => footers.push((Some(index), index)),
+                Some(CssDisplayInternal::TableRowGroup) => bodies.push((Some(index), index)),
+                Some(CssDisplayInternal::TableRow) => bodies.push((None, index)),
+                _ => hide(child),
+            }
+        }
+
+        for (group, index) in headers.into_iter().chain(bodies).chain(footers) {
+            let Some(group) = group else {
+                // Consecutive rows that are children of the table form a
+                // single (anonymous) row group.
+                let row = structure.rows.len();
+                match structure.row_groups.last_mut() {
+                    Some(last) if last.index.is_none() && last.rows.end == row => last.rows.end += 1,
+                    _ => structure.row_groups.push(TableRowGroup { index: None, rows: row..row + 1 }),
+                }
+
+                structure.add_row(children, BoxPath { group: None, row: index });
+                continue;
+            };
+
+            let start = structure.rows.len();
+            for row in 0..children[group].children.len() {
+                let child = &mut children[group].children[row];
+                if internal_display(child) == Some(CssDisplayInternal::TableRow) {
+                    structure.add_row(children, BoxPath { group: Some(group), row });
+                } else {
+                    hide(child);
+                }
+            }
+
+            structure.row_groups.push(TableRowGroup {
+                index: Some(group),
+                rows: start..structure.rows.len(),
+            });
+        }
+
+        structure
+    }
+
+    fn add_row(&mut self, children: &mut [LayoutBox], path: BoxPath) {
+        self.rows.push(TableRow { path });
+
+        for (index, child) in path.layout_box_mut(children).children.iter_mut().enumerate() {
+            if internal_display(child) == Some(CssDisplayInternal::TableCell) {
+                self.cells.push(TableCell { row: path, index });
+            } else {
+                hide(child);
+            }
+        }
+    }
+
+    /// The row and column span of the cells of each row.
+    fn cell_spans(&self, children: &[LayoutBox]) -> Vec<Vec<(usize, usize)>> {
+        let mut spans = vec![Vec::new(); self.rows.len()];
+        let mut row = 0;
+        for cell in &self.cells {
+            while self.rows[row].path != cell.row {
+                row += 1;
+            }
+
+            let element = cell.layout_box(children).node.as_html_element_kind()
+                .and_then(HtmlElementKind::as_table_cell_element);
+
+            spans[row].push(match element {
+                Some(element) => (element.row_span(), element.column_span()),
+                None => (1, 1),
+            });
+        }
+        spans
+    }
+
+    /// The index of the row after the row group of each row.
+    fn row_group_ends(&self) -> Vec<usize> {
+        let mut ends = vec![0; self.rows.len()];
+        for group in &self.row_groups {
+            ends[group.rows.clone()].fill(group.rows.end);
+        }
+        ends
+    }
+}
+
+fn internal_display(layout_box: &LayoutBox) -> Option<CssDisplayInternal> {
+    match layout_box.computed_style().display() {
+        CssDisplay::Internal(internal) => Some(internal),
+        _ => None,
+    }
+}
+
+/// Don't display the box, e.g. because it is whitespace between the rows.
+fn hide(layout_box: &mut LayoutBox) {
+    layout_box.dimensions = layout_box.actual_value_map.dimensions;
+    layout_box.dimensions.width = CssReferencePixels::new(0.0);
+    layout_box.dimensions.height = CssReferencePixels::new(0.0);
+    layout_box.line_box_fragments.clear();
+
+    for child in &mut layout_box.children {
+        hide(child);
+    }
+}
+
+/// Give the row or row group box the given margin box.
+fn place_box(layout_box: &mut LayoutBox, position: Point2D<CssDecimal>, width: CssDecimal, height: CssDecimal) {
+    layout_box.dimensions = layout_box.actual_value_map.dimensions;
+    layout_box.dimensions.set_margin_position(position);
+    layout_box.dimensions.set_margin_size(CssReferencePixels::new(width), CssReferencePixels::new(height));
+    layout_box.dimensions.width.ensure_abs();
+    layout_box.dimensions.height.ensure_abs();
+}
+
+/// Move the box and its descendants down by `offset`.
+fn translate_vertically(layout_box: &mut LayoutBox, offset: CssDecimal) {
+    layout_box.dimensions.content_position.y += offset;
+
+    for fragment in &mut layout_box.line_box_fragments {
+        fragment.position.y += offset;
+    }
+
+    for child in &mut layout_box.children {
+        translate_vertically(child, offset);
+    }
+}
+
+/// The widths of the borders of the box, in the order top, right, bottom and
+/// left.
+fn border_widths(layout_box: &LayoutBox) -> [CssDecimal; 4] {
+    let lengths = LengthContext::new(layout_box);
+    let style = layout_box.computed_style();
+
+    let resolve = |border: &BorderProperties| {
+        if border.style == CssLineStyle::None {
+            return 0.0;
+        }
+
+        lengths.resolve(border.width, None).unwrap_or_default()
+    };
+
+    [
+        resolve(&style.border_top),
+        resolve(&style.border_right),
+        resolve(&style.border_bottom),
+        resolve(&style.border_left),
+    ]
+}
+
+/// The baseline of the cell, relative to the top of its margin box, which is
+/// the baseline of its first line box, or the bottom of its content box if it
+/// doesn't have any.
+///
+/// TODO: the bottom of the first line box is used, since the ascent of the
+///       font isn't available here.
+fn cell_baseline(cell: &LayoutBox) -> CssDecimal {
+    fn first_line_bottom(layout_box: &LayoutBox) -> Option<CssDecimal> {
+        if let Some(fragment) = layout_box.line_box_fragments.first() {
+            return Some(fragment.position.y + fragment.size.height);
+        }
+
+        layout_box.children.iter().find_map(first_line_bottom)
+    }
+
+    let dimensions = cell.dimensions();
+    let baseline = first_line_bottom(cell)
+        .unwrap_or(dimensions.content_position.y + dimensions.height().value());
+
+    baseline - dimensions.position_margin_box().y
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+struct TableRowMetrics {
+    /// The offset of the top of the row, relative to the content box of the
+    /// table.
+    offset: CssDecimal,
+    height: CssDecimal,
+    baseline: CssDecimal,
+}
+
+/// The slots of the grid a cell occupies.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct CellPlacement {
+    row: usize,
+    column: usize,
+    row_span: usize,
+    column_span: usize,
+}
+
+impl CellPlacement {
+    fn rows(&self) -> Range<usize> {
+        self.row..self.row + self.row_span
+    }
+
+    fn columns(&self) -> Range<usize> {
+        self.column..self.column + self.column_span
+    }
+}
+
+/// Place the cells of each row in the first free slots of the grid, after
+/// the slots occupied by cells of previous rows spanning multiple rows.
+/// Returns the placement of the cells of each row, and the number of columns.
+///
+/// A row span of zero, and row spans extending beyond the row group, are
+/// clamped to the end of the row group, which is given by `group_ends` for
+/// each row.
+///
+/// # References
+/// * [HTML § 4.9.12 Processing model](https://html.spec.whatwg.org/multipage/tables.html#forming-a-table)
+fn place_cells(rows: &[Vec<(usize, usize)>], group_ends: &[usize]) -> (Vec<Vec<CellPlacement>>, usize) {
+    let mut occupied: Vec<Vec<bool>> = vec![Vec::new(); rows.len()];
+    let mut column_count = 0;
+
+    let placements = rows.iter()
+        .enumerate()
+        .map(|(row, cells)| {
+            let mut column = 0;
+            cells.iter()
+                .map(|(row_span, column_span)| {
+                    while occupied[row].get(column).copied().unwrap_or(false) {
+                        column += 1;
+                    }
+
+                    let group_end = group_ends[row];
+                    let row_span = match row_span {
+                        0 => group_end - row,
+                        span => (*span).min(group_end - row),
+                    };
+
+                    let placement = CellPlacement { row, column, row_span, column_span: *column_span };
+                    for slots in &mut occupied[placement.rows()] {
+                        if slots.len() < placement.columns().end {
+                            slots.resize(placement.columns().end, false);
+                        }
+                        slots[placement.columns()].fill(true);
+                    }
+
+                    column += column_span;
+                    column_count = column_count.max(column);
+                    placement
+                })
+                .collect()
+        })
+        .collect();
+
+    (placements, column_count)
+}
+
+/// The min-content and max-content widths of a cell.
+#[derive(Clone, Debug)]
+struct ColumnItem {
+    columns: Range<usize>,
+    min: CssDecimal,
+    max: CssDecimal,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+struct ColumnWidth {
+    min: CssDecimal,
+    max: CssDecimal,
+}
+
+/// Compute the min-content and max-content widths of the columns in the
+/// automatic table layout. Cells spanning multiple columns distribute the
+/// width they need in addition to the columns they span over them, in
+/// proportion to the max-content widths of those columns.
+///
+/// # References
+/// * [CSS 2.2 § 17.5.2.2 Automatic table layout](https://drafts.csswg.org/css2/#auto-table-layout)
+fn column_widths(column_count: usize, items: &[ColumnItem], spacing: CssDecimal) -> Vec<ColumnWidth> {
+    let mut columns = vec![ColumnWidth::default(); column_count];
+
+    let mut spanning_items = Vec::new();
+    for item in items {
+        if item.columns.len() != 1 {
+            spanning_items.push(item);
+            continue;
+        }
+
+        let column = &mut columns[item.columns.start];
+        column.min = column.min.max(item.min);
+        column.max = column.max.max(item.max);
+    }
+
+    spanning_items.sort_by_key(|item| item.columns.len());
+    for item in spanning_items {
+        let spanned = &mut columns[item.columns.clone()];
+        let inner_spacing = (spanned.len() - 1) as CssDecimal * spacing;
+
+        let max_sum: CssDecimal = spanned.iter().map(|column| column.max).sum();
+        let share = |column: &ColumnWidth, extra: CssDecimal| {
+            if max_sum > 0.0 {
+                extra * column.max / max_sum
+            } else {
+                extra / spanned.len() as CssDecimal
+            }
+        };
+
+        let extra_min = item.min - inner_spacing - spanned.iter().map(|column| column.min).sum::<CssDecimal>();
+        let extra_max = item.max - inner_spacing - max_sum;
+
+        let growth: Vec<_> = spanned.iter()
+            .map(|column| (share(column, extra_min.max(0.0)), share(column, extra_max.max(0.0))))
+            .collect();
+
+        for (column, (min, max)) in spanned.iter_mut().zip(growth) {
+            column.min += min;
+            column.max += max;
+        }
+    }
+
+    for column in &mut columns {
+        column.max = column.max.max(column.min);
+    }
+
+    columns
+}
+
+/// Distribute the `width` available for the columns over them. When it lies
+/// between the sum of the min-content and max-content widths, the columns
+/// are sized in between as well. Any width beyond the max-content widths is
+/// distributed in proportion to them.
+fn distribute_width(columns: &[ColumnWidth], width: CssDecimal) -> Vec<CssDecimal> {
+    let min_sum: CssDecimal = columns.iter().map(|column| column.min).sum();
+    let max_sum: CssDecimal = columns.iter().map(|column| column.max).sum();
+
+    if width <= min_sum {
+        return columns.iter().map(|column| column.min).collect();
+    }
+
+    if width <= max_sum {
+        let ratio = (width - min_sum) / (max_sum - min_sum);
+        return columns.iter()
+            .map(|column| column.min + (column.max - column.min) * ratio)
+            .collect();
+    }
+
+    let extra = width - max_sum;
+    columns.iter()
+        .map(|column| {
+            if max_sum > 0.0 {
+                column.max + extra * column.max / max_sum
+            } else {
+                column.max + extra / columns.len() as CssDecimal
+            }
+        })
+        .collect()
+}
+
+/// Compute the widths of the columns in the fixed table layout, which only
+/// uses the widths of the cells of the first row. The columns without a
+/// width share the remaining `width`.
+///
+/// # References
+/// * [CSS 2.2 § 17.5.2.1 Fixed table layout](https://drafts.csswg.org/css2/#fixed-table-layout)
+fn fixed_column_widths(
+    column_count: usize,
+    first_row: &[(Range<usize>, Option<CssDecimal>)],
+    width: CssDecimal,
+    spacing: CssDecimal,
+) -> Vec<CssDecimal> {
+    let mut columns = vec![None; column_count];
+    for (span, cell_width) in first_row {
+        let Some(cell_width) = cell_width else { continue };
+
+        let inner_spacing = (span.len() - 1) as CssDecimal * spacing;
+        let share = ((cell_width - inner_spacing) / span.len() as CssDecimal).max(0.0);
+        columns[span.clone()].fill(Some(share));
+    }
+
+    let assigned: CssDecimal = columns.iter().flatten().sum();
+    let remaining = (width - assigned).max(0.0);
+
+    let auto_columns = columns.iter().filter(|column| column.is_none()).count();
+    if auto_columns != 0 {
+        let share = remaining / auto_columns as CssDecimal;
+        return columns.iter().map(|column| column.unwrap_or(share)).collect();
+    }
+
+    // The columns are widened when the table is wider than them.
+    let share = if column_count == 0 { 0.0 } else { remaining / column_count as CssDecimal };
+    columns.iter().map(|column| column.unwrap_or_default() + share).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placement(row: usize, column: usize, row_span: usize, column_span: usize) -> CellPlacement {
+        CellPlacement { row, column, row_span, column_span }
+    }
+
+    fn item(columns: Range<usize>, min: CssDecimal, max: CssDecimal) -> ColumnItem {
+        ColumnItem { columns, min, max }
+    }
+
+    #[test]
+    fn place_cells_skips_slots_of_row_spans() {
+        let rows = vec![
+            vec![(2, 1), (1, 2)],
+            vec![(1, 1), (1, 1)],
+        ];
+
+        let (placements, columns) = place_cells(&rows, &[2, 2]);
+
+        assert_eq!(columns, 3);
+        assert_eq!(placements, vec![
+            vec![placement(0, 0, 2, 1), placement(0, 1, 1, 2)],
+            vec![placement(1, 1, 1, 1), placement(1, 2, 1, 1)],
+        ]);
+    }
+
+    #[test]
+    fn place_cells_clamps_row_spans_to_row_group() {
+        let rows = vec![
+            vec![(0, 1), (5, 1)],
+            vec![(1, 1)],
+            vec![(1, 1)],
+        ];
+
+        let (placements, columns) = place_cells(&rows, &[2, 2, 3]);
+
+        assert_eq!(columns, 3);
+        assert_eq!(placements, vec![
+            vec![placement(0, 0, 2, 1), placement(0, 1, 2, 1)],
+            vec![placement(1, 2, 1, 1)],
+            vec![placement(2, 0, 1, 1)],
+        ]);
+    }
+
+    #[test]
+    fn column_widths_of_spanning_cells() {
+        let items = [
+            item(0..1, 10.0, 30.0),
+            item(1..2, 10.0, 20.0),
+            item(0..2, 32.0, 102.0),
+        ];
+
+        let columns = column_widths(2, &items, 2.0);
+        assert_eq!(columns, [
+            ColumnWidth { min: 16.0, max: 60.0 },
+            ColumnWidth { min: 14.0, max: 40.0 },
+        ]);
+    }
+
+    #[test]
+    fn distribute_width_between_min_and_max() {
+        let columns = [
+            ColumnWidth { min: 10.0, max: 30.0 },
+            ColumnWidth { min: 20.0, max: 20.0 },
+        ];
+
+        assert_eq!(distribute_width(&columns, 20.0), [10.0, 20.0]);
+        assert_eq!(distribute_width(&columns, 40.0), [20.0, 20.0]);
+        assert_eq!(distribute_width(&columns, 100.0), [60.0, 40.0]);
+    }
+
+    #[test]
+    fn fixed_layout_uses_first_row() {
+        let first_row = [
+            (0..1, Some(50.0)),
+            (1..3, None),
+            (3..5, Some(42.0)),
+        ];
+
+        let columns = fixed_column_widths(5, &first_row, 200.0, 2.0);
+        assert_eq!(columns, [50.0, 55.0, 55.0, 20.0, 20.0]);
+    }
+}
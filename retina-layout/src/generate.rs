@@ -5,8 +5,16 @@ use std::collections::HashSet;
 
 use euclid::default::Point2D;
 use log::warn;
-use retina_common::Color;
-use retina_dom::{Node, NodeKind, ImageData, Text};
+use retina_common::{Color, StrTendril};
+use retina_dom::{
+    HtmlElementKind,
+    HtmlUnknownElement,
+    ImageData,
+    Node,
+    NodeKind,
+    Text,
+    qual_name,
+};
 use retina_fetch::Url;
 
 use retina_gfx_font::{
@@ -25,10 +33,15 @@ use retina_gfx_font::{
 use retina_style::{
     Stylesheet,
     CssColor,
+    CssContent,
+    CssContentItem,
+    CssDecimal,
     CssDisplay,
     CssDisplayBox,
     CssDisplayInside,
+    CssDisplayInternal,
     CssDisplayOutside,
+    CssFloatValue,
     CssFontFamilyName,
     CssFontKerning,
     CssFontVariantEastAsian,
@@ -40,66 +53,111 @@ use retina_style::{
     CssImage,
     CssLength,
     CssLineStyle,
+    CssPosition,
+    CssPseudoElement,
     CssReferencePixels,
+    CssTextOverflow,
 };
 
 use retina_style_computation::{
     Cascade,
     BorderProperties,
+    DependencyMap,
     PropertyMap,
     StyleCollector,
 };
 
 use crate::{
     ActualValueMap,
+    actual_values::clamp_size,
     DomNode,
     formatting_context::FormattingContextKind,
     LayoutBox,
     LayoutBoxDimensions,
     LayoutBoxKind,
     LayoutEdge, replaced::ReplacedElementType,
+    SizeConstraint,
+    SizeConstraints,
+    stacking_context::stacking_context_order,
 };
 
-pub struct LayoutGenerator<'stylesheets, ImageLoader>
-        where ImageLoader: FnMut(Url) -> ImageData {
-    stylesheets: &'stylesheets [Stylesheet],
+/// The initial value of the `font-size` property, which is `medium`.
+const INITIAL_FONT_SIZE: CssDecimal = 16.0;
+
+pub struct LayoutGenerator<'stylesheets, ImageLoader, StyleAdjuster>
+        where ImageLoader: FnMut(Url) -> ImageData,
+              StyleAdjuster: FnMut(&DomNode, &mut PropertyMap) {
+    style_collector: StyleCollector<'stylesheets>,
+
+    /// The styles of the previous generation, which are reused for the nodes
+    /// that weren't invalidated since.
+    dependency_map: &'stylesheets mut DependencyMap,
+
+    /// Whether the ancestor of the node that is being generated has a
+    /// different style than in the previous generation, such that the
+    /// recorded styles of the node can't be reused, since it inherits from
+    /// that ancestor.
+    restyle_descendants: bool,
+
     viewport_width: CssReferencePixels,
     viewport_height: CssReferencePixels,
     font_provider: FontProvider,
     document_url: &'stylesheets Url,
     image_loader: ImageLoader,
+
+    /// Adjusts the computed style of an element after the cascade, which is
+    /// e.g. used to override the values of running transitions.
+    style_adjuster: StyleAdjuster,
+
     invalid_fonts: HashSet<FontDescriptor>,
+
+    /// The font size of the root element, used to resolve `rem` units.
+    root_font_size: CssReferencePixels,
+
+    /// The boxes with `position: fixed`, which are taken out of the normal
+    /// flow and stored on the initial containing block.
+    fixed_boxes: Vec<LayoutBox>,
 }
 
-impl<'stylesheets, ImageLoader> LayoutGenerator<'stylesheets, ImageLoader>
-        where ImageLoader: FnMut(Url) -> ImageData {
+impl<'stylesheets, ImageLoader, StyleAdjuster> LayoutGenerator<'stylesheets, ImageLoader, StyleAdjuster>
+        where ImageLoader: FnMut(Url) -> ImageData,
+              StyleAdjuster: FnMut(&DomNode, &mut PropertyMap) {
 
     pub fn generate(
         root: DomNode,
         stylesheets: &'stylesheets [Stylesheet],
+        dependency_map: &'stylesheets mut DependencyMap,
         viewport_width: CssReferencePixels,
         viewport_height: CssReferencePixels,
         font_provider: FontProvider,
         document_url: &'stylesheets Url,
         image_loader: ImageLoader,
+        style_adjuster: StyleAdjuster,
     ) -> LayoutBox {
         let mut instance = Self {
-            stylesheets,
+            style_collector: StyleCollector::new(stylesheets),
+            dependency_map,
+            restyle_descendants: false,
             viewport_width,
             viewport_height,
             font_provider,
             document_url,
             image_loader,
+            style_adjuster,
             invalid_fonts: Default::default(),
+            root_font_size: CssReferencePixels::new(INITIAL_FONT_SIZE),
+            fixed_boxes: Vec::new(),
         };
 
+        // This is the `<html>` element for HTML documents, but XML documents
+        // (e.g. SVG) can have any element as the document element.
         let html_element = Node::clone(
             &root.as_parent_node()
                 .expect("root DOM node not the Document")
                 .children()
                 .iter()
-                .find(|node| node.tag_name() == Some("html"))
-                .expect("DOM Document should have an HTMLHtmlElement")
+                .find(|node| node.is_element())
+                .expect("DOM Document should have a document element")
         );
 
         let mut initial_containing_block = instance.generate_initial_containing_block(root);
@@ -108,6 +166,7 @@ impl<'stylesheets, ImageLoader> LayoutGenerator<'stylesheets, ImageLoader>
             .expect("root node has no layout box generated");
 
         initial_containing_block.children.push(html_box);
+        initial_containing_block.fixed_boxes = std::mem::take(&mut instance.fixed_boxes);
 
         initial_containing_block.dimensions_mut().set_margin_size(
             viewport_width,
@@ -188,6 +247,19 @@ impl<'stylesheets, ImageLoader> LayoutGenerator<'stylesheets, ImageLoader>
         }
     }
 
+    /// Atomic inlines (e.g. `inline-block`) have their margins, borders and
+    /// padding resolved like block boxes, but their size is determined by
+    /// their contents when they're laid out.
+    fn calculate_dimensions_for_atomic_inline(
+        &self,
+        computed_style: &PropertyMap,
+        parent: &LayoutBox,
+        font_size: CssReferencePixels,
+    ) -> LayoutBoxDimensions {
+        // TODO use the shrink-to-fit width for `width: auto`
+        self.calculate_dimensions_for_block_flow(computed_style, parent, font_size)
+    }
+
     fn calculate_dimensions_for_inline_flow(
         &self,
         computed_style: &PropertyMap,
@@ -201,7 +273,7 @@ impl<'stylesheets, ImageLoader> LayoutGenerator<'stylesheets, ImageLoader>
     fn compute_actual_values(
         &self,
         parent: &LayoutBox,
-        computed_style: &PropertyMap
+        computed_style: &PropertyMap,
     ) -> ActualValueMap {
         let text_color = match computed_style.color() {
             CssColor::Color(color) => color,
@@ -215,7 +287,30 @@ impl<'stylesheets, ImageLoader> LayoutGenerator<'stylesheets, ImageLoader>
             },
             text_hinting_options: self.convert_text_hinting_options(computed_style),
             text_color,
+            root_font_size: self.root_font_size,
+            viewport_width: self.viewport_width,
+            viewport_height: self.viewport_height,
             dimensions: Default::default(),
+            size_constraints: Default::default(),
+            text_overflow: self.resolve_text_overflow(parent),
+            letter_spacing: 0.0,
+            word_spacing: 0.0,
+        }
+    }
+
+    /// The `text-overflow` property applies to the inline content of a block
+    /// container that clips its overflow, including the content of its
+    /// inline descendants.
+    fn resolve_text_overflow(&self, parent: &LayoutBox) -> CssTextOverflow {
+        if parent.clips_overflow() {
+            return parent.computed_style().text_overflow();
+        }
+
+        match parent.computed_style().display() {
+            CssDisplay::Normal { inside: CssDisplayInside::Flow, outside: CssDisplayOutside::Inline, .. } => {
+                parent.actual_value_map.text_overflow
+            }
+            _ => CssTextOverflow::Clip,
         }
     }
 
@@ -381,27 +476,86 @@ impl<'stylesheets, ImageLoader> LayoutGenerator<'stylesheets, ImageLoader>
     ) -> CssReferencePixels {
         _ = computed_style;
         match length_value {
-            CssLength::Auto => parent_value,
+            CssLength::Auto | CssLength::MaxContent | CssLength::MinContent => parent_value,
+            CssLength::Calc(calc) => calc.terms()
+                .map(|term| self.resolve_length(font_size, parent_value, term, computed_style))
+                .fold(CssReferencePixels::new(0.0), |sum, term| sum + term),
             CssLength::FontSize(percentage) => font_size * percentage,
-
-            // TODO this should use the size of the root element
-            CssLength::FontSizeOfRootElement(percentage) => font_size * percentage,
+            CssLength::FontSizeOfRootElement(percentage) => self.root_font_size * percentage,
 
             CssLength::Percentage(percentage) => parent_value * percentage,
             CssLength::Pixels(pixels) => CssReferencePixels::new(pixels),
-            CssLength::UaDefaultViewportHeightPercentage(percentage) => self.viewport_height * percentage,
-            CssLength::UaDefaultViewportWidthPercentage(percentage) => self.viewport_width * percentage,
+            CssLength::UaDefaultViewportHeightPercentage(percentage) => self.viewport_height * (percentage / 100.0),
+            CssLength::UaDefaultViewportMaxPercentage(percentage) => {
+                CssReferencePixels::new(self.viewport_width.value().max(self.viewport_height.value()) * (percentage / 100.0))
+            }
+            CssLength::UaDefaultViewportMinPercentage(percentage) => {
+                CssReferencePixels::new(self.viewport_width.value().min(self.viewport_height.value()) * (percentage / 100.0))
+            }
+            CssLength::UaDefaultViewportWidthPercentage(percentage) => self.viewport_width * (percentage / 100.0),
         }
     }
 
-    fn resolve_style(
+    fn resolve_size_constraint(
         &self,
+        font_size: CssReferencePixels,
+        parent_value: CssReferencePixels,
+        length_value: CssLength,
+        computed_style: &PropertyMap,
+    ) -> SizeConstraint {
+        match length_value {
+            CssLength::Auto => SizeConstraint::None,
+            CssLength::MaxContent => SizeConstraint::MaxContent,
+            CssLength::MinContent => SizeConstraint::MinContent,
+            length => SizeConstraint::Length(self.resolve_length(font_size, parent_value, length, computed_style)),
+        }
+    }
+
+    /// Resolve the `min-*` and `max-*` constraints of the box, of which the
+    /// percentages refer to the size of the containing block, and clamp the
+    /// initial size of the box to them. The `min-content` and `max-content`
+    /// constraints are only known after the contents are laid out.
+    ///
+    /// # References
+    /// * [CSS 2.2 § 10.4 Minimum and maximum widths](https://drafts.csswg.org/css2/#min-max-widths)
+    /// * [CSS 2.2 § 10.7 Minimum and maximum heights](https://drafts.csswg.org/css2/#min-max-heights)
+    fn resolve_size_constraints(&self, layout_box: &mut LayoutBox, parent: &LayoutBox) {
+        let font_size = layout_box.font_size;
+        let computed_style = &layout_box.computed_style;
+        let parent_width = parent.dimensions().width;
+        let parent_height = parent.dimensions().height;
+
+        let constraints = SizeConstraints {
+            min_width: self.resolve_size_constraint(font_size, parent_width, computed_style.min_width(), computed_style),
+            max_width: self.resolve_size_constraint(font_size, parent_width, computed_style.max_width(), computed_style),
+            min_height: self.resolve_size_constraint(font_size, parent_height, computed_style.min_height(), computed_style),
+            max_height: self.resolve_size_constraint(font_size, parent_height, computed_style.max_height(), computed_style),
+        };
+
+        let dimensions = &mut layout_box.actual_value_map.dimensions;
+        dimensions.width = clamp_size(dimensions.width, constraints.min_width.length(), constraints.max_width.length());
+        dimensions.height = clamp_size(dimensions.height, constraints.min_height.length(), constraints.max_height.length());
+
+        layout_box.actual_value_map.size_constraints = constraints;
+    }
+
+    /// Resolve the style of the `node` after the cascade, which is taken from
+    /// the [`DependencyMap`] if the node wasn't invalidated.
+    fn resolve_style(
+        &mut self,
         node: &DomNode,
         parent: Option<&LayoutBox>,
     ) -> PropertyMap {
-        StyleCollector::new(self.stylesheets)
-            .collect(node.as_ref())
-            .cascade(Some(node.as_ref()), parent.map(|parent| parent.computed_style()))
+        if !self.restyle_descendants {
+            if let Some(cascaded_style) = self.dependency_map.cascaded_style(node) {
+                return cascaded_style.clone();
+            }
+        }
+
+        let collected_styles = self.style_collector.collect(node.as_ref());
+        let cascaded_style = collected_styles.cascade(Some(node.as_ref()), parent.map(|parent| parent.computed_style()));
+        self.dependency_map.record(node, &collected_styles, cascaded_style.clone());
+        cascaded_style
     }
 
     fn generate_for(
@@ -409,10 +563,116 @@ impl<'stylesheets, ImageLoader> LayoutGenerator<'stylesheets, ImageLoader>
         node: DomNode,
         parent: &LayoutBox,
     ) -> Option<LayoutBox> {
-        let computed_style = self.resolve_style(&node, Some(parent));
+        let mut computed_style = self.resolve_style(&node, Some(parent));
+        (self.style_adjuster)(&node, &mut computed_style);
+
+        let restyle_descendants = self.restyle_descendants;
+        if self.dependency_map.record_computed_style(&node, &computed_style) {
+            self.restyle_descendants = true;
+        }
+
+        let layout_box = self.generate_box(node, computed_style, parent).map(|mut layout_box| {
+            if layout_box.node.is_element() {
+                if let Some(before) = self.generate_pseudo_element(&layout_box, CssPseudoElement::Before) {
+                    layout_box.children.insert(0, before);
+                }
+
+                if let Some(after) = self.generate_pseudo_element(&layout_box, CssPseudoElement::After) {
+                    layout_box.children.push(after);
+                }
+            }
+
+            layout_box
+        });
+
+        self.restyle_descendants = restyle_descendants;
+        layout_box
+    }
+
+    /// Generates the box of the `::before` or `::after` pseudo-element of the
+    /// `element`, which is a synthetic element containing a text node of the
+    /// [generated content][spec].
+    ///
+    /// [spec]: https://drafts.csswg.org/css-pseudo/#generated-content
+    fn generate_pseudo_element(
+        &mut self,
+        element: &LayoutBox,
+        pseudo_element: CssPseudoElement,
+    ) -> Option<LayoutBox> {
+        let cached_style = if self.restyle_descendants {
+            None
+        } else {
+            self.dependency_map.cascaded_pseudo_element_style(&element.node, pseudo_element).cloned()
+        };
+
+        let computed_style = cached_style.unwrap_or_else(|| {
+            // The element itself isn't passed, since its `style` attribute and
+            // presentational hints don't apply to the pseudo-element.
+            let computed_style = self.style_collector
+                .collect_for_pseudo_element(element.node.as_ref(), pseudo_element)
+                .cascade(None, Some(element.computed_style()));
+
+            self.dependency_map.record_pseudo_element(&element.node, pseudo_element, computed_style.clone());
+            computed_style
+        });
+
+        let CssContent::Items(items) = computed_style.content() else {
+            return None;
+        };
+
+        let text: String = items.iter()
+            .map(|item| match item {
+                CssContentItem::String(string) => string.as_ref(),
+
+                // TODO: `counter-reset` and `counter-increment` aren't
+                // supported, and a counter that doesn't exist is instantiated
+                // with the value zero.
+                CssContentItem::Counter(..) => "0",
+            })
+            .collect();
+
+        let name = format!("::{}", pseudo_element.as_ref());
+        let node = Node::new(NodeKind::HtmlElement(HtmlElementKind::Unknown(
+            HtmlUnknownElement::new(qual_name(&name))
+        )));
+        node.append_child(Node::new(NodeKind::Text(Text::new(StrTendril::from(text.as_str())))));
+
+        self.generate_box(node, computed_style, element)
+    }
+
+    fn generate_box(
+        &mut self,
+        node: DomNode,
+        mut computed_style: PropertyMap,
+        parent: &LayoutBox,
+    ) -> Option<LayoutBox> {
         let font = self.resolve_font(&node, parent, &computed_style);
+
+        // The `em` unit of the `font-size` property refers to the font size of
+        // the parent, and `rem` refers to the font size of the root element,
+        // or the initial font size when this is the root element.
         let font_size = self.resolve_length(parent.font_size, parent.font_size, computed_style.font_size(), &computed_style);
-        let actual_value_map = self.compute_actual_values(parent, &computed_style);
+
+        // Store the absolute value, such that children inheriting the
+        // font-size don't have to resolve it against other elements again.
+        computed_style.font_size = Some(CssLength::Pixels(font_size.value()));
+
+        if parent.kind == LayoutBoxKind::Root {
+            self.root_font_size = font_size;
+        }
+
+        // The spacing computes to an absolute length as well, which children
+        // inherit instead of resolving it against their own font size.
+        let letter_spacing = computed_style.letter_spacing()
+            .map(|spacing| self.resolve_length(font_size, font_size, spacing, &computed_style));
+        let word_spacing = computed_style.word_spacing()
+            .map(|spacing| self.resolve_length(font_size, font_size, spacing, &computed_style));
+        computed_style.letter_spacing = letter_spacing.map(|spacing| CssLength::Pixels(spacing.value()));
+        computed_style.word_spacing = word_spacing.map(|spacing| CssLength::Pixels(spacing.value()));
+
+        let mut actual_value_map = self.compute_actual_values(parent, &computed_style);
+        actual_value_map.letter_spacing = letter_spacing.map_or(0.0, |spacing| spacing.value());
+        actual_value_map.word_spacing = word_spacing.map_or(0.0, |spacing| spacing.value());
         let font_emoji = parent.font_emoji.clone();
 
         let mut layout_box = LayoutBox::new(
@@ -434,17 +694,99 @@ impl<'stylesheets, ImageLoader> LayoutGenerator<'stylesheets, ImageLoader>
             return Some(layout_box);
         }
 
+        // > Otherwise, if 'float' has a value other than 'none', the box is
+        // > floated and 'display' is set according to the table below.
+        //
+        // i.e. floats are blockified (CSS 2.2 § 9.7).
+        if layout_box.computed_style().float() != CssFloatValue::None {
+            if let CssDisplay::Normal { inside, outside: CssDisplayOutside::Inline, is_list_item } = layout_box.computed_style().display() {
+                layout_box.computed_style.display = Some(CssDisplay::Normal {
+                    inside,
+                    outside: CssDisplayOutside::Block,
+                    is_list_item,
+                });
+            }
+        }
+
         let mut layout_box = match layout_box.computed_style().display() {
             CssDisplay::Box(CssDisplayBox::None) => return None,
 
             // `display: inline`
-            CssDisplay::Normal { inside: CssDisplayInside::Flow, outside: CssDisplayOutside::Inline, .. } |
-            CssDisplay::Normal { inside: CssDisplayInside::FlowRoot, outside: CssDisplayOutside::Inline, .. } => {
+            CssDisplay::Normal { inside: CssDisplayInside::Flow, outside: CssDisplayOutside::Inline, .. } => {
                 layout_box.actual_value_map.dimensions = self.calculate_dimensions_for_inline_flow(layout_box.computed_style(), parent, font_size);
                 layout_box.formatting_context = FormattingContextKind::Inline;
                 layout_box
             }
 
+            // `display: inline-block`
+            CssDisplay::Normal { inside: CssDisplayInside::FlowRoot, outside: CssDisplayOutside::Inline, .. } => {
+                layout_box.actual_value_map.dimensions = self.calculate_dimensions_for_atomic_inline(layout_box.computed_style(), parent, font_size);
+                layout_box.formatting_context = FormattingContextKind::InlineBlock;
+                layout_box
+            }
+
+            // `display: inline-flex`
+            CssDisplay::Normal { inside: CssDisplayInside::Flex, outside: CssDisplayOutside::Inline, .. } => {
+                layout_box.actual_value_map.dimensions = self.calculate_dimensions_for_atomic_inline(layout_box.computed_style(), parent, font_size);
+                layout_box.formatting_context = FormattingContextKind::InlineFlex;
+                layout_box
+            }
+
+            // `display: inline-grid`
+            CssDisplay::Normal { inside: CssDisplayInside::Grid, outside: CssDisplayOutside::Inline, .. } => {
+                layout_box.actual_value_map.dimensions = self.calculate_dimensions_for_atomic_inline(layout_box.computed_style(), parent, font_size);
+                layout_box.formatting_context = FormattingContextKind::InlineGrid;
+                layout_box
+            }
+
+            // `display: flex`
+            CssDisplay::Normal { inside: CssDisplayInside::Flex, outside: CssDisplayOutside::Block, .. } => {
+                layout_box.actual_value_map.dimensions = self.calculate_dimensions_for_block_flow(layout_box.computed_style(), parent, font_size);
+                layout_box.formatting_context = FormattingContextKind::Flex;
+                layout_box
+            }
+
+            // `display: grid`
+            CssDisplay::Normal { inside: CssDisplayInside::Grid, outside: CssDisplayOutside::Block, .. } => {
+                layout_box.actual_value_map.dimensions = self.calculate_dimensions_for_block_flow(layout_box.computed_style(), parent, font_size);
+                layout_box.formatting_context = FormattingContextKind::Grid;
+                layout_box
+            }
+
+            // `display: inline-table`
+            CssDisplay::Normal { inside: CssDisplayInside::Table, outside: CssDisplayOutside::Inline, .. } => {
+                layout_box.actual_value_map.dimensions = self.calculate_dimensions_for_atomic_inline(layout_box.computed_style(), parent, font_size);
+                layout_box.formatting_context = FormattingContextKind::InlineTable;
+                layout_box
+            }
+
+            // `display: table`
+            CssDisplay::Normal { inside: CssDisplayInside::Table, outside: CssDisplayOutside::Block, .. } => {
+                layout_box.actual_value_map.dimensions = self.calculate_dimensions_for_block_flow(layout_box.computed_style(), parent, font_size);
+                layout_box.formatting_context = FormattingContextKind::Table;
+                layout_box
+            }
+
+            // TODO: the columns don't generate boxes, since their widths and
+            //       backgrounds aren't used yet.
+            CssDisplay::Internal(CssDisplayInternal::TableColumn | CssDisplayInternal::TableColumnGroup) => return None,
+
+            // The rows and row groups are positioned by the table formatting
+            // context, and the cells and captions establish a block
+            // formatting context for their contents.
+            CssDisplay::Internal(
+                CssDisplayInternal::TableRowGroup
+                    | CssDisplayInternal::TableHeaderGroup
+                    | CssDisplayInternal::TableFooterGroup
+                    | CssDisplayInternal::TableRow
+                    | CssDisplayInternal::TableCell
+                    | CssDisplayInternal::TableCaption
+            ) => {
+                layout_box.actual_value_map.dimensions = self.calculate_dimensions_for_block_flow(layout_box.computed_style(), parent, font_size);
+                layout_box.formatting_context = FormattingContextKind::Block;
+                layout_box
+            }
+
             CssDisplay::Normal { inside: CssDisplayInside::Flow, outside: CssDisplayOutside::Block, .. } |
             CssDisplay::Normal { inside: CssDisplayInside::FlowRoot, outside: CssDisplayOutside::Block, .. } => {
                 layout_box.actual_value_map.dimensions = self.calculate_dimensions_for_block_flow(layout_box.computed_style(), parent, font_size);
@@ -461,7 +803,10 @@ impl<'stylesheets, ImageLoader> LayoutGenerator<'stylesheets, ImageLoader>
             }
         };
 
+        self.resolve_size_constraints(&mut layout_box, parent);
+
         layout_box.dimensions = layout_box.actual_value_map.dimensions;
+        layout_box.stacking_context_order = stacking_context_order(layout_box.computed_style());
 
         if let Some(CssImage::Url(background_image_url)) = layout_box.computed_style.background_image.clone() {
             match Url::options().base_url(Some(&self.document_url)).parse(&background_image_url) {
@@ -476,9 +821,21 @@ impl<'stylesheets, ImageLoader> LayoutGenerator<'stylesheets, ImageLoader>
             }
         }
 
-        if let Some(node) = layout_box.node.as_parent_node() {
+        // The children of a `<canvas>` are its fallback content, which isn't
+        // rendered, since scripting is supported.
+        let is_canvas = matches!(layout_box.node.as_html_element_kind(), Some(HtmlElementKind::Canvas(..)));
+
+        if let Some(node) = layout_box.node.as_parent_node().filter(|_| !is_canvas) {
             for child in node.children().iter() {
-                if let Some(child) = self.generate_for(Node::clone(child), &layout_box) {
+                let Some(child) = self.generate_for(Node::clone(child), &layout_box) else {
+                    continue;
+                };
+
+                // TODO: the dimensions of fixed boxes are computed against
+                //       the parent instead of the viewport.
+                if child.computed_style().position() == CssPosition::Fixed {
+                    self.fixed_boxes.push(child);
+                } else {
                     layout_box.children.push(child);
                 }
             }
@@ -499,7 +856,7 @@ impl<'stylesheets, ImageLoader> LayoutGenerator<'stylesheets, ImageLoader>
             ..Default::default()
         };
 
-        let default_reference_pixels = CssReferencePixels::new(16.0);
+        let default_reference_pixels = CssReferencePixels::new(INITIAL_FONT_SIZE);
 
         let font = self.font_provider.get(&FontDescriptor {
             name: retina_gfx_font::FamilyName::Serif,
@@ -521,10 +878,17 @@ impl<'stylesheets, ImageLoader> LayoutGenerator<'stylesheets, ImageLoader>
             text_color: Color::BLACK,
             background_color: Color::WHITE,
             text_hinting_options: TextHintingOptions::default(),
+            root_font_size: default_reference_pixels,
+            viewport_width: self.viewport_width,
+            viewport_height: self.viewport_height,
             dimensions,
+            size_constraints: Default::default(),
+            text_overflow: Default::default(),
+            letter_spacing: 0.0,
+            word_spacing: 0.0,
         };
 
-        LayoutBox::new(
+        let mut layout_box = LayoutBox::new(
             LayoutBoxKind::Root,
             FormattingContextKind::Block,
             root,
@@ -534,7 +898,11 @@ impl<'stylesheets, ImageLoader> LayoutGenerator<'stylesheets, ImageLoader>
             font,
             font_emoji,
             font_size
-        )
+        );
+
+        // The root element forms the root stacking context.
+        layout_box.stacking_context_order = Some(0);
+        layout_box
     }
 
     fn generate_replaced_element_layout(&self, layout_box: &mut LayoutBox) {
@@ -556,6 +924,22 @@ impl<'stylesheets, ImageLoader> LayoutGenerator<'stylesheets, ImageLoader>
                 return;
             }
 
+            ReplacedElementType::InputPassword => {
+                let value = layout_box.node.as_html_element_kind()
+                    .and_then(|element| element.as_input_element())
+                    .map(|input| input.value().chars().count())
+                    .unwrap_or_default();
+
+                if value == 0 {
+                    layout_box.node.as_dom_element()
+                        .and_then(|element| element.attributes().find_by_str_as_tendril("placeholder"))
+                        .unwrap_or_default()
+                } else {
+                    // Obscure the characters of the password.
+                    "\u{2022}".repeat(value).into()
+                }
+            }
+
             ReplacedElementType::InputButton | ReplacedElementType::InputText => {
                 let value = layout_box.node.as_dom_element()
                     .and_then(|element| element.attributes().find_by_str_as_tendril("value"))
@@ -579,7 +963,7 @@ impl<'stylesheets, ImageLoader> LayoutGenerator<'stylesheets, ImageLoader>
             return;
         }
 
-        // TODO will probably break when adding ::before and ::after
+        // The boxes of ::before and ::after are added after this.
         debug_assert!(layout_box.children.is_empty());
 
         let text_child = LayoutBox::new(
@@ -598,3 +982,56 @@ impl<'stylesheets, ImageLoader> LayoutGenerator<'stylesheets, ImageLoader>
     }
 
 }
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use retina_style_computation::DependencyMap;
+
+    use crate::{
+        formatting_context::FormattingContextKind,
+        LayoutBox,
+        test_util::{find_box_by_id, generate_layout, generate_layout_of_document},
+    };
+
+    #[rstest]
+    #[case("block", FormattingContextKind::Block)]
+    #[case("flow-root", FormattingContextKind::Block)]
+    #[case("flex", FormattingContextKind::Flex)]
+    #[case("grid", FormattingContextKind::Grid)]
+    #[case("inline-flex", FormattingContextKind::InlineFlex)]
+    #[case("inline-grid", FormattingContextKind::InlineGrid)]
+    fn formatting_context_of_display(#[case] display: &str, #[case] expected: FormattingContextKind) {
+        let css = format!("html, body {{ display: block }} #container {{ display: {display} }}");
+        let root = generate_layout("<div id=container><div>A</div><div>B</div></div>", &css);
+
+        let container = find_box_by_id(&root, "container").expect("no box generated for the container");
+        assert_eq!(container.formatting_context, expected);
+    }
+
+    #[test]
+    fn restyle_only_invalidated_nodes() {
+        let css = "html, body, div { display: block } div { height: 10px } div:hover { height: 50px }";
+        let document = retina_dom::Parser::parse("<div id=hovered>A</div><div id=other>B</div>");
+        let mut dependency_map = DependencyMap::new();
+
+        let height_of = |root: &LayoutBox, id: &str| {
+            find_box_by_id(root, id).unwrap().dimensions().height.value()
+        };
+
+        let root = generate_layout_of_document(document.clone(), css, &mut dependency_map);
+        assert_eq!(height_of(&root, "hovered"), 10.0);
+
+        let hovered = find_box_by_id(&root, "hovered").unwrap().node.clone();
+        hovered.as_dom_element().unwrap().set_hovered(true);
+
+        // The recorded style is reused, since the node wasn't invalidated.
+        let root = generate_layout_of_document(document.clone(), css, &mut dependency_map);
+        assert_eq!(height_of(&root, "hovered"), 10.0);
+
+        dependency_map.invalidate(&hovered, true);
+        let root = generate_layout_of_document(document, css, &mut dependency_map);
+        assert_eq!(height_of(&root, "hovered"), 50.0);
+        assert_eq!(height_of(&root, "other"), 10.0);
+    }
+}
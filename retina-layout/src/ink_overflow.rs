@@ -0,0 +1,114 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! Computation of the area boxes paint onto, which can be larger than their
+//! border boxes.
+//!
+//! # References
+//! * [CSS Overflow Module Level 3 § 2.2. Ink Overflow](https://drafts.csswg.org/css-overflow/#ink)
+
+use euclid::default::{Rect, Vector2D};
+use retina_style::{CssDecimal, CssLineStyle, CssOutlineStyle};
+
+use crate::LayoutBox;
+
+impl LayoutBox {
+    /// Checks whether the ink overflow rectangle of this box overlaps with
+    /// the given `rect`, i.e. whether painting this box could affect the
+    /// pixels inside that rect.
+    pub fn covers_rect(&self, rect: &Rect<CssDecimal>) -> bool {
+        self.rect_ink_overflow().intersects(rect)
+    }
+
+    /// Get the [ink overflow rectangle][spec] of this box: the border box,
+    /// extended by the effects that are painted outside of it without
+    /// affecting the layout, like the outer `box-shadow`s and the outline.
+    ///
+    /// [spec]: https://drafts.csswg.org/css-overflow/#ink-overflow-rectangle
+    pub fn rect_ink_overflow(&self) -> Rect<CssDecimal> {
+        let border_box = self.dimensions.rect_border_box();
+        let resolve = |length| self.resolve_length(length, None).unwrap_or_default();
+
+        let mut rect = border_box;
+
+        for shadow in self.computed_style.box_shadow().iter().filter(|shadow| !shadow.inset) {
+            let offset = Vector2D::new(resolve(shadow.offset_x), resolve(shadow.offset_y));
+
+            // The painter blurs up to three standard deviations, which is
+            // half the blur radius, beyond the shape of the shadow.
+            let blur = resolve(shadow.blur_radius).max(0.0) * 1.5;
+            let extent = resolve(shadow.spread_radius) + blur;
+
+            rect = rect.union(&border_box.translate(offset).inflate(extent, extent));
+        }
+
+        let outline = self.computed_style.outline;
+        if let CssOutlineStyle::Auto | CssOutlineStyle::LineStyle(CssLineStyle::Solid) = outline.style {
+            let width = resolve(outline.width);
+            if width > 0.0 {
+                let extent = resolve(outline.offset) + width;
+                rect = rect.union(&border_box.inflate(extent, extent));
+            }
+        }
+
+        rect
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::{find_box_by_id, generate_layout};
+
+    #[test]
+    fn outer_box_shadows() {
+        let root = generate_layout(
+            "<div id=shadow></div>",
+            "#shadow { width: 100px; height: 50px; box-shadow: 10px 20px 4px 2px black, inset -30px 0 black }",
+        );
+
+        let layout_box = find_box_by_id(&root, "shadow").unwrap();
+        let border_box = layout_box.dimensions().rect_border_box();
+        let ink_overflow = layout_box.rect_ink_overflow();
+
+        assert_eq!(ink_overflow.min_x(), border_box.min_x());
+        assert_eq!(ink_overflow.min_y(), border_box.min_y());
+        assert_eq!(ink_overflow.max_x(), border_box.max_x() + 10.0 + 2.0 + 6.0);
+        assert_eq!(ink_overflow.max_y(), border_box.max_y() + 20.0 + 2.0 + 6.0);
+
+        assert!(layout_box.covers_rect(&border_box.translate(euclid::vec2(110.0, 0.0))));
+        assert!(!layout_box.covers_rect(&border_box.translate(euclid::vec2(120.0, 0.0))));
+    }
+
+    #[test]
+    fn shrunk_box_shadow() {
+        let root = generate_layout(
+            "<div id=shadow></div>",
+            "#shadow { width: 100px; height: 50px; box-shadow: 0 0 0 -40px black }",
+        );
+
+        let layout_box = find_box_by_id(&root, "shadow").unwrap();
+        assert_eq!(layout_box.rect_ink_overflow(), layout_box.dimensions().rect_border_box());
+    }
+
+    #[test]
+    fn outline() {
+        let root = generate_layout(
+            "<div id=outline></div>",
+            "#outline { width: 100px; height: 50px; outline: 3px solid red; outline-offset: 2px }",
+        );
+
+        let layout_box = find_box_by_id(&root, "outline").unwrap();
+        assert_eq!(layout_box.rect_ink_overflow(), layout_box.dimensions().rect_border_box().inflate(5.0, 5.0));
+    }
+
+    #[test]
+    fn outline_without_style() {
+        let root = generate_layout(
+            "<div id=outline></div>",
+            "#outline { width: 100px; height: 50px; outline-width: 3px }",
+        );
+
+        let layout_box = find_box_by_id(&root, "outline").unwrap();
+        assert_eq!(layout_box.rect_ink_overflow(), layout_box.dimensions().rect_border_box());
+    }
+}
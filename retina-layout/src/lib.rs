@@ -18,14 +18,24 @@ mod actual_values;
 mod boxes;
 mod formatting_context;
 mod generate;
+mod ink_overflow;
 pub(crate) mod replaced;
+mod stacking_context;
 pub(crate) mod text;
+mod transform;
+
+#[cfg(test)]
+mod test_util;
 
 use retina_gfx_font::FontStyle;
 use retina_style::CssFontStyle;
 
 pub use self::{
-    actual_values::ActualValueMap,
+    actual_values::{
+        ActualValueMap,
+        SizeConstraint,
+        SizeConstraints,
+    },
     boxes::{
         LayoutBox,
         LayoutBoxDimensions,
@@ -33,6 +43,11 @@ pub use self::{
         LayoutEdge,
     },
     generate::LayoutGenerator,
+    stacking_context::{
+        StackingContext,
+        StackingContextEntry,
+        StackingLayer,
+    },
 };
 
 pub(crate) type DomNode = retina_dom::Node;
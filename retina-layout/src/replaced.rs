@@ -1,13 +1,14 @@
 // Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
 // All Rights Reserved.
 
-use retina_dom::{Node, qual_name};
+use retina_dom::{HtmlElementKind, InputType, Node, qual_name};
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) enum ReplacedElementType {
     Button,
     Checkbox,
     InputButton,
+    InputPassword,
     InputText,
 }
 
@@ -18,14 +19,14 @@ impl ReplacedElementType {
             return Some(Self::Button);
         }
 
-        if *element.as_dom_element().qualified_name().local != qual_name("input").local {
+        let HtmlElementKind::Input(input) = element else {
             return None;
-        }
+        };
 
-        let ty = element.as_dom_element().attributes().find_by_str("type").unwrap_or("text");
-        match ty {
-            "button" | "submit" => Some(Self::InputButton),
-            "checkbox" => Some(Self::Checkbox),
+        match input.input_type() {
+            InputType::Checkbox | InputType::Radio => Some(Self::Checkbox),
+            InputType::Password => Some(Self::InputPassword),
+            ty if ty.is_button() => Some(Self::InputButton),
             _ => Some(Self::InputText),
         }
     }
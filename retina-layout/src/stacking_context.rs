@@ -0,0 +1,341 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! Stacking contexts determine the order in which the boxes are painted,
+//! which for overlapping boxes decides which one ends up on top.
+//!
+//! # References
+//! * [CSS 2.2 § 9.9 Layered presentation](https://www.w3.org/TR/CSS22/visuren.html#layers)
+//! * [CSS 2.2 Appendix E. Elaborate description of Stacking Contexts](https://www.w3.org/TR/CSS22/zindex.html)
+
+use euclid::default::{Rect, Vector2D};
+use retina_style::{
+    CssDecimal,
+    CssDisplay,
+    CssDisplayOutside,
+    CssFloatValue,
+    CssPosition,
+    CssTransform,
+    CssZIndex,
+};
+use retina_style_computation::PropertyMap;
+
+use crate::{LayoutBox, LayoutBoxKind};
+
+/// The layer of a box that doesn't establish a stacking context, within the
+/// stacking context of its ancestor.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StackingLayer {
+    /// In-flow, non-positioned, block-level boxes.
+    Block,
+
+    /// Non-positioned floats.
+    Float,
+
+    /// In-flow, non-positioned, inline-level boxes (including the anonymous
+    /// boxes containing text).
+    Inline,
+}
+
+impl StackingLayer {
+    fn of(layout_box: &LayoutBox) -> Self {
+        if layout_box.kind == LayoutBoxKind::Anonymous {
+            return Self::Inline;
+        }
+
+        if layout_box.computed_style().float() != CssFloatValue::None {
+            return Self::Float;
+        }
+
+        match layout_box.computed_style().display() {
+            CssDisplay::Normal { outside: CssDisplayOutside::Inline, .. } => Self::Inline,
+            _ => Self::Block,
+        }
+    }
+}
+
+/// A box that is painted as part of a [`StackingContext`] it doesn't
+/// establish itself.
+#[derive(Copy, Clone, Debug)]
+pub struct StackingContextEntry<'boxes> {
+    pub layout_box: &'boxes LayoutBox,
+    pub layer: StackingLayer,
+
+    /// The sum of the negated scroll offsets of the ancestors.
+    pub translation: Vector2D<CssDecimal>,
+
+    /// The rect the box is clipped to by the ancestors that clip their
+    /// overflow, if any.
+    pub clip: Option<Rect<CssDecimal>>,
+}
+
+/// A box establishing a stacking context, with the descendants painted as
+/// part of it, and the stacking contexts nested in it.
+#[derive(Clone, Debug)]
+pub struct StackingContext<'boxes> {
+    layout_box: &'boxes LayoutBox,
+    translation: Vector2D<CssDecimal>,
+    clip: Option<Rect<CssDecimal>>,
+    z_index: i32,
+
+    /// The descendants that don't establish a stacking context, in tree
+    /// order.
+    descendants: Vec<StackingContextEntry<'boxes>>,
+
+    /// The child stacking contexts, sorted by their `z-index`. Contexts with
+    /// the same `z-index` are kept in tree order.
+    children: Vec<StackingContext<'boxes>>,
+}
+
+impl<'boxes> StackingContext<'boxes> {
+    /// Build the stacking context tree for the given box, which is treated as
+    /// the root of a stacking context, regardless of its style.
+    pub fn build(layout_box: &'boxes LayoutBox, translation: Vector2D<CssDecimal>) -> Self {
+        Self::build_with_clip(layout_box, translation, None)
+    }
+
+    fn build_with_clip(
+        layout_box: &'boxes LayoutBox,
+        translation: Vector2D<CssDecimal>,
+        clip: Option<Rect<CssDecimal>>,
+    ) -> Self {
+        let mut context = Self {
+            layout_box,
+            translation,
+            clip,
+            z_index: layout_box.stacking_context_order.unwrap_or(0),
+            descendants: Vec::new(),
+            children: Vec::new(),
+        };
+
+        // The descendants of a transformed box are painted in the coordinate
+        // space of that box, so the clip of its ancestors doesn't apply to
+        // them, but to the box as a whole.
+        let inherited_clip = if layout_box.computed_style.transform() == &CssTransform::None {
+            clip
+        } else {
+            None
+        };
+
+        let child_clip = clip_for_children(layout_box, translation, inherited_clip);
+        context.collect(layout_box, translation - layout_box.scroll_offset(), child_clip);
+
+        // `sort_by_key` is stable, which preserves the tree order of
+        // contexts with the same stack level.
+        context.children.sort_by_key(|child| child.z_index);
+        context
+    }
+
+    fn collect(
+        &mut self,
+        parent: &'boxes LayoutBox,
+        translation: Vector2D<CssDecimal>,
+        clip: Option<Rect<CssDecimal>>,
+    ) {
+        for child in &parent.children {
+            if child.stacking_context_order.is_some() {
+                self.children.push(Self::build_with_clip(child, translation, clip));
+                continue;
+            }
+
+            self.descendants.push(StackingContextEntry {
+                layout_box: child,
+                layer: StackingLayer::of(child),
+                translation,
+                clip,
+            });
+
+            let child_clip = clip_for_children(child, translation, clip);
+            self.collect(child, translation - child.scroll_offset(), child_clip);
+        }
+    }
+
+    /// Get the box establishing this stacking context.
+    pub fn layout_box(&self) -> &'boxes LayoutBox {
+        self.layout_box
+    }
+
+    pub fn translation(&self) -> Vector2D<CssDecimal> {
+        self.translation
+    }
+
+    /// Get the rect the box establishing this stacking context is clipped to
+    /// by its ancestors, if any.
+    pub fn clip(&self) -> Option<Rect<CssDecimal>> {
+        self.clip
+    }
+
+    pub fn z_index(&self) -> i32 {
+        self.z_index
+    }
+
+    /// Get the area the boxes of this stacking context paint onto, including
+    /// the nested stacking contexts, without any transforms applied.
+    pub fn bounds(&self) -> Rect<CssDecimal> {
+        let mut bounds = self.layout_box.rect_ink_overflow().translate(self.translation);
+
+        for entry in &self.descendants {
+            let ink_overflow = entry.layout_box.rect_ink_overflow().translate(entry.translation);
+            bounds = bounds.union(&ink_overflow);
+        }
+
+        for child in &self.children {
+            bounds = bounds.union(&child.bounds());
+        }
+
+        bounds
+    }
+
+    /// Get the descendants painted as part of this stacking context, in tree
+    /// order.
+    pub fn descendants(&self) -> &[StackingContextEntry<'boxes>] {
+        &self.descendants
+    }
+
+    /// Get the descendants of the given layer, in tree order.
+    pub fn descendants_in_layer(
+        &self,
+        layer: StackingLayer,
+    ) -> impl Iterator<Item = &StackingContextEntry<'boxes>> {
+        self.descendants.iter().filter(move |entry| entry.layer == layer)
+    }
+
+    /// Get the child stacking contexts with a negative stack level, which are
+    /// painted below the in-flow descendants.
+    pub fn negative_children(&self) -> &[StackingContext<'boxes>] {
+        &self.children[..self.first_non_negative_child()]
+    }
+
+    /// Get the child stacking contexts with a stack level of zero or higher,
+    /// which are painted above the in-flow descendants.
+    pub fn non_negative_children(&self) -> &[StackingContext<'boxes>] {
+        &self.children[self.first_non_negative_child()..]
+    }
+
+    fn first_non_negative_child(&self) -> usize {
+        self.children.partition_point(|child| child.z_index < 0)
+    }
+}
+
+/// Compute the rect the children of the box are clipped to, which is the
+/// padding box when the box [clips its overflow][spec], intersected with the
+/// rect the box itself is clipped to.
+///
+/// TODO: absolutely positioned descendants shouldn't be clipped when their
+///       containing block is an ancestor of the box.
+///
+/// [spec]: https://drafts.csswg.org/css-overflow/#overflow-properties
+fn clip_for_children(
+    layout_box: &LayoutBox,
+    translation: Vector2D<CssDecimal>,
+    clip: Option<Rect<CssDecimal>>,
+) -> Option<Rect<CssDecimal>> {
+    if !layout_box.clips_overflow() {
+        return clip;
+    }
+
+    let padding_box = layout_box.dimensions.rect_padding_box().translate(translation);
+    match clip {
+        Some(clip) => Some(clip.intersection(&padding_box).unwrap_or_else(Rect::zero)),
+        None => Some(padding_box),
+    }
+}
+
+/// Compute the stack level of a box, if it establishes a stacking context.
+///
+/// # References
+/// * [CSS Color Module Level 4 § 13.2. Transparency: the `opacity` property](https://drafts.csswg.org/css-color/#transparency)
+/// * [CSS 2.2 § 9.9.1 Specifying the stack level: the `z-index` property](https://www.w3.org/TR/CSS22/visuren.html#z-index)
+/// * [CSS Positioned Layout Module Level 3 § 3.4. Sticky positioning](https://drafts.csswg.org/css-position/#sticky-pos)
+/// * [CSS Transforms Module Level 1 § 3. The Transform Rendering Model](https://drafts.csswg.org/css-transforms/#transform-rendering)
+pub(crate) fn stacking_context_order(style: &PropertyMap) -> Option<i32> {
+    // > Since an element with opacity less than 1 is composited from a single
+    // > offscreen image, content outside of it cannot be layered in z-order
+    // > between pieces of content inside of it. For the same reason,
+    // > implementations must create a new stacking context for any element
+    // > with opacity less than 1.
+    let is_composited = style.transform() != &CssTransform::None
+        || style.opacity().value() < 1.0;
+
+    match (style.position(), style.z_index()) {
+        // Transformed and translucent boxes establish a stacking context,
+        // even when they aren't positioned.
+        (CssPosition::Static, _) if is_composited => Some(0),
+        (CssPosition::Static, _) => None,
+
+        // Fixed and sticky boxes always establish a stacking context.
+        (CssPosition::Fixed | CssPosition::Sticky, CssZIndex::Auto) => Some(0),
+
+        (_, CssZIndex::Integer(z_index)) => Some(z_index),
+        (_, CssZIndex::Auto) if is_composited => Some(0),
+        (_, CssZIndex::Auto) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(CssPosition::Static, CssZIndex::Auto, None)]
+    #[case(CssPosition::Static, CssZIndex::Integer(10), None)]
+    #[case(CssPosition::Relative, CssZIndex::Auto, None)]
+    #[case(CssPosition::Relative, CssZIndex::Integer(-1), Some(-1))]
+    #[case(CssPosition::Absolute, CssZIndex::Integer(0), Some(0))]
+    #[case(CssPosition::Fixed, CssZIndex::Auto, Some(0))]
+    #[case(CssPosition::Sticky, CssZIndex::Integer(3), Some(3))]
+    fn order(
+        #[case] position: CssPosition,
+        #[case] z_index: CssZIndex,
+        #[case] expected: Option<i32>,
+    ) {
+        let style = PropertyMap {
+            position: Some(position),
+            z_index: Some(z_index),
+            ..Default::default()
+        };
+
+        assert_eq!(stacking_context_order(&style), expected);
+    }
+
+    #[rstest]
+    #[case(CssPosition::Static, CssZIndex::Auto, Some(0))]
+    #[case(CssPosition::Relative, CssZIndex::Auto, Some(0))]
+    #[case(CssPosition::Relative, CssZIndex::Integer(2), Some(2))]
+    fn order_of_transformed_box(
+        #[case] position: CssPosition,
+        #[case] z_index: CssZIndex,
+        #[case] expected: Option<i32>,
+    ) {
+        let style = PropertyMap {
+            position: Some(position),
+            z_index: Some(z_index),
+            transform: Some(CssTransform::List(vec![
+                retina_style::CssTransformFunction::Scale(2.0, 2.0),
+            ])),
+            ..Default::default()
+        };
+
+        assert_eq!(stacking_context_order(&style), expected);
+    }
+
+    #[rstest]
+    #[case(CssPosition::Static, 1.0, None)]
+    #[case(CssPosition::Static, 0.5, Some(0))]
+    #[case(CssPosition::Relative, 0.0, Some(0))]
+    #[case(CssPosition::Relative, 1.0, None)]
+    fn order_of_translucent_box(
+        #[case] position: CssPosition,
+        #[case] opacity: CssDecimal,
+        #[case] expected: Option<i32>,
+    ) {
+        let style = PropertyMap {
+            position: Some(position),
+            opacity: Some(retina_style::CssOpacity(opacity)),
+            ..Default::default()
+        };
+
+        assert_eq!(stacking_context_order(&style), expected);
+    }
+}
@@ -0,0 +1,165 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! Utilities for the tests of this crate, which lay out documents using a
+//! font with fixed metrics, such that no font backend is required.
+
+use std::{path::Path, sync::Arc};
+
+use euclid::default::{Point2D, Rect, Size2D};
+use retina_common::{Color, LoadTime};
+use retina_dom::{ImageData, Node};
+use retina_fetch::Url;
+use retina_gfx::Painter;
+use retina_gfx_font::{
+    Font,
+    FontDescriptor,
+    FontHandle,
+    FontProvider,
+    FontProviderBackend,
+    TextHintingOptions,
+};
+use retina_style::{CascadeOrigin, CssDecimal, CssReferencePixels};
+use retina_style_computation::DependencyMap;
+
+use crate::{LayoutBox, LayoutGenerator};
+
+/// The width of the viewport the documents are laid out in.
+pub(crate) const VIEWPORT_WIDTH: CssDecimal = 800.0;
+
+/// The height of the viewport the documents are laid out in.
+pub(crate) const VIEWPORT_HEIGHT: CssDecimal = 600.0;
+
+/// Lay out the `html` document, styled by the author stylesheet `css`.
+pub(crate) fn generate_layout(html: &str, css: &str) -> LayoutBox {
+    let document = retina_dom::Parser::parse(html);
+    generate_layout_of_document(document, css, &mut DependencyMap::new())
+}
+
+/// Lay out the `document`, reusing the styles recorded in the
+/// `dependency_map` by previous layouts.
+pub(crate) fn generate_layout_of_document(document: Node, css: &str, dependency_map: &mut DependencyMap) -> LayoutBox {
+    let stylesheets = [retina_style_parser::parse_stylesheet(CascadeOrigin::Author, css)];
+    let document_url = Url::parse("about:blank").unwrap();
+
+    LayoutGenerator::generate(
+        document,
+        &stylesheets,
+        dependency_map,
+        CssReferencePixels::new(VIEWPORT_WIDTH),
+        CssReferencePixels::new(VIEWPORT_HEIGHT),
+        FontProvider::new(Arc::new(TestFontProvider)),
+        &document_url,
+        |_| ImageData::new(),
+        |_, _| {},
+    )
+}
+
+/// Find the box generated for the element with the given `id`.
+pub(crate) fn find_box_by_id<'bx>(layout_box: &'bx LayoutBox, id: &str) -> Option<&'bx LayoutBox> {
+    if layout_box.node.as_dom_element().is_some_and(|element| element.id() == id) {
+        return Some(layout_box);
+    }
+
+    layout_box.children()
+        .iter()
+        .find_map(|child| find_box_by_id(child, id))
+}
+
+/// Provides a [`TestFont`] for every descriptor.
+struct TestFontProvider;
+
+impl FontProviderBackend for TestFontProvider {
+    fn get(&self, descriptor: &FontDescriptor) -> Option<FontHandle> {
+        Some(FontHandle::new(Arc::new(TestFont {
+            descriptor: descriptor.clone(),
+        })))
+    }
+
+    fn load(&self, _: FontDescriptor, _: Vec<u8>, _: u32) -> bool {
+        false
+    }
+
+    fn load_defaults(&self) {}
+
+    fn load_from_system(&self, _: FontDescriptor) -> bool {
+        false
+    }
+
+    fn load_from_file(&self, _: LoadTime, _: &Path, _: FontDescriptor, _: u32) -> bool {
+        false
+    }
+}
+
+/// A font of which every character is half an em wide and one em high, and
+/// one em square when it is set vertically.
+struct TestFont {
+    descriptor: FontDescriptor,
+}
+
+impl TestFont {
+    const ADVANCE: f32 = 0.5;
+}
+
+impl Font for TestFont {
+    fn calculate_size(&self, size: f32, text: &str, _: TextHintingOptions) -> Size2D<f32> {
+        Size2D::new(text.chars().count() as f32 * size * Self::ADVANCE, size)
+    }
+
+    fn calculate_vertical_size(&self, size: f32, text: &str, _: TextHintingOptions) -> Size2D<f32> {
+        Size2D::new(size, text.chars().count() as f32 * size)
+    }
+
+    fn descriptor(&self) -> &FontDescriptor {
+        &self.descriptor
+    }
+
+    /// Every character has a glyph filling its advance, except for the
+    /// control characters, which have none.
+    fn glyph_bounds(&self, codepoint: char, point_size: f32) -> Rect<f32> {
+        if codepoint.is_control() {
+            return Rect::zero();
+        }
+
+        Rect::new(Point2D::zero(), Size2D::new(point_size * Self::ADVANCE, point_size))
+    }
+
+    fn baseline_offset(&self, point_size: f32) -> f32 {
+        point_size * 0.8
+    }
+
+    fn underline_position(&self, point_size: f32) -> f32 {
+        point_size * 0.9
+    }
+
+    fn underline_thickness(&self, point_size: f32) -> f32 {
+        point_size / 16.0
+    }
+
+    fn paint(&self, _: &str, _: Color, _: Point2D<f32>, _: f32, _: TextHintingOptions, _: &mut Painter) {
+        unimplemented!("the test font can't be painted")
+    }
+
+    fn paint_vertical(&self, _: &str, _: Color, _: Point2D<f32>, _: f32, _: TextHintingOptions, _: &mut Painter) {
+        unimplemented!("the test font can't be painted")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use retina_gfx_font::{FamilyName, FontStyle, FontWeight};
+
+    use super::*;
+
+    #[test]
+    fn glyph_bounds() {
+        let font = TestFontProvider.get(&FontDescriptor {
+            name: FamilyName::Serif,
+            style: FontStyle::Normal,
+            weight: FontWeight::REGULAR,
+        }).unwrap();
+
+        assert_eq!(font.glyph_bounds('a', 16.0), Rect::new(Point2D::zero(), Size2D::new(8.0, 16.0)));
+        assert_eq!(font.glyph_bounds('\u{7}', 16.0), Rect::zero());
+    }
+}
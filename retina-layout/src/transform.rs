@@ -0,0 +1,169 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! Computation of the transformation matrices of boxes with a `transform`.
+//!
+//! # References
+//! * [CSS Transforms Module Level 1](https://drafts.csswg.org/css-transforms/)
+//! * [CSS Transforms Module Level 2](https://drafts.csswg.org/css-transforms-2/)
+
+use euclid::default::{Size2D, Transform3D, Vector3D};
+use retina_style::{CssDecimal, CssTransform, CssTransformFunction};
+
+use crate::{formatting_context::LengthContext, LayoutBox};
+
+impl LayoutBox {
+    /// Get the transformation matrix of the `transform` property, in the
+    /// coordinates of the page, or `None` if the box isn't transformed.
+    ///
+    /// TODO: `transform-origin` isn't supported, so the initial value of
+    ///       `50% 50%` is used.
+    ///
+    /// # References
+    /// * [CSS Transforms Module Level 1 § 6. The Transform Rendering Model](https://drafts.csswg.org/css-transforms/#transform-rendering)
+    pub fn transform_matrix(&self) -> Option<Transform3D<CssDecimal>> {
+        let CssTransform::List(functions) = self.computed_style.transform() else {
+            return None;
+        };
+
+        let border_box = self.dimensions.rect_border_box();
+        let matrix = transform_list_matrix(functions, border_box.size, &LengthContext::new(self));
+
+        let origin = border_box.center();
+        Some(
+            Transform3D::translation(-origin.x, -origin.y, 0.0)
+                .then(&matrix)
+                .then_translate(Vector3D::new(origin.x, origin.y, 0.0))
+        )
+    }
+}
+
+/// Compute the matrix of a `<transform-list>`, where the percentages of the
+/// translations are relative to the size of the `reference_box`.
+///
+/// # References
+/// * [CSS Transforms Module Level 1 § 8. The Transformation Matrix Computation](https://drafts.csswg.org/css-transforms/#transformation-matrix-computation)
+fn transform_list_matrix(
+    functions: &[CssTransformFunction],
+    reference_box: Size2D<CssDecimal>,
+    lengths: &LengthContext,
+) -> Transform3D<CssDecimal> {
+    // The functions are post-multiplied, i.e. the rightmost function is
+    // applied to the points first. euclid multiplies with row vectors, so
+    // `then()` is called in the reverse order.
+    functions.iter()
+        .rev()
+        .fold(Transform3D::identity(), |matrix, function| {
+            matrix.then(&transform_function_matrix(function, reference_box, lengths))
+        })
+}
+
+fn transform_function_matrix(
+    function: &CssTransformFunction,
+    reference_box: Size2D<CssDecimal>,
+    lengths: &LengthContext,
+) -> Transform3D<CssDecimal> {
+    match *function {
+        CssTransformFunction::Matrix(values) => matrix_2d(values),
+
+        // The values of `matrix3d()` are in column-major order, which is the
+        // row-major order of the transposed matrix euclid uses.
+        CssTransformFunction::Matrix3d(v) => Transform3D::new(
+            v[0], v[1], v[2], v[3],
+            v[4], v[5], v[6], v[7],
+            v[8], v[9], v[10], v[11],
+            v[12], v[13], v[14], v[15],
+        ),
+
+        // > perspective(none) ... the value of the function is the identity
+        // > matrix
+        CssTransformFunction::Perspective(None) => Transform3D::identity(),
+        CssTransformFunction::Perspective(Some(length)) => {
+            // Values below 1px are clamped to 1px, which also avoids
+            // dividing by zero.
+            let distance = lengths.resolve(length, None).unwrap_or(0.0).max(1.0);
+            Transform3D::perspective(distance)
+        }
+
+        CssTransformFunction::Rotate(angle) => {
+            let (sin, cos) = angle.to_radians().sin_cos();
+            matrix_2d([cos, sin, -sin, cos, 0.0, 0.0])
+        }
+
+        CssTransformFunction::Scale(x, y) => matrix_2d([x, 0.0, 0.0, y, 0.0, 0.0]),
+
+        CssTransformFunction::Skew(x, y) => {
+            matrix_2d([1.0, y.to_radians().tan(), x.to_radians().tan(), 1.0, 0.0, 0.0])
+        }
+
+        CssTransformFunction::Translate(x, y) => {
+            let x = lengths.resolve(x, Some(reference_box.width)).unwrap_or(0.0);
+            let y = lengths.resolve(y, Some(reference_box.height)).unwrap_or(0.0);
+            matrix_2d([1.0, 0.0, 0.0, 1.0, x, y])
+        }
+    }
+}
+
+/// Create the 3D matrix equivalent of `matrix(a, b, c, d, e, f)`.
+fn matrix_2d([a, b, c, d, e, f]: [CssDecimal; 6]) -> Transform3D<CssDecimal> {
+    Transform3D::new(
+        a, b, 0.0, 0.0,
+        c, d, 0.0, 0.0,
+        0.0, 0.0, 1.0, 0.0,
+        e, f, 0.0, 1.0,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use euclid::default::Point2D;
+    use retina_style::{CssAngle, CssLength};
+    use rstest::rstest;
+
+    fn lengths() -> LengthContext {
+        LengthContext { font_size: 16.0, root_font_size: 16.0, viewport_width: 800.0, viewport_height: 600.0 }
+    }
+
+    #[rstest]
+    #[case(vec![], Point2D::new(3.0, 4.0), Point2D::new(3.0, 4.0))]
+    #[case(
+        vec![CssTransformFunction::Translate(CssLength::Pixels(10.0), CssLength::Percentage(0.5))],
+        Point2D::new(0.0, 0.0),
+        Point2D::new(10.0, 20.0),
+    )]
+    #[case(
+        vec![CssTransformFunction::Rotate(CssAngle::Degrees(90.0))],
+        Point2D::new(1.0, 0.0),
+        Point2D::new(0.0, 1.0),
+    )]
+    #[case(
+        vec![
+            CssTransformFunction::Translate(CssLength::Pixels(10.0), CssLength::Pixels(0.0)),
+            CssTransformFunction::Scale(2.0, 3.0),
+        ],
+        Point2D::new(1.0, 1.0),
+        Point2D::new(12.0, 3.0),
+    )]
+    #[case(
+        vec![CssTransformFunction::Skew(CssAngle::Degrees(45.0), CssAngle::default())],
+        Point2D::new(0.0, 2.0),
+        Point2D::new(2.0, 2.0),
+    )]
+    #[case(
+        vec![CssTransformFunction::Matrix([1.0, 0.0, 0.0, 1.0, 5.0, 6.0])],
+        Point2D::new(1.0, 1.0),
+        Point2D::new(6.0, 7.0),
+    )]
+    fn transform_point(
+        #[case] functions: Vec<CssTransformFunction>,
+        #[case] point: Point2D<CssDecimal>,
+        #[case] expected: Point2D<CssDecimal>,
+    ) {
+        let matrix = transform_list_matrix(&functions, Size2D::new(100.0, 40.0), &lengths());
+        let actual = matrix.transform_point2d(point).unwrap();
+
+        assert!((actual.x - expected.x).abs() < 1e-9, "{actual:?} != {expected:?}");
+        assert!((actual.y - expected.y).abs() < 1e-9, "{actual:?} != {expected:?}");
+    }
+}
@@ -11,11 +11,11 @@ pub use mime;
 /// An XML MIME type is any MIME type whose subtype ends in "+xml" or whose
 /// essence is "text/xml" or "application/xml". [RFC7303]
 pub fn is_xml_mime_type(mime: &Mime) -> bool {
-    if mime == &mime::TEXT_XML {
+    if mime.essence_str() == mime::TEXT_XML.essence_str() {
         return true;
     }
 
-    if mime.type_() == mime::APPLICATION && mime.type_() == mime::XML {
+    if mime.type_() == mime::APPLICATION && mime.subtype() == mime::XML {
         return true;
     }
 
@@ -0,0 +1,430 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! The state of the running [CSS Animations][spec], which animate the values
+//! of properties using the keyframes of an `@keyframes` rule.
+//!
+//! [spec]: https://drafts.csswg.org/css-animations/
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use retina_dom::Node;
+use retina_style::{
+    AtKeyframesRule,
+    CssAnimation,
+    CssAnimationDirection,
+    CssAnimationFillMode,
+    CssAnimationIterationCount,
+    CssDecimal,
+    CssInterpolatable,
+    Property,
+    Stylesheet,
+    Value,
+};
+use retina_style_computation::{
+    NodeId,
+    PropertyMap,
+    INTERPOLATABLE_PROPERTIES,
+};
+
+/// Tracks the moments the animations of the elements started, and overrides
+/// the computed values of the animated properties.
+///
+/// A style update is the generation of the layout tree, which calls
+/// [`AnimationState::update_style()`] for every element in between
+/// [`AnimationState::begin_style_update()`] and
+/// [`AnimationState::end_style_update()`].
+#[derive(Debug)]
+pub(crate) struct AnimationState {
+    /// The moments the animations started, keyed by the element and the
+    /// `animation-name`.
+    start_instants: HashMap<(NodeId, String), Instant>,
+
+    /// The animations that were applied in the style update in progress.
+    current_animations: HashMap<(NodeId, String), Instant>,
+
+    /// Whether or not any animation of the style update in progress hasn't
+    /// reached the end of its active interval yet.
+    has_running_animations: bool,
+
+    /// The moment of the style update in progress.
+    now: Instant,
+}
+
+impl Default for AnimationState {
+    fn default() -> Self {
+        Self {
+            start_instants: HashMap::new(),
+            current_animations: HashMap::new(),
+            has_running_animations: false,
+            now: Instant::now(),
+        }
+    }
+}
+
+impl AnimationState {
+    /// Whether or not there are animations that still have to be painted,
+    /// which requires the styles to be updated for the next frame.
+    pub(crate) fn has_running_animations(&self) -> bool {
+        self.has_running_animations
+    }
+
+    pub(crate) fn begin_style_update(&mut self, now: Instant) {
+        self.now = now;
+        self.current_animations.clear();
+        self.has_running_animations = false;
+    }
+
+    /// Override the computed values of the properties of the element that
+    /// are animated by the items of its `animation` property. When multiple
+    /// animations animate the same property, the last one wins.
+    ///
+    /// # References
+    /// * [CSS Animations Level 1 § 4. Declaring Animations](https://drafts.csswg.org/css-animations/#animation-definition)
+    pub(crate) fn update_style(&mut self, node: &Node, style: &mut PropertyMap, style_sheets: &[Stylesheet]) {
+        if !node.is_element() || style.animation().is_empty() {
+            return;
+        }
+
+        let node_id = NodeId::of(node);
+        let underlying_style = style.clone();
+
+        for animation in underlying_style.animation() {
+            let Some(name) = &animation.name else { continue };
+            let Some(keyframes) = find_keyframes(style_sheets, name) else { continue };
+
+            // TODO: `animation-play-state: paused` should stop the clock of
+            //       the animation instead of being ignored.
+            let key = (node_id, name.to_string());
+            let start_instant = self.start_instants.get(&key).copied().unwrap_or(self.now);
+            self.current_animations.insert(key, start_instant);
+
+            let elapsed = self.now.saturating_duration_since(start_instant);
+            let Some(sample) = sample_animation(animation, elapsed) else { continue };
+            if sample.is_running {
+                self.has_running_animations = true;
+            }
+
+            apply_keyframes(keyframes, animation, sample.progress, &underlying_style, style);
+        }
+    }
+
+    /// Forget the animations that weren't applied in this update, e.g.
+    /// because the element was removed or its `animation-name` changed, such
+    /// that they restart when they are applied again.
+    pub(crate) fn end_style_update(&mut self) {
+        std::mem::swap(&mut self.start_instants, &mut self.current_animations);
+        self.current_animations.clear();
+    }
+}
+
+/// The sampled state of an animation at a given moment.
+struct AnimationSample {
+    /// The [directed progress][spec] within the current iteration.
+    ///
+    /// [spec]: https://drafts.csswg.org/web-animations-1/#directed-progress
+    progress: CssDecimal,
+
+    /// Whether or not the animation hasn't reached the end of its active
+    /// interval yet.
+    is_running: bool,
+}
+
+/// Compute the directed progress of the animation after the `elapsed` time
+/// since it started, or [`None`] if the animation doesn't apply at this
+/// moment, i.e. when it's delayed or has finished and its
+/// `animation-fill-mode` doesn't fill that period.
+///
+/// # References
+/// * [Web Animations § 4.6. Core animation model calculations](https://drafts.csswg.org/web-animations-1/#core-animation-model-calculations)
+fn sample_animation(animation: &CssAnimation, elapsed: Duration) -> Option<AnimationSample> {
+    let iteration_count = match animation.iteration_count {
+        CssAnimationIterationCount::Infinite => CssDecimal::INFINITY,
+        CssAnimationIterationCount::Count(count) => count.max(0.0),
+    };
+
+    let fills_backwards = matches!(animation.fill_mode, CssAnimationFillMode::Backwards | CssAnimationFillMode::Both);
+    let fills_forwards = matches!(animation.fill_mode, CssAnimationFillMode::Forwards | CssAnimationFillMode::Both);
+
+    let duration = animation.duration.as_secs_f64();
+    let active_duration = if duration == 0.0 { 0.0 } else { duration * iteration_count };
+
+    let (overall_progress, is_running) = if elapsed < animation.delay {
+        if !fills_backwards {
+            return None;
+        }
+        (0.0, true)
+    } else {
+        let local_time = (elapsed - animation.delay).as_secs_f64();
+        if local_time >= active_duration {
+            if !fills_forwards {
+                return None;
+            }
+            // An infinite animation without a duration ends immediately.
+            (if iteration_count.is_finite() { iteration_count } else { 1.0 }, false)
+        } else {
+            (local_time / duration, true)
+        }
+    };
+
+    let mut current_iteration = overall_progress.floor();
+    let mut progress = overall_progress - current_iteration;
+
+    // At the end of the active interval, the animation is at the end of its
+    // last iteration instead of at the start of the next.
+    if !is_running && progress == 0.0 && overall_progress > 0.0 {
+        current_iteration -= 1.0;
+        progress = 1.0;
+    }
+
+    let is_even_iteration = current_iteration % 2.0 == 0.0;
+    let is_reversed = match animation.direction {
+        CssAnimationDirection::Normal => false,
+        CssAnimationDirection::Reverse => true,
+        CssAnimationDirection::Alternate => !is_even_iteration,
+        CssAnimationDirection::AlternateReverse => is_even_iteration,
+    };
+
+    Some(AnimationSample {
+        progress: if is_reversed { 1.0 - progress } else { progress },
+        is_running,
+    })
+}
+
+/// Find the `@keyframes` rule with the given name, where later style sheets
+/// take precedence.
+fn find_keyframes<'a>(style_sheets: &'a [Stylesheet], name: &str) -> Option<&'a AtKeyframesRule> {
+    style_sheets.iter()
+        .rev()
+        .find_map(|stylesheet| stylesheet.keyframes(name))
+}
+
+/// Apply the values of the animated properties at the given progress, by
+/// interpolating between the keyframes around that progress. When the
+/// `0%` or `100%` keyframe of a property is missing, the underlying value
+/// of the property is used instead.
+///
+/// # References
+/// * [CSS Animations Level 1 § 3. Declaring Keyframes](https://drafts.csswg.org/css-animations/#keyframes)
+fn apply_keyframes(
+    keyframes: &AtKeyframesRule,
+    animation: &CssAnimation,
+    progress: CssDecimal,
+    underlying_style: &PropertyMap,
+    style: &mut PropertyMap,
+) {
+    for &property in INTERPOLATABLE_PROPERTIES {
+        let Some(underlying_value) = underlying_style.interpolatable_value(property) else {
+            continue;
+        };
+
+        let mut values: Vec<(CssDecimal, Value)> = Vec::new();
+        for keyframe in &keyframes.keyframes {
+            let is_specified = keyframe.declarations.iter()
+                .any(|declaration| animated_longhands(declaration.property()).contains(&property));
+            if !is_specified {
+                continue;
+            }
+
+            let mut keyframe_style = underlying_style.clone();
+            for declaration in &keyframe.declarations {
+                _ = keyframe_style.apply_property(declaration.property(), declaration.value().clone());
+            }
+
+            let Some(value) = keyframe_style.interpolatable_value(property) else { continue };
+            for &offset in &keyframe.offsets {
+                values.push((offset, value.clone()));
+            }
+        }
+
+        if values.is_empty() {
+            continue;
+        }
+
+        // The sort is stable, such that later keyframes with the same offset
+        // take precedence when searching from the end.
+        values.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        if values.first().is_none_or(|(offset, _)| *offset != 0.0) {
+            values.insert(0, (0.0, underlying_value.clone()));
+        }
+        if values.last().is_none_or(|(offset, _)| *offset != 1.0) {
+            values.push((1.0, underlying_value));
+        }
+
+        let end_index = values.iter()
+            .position(|(offset, _)| *offset > progress)
+            .unwrap_or(values.len() - 1)
+            .max(1);
+        let (start_offset, start_value) = &values[end_index - 1];
+        let (end_offset, end_value) = &values[end_index];
+
+        let interval = end_offset - start_offset;
+        let interval_progress = if interval <= 0.0 {
+            1.0
+        } else {
+            ((progress - start_offset) / interval).clamp(0.0, 1.0)
+        };
+
+        // TODO: `animation-timing-function` declarations inside of keyframes
+        //       should override the timing function of their interval.
+        let eased_progress = animation.timing_function.evaluate(interval_progress);
+
+        // Values that can't be interpolated flip halfway the interval.
+        let value = start_value.interpolate(end_value, eased_progress)
+            .unwrap_or_else(|| if eased_progress < 0.5 { start_value.clone() } else { end_value.clone() });
+
+        _ = style.apply_property(property, value);
+    }
+}
+
+/// The interpolatable longhands that are set by a declaration of the given
+/// property.
+///
+/// TODO: the logical properties assume a `horizontal-tb` writing mode.
+fn animated_longhands(property: Property) -> &'static [Property] {
+    match property {
+        Property::Margin => &[Property::MarginTop, Property::MarginRight, Property::MarginBottom, Property::MarginLeft],
+        Property::MarginBlock => &[Property::MarginTop, Property::MarginBottom],
+        Property::MarginBlockEnd => &[Property::MarginBottom],
+        Property::MarginBlockStart => &[Property::MarginTop],
+        Property::MarginInline => &[Property::MarginLeft, Property::MarginRight],
+        Property::MarginInlineEnd => &[Property::MarginRight],
+        Property::MarginInlineStart => &[Property::MarginLeft],
+        Property::Outline => &[Property::OutlineColor, Property::OutlineWidth],
+        Property::Padding => &[Property::PaddingTop, Property::PaddingRight, Property::PaddingBottom, Property::PaddingLeft],
+        Property::TextDecoration => &[Property::TextDecorationColor],
+        property => INTERPOLATABLE_PROPERTIES.iter()
+            .position(|interpolatable| *interpolatable == property)
+            .map_or(&[], |index| &INTERPOLATABLE_PROPERTIES[index..=index]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use retina_style::{CascadeOrigin, CssEasingFunction, CssOpacity};
+    use retina_style_parser::CssParsable;
+    use rstest::rstest;
+
+    const DURATION: Duration = Duration::from_millis(100);
+
+    fn animation(
+        iteration_count: CssDecimal,
+        direction: CssAnimationDirection,
+        fill_mode: CssAnimationFillMode,
+        delay: u64,
+    ) -> CssAnimation {
+        CssAnimation {
+            duration: DURATION,
+            timing_function: CssEasingFunction::Linear,
+            delay: Duration::from_millis(delay),
+            iteration_count: CssAnimationIterationCount::Count(iteration_count),
+            direction,
+            fill_mode,
+            ..Default::default()
+        }
+    }
+
+    fn assert_sample(actual: Option<AnimationSample>, expected: Option<(CssDecimal, bool)>) {
+        let actual = actual.map(|sample| (sample.progress, sample.is_running));
+        match (actual, expected) {
+            (Some((progress, is_running)), Some((expected_progress, expected_is_running))) => {
+                assert!((progress - expected_progress).abs() < 1e-6, "progress is {progress}, expected {expected_progress}");
+                assert_eq!(is_running, expected_is_running);
+            }
+            (actual, expected) => assert_eq!(actual, expected),
+        }
+    }
+
+    #[rstest]
+    #[case(1.0, 25, Some((0.25, true)))]
+    #[case(3.0, 150, Some((0.5, true)))]
+    #[case(3.0, 225, Some((0.25, true)))]
+    #[case(1.0, 100, None)]
+    #[case(0.0, 0, None)]
+    fn sample_iteration(#[case] iteration_count: CssDecimal, #[case] elapsed: u64, #[case] expected: Option<(CssDecimal, bool)>) {
+        let animation = animation(iteration_count, CssAnimationDirection::Normal, CssAnimationFillMode::None, 0);
+        assert_sample(sample_animation(&animation, Duration::from_millis(elapsed)), expected);
+    }
+
+    #[rstest]
+    #[case(CssAnimationDirection::Reverse, 25, 0.75)]
+    #[case(CssAnimationDirection::Reverse, 125, 0.75)]
+    #[case(CssAnimationDirection::Alternate, 25, 0.25)]
+    #[case(CssAnimationDirection::Alternate, 125, 0.75)]
+    #[case(CssAnimationDirection::Alternate, 225, 0.25)]
+    #[case(CssAnimationDirection::AlternateReverse, 25, 0.75)]
+    #[case(CssAnimationDirection::AlternateReverse, 125, 0.25)]
+    #[case(CssAnimationDirection::AlternateReverse, 225, 0.75)]
+    fn sample_direction(#[case] direction: CssAnimationDirection, #[case] elapsed: u64, #[case] expected: CssDecimal) {
+        let animation = animation(3.0, direction, CssAnimationFillMode::None, 0);
+        assert_sample(sample_animation(&animation, Duration::from_millis(elapsed)), Some((expected, true)));
+    }
+
+    #[rstest]
+    #[case(CssAnimationFillMode::None, None)]
+    #[case(CssAnimationFillMode::Forwards, None)]
+    #[case(CssAnimationFillMode::Backwards, Some((0.0, true)))]
+    #[case(CssAnimationFillMode::Both, Some((0.0, true)))]
+    fn sample_during_delay(#[case] fill_mode: CssAnimationFillMode, #[case] expected: Option<(CssDecimal, bool)>) {
+        let animation = animation(1.0, CssAnimationDirection::Normal, fill_mode, 50);
+        assert_sample(sample_animation(&animation, Duration::from_millis(25)), expected);
+    }
+
+    #[rstest]
+    #[case(CssAnimationFillMode::None, None)]
+    #[case(CssAnimationFillMode::Forwards, Some((1.0, false)))]
+    #[case(CssAnimationFillMode::Backwards, None)]
+    #[case(CssAnimationFillMode::Both, Some((1.0, false)))]
+    fn sample_after_active_interval(#[case] fill_mode: CssAnimationFillMode, #[case] expected: Option<(CssDecimal, bool)>) {
+        let animation = animation(1.0, CssAnimationDirection::Normal, fill_mode, 50);
+        assert_sample(sample_animation(&animation, Duration::from_millis(200)), expected);
+    }
+
+    /// At the end of the active interval, the animation is at the end of its
+    /// last iteration instead of at the start of the next one.
+    #[rstest]
+    #[case(2.0, CssAnimationDirection::Normal, 1.0)]
+    #[case(2.0, CssAnimationDirection::Alternate, 0.0)]
+    #[case(3.0, CssAnimationDirection::Alternate, 1.0)]
+    #[case(2.0, CssAnimationDirection::AlternateReverse, 1.0)]
+    #[case(1.5, CssAnimationDirection::Normal, 0.5)]
+    #[case(1.5, CssAnimationDirection::Alternate, 0.5)]
+    fn sample_end_of_active_interval(
+        #[case] iteration_count: CssDecimal,
+        #[case] direction: CssAnimationDirection,
+        #[case] expected: CssDecimal,
+    ) {
+        let animation = animation(iteration_count, direction, CssAnimationFillMode::Forwards, 0);
+        assert_sample(sample_animation(&animation, Duration::from_secs(1)), Some((expected, false)));
+    }
+
+    #[rstest]
+    #[case("50% { opacity: 0.5 }", 0.25, 0.75)]
+    #[case("50% { opacity: 0.5 }", 0.5, 0.5)]
+    #[case("50% { opacity: 0.5 }", 0.75, 0.75)]
+    #[case("from { opacity: 0 }", 0.5, 0.5)]
+    #[case("to { opacity: 0 }", 0.25, 0.75)]
+    #[case("from { opacity: 0 } to { opacity: 0.5 }", 0.5, 0.25)]
+    #[case("from { color: red }", 0.5, 1.0)]
+    fn apply_keyframes_with_underlying_value(#[case] keyframes: &str, #[case] progress: CssDecimal, #[case] expected: CssDecimal) {
+        let stylesheet = Stylesheet::parse(CascadeOrigin::Author, &format!("@keyframes a {{ {keyframes} }}"));
+        let keyframes = stylesheet.keyframes("a").expect("keyframes should be parsed");
+
+        let animation = animation(1.0, CssAnimationDirection::Normal, CssAnimationFillMode::None, 0);
+        let underlying_style = PropertyMap {
+            opacity: Some(CssOpacity(1.0)),
+            ..Default::default()
+        };
+        let mut style = underlying_style.clone();
+
+        apply_keyframes(keyframes, &animation, progress, &underlying_style, &mut style);
+
+        let opacity = style.opacity().0;
+        assert!((opacity - expected).abs() < 1e-6, "opacity is {opacity}, expected {expected}");
+    }
+}
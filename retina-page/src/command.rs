@@ -6,16 +6,43 @@ use retina_gfx::{
     MouseMoveEvent,
     MouseScrollDelta,
 };
+use retina_user_agent::permission::{Origin, PermissionName};
 
 /// The browser can send commands to the page that the page must act upon.
 #[derive(Clone, Debug, PartialEq)]
 pub enum PageCommand {
     Action(PageCommandAction),
 
+    /// Run the script in the browsing context of the page, e.g. for automation
+    /// or developer tools. The page responds with a
+    /// [`PageMessage::ScriptResult`](crate::PageMessage::ScriptResult)
+    /// carrying the same `id`.
+    ExecuteScript {
+        source: String,
+        id: u64,
+    },
+
+    /// Navigate to the URL of a hyperlink that was followed in this page.
+    /// When `no_referrer` is set, e.g. because of `rel="noreferrer"`, the
+    /// URL of this page isn't sent as the referrer.
+    FollowHyperlink {
+        url: String,
+        no_referrer: bool,
+    },
+
     MouseMove {
         event: MouseMoveEvent,
     },
 
+    /// Navigate to the URL on behalf of a script of this page, e.g. using
+    /// `location.assign()`. When `replace` is set, the current session
+    /// history entry is replaced instead of a new one being added. Only the
+    /// indicated part is scrolled to when just the fragment changes.
+    Navigate {
+        url: String,
+        replace: bool,
+    },
+
     OpenDomTreeView,
 
     OpenLayoutTreeView,
@@ -28,7 +55,20 @@ pub enum PageCommand {
     // Open the URL in this page.
     OpenUrl(String),
 
-    Reload,
+    /// Reload the current page. When `bypass_cache` is set, the document is
+    /// fetched without using cached responses.
+    Reload {
+        bypass_cache: bool,
+    },
+
+    /// Request permission for the origin to use a powerful feature. The user
+    /// is only prompted when they didn't answer before, after which their
+    /// answer is stored. The page responds with a
+    /// [`PageMessage::PermissionResult`](crate::PageMessage::PermissionResult).
+    RequestPermission {
+        origin: Origin,
+        name: PermissionName,
+    },
 
     ResizeCanvas {
         size: Size2D<u32, u32>,
@@ -43,6 +83,13 @@ pub enum PageCommand {
 pub enum PageCommandAction {
     Click,
     RightClick,
+
+    /// Move the focus to the next element in the tab order (`Tab`).
+    FocusNext,
+
+    /// Move the focus to the previous element in the tab order (`Shift+Tab`).
+    FocusPrevious,
+
     PageUp,
     PageDown,
     ScrollToTop,
@@ -3,7 +3,6 @@
 
 use std::sync::{mpsc::SyncSender, Arc};
 
-use log::warn;
 use retina_dom::{HtmlElementKind, Node};
 use retina_gfx::{
     CursorIcon,
@@ -17,7 +16,14 @@ use retina_style::CssCursor;
 use tokio::sync::mpsc::Sender;
 use url::Url;
 
-use crate::{PageMessage, message::PageTaskMessage, PageCommand, scroller::Scroller};
+use crate::{
+    PageMessage,
+    form_submission,
+    hyperlink::{self, HyperlinkNavigation},
+    message::PageTaskMessage,
+    PageCommand,
+    scroller::Scroller,
+};
 
 #[derive(Debug)]
 pub(crate) struct CursorState {
@@ -27,6 +33,10 @@ pub(crate) struct CursorState {
     page_message_sender: SyncSender<PageMessage>,
     task_sender: Sender<PageTaskMessage>,
     node: Option<Node>,
+
+    /// The elements the cursor is hovering over, i.e. the elements matched by
+    /// `:hover`, from the outermost to the innermost.
+    hovered: Vec<Node>,
 }
 
 impl CursorState {
@@ -41,6 +51,7 @@ impl CursorState {
             page_message_sender,
             task_sender,
             node: None,
+            hovered: Vec::new(),
         }
     }
 
@@ -48,23 +59,38 @@ impl CursorState {
         let Some(mut node) = self.node.clone() else { return };
 
         loop {
-            if let Some(element) = node.as_dom_element() {
-                if element.qualified_name().local.as_ref().eq_ignore_ascii_case("a") {
-                    if let Some(href) = element.attributes().find_by_str("href") {
-                        match Url::options().base_url(Some(current_url)).parse(href) {
-                            Ok(url) => {
-                                _ = self.task_sender.send(PageTaskMessage::Command {
-                                    command: PageCommand::OpenUrl(url.to_string()),
-                                }).await.ok();
-                            }
-                            Err(e) => {
-                                warn!("Invalid anchor hyper reference \"{href}\": {e}");
-                            }
-                        }
+            if form_submission::is_submit_button(&node) {
+                if let Some(form) = form_submission::find_form_owner(&node) {
+                    _ = self.task_sender.send(PageTaskMessage::SubmitForm {
+                        form,
+                        submitter: node,
+                    }).await.ok();
+                }
+
+                return;
+            }
+
+            if let Some(HtmlElementKind::Anchor(anchor)) = node.as_html_element_kind() {
+                match hyperlink::follow_hyperlink(anchor, current_url) {
+                    Some(HyperlinkNavigation::CurrentPage { url, no_referrer }) => {
+                        _ = self.task_sender.send(PageTaskMessage::Command {
+                            command: PageCommand::FollowHyperlink {
+                                url: url.to_string(),
+                                no_referrer,
+                            },
+                        }).await.ok();
+                    }
+
+                    Some(HyperlinkNavigation::NewPage { url }) => {
+                        _ = self.page_message_sender.send(PageMessage::OpenNewPage {
+                            url: url.to_string(),
+                        }).ok();
                     }
 
-                    return;
+                    None => (),
                 }
+
+                return;
             }
 
             let Some(parent) = node.as_node().parent() else { break };
@@ -208,6 +234,12 @@ impl CursorState {
         }
     }
 
+    /// Get the position of the cursor in the page, i.e. relative to the top
+    /// left corner of the page instead of the viewport.
+    pub fn page_position(&self, scroller: &Scroller) -> Point2D<f64, UnknownUnit> {
+        scroller.viewport_position() + self.mouse_position.to_vector()
+    }
+
     pub async fn evaluate_move(
         &mut self,
         mouse_move_event: MouseMoveEvent,
@@ -230,7 +262,8 @@ impl CursorState {
             self.scroll_position.y + self.mouse_position.y
         );
 
-        let hit_stack = hit_test(position, layout_root);
+        let hit_stack = hit_test(position, self.mouse_position, layout_root);
+        self.update_hovered_elements(&hit_stack).await;
 
         match hit_stack.last() {
             Some(layout_box) => {
@@ -243,6 +276,57 @@ impl CursorState {
         }
     }
 
+    /// Records the elements of the boxes that are hit as hovered, and requests
+    /// the styles to be recomputed when that set changed, since `:hover`
+    /// selectors might match differently.
+    ///
+    /// # References
+    /// * [CSS - Selectors Level 4 - 9.2](https://drafts.csswg.org/selectors/#the-hover-pseudo)
+    async fn update_hovered_elements(&mut self, hit_stack: &[&LayoutBox]) {
+        let mut hovered: Vec<Node> = Vec::with_capacity(hit_stack.len());
+        for layout_box in hit_stack {
+            let node = &layout_box.node;
+            if !node.is_element() {
+                continue;
+            }
+
+            if hovered.last().is_some_and(|last| Node::ptr_eq(last, node)) {
+                continue;
+            }
+
+            hovered.push(node.clone());
+        }
+
+        let unchanged = hovered.len() == self.hovered.len()
+            && hovered.iter().zip(&self.hovered).all(|(a, b)| Node::ptr_eq(a, b));
+        if unchanged {
+            return;
+        }
+
+        for node in &self.hovered {
+            if let Some(element) = node.as_dom_element() {
+                element.set_hovered(false);
+            }
+        }
+
+        for node in &hovered {
+            if let Some(element) = node.as_dom_element() {
+                element.set_hovered(true);
+            }
+        }
+
+        // Only the elements that started or stopped being hovered changed.
+        let contains = |nodes: &[Node], node: &Node| nodes.iter().any(|candidate| Node::ptr_eq(candidate, node));
+        let elements = self.hovered.iter()
+            .filter(|node| !contains(&hovered, node))
+            .chain(hovered.iter().filter(|node| !contains(&self.hovered, node)))
+            .cloned()
+            .collect();
+
+        self.hovered = hovered;
+        _ = self.task_sender.send(PageTaskMessage::ElementStateChanged { elements }).await.ok();
+    }
+
     async fn set_cursor(&mut self, cursor: CursorIcon) {
         if self.cursor == cursor {
             return;
@@ -299,16 +383,34 @@ fn convert_cursor_type(cursor: CssCursor, layout_box: &LayoutBox) -> CursorIcon
     CursorIcon::Winit(winit_cursor)
 }
 
-fn hit_test<U>(position: Point2D<f64, U>, layout_root: Option<&LayoutBox>) -> Vec<&LayoutBox> {
-    _ = position;
-    _ = layout_root;
+/// Find the boxes under the cursor, from the root to the innermost box. The
+/// `position` is relative to the page, and the `viewport_position` is relative
+/// to the viewport, against which `position: fixed` boxes are positioned.
+fn hit_test<U>(
+    position: Point2D<f64, U>,
+    viewport_position: Point2D<f64, U>,
+    layout_root: Option<&LayoutBox>,
+) -> Vec<&LayoutBox> {
     let mut hit_stack = Vec::new();
 
-    match layout_root {
-        Some(layout_box) => hit_test_impl(position, layout_box, &mut hit_stack),
-        None => (),
+    let Some(layout_root) = layout_root else {
+        return hit_stack;
+    };
+
+    // The fixed boxes are painted over the page, with the last one on top.
+    for fixed_box in layout_root.fixed_boxes().iter().rev() {
+        let border_edge = fixed_box.dimensions()
+            .rect_border_box()
+            .to_box2d();
+
+        if border_edge.contains(viewport_position.cast_unit()) {
+            hit_stack.push(layout_root);
+            hit_test_impl(viewport_position, fixed_box, &mut hit_stack);
+            return hit_stack;
+        }
     }
 
+    hit_test_impl(position, layout_root, &mut hit_stack);
     hit_stack
 }
 
@@ -319,6 +421,10 @@ fn hit_test_impl<'boxes, U>(
 ) {
     hit_stack.push(layout_box);
 
+    // The descendants of a scroll container are painted shifted by the scroll
+    // offset, so the position must be shifted in the opposite direction.
+    let position = position + layout_box.scroll_offset().cast_unit();
+
     for child in layout_box.children() {
         // <https://drafts.csswg.org/css-ui/#cursor>
         // This property specifies the type of cursor to be displayed for the
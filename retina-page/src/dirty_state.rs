@@ -17,6 +17,10 @@ const MAXIMUM_DELAY_BETWEEN_CLEANSING_WITHOUT_TIMEOUT: Duration = Duration::from
 pub(crate) struct DirtyState {
     phase: DirtyPhase,
     last_update: Option<Instant>,
+
+    /// Whether the styles of all elements must be recomputed, instead of only
+    /// those invalidated in the [`DependencyMap`][retina_style_computation::DependencyMap].
+    restyle_all: bool,
 }
 
 impl DirtyState {
@@ -24,6 +28,7 @@ impl DirtyState {
         Self {
             phase: DirtyPhase::GenerateLayoutTree,
             last_update: None,
+            restyle_all: true,
         }
     }
 
@@ -53,6 +58,43 @@ impl DirtyState {
         }
     }
 
+    /// The computed values of CSS properties of any element might have
+    /// changed, so the cascade, layout and painting must all be redone.
+    #[inline]
+    pub(crate) fn request_restyle(&mut self) {
+        self.restyle_all = true;
+        self.request(DirtyPhase::GenerateLayoutTree);
+    }
+
+    /// Only the computed values of the elements that were invalidated in the
+    /// [`DependencyMap`][retina_style_computation::DependencyMap] (and those
+    /// inheriting from them) might have changed, so only these have to be
+    /// cascaded again, after which the layout and painting must be redone.
+    #[inline]
+    pub(crate) fn request_incremental_restyle(&mut self) {
+        self.request(DirtyPhase::GenerateLayoutTree);
+    }
+
+    /// The dimensions of boxes might have changed, but the computed values
+    /// are still valid, so only the layout and painting must be redone.
+    #[inline]
+    pub(crate) fn request_layout(&mut self) {
+        self.request(DirtyPhase::Layout);
+    }
+
+    /// Only visual properties (e.g. `background-color`) changed, so the
+    /// layout is still valid and only the page has to be repainted.
+    #[inline]
+    pub(crate) fn request_paint(&mut self) {
+        self.request(DirtyPhase::Paint);
+    }
+
+    /// Returns whether the styles of all elements must be recomputed, and
+    /// resets that request.
+    pub(crate) fn take_restyle_all(&mut self) -> bool {
+        std::mem::take(&mut self.restyle_all)
+    }
+
     pub(crate) fn mark_layout_tree_generated(&mut self) {
         self.phase = DirtyPhase::Paint;
     }
@@ -73,11 +115,16 @@ impl DirtyState {
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) enum DirtyPhase {
+    /// Nothing has to be done.
     Ready,
 
+    /// Repaint using the existing layout tree.
     Paint,
 
+    /// Rerun the layout of the existing layout tree, and repaint.
     Layout,
 
+    /// Recompute the styles by regenerating the layout tree, then lay out and
+    /// repaint.
     GenerateLayoutTree,
 }
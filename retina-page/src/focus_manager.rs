@@ -0,0 +1,255 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use log::info;
+use retina_dom::{Element, Node};
+
+/// The direction in which the focus moves through the sequential focus
+/// navigation order.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FocusDirection {
+    /// `Tab`
+    Forward,
+
+    /// `Shift + Tab`
+    Backward,
+}
+
+/// Keeps track of the focused element of the page.
+///
+/// ## References
+/// * [HTML - Focus](https://html.spec.whatwg.org/multipage/interaction.html#focus)
+#[derive(Debug, Default)]
+pub(crate) struct FocusManager {
+    focused: Option<Node>,
+}
+
+impl FocusManager {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the element that is focused, if any.
+    pub(crate) fn focused(&self) -> Option<&Node> {
+        self.focused.as_ref()
+    }
+
+    /// Move the focus to the next or previous element in the tab order,
+    /// wrapping around at the ends. Returns the newly focused element, if
+    /// any.
+    pub(crate) fn advance_focus(&mut self, document: &Node, direction: FocusDirection) -> Option<&Node> {
+        let tab_order = self.build_tab_order(document);
+        if tab_order.is_empty() {
+            self.clear();
+            return None;
+        }
+
+        let current = self.focused.as_ref()
+            .and_then(|focused| tab_order.iter().position(|node| Node::ptr_eq(node, focused)));
+
+        let index = match (current, direction) {
+            (None, FocusDirection::Forward) => 0,
+            (None, FocusDirection::Backward) => tab_order.len() - 1,
+            (Some(index), FocusDirection::Forward) => (index + 1) % tab_order.len(),
+            (Some(index), FocusDirection::Backward) => (index + tab_order.len() - 1) % tab_order.len(),
+        };
+
+        let node = tab_order[index].clone();
+        if let Some(element) = node.as_dom_element() {
+            info!("[focus] Focused <{}>", element.qualified_name().local);
+        }
+
+        self.clear();
+        if let Some(element) = node.as_dom_element() {
+            element.set_focused(true);
+        }
+
+        self.focused = Some(node);
+        self.focused.as_ref()
+    }
+
+    /// Discard the focused element, e.g. when a new document is loaded.
+    pub(crate) fn clear(&mut self) {
+        let Some(focused) = self.focused.take() else { return };
+        if let Some(element) = focused.as_dom_element() {
+            element.set_focused(false);
+        }
+    }
+
+    /// Collect the elements of the document in sequential focus navigation
+    /// order. Elements with a positive `tabindex` come first, in ascending
+    /// order of their `tabindex`, followed by the elements with a `tabindex`
+    /// of zero and the elements that are focusable by default. Elements with a
+    /// negative `tabindex` can only be focused programmatically, and are
+    /// therefore not part of the tab order.
+    ///
+    /// ## References
+    /// * [HTML - The tabindex attribute](https://html.spec.whatwg.org/multipage/interaction.html#the-tabindex-attribute)
+    pub(crate) fn build_tab_order(&self, document: &Node) -> Vec<Node> {
+        let mut positive = Vec::new();
+        let mut natural = Vec::new();
+
+        document.for_each_child_node_recursive_handle(&mut |node| {
+            let Some(element) = node.as_dom_element() else { return };
+
+            let tab_index = element.attributes()
+                .find_by_str("tabindex")
+                .and_then(parse_tab_index);
+
+            match tab_index {
+                Some(tab_index) if tab_index > 0 => positive.push((tab_index, node.clone())),
+                Some(0) => natural.push(node.clone()),
+                Some(_) => (),
+                None => if is_focusable_by_default(element) {
+                    natural.push(node.clone());
+                }
+            }
+        });
+
+        // This sort is stable, so elements with the same `tabindex` keep their
+        // tree order.
+        positive.sort_by_key(|(tab_index, _)| *tab_index);
+
+        positive.into_iter()
+            .map(|(_, node)| node)
+            .chain(natural)
+            .collect()
+    }
+}
+
+/// Elements that are focusable without a `tabindex` attribute.
+///
+/// ## References
+/// * [HTML - The tabindex attribute](https://html.spec.whatwg.org/multipage/interaction.html#the-tabindex-attribute)
+fn is_focusable_by_default(element: &Element) -> bool {
+    let attributes = element.attributes();
+    let name = element.qualified_name().local.as_ref();
+
+    if name.eq_ignore_ascii_case("a") {
+        return attributes.find_by_str("href").is_some();
+    }
+
+    let is_form_control = ["button", "input", "select", "textarea"]
+        .iter()
+        .any(|control| name.eq_ignore_ascii_case(control));
+
+    if !is_form_control || attributes.find_by_str("disabled").is_some() {
+        return false;
+    }
+
+    if name.eq_ignore_ascii_case("input") {
+        if let Some(ty) = attributes.find_by_str("type") {
+            return !ty.trim().eq_ignore_ascii_case("hidden");
+        }
+    }
+
+    true
+}
+
+/// Parse the `tabindex` attribute value.
+///
+/// ## References
+/// * [HTML - Rules for parsing integers](https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#rules-for-parsing-integers)
+fn parse_tab_index(value: &str) -> Option<i32> {
+    let value = value.trim_start_matches(|c: char| c.is_ascii_whitespace());
+
+    let (negative, value) = match value.as_bytes().first()? {
+        b'-' => (true, &value[1..]),
+        b'+' => (false, &value[1..]),
+        _ => (false, value),
+    };
+
+    let digits = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len());
+    let value: i32 = value[..digits].parse().ok()?;
+
+    Some(if negative { -value } else { value })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    fn ids(nodes: &[Node]) -> Vec<String> {
+        nodes.iter()
+            .map(|node| node.as_dom_element().unwrap().id().to_string())
+            .collect()
+    }
+
+    fn focused_id(manager: &FocusManager) -> Option<String> {
+        manager.focused().map(|node| node.as_dom_element().unwrap().id().to_string())
+    }
+
+    #[test]
+    fn tab_order() {
+        let document = retina_dom::Parser::parse(r#"
+            <div id="plain"></div>
+            <a id="link" href="/"></a>
+            <a id="anchor"></a>
+            <div id="second" tabindex="2"></div>
+            <button id="button"></button>
+            <button id="disabled" disabled></button>
+            <input id="hidden" type="hidden">
+            <input id="text">
+            <div id="zero" tabindex="0"></div>
+            <button id="negative" tabindex="-1"></button>
+            <div id="first" tabindex="1"></div>
+            <div id="second-too" tabindex=" 2"></div>
+        "#);
+
+        let order = FocusManager::new().build_tab_order(&document);
+        assert_eq!(
+            ids(&order),
+            ["first", "second", "second-too", "link", "button", "text", "zero"],
+        );
+    }
+
+    #[test]
+    fn advance_focus_wraps_around() {
+        let document = retina_dom::Parser::parse(r#"
+            <button id="a"></button>
+            <button id="b"></button>
+            <button id="c"></button>
+        "#);
+
+        let mut manager = FocusManager::new();
+        let mut forward = Vec::new();
+        for _ in 0..4 {
+            manager.advance_focus(&document, FocusDirection::Forward);
+            forward.push(focused_id(&manager).unwrap());
+        }
+        assert_eq!(forward, ["a", "b", "c", "a"]);
+
+        manager.clear();
+        let mut backward = Vec::new();
+        for _ in 0..4 {
+            manager.advance_focus(&document, FocusDirection::Backward);
+            backward.push(focused_id(&manager).unwrap());
+        }
+        assert_eq!(backward, ["c", "b", "a", "c"]);
+    }
+
+    #[test]
+    fn advance_focus_without_focusable_elements() {
+        let document = retina_dom::Parser::parse("<div></div>");
+
+        let mut manager = FocusManager::new();
+        assert!(manager.advance_focus(&document, FocusDirection::Forward).is_none());
+        assert_eq!(focused_id(&manager), None);
+    }
+
+    #[rstest]
+    #[case("1", Some(1))]
+    #[case("  3", Some(3))]
+    #[case("+4", Some(4))]
+    #[case("-1", Some(-1))]
+    #[case("0", Some(0))]
+    #[case("12abc", Some(12))]
+    #[case("", None)]
+    #[case("abc", None)]
+    #[case("-", None)]
+    fn tab_index(#[case] value: &str, #[case] expected: Option<i32>) {
+        assert_eq!(parse_tab_index(value), expected);
+    }
+}
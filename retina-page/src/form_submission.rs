@@ -0,0 +1,412 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! The [form submission algorithm][spec], which converts the controls of a
+//! `<form>` into the [`Request`] that navigates the page.
+//!
+//! [spec]: https://html.spec.whatwg.org/multipage/form-control-infrastructure.html#form-submission-algorithm
+
+use log::{info, warn};
+use retina_dom::{
+    FormEnctype,
+    FormMethod,
+    HtmlElementKind,
+    HtmlFormElement,
+    InputType,
+    Node,
+};
+use retina_fetch::{
+    FormData,
+    FormDataEntryValue,
+    Request,
+    RequestBody,
+    RequestReferrer,
+    mime,
+};
+use url::{form_urlencoded, Url};
+
+/// Find the [form owner][spec] of the control, which is the nearest `<form>`
+/// ancestor.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/form-control-infrastructure.html#form-owner
+pub(crate) fn find_form_owner(control: &Node) -> Option<Node> {
+    let mut node = control.clone();
+    loop {
+        let parent = node.as_node().parent()?.upgrade()?;
+        node = Node::from(parent);
+
+        if node.as_html_element_kind().is_some_and(|element| element.as_form_element().is_some()) {
+            return Some(node);
+        }
+    }
+}
+
+/// Whether the element is a [submit button][spec], i.e. activating it submits
+/// its form.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/forms.html#concept-submit-button
+pub(crate) fn is_submit_button(node: &Node) -> bool {
+    match node.as_html_element_kind() {
+        Some(HtmlElementKind::Button(button)) => button.is_submit_button() && !button.is_disabled(),
+        Some(HtmlElementKind::Input(input)) => {
+            matches!(input.input_type(), InputType::Submit | InputType::Image) && !input.is_disabled()
+        }
+        _ => false,
+    }
+}
+
+/// Run the form submission algorithm for the `form` submitted by the
+/// `submitter`, returning the request to navigate to, or [`None`] if the form
+/// can't be submitted, e.g. because a control is invalid.
+pub(crate) fn create_form_submission_request(
+    form: &Node,
+    submitter: &Node,
+    document_url: &Url,
+    base_url: &Url,
+) -> Option<Request> {
+    let form = form.as_html_element_kind()?.as_form_element()?;
+
+    let no_validate = form.no_validate() || submitter.as_dom_element()
+        .is_some_and(|element| element.attributes().find_by_str("formnovalidate").is_some());
+
+    if !no_validate && !validate_controls(form) {
+        info!("[form] Not submitting the form, since some of its controls are invalid");
+        return None;
+    }
+
+    let form_data = construct_entry_list(form, submitter);
+
+    // If the action is the empty string, the URL of the document is used.
+    let mut url = if form.action().is_empty() {
+        document_url.clone()
+    } else {
+        match Url::options().base_url(Some(base_url)).parse(form.action()) {
+            Ok(url) => url,
+            Err(e) => {
+                warn!("[form] Invalid action URL \"{}\": {e}", form.action());
+                return None;
+            }
+        }
+    };
+
+    let referrer = RequestReferrer::Url(document_url.clone());
+
+    match form.method() {
+        FormMethod::Get => {
+            // Mutate action URL
+            url.set_query(Some(&encode_url_encoded(&form_data)));
+            Some(Request::get_document(url, referrer))
+        }
+
+        FormMethod::Post => {
+            // Submit as entity body
+            let body = match form.enctype() {
+                FormEnctype::UrlEncoded => RequestBody::Bytes {
+                    content_type: mime::APPLICATION_WWW_FORM_URLENCODED,
+                    bytes: encode_url_encoded(&form_data).into_bytes(),
+                },
+                FormEnctype::Multipart => RequestBody::FormData(form_data),
+                FormEnctype::TextPlain => RequestBody::Bytes {
+                    content_type: mime::TEXT_PLAIN_UTF_8,
+                    bytes: encode_text_plain(&form_data).into_bytes(),
+                },
+            };
+
+            let mut request = Request::get_document(url, referrer);
+            request.set_method(retina_fetch::Method::POST);
+            request.set_body(body);
+            Some(request)
+        }
+
+        FormMethod::Dialog => {
+            warn!("[form] Submitting to a <dialog> isn't supported yet");
+            None
+        }
+    }
+}
+
+/// [Interactively validate the constraints][spec] of the controls, which
+/// currently only reports the invalid controls in the log.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/form-control-infrastructure.html#interactively-validate-the-constraints
+fn validate_controls(form: &HtmlFormElement) -> bool {
+    let mut valid = true;
+
+    for control in form.controls() {
+        let Some(HtmlElementKind::Input(input)) = control.as_html_element_kind() else {
+            continue;
+        };
+
+        if input.is_value_missing() {
+            warn!("[form] The input \"{}\" is required", input.name());
+            valid = false;
+        } else if !input.is_valid() {
+            warn!("[form] The value of input \"{}\" doesn't match its pattern", input.name());
+            valid = false;
+        }
+    }
+
+    valid
+}
+
+/// [Construct the entry list][spec] of the form, i.e. the names and values of
+/// the controls that are submitted.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/form-control-infrastructure.html#constructing-the-form-data-set
+fn construct_entry_list(form: &HtmlFormElement, submitter: &Node) -> FormData {
+    let mut form_data = FormData::new();
+
+    for control in form.controls() {
+        match control.as_html_element_kind() {
+            // Only the button that submitted the form is included.
+            Some(HtmlElementKind::Button(button)) => {
+                let is_submitter = Node::ptr_eq(&control, submitter);
+                if is_submitter && !button.is_disabled() && !button.name().is_empty() {
+                    form_data.append(button.name(), button.value());
+                }
+            }
+
+            Some(HtmlElementKind::Input(input)) => {
+                if input.is_disabled() || input.name().is_empty() {
+                    continue;
+                }
+
+                let ty = input.input_type();
+                if ty.is_button() && !Node::ptr_eq(&control, submitter) {
+                    continue;
+                }
+
+                if ty.is_checkable() && !input.is_checked() {
+                    continue;
+                }
+
+                match ty {
+                    // The value of a checkbox or radio button without a
+                    // `value` attribute is "on".
+                    InputType::Checkbox | InputType::Radio => {
+                        let value = control.as_dom_element()
+                            .and_then(|element| element.attributes().find_by_str("value"))
+                            .unwrap_or("on");
+                        form_data.append(input.name(), value);
+                    }
+
+                    // TODO: the coordinates of the click.
+                    InputType::Image => (),
+
+                    // Selecting files isn't supported yet.
+                    InputType::File => (),
+
+                    _ => form_data.append(input.name(), input.value()),
+                }
+            }
+
+            _ => (),
+        }
+    }
+
+    form_data
+}
+
+/// Serialize the entries using the [`application/x-www-form-urlencoded`
+/// serializer][spec], where files are replaced by their names.
+///
+/// [spec]: https://url.spec.whatwg.org/#concept-urlencoded-serializer
+fn encode_url_encoded(form_data: &FormData) -> String {
+    let mut serializer = form_urlencoded::Serializer::new(String::new());
+    for (name, value) in form_data.entries() {
+        serializer.append_pair(name, entry_value_as_str(value));
+    }
+    serializer.finish()
+}
+
+/// Serialize the entries using the [`text/plain` encoding algorithm][spec].
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/form-control-infrastructure.html#text/plain-encoding-algorithm
+fn encode_text_plain(form_data: &FormData) -> String {
+    let mut result = String::new();
+    for (name, value) in form_data.entries() {
+        result.push_str(name);
+        result.push('=');
+        result.push_str(entry_value_as_str(value));
+        result.push_str("\r\n");
+    }
+    result
+}
+
+fn entry_value_as_str(value: &FormDataEntryValue) -> &str {
+    match value {
+        FormDataEntryValue::String(value) => value,
+        FormDataEntryValue::File { file_name, .. } => file_name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn find_by_id(document: &Node, id: &str) -> Node {
+        let mut found = None;
+        document.for_each_child_node_recursive_handle(&mut |node| {
+            if found.is_none() && node.as_dom_element().is_some_and(|element| element.id() == id) {
+                found = Some(node.clone());
+            }
+        });
+        found.unwrap_or_else(|| panic!("no element with id \"{id}\""))
+    }
+
+    fn submit(html: &str) -> Option<Request> {
+        let document = retina_dom::Parser::parse(html);
+        let form = find_by_id(&document, "form");
+        let submitter = find_by_id(&document, "submitter");
+        let document_url = Url::parse("https://example.com/page?old=1").unwrap();
+        create_form_submission_request(&form, &submitter, &document_url, &document_url)
+    }
+
+    fn entries(form_data: &FormData) -> Vec<(&str, &str)> {
+        form_data.entries()
+            .iter()
+            .map(|(name, value)| (name.as_str(), entry_value_as_str(value)))
+            .collect()
+    }
+
+    #[test]
+    fn url_encoded() {
+        let mut form_data = FormData::new();
+        form_data.append("name", "John Doe");
+        form_data.append("q", "a&b=c");
+        form_data.append("accent", "é");
+        form_data.append_file("file", "photo.png", mime::IMAGE_PNG, vec![1, 2, 3]);
+
+        assert_eq!(
+            encode_url_encoded(&form_data),
+            "name=John+Doe&q=a%26b%3Dc&accent=%C3%A9&file=photo.png",
+        );
+    }
+
+    #[test]
+    fn text_plain() {
+        let mut form_data = FormData::new();
+        form_data.append("a", "1");
+        form_data.append("b", "x y");
+
+        assert_eq!(encode_text_plain(&form_data), "a=1\r\nb=x y\r\n");
+    }
+
+    #[test]
+    fn entry_list() {
+        let document = retina_dom::Parser::parse(r#"
+            <form id="form">
+                <input name="text" value="hello">
+                <input value="nameless">
+                <input name="disabled" value="no" disabled>
+                <input type="hidden" name="hidden" value="secret">
+                <input type="checkbox" name="unchecked" value="no">
+                <input type="checkbox" name="checked" checked>
+                <input type="radio" name="radio" value="b" checked>
+                <button name="other" value="no">Other</button>
+                <button id="submitter" name="action" value="save">Save</button>
+            </form>
+        "#);
+
+        let form = find_by_id(&document, "form");
+        let submitter = find_by_id(&document, "submitter");
+        let form = form.as_html_element_kind().unwrap().as_form_element().unwrap();
+
+        assert_eq!(
+            entries(&construct_entry_list(form, &submitter)),
+            [
+                ("text", "hello"),
+                ("hidden", "secret"),
+                ("checked", "on"),
+                ("radio", "b"),
+                ("action", "save"),
+            ],
+        );
+    }
+
+    #[test]
+    fn get_replaces_query() {
+        let request = submit(r#"
+            <form id="form" action="/search?old=1">
+                <input name="q" value="retina browser">
+                <button id="submitter">Search</button>
+            </form>
+        "#).unwrap();
+
+        assert_eq!(request.method(), &retina_fetch::Method::GET);
+        assert_eq!(request.url().as_str(), "https://example.com/search?q=retina+browser");
+        assert_eq!(request.body(), None);
+    }
+
+    #[test]
+    fn get_without_action() {
+        let request = submit(r#"
+            <form id="form">
+                <input name="q" value="x">
+                <button id="submitter">Search</button>
+            </form>
+        "#).unwrap();
+
+        assert_eq!(request.url().as_str(), "https://example.com/page?q=x");
+    }
+
+    #[test]
+    fn post_url_encoded() {
+        let request = submit(r#"
+            <form id="form" method="post" action="submit">
+                <input name="a" value="1 2">
+                <input type="submit" id="submitter" name="go" value="Go">
+            </form>
+        "#).unwrap();
+
+        assert_eq!(request.method(), &retina_fetch::Method::POST);
+        assert_eq!(request.url().as_str(), "https://example.com/submit");
+        assert_eq!(request.body(), Some(&RequestBody::Bytes {
+            content_type: mime::APPLICATION_WWW_FORM_URLENCODED,
+            bytes: b"a=1+2&go=Go".to_vec(),
+        }));
+    }
+
+    #[test]
+    fn post_text_plain() {
+        let request = submit(r#"
+            <form id="form" method="post" enctype="text/plain">
+                <input name="a" value="1 2">
+                <button id="submitter">Send</button>
+            </form>
+        "#).unwrap();
+
+        assert_eq!(request.body(), Some(&RequestBody::Bytes {
+            content_type: mime::TEXT_PLAIN_UTF_8,
+            bytes: b"a=1 2\r\n".to_vec(),
+        }));
+    }
+
+    #[test]
+    fn post_multipart() {
+        let request = submit(r#"
+            <form id="form" method="post" enctype="multipart/form-data">
+                <input name="a" value="1">
+                <button id="submitter">Send</button>
+            </form>
+        "#).unwrap();
+
+        let mut form_data = FormData::new();
+        form_data.append("a", "1");
+        assert_eq!(request.body(), Some(&RequestBody::FormData(form_data)));
+    }
+
+    #[test]
+    fn invalid_controls_block_submission() {
+        let html = |attributes: &str| format!(r#"
+            <form id="form" {attributes}>
+                <input name="a" required>
+                <button id="submitter">Send</button>
+            </form>
+        "#);
+
+        assert!(submit(&html("")).is_none());
+        assert!(submit(&html("novalidate")).is_some());
+    }
+}
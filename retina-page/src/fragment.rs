@@ -0,0 +1,99 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! [Navigating to a fragment][spec], which scrolls to the element indicated
+//! by the fragment of the URL, without loading the document again.
+//!
+//! [spec]: https://html.spec.whatwg.org/multipage/browsing-the-web.html#navigate-fragid
+
+use percent_encoding::percent_decode_str;
+use retina_dom::Node;
+use retina_layout::LayoutBox;
+use url::Url;
+
+/// The [indicated part][spec] of the document, which is scrolled to.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/browsing-the-web.html#the-indicated-part-of-the-document
+#[derive(Clone, Debug)]
+pub(crate) enum IndicatedPart {
+    TopOfDocument,
+    Element(Node),
+}
+
+/// [Select the indicated part](https://html.spec.whatwg.org/multipage/browsing-the-web.html#select-the-indicated-part)
+/// of the `document` for the fragment of `url`.
+pub(crate) fn select_indicated_part(document: &Node, url: &Url) -> Option<IndicatedPart> {
+    // 2. Let fragment be document's URL's fragment.
+    let fragment = url.fragment()?;
+
+    // 3. If fragment is the empty string, then return the special value top
+    //    of the document.
+    if fragment.is_empty() {
+        return Some(IndicatedPart::TopOfDocument);
+    }
+
+    // 4. Let potentialIndicatedElement be the result of finding a potential
+    //    indicated element given document and fragment.
+    // 5. If potentialIndicatedElement is not null, then return
+    //    potentialIndicatedElement.
+    if let Some(element) = find_potential_indicated_element(document, fragment) {
+        return Some(IndicatedPart::Element(element));
+    }
+
+    // 6. Let fragmentBytes be the result of percent-decoding fragment.
+    // 7. Let decodedFragment be the result of running UTF-8 decode without
+    //    BOM on fragmentBytes.
+    let decoded_fragment = percent_decode_str(fragment).decode_utf8_lossy();
+
+    // 8. Set potentialIndicatedElement to the result of finding a potential
+    //    indicated element given document and decodedFragment.
+    // 9. If potentialIndicatedElement is not null, then return
+    //    potentialIndicatedElement.
+    if let Some(element) = find_potential_indicated_element(document, &decoded_fragment) {
+        return Some(IndicatedPart::Element(element));
+    }
+
+    // 10. If decodedFragment is an ASCII case-insensitive match for the
+    //     string top, then return the top of the document.
+    if decoded_fragment.eq_ignore_ascii_case("top") {
+        return Some(IndicatedPart::TopOfDocument);
+    }
+
+    // 11. Return null.
+    None
+}
+
+/// [Find a potential indicated element](https://html.spec.whatwg.org/multipage/browsing-the-web.html#find-a-potential-indicated-element),
+/// which is the element with the ID `fragment`, or the first `<a>` element
+/// with the name `fragment`.
+fn find_potential_indicated_element(document: &Node, fragment: &str) -> Option<Node> {
+    if let Some(element) = document.as_parent_node().and_then(|parent| parent.get_element_by_id(fragment)) {
+        return Some(element);
+    }
+
+    let mut anchor = None;
+    document.for_each_child_node_recursive_handle(&mut |node| {
+        if anchor.is_some() {
+            return;
+        }
+
+        let Some(element) = node.as_dom_element() else { return };
+        if element.qualified_name().local.as_ref() == "a"
+                && element.attributes().find_by_str("name") == Some(fragment) {
+            anchor = Some(node.clone());
+        }
+    });
+    anchor
+}
+
+/// Find the first box that was generated for the `node`.
+pub(crate) fn find_layout_box<'layout>(layout_box: &'layout LayoutBox, node: &Node) -> Option<&'layout LayoutBox> {
+    if Node::ptr_eq(&layout_box.node, node) {
+        return Some(layout_box);
+    }
+
+    layout_box.children()
+        .iter()
+        .find_map(|child| find_layout_box(child, node))
+}
+
@@ -0,0 +1,139 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use retina_dom::{
+    DocumentWrapper,
+    HtmlElementKind,
+    MetaViewport,
+    Node,
+};
+
+/// The metadata of a document, which is described by the children of the
+/// [`<head>`][spec] element.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/semantics.html#the-head-element
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct HeadMetadata {
+    /// The contents of the first `<title>` element, with the whitespace
+    /// stripped and collapsed.
+    pub(crate) title: Option<String>,
+
+    /// The parsed `content` of the `<meta name="viewport">` element.
+    pub(crate) viewport: Option<MetaViewport>,
+
+    /// The `http-equiv` and `content` pairs of the [pragma directives][spec].
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/semantics.html#pragma-directives
+    pub(crate) http_equiv: Vec<(String, String)>,
+
+    /// The `href`s of the `<link rel="stylesheet">` elements.
+    pub(crate) stylesheet_hrefs: Vec<String>,
+}
+
+impl HeadMetadata {
+    /// Collect the metadata from the children of the `<head>` of the
+    /// `document`.
+    pub(crate) fn extract(document: &Node) -> Self {
+        let mut metadata = Self::default();
+
+        let Some(head) = DocumentWrapper(document.clone()).find_head() else {
+            return metadata;
+        };
+
+        let Some(head) = head.as_parent_node() else {
+            return metadata;
+        };
+
+        for child in head.children().iter() {
+            let Some(html_kind) = child.as_html_element_kind() else { continue };
+
+            let element = html_kind.as_dom_element();
+            match (html_kind, element.qualified_name().local.as_ref()) {
+                (HtmlElementKind::Link(link), _) => {
+                    if let Some(href) = link.stylesheet_href() {
+                        metadata.stylesheet_hrefs.push(href.to_string());
+                    }
+                }
+
+                (_, "title") if metadata.title.is_none() => {
+                    metadata.title = Some(child_text_content(child));
+                }
+
+                (_, "meta") => {
+                    let attributes = element.attributes();
+                    let content = attributes.find_by_str("content").unwrap_or("");
+
+                    if let Some(http_equiv) = attributes.find_by_str("http-equiv") {
+                        metadata.http_equiv.push((http_equiv.to_ascii_lowercase(), content.to_string()));
+                    } else if metadata.viewport.is_none()
+                        && attributes.find_by_str("name").is_some_and(|name| name.eq_ignore_ascii_case("viewport")) {
+                        metadata.viewport = Some(MetaViewport::parse(content));
+                    }
+                }
+
+                _ => (),
+            }
+        }
+
+        metadata
+    }
+}
+
+/// Concatenate the text children of the element, and strip and collapse the
+/// ASCII whitespace.
+fn child_text_content(node: &Node) -> String {
+    let Some(parent_node) = node.as_parent_node() else {
+        return String::new();
+    };
+
+    let text: String = parent_node.children()
+        .iter()
+        .filter_map(|child| child.as_text())
+        .map(|text| text.data_as_str())
+        .collect();
+
+    text.split_ascii_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn extract() {
+        let document = retina_dom::Parser::parse(concat!(
+            "<head>",
+            "<title>\n  Hello \t World  </title>",
+            "<title>Second</title>",
+            "<meta name=viewport content=\"width=device-width\">",
+            "<meta name=VIEWPORT content=\"width=500\">",
+            "<meta http-equiv=Content-Security-Policy content=\"default-src 'self'\">",
+            "<meta http-equiv=REFRESH content=5>",
+            "<link rel=stylesheet href=a.css>",
+            "<link rel=\"alternate stylesheet\" href=b.css>",
+            "<link rel=icon href=favicon.ico>",
+            "<link rel=stylesheet>",
+            "<link rel=\"preload stylesheet\" href=c.css>",
+            "</head>",
+        ));
+
+        assert_eq!(HeadMetadata::extract(&document), HeadMetadata {
+            title: Some("Hello World".to_string()),
+            viewport: Some(MetaViewport::parse("width=device-width")),
+            http_equiv: vec![
+                ("content-security-policy".to_string(), "default-src 'self'".to_string()),
+                ("refresh".to_string(), "5".to_string()),
+            ],
+            stylesheet_hrefs: vec!["a.css".to_string(), "c.css".to_string()],
+        });
+    }
+
+    #[test]
+    fn extract_without_head_elements() {
+        let document = retina_dom::Parser::parse("<p>Hello</p>");
+        assert_eq!(HeadMetadata::extract(&document), HeadMetadata::default());
+    }
+}
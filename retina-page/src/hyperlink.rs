@@ -0,0 +1,61 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! [Following hyperlinks][spec] of `<a>` elements, which either navigates
+//! this page or opens a new one.
+//!
+//! [spec]: https://html.spec.whatwg.org/multipage/links.html#following-hyperlinks-2
+
+use log::{info, warn};
+use retina_dom::HtmlAnchorElement;
+use url::Url;
+
+/// Where the URL of a followed hyperlink is opened.
+#[derive(Clone, Debug)]
+pub(crate) enum HyperlinkNavigation {
+    /// Navigate this page to the `url`. The `Referer` isn't sent when
+    /// `no_referrer` is set.
+    CurrentPage {
+        url: Url,
+        no_referrer: bool,
+    },
+
+    /// Open the `url` in a new page, which is a new top-level navigable that
+    /// never has an opener, nor a referrer.
+    NewPage {
+        url: Url,
+    },
+}
+
+/// Follow the hyperlink of the `anchor`, resolving the `href` against the
+/// `base_url` of the document. This returns [`None`] if the element isn't a
+/// hyperlink, or the URL is invalid.
+pub(crate) fn follow_hyperlink(anchor: &HtmlAnchorElement, base_url: &Url) -> Option<HyperlinkNavigation> {
+    let href = anchor.href()?;
+
+    let url = match Url::options().base_url(Some(base_url)).parse(href) {
+        Ok(url) => url,
+        Err(e) => {
+            warn!("[hyperlink] Invalid anchor hyper reference \"{href}\": {e}");
+            return None;
+        }
+    };
+
+    if anchor.download().is_some() {
+        info!("[hyperlink] Downloading isn't supported, navigating to \"{url}\" instead");
+    }
+
+    if anchor.opens_new_browsing_context() {
+        if !anchor.no_opener() {
+            info!("[hyperlink] Opening \"{url}\" without an opener, since pages can't reach each other");
+        }
+
+        return Some(HyperlinkNavigation::NewPage { url });
+    }
+
+    Some(HyperlinkNavigation::CurrentPage {
+        url,
+        no_referrer: anchor.no_referrer(),
+    })
+}
+
@@ -1,16 +1,169 @@
 // Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
 // All Rights Reserved.
 
-use std::{collections::HashMap, future::Future, sync::RwLock};
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+    future::Future,
+    num::NonZeroUsize,
+    pin::Pin,
+    sync::{Arc, RwLock},
+};
 
 use retina_dom::ImageData;
 use retina_fetch::Fetch;
+use tokio::sync::{
+    mpsc::{self, UnboundedReceiver},
+    Semaphore,
+};
 use url::Url;
 
+/// The priority with which an image is fetched and decoded.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DecodePriority {
+    /// The image is (probably) below the viewport fold.
+    Low,
+
+    /// The image is visible, or its position isn't known yet.
+    High,
+}
+
+/// A unit of work for the [`BoundedDecodePool`].
+pub struct DecodeTask {
+    priority: DecodePriority,
+
+    /// Tasks with the same priority are executed in the order they were
+    /// queued.
+    sequence: u64,
+
+    future: Pin<Box<dyn Future<Output = ()> + Send>>,
+}
+
+impl std::fmt::Debug for DecodeTask {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DecodeTask")
+            .field("priority", &self.priority)
+            .field("sequence", &self.sequence)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for DecodeTask {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for DecodeTask {}
+
+impl PartialOrd for DecodeTask {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DecodeTask {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority.cmp(&other.priority)
+            .then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
+
+/// Limits the amount of images that are fetched and decoded at the same time,
+/// such that a page with a lot of images doesn't starve the other tasks of the
+/// page.
+#[derive(Debug)]
+pub struct BoundedDecodePool {
+    semaphore: Arc<Semaphore>,
+    queue: mpsc::UnboundedSender<DecodeTask>,
+    next_sequence: std::sync::atomic::AtomicU64,
+}
+
+impl BoundedDecodePool {
+    /// Creates a new pool. This must be called from within a Tokio runtime.
+    pub fn new(max_concurrent_decodes: NonZeroUsize) -> Self {
+        let semaphore = Arc::new(Semaphore::new(max_concurrent_decodes.get()));
+        let (queue, receiver) = mpsc::unbounded_channel();
+
+        tokio::task::spawn(Self::dispatch(Arc::clone(&semaphore), receiver));
+
+        Self {
+            semaphore,
+            queue,
+            next_sequence: Default::default(),
+        }
+    }
+
+    /// The maximum amount of decodes that can run concurrently.
+    pub fn default_max_concurrent_decodes() -> NonZeroUsize {
+        std::thread::available_parallelism()
+            .unwrap_or(NonZeroUsize::MIN)
+    }
+
+    pub fn submit<F>(&self, priority: DecodePriority, future: F)
+            where F: Future<Output = ()> + Send + 'static {
+        let sequence = self.next_sequence.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let task = DecodeTask {
+            priority,
+            sequence,
+            future: Box::pin(future),
+        };
+
+        if self.queue.send(task).is_err() {
+            log::error!("[image] Decode pool dispatcher has shut down");
+        }
+    }
+
+    async fn dispatch(semaphore: Arc<Semaphore>, mut receiver: UnboundedReceiver<DecodeTask>) {
+        let mut pending = BinaryHeap::new();
+
+        loop {
+            if pending.is_empty() {
+                match receiver.recv().await {
+                    Some(task) => pending.push(task),
+                    None => return,
+                }
+            }
+
+            let Ok(permit) = Arc::clone(&semaphore).acquire_owned().await else {
+                return;
+            };
+
+            // Tasks may have been queued whilst waiting for a permit, which
+            // could have a higher priority than the ones we already know of.
+            while let Ok(task) = receiver.try_recv() {
+                pending.push(task);
+            }
+
+            let Some(task) = pending.pop() else {
+                continue;
+            };
+
+            tokio::task::spawn(async move {
+                task.future.await;
+                drop(permit);
+            });
+        }
+    }
+}
+
+impl Default for BoundedDecodePool {
+    fn default() -> Self {
+        Self::new(Self::default_max_concurrent_decodes())
+    }
+}
+
+impl Drop for BoundedDecodePool {
+    fn drop(&mut self) {
+        self.semaphore.close();
+    }
+}
+
 #[derive(Debug)]
 pub struct ImageProvider {
     images: RwLock<HashMap<Url, ImageData>>,
     fetch: Fetch,
+    decode_pool: BoundedDecodePool,
 }
 
 impl ImageProvider {
@@ -18,12 +171,14 @@ impl ImageProvider {
         Self {
             fetch,
             images: RwLock::new(HashMap::new()),
+            decode_pool: BoundedDecodePool::default(),
         }
     }
 
     pub fn get_from_url<CallbackOnLoad, CallbackOnLoadFuture>(
         &self,
         url: Url,
+        priority: DecodePriority,
         on_load: CallbackOnLoad,
     ) -> ImageData
             where CallbackOnLoad: FnOnce(ImageData) -> CallbackOnLoadFuture + Send + 'static,
@@ -38,7 +193,7 @@ impl ImageProvider {
         {
             let image = image.clone();
             let url = url.clone();
-            tokio::task::spawn(async move {
+            self.decode_pool.submit(priority, async move {
                 image.update(url, fetch, "").await;
                 on_load(image).await;
             });
@@ -48,3 +203,47 @@ impl ImageProvider {
         image
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::sync::Mutex;
+    use tokio::sync::oneshot;
+
+    #[tokio::test]
+    async fn high_priority_task_runs_before_queued_low_priority_tasks() {
+        let pool = BoundedDecodePool::new(NonZeroUsize::MIN);
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        // Occupy the only permit, such that the other tasks are queued.
+        let (started_sender, started_receiver) = oneshot::channel();
+        let (release_sender, release_receiver) = oneshot::channel::<()>();
+        pool.submit(DecodePriority::Low, async move {
+            _ = started_sender.send(());
+            _ = release_receiver.await;
+        });
+        started_receiver.await.unwrap();
+
+        let (done_sender, mut done_receiver) = mpsc::unbounded_channel();
+        for (priority, name) in [
+            (DecodePriority::Low, "low 1"),
+            (DecodePriority::Low, "low 2"),
+            (DecodePriority::High, "high"),
+        ] {
+            let order = Arc::clone(&order);
+            let done_sender = done_sender.clone();
+            pool.submit(priority, async move {
+                order.lock().unwrap().push(name);
+                _ = done_sender.send(());
+            });
+        }
+
+        release_sender.send(()).unwrap();
+        for _ in 0..3 {
+            done_receiver.recv().await.unwrap();
+        }
+
+        assert_eq!(*order.lock().unwrap(), ["high", "low 1", "low 2"]);
+    }
+}
@@ -1,44 +1,62 @@
 // Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
 // All Rights Reserved.
 
+pub(crate) mod animation;
 pub(crate) mod dirty_state;
 pub(crate) mod command;
 pub(crate) mod cursor_state;
+pub(crate) mod focus_manager;
 pub(crate) mod font_loader;
+pub(crate) mod form_submission;
+pub(crate) mod fragment;
 pub(crate) mod handle;
+pub(crate) mod head;
+pub(crate) mod hyperlink;
 pub(crate) mod image_provider;
 pub(crate) mod message;
 pub(crate) mod page;
 pub(crate) mod scroller;
+pub(crate) mod transition;
 
 pub use command::{PageCommand, PageCommandAction};
 use cursor_state::CursorState;
+use focus_manager::FocusManager;
 pub use handle::{PageHandle, PageHandleCommunicationError, PageHandleReceiveHalf, PageHandleSendHalf};
 use image_provider::ImageProvider;
 pub use message::{PageMessage, PageProgress};
 
 use self::{
+    animation::AnimationState,
     font_loader::FontLoader,
+    head::HeadMetadata,
     page::Page,
     dirty_state::DirtyState,
     scroller::Scroller,
+    transition::TransitionState,
 };
 
-use retina_compositor::Compositor;
+use retina_compositor::{Compositor, DEFAULT_TILE_SIZE};
 use retina_gfx::{canvas::CanvasPaintingContext, euclid::Size2D};
 use retina_gfx_font::FontProvider;
+use retina_style_computation::DependencyMap;
+use retina_user_agent::{
+    permission::PermissionStore,
+    storage::LocalStorage,
+};
 
 use std::{
     panic::PanicInfo,
     sync::{
         Arc,
+        OnceLock,
+        RwLock,
         mpsc::{
             channel,
             sync_channel,
             SyncSender,
         },
     },
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use url::Url;
@@ -91,7 +109,7 @@ pub fn spawn(
                 url.clone(),
             );
 
-            let compositor = Compositor::new(canvas.context().clone());
+            let compositor = Compositor::new(canvas.context().clone(), DEFAULT_TILE_SIZE);
 
             let image_provider = ImageProvider::new(fetch.clone());
             let cursor_state = CursorState::new(message_sender.clone(), page_task_message_sender.clone());
@@ -105,10 +123,12 @@ pub fn spawn(
                 queued_redirect_url: None,
                 title: String::new(),
                 document: None,
+                head_metadata: HeadMetadata::default(),
                 style_sheets: None,
                 layout_root: None,
 
                 cursor_state,
+                focus_manager: FocusManager::new(),
                 scroller: Scroller::new(canvas_size.cast().cast_unit()),
                 canvas,
                 font_provider,
@@ -118,9 +138,17 @@ pub fn spawn(
                 browsing_context: None,
                 event_queue: None,
                 dirty_state: DirtyState::new(),
+                dependency_map: DependencyMap::new(),
                 font_loader,
                 image_provider,
                 earliest_scroll_request: None,
+                transition_state: TransitionState::default(),
+                animation_state: AnimationState::default(),
+                speculative_stylesheet_urls: Default::default(),
+                navigation_request: None,
+                time_origin: Instant::now(),
+                permission_store: shared_permission_store(),
+                local_storage: shared_local_storage(),
             };
 
             page.start(command_receiver, page_task_message_receiver).await.unwrap()
@@ -130,6 +158,38 @@ pub fn spawn(
     handle
 }
 
+/// The `localStorage` is shared between all pages, such that the `storage`
+/// events are fired in the other pages of the same origin.
+fn shared_local_storage() -> Arc<RwLock<LocalStorage>> {
+    static LOCAL_STORAGE: OnceLock<Arc<RwLock<LocalStorage>>> = OnceLock::new();
+
+    let local_storage = LOCAL_STORAGE.get_or_init(|| {
+        let local_storage = match LocalStorage::default_path() {
+            Some(path) => LocalStorage::open(path),
+            None => LocalStorage::new_in_memory(),
+        };
+        Arc::new(RwLock::new(local_storage))
+    });
+
+    Arc::clone(local_storage)
+}
+
+/// The permissions are shared between all pages, such that a permission
+/// granted in one page applies to the other pages of the same origin.
+fn shared_permission_store() -> Arc<RwLock<PermissionStore>> {
+    static PERMISSION_STORE: OnceLock<Arc<RwLock<PermissionStore>>> = OnceLock::new();
+
+    let permission_store = PERMISSION_STORE.get_or_init(|| {
+        let permission_store = match PermissionStore::default_path() {
+            Some(path) => PermissionStore::open(path),
+            None => PermissionStore::new_in_memory(),
+        };
+        Arc::new(RwLock::new(permission_store))
+    });
+
+    Arc::clone(permission_store)
+}
+
 fn handle_panic(
     sender: &SyncSender<PageMessage>,
     info: &PanicInfo<'_>,
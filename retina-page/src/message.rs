@@ -1,6 +1,7 @@
 // Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
 // All Rights Reserved.
 
+use retina_dom::Node;
 use retina_gfx::{
     Color,
     CursorIcon,
@@ -10,6 +11,7 @@ use retina_gfx_font::FontDescriptor;
 use retina_gfx_gui::ContextMenu;
 
 use retina_style::Stylesheet;
+use retina_user_agent::permission::{Origin, PermissionName, PermissionState};
 
 use crate::{
     font_loader::FontState,
@@ -38,16 +40,37 @@ pub enum PageMessage {
         height: u32
     },
 
+    /// Open the URL in a new page, e.g. because a hyperlink with
+    /// `target="_blank"` was followed. The new page has no opener, nor a
+    /// referrer.
+    OpenNewPage {
+        url: String,
+    },
+
     PaintReceived {
         texture_view: wgpu::TextureView,
         texture_size: Size2D<u32, u32>,
         background_color: Color,
     },
 
+    /// The result of a [`PageCommand::RequestPermission`](crate::PageCommand::RequestPermission).
+    PermissionResult {
+        origin: Origin,
+        name: PermissionName,
+        state: PermissionState,
+    },
+
     Progress {
         progress: PageProgress,
     },
 
+    /// The result of a [`PageCommand::ExecuteScript`](crate::PageCommand::ExecuteScript),
+    /// which is the completion value serialized to JSON, or the error message.
+    ScriptResult {
+        id: u64,
+        result: Result<String, String>,
+    },
+
     /// The title of the page.
     Title {
         title: String,
@@ -81,6 +104,13 @@ pub(crate) enum PageTaskMessage {
     /// The browser (probably) closed.
     CommandPipelineClosed,
 
+    /// The state of the `elements` that is matched by a user action
+    /// pseudo-class (e.g. `:hover`) changed, so their styles have to be
+    /// recomputed.
+    ElementStateChanged {
+        elements: Vec<Node>,
+    },
+
     /// A font was loaded.
     FontLoadResult {
         descriptor: FontDescriptor,
@@ -93,7 +123,20 @@ pub(crate) enum PageTaskMessage {
     /// A new image was loaded.
     ImageLoaded,
 
+    /// A style sheet referenced by an `@import` rule was loaded.
+    StylesheetImported {
+        /// The URL as written in the `@import` rule.
+        href: String,
+        stylesheet: Stylesheet,
+    },
+
     StylesheetLoaded {
         stylesheet: Stylesheet,
     },
+
+    /// A submit button was activated, so its form should be submitted.
+    SubmitForm {
+        form: Node,
+        submitter: Node,
+    },
 }
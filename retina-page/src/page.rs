@@ -2,8 +2,12 @@
 // All Rights Reserved.
 
 use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    pin::Pin,
     sync::{
         Arc,
+        RwLock,
         mpsc::{
             Receiver as SyncReceiver,
             SyncSender,
@@ -26,32 +30,43 @@ use retina_dom::{
     ImageData,
     ImageDataState,
     ImageDataKind,
+    LazyLoadingKind,
     LinkType,
     Node,
 };
 
-use retina_fetch::{Fetch, Request, RequestMode, RequestReferrer};
-use retina_gfx::{canvas::CanvasPaintingContext, Context};
+use retina_fetch::{Fetch, Request, RequestCacheMode, RequestMode, RequestReferrer};
+use retina_gfx::{canvas::CanvasPaintingContext, Context, euclid::default::Point2D};
 use retina_gfx_font::FontProvider;
 use retina_layout::{
     LayoutBox,
     LayoutBoxKind,
     LayoutGenerator,
 };
-use retina_scrittura::BrowsingContext;
-use retina_style::{Stylesheet, CascadeOrigin, CssReferencePixels};
+use retina_scrittura::{is_fragment_navigation, BrowsingContext, HistoryHandling, NavigationRequest};
+use retina_user_agent::{
+    permission::{PermissionState, PermissionStore},
+    storage::LocalStorage,
+};
+use retina_style::{AtImportRule, Stylesheet, CascadeOrigin, CssReferencePixels, Rule};
+use retina_style_computation::{DependencyMap, NodeId};
 use retina_style_parser::CssParsable;
 use tokio::{sync::mpsc::{Receiver as AsyncReceiver, Sender as AsyncSender}, runtime::Runtime};
 use url::Url;
 
 use crate::{
     cursor_state::CursorState,
+    focus_manager::{FocusDirection, FocusManager},
     dirty_state::{
         DirtyPhase,
         DirtyState,
     },
+    animation::AnimationState,
     font_loader::FontLoader,
-    image_provider::ImageProvider,
+    form_submission,
+    fragment::{self, IndicatedPart},
+    head::HeadMetadata,
+    image_provider::{DecodePriority, ImageProvider},
     message::PageTaskMessage,
     PageCommand,
     PageCommandAction,
@@ -61,6 +76,7 @@ use crate::{
         Scroller,
         ScrollResult,
     },
+    transition::TransitionState,
 };
 
 pub(crate) struct Page {
@@ -72,10 +88,12 @@ pub(crate) struct Page {
     pub(crate) queued_redirect_url: Option<Url>,
     pub(crate) title: String,
     pub(crate) document: Option<Node>,
+    pub(crate) head_metadata: HeadMetadata,
     pub(crate) style_sheets: Option<Vec<Stylesheet>>,
     pub(crate) layout_root: Option<LayoutBox>,
 
     pub(crate) cursor_state: CursorState,
+    pub(crate) focus_manager: FocusManager,
     pub(crate) scroller: Scroller,
     pub(crate) canvas: CanvasPaintingContext,
     pub(crate) font_provider: FontProvider,
@@ -86,9 +104,29 @@ pub(crate) struct Page {
     pub(crate) event_queue: Option<EventQueue>,
     pub(crate) dirty_state: DirtyState,
 
+    /// The styles of the elements of the last generated layout tree, which
+    /// are reused for the elements that weren't invalidated since.
+    pub(crate) dependency_map: DependencyMap,
+
     pub(crate) font_loader: FontLoader,
     pub(crate) image_provider: ImageProvider,
     pub(crate) earliest_scroll_request: Option<Instant>,
+    pub(crate) transition_state: TransitionState,
+    pub(crate) animation_state: AnimationState,
+
+    /// The style sheets that were already being loaded whilst parsing.
+    pub(crate) speculative_stylesheet_urls: HashSet<Url>,
+
+    /// The [time origin](https://w3c.github.io/hr-time/#dfn-time-origin) of
+    /// the document, which is the start of its navigation.
+    pub(crate) time_origin: Instant,
+
+    pub(crate) permission_store: Arc<RwLock<PermissionStore>>,
+    pub(crate) local_storage: Arc<RwLock<LocalStorage>>,
+
+    /// The request to load the next page with instead of a plain `GET`, e.g.
+    /// for a form submission.
+    pub(crate) navigation_request: Option<Request>,
 }
 
 enum ActionResult {
@@ -113,6 +151,10 @@ enum PageTaskMessageListenResult {
 
 type ErrorKind = Box<dyn std::error::Error>;
 
+/// The maximum depth of nested `@import` rules that are followed, which also
+/// protects against style sheets that (indirectly) import themselves.
+const MAXIMUM_STYLESHEET_IMPORT_DEPTH: usize = 8;
+
 impl Page {
     pub(crate) async fn start(
         mut self,
@@ -147,6 +189,58 @@ impl Page {
                 PageTaskMessageListenResult::Timeout => self.clean_dirty_state().await?,
             }
 
+            if let Some(browsing_context) = &mut self.browsing_context {
+                if browsing_context.run_expired_timers() != 0 {
+                    // The callbacks might have modified the document.
+                    self.dirty_state.request_restyle();
+                }
+
+                if browsing_context.run_completed_fetches() != 0 {
+                    // The promise reactions might have modified the document.
+                    self.dirty_state.request_restyle();
+                }
+
+                if browsing_context.run_storage_notifications() != 0 {
+                    // The `storage` event handlers might have modified the
+                    // document.
+                    self.dirty_state.request_restyle();
+                }
+
+                if browsing_context.run_performance_observer_task() != 0 {
+                    // The `PerformanceObserver` callbacks might have modified
+                    // the document.
+                    self.dirty_state.request_restyle();
+                }
+            }
+
+            let navigation_request = self.browsing_context.as_ref()
+                .and_then(BrowsingContext::take_navigation_request);
+            if let Some(navigation_request) = navigation_request {
+                self.handle_navigation_request(navigation_request).await?;
+            }
+
+            if self.browsing_context.as_ref().is_some_and(BrowsingContext::has_animation_frame_callbacks) {
+                // Request the next frame, in which the callbacks are run.
+                self.dirty_state.request_paint();
+            }
+
+            if self.scroller.is_smooth_scrolling() {
+                // Request the next frame of the smooth scroll animation.
+                self.dirty_state.request_paint();
+            }
+
+            if self.transition_state.has_running_transitions() {
+                // The values of the transitioning properties are overridden
+                // after the cascade, so the cascaded styles remain valid, but
+                // the overridden values have to be recomputed for the next
+                // frame.
+                self.dirty_state.request_incremental_restyle();
+            }
+
+            if self.animation_state.has_running_animations() {
+                self.dirty_state.request_incremental_restyle();
+            }
+
             if self.dirty_state.must_act_now() {
                 self.clean_dirty_state().await?;
             }
@@ -159,6 +253,10 @@ impl Page {
 
     /// Cleans the [`DirtyState`].
     async fn clean_dirty_state(&mut self) -> Result<(), ErrorKind> {
+        if self.dirty_state.phase() != DirtyPhase::Ready {
+            self.run_animation_frame_callbacks();
+        }
+
         loop {
             match self.dirty_state.phase() {
                 DirtyPhase::GenerateLayoutTree => self.generate_layout_tree().await?,
@@ -176,46 +274,162 @@ impl Page {
         Ok(())
     }
 
-    pub(crate) fn find_title(&mut self) {
-        let mut has_found = false;
-        self.document.as_ref().unwrap().for_each_child_node_recursive(&mut |node, _| {
-            if has_found {
-                return;
+    /// Run the `requestAnimationFrame()` callbacks, which must happen before
+    /// the rendering is updated, such that their changes end up in this
+    /// frame.
+    fn run_animation_frame_callbacks(&mut self) {
+        let Some(browsing_context) = &mut self.browsing_context else {
+            return;
+        };
+
+        if browsing_context.run_animation_frame_callbacks(Instant::now()) != 0 {
+            // The callbacks might have modified the document.
+            self.dirty_state.request_restyle();
+        }
+    }
+
+    /// Perform the navigation a script requested, e.g. using
+    /// `location.assign()`.
+    async fn handle_navigation_request(&mut self, request: NavigationRequest) -> Result<(), ErrorKind> {
+        let command = match request {
+            NavigationRequest::Navigate { url, history_handling } => PageCommand::Navigate {
+                url: url.to_string(),
+                replace: history_handling == HistoryHandling::Replace,
+            },
+            NavigationRequest::Reload => PageCommand::Reload { bypass_cache: false },
+        };
+
+        self.handle_command(command).await
+    }
+
+    /// Scroll to the [indicated part][spec] of the document, after
+    /// navigating to a fragment.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/browsing-the-web.html#scroll-to-the-fragment-identifier
+    fn scroll_to_fragment(&mut self) {
+        let Some(document) = &self.document else {
+            return;
+        };
+
+        let result = match fragment::select_indicated_part(document, &self.url) {
+            None => return,
+            Some(IndicatedPart::TopOfDocument) => self.scroller.scroll_to_top(),
+            Some(IndicatedPart::Element(element)) => {
+                let Some(layout_box) = self.layout_root.as_ref()
+                    .and_then(|layout_root| fragment::find_layout_box(layout_root, &element)) else {
+                    return;
+                };
+
+                let position = layout_box.dimensions().position_border_box();
+                let x = self.scroller.viewport_position().x;
+                self.scroller.scroll_to(Point2D::new(x, position.y))
             }
+        };
 
-            if let Some(element) = node.as_dom_element() {
-                if element.qualified_name().local.as_ref().eq_ignore_ascii_case("title") {
-                    if let Some(node) = element.as_parent_node().children().first() {
-                        if let Some(text) = node.as_text() {
-                            self.title = text.data_as_str().to_string();
-                            _ = self.message_sender.send(PageMessage::Title {
-                                title: text.data_as_str().to_string(),
-                            });
-                            has_found = true;
-                        }
-                    }
-                }
+        if result.was_changed() {
+            self.load_images_in_background();
+            self.dirty_state.request_paint();
+        }
+    }
+
+    /// Collect the metadata from the `<head>` of the document, which must
+    /// happen before the `<body>` is laid out.
+    pub(crate) fn process_head(&mut self) {
+        let Some(document) = self.document.as_ref() else {
+            return;
+        };
+
+        self.head_metadata = HeadMetadata::extract(document);
+
+        if let Some(title) = &self.head_metadata.title {
+            self.title = title.clone();
+            _ = self.message_sender.send(PageMessage::Title {
+                title: title.clone(),
+            });
+        }
+
+        if let Some(viewport) = &self.head_metadata.viewport {
+            info!("[head] Viewport: {viewport:?}");
+            // TODO: apply the initial-scale once the page can be zoomed.
+            if viewport.initial_scale.is_some_and(|scale| scale != 1.0) {
+                warn!("[head] Zooming isn't supported, ignoring initial-scale: {viewport:?}");
             }
-        }, 0);
+        }
+
+        for (http_equiv, content) in &self.head_metadata.http_equiv {
+            info!("[head] Pragma directive http-equiv=\"{http_equiv}\" content=\"{content}\"");
+        }
+    }
+
+    /// Get the [document base URL][spec], against which relative URLs are
+    /// resolved. This is the URL of the `<base>` element if there is one, and
+    /// the URL of the document otherwise.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/urls-and-fetching.html#document-base-url
+    pub(crate) fn base_url(&self) -> Url {
+        self.document.as_ref()
+            .and_then(|document| document.as_document())
+            .and_then(|document| document.data().base_url().cloned())
+            .unwrap_or_else(|| self.url.clone())
+    }
+
+    /// Get the width of the layout viewport, which is the width of the window,
+    /// unless the `<meta name="viewport">` of the document specifies otherwise.
+    fn layout_viewport_width(&self) -> CssReferencePixels {
+        let window_width = self.canvas.size().width as f64;
+        let width = match &self.head_metadata.viewport {
+            Some(viewport) => viewport.layout_width(window_width),
+            None => window_width,
+        };
+        CssReferencePixels::new(width)
     }
 
     pub(crate) async fn generate_layout_tree(&mut self) -> Result<(), ErrorKind> {
+        if self.dirty_state.take_restyle_all() {
+            self.dependency_map.clear();
+        }
+
         self.dirty_state.mark_layout_tree_generated();
         let begin_time = Instant::now();
 
-        let document_url = self.url.clone();
+        let document_url = self.base_url();
+
+        let mut transition_state = std::mem::take(&mut self.transition_state);
+        transition_state.begin_style_update(begin_time);
 
-        let layout_root = LayoutGenerator::generate(
+        let mut animation_state = std::mem::take(&mut self.animation_state);
+        animation_state.begin_style_update(begin_time);
+        let style_sheets = self.style_sheets.as_ref().unwrap();
+        let mut dependency_map = std::mem::take(&mut self.dependency_map);
+
+        let mut layout_root = LayoutGenerator::generate(
             Node::clone(self.document.as_ref().unwrap()),
-            &self.style_sheets.as_ref().unwrap(),
-            CssReferencePixels::new(self.canvas.size().width as _),
+            style_sheets,
+            &mut dependency_map,
+            self.layout_viewport_width(),
             CssReferencePixels::new(self.canvas.size().height as _),
             self.font_provider.clone(),
             &document_url,
-            |url| self.load_image(url),
+            |url| self.load_image(url, DecodePriority::High),
+            |node, style| {
+                transition_state.update_style(node, style);
+
+                // Animations override transitions, and their values don't
+                // start transitions themselves.
+                animation_state.update_style(node, style, style_sheets);
+            },
         );
 
+        transition_state.end_style_update();
+        self.transition_state = transition_state;
+
+        animation_state.end_style_update();
+        self.animation_state = animation_state;
+
+        self.dependency_map = dependency_map;
+
         self.scroller.did_content_resize(layout_root.dimensions().size_margin_box());
+        self.scroller.restore_container_offsets(&mut layout_root);
 
         let time_taken = begin_time.elapsed();
         if time_taken.as_millis() > 200 {
@@ -229,6 +443,10 @@ impl Page {
         self.load_resources_from_style_lazily_in_background(&layout_root);
         self.layout_root = Some(layout_root);
 
+        // Now that the positions of the images are known, the lazily loaded
+        // ones that are in the viewport can be fetched.
+        self.load_images_in_background();
+
         self.compositor.mark_tile_cache_dirty();
         Ok(())
     }
@@ -236,13 +454,15 @@ impl Page {
     pub(crate) async fn handle_action(&mut self, action: PageCommandAction) -> Result<(), ErrorKind> {
         let result = match action {
             PageCommandAction::Click => {
-                self.cursor_state.click(&self.url).await;
+                self.cursor_state.click(&self.base_url()).await;
                 ActionResult::Unchanged
             }
             PageCommandAction::RightClick => {
-                self.cursor_state.right_click(&self.url).await;
+                self.cursor_state.right_click(&self.base_url()).await;
                 ActionResult::Unchanged
             }
+            PageCommandAction::FocusNext => self.advance_focus(FocusDirection::Forward),
+            PageCommandAction::FocusPrevious => self.advance_focus(FocusDirection::Backward),
             PageCommandAction::PageDown => self.scroller.page_down().into(),
             PageCommandAction::PageUp => self.scroller.page_up().into(),
             PageCommandAction::ScrollToBottom => self.scroller.scroll_to_bottom().into(),
@@ -252,13 +472,50 @@ impl Page {
         match result {
             ActionResult::Unchanged => (),
             ActionResult::Repaint => {
-                self.dirty_state.request(DirtyPhase::Paint);
+                self.load_images_in_background();
+                self.dirty_state.request_paint();
             }
         }
 
         Ok(())
     }
 
+    fn advance_focus(&mut self, direction: FocusDirection) -> ActionResult {
+        let Some(document) = &self.document else {
+            return ActionResult::Unchanged;
+        };
+
+        let previously_focused = self.focus_manager.focused().cloned();
+        let focused = self.focus_manager.advance_focus(document, direction).cloned();
+
+        // The `:focus` pseudo-class now matches a different element.
+        for element in previously_focused.iter().chain(focused.iter()) {
+            self.invalidate_style_of_element(element);
+        }
+        self.dirty_state.request_incremental_restyle();
+
+        ActionResult::Unchanged
+    }
+
+    /// Invalidate the styles that might depend on the state of the `element`,
+    /// e.g. because it is matched by `:hover` or `:focus`.
+    fn invalidate_style_of_element(&mut self, element: &Node) {
+        // Selectors with sibling combinators can match the following siblings
+        // of the element and their descendants, so the whole subtree of the
+        // parent is invalidated.
+        let parent = element.parent_node().unwrap_or_else(|| element.clone());
+        self.dependency_map.invalidate(&parent, true);
+
+        // The `:has()` pseudo-class can make the style of the ancestors depend
+        // on the element as well. Their descendants are restyled when the style
+        // of the ancestor changed.
+        let mut ancestor = parent.parent_node();
+        while let Some(node) = ancestor {
+            self.dependency_map.invalidate(&node, false);
+            ancestor = node.parent_node();
+        }
+    }
+
     pub(crate) async fn handle_command(&mut self, command: PageCommand) -> Result<(), ErrorKind> {
         match command {
             PageCommand::Action(action) => self.handle_action(action).await?,
@@ -274,7 +531,7 @@ impl Page {
                     self.canvas.resize(size);
                     self.scroller.did_viewport_resize(size.cast().cast_unit());
 
-                    self.dirty_state.request(DirtyPhase::Layout);
+                    self.dirty_state.request_layout();
                 }
             }
 
@@ -322,8 +579,42 @@ impl Page {
                 }
             }
 
+            PageCommand::FollowHyperlink { url, no_referrer } => {
+                match Url::parse(&url) {
+                    Ok(url) => {
+                        let document_url = std::mem::replace(&mut self.url, url);
+                        self.referrer = (!no_referrer).then_some(document_url);
+                        self.load().await?;
+                    }
+
+                    Err(e) => {
+                        error!("Cannot follow the hyperlink, since the URL: \"{url}\" is invalid: {e}");
+                    }
+                }
+            }
+
+            PageCommand::Navigate { url, .. } => {
+                // TODO: there is no session history yet, so replacing the
+                //       current entry is the same as adding a new one.
+                match Url::parse(&url) {
+                    Ok(url) if is_fragment_navigation(&self.url, &url) => {
+                        self.url = url;
+                        self.scroll_to_fragment();
+                    }
+
+                    Ok(url) => {
+                        self.referrer = Some(std::mem::replace(&mut self.url, url));
+                        self.load().await?;
+                    }
+
+                    Err(e) => {
+                        error!("Cannot navigate, since the URL: \"{url}\" is invalid: {e}");
+                    }
+                }
+            }
+
             PageCommand::OpenUrl(input) => {
-                let url_parse_result = retina_fetch::parse_page_url(&input);
+                let url_parse_result = retina_fetch::parse_page_url(&input, None);
 
                 match url_parse_result {
                     Ok(url) => {
@@ -337,27 +628,64 @@ impl Page {
                 }
             }
 
-            PageCommand::Reload => {
+            PageCommand::ExecuteScript { source, id } => {
+                let result = match &mut self.browsing_context {
+                    Some(browsing_context) => browsing_context.run_script_from_string_source_to_json(&source),
+                    None => Err(String::from("no document loaded")),
+                };
+
+                self.message_sender.send(PageMessage::ScriptResult { id, result })?;
+            }
+
+            PageCommand::RequestPermission { origin, name } => {
+                // TODO: prompt the user with a native dialog once the browser
+                //       supports them. Until then, the User Agent denies the
+                //       permission on their behalf.
+                let state = self.permission_store.write().unwrap()
+                    .request(&origin, name, || PermissionState::Denied);
+
+                self.message_sender.send(PageMessage::PermissionResult { origin, name, state })?;
+            }
+
+            PageCommand::Reload { bypass_cache } => {
                 self.referrer = None;
-                self.load().await?;
+
+                let cache_mode = if bypass_cache {
+                    RequestCacheMode::Reload
+                } else {
+                    RequestCacheMode::Default
+                };
+                self.load_with_cache_mode(cache_mode).await?;
             }
 
             PageCommand::Scroll { delta } => {
-                if let Some(layout_root) = &self.layout_root {
-                    let scroll_result = match delta {
+                if let Some(layout_root) = &mut self.layout_root {
+                    let (x, y) = match delta {
                         retina_gfx::MouseScrollDelta::LineDelta(x, y) => {
-                            self.scroller.scroll_lines(x, y, layout_root.font_size().value())
-                        }
-                        retina_gfx::MouseScrollDelta::PixelDelta(pos) => {
-                            self.scroller.scroll_pixels(pos.x, pos.y)
+                            let font_size = layout_root.font_size().value();
+                            (x as f64 * font_size, y as f64 * font_size)
                         }
+                        retina_gfx::MouseScrollDelta::PixelDelta(pos) => (pos.x, pos.y),
+                    };
+
+                    // Scroll containers under the cursor take precedence over
+                    // the page itself.
+                    let position = self.cursor_state.page_position(&self.scroller);
+                    let scroll_result = if self.scroller.scroll_container_at(layout_root, position, x, y).was_changed() {
+                        // The tiles contain the content of the containers at
+                        // their previous scroll offsets.
+                        self.compositor.mark_tile_cache_dirty();
+                        ScrollResult::Changed
+                    } else {
+                        self.scroller.scroll_pixels(x, y)
                     };
 
                     if scroll_result.was_changed() {
                         if self.earliest_scroll_request.is_none() {
                             self.earliest_scroll_request = Some(Instant::now());
                         }
-                        self.dirty_state.request(DirtyPhase::Paint);
+                        self.load_images_in_background();
+                        self.dirty_state.request_paint();
                     }
                 }
 
@@ -399,12 +727,19 @@ impl Page {
                 return Ok(PageTaskMessageListenResult::PipelineClosed);
             }
 
+            PageTaskMessage::ElementStateChanged { elements } => {
+                for element in &elements {
+                    self.invalidate_style_of_element(element);
+                }
+                self.dirty_state.request_incremental_restyle();
+            }
+
             PageTaskMessage::FontLoadResult { descriptor, state } => {
                 let result = self.font_loader.process_load_state(descriptor, state);
                 if result.rerun_layout {
                     // TODO: DirtyPhase::Layout should recompute the font, but
                     // that is impossible in the current system.
-                    self.dirty_state.request(DirtyPhase::GenerateLayoutTree);
+                    self.dirty_state.request_restyle();
                 }
 
                 if result.rerun_algorithm {
@@ -423,19 +758,49 @@ impl Page {
                 // 3. Compositor should only repaint those tiles.
 
                 self.compositor.mark_tile_cache_dirty();
-                self.dirty_state.request(DirtyPhase::Paint);
+                self.dirty_state.request_paint();
             }
 
             PageTaskMessage::ImageLoaded => {
                 info!("Image loaded!");
                 self.compositor.mark_tile_cache_dirty();
-                self.dirty_state.request(DirtyPhase::Paint);
+                self.dirty_state.request_paint();
+            }
+
+            PageTaskMessage::StylesheetImported { href, stylesheet } => {
+                let import = self.style_sheets.iter_mut()
+                    .flatten()
+                    .find_map(|style_sheet| Self::find_unresolved_import(style_sheet, &href));
+
+                if let Some(import) = import {
+                    import.stylesheet = Some(stylesheet);
+                    self.layout_root = None;
+                    self.dirty_state.request_restyle();
+                } else {
+                    warn!("[stylesheet] Imported stylesheet \"{href}\" has no corresponding @import rule");
+                }
+            }
+
+            PageTaskMessage::SubmitForm { form, submitter } => {
+                let request = form_submission::create_form_submission_request(
+                    &form,
+                    &submitter,
+                    &self.url,
+                    &self.base_url(),
+                );
+
+                if let Some(request) = request {
+                    self.referrer = Some(std::mem::replace(&mut self.url, request.url().clone()));
+                    self.navigation_request = Some(request);
+                    self.load().await?;
+                    self.message_sender.send(PageMessage::Progress { progress: PageProgress::Ready })?;
+                }
             }
 
             PageTaskMessage::StylesheetLoaded { stylesheet } => {
                 self.layout_root = None;
                 self.style_sheets.get_or_insert(Default::default()).push(stylesheet);
-                self.dirty_state.request(DirtyPhase::GenerateLayoutTree);
+                self.dirty_state.request_restyle();
 
                 self.message_sender.send(PageMessage::Progress { progress: PageProgress::Ready })?;
             }
@@ -464,7 +829,12 @@ impl Page {
     }
 
     pub(crate) async fn load(&mut self) -> Result<(), ErrorKind> {
+        self.load_with_cache_mode(RequestCacheMode::Default).await
+    }
+
+    pub(crate) async fn load_with_cache_mode(&mut self, cache_mode: RequestCacheMode) -> Result<(), ErrorKind> {
         info!("Loading page: {:?}", self.url);
+        self.time_origin = Instant::now();
         self.font_loader.set_document_url(self.url.clone());
 
         // Discard the previous title
@@ -473,18 +843,19 @@ impl Page {
         });
 
         _ = self.scroller.scroll_to_top();
+        self.focus_manager.clear();
 
-        self.load_page().await?;
+        self.load_page(cache_mode).await?;
+        self.process_head();
         self.load_stylesheets_in_background();
         self.load_images_in_background();
-        self.find_title();
         self.load_favicon_in_background();
 
         self.parse_stylesheets().await?;
 
         self.generate_layout_tree().await?;
 
-        self.dirty_state.request(DirtyPhase::Paint);
+        self.dirty_state.request_paint();
 
         Ok(())
     }
@@ -498,11 +869,12 @@ impl Page {
 
         let fetch = self.fetch.clone();
         let sender = self.message_sender.clone();
-        let base_url = self.url.clone();
+        let base_url = self.base_url();
 
         tokio::task::spawn(async move {
-            let document = DocumentWrapper(document);
-            let head = document.head();
+            let Some(head) = DocumentWrapper(document).find_head() else {
+                return;
+            };
             let head = head.as_parent_node().unwrap();
 
             // Load the hrefs before loading the content to ensure the children
@@ -564,11 +936,11 @@ impl Page {
         self.font_loader.register(layout_box);
     }
 
-    pub(crate) fn load_image(&self, url: Url) -> ImageData {
+    pub(crate) fn load_image(&self, url: Url, priority: DecodePriority) -> ImageData {
         let task_message_sender = self.page_task_message_sender.clone();
         let gfx_context = self.canvas.context().clone();
 
-        self.image_provider.get_from_url(url.clone(), |data| async move {
+        self.image_provider.get_from_url(url.clone(), priority, |data| async move {
             _ = task_message_sender.send(PageTaskMessage::ImageLoaded).await.ok();
 
             let source = &url.to_string();
@@ -576,11 +948,30 @@ impl Page {
         })
     }
 
+    /// Starts loading the `<img>` elements that haven't been loaded yet.
+    /// Images that are in the viewport are prioritized over the ones below
+    /// the fold, and `loading="lazy"` images are deferred until they enter
+    /// the viewport.
+    ///
+    /// This is called again after layout and after each scroll, which takes
+    /// the role of the `IntersectionObserver` that the specification uses.
+    ///
+    /// ## References
+    /// * [HTML - Lazy loading attributes](https://html.spec.whatwg.org/multipage/urls-and-fetching.html#lazy-loading-attributes)
     pub(crate) fn load_images_in_background(&self) {
         let Some(document) = self.document.clone() else {
             return;
         };
 
+        let mut image_boxes = HashMap::new();
+        if let Some(layout_root) = &self.layout_root {
+            collect_image_boxes(layout_root, &mut image_boxes);
+        }
+
+        let viewport_top = self.scroller.viewport_position().y;
+        let viewport_bottom = viewport_top + self.scroller.viewport_size().height;
+
+        let base_url = self.base_url();
         document.for_each_child_node_recursive_handle(&mut |node| {
             let Some(html_element) = node.as_html_element_kind() else { return };
             let HtmlElementKind::Img(image) = html_element else { return };
@@ -590,11 +981,27 @@ impl Page {
                 return;
             }
 
-            let Ok(url) = Url::options().base_url(Some(&self.url)).parse(image.src()) else {
+            // `None` when the position isn't known, e.g. before layout.
+            let in_viewport = image_boxes.get(&NodeId::of(node))
+                .map(|layout_box| {
+                    let rect = layout_box.dimensions().rect_border_box();
+                    rect.max_y() >= viewport_top && rect.min_y() <= viewport_bottom
+                });
+
+            if image.lazy_loading() == LazyLoadingKind::Lazy && in_viewport != Some(true) {
+                return;
+            }
+
+            let Ok(url) = Url::options().base_url(Some(&base_url)).parse(image.src()) else {
                 return;
             };
 
-            *data = self.load_image(url);
+            let priority = match in_viewport {
+                Some(false) => DecodePriority::Low,
+                _ => DecodePriority::High,
+            };
+
+            *data = self.load_image(url, priority);
         });
     }
 
@@ -706,12 +1113,17 @@ impl Page {
         });
     }
 
-    pub(crate) async fn load_page(&mut self) -> Result<(), ErrorKind> {
+    pub(crate) async fn load_page(&mut self, cache_mode: RequestCacheMode) -> Result<(), ErrorKind> {
         let referrer = self.referrer.clone()
             .map(|url| RequestReferrer::Url(url))
             .unwrap_or_default();
 
-        let mut document = match self.fetch.fetch_document(self.url.clone(), referrer).await {
+        let document = match self.navigation_request.take() {
+            Some(request) => self.fetch.fetch(request),
+            None => self.fetch.fetch_document(self.url.clone(), referrer, cache_mode),
+        };
+
+        let mut document = match document.await {
             Ok(response) => response,
             Err(e) => {
                 return self.handle_load_error(e);
@@ -727,17 +1139,47 @@ impl Page {
         let document = match retina_file_viewer::transform(&mut document).await {
             Some(document) => document,
             None => {
+                let is_xml = retina_media_type::is_xml_mime_type(&document.content_type());
                 let mut reader = document.body().await;
 
-                retina_dom::Parser::parse_with_reader(&mut reader)
+                if is_xml {
+                    retina_dom::Parser::parse_xml_with_reader(&mut reader, Some(self.url.clone()))
+                } else {
+                    self.parse_html_with_speculative_stylesheet_loads(&mut reader)
+                }
             }
         };
 
         self.load_page_with_document(document)
     }
 
+    /// Parse the HTML document, and start loading the style sheets as soon as
+    /// the parser finds their `<link>` elements, instead of waiting for the
+    /// whole document to be parsed.
+    fn parse_html_with_speculative_stylesheet_loads<R: std::io::Read>(&mut self, reader: &mut R) -> Node {
+        let fetch = self.fetch.clone();
+        let task_message_sender = self.page_task_message_sender.clone();
+        let document_url = self.url.clone();
+
+        let mut speculative_stylesheet_urls = HashSet::new();
+        let document = retina_dom::Parser::parse_with_reader_and_stylesheet_observer(
+            reader,
+            Some(self.url.clone()),
+            &mut |url| {
+                if speculative_stylesheet_urls.insert(url.clone()) {
+                    info!("[stylesheet] Speculatively loading: \"{}\"", url.as_str());
+                    Self::load_stylesheet_in_background(url, fetch.clone(), task_message_sender.clone(), document_url.clone());
+                }
+            },
+        );
+
+        self.speculative_stylesheet_urls = speculative_stylesheet_urls;
+        document
+    }
+
     fn load_page_with_document(&mut self, document: Node) -> Result<(), ErrorKind> {
         self.document = Some(document.clone());
+        self.dependency_map.clear();
 
         self.message_sender.send(PageMessage::Progress {
             progress: PageProgress::ParsedHtml,
@@ -746,7 +1188,15 @@ impl Page {
         let event_queue = EventQueue::new();
         self.event_queue = Some(event_queue.clone());
 
-        let browsing_context = BrowsingContext::new(document, event_queue);
+        let browsing_context = BrowsingContext::new(
+            document,
+            self.url.clone(),
+            event_queue,
+            self.fetch.clone(),
+            Arc::clone(&self.permission_store),
+            Arc::clone(&self.local_storage),
+            self.time_origin,
+        );
         self.browsing_context = Some(browsing_context);
 
         Ok(())
@@ -773,37 +1223,46 @@ impl Page {
         };
 
         let document_url = self.url.clone();
-        let base_url = Some(self.url.clone());
+        let base_url = Some(self.base_url());
         let fetch = self.fetch.clone();
         let task_message_sender = self.page_task_message_sender.clone();
+        let head_stylesheet_hrefs = self.head_metadata.stylesheet_hrefs.clone();
+        let speculative_stylesheet_urls = self.speculative_stylesheet_urls.clone();
 
         tokio::task::spawn(async move {
             let base_url = base_url;
             let base_url = base_url.as_ref();
 
-            document.for_each_child_node_recursive(&mut |node, _| {
-                let Some(html_kind) = node.as_html_element_kind() else { return };
-                let HtmlElementKind::Link(link) = html_kind else { return };
-                info!("[stylesheet] Found link: {link:#?}");
-                if !link.relationship().contains(LinkType::Stylesheet) {
-                    warn!("[stylesheet] Not a stylesheet: {:#?}", link.relationship().collect::<Vec<_>>()); return }
+            // The `<link>` elements in the `<head>` are already collected, but
+            // `<link rel="stylesheet">` is also allowed in the `<body>`.
+            let mut hrefs = head_stylesheet_hrefs;
+            if let Some(body) = DocumentWrapper(document).find_body() {
+                body.for_each_child_node_recursive(&mut |node, _| {
+                    let Some(HtmlElementKind::Link(link)) = node.as_html_element_kind() else { return };
+                    info!("[stylesheet] Found link in <body>: {link:#?}");
 
-                let href = link.href();
-                if href.is_empty() {
-                    warn!("[stylesheet] Link rel=\"stylesheet\" without `href` attribute: {link:#?}");
-                    return;
-                }
+                    if let Some(href) = link.stylesheet_href() {
+                        hrefs.push(href.to_string());
+                    }
+                }, 0);
+            }
 
-                let url = match url::Url::options().base_url(base_url).parse(href) {
+            for href in hrefs {
+                let url = match url::Url::options().base_url(base_url).parse(&href) {
                     Ok(url) => url,
                     Err(err) => {
                         warn!("[stylesheet] Invalid stylesheet <link>: \"{href:?}\": {err}");
-                        return;
+                        continue;
                     }
                 };
 
+                // The parser already found this <link> and started loading it.
+                if speculative_stylesheet_urls.contains(&url) {
+                    continue;
+                }
+
                 Self::load_stylesheet_in_background(url, fetch.clone(), task_message_sender.clone(), document_url.clone());
-            }, 0);
+            }
         });
     }
 
@@ -813,45 +1272,143 @@ impl Page {
         page_task_message_sender: AsyncSender<PageTaskMessage>,
         document_url: Url,
     ) {
+        info!("[stylesheet] Initiating stylesheet load: \"{}\"", url.as_str());
+
+        tokio::task::spawn(async move {
+            let href = url.to_string();
+
+            let Some(stylesheet) = Self::fetch_stylesheet(url, fetch, document_url, 0).await else {
+                return;
+            };
+
+            let result = page_task_message_sender.send(PageTaskMessage::StylesheetLoaded { stylesheet }).await;
+
+            if let Err(e) = result {
+                error!("Failed to notify of a new stylesheet \"{href}\": {e}");
+                return;
+            }
+        });
+    }
+
+    /// Load the style sheets that the `@import` rules of a `<style>` element
+    /// refer to. The loaded style sheets are merged into the `@import` rules
+    /// after they are loaded, in [`Self::handle_task_message()`].
+    fn load_imported_stylesheets_in_background(&self, stylesheet: &Stylesheet) {
+        let base_url = self.base_url();
+        for href in Self::unresolved_imports(stylesheet) {
+            let url = match Url::options().base_url(Some(&base_url)).parse(&href) {
+                Ok(url) => url,
+                Err(err) => {
+                    warn!("[stylesheet] Invalid @import URL \"{href}\": {err}");
+                    continue;
+                }
+            };
+
+            let fetch = self.fetch.clone();
+            let document_url = self.url.clone();
+            let page_task_message_sender = self.page_task_message_sender.clone();
+
+            tokio::task::spawn(async move {
+                let Some(stylesheet) = Self::fetch_stylesheet(url, fetch, document_url, 1).await else {
+                    return;
+                };
+
+                let result = page_task_message_sender.send(PageTaskMessage::StylesheetImported {
+                    href: href.clone(),
+                    stylesheet,
+                }).await;
+
+                if let Err(e) = result {
+                    error!("Failed to notify of an imported stylesheet \"{href}\": {e}");
+                }
+            });
+        }
+    }
+
+    /// Fetch and parse the style sheet, including the style sheets it
+    /// `@import`s.
+    fn fetch_stylesheet(
+        url: Url,
+        fetch: Fetch,
+        document_url: Url,
+        import_depth: usize,
+    ) -> Pin<Box<dyn Future<Output = Option<Stylesheet>> + Send>> {
         use retina_fetch::{
             RequestDestination,
             RequestInitiator,
         };
 
-        info!("[stylesheet] Initiating stylesheet load: \"{}\"", url.as_str());
-
-        tokio::task::spawn(async move {
+        Box::pin(async move {
             let href = url.as_str();
 
-            let request = Request::new(url.clone(), RequestInitiator::default(), RequestDestination::Style, RequestMode::default(), RequestReferrer::Url(document_url));
+            let request = Request::new(url.clone(), RequestInitiator::default(), RequestDestination::Style, RequestMode::default(), RequestReferrer::Url(document_url.clone()));
             let mut response = match fetch.fetch(request).await {
                 Ok(response) => response,
                 Err(e) => {
                     error!("[stylesheet] Failed to load stylesheet \"{href}\": {e:#?}");
-                    return;
+                    return None;
                 }
             };
 
             let mut text = String::new();
             if let Err(e) = response.body().await.read_to_string(&mut text) {
                 error!("[stylesheet] Failed to load stylesheet \"{href}\": {e:#?}");
-                return;
+                return None;
             }
 
-            let stylesheet = Stylesheet::parse(CascadeOrigin::Author, &text);
+            let mut stylesheet = Stylesheet::parse(CascadeOrigin::Author, &text);
             info!(
                 "[stylesheet] Loaded stylesheet from \"{}\" containing {} rules",
                 href,
                 stylesheet.rules().len()
             );
 
-            let result = page_task_message_sender.send(PageTaskMessage::StylesheetLoaded { stylesheet }).await;
+            if import_depth >= MAXIMUM_STYLESHEET_IMPORT_DEPTH {
+                warn!("[stylesheet] Not following the @import rules of \"{href}\", since they are nested too deeply");
+                return Some(stylesheet);
+            }
 
-            if let Err(e) = result {
-                error!("Failed to notify of a new stylesheet \"{href}\": {e}");
-                return;
+            for import_href in Self::unresolved_imports(&stylesheet) {
+                let import_url = match Url::options().base_url(Some(&url)).parse(&import_href) {
+                    Ok(url) => url,
+                    Err(err) => {
+                        warn!("[stylesheet] Invalid @import URL \"{import_href}\" in \"{href}\": {err}");
+                        continue;
+                    }
+                };
+
+                let imported = Self::fetch_stylesheet(import_url, fetch.clone(), document_url.clone(), import_depth + 1).await;
+                if let Some(import) = Self::find_unresolved_import(&mut stylesheet, &import_href) {
+                    import.stylesheet = imported;
+                }
             }
-        });
+
+            Some(stylesheet)
+        })
+    }
+
+    /// Find the `@import` rule with the given URL that hasn't been loaded yet.
+    fn find_unresolved_import<'stylesheet>(
+        stylesheet: &'stylesheet mut Stylesheet,
+        href: &str,
+    ) -> Option<&'stylesheet mut AtImportRule> {
+        stylesheet.rules_mut()
+            .iter_mut()
+            .find_map(|rule| match rule {
+                Rule::AtImport(import) if import.url == href && import.stylesheet.is_none() => Some(import),
+                _ => None,
+            })
+    }
+
+    /// Get the URLs of the `@import` rules which haven't been loaded yet.
+    fn unresolved_imports(stylesheet: &Stylesheet) -> Vec<String> {
+        stylesheet.rules()
+            .iter()
+            .filter_map(|rule| match rule {
+                Rule::AtImport(import) if import.stylesheet.is_none() => Some(import.url.clone()),
+                _ => None,
+            })
+            .collect()
     }
 
     pub(crate) async fn paint(&mut self) -> Result<(), ErrorKind> {
@@ -861,6 +1418,10 @@ impl Page {
         }
         self.dirty_state.mark_painted();
 
+        if self.scroller.advance_smooth_scroll(Instant::now()).was_changed() {
+            self.load_images_in_background();
+        }
+
         let Some(layout_root) = self.layout_root.as_ref() else {
             warn!("Painted without a layout root!");
             return Ok(());
@@ -915,6 +1476,10 @@ impl Page {
                 }
             }, 0);
 
+        for stylesheet in &stylesheets {
+            self.load_imported_stylesheets_in_background(stylesheet);
+        }
+
         let time_taken = begin_time.elapsed();
         log::info!("Stylesheets from <style> elements took {} ms to parse", time_taken.as_millis());
 
@@ -930,9 +1495,10 @@ impl Page {
     async fn relayout(&mut self) -> Result<(), ErrorKind> {
         self.dirty_state.mark_layed_out();
 
+        let layout_viewport_width = self.layout_viewport_width();
         if let Some(layout_root) = &mut self.layout_root {
             layout_root.dimensions_mut().set_margin_size(
-                CssReferencePixels::new(self.canvas.size().width as _),
+                layout_viewport_width,
                 CssReferencePixels::new(self.canvas.size().height as _)
             );
             layout_root.run_layout(None, None);
@@ -965,3 +1531,15 @@ impl Page {
         });
     }
 }
+
+/// Index the boxes of the `<img>` elements by their node, where the first box
+/// of an element is used when it generated multiple.
+fn collect_image_boxes<'layout>(layout_box: &'layout LayoutBox, result: &mut HashMap<NodeId, &'layout LayoutBox>) {
+    if matches!(layout_box.node.as_html_element_kind(), Some(HtmlElementKind::Img(..))) {
+        result.entry(NodeId::of(&layout_box.node)).or_insert(layout_box);
+    }
+
+    for child in layout_box.children() {
+        collect_image_boxes(child, result);
+    }
+}
@@ -1,13 +1,38 @@
 // Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
 // All Rights Reserved.
 
-use retina_gfx::euclid::default::{Point2D, Size2D};
+use std::time::{Duration, Instant};
 
-#[derive(Copy, Clone, Debug, Default)]
+use retina_dom::Node;
+use retina_gfx::euclid::default::{Point2D, Size2D, Vector2D};
+use retina_layout::LayoutBox;
+
+/// The duration of the animation when scrolling a page up or down.
+const PAGE_SCROLL_DURATION: Duration = Duration::from_millis(150);
+
+#[derive(Clone, Debug, Default)]
 pub struct Scroller {
     position: Point2D<f64>,
     viewport_size: Size2D<f64>,
     content_size: Size2D<f64>,
+    smooth_scroll: Option<SmoothScrollState>,
+
+    /// The scroll offsets of the [scroll containers][spec] inside the page
+    /// (e.g. elements with `overflow: scroll`), such that they're kept when
+    /// the layout tree is generated again.
+    ///
+    /// [spec]: https://drafts.csswg.org/css-overflow/#scroll-container
+    container_offsets: Vec<(Node, Vector2D<f64>)>,
+}
+
+/// The state of an in-progress smooth scroll, which is advanced every frame
+/// using [`Scroller::advance_smooth_scroll()`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SmoothScrollState {
+    pub start: Point2D<f64>,
+    pub end: Point2D<f64>,
+    pub start_time: Instant,
+    pub duration: Duration,
 }
 
 impl Scroller {
@@ -41,33 +66,118 @@ impl Scroller {
         self.adjust_position_if_needed();
     }
 
-    pub fn page_down(&mut self) -> ScrollResult {
+    /// Advance the smooth scroll animation (if any) to the given point in
+    /// time. The animation is finished when the `duration` has elapsed.
+    pub fn advance_smooth_scroll(&mut self, now: Instant) -> ScrollResult {
+        let Some(state) = self.smooth_scroll else {
+            return ScrollResult::Unchanged;
+        };
+
+        let elapsed = now.saturating_duration_since(state.start_time);
         self.with_result(|scroller| {
-            scroller.position.y += scroller.viewport_size.height;
+            if elapsed >= state.duration {
+                scroller.position = state.end;
+                scroller.smooth_scroll = None;
+                return;
+            }
+
+            let progress = elapsed.as_secs_f64() / state.duration.as_secs_f64();
+            scroller.position = state.start.lerp(state.end, ease_in_out(progress));
         })
     }
 
+    pub const fn is_smooth_scrolling(&self) -> bool {
+        self.smooth_scroll.is_some()
+    }
+
+    pub fn page_down(&mut self) -> ScrollResult {
+        let mut target = self.smooth_scroll_target();
+        target.y += self.viewport_size.height;
+        self.smooth_scroll_to(target, PAGE_SCROLL_DURATION)
+    }
+
     pub fn page_up(&mut self) -> ScrollResult {
-        self.with_result(|scroller| {
-            scroller.position.y -= scroller.viewport_size.height;
-        })
+        let mut target = self.smooth_scroll_target();
+        target.y -= self.viewport_size.height;
+        self.smooth_scroll_to(target, PAGE_SCROLL_DURATION)
+    }
+
+    /// Scroll the innermost scroll container at the given `position` in the
+    /// page that can still be scrolled in the direction of the delta.
+    /// Returns [`ScrollResult::Unchanged`] if there is no such container, in
+    /// which case the page itself should be scrolled instead.
+    pub fn scroll_container_at(
+        &mut self,
+        layout_root: &mut LayoutBox,
+        position: Point2D<f64>,
+        x: f64,
+        y: f64,
+    ) -> ScrollResult {
+        let mut path = Vec::new();
+        hit_test_path(layout_root, position, &mut path);
+
+        for depth in (0..=path.len()).rev() {
+            let layout_box = follow_path_mut(layout_root, &path[..depth]);
+            if !layout_box.is_user_scrollable() {
+                continue;
+            }
+
+            let style = layout_box.computed_style();
+            let x = if style.overflow_x().is_user_scrollable() { x } else { 0.0 };
+            let y = if style.overflow_y().is_user_scrollable() { y } else { 0.0 };
+
+            let current = layout_box.scroll_offset();
+            let max = layout_box.max_scroll_offset();
+            let offset = Vector2D::new(
+                (current.x - x).clamp(0.0, max.x),
+                (current.y - y).clamp(0.0, max.y),
+            );
+
+            if offset == current {
+                continue;
+            }
+
+            layout_box.set_scroll_offset(offset);
+
+            let node = layout_box.node.clone();
+            match self.container_offsets.iter_mut().find(|(container, _)| Node::ptr_eq(container, &node)) {
+                Some((_, container_offset)) => *container_offset = offset,
+                None => self.container_offsets.push((node, offset)),
+            }
+
+            return ScrollResult::Changed;
+        }
+
+        ScrollResult::Unchanged
     }
 
-    pub fn scroll_lines(&mut self, x: f32, y: f32, font_size: f64) -> ScrollResult {
-        self.scroll_pixels(
-            x as f64 * font_size,
-            y as f64 * font_size,
-        )
+    /// Apply the scroll offsets of the scroll containers to a newly generated
+    /// layout tree, and forget the containers that are no longer in it.
+    pub fn restore_container_offsets(&mut self, layout_root: &mut LayoutBox) {
+        if self.container_offsets.is_empty() {
+            return;
+        }
+
+        let mut restored = Vec::with_capacity(self.container_offsets.len());
+        restore_container_offsets(layout_root, &self.container_offsets, &mut restored);
+        self.container_offsets = restored;
     }
 
     pub fn scroll_pixels(&mut self, x: f64, y: f64) -> ScrollResult {
+        self.smooth_scroll = None;
         self.with_result(|scroller| {
             scroller.position.x -= x;
             scroller.position.y -= y;
         })
     }
 
+    pub fn scroll_to(&mut self, position: Point2D<f64>) -> ScrollResult {
+        self.smooth_scroll = None;
+        self.with_result(|scroller| scroller.position = position)
+    }
+
     pub fn scroll_to_bottom(&mut self) -> ScrollResult {
+        self.smooth_scroll = None;
         if self.content_size.height <= self.viewport_size.height {
             return ScrollResult::Unchanged;
         }
@@ -78,13 +188,53 @@ impl Scroller {
     }
 
     pub fn scroll_to_top(&mut self) -> ScrollResult {
+        self.smooth_scroll = None;
         self.with_result(|scroller| scroller.position.y = 0.0)
     }
 
+    /// Start animating the scroll position towards the `target`, instead of
+    /// jumping to it immediately. The position is interpolated using the
+    /// `ease-in-out` timing function.
+    pub fn smooth_scroll_to(&mut self, target: Point2D<f64>, duration: Duration) -> ScrollResult {
+        let start = self.position;
+
+        self.position = target;
+        self.adjust_position_if_needed();
+        let end = self.position;
+        self.position = start;
+
+        if start == end {
+            self.smooth_scroll = None;
+            return ScrollResult::Unchanged;
+        }
+
+        self.smooth_scroll = Some(SmoothScrollState {
+            start,
+            end,
+            start_time: Instant::now(),
+            duration,
+        });
+
+        ScrollResult::Changed
+    }
+
+    /// The position the scroller will end up at, which is not the current
+    /// position when a smooth scroll is in progress.
+    fn smooth_scroll_target(&self) -> Point2D<f64> {
+        match self.smooth_scroll {
+            Some(state) => state.end,
+            None => self.position,
+        }
+    }
+
     pub const fn viewport_position(&self) -> Point2D<f64> {
         self.position
     }
 
+    pub const fn viewport_size(&self) -> Size2D<f64> {
+        self.viewport_size
+    }
+
     fn with_result<F>(&mut self, f: F) -> ScrollResult
             where F: FnOnce(&mut Self) {
         let original_position = self.position;
@@ -101,6 +251,84 @@ impl Scroller {
     }
 }
 
+/// Find the path of child indices to the innermost box at the `position`,
+/// taking the scroll offsets of the ancestors into account.
+fn hit_test_path(layout_box: &LayoutBox, position: Point2D<f64>, path: &mut Vec<usize>) {
+    let position = position + layout_box.scroll_offset();
+
+    for (index, child) in layout_box.children().iter().enumerate() {
+        if child.dimensions().rect_border_box().contains(position) {
+            path.push(index);
+            hit_test_path(child, position, path);
+            return;
+        }
+    }
+}
+
+fn follow_path_mut<'boxes>(mut layout_box: &'boxes mut LayoutBox, path: &[usize]) -> &'boxes mut LayoutBox {
+    for index in path {
+        layout_box = &mut layout_box.children_mut()[*index];
+    }
+    layout_box
+}
+
+fn restore_container_offsets(
+    layout_box: &mut LayoutBox,
+    offsets: &[(Node, Vector2D<f64>)],
+    restored: &mut Vec<(Node, Vector2D<f64>)>,
+) {
+    if let Some((node, offset)) = offsets.iter().find(|(node, _)| Node::ptr_eq(node, &layout_box.node)) {
+        // The content might have shrunk since it was scrolled.
+        let offset = offset.min(layout_box.max_scroll_offset());
+        layout_box.set_scroll_offset(offset);
+        restored.push((node.clone(), offset));
+    }
+
+    for child in layout_box.children_mut() {
+        restore_container_offsets(child, offsets, restored);
+    }
+}
+
+/// The `ease-in-out` timing function, equivalent to
+/// `cubic-bezier(0.42, 0, 0.58, 1)`, evaluated at the given input progress.
+///
+/// ## References
+/// * [CSS Easing Functions Level 1](https://www.w3.org/TR/css-easing-1/#cubic-bezier-easing-functions)
+fn ease_in_out(progress: f64) -> f64 {
+    const X1: f64 = 0.42;
+    const X2: f64 = 0.58;
+    const Y1: f64 = 0.0;
+    const Y2: f64 = 1.0;
+
+    fn bezier(t: f64, p1: f64, p2: f64) -> f64 {
+        let inverse = 1.0 - t;
+        3.0 * inverse * inverse * t * p1 + 3.0 * inverse * t * t * p2 + t * t * t
+    }
+
+    let progress = progress.clamp(0.0, 1.0);
+
+    // The curve is monotonic in x, so find the `t` for the input progress
+    // using bisection.
+    let mut low = 0.0;
+    let mut high = 1.0;
+    let mut t = progress;
+    for _ in 0..32 {
+        let x = bezier(t, X1, X2);
+        if (x - progress).abs() < 1e-7 {
+            break;
+        }
+
+        if x < progress {
+            low = t;
+        } else {
+            high = t;
+        }
+        t = (low + high) / 2.0;
+    }
+
+    bezier(t, Y1, Y2)
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum ScrollResult {
     Unchanged,
@@ -113,3 +341,110 @@ impl ScrollResult {
         matches!(self, Self::Changed)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    const DURATION: Duration = Duration::from_millis(100);
+
+    fn scroller() -> Scroller {
+        let mut scroller = Scroller::new(Size2D::new(100.0, 100.0));
+        scroller.did_content_resize(Size2D::new(100.0, 1000.0));
+        scroller
+    }
+
+    fn start_time(scroller: &Scroller) -> Instant {
+        scroller.smooth_scroll.expect("smooth scroll should be in progress").start_time
+    }
+
+    #[rstest]
+    #[case(0.0, 0.0)]
+    #[case(0.5, 0.5)]
+    #[case(1.0, 1.0)]
+    #[case(-1.0, 0.0)]
+    #[case(2.0, 1.0)]
+    fn ease_in_out_fixed_points(#[case] progress: f64, #[case] expected: f64) {
+        assert!((ease_in_out(progress) - expected).abs() < 1e-6, "ease_in_out({progress}) = {}", ease_in_out(progress));
+    }
+
+    #[test]
+    fn ease_in_out_is_symmetric_and_monotonic() {
+        let mut previous = 0.0;
+        for step in 1..=100 {
+            let progress = step as f64 / 100.0;
+            let value = ease_in_out(progress);
+            assert!(value >= previous, "ease_in_out({progress}) = {value} < {previous}");
+            assert!((value + ease_in_out(1.0 - progress) - 1.0).abs() < 1e-6);
+            previous = value;
+        }
+
+        // Slower than linear at the start, faster in the middle.
+        assert!(ease_in_out(0.25) < 0.25);
+        assert!(ease_in_out(0.75) > 0.75);
+    }
+
+    #[test]
+    fn smooth_scroll_interpolates_until_finished() {
+        let mut scroller = scroller();
+        assert_eq!(scroller.smooth_scroll_to(Point2D::new(0.0, 300.0), DURATION), ScrollResult::Changed);
+        assert!(scroller.is_smooth_scrolling());
+        assert_eq!(scroller.viewport_position(), Point2D::zero());
+
+        let start_time = start_time(&scroller);
+
+        assert_eq!(scroller.advance_smooth_scroll(start_time + DURATION / 4), ScrollResult::Changed);
+        let quarter = scroller.viewport_position().y;
+        assert!(quarter > 0.0 && quarter < 75.0, "position after a quarter: {quarter}");
+
+        scroller.advance_smooth_scroll(start_time + DURATION / 2);
+        assert!((scroller.viewport_position().y - 150.0).abs() < 1e-3);
+        assert!(scroller.is_smooth_scrolling());
+
+        assert_eq!(scroller.advance_smooth_scroll(start_time + DURATION * 2), ScrollResult::Changed);
+        assert_eq!(scroller.viewport_position(), Point2D::new(0.0, 300.0));
+        assert!(!scroller.is_smooth_scrolling());
+
+        assert_eq!(scroller.advance_smooth_scroll(start_time + DURATION * 3), ScrollResult::Unchanged);
+    }
+
+    #[test]
+    fn smooth_scroll_target_is_clamped_to_content() {
+        let mut scroller = scroller();
+        scroller.smooth_scroll_to(Point2D::new(0.0, 5000.0), DURATION);
+        scroller.advance_smooth_scroll(start_time(&scroller) + DURATION);
+        assert_eq!(scroller.viewport_position(), Point2D::new(0.0, 900.0));
+
+        assert_eq!(scroller.smooth_scroll_to(Point2D::new(0.0, 900.0), DURATION), ScrollResult::Unchanged);
+        assert!(!scroller.is_smooth_scrolling());
+    }
+
+    #[test]
+    fn page_down_continues_from_target() {
+        let mut scroller = scroller();
+        scroller.page_down();
+        scroller.page_down();
+
+        scroller.advance_smooth_scroll(start_time(&scroller) + PAGE_SCROLL_DURATION);
+        assert_eq!(scroller.viewport_position(), Point2D::new(0.0, 200.0));
+
+        scroller.page_up();
+        scroller.advance_smooth_scroll(start_time(&scroller) + PAGE_SCROLL_DURATION);
+        assert_eq!(scroller.viewport_position(), Point2D::new(0.0, 100.0));
+    }
+
+    #[test]
+    fn other_scrolls_cancel_smooth_scroll() {
+        let mut scroller = scroller();
+        scroller.page_down();
+        let start_time = start_time(&scroller);
+
+        scroller.scroll_pixels(0.0, -10.0);
+        assert!(!scroller.is_smooth_scrolling());
+
+        assert_eq!(scroller.advance_smooth_scroll(start_time + PAGE_SCROLL_DURATION), ScrollResult::Unchanged);
+        assert_eq!(scroller.viewport_position(), Point2D::new(0.0, 10.0));
+    }
+}
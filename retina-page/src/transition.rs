@@ -0,0 +1,391 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! The state of the running [CSS Transitions][spec], which animate the
+//! changes of computed values from their old to their new value.
+//!
+//! [spec]: https://drafts.csswg.org/css-transitions/
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use retina_dom::Node;
+use retina_style::{
+    CssEasingFunction,
+    CssInterpolatable,
+    CssTransition,
+    CssTransitionProperty,
+    Property,
+    Value,
+};
+use retina_style_computation::{
+    NodeId,
+    PropertyMap,
+    INTERPOLATABLE_PROPERTIES,
+};
+
+/// A transition of a single property of an element.
+#[derive(Clone, Debug)]
+pub(crate) struct TransitionAnimation {
+    pub(crate) from: Value,
+    pub(crate) to: Value,
+    pub(crate) duration: Duration,
+    pub(crate) easing: CssEasingFunction,
+
+    /// The moment the transition starts, i.e. after the `transition-delay`.
+    pub(crate) start_instant: Instant,
+}
+
+impl TransitionAnimation {
+    /// Whether or not the transition has completed at the given moment, after
+    /// which the property has its new value.
+    pub(crate) fn is_finished(&self, now: Instant) -> bool {
+        now >= self.start_instant + self.duration
+    }
+
+    /// Compute the value of the property at the given moment. Before the
+    /// transition starts, this is the start value.
+    pub(crate) fn value_at(&self, now: Instant) -> Value {
+        let elapsed = now.saturating_duration_since(self.start_instant);
+        let progress = if self.duration.is_zero() {
+            1.0
+        } else {
+            (elapsed.as_secs_f64() / self.duration.as_secs_f64()).min(1.0)
+        };
+
+        self.from.interpolate(&self.to, self.easing.evaluate(progress))
+            .unwrap_or_else(|| self.to.clone())
+    }
+}
+
+/// Tracks the computed values of the transitionable properties across style
+/// updates, to start transitions when they change, and overrides the computed
+/// values of the properties that are transitioning.
+///
+/// A style update is the generation of the layout tree, which calls
+/// [`TransitionState::update_style()`] for every element in between
+/// [`TransitionState::begin_style_update()`] and
+/// [`TransitionState::end_style_update()`].
+#[derive(Debug)]
+pub(crate) struct TransitionState {
+    animations: HashMap<(NodeId, Property), TransitionAnimation>,
+
+    /// The [after-change style][spec] values of the previous style update.
+    ///
+    /// [spec]: https://drafts.csswg.org/css-transitions/#after-change-style
+    previous_values: HashMap<(NodeId, Property), Value>,
+
+    /// The after-change style values of the style update in progress.
+    current_values: HashMap<(NodeId, Property), Value>,
+
+    /// The moment of the style update in progress.
+    now: Instant,
+}
+
+impl Default for TransitionState {
+    fn default() -> Self {
+        Self {
+            animations: HashMap::new(),
+            previous_values: HashMap::new(),
+            current_values: HashMap::new(),
+            now: Instant::now(),
+        }
+    }
+}
+
+impl TransitionState {
+    /// Whether or not there are transitions that still have to be painted,
+    /// which requires the styles to be updated for the next frame.
+    pub(crate) fn has_running_transitions(&self) -> bool {
+        !self.animations.is_empty()
+    }
+
+    pub(crate) fn begin_style_update(&mut self, now: Instant) {
+        self.now = now;
+        self.current_values.clear();
+    }
+
+    /// Start the transitions of the properties of the element of which the
+    /// computed value changed, and override the computed values of the
+    /// properties that are transitioning.
+    ///
+    /// # References
+    /// * [CSS Transitions § 3. Starting of transitions](https://drafts.csswg.org/css-transitions/#starting)
+    pub(crate) fn update_style(&mut self, node: &Node, style: &mut PropertyMap) {
+        if !node.is_element() {
+            return;
+        }
+
+        let node_id = NodeId::of(node);
+        for &property in INTERPOLATABLE_PROPERTIES {
+            let Some(after_change) = style.interpolatable_value(property) else {
+                continue;
+            };
+
+            let key = (node_id, property);
+            self.update_property(key, &after_change, style.transition());
+            self.current_values.insert(key, after_change);
+
+            if let Some(animation) = self.animations.get(&key) {
+                _ = style.apply_property(property, animation.value_at(self.now));
+            }
+        }
+    }
+
+    fn update_property(
+        &mut self,
+        key: (NodeId, Property),
+        after_change: &Value,
+        transitions: &[CssTransition],
+    ) {
+        if let Some(animation) = self.animations.get(&key) {
+            if animation.is_finished(self.now) {
+                self.animations.remove(&key);
+            } else if &animation.to == after_change {
+                return;
+            }
+        }
+
+        let Some(before_change) = self.before_change_value(key) else {
+            return;
+        };
+
+        if &before_change == after_change {
+            return;
+        }
+
+        // A change without a matching `transition` cancels the running
+        // transition, such that the new value applies immediately.
+        let Some(transition) = matching_transition(transitions, key.1) else {
+            self.animations.remove(&key);
+            return;
+        };
+
+        if transition.duration.is_zero() || before_change.interpolate(after_change, 0.5).is_none() {
+            self.animations.remove(&key);
+            return;
+        }
+
+        self.animations.insert(key, TransitionAnimation {
+            from: before_change,
+            to: after_change.clone(),
+            duration: transition.duration,
+            easing: transition.timing_function,
+            start_instant: self.now + transition.delay,
+        });
+    }
+
+    /// Get the [before-change style][spec] value, which is the current value
+    /// of a running transition, or the value of the previous style update.
+    ///
+    /// [spec]: https://drafts.csswg.org/css-transitions/#before-change-style
+    fn before_change_value(&self, key: (NodeId, Property)) -> Option<Value> {
+        if let Some(animation) = self.animations.get(&key) {
+            return Some(animation.value_at(self.now));
+        }
+
+        self.previous_values.get(&key).cloned()
+    }
+
+    /// Forget the values and transitions of the elements that weren't styled
+    /// in this update, e.g. because they were removed.
+    pub(crate) fn end_style_update(&mut self) {
+        std::mem::swap(&mut self.previous_values, &mut self.current_values);
+        self.current_values.clear();
+
+        let now = self.now;
+        let values = &self.previous_values;
+        self.animations.retain(|key, animation| {
+            values.contains_key(key) && !animation.is_finished(now)
+        });
+    }
+}
+
+/// Find the item of the `transition` list applying to the property. When
+/// multiple items match, the last one wins.
+///
+/// [spec]: https://drafts.csswg.org/css-transitions/#transition-property-property
+fn matching_transition(transitions: &[CssTransition], property: Property) -> Option<&CssTransition> {
+    transitions.iter()
+        .rev()
+        .find(|transition| match transition.property {
+            CssTransitionProperty::All => true,
+            CssTransitionProperty::None => false,
+            CssTransitionProperty::Property(transition_property) => transition_property == property,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use retina_style::{CssDecimal, CssOpacity};
+    use rstest::rstest;
+
+    fn element() -> Node {
+        let document = retina_dom::Parser::parse("<div></div>");
+
+        let mut element = None;
+        document.for_each_child_node_recursive_handle(&mut |node| {
+            if node.tag_name() == Some("div") {
+                element = Some(node.clone());
+            }
+        });
+        element.unwrap()
+    }
+
+    fn opacity_transition(duration: u64, delay: u64) -> CssTransition {
+        CssTransition {
+            property: CssTransitionProperty::Property(Property::Opacity),
+            duration: Duration::from_millis(duration),
+            timing_function: CssEasingFunction::Linear,
+            delay: Duration::from_millis(delay),
+        }
+    }
+
+    /// Run a style update of the element at `now`, and return the opacity
+    /// after the transitions are applied.
+    fn update(
+        state: &mut TransitionState,
+        element: &Node,
+        now: Instant,
+        opacity: CssDecimal,
+        transitions: &[CssTransition],
+    ) -> CssDecimal {
+        let mut style = PropertyMap {
+            opacity: Some(CssOpacity(opacity)),
+            transition: Some(transitions.to_vec()),
+            ..Default::default()
+        };
+
+        state.begin_style_update(now);
+        state.update_style(element, &mut style);
+        state.end_style_update();
+
+        style.opacity().0
+    }
+
+    fn assert_opacity(actual: CssDecimal, expected: CssDecimal) {
+        assert!((actual - expected).abs() < 1e-6, "opacity is {actual}, expected {expected}");
+    }
+
+    fn after(start: Instant, millis: u64) -> Instant {
+        start + Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn first_style_update_doesnt_transition() {
+        let element = element();
+        let mut state = TransitionState::default();
+        let start = Instant::now();
+
+        assert_opacity(update(&mut state, &element, start, 0.5, &[opacity_transition(100, 0)]), 0.5);
+        assert!(!state.has_running_transitions());
+    }
+
+    #[test]
+    fn change_starts_transition() {
+        let element = element();
+        let mut state = TransitionState::default();
+        let start = Instant::now();
+        let transitions = &[opacity_transition(100, 0)];
+
+        update(&mut state, &element, start, 0.0, transitions);
+        assert_opacity(update(&mut state, &element, start, 1.0, transitions), 0.0);
+        assert!(state.has_running_transitions());
+
+        assert_opacity(update(&mut state, &element, after(start, 50), 1.0, transitions), 0.5);
+        assert!(state.has_running_transitions());
+
+        assert_opacity(update(&mut state, &element, after(start, 100), 1.0, transitions), 1.0);
+        assert!(!state.has_running_transitions());
+    }
+
+    #[test]
+    fn delay_postpones_transition() {
+        let element = element();
+        let mut state = TransitionState::default();
+        let start = Instant::now();
+        let transitions = &[opacity_transition(100, 50)];
+
+        update(&mut state, &element, start, 0.0, transitions);
+        update(&mut state, &element, start, 1.0, transitions);
+
+        assert_opacity(update(&mut state, &element, after(start, 25), 1.0, transitions), 0.0);
+        assert_opacity(update(&mut state, &element, after(start, 100), 1.0, transitions), 0.5);
+        assert_opacity(update(&mut state, &element, after(start, 150), 1.0, transitions), 1.0);
+        assert!(!state.has_running_transitions());
+    }
+
+    #[test]
+    fn change_during_transition_starts_from_current_value() {
+        let element = element();
+        let mut state = TransitionState::default();
+        let start = Instant::now();
+        let transitions = &[opacity_transition(100, 0)];
+
+        update(&mut state, &element, start, 0.0, transitions);
+        update(&mut state, &element, start, 1.0, transitions);
+
+        // The before-change value is the current value of the running
+        // transition, i.e. 0.5, instead of the previous value of 1.0.
+        assert_opacity(update(&mut state, &element, after(start, 50), 0.0, transitions), 0.5);
+        assert_opacity(update(&mut state, &element, after(start, 100), 0.0, transitions), 0.25);
+        assert_opacity(update(&mut state, &element, after(start, 150), 0.0, transitions), 0.0);
+        assert!(!state.has_running_transitions());
+    }
+
+    #[test]
+    fn change_without_matching_transition_cancels_transition() {
+        let element = element();
+        let mut state = TransitionState::default();
+        let start = Instant::now();
+        let transitions = &[opacity_transition(100, 0)];
+
+        update(&mut state, &element, start, 0.0, transitions);
+        update(&mut state, &element, start, 1.0, transitions);
+        assert!(state.has_running_transitions());
+
+        assert_opacity(update(&mut state, &element, after(start, 50), 0.25, &[]), 0.25);
+        assert!(!state.has_running_transitions());
+    }
+
+    #[test]
+    fn unstyled_element_forgets_its_transitions() {
+        let element = element();
+        let mut state = TransitionState::default();
+        let start = Instant::now();
+        let transitions = &[opacity_transition(100, 0)];
+
+        update(&mut state, &element, start, 0.0, transitions);
+        update(&mut state, &element, start, 1.0, transitions);
+        assert!(state.has_running_transitions());
+
+        state.begin_style_update(after(start, 50));
+        state.end_style_update();
+        assert!(!state.has_running_transitions());
+    }
+
+    #[rstest]
+    #[case(&[CssTransitionProperty::All, CssTransitionProperty::Property(Property::Opacity)], Some(1))]
+    #[case(&[CssTransitionProperty::Property(Property::Opacity), CssTransitionProperty::All], Some(1))]
+    #[case(&[CssTransitionProperty::Property(Property::Opacity), CssTransitionProperty::Property(Property::Color)], Some(0))]
+    #[case(&[CssTransitionProperty::Property(Property::Color)], None)]
+    #[case(&[CssTransitionProperty::None], None)]
+    fn matching_transition_last_one_wins(#[case] properties: &[CssTransitionProperty], #[case] expected: Option<usize>) {
+        let transitions: Vec<CssTransition> = properties.iter()
+            .enumerate()
+            .map(|(index, &property)| CssTransition {
+                property,
+                duration: Duration::from_millis(index as u64),
+                ..Default::default()
+            })
+            .collect();
+
+        let actual = matching_transition(&transitions, Property::Opacity)
+            .map(|transition| transition.duration.as_millis() as usize);
+        assert_eq!(actual, expected);
+    }
+}
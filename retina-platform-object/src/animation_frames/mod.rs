@@ -0,0 +1,137 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! The [animation frames](https://html.spec.whatwg.org/multipage/imagebitmap-and-animations.html#animation-frames)
+//! of the `AnimationFrameProvider` mixin, i.e. `requestAnimationFrame()`.
+
+use std::{
+    collections::BTreeMap,
+    time::Instant,
+};
+
+use boa_engine::prelude::*;
+
+use boa_engine::object::builtins::JsFunction;
+use boa_gc::{Finalize, Trace, custom_trace};
+use log::error;
+
+use crate::prelude::*;
+
+/// The handle of an animation frame callback, which is returned by
+/// `requestAnimationFrame()` and can be passed to `cancelAnimationFrame()`.
+pub type AnimationFrameHandle = u32;
+
+/// The [map of animation frame callbacks](https://html.spec.whatwg.org/multipage/imagebitmap-and-animations.html#list-of-animation-frame-callbacks)
+/// of a `Window`, which are run before the next frame is painted.
+#[derive(Finalize, Default)]
+pub struct AnimationFrameCallbacks {
+    /// The [animation frame callback identifier](https://html.spec.whatwg.org/multipage/imagebitmap-and-animations.html#animation-frame-callback-identifier).
+    last_handle: AnimationFrameHandle,
+
+    /// The callbacks by their handle. Since handles are increasing, the
+    /// callbacks are ordered by the time they were requested.
+    callbacks: BTreeMap<AnimationFrameHandle, JsFunction>,
+}
+
+unsafe impl Trace for AnimationFrameCallbacks {
+    custom_trace!(this, {
+        mark(&this.callbacks);
+    });
+}
+
+impl AnimationFrameCallbacks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.callbacks.is_empty()
+    }
+
+    fn insert(&mut self, callback: JsFunction) -> AnimationFrameHandle {
+        // 2. Increment target's animation frame callback identifier by one,
+        //    and let handle be the result.
+        self.last_handle += 1;
+        let handle = self.last_handle;
+
+        // 3. Let callbacks be target's map of animation frame callbacks.
+        // 4. Set callbacks[handle] to callback.
+        self.callbacks.insert(handle, callback);
+
+        // 5. Return handle.
+        handle
+    }
+}
+
+/// [HTML Living Standard § 8.10 `requestAnimationFrame()`](https://html.spec.whatwg.org/multipage/imagebitmap-and-animations.html#dom-animationframeprovider-requestanimationframe)
+pub fn request_animation_frame(_this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+    let callback = args.first()
+        .and_then(JsValue::as_callable)
+        .cloned()
+        .and_then(JsFunction::from_object)
+        .ok_or_else(|| JsNativeError::typ().with_message("The callback is not a function"))?;
+
+    let handle = context.with_window(|window| {
+        window.animation_frame_callbacks().borrow_mut().insert(callback)
+    });
+
+    Ok(JsValue::new(handle))
+}
+
+/// [HTML Living Standard § 8.10 `cancelAnimationFrame()`](https://html.spec.whatwg.org/multipage/imagebitmap-and-animations.html#animationframeprovider-cancelanimationframe)
+pub fn cancel_animation_frame(_this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+    let handle = args.first()
+        .cloned()
+        .unwrap_or_default()
+        .to_number(context)?;
+
+    // 2. Let callbacks be this's target object's map of animation frame
+    //    callbacks.
+    // 3. Remove callbacks[handle].
+    if handle.is_finite() && handle >= 1.0 && handle <= AnimationFrameHandle::MAX as f64 {
+        context.with_window(|window| {
+            window.animation_frame_callbacks().borrow_mut().callbacks.remove(&(handle as AnimationFrameHandle))
+        });
+    }
+
+    Ok(JsValue::Undefined)
+}
+
+/// [Run the animation frame callbacks](https://html.spec.whatwg.org/multipage/imagebitmap-and-animations.html#run-the-animation-frame-callbacks)
+/// with the timestamp of `now`. Callbacks that are requested by these
+/// callbacks are run in the next frame. Returns the number of callbacks that
+/// ran.
+pub fn run_animation_frame_callbacks(now: Instant, context: &mut Context) -> usize {
+    let timestamp = context.with_window(|window| window.relative_high_resolution_time(now));
+
+    // 1. Let callbacks be target's map of animation frame callbacks.
+    // 2. Let callbackHandles be the result of getting the keys of callbacks.
+    let callback_handles: Vec<AnimationFrameHandle> = context.with_window(|window| {
+        window.animation_frame_callbacks().borrow().callbacks.keys().copied().collect()
+    });
+
+    let mut count = 0;
+
+    // 3. For each handle in callbackHandles, if handle exists in callbacks:
+    for handle in callback_handles {
+        // 3.1. Let callback be callbacks[handle].
+        // 3.2. Remove callbacks[handle].
+        let callback = context.with_window(|window| {
+            window.animation_frame_callbacks().borrow_mut().callbacks.remove(&handle)
+        });
+
+        let Some(callback) = callback else {
+            continue;
+        };
+
+        // 3.3. Invoke callback, passing « now » and "report".
+        if let Err(e) = callback.call(&JsValue::Undefined, &[JsValue::new(timestamp)], context) {
+            error!("[animation-frames] Uncaught exception in callback {handle}: {e}");
+        }
+
+        context.run_jobs();
+        count += 1;
+    }
+
+    count
+}
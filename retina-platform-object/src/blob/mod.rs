@@ -0,0 +1,166 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! The [`Blob`](https://w3c.github.io/FileAPI/#blob-section) interface of the
+//! File API.
+
+use boa_engine::prelude::*;
+
+use boa_engine::class::{Class, ClassBuilder};
+use boa_engine::object::ObjectData;
+use boa_engine::object::builtins::{JsArray, JsArrayBuffer, JsPromise};
+use boa_engine::property::Attribute;
+use boa_gc::{Finalize, Trace, empty_trace};
+
+use crate::prelude::*;
+
+/// The [Interface `Blob`](https://w3c.github.io/FileAPI/#blob-section)
+/// platform object, which is immutable raw data.
+#[derive(Finalize)]
+pub struct Blob {
+    bytes: Vec<u8>,
+    content_type: String,
+}
+
+unsafe impl Trace for Blob {
+    empty_trace!();
+}
+
+impl Blob {
+    pub fn new(bytes: Vec<u8>, content_type: String) -> Self {
+        Self {
+            bytes,
+            content_type: normalize_type(&content_type),
+        }
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn content_type(&self) -> &str {
+        &self.content_type
+    }
+
+    pub fn create_object(self, context: &mut Context) -> JsResult<JsObject> {
+        Ok(JsObject::from_proto_and_data(
+            Some(class_prototype::<Self>(context)?),
+            ObjectData::native_object(self),
+        ))
+    }
+
+    fn with_blob<T>(this: &JsValue, f: impl FnOnce(&Self) -> T) -> JsResult<T> {
+        let this = this.as_object()
+            .and_then(|obj| obj.downcast_ref::<Self>())
+            .ok_or_else(|| JsError::from_opaque("Value is not `Blob`".into()))?;
+        Ok(f(&this))
+    }
+
+    /// [File API § 3.2 `size`](https://w3c.github.io/FileAPI/#dfn-size)
+    fn get_size(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_blob(this, |blob| JsValue::new(blob.bytes.len() as f64))
+    }
+
+    /// [File API § 3.2 `type`](https://w3c.github.io/FileAPI/#dfn-type)
+    fn get_type(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_blob(this, |blob| JsValue::String(JsString::from(blob.content_type.as_str())))
+    }
+
+    /// [File API § 3.3.3 `text()`](https://w3c.github.io/FileAPI/#dom-blob-text)
+    fn text(this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let text = Self::with_blob(this, |blob| String::from_utf8_lossy(&blob.bytes).into_owned())?;
+        Ok(JsPromise::resolve(JsString::from(text), context)?.into())
+    }
+
+    /// [File API § 3.3.4 `arrayBuffer()`](https://w3c.github.io/FileAPI/#dom-blob-arraybuffer)
+    fn array_buffer(this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let bytes = Self::with_blob(this, |blob| blob.bytes.clone())?;
+        let buffer = JsArrayBuffer::from_byte_block(bytes, context)?;
+        Ok(JsPromise::resolve(buffer, context)?.into())
+    }
+}
+
+impl Class for Blob {
+    const NAME: &'static str = "Blob";
+    const LENGTH: usize = 0;
+    const ATTRIBUTES: Attribute = Attribute::empty();
+
+    /// [File API § 3.1 Constructors](https://w3c.github.io/FileAPI/#constructorBlob)
+    fn constructor(_this: &JsValue, args: &[JsValue], context: &mut Context<'_>) -> JsResult<Self> {
+        let mut bytes = Vec::new();
+
+        // 2. Let bytes be the result of processing blob parts given blobParts
+        //    and options.
+        if let Some(parts) = args.first().filter(|parts| !parts.is_undefined()) {
+            let parts = parts.as_object()
+                .cloned()
+                .and_then(|object| JsArray::from_object(object).ok())
+                .ok_or_else(|| JsNativeError::typ().with_message("`blobParts` is not a sequence"))?;
+
+            for index in 0..parts.length(context)? {
+                let part = parts.get(index, context)?;
+                bytes.extend(bytes_of_part(&part, context)?);
+            }
+        }
+
+        // 3. If the type member of the options argument is not the empty
+        //    string, run the following sub-steps:
+        let content_type = match args.get(1).and_then(JsValue::as_object) {
+            Some(options) => {
+                let content_type = options.get("type", context)?;
+                if content_type.is_undefined() {
+                    String::new()
+                } else {
+                    content_type.to_string(context)?.to_std_string_escaped()
+                }
+            }
+            None => String::new(),
+        };
+
+        Ok(Self::new(bytes, content_type))
+    }
+
+    fn init(class: &mut ClassBuilder<'_, '_>) -> JsResult<()> {
+        class.accessor_with_function_ptr("size", Some(Self::get_size), None, Attribute::all());
+        class.accessor_with_function_ptr("type", Some(Self::get_type), None, Attribute::all());
+        class.method("arrayBuffer", 0, NativeFunction::from_fn_ptr(Self::array_buffer));
+        class.method("text", 0, NativeFunction::from_fn_ptr(Self::text));
+        Ok(())
+    }
+}
+
+/// Get the bytes of an element of `blobParts`, which is an `ArrayBuffer`, a
+/// `Blob` or otherwise converted to a string, as per
+/// [process blob parts](https://w3c.github.io/FileAPI/#process-blob-parts).
+fn bytes_of_part(part: &JsValue, context: &mut Context) -> JsResult<Vec<u8>> {
+    if let Some(object) = part.as_object() {
+        if let Some(bytes) = array_buffer_bytes(object) {
+            return Ok(bytes);
+        }
+
+        if let Some(blob) = object.downcast_ref::<Blob>() {
+            return Ok(blob.bytes.clone());
+        }
+    }
+
+    Ok(part.to_string(context)?.to_std_string_escaped().into_bytes())
+}
+
+/// Copy the bytes of the `object` if it is an `ArrayBuffer` that isn't
+/// detached.
+pub(crate) fn array_buffer_bytes(object: &JsObject) -> Option<Vec<u8>> {
+    object.borrow()
+        .as_array_buffer()
+        .and_then(|buffer| buffer.array_buffer_data.clone())
+}
+
+/// The [`type`](https://w3c.github.io/FileAPI/#dfn-type) is empty if it
+/// contains characters outside of U+0020 to U+007E, and is otherwise
+/// converted to ASCII lowercase.
+fn normalize_type(content_type: &str) -> String {
+    if content_type.chars().all(|c| (' '..='~').contains(&c)) {
+        content_type.to_ascii_lowercase()
+    } else {
+        String::new()
+    }
+}
@@ -0,0 +1,446 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! The [Canvas 2D API](https://html.spec.whatwg.org/multipage/canvas.html#2dcontext).
+
+use boa_engine::prelude::*;
+
+use boa_engine::class::{Class, ClassBuilder};
+use boa_engine::object::ObjectData;
+use boa_engine::property::Attribute;
+use std::sync::Arc;
+
+use boa_gc::{Finalize, Trace, empty_trace};
+use retina_common::Color;
+use retina_dom::{
+    CanvasBitmap,
+    CanvasImageSource,
+    HtmlCanvasElement,
+    Node,
+};
+use retina_gfx::raster::{
+    self,
+    CanvasError,
+    CanvasFillRule,
+    CanvasFont,
+    RasterImage,
+    RasterSurface,
+};
+use retina_style::CssColor;
+
+use crate::dom::HTMLElement;
+use crate::prelude::*;
+
+/// The [Interface `CanvasRenderingContext2D`](https://html.spec.whatwg.org/multipage/canvas.html#canvasrenderingcontext2d)
+/// platform object, as returned by `canvas.getContext("2d")`.
+#[derive(Finalize)]
+pub struct CanvasRenderingContext2D {
+    canvas: Node,
+    context: raster::CanvasRenderingContext2D,
+}
+
+impl CanvasRenderingContext2D {
+    /// Get the `2d` context of the `canvas` element, which is created the
+    /// first time, such that every `getContext("2d")` call returns the same
+    /// context.
+    pub fn new(node: Node, canvas: &HtmlCanvasElement) -> Option<Self> {
+        let context = canvas.rendering_context(|bitmap| {
+            let surface = Arc::new(CanvasBitmapSurface(bitmap.clone()));
+            Arc::new(raster::CanvasRenderingContext2D::new(surface))
+        });
+
+        Some(Self {
+            canvas: node,
+            context: context.downcast_ref::<raster::CanvasRenderingContext2D>()?.clone(),
+        })
+    }
+
+    /// Create a JavaScript object, using the prototype of the registered
+    /// `CanvasRenderingContext2D` class.
+    pub fn create_object(self, context: &mut Context) -> JsResult<JsObject> {
+        let prototype = class_prototype::<Self>(context)?;
+        Ok(JsObject::from_proto_and_data(
+            Some(prototype),
+            ObjectData::native_object(self),
+        ))
+    }
+
+    fn with_context<T>(this: &JsValue, f: impl FnOnce(&raster::CanvasRenderingContext2D) -> T) -> JsResult<T> {
+        let this = this.as_object()
+            .and_then(|obj| obj.downcast_ref::<Self>())
+            .ok_or_else(|| JsError::from_opaque("Value is not `CanvasRenderingContext2D`".into()))?;
+        Ok(f(&this.context))
+    }
+
+    /// [HTML Living Standard § 4.12.5.1 `canvas`](https://html.spec.whatwg.org/multipage/canvas.html#dom-context-2d-canvas)
+    pub fn get_canvas(this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let canvas = this.as_object()
+            .and_then(|obj| obj.downcast_ref::<Self>())
+            .map(|this| this.canvas.clone())
+            .ok_or_else(|| JsError::from_opaque("Value is not `CanvasRenderingContext2D`".into()))?;
+
+        Ok(JsValue::Object(HTMLElement::create_object(canvas, context)?))
+    }
+
+    /// [HTML Living Standard § 4.12.5.1.1 `save()`](https://html.spec.whatwg.org/multipage/canvas.html#dom-context-2d-save)
+    pub fn save(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_context(this, |context| context.save())?;
+        Ok(JsValue::Undefined)
+    }
+
+    /// [HTML Living Standard § 4.12.5.1.1 `restore()`](https://html.spec.whatwg.org/multipage/canvas.html#dom-context-2d-restore)
+    pub fn restore(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_context(this, |context| context.restore())?;
+        Ok(JsValue::Undefined)
+    }
+
+    /// [HTML Living Standard § 4.12.5.1.8 `translate()`](https://html.spec.whatwg.org/multipage/canvas.html#dom-context-2d-translate)
+    pub fn translate(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        if let Some([x, y]) = finite_number_arguments(args, context)? {
+            Self::with_context(this, |context| context.translate(x, y))?;
+        }
+        Ok(JsValue::Undefined)
+    }
+
+    /// [HTML Living Standard § 4.12.5.1.8 `rotate()`](https://html.spec.whatwg.org/multipage/canvas.html#dom-context-2d-rotate)
+    pub fn rotate(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        if let Some([angle]) = finite_number_arguments(args, context)? {
+            Self::with_context(this, |context| context.rotate(angle))?;
+        }
+        Ok(JsValue::Undefined)
+    }
+
+    /// [HTML Living Standard § 4.12.5.1.8 `scale()`](https://html.spec.whatwg.org/multipage/canvas.html#dom-context-2d-scale)
+    pub fn scale(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        if let Some([x, y]) = finite_number_arguments(args, context)? {
+            Self::with_context(this, |context| context.scale(x, y))?;
+        }
+        Ok(JsValue::Undefined)
+    }
+
+    /// [HTML Living Standard § 4.12.5.1.8 `transform()`](https://html.spec.whatwg.org/multipage/canvas.html#dom-context-2d-transform)
+    pub fn transform(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        if let Some([a, b, c, d, e, f]) = finite_number_arguments(args, context)? {
+            Self::with_context(this, |context| context.transform(a, b, c, d, e, f))?;
+        }
+        Ok(JsValue::Undefined)
+    }
+
+    /// [HTML Living Standard § 4.12.5.1.10 `fillStyle`](https://html.spec.whatwg.org/multipage/canvas.html#dom-context-2d-fillstyle)
+    pub fn get_fill_style(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        let color = Self::with_context(this, |context| context.fill_style())?;
+        Ok(JsValue::String(serialize_color(color).into()))
+    }
+
+    pub fn set_fill_style(this: &JsValue, args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        if let Some(color) = parse_color(args.first()) {
+            Self::with_context(this, |context| context.set_fill_style(color))?;
+        }
+        Ok(JsValue::Undefined)
+    }
+
+    /// [HTML Living Standard § 4.12.5.1.10 `strokeStyle`](https://html.spec.whatwg.org/multipage/canvas.html#dom-context-2d-strokestyle)
+    pub fn get_stroke_style(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        let color = Self::with_context(this, |context| context.stroke_style())?;
+        Ok(JsValue::String(serialize_color(color).into()))
+    }
+
+    pub fn set_stroke_style(this: &JsValue, args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        if let Some(color) = parse_color(args.first()) {
+            Self::with_context(this, |context| context.set_stroke_style(color))?;
+        }
+        Ok(JsValue::Undefined)
+    }
+
+    /// [HTML Living Standard § 4.12.5.1.3 `lineWidth`](https://html.spec.whatwg.org/multipage/canvas.html#dom-context-2d-linewidth)
+    pub fn get_line_width(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        let line_width = Self::with_context(this, |context| context.line_width())?;
+        Ok(JsValue::from(line_width as f64))
+    }
+
+    pub fn set_line_width(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let line_width = args.first()
+            .cloned()
+            .unwrap_or_default()
+            .to_number(context)?;
+
+        Self::with_context(this, |context| context.set_line_width(line_width as f32))?;
+        Ok(JsValue::Undefined)
+    }
+
+    /// [HTML Living Standard § 4.12.5.1.4 `font`](https://html.spec.whatwg.org/multipage/canvas.html#dom-context-2d-font)
+    pub fn get_font(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        let font = Self::with_context(this, |context| context.font())?;
+        Ok(JsValue::String(font.as_str().into()))
+    }
+
+    pub fn set_font(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let font = args.first()
+            .cloned()
+            .unwrap_or_default()
+            .to_string(context)?
+            .to_std_string_escaped();
+
+        // Values that can't be parsed are ignored.
+        if let Some(font) = CanvasFont::parse(&font) {
+            Self::with_context(this, |context| context.set_font(font))?;
+        }
+        Ok(JsValue::Undefined)
+    }
+
+    /// [HTML Living Standard § 4.12.5.1.12 `clearRect()`](https://html.spec.whatwg.org/multipage/canvas.html#dom-context-2d-clearrect)
+    pub fn clear_rect(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        if let Some([x, y, width, height]) = finite_number_arguments(args, context)? {
+            Self::with_context(this, |context| context.clear_rect(x, y, width, height))?;
+        }
+        Ok(JsValue::Undefined)
+    }
+
+    /// [HTML Living Standard § 4.12.5.1.12 `fillRect()`](https://html.spec.whatwg.org/multipage/canvas.html#dom-context-2d-fillrect)
+    pub fn fill_rect(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        if let Some([x, y, width, height]) = finite_number_arguments(args, context)? {
+            Self::with_context(this, |context| context.fill_rect(x, y, width, height))?;
+        }
+        Ok(JsValue::Undefined)
+    }
+
+    /// [HTML Living Standard § 4.12.5.1.12 `strokeRect()`](https://html.spec.whatwg.org/multipage/canvas.html#dom-context-2d-strokerect)
+    pub fn stroke_rect(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        if let Some([x, y, width, height]) = finite_number_arguments(args, context)? {
+            Self::with_context(this, |context| context.stroke_rect(x, y, width, height))?;
+        }
+        Ok(JsValue::Undefined)
+    }
+
+    /// [HTML Living Standard § 4.12.5.1.13 `fillText()`](https://html.spec.whatwg.org/multipage/canvas.html#dom-context-2d-filltext)
+    ///
+    /// ## TODO
+    /// The `maxWidth` argument is ignored.
+    pub fn fill_text(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let text = args.first()
+            .cloned()
+            .unwrap_or_default()
+            .to_string(context)?
+            .to_std_string_escaped();
+
+        if let Some([x, y]) = finite_number_arguments(args.get(1..).unwrap_or_default(), context)? {
+            Self::with_context(this, |context| context.fill_text(&text, x, y))?;
+        }
+        Ok(JsValue::Undefined)
+    }
+
+    /// [HTML Living Standard § 4.12.5.1.13 `strokeText()`](https://html.spec.whatwg.org/multipage/canvas.html#dom-context-2d-stroketext)
+    ///
+    /// ## TODO
+    /// The `maxWidth` argument is ignored.
+    pub fn stroke_text(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let text = args.first()
+            .cloned()
+            .unwrap_or_default()
+            .to_string(context)?
+            .to_std_string_escaped();
+
+        if let Some([x, y]) = finite_number_arguments(args.get(1..).unwrap_or_default(), context)? {
+            Self::with_context(this, |context| context.stroke_text(&text, x, y))?;
+        }
+        Ok(JsValue::Undefined)
+    }
+
+    /// [HTML Living Standard § 4.12.5.1.16 `drawImage()`](https://html.spec.whatwg.org/multipage/canvas.html#dom-context-2d-drawimage)
+    pub fn draw_image(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let node = args.first()
+            .and_then(JsValue::as_object)
+            .and_then(|obj| obj.downcast_ref::<HTMLElement>().map(|element| element.node().clone()))
+            .ok_or_else(|| {
+                let message = "The image argument is not an `HTMLImageElement` or `HTMLCanvasElement`";
+                JsError::from_native(JsNativeError::typ().with_message(message))
+            })?;
+
+        let numbers = &args[1..];
+        let rects = match numbers.len() {
+            2 => finite_number_arguments::<2>(numbers, context)?.map(|[dx, dy]| (None, [dx, dy], None)),
+            4 => finite_number_arguments::<4>(numbers, context)?.map(|[dx, dy, dw, dh]| (None, [dx, dy], Some([dw, dh]))),
+            8 => finite_number_arguments::<8>(numbers, context)?
+                .map(|[sx, sy, sw, sh, dx, dy, dw, dh]| (Some([sx, sy, sw, sh]), [dx, dy], Some([dw, dh]))),
+            count => {
+                let message = format!("drawImage() expects 3, 5 or 9 arguments, but got {}", count + 1);
+                return Err(JsError::from_native(JsNativeError::typ().with_message(message)));
+            }
+        };
+
+        let Some((source, [dx, dy], destination_size)) = rects else {
+            return Ok(JsValue::Undefined);
+        };
+
+        // Images that aren't available (yet) aren't drawn.
+        let Some(image) = CanvasImageSource::from_node(&node) else {
+            return Ok(JsValue::Undefined);
+        };
+
+        let natural_size = [image.width() as f32, image.height() as f32];
+        let source = source.unwrap_or([0.0, 0.0, natural_size[0], natural_size[1]]);
+        let [dw, dh] = destination_size.unwrap_or(natural_size);
+
+        let image = RasterImage {
+            data: image.pixels().data(),
+            width: image.width(),
+            height: image.height(),
+        };
+        Self::with_context(this, |context| context.draw_image(image, source, [dx, dy, dw, dh]))?;
+        Ok(JsValue::Undefined)
+    }
+
+    /// [HTML Living Standard § 4.12.5.1.14 `beginPath()`](https://html.spec.whatwg.org/multipage/canvas.html#dom-context-2d-beginpath)
+    pub fn begin_path(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_context(this, |context| context.begin_path())?;
+        Ok(JsValue::Undefined)
+    }
+
+    /// [HTML Living Standard § 4.12.5.1.6 `moveTo()`](https://html.spec.whatwg.org/multipage/canvas.html#dom-context-2d-moveto)
+    pub fn move_to(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        if let Some([x, y]) = finite_number_arguments(args, context)? {
+            Self::with_context(this, |context| context.move_to(x, y))?;
+        }
+        Ok(JsValue::Undefined)
+    }
+
+    /// [HTML Living Standard § 4.12.5.1.6 `lineTo()`](https://html.spec.whatwg.org/multipage/canvas.html#dom-context-2d-lineto)
+    pub fn line_to(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        if let Some([x, y]) = finite_number_arguments(args, context)? {
+            Self::with_context(this, |context| context.line_to(x, y))?;
+        }
+        Ok(JsValue::Undefined)
+    }
+
+    /// [HTML Living Standard § 4.12.5.1.6 `arc()`](https://html.spec.whatwg.org/multipage/canvas.html#dom-context-2d-arc)
+    pub fn arc(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let Some([x, y, radius, start_angle, end_angle]) = finite_number_arguments(args, context)? else {
+            return Ok(JsValue::Undefined);
+        };
+
+        let counterclockwise = args.get(5).is_some_and(JsValue::to_boolean);
+
+        let result = Self::with_context(this, |context| {
+            context.arc(x, y, radius, start_angle, end_angle, counterclockwise)
+        })?;
+
+        match result {
+            Ok(()) => Ok(JsValue::Undefined),
+            Err(CanvasError::NegativeRadius) => {
+                let message = format!("IndexSizeError: The radius {radius} is negative");
+                Err(JsError::from_native(JsNativeError::range().with_message(message)))
+            }
+        }
+    }
+
+    /// [HTML Living Standard § 4.12.5.1.6 `closePath()`](https://html.spec.whatwg.org/multipage/canvas.html#dom-context-2d-closepath)
+    pub fn close_path(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_context(this, |context| context.close_path())?;
+        Ok(JsValue::Undefined)
+    }
+
+    /// [HTML Living Standard § 4.12.5.1.14 `fill()`](https://html.spec.whatwg.org/multipage/canvas.html#dom-context-2d-fill)
+    pub fn fill(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let fill_rule = match args.first() {
+            None | Some(JsValue::Undefined) => CanvasFillRule::default(),
+            Some(value) => {
+                let value = value.to_string(context)?.to_std_string_escaped();
+                value.parse().map_err(|_| {
+                    let message = format!("\"{value}\" is not a valid `CanvasFillRule`");
+                    JsError::from_native(JsNativeError::typ().with_message(message))
+                })?
+            }
+        };
+
+        Self::with_context(this, |context| context.fill(fill_rule))?;
+        Ok(JsValue::Undefined)
+    }
+
+    /// [HTML Living Standard § 4.12.5.1.14 `stroke()`](https://html.spec.whatwg.org/multipage/canvas.html#dom-context-2d-stroke)
+    pub fn stroke(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_context(this, |context| context.stroke())?;
+        Ok(JsValue::Undefined)
+    }
+}
+
+impl Class for CanvasRenderingContext2D {
+    const NAME: &'static str = "CanvasRenderingContext2D";
+    const LENGTH: usize = 0;
+    const ATTRIBUTES: Attribute = Attribute::empty();
+
+    fn constructor(_: &JsValue, _: &[JsValue], _: &mut Context<'_>) -> JsResult<Self> {
+        illegal_constructor()
+    }
+
+    fn init(class: &mut ClassBuilder<'_, '_>) -> JsResult<()> {
+        class.accessor_with_function_ptr("canvas", Some(Self::get_canvas), None, Attribute::all());
+        class.accessor_with_function_ptr("fillStyle", Some(Self::get_fill_style), Some(Self::set_fill_style), Attribute::all());
+        class.accessor_with_function_ptr("font", Some(Self::get_font), Some(Self::set_font), Attribute::all());
+        class.accessor_with_function_ptr("lineWidth", Some(Self::get_line_width), Some(Self::set_line_width), Attribute::all());
+        class.accessor_with_function_ptr("strokeStyle", Some(Self::get_stroke_style), Some(Self::set_stroke_style), Attribute::all());
+
+        class.method("arc", 5, NativeFunction::from_fn_ptr(Self::arc));
+        class.method("beginPath", 0, NativeFunction::from_fn_ptr(Self::begin_path));
+        class.method("clearRect", 4, NativeFunction::from_fn_ptr(Self::clear_rect));
+        class.method("closePath", 0, NativeFunction::from_fn_ptr(Self::close_path));
+        class.method("drawImage", 3, NativeFunction::from_fn_ptr(Self::draw_image));
+        class.method("fill", 0, NativeFunction::from_fn_ptr(Self::fill));
+        class.method("fillRect", 4, NativeFunction::from_fn_ptr(Self::fill_rect));
+        class.method("fillText", 3, NativeFunction::from_fn_ptr(Self::fill_text));
+        class.method("lineTo", 2, NativeFunction::from_fn_ptr(Self::line_to));
+        class.method("moveTo", 2, NativeFunction::from_fn_ptr(Self::move_to));
+        class.method("restore", 0, NativeFunction::from_fn_ptr(Self::restore));
+        class.method("rotate", 1, NativeFunction::from_fn_ptr(Self::rotate));
+        class.method("save", 0, NativeFunction::from_fn_ptr(Self::save));
+        class.method("scale", 2, NativeFunction::from_fn_ptr(Self::scale));
+        class.method("stroke", 0, NativeFunction::from_fn_ptr(Self::stroke));
+        class.method("strokeRect", 4, NativeFunction::from_fn_ptr(Self::stroke_rect));
+        class.method("strokeText", 3, NativeFunction::from_fn_ptr(Self::stroke_text));
+        class.method("transform", 6, NativeFunction::from_fn_ptr(Self::transform));
+        class.method("translate", 2, NativeFunction::from_fn_ptr(Self::translate));
+        Ok(())
+    }
+}
+
+unsafe impl Trace for CanvasRenderingContext2D { empty_trace!(); }
+
+/// Parse the value of `fillStyle` or `strokeStyle`, where values that aren't
+/// a string or a valid color are ignored.
+///
+/// ## TODO
+/// Support `CanvasGradient` and `CanvasPattern` objects.
+fn parse_color(value: Option<&JsValue>) -> Option<Color> {
+    let value = value?.as_string()?.to_std_string_escaped();
+    match retina_style_parser::parse_value_color(&value)? {
+        CssColor::Color(color) => Some(color),
+
+        // The canvas isn't associated with a computed style, so
+        // `currentcolor` is black.
+        CssColor::CurrentColor => Some(Color::BLACK),
+    }
+}
+
+/// [Serialize the color][spec] of a `fillStyle` or `strokeStyle`.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/canvas.html#serialisation-of-a-color
+fn serialize_color(color: Color) -> String {
+    if color.alpha_byte() == 255 {
+        format!("#{:02x}{:02x}{:02x}", color.red_byte(), color.green_byte(), color.blue_byte())
+    } else {
+        format!("rgba({}, {}, {}, {})", color.red_byte(), color.green_byte(), color.blue_byte(), color.alpha())
+    }
+}
+
+/// Lets the rasterizer of the graphics library draw onto the output bitmap of
+/// a `<canvas>` element.
+#[derive(Debug)]
+struct CanvasBitmapSurface(CanvasBitmap);
+
+impl RasterSurface for CanvasBitmapSurface {
+    fn draw(&self, f: &mut dyn FnMut(&mut [u8], u32, u32)) {
+        self.0.draw(|pixels| {
+            let (width, height) = (pixels.width(), pixels.height());
+            f(pixels.data_mut(), width, height);
+        });
+    }
+}
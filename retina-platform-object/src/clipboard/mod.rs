@@ -0,0 +1,88 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! The [Clipboard API](https://w3c.github.io/clipboard-apis/).
+
+use boa_engine::prelude::*;
+
+use boa_engine::class::{Class, ClassBuilder};
+use boa_engine::object::{builtins::JsPromise, ObjectData};
+use boa_engine::property::Attribute;
+use boa_gc::{Finalize, Trace, empty_trace};
+use copypasta::{ClipboardContext, ClipboardProvider};
+use log::warn;
+
+use crate::prelude::{class_prototype, dom_exception, illegal_constructor};
+
+/// The [Interface `Clipboard`](https://w3c.github.io/clipboard-apis/#clipboard-interface)
+/// platform object, available as `navigator.clipboard`.
+#[derive(Finalize)]
+pub struct Clipboard;
+
+impl Clipboard {
+    /// Create a JavaScript object, using the prototype of the registered
+    /// `Clipboard` class.
+    pub fn create_object(self, context: &mut Context) -> JsResult<JsObject> {
+        Ok(JsObject::from_proto_and_data(
+            Some(class_prototype::<Self>(context)?),
+            ObjectData::native_object(self),
+        ))
+    }
+
+    /// [Clipboard API § 5.2.3 `readText()`](https://w3c.github.io/clipboard-apis/#dom-clipboard-readtext)
+    pub fn read_text(_this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let result = ClipboardContext::new()
+            .and_then(|mut clipboard| clipboard.get_contents());
+
+        match result {
+            Ok(text) => Ok(JsPromise::resolve(JsString::from(text), context)?.into()),
+            Err(e) => {
+                let error = Self::not_allowed_error(e, context);
+                Ok(JsPromise::reject(error, context)?.into())
+            }
+        }
+    }
+
+    /// [Clipboard API § 5.2.5 `writeText()`](https://w3c.github.io/clipboard-apis/#dom-clipboard-writetext)
+    pub fn write_text(_this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let text = args.get(0)
+            .cloned()
+            .unwrap_or_default()
+            .to_string(context)?
+            .to_std_string_escaped();
+
+        let result = ClipboardContext::new()
+            .and_then(|mut clipboard| clipboard.set_contents(text));
+
+        match result {
+            Ok(()) => Ok(JsPromise::resolve(JsValue::Undefined, context)?.into()),
+            Err(e) => {
+                let error = Self::not_allowed_error(e, context);
+                Ok(JsPromise::reject(error, context)?.into())
+            }
+        }
+    }
+
+    fn not_allowed_error(error: Box<dyn std::error::Error + Send + Sync>, context: &mut Context) -> JsError {
+        warn!("[clipboard] Failed to access the system clipboard: {error}");
+        dom_exception("NotAllowedError", &error.to_string(), context)
+    }
+}
+
+impl Class for Clipboard {
+    const NAME: &'static str = "Clipboard";
+    const LENGTH: usize = 0;
+    const ATTRIBUTES: Attribute = Attribute::empty();
+
+    fn constructor(_: &JsValue, _: &[JsValue], _: &mut Context<'_>) -> JsResult<Self> {
+        illegal_constructor()
+    }
+
+    fn init(class: &mut ClassBuilder<'_, '_>) -> JsResult<()> {
+        class.method("readText", 0, NativeFunction::from_fn_ptr(Self::read_text));
+        class.method("writeText", 1, NativeFunction::from_fn_ptr(Self::write_text));
+        Ok(())
+    }
+}
+
+unsafe impl Trace for Clipboard { empty_trace!(); }
@@ -0,0 +1,630 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! The [Console Standard](https://console.spec.whatwg.org/), which writes the
+//! messages of the page to the [`log`] of the browser.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::{hash_map::Entry, HashMap},
+    time::Instant,
+};
+
+use boa_engine::prelude::*;
+
+use boa_engine::class::{Class, ClassBuilder};
+use boa_engine::object::{builtins::JsArray, ObjectData};
+use boa_engine::property::Attribute;
+use boa_gc::{Finalize, Trace, empty_trace};
+use log::{log, Level};
+
+use crate::prelude::{class_prototype, illegal_constructor};
+
+/// The label used by `count()`, `time()` and friends when none is given.
+const DEFAULT_LABEL: &str = "default";
+
+/// The [namespace `console`](https://console.spec.whatwg.org/#console-namespace)
+/// platform object, available as `window.console`.
+#[derive(Finalize)]
+pub struct Console {
+    /// The depth of the [group stack](https://console.spec.whatwg.org/#group-stack),
+    /// which is used to indent the messages.
+    group_depth: Cell<usize>,
+
+    /// The [count map](https://console.spec.whatwg.org/#count-map).
+    counts: RefCell<HashMap<String, u64>>,
+
+    /// The [timer table](https://console.spec.whatwg.org/#timer-table).
+    timers: RefCell<HashMap<String, Instant>>,
+}
+
+unsafe impl Trace for Console { empty_trace!(); }
+
+impl Console {
+    pub fn new() -> Self {
+        Self {
+            group_depth: Cell::new(0),
+            counts: RefCell::new(HashMap::new()),
+            timers: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Create a JavaScript object, using the prototype of the registered
+    /// `Console` class.
+    pub fn create_object(self, context: &mut Context) -> JsResult<JsObject> {
+        Ok(JsObject::from_proto_and_data(
+            Some(class_prototype::<Self>(context)?),
+            ObjectData::native_object(self),
+        ))
+    }
+
+    /// Run `f` with the console of `this`. Since `console` is a namespace,
+    /// the methods can also be called without a `this` value, e.g. when they
+    /// are passed as a callback, in which case the global `console` is used.
+    fn with_console<T>(this: &JsValue, context: &mut Context, f: impl FnOnce(&Self) -> T) -> JsResult<T> {
+        if let Some(console) = this.as_object().and_then(|object| object.downcast_ref::<Self>()) {
+            return Ok(f(&console));
+        }
+
+        let global = context.global_object().get("console", context)?;
+        let console = global.as_object()
+            .and_then(|object| object.downcast_ref::<Self>())
+            .ok_or_else(|| JsError::from_opaque("`console` is not `Console`".into()))?;
+        Ok(f(&console))
+    }
+
+    /// The [Printer](https://console.spec.whatwg.org/#printer) operation,
+    /// which indents the `message` according to the group stack.
+    fn print(&self, level: Level, message: &str) {
+        let indentation = "  ".repeat(self.group_depth.get());
+        for line in message.lines() {
+            log!(level, "[console] {indentation}{line}");
+        }
+    }
+
+    /// The [Logger](https://console.spec.whatwg.org/#logger) operation.
+    fn logger(this: &JsValue, level: Level, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 1. If args is empty, return.
+        if args.is_empty() {
+            return Ok(JsValue::Undefined);
+        }
+
+        let message = format_arguments(args, context)?;
+        Self::with_console(this, context, |console| console.print(level, &message))?;
+        Ok(JsValue::Undefined)
+    }
+
+    /// [Console Standard § 1.1.1 `assert(condition, ...data)`](https://console.spec.whatwg.org/#assert)
+    pub fn assert(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 1. If condition is true, return.
+        if args.first().is_some_and(JsValue::to_boolean) {
+            return Ok(JsValue::Undefined);
+        }
+
+        // 2. Let message be a string without any formatting specifiers
+        //    indicating generically an assertion failure (such as "Assertion
+        //    failed").
+        let message = JsValue::from(JsString::from("Assertion failed"));
+        let mut data = args.get(1..).unwrap_or_default().to_vec();
+
+        match data.first().and_then(JsValue::as_string).cloned() {
+            // 3. If data is empty, append message to data.
+            None if data.is_empty() => data.push(message),
+
+            // 4. Otherwise:
+            // 4.3. If first is not a String, then prepend message to data.
+            None => data.insert(0, message),
+
+            // 4.4. Otherwise:
+            // 4.4.1. Let concat be the concatenation of message, U+003A (:),
+            //        U+0020 SPACE, and first.
+            // 4.4.2. Set data[0] to concat.
+            Some(first) => {
+                data[0] = JsString::from(format!("Assertion failed: {}", first.to_std_string_escaped())).into();
+            }
+        }
+
+        // 5. Perform Logger("assert", data).
+        Self::logger(this, Level::Error, &data, context)
+    }
+
+    /// [Console Standard § 1.1.3 `debug(...data)`](https://console.spec.whatwg.org/#debug)
+    pub fn debug(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::logger(this, Level::Debug, args, context)
+    }
+
+    /// [Console Standard § 1.1.4 `error(...data)`](https://console.spec.whatwg.org/#error)
+    pub fn error(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::logger(this, Level::Error, args, context)
+    }
+
+    /// [Console Standard § 1.1.5 `info(...data)`](https://console.spec.whatwg.org/#info)
+    pub fn info(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::logger(this, Level::Info, args, context)
+    }
+
+    /// [Console Standard § 1.1.6 `log(...data)`](https://console.spec.whatwg.org/#log)
+    pub fn log(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::logger(this, Level::Info, args, context)
+    }
+
+    /// [Console Standard § 1.1.7 `table(tabularData, properties)`](https://console.spec.whatwg.org/#table)
+    pub fn table(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // Fall back to just logging the argument if it can’t be parsed as
+        // tabular.
+        let Some(data) = args.first().and_then(JsValue::as_object).cloned() else {
+            return Self::logger(this, Level::Info, args, context);
+        };
+
+        let filter = match args.get(1).and_then(JsValue::as_object) {
+            Some(properties) => Some(array_to_strings(properties, context)?),
+            None => None,
+        };
+
+        let table = format_table(&data, filter, context)?;
+        Self::with_console(this, context, |console| console.print(Level::Info, &table))?;
+        Ok(JsValue::Undefined)
+    }
+
+    /// [Console Standard § 1.1.9 `warn(...data)`](https://console.spec.whatwg.org/#warn)
+    pub fn warn(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::logger(this, Level::Warn, args, context)
+    }
+
+    /// [Console Standard § 1.2.1 `count(label)`](https://console.spec.whatwg.org/#count)
+    pub fn count(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let label = label_argument(args, context)?;
+        Self::with_console(this, context, |console| {
+            // 1. Let map be the associated count map.
+            // 2. If map[label] exists, set map[label] to map[label] + 1.
+            // 3. Otherwise, set map[label] to 1.
+            let count = {
+                let mut counts = console.counts.borrow_mut();
+                let count = counts.entry(label.clone()).or_insert(0);
+                *count += 1;
+                *count
+            };
+
+            // 4. Let concat be the concatenation of label, U+003A (:), U+0020
+            //    SPACE, and ToString(map[label]).
+            // 5. Perform Logger("count", « concat »).
+            console.print(Level::Info, &format!("{label}: {count}"));
+        })?;
+        Ok(JsValue::Undefined)
+    }
+
+    /// [Console Standard § 1.2.2 `countReset(label)`](https://console.spec.whatwg.org/#countreset)
+    pub fn count_reset(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let label = label_argument(args, context)?;
+        Self::with_console(this, context, |console| {
+            // 2. If map[label] exists, set map[label] to 0.
+            // 3. Otherwise, perform Logger("countReset", « message ») where
+            //    message is a string indicating that label doesn't have an
+            //    associated count.
+            match console.counts.borrow_mut().get_mut(&label) {
+                Some(count) => *count = 0,
+                None => console.print(Level::Warn, &format!("Count for \"{label}\" does not exist")),
+            }
+        })?;
+        Ok(JsValue::Undefined)
+    }
+
+    /// [Console Standard § 1.3.1 `group(...data)`](https://console.spec.whatwg.org/#group),
+    /// which is also used for [`groupCollapsed(...data)`](https://console.spec.whatwg.org/#groupcollapsed),
+    /// since groups can't be collapsed in the log.
+    pub fn group(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 2. If data is not empty, let groupLabel be the result of
+        //    Formatter(data). Otherwise, let groupLabel be an implementation
+        //    chosen label representing a group.
+        let label = match args.is_empty() {
+            true => String::from("console.group"),
+            false => format_arguments(args, context)?,
+        };
+
+        Self::with_console(this, context, |console| {
+            // 4. Perform Printer("group", « group »).
+            console.print(Level::Info, &label);
+
+            // 5. Push group onto the appropriate group stack.
+            console.group_depth.set(console.group_depth.get() + 1);
+        })?;
+        Ok(JsValue::Undefined)
+    }
+
+    /// [Console Standard § 1.3.3 `groupEnd()`](https://console.spec.whatwg.org/#groupend)
+    pub fn group_end(this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 1. Pop the last group from the group stack.
+        Self::with_console(this, context, |console| {
+            console.group_depth.set(console.group_depth.get().saturating_sub(1));
+        })?;
+        Ok(JsValue::Undefined)
+    }
+
+    /// [Console Standard § 1.4.1 `time(label)`](https://console.spec.whatwg.org/#time)
+    pub fn time(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let label = label_argument(args, context)?;
+        Self::with_console(this, context, |console| {
+            // 1. If the associated timer table contains an entry with key
+            //    label, return, optionally reporting a warning to the console
+            //    indicating that a timer with label label has already
+            //    started.
+            // 2. Otherwise, set the value of the entry with key label in the
+            //    associated timer table to the current time.
+            let started = match console.timers.borrow_mut().entry(label.clone()) {
+                Entry::Occupied(..) => false,
+                Entry::Vacant(entry) => {
+                    entry.insert(Instant::now());
+                    true
+                }
+            };
+
+            if !started {
+                console.print(Level::Warn, &format!("Timer \"{label}\" already exists"));
+            }
+        })?;
+        Ok(JsValue::Undefined)
+    }
+
+    /// [Console Standard § 1.4.2 `timeLog(label, ...data)`](https://console.spec.whatwg.org/#timelog)
+    pub fn time_log(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::timer_report(this, args, false, context)
+    }
+
+    /// [Console Standard § 1.4.3 `timeEnd(label)`](https://console.spec.whatwg.org/#timeend)
+    pub fn time_end(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::timer_report(this, &args[..args.len().min(1)], true, context)
+    }
+
+    /// Print the duration of the timer of the label in `args`, followed by
+    /// the rest of the `args`. The timer is removed from the timer table if
+    /// `end` is set.
+    fn timer_report(this: &JsValue, args: &[JsValue], end: bool, context: &mut Context) -> JsResult<JsValue> {
+        let label = label_argument(args, context)?;
+        let data = match args.len() > 1 {
+            true => Some(format_arguments(&args[1..], context)?),
+            false => None,
+        };
+
+        Self::with_console(this, context, |console| {
+            // 1. Let timerTable be the associated timer table.
+            // 2. Let startTime be timerTable[label].
+            let start_time = {
+                let mut timers = console.timers.borrow_mut();
+                match end {
+                    // 3. Remove timerTable[label].
+                    true => timers.remove(&label),
+                    false => timers.get(&label).copied(),
+                }
+            };
+
+            let Some(start_time) = start_time else {
+                console.print(Level::Warn, &format!("Timer \"{label}\" does not exist"));
+                return;
+            };
+
+            // 4. Let duration be a string representing the difference
+            //    between the current time and startTime, in an
+            //    implementation-defined format.
+            let duration = start_time.elapsed().as_secs_f64() * 1000.0;
+
+            // 5. Let concat be the concatenation of label, U+003A (:), U+0020
+            //    SPACE, and duration.
+            let mut concat = format!("{label}: {duration:.3}ms");
+            if let Some(data) = &data {
+                concat.push(' ');
+                concat.push_str(data);
+            }
+
+            // 6. Perform Printer("timeEnd", « concat »).
+            console.print(Level::Info, &concat);
+        })?;
+        Ok(JsValue::Undefined)
+    }
+}
+
+impl Default for Console {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Class for Console {
+    const NAME: &'static str = "Console";
+    const LENGTH: usize = 0;
+    const ATTRIBUTES: Attribute = Attribute::empty();
+
+    fn constructor(_: &JsValue, _: &[JsValue], _: &mut Context<'_>) -> JsResult<Self> {
+        illegal_constructor()
+    }
+
+    fn init(class: &mut ClassBuilder<'_, '_>) -> JsResult<()> {
+        class.method("assert", 0, NativeFunction::from_fn_ptr(Self::assert));
+        class.method("count", 0, NativeFunction::from_fn_ptr(Self::count));
+        class.method("countReset", 0, NativeFunction::from_fn_ptr(Self::count_reset));
+        class.method("debug", 0, NativeFunction::from_fn_ptr(Self::debug));
+        class.method("error", 0, NativeFunction::from_fn_ptr(Self::error));
+        class.method("group", 0, NativeFunction::from_fn_ptr(Self::group));
+        class.method("groupCollapsed", 0, NativeFunction::from_fn_ptr(Self::group));
+        class.method("groupEnd", 0, NativeFunction::from_fn_ptr(Self::group_end));
+        class.method("info", 0, NativeFunction::from_fn_ptr(Self::info));
+        class.method("log", 0, NativeFunction::from_fn_ptr(Self::log));
+        class.method("table", 1, NativeFunction::from_fn_ptr(Self::table));
+        class.method("time", 0, NativeFunction::from_fn_ptr(Self::time));
+        class.method("timeEnd", 0, NativeFunction::from_fn_ptr(Self::time_end));
+        class.method("timeLog", 0, NativeFunction::from_fn_ptr(Self::time_log));
+        class.method("warn", 0, NativeFunction::from_fn_ptr(Self::warn));
+        Ok(())
+    }
+}
+
+/// Get the `label` argument, which defaults to `"default"`.
+fn label_argument(args: &[JsValue], context: &mut Context) -> JsResult<String> {
+    match args.first() {
+        None | Some(JsValue::Undefined) => Ok(String::from(DEFAULT_LABEL)),
+        Some(label) => Ok(label.to_string(context)?.to_std_string_escaped()),
+    }
+}
+
+/// The [Formatter](https://console.spec.whatwg.org/#formatter) operation,
+/// which applies the format specifiers of the first argument, and joins the
+/// result with the remaining arguments.
+fn format_arguments(args: &[JsValue], context: &mut Context) -> JsResult<String> {
+    let Some(first) = args.first() else {
+        return Ok(String::new());
+    };
+
+    let mut rest = args[1..].iter();
+    let mut output = match first.as_string() {
+        Some(target) if !rest.as_slice().is_empty() => {
+            apply_format_specifiers(&target.to_std_string_escaped(), &mut rest, context)?
+        }
+        _ => format_value(first, context)?,
+    };
+
+    for value in rest {
+        output.push(' ');
+        output.push_str(&format_value(value, context)?);
+    }
+
+    Ok(output)
+}
+
+/// Replace the format specifiers of the `target` with the `args` they
+/// consume. Specifiers without a corresponding argument are left as is.
+fn apply_format_specifiers<'a>(
+    target: &str,
+    args: &mut impl Iterator<Item = &'a JsValue>,
+    context: &mut Context,
+) -> JsResult<String> {
+    let mut output = String::with_capacity(target.len());
+    let mut chars = target.chars().peekable();
+
+    while let Some(character) = chars.next() {
+        if character != '%' {
+            output.push(character);
+            continue;
+        }
+
+        let Some(&specifier) = chars.peek() else {
+            output.push(character);
+            break;
+        };
+
+        if specifier == '%' {
+            chars.next();
+            output.push('%');
+            continue;
+        }
+
+        if !matches!(specifier, 's' | 'd' | 'i' | 'f' | 'o' | 'O' | 'c') {
+            output.push(character);
+            continue;
+        }
+
+        let Some(current) = args.next() else {
+            output.push(character);
+            continue;
+        };
+        chars.next();
+
+        match specifier {
+            // %s: Let converted be the result of Call(%String%, undefined,
+            //     « current »).
+            's' => match current {
+                JsValue::Symbol(symbol) => output.push_str(&symbol.descriptive_string().to_std_string_escaped()),
+                JsValue::String(string) => output.push_str(&string.to_std_string_escaped()),
+                JsValue::Object(..) => output.push_str(&current.display().to_string()),
+                _ => output.push_str(&current.to_string(context)?.to_std_string_escaped()),
+            },
+
+            // %d or %i: If Type(current) is Symbol, let converted be NaN.
+            //           Otherwise, let converted be the result of
+            //           Call(%parseInt%, undefined, « current, 10 »).
+            'd' | 'i' => {
+                let number = number_argument(current, context)?;
+                let number = if number.is_finite() { number.trunc() } else { f64::NAN };
+                output.push_str(&JsValue::new(number).to_string(context)?.to_std_string_escaped());
+            }
+
+            // %f: If Type(current) is Symbol, let converted be NaN.
+            //     Otherwise, let converted be the result of
+            //     Call(%parseFloat%, undefined, « current »).
+            'f' => {
+                let number = number_argument(current, context)?;
+                output.push_str(&JsValue::new(number).to_string(context)?.to_std_string_escaped());
+            }
+
+            // %o and %O: Let converted be current with (generic) JavaScript
+            //            object formatting.
+            'o' | 'O' => output.push_str(&current.display().to_string()),
+
+            // %c: Styling isn't supported, so the argument is consumed
+            //     without any output.
+            _ => (),
+        }
+    }
+
+    Ok(output)
+}
+
+/// Convert the argument of a numeric format specifier, which is [`NaN`]
+/// for values that can't be converted to a number, e.g. symbols.
+///
+/// [`NaN`]: f64::NAN
+fn number_argument(value: &JsValue, context: &mut Context) -> JsResult<f64> {
+    match value {
+        JsValue::Symbol(..) => Ok(f64::NAN),
+        JsValue::BigInt(bigint) => Ok(bigint.to_f64()),
+        JsValue::String(string) => {
+            let string = string.to_std_string_escaped();
+            Ok(parse_float_prefix(string.trim_start()))
+        }
+        _ => value.to_number(context),
+    }
+}
+
+/// Parse the longest prefix of `input` that is a decimal number, like the
+/// `parseFloat()` function does.
+fn parse_float_prefix(input: &str) -> f64 {
+    for prefix in ["Infinity", "+Infinity"] {
+        if input.starts_with(prefix) {
+            return f64::INFINITY;
+        }
+    }
+
+    if input.starts_with("-Infinity") {
+        return f64::NEG_INFINITY;
+    }
+
+    (1..=input.len())
+        .rev()
+        .filter(|end| input.is_char_boundary(*end))
+        .find_map(|end| {
+            let prefix = &input[..end];
+            if prefix.ends_with(|c: char| c.is_ascii_digit() || c == '.') {
+                prefix.parse::<f64>().ok()
+            } else {
+                None
+            }
+        })
+        .unwrap_or(f64::NAN)
+}
+
+/// Format the `value` for the log, where strings are written as is, and
+/// other values are formatted like they would be in a REPL.
+fn format_value(value: &JsValue, context: &mut Context) -> JsResult<String> {
+    match value {
+        JsValue::String(string) => Ok(string.to_std_string_escaped()),
+        JsValue::Object(..) | JsValue::Symbol(..) => Ok(value.display().to_string()),
+        _ => Ok(value.to_string(context)?.to_std_string_escaped()),
+    }
+}
+
+/// Get the enumerable own property names of the `object`, using
+/// `Object.keys()`.
+fn object_keys(object: &JsObject, context: &mut Context) -> JsResult<Vec<String>> {
+    let keys = context.global_object()
+        .get("Object", context)?
+        .as_object()
+        .cloned()
+        .ok_or_else(|| JsError::from_opaque("`Object` is not an object".into()))?
+        .get("keys", context)?;
+
+    let keys = keys.as_callable()
+        .ok_or_else(|| JsError::from_opaque("`Object.keys` is not callable".into()))?
+        .call(&JsValue::Undefined, &[object.clone().into()], context)?;
+
+    match keys.as_object() {
+        Some(keys) => array_to_strings(keys, context),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Convert the elements of the array-like `object` to strings.
+fn array_to_strings(object: &JsObject, context: &mut Context) -> JsResult<Vec<String>> {
+    let array = JsArray::from_object(object.clone())?;
+    let mut strings = Vec::new();
+    for index in 0..array.length(context)? {
+        strings.push(array.get(index, context)?.to_string(context)?.to_std_string_escaped());
+    }
+    Ok(strings)
+}
+
+/// Format the `data` as a table, with a row for each property. The columns
+/// are the properties of the rows that are objects, or the `filter` if it is
+/// given. Rows that aren't objects have their value in the `Values` column.
+fn format_table(data: &JsObject, filter: Option<Vec<String>>, context: &mut Context) -> JsResult<String> {
+    const INDEX_COLUMN: &str = "(index)";
+    const VALUES_COLUMN: &str = "Values";
+
+    let mut columns: Vec<String> = filter.clone().unwrap_or_default();
+    let mut has_values_column = false;
+    let mut rows = Vec::new();
+
+    for key in object_keys(data, context)? {
+        let value = data.get(key.as_str(), context)?;
+        let mut cells = HashMap::new();
+
+        match value.as_object() {
+            Some(row) if !row.is_callable() => {
+                for column in object_keys(row, context)? {
+                    if filter.as_ref().is_some_and(|filter| !filter.contains(&column)) {
+                        continue;
+                    }
+
+                    let cell = format_value(&row.get(column.as_str(), context)?, context)?;
+                    if !columns.contains(&column) {
+                        columns.push(column.clone());
+                    }
+                    cells.insert(column, cell);
+                }
+            }
+            _ => {
+                has_values_column = true;
+                cells.insert(String::from(VALUES_COLUMN), format_value(&value, context)?);
+            }
+        }
+
+        rows.push((key, cells));
+    }
+
+    let mut header = vec![String::from(INDEX_COLUMN)];
+    header.extend(columns);
+    if has_values_column {
+        header.push(String::from(VALUES_COLUMN));
+    }
+
+    let rows: Vec<Vec<String>> = rows.into_iter()
+        .map(|(key, mut cells)| {
+            std::iter::once(key)
+                .chain(header[1..].iter().map(|column| cells.remove(column).unwrap_or_default()))
+                .collect()
+        })
+        .collect();
+
+    let widths: Vec<usize> = (0..header.len())
+        .map(|column| {
+            std::iter::once(&header)
+                .chain(rows.iter())
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let format_row = |row: &[String]| {
+        row.iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:width$}"))
+            .collect::<Vec<_>>()
+            .join(" | ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut lines = vec![format_row(&header)];
+    lines.push(widths.iter().map(|width| "-".repeat(*width)).collect::<Vec<_>>().join("-+-"));
+    lines.extend(rows.iter().map(|row| format_row(row)));
+
+    Ok(lines.join("\n"))
+}
@@ -0,0 +1,65 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use boa_engine::prelude::*;
+
+use boa_engine::class::{Class, ClassBuilder};
+use boa_engine::property::Attribute;
+use boa_gc::{Finalize, Trace, custom_trace};
+
+use crate::prelude::*;
+
+use super::AbortSignal;
+
+/// The [Interface `AbortController`](https://dom.spec.whatwg.org/#interface-abortcontroller)
+/// platform object.
+#[derive(Finalize)]
+pub struct AbortController {
+    /// The [`AbortSignal`] object.
+    signal: JsObject,
+}
+
+unsafe impl Trace for AbortController {
+    custom_trace!(this, {
+        mark(&this.signal);
+    });
+}
+
+impl AbortController {
+    fn signal_of(this: &JsValue) -> JsResult<JsObject> {
+        this.as_object()
+            .and_then(|obj| obj.downcast_ref::<Self>().map(|controller| controller.signal.clone()))
+            .ok_or_else(|| JsError::from_opaque("Value is not `AbortController`".into()))
+    }
+
+    /// [DOM Living Standard § 3.1 `signal`](https://dom.spec.whatwg.org/#dom-abortcontroller-signal)
+    fn get_signal(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::signal_of(this).map(Into::into)
+    }
+
+    /// [DOM Living Standard § 3.1 `abort()`](https://dom.spec.whatwg.org/#dom-abortcontroller-abort)
+    fn abort(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let signal = Self::signal_of(this)?;
+        AbortSignal::signal_abort(&signal, args.first().cloned().unwrap_or_default(), context)?;
+        Ok(JsValue::Undefined)
+    }
+}
+
+impl Class for AbortController {
+    const NAME: &'static str = "AbortController";
+    const LENGTH: usize = 0;
+    const ATTRIBUTES: Attribute = Attribute::empty();
+
+    /// [DOM Living Standard § 3.1 `new AbortController()`](https://dom.spec.whatwg.org/#dom-abortcontroller-abortcontroller)
+    fn constructor(_: &JsValue, _: &[JsValue], context: &mut Context<'_>) -> JsResult<Self> {
+        Ok(Self {
+            signal: AbortSignal::new().create_object(context)?,
+        })
+    }
+
+    fn init(class: &mut ClassBuilder<'_, '_>) -> JsResult<()> {
+        class.accessor_with_function_ptr("signal", Some(Self::get_signal), None, Attribute::all());
+        class.method("abort", 0, NativeFunction::from_fn_ptr(Self::abort));
+        Ok(())
+    }
+}
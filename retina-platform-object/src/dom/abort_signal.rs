@@ -0,0 +1,144 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use std::cell::RefCell;
+
+use boa_engine::prelude::*;
+
+use boa_engine::class::{Class, ClassBuilder};
+use boa_engine::object::ObjectData;
+use boa_engine::property::Attribute;
+use boa_gc::{Finalize, GcRefCell, Trace, custom_trace};
+
+use crate::fetch::{self, FetchId};
+use crate::prelude::*;
+
+/// The [Interface `AbortSignal`](https://dom.spec.whatwg.org/#interface-AbortSignal)
+/// platform object.
+#[derive(Finalize, Default)]
+pub struct AbortSignal {
+    /// The [abort reason](https://dom.spec.whatwg.org/#abortsignal-abort-reason),
+    /// which is set when the signal is aborted.
+    reason: GcRefCell<Option<JsValue>>,
+
+    /// The requests of `fetch()` that are aborted together with this signal.
+    fetches: RefCell<Vec<FetchId>>,
+}
+
+unsafe impl Trace for AbortSignal {
+    custom_trace!(this, {
+        mark(&this.reason);
+    });
+}
+
+impl AbortSignal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn create_object(self, context: &mut Context) -> JsResult<JsObject> {
+        Ok(JsObject::from_proto_and_data(
+            Some(class_prototype::<Self>(context)?),
+            ObjectData::native_object(self),
+        ))
+    }
+
+    /// [Whether the signal is aborted](https://dom.spec.whatwg.org/#abortsignal-aborted).
+    pub fn is_aborted(&self) -> bool {
+        self.reason.borrow().is_some()
+    }
+
+    /// The [abort reason](https://dom.spec.whatwg.org/#abortsignal-abort-reason),
+    /// if the signal is aborted.
+    pub fn reason(&self) -> Option<JsValue> {
+        self.reason.borrow().clone()
+    }
+
+    /// Abort the `fetch()` request with `id` when this signal is aborted.
+    pub fn add_fetch(&self, id: FetchId) {
+        self.fetches.borrow_mut().push(id);
+    }
+
+    /// [Signal abort](https://dom.spec.whatwg.org/#abortsignal-signal-abort)
+    /// on the `signal` object, with the given `reason`, or an `"AbortError"`
+    /// when it is `undefined`.
+    pub fn signal_abort(signal: &JsObject, reason: JsValue, context: &mut Context) -> JsResult<()> {
+        let Some(this) = signal.downcast_ref::<Self>() else {
+            return Err(JsError::from_opaque("Value is not `AbortSignal`".into()));
+        };
+
+        // 1. If signal is aborted, then return.
+        if this.is_aborted() {
+            return Ok(());
+        }
+
+        // 2. Set signal’s abort reason to reason if it is given; otherwise to
+        //    a new "AbortError" DOMException.
+        let reason = if reason.is_undefined() {
+            dom_exception("AbortError", "The operation was aborted.", context).to_opaque(context)
+        } else {
+            reason
+        };
+        *this.reason.borrow_mut() = Some(reason.clone());
+
+        // 3. For each algorithm of signal’s abort algorithms: run algorithm.
+        let fetches = std::mem::take(&mut *this.fetches.borrow_mut());
+        drop(this);
+
+        for id in fetches {
+            fetch::abort_fetch(id, reason.clone(), context);
+        }
+
+        Ok(())
+    }
+
+    fn with_signal<T>(this: &JsValue, f: impl FnOnce(&Self) -> T) -> JsResult<T> {
+        let this = this.as_object()
+            .and_then(|obj| obj.downcast_ref::<Self>())
+            .ok_or_else(|| JsError::from_opaque("Value is not `AbortSignal`".into()))?;
+        Ok(f(&this))
+    }
+
+    /// [DOM Living Standard § 3.2 `abort()`](https://dom.spec.whatwg.org/#dom-abortsignal-abort)
+    fn abort(_this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let signal = Self::new().create_object(context)?;
+        Self::signal_abort(&signal, args.first().cloned().unwrap_or_default(), context)?;
+        Ok(signal.into())
+    }
+
+    /// [DOM Living Standard § 3.2 `aborted`](https://dom.spec.whatwg.org/#dom-abortsignal-aborted)
+    fn get_aborted(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_signal(this, |signal| JsValue::Boolean(signal.is_aborted()))
+    }
+
+    /// [DOM Living Standard § 3.2 `reason`](https://dom.spec.whatwg.org/#dom-abortsignal-reason)
+    fn get_reason(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_signal(this, |signal| signal.reason().unwrap_or_default())
+    }
+
+    /// [DOM Living Standard § 3.2 `throwIfAborted()`](https://dom.spec.whatwg.org/#dom-abortsignal-throwifaborted)
+    fn throw_if_aborted(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        match Self::with_signal(this, Self::reason)? {
+            Some(reason) => Err(JsError::from_opaque(reason)),
+            None => Ok(JsValue::Undefined),
+        }
+    }
+}
+
+impl Class for AbortSignal {
+    const NAME: &'static str = "AbortSignal";
+    const LENGTH: usize = 0;
+    const ATTRIBUTES: Attribute = Attribute::empty();
+
+    fn constructor(_: &JsValue, _: &[JsValue], _: &mut Context<'_>) -> JsResult<Self> {
+        illegal_constructor()
+    }
+
+    fn init(class: &mut ClassBuilder<'_, '_>) -> JsResult<()> {
+        class.static_method("abort", 0, NativeFunction::from_fn_ptr(Self::abort));
+        class.accessor_with_function_ptr("aborted", Some(Self::get_aborted), None, Attribute::all());
+        class.accessor_with_function_ptr("reason", Some(Self::get_reason), None, Attribute::all());
+        class.method("throwIfAborted", 0, NativeFunction::from_fn_ptr(Self::throw_if_aborted));
+        Ok(())
+    }
+}
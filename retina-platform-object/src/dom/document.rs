@@ -5,10 +5,14 @@ use boa_engine::prelude::*;
 use crate::prelude::*;
 
 use boa_engine::class::{Class, ClassBuilder};
+use boa_engine::object::builtins::JsArray;
 use boa_engine::property::Attribute;
 use boa_gc::{Finalize, Trace, empty_trace};
 use retina_common::StrTendril;
 use retina_dom::Node;
+use retina_style_computation::ElementSelectorExt;
+
+use super::HTMLElement;
 
 
 #[derive(Finalize)]
@@ -24,6 +28,10 @@ impl Document {
         }
     }
 
+    pub fn node(&self) -> &Node {
+        &self.document_node
+    }
+
     pub fn get_title(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
         let this = this.as_object()
             .and_then(|obj| obj.downcast_ref::<Self>())
@@ -46,6 +54,77 @@ impl Document {
 
         Ok(JsValue::String(title))
     }
+
+    /// [DOM Living Standard § 4.2.4 `getElementById()`](https://dom.spec.whatwg.org/#dom-nonelementparentnode-getelementbyid)
+    pub fn get_element_by_id(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let node = {
+            let this = this.as_object()
+                .and_then(|obj| obj.downcast_ref::<Self>())
+                .ok_or_else(|| JsError::from_opaque("Value is not `Document`".into()))?;
+
+            let id = args.get(0)
+                .cloned()
+                .unwrap_or_default()
+                .to_string(context)?
+                .to_std_string_escaped();
+
+            this.document_node.as_document().unwrap().get_element_by_id(&id)
+        };
+
+        match node {
+            Some(node) => Ok(JsValue::Object(HTMLElement::create_object(node, context)?)),
+            None => Ok(JsValue::Null),
+        }
+    }
+
+    /// [DOM Living Standard § 4.2.6 `querySelector()`](https://dom.spec.whatwg.org/#dom-parentnode-queryselector)
+    pub fn query_selector(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let node = {
+            let this = this.as_object()
+                .and_then(|obj| obj.downcast_ref::<Self>())
+                .ok_or_else(|| JsError::from_opaque("Value is not `Document`".into()))?;
+
+            let selectors = args.get(0)
+                .cloned()
+                .unwrap_or_default()
+                .to_string(context)?
+                .to_std_string_escaped();
+
+            this.document_node.query_selector(&selectors)
+                .map_err(|_| format!("\"{selectors}\" is not a valid selector"))
+        };
+        let node = node.map_err(|message| dom_exception("SyntaxError", &message, context))?;
+
+        match node {
+            Some(node) => Ok(JsValue::Object(HTMLElement::create_object(node, context)?)),
+            None => Ok(JsValue::Null),
+        }
+    }
+
+    /// [DOM Living Standard § 4.2.6 `querySelectorAll()`](https://dom.spec.whatwg.org/#dom-parentnode-queryselectorall)
+    pub fn query_selector_all(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let nodes = {
+            let this = this.as_object()
+                .and_then(|obj| obj.downcast_ref::<Self>())
+                .ok_or_else(|| JsError::from_opaque("Value is not `Document`".into()))?;
+
+            let selectors = args.get(0)
+                .cloned()
+                .unwrap_or_default()
+                .to_string(context)?
+                .to_std_string_escaped();
+
+            this.document_node.query_selector_all(&selectors)
+                .map_err(|_| format!("\"{selectors}\" is not a valid selector"))
+        };
+        let nodes = nodes.map_err(|message| dom_exception("SyntaxError", &message, context))?;
+
+        let elements = nodes.into_iter()
+            .map(|node| HTMLElement::create_object(node, context).map(JsValue::Object))
+            .collect::<JsResult<Vec<_>>>()?;
+
+        Ok(JsValue::Object(JsArray::from_iter(elements, context).into()))
+    }
 }
 
 impl Class for Document {
@@ -67,6 +146,9 @@ impl Class for Document {
             Some(Self::set_title),
             Attribute::all()
         );
+        class.method("getElementById", 1, NativeFunction::from_fn_ptr(Self::get_element_by_id));
+        class.method("querySelector", 1, NativeFunction::from_fn_ptr(Self::query_selector));
+        class.method("querySelectorAll", 1, NativeFunction::from_fn_ptr(Self::query_selector_all));
         Ok(())
     }
 }
@@ -0,0 +1,91 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use boa_engine::prelude::*;
+
+use boa_engine::class::{Class, ClassBuilder};
+use boa_engine::property::Attribute;
+use boa_gc::{Finalize, Trace, empty_trace};
+use retina_dom::{HtmlCanvasElement, HtmlElementKind, Node};
+
+use crate::canvas::CanvasRenderingContext2D;
+use crate::prelude::*;
+
+use super::HTMLElement;
+
+/// The [Interface `HTMLCanvasElement`](https://html.spec.whatwg.org/multipage/canvas.html#htmlcanvaselement)
+/// platform object. The objects of this class are [`HTMLElement`]s of which
+/// the prototype inherits from this class.
+#[derive(Finalize)]
+pub struct HTMLCanvasElement;
+
+impl HTMLCanvasElement {
+    /// Run `f` with the canvas element of `this`, which is an [`HTMLElement`]
+    /// object.
+    fn with_canvas<T>(this: &JsValue, f: impl FnOnce(&Node, &HtmlCanvasElement) -> T) -> JsResult<T> {
+        let this = this.as_object()
+            .and_then(|obj| obj.downcast_ref::<HTMLElement>())
+            .ok_or_else(|| JsError::from_opaque("Value is not `HTMLCanvasElement`".into()))?;
+
+        match this.node().as_html_element_kind() {
+            Some(HtmlElementKind::Canvas(canvas)) => Ok(f(this.node(), canvas)),
+            _ => Err(JsError::from_opaque("Value is not `HTMLCanvasElement`".into())),
+        }
+    }
+
+    /// [HTML Living Standard § 4.12.5 `getContext()`](https://html.spec.whatwg.org/multipage/canvas.html#dom-canvas-getcontext)
+    pub fn get_context(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let context_id = args.first()
+            .cloned()
+            .unwrap_or_default()
+            .to_string(context)?
+            .to_std_string_escaped();
+
+        // Only the `2d` context is supported.
+        if context_id != "2d" {
+            return Ok(JsValue::Null);
+        }
+
+        let context_2d = Self::with_canvas(this, |node, canvas| {
+            CanvasRenderingContext2D::new(node.clone(), canvas)
+        })?;
+
+        // The canvas already has a context of another type.
+        let Some(context_2d) = context_2d else {
+            return Ok(JsValue::Null);
+        };
+
+        let object = context_2d.create_object(context)?;
+        Ok(JsValue::Object(object))
+    }
+
+    /// [HTML Living Standard § 4.12.5 `width`](https://html.spec.whatwg.org/multipage/canvas.html#dom-canvas-width)
+    pub fn get_width(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_canvas(this, |_, canvas| JsValue::from(canvas.width()))
+    }
+
+    /// [HTML Living Standard § 4.12.5 `height`](https://html.spec.whatwg.org/multipage/canvas.html#dom-canvas-height)
+    pub fn get_height(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_canvas(this, |_, canvas| JsValue::from(canvas.height()))
+    }
+}
+
+impl Class for HTMLCanvasElement {
+    const NAME: &'static str = "HTMLCanvasElement";
+    const LENGTH: usize = 0;
+    const ATTRIBUTES: Attribute = Attribute::empty();
+
+    fn constructor(_: &JsValue, _: &[JsValue], _: &mut Context<'_>) -> JsResult<Self> {
+        illegal_constructor()
+    }
+
+    fn init(class: &mut ClassBuilder<'_, '_>) -> JsResult<()> {
+        // TODO: setting the dimensions requires mutable attributes.
+        class.accessor_with_function_ptr("height", Some(Self::get_height), None, Attribute::all());
+        class.accessor_with_function_ptr("width", Some(Self::get_width), None, Attribute::all());
+        class.method("getContext", 1, NativeFunction::from_fn_ptr(Self::get_context));
+        Ok(())
+    }
+}
+
+unsafe impl Trace for HTMLCanvasElement { empty_trace!(); }
@@ -0,0 +1,242 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use boa_engine::prelude::*;
+
+use boa_engine::class::{Class, ClassBuilder};
+use boa_engine::object::ObjectData;
+use boa_engine::object::builtins::JsArray;
+use boa_engine::property::Attribute;
+use boa_gc::{Finalize, Trace, empty_trace};
+use retina_dom::{HtmlElementKind, InsertAdjacentError, Node};
+use retina_style_computation::ElementSelectorExt;
+
+use crate::prelude::{class_prototype, dom_exception, illegal_constructor, ClassBuilderExt};
+
+use super::HTMLCanvasElement;
+
+/// The [Interface `HTMLElement`](https://html.spec.whatwg.org/multipage/dom.html#htmlelement)
+/// platform object.
+#[derive(Finalize)]
+pub struct HTMLElement {
+    node: Node,
+}
+
+impl HTMLElement {
+    pub fn new(node: Node) -> Self {
+        Self {
+            node,
+        }
+    }
+
+    pub fn node(&self) -> &Node {
+        &self.node
+    }
+
+    /// Wrap the given element node in a JavaScript object, using the
+    /// prototype of the registered `HTMLElement` class, or of the subclass
+    /// for the specific element, e.g. `HTMLCanvasElement`.
+    pub fn create_object(node: Node, context: &mut Context) -> JsResult<JsObject> {
+        let prototype = match node.as_html_element_kind() {
+            Some(HtmlElementKind::Canvas(..)) => class_prototype::<HTMLCanvasElement>(context)?,
+            _ => class_prototype::<Self>(context)?,
+        };
+
+        Ok(JsObject::from_proto_and_data(
+            Some(prototype),
+            ObjectData::native_object(Self::new(node)),
+        ))
+    }
+
+    /// [DOM Living Standard § 4.9 `closest()`](https://dom.spec.whatwg.org/#dom-element-closest)
+    pub fn closest(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let node = {
+            let this = this.as_object()
+                .and_then(|obj| obj.downcast_ref::<Self>())
+                .ok_or_else(|| JsError::from_opaque("Value is not `HTMLElement`".into()))?;
+
+            let selectors = args.get(0)
+                .cloned()
+                .unwrap_or_default()
+                .to_string(context)?
+                .to_std_string_escaped();
+
+            this.node.closest(&selectors)
+        };
+
+        match node {
+            Some(node) => Ok(JsValue::Object(Self::create_object(node, context)?)),
+            None => Ok(JsValue::Null),
+        }
+    }
+
+    /// [DOM Living Standard § 4.4 `cloneNode()`](https://dom.spec.whatwg.org/#dom-node-clonenode)
+    pub fn clone_node(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let node = {
+            let this = this.as_object()
+                .and_then(|obj| obj.downcast_ref::<Self>())
+                .ok_or_else(|| JsError::from_opaque("Value is not `HTMLElement`".into()))?;
+
+            let deep = args.first()
+                .map(JsValue::to_boolean)
+                .unwrap_or(false);
+
+            this.node.clone_node(deep)
+        };
+
+        Ok(JsValue::Object(Self::create_object(node, context)?))
+    }
+
+    /// [HTML Living Standard § 8.5.2 `innerHTML`](https://html.spec.whatwg.org/multipage/dynamic-markup-insertion.html#dom-element-innerhtml)
+    pub fn get_inner_html(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        let this = this.as_object()
+            .and_then(|obj| obj.downcast_ref::<Self>())
+            .ok_or_else(|| JsError::from_opaque("Value is not `HTMLElement`".into()))?;
+
+        Ok(JsValue::String(JsString::from(this.node.inner_html())))
+    }
+
+    /// [HTML Living Standard § 8.5.2 `innerHTML`](https://html.spec.whatwg.org/multipage/dynamic-markup-insertion.html#dom-element-innerhtml)
+    pub fn set_inner_html(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let this = this.as_object()
+            .and_then(|obj| obj.downcast_ref::<Self>())
+            .ok_or_else(|| JsError::from_opaque("Value is not `HTMLElement`".into()))?;
+
+        let html = args.first()
+            .cloned()
+            .unwrap_or_default()
+            .to_string(context)?
+            .to_std_string_escaped();
+
+        this.node.set_inner_html(&html);
+        Ok(JsValue::Undefined)
+    }
+
+    /// [HTML Living Standard § 8.5.3 `insertAdjacentHTML()`](https://html.spec.whatwg.org/multipage/dynamic-markup-insertion.html#dom-element-insertadjacenthtml)
+    pub fn insert_adjacent_html(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let this = this.as_object()
+            .and_then(|obj| obj.downcast_ref::<Self>())
+            .ok_or_else(|| JsError::from_opaque("Value is not `HTMLElement`".into()))?;
+
+        let position = args.get(0)
+            .cloned()
+            .unwrap_or_default()
+            .to_string(context)?
+            .to_std_string_escaped();
+
+        let html = args.get(1)
+            .cloned()
+            .unwrap_or_default()
+            .to_string(context)?
+            .to_std_string_escaped();
+
+        match this.node.insert_adjacent_html(&position, &html) {
+            Ok(()) => Ok(JsValue::Undefined),
+
+            Err(InsertAdjacentError::InvalidPosition) => {
+                let message = format!("Invalid position: \"{position}\"");
+                Err(dom_exception("SyntaxError", &message, context))
+            }
+
+            Err(InsertAdjacentError::NoParentElement) => {
+                Err(dom_exception("NoModificationAllowedError", "The element has no parent element", context))
+            }
+
+            Err(InsertAdjacentError::NotAnElement) => {
+                Err(JsError::from_opaque("Value is not an element".into()))
+            }
+        }
+    }
+
+    /// [DOM Living Standard § 4.2.6 `querySelector()`](https://dom.spec.whatwg.org/#dom-parentnode-queryselector)
+    pub fn query_selector(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let node = {
+            let this = this.as_object()
+                .and_then(|obj| obj.downcast_ref::<Self>())
+                .ok_or_else(|| JsError::from_opaque("Value is not `HTMLElement`".into()))?;
+
+            let selectors = args.get(0)
+                .cloned()
+                .unwrap_or_default()
+                .to_string(context)?
+                .to_std_string_escaped();
+
+            this.node.query_selector(&selectors)
+                .map_err(|_| format!("\"{selectors}\" is not a valid selector"))
+        };
+        let node = node.map_err(|message| dom_exception("SyntaxError", &message, context))?;
+
+        match node {
+            Some(node) => Ok(JsValue::Object(Self::create_object(node, context)?)),
+            None => Ok(JsValue::Null),
+        }
+    }
+
+    /// [DOM Living Standard § 4.2.6 `querySelectorAll()`](https://dom.spec.whatwg.org/#dom-parentnode-queryselectorall)
+    pub fn query_selector_all(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let nodes = {
+            let this = this.as_object()
+                .and_then(|obj| obj.downcast_ref::<Self>())
+                .ok_or_else(|| JsError::from_opaque("Value is not `HTMLElement`".into()))?;
+
+            let selectors = args.get(0)
+                .cloned()
+                .unwrap_or_default()
+                .to_string(context)?
+                .to_std_string_escaped();
+
+            this.node.query_selector_all(&selectors)
+                .map_err(|_| format!("\"{selectors}\" is not a valid selector"))
+        };
+        let nodes = nodes.map_err(|message| dom_exception("SyntaxError", &message, context))?;
+
+        let elements = nodes.into_iter()
+            .map(|node| Self::create_object(node, context).map(JsValue::Object))
+            .collect::<JsResult<Vec<_>>>()?;
+
+        Ok(JsValue::Object(JsArray::from_iter(elements, context).into()))
+    }
+
+    /// [DOM Living Standard § 4.9 `matches()`](https://dom.spec.whatwg.org/#dom-element-matches)
+    pub fn matches(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let this = this.as_object()
+            .and_then(|obj| obj.downcast_ref::<Self>())
+            .ok_or_else(|| JsError::from_opaque("Value is not `HTMLElement`".into()))?;
+
+        let selectors = args.get(0)
+            .cloned()
+            .unwrap_or_default()
+            .to_string(context)?
+            .to_std_string_escaped();
+
+        Ok(JsValue::Boolean(this.node.matches(&selectors)))
+    }
+}
+
+impl Class for HTMLElement {
+    const NAME: &'static str = "HTMLElement";
+    const LENGTH: usize = 0;
+    const ATTRIBUTES: Attribute = Attribute::empty();
+
+    fn constructor(_: &JsValue, _: &[JsValue], _: &mut Context<'_>) -> JsResult<Self> {
+        illegal_constructor()
+    }
+
+    fn init(class: &mut ClassBuilder<'_, '_>) -> JsResult<()> {
+        class.accessor_with_function_ptr(
+            "innerHTML",
+            Some(Self::get_inner_html),
+            Some(Self::set_inner_html),
+            Attribute::all()
+        );
+        class.method("closest", 1, NativeFunction::from_fn_ptr(Self::closest));
+        class.method("cloneNode", 0, NativeFunction::from_fn_ptr(Self::clone_node));
+        class.method("insertAdjacentHTML", 2, NativeFunction::from_fn_ptr(Self::insert_adjacent_html));
+        class.method("matches", 1, NativeFunction::from_fn_ptr(Self::matches));
+        class.method("querySelector", 1, NativeFunction::from_fn_ptr(Self::query_selector));
+        class.method("querySelectorAll", 1, NativeFunction::from_fn_ptr(Self::query_selector_all));
+        Ok(())
+    }
+}
+
+unsafe impl Trace for HTMLElement { empty_trace!(); }
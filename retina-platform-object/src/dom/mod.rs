@@ -1,6 +1,18 @@
 // Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
 // All Rights Reserved.
 
+pub mod abort_controller;
+pub mod abort_signal;
 pub mod document;
+pub mod html_canvas_element;
+pub mod html_element;
+pub mod mutation_observer;
+pub mod xml_http_request;
 
+pub use abort_controller::AbortController;
+pub use abort_signal::AbortSignal;
 pub use document::Document;
+pub use html_canvas_element::HTMLCanvasElement;
+pub use html_element::HTMLElement;
+pub use mutation_observer::MutationObserver;
+pub use xml_http_request::XmlHttpRequest;
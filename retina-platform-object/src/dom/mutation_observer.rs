@@ -0,0 +1,245 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use boa_engine::prelude::*;
+
+use boa_engine::class::{Class, ClassBuilder};
+use boa_engine::object::ObjectInitializer;
+use boa_engine::object::builtins::{JsArray, JsFunction};
+use boa_engine::property::Attribute;
+use boa_gc::{Finalize, Trace, custom_trace};
+use log::error;
+use retina_dom::{MutationObserverInit, MutationRecord, Node};
+
+use crate::prelude::*;
+
+use super::{Document, HTMLElement};
+
+/// The [Interface `MutationObserver`](https://dom.spec.whatwg.org/#interface-mutationobserver)
+/// platform object.
+#[derive(Finalize)]
+pub struct MutationObserver {
+    observer: retina_dom::MutationObserver,
+    callback: JsFunction,
+}
+
+unsafe impl Trace for MutationObserver {
+    custom_trace!(this, {
+        mark(&this.callback);
+    });
+}
+
+impl MutationObserver {
+    fn with_observer<T>(this: &JsValue, f: impl FnOnce(&retina_dom::MutationObserver) -> T) -> JsResult<T> {
+        let this = this.as_object()
+            .and_then(|obj| obj.downcast_ref::<Self>())
+            .ok_or_else(|| JsError::from_opaque("Value is not `MutationObserver`".into()))?;
+        Ok(f(&this.observer))
+    }
+
+    /// [DOM Living Standard § 4.3.1 `observe()`](https://dom.spec.whatwg.org/#dom-mutationobserver-observe)
+    pub fn observe(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let target = args.first()
+            .and_then(JsValue::as_object)
+            .and_then(|object| {
+                if let Some(element) = object.downcast_ref::<HTMLElement>() {
+                    return Some(element.node().clone());
+                }
+
+                object.downcast_ref::<Document>().map(|document| document.node().clone())
+            })
+            .ok_or_else(|| JsNativeError::typ().with_message("The target is not a `Node`"))?;
+
+        let options = Self::convert_options(args.get(1).cloned().unwrap_or_default(), context)?;
+
+        Self::with_observer(this, |observer| observer.observe(&target, options))?
+            .map_err(|e| JsNativeError::typ().with_message(e.to_string()))?;
+
+        if let Some(object) = this.as_object() {
+            context.with_window(|window| window.add_mutation_observer(object));
+        }
+
+        Ok(JsValue::Undefined)
+    }
+
+    /// [DOM Living Standard § 4.3.1 `disconnect()`](https://dom.spec.whatwg.org/#dom-mutationobserver-disconnect)
+    pub fn disconnect(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_observer(this, |observer| observer.disconnect())?;
+        Ok(JsValue::Undefined)
+    }
+
+    /// [DOM Living Standard § 4.3.1 `takeRecords()`](https://dom.spec.whatwg.org/#dom-mutationobserver-takerecords)
+    pub fn take_records(this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let records = Self::with_observer(this, |observer| observer.take_records())?;
+        Self::create_records_array(records, context)
+    }
+
+    /// [Notify the mutation observers](https://dom.spec.whatwg.org/#notify-mutation-observers)
+    /// of the `window`, by invoking their callbacks with the records that
+    /// were queued since the last notification.
+    pub fn notify_mutation_observers(context: &mut Context) {
+        // 2. Let notifySet be a clone of the surrounding agent’s mutation
+        //    observers.
+        let notify_set = context.with_window(|window| window.mutation_observers());
+
+        // 5. For each mo of notifySet:
+        for object in notify_set {
+            let Some((records, callback)) = object.downcast_ref::<Self>()
+                .map(|mo| (mo.observer.take_records(), mo.callback.clone())) else { continue };
+
+            // 3. If records is not empty, then invoke mo’s callback with
+            //    « records, mo » and "report", and with callback this value
+            //    mo.
+            if records.is_empty() {
+                continue;
+            }
+
+            let result = Self::create_records_array(records, context)
+                .and_then(|records| {
+                    let this = JsValue::Object(object.clone());
+                    callback.call(&this, &[records, this.clone()], context)
+                });
+
+            if let Err(e) = result {
+                error!("[MutationObserver] Uncaught exception in callback: {e}");
+            }
+        }
+    }
+
+    /// Convert the `options` argument to a [`MutationObserverInit`], where
+    /// the members that are `undefined` are omitted.
+    fn convert_options(options: JsValue, context: &mut Context) -> JsResult<MutationObserverInit> {
+        let Some(options) = options.as_object() else {
+            return Ok(MutationObserverInit::default());
+        };
+
+        let optional_boolean = |name: &str, context: &mut Context| -> JsResult<Option<bool>> {
+            let value = options.get(name, context)?;
+            Ok((!value.is_undefined()).then(|| value.to_boolean()))
+        };
+
+        let child_list = optional_boolean("childList", context)?.unwrap_or(false);
+        let attributes = optional_boolean("attributes", context)?;
+        let character_data = optional_boolean("characterData", context)?;
+        let subtree = optional_boolean("subtree", context)?.unwrap_or(false);
+        let attribute_old_value = optional_boolean("attributeOldValue", context)?;
+        let character_data_old_value = optional_boolean("characterDataOldValue", context)?;
+
+        let attribute_filter = options.get("attributeFilter", context)?;
+        let attribute_filter = if attribute_filter.is_undefined() {
+            None
+        } else {
+            let array = attribute_filter.as_object()
+                .cloned()
+                .and_then(|object| JsArray::from_object(object).ok())
+                .ok_or_else(|| JsNativeError::typ().with_message("`attributeFilter` is not a sequence"))?;
+
+            let mut names = Vec::new();
+            for index in 0..array.length(context)? {
+                names.push(array.get(index, context)?.to_string(context)?.to_std_string_escaped());
+            }
+            Some(names)
+        };
+
+        Ok(MutationObserverInit {
+            child_list,
+            attributes,
+            character_data,
+            subtree,
+            attribute_old_value,
+            character_data_old_value,
+            attribute_filter,
+        })
+    }
+
+    fn create_records_array(records: Vec<MutationRecord>, context: &mut Context) -> JsResult<JsValue> {
+        let records = records.into_iter()
+            .map(|record| create_record_object(record, context))
+            .collect::<JsResult<Vec<_>>>()?;
+        Ok(JsValue::Object(JsArray::from_iter(records, context).into()))
+    }
+}
+
+/// Create the [`MutationRecord`](https://dom.spec.whatwg.org/#interface-mutationrecord)
+/// object of the `record`.
+fn create_record_object(record: MutationRecord, context: &mut Context) -> JsResult<JsValue> {
+    let target = create_node_object(record.target, context)?;
+    let added_nodes = create_node_list(record.added_nodes, context)?;
+    let removed_nodes = create_node_list(record.removed_nodes, context)?;
+    let previous_sibling = record.previous_sibling.map_or(Ok(JsValue::Null), |node| create_node_object(node, context))?;
+    let next_sibling = record.next_sibling.map_or(Ok(JsValue::Null), |node| create_node_object(node, context))?;
+    let attribute_name = record.attribute_name.map_or(JsValue::Null, |name| JsString::from(name).into());
+    let old_value = record.old_value.map_or(JsValue::Null, |value| JsString::from(value).into());
+
+    let attribute = Attribute::READONLY | Attribute::ENUMERABLE;
+    let object = ObjectInitializer::new(context)
+        .property("type", JsString::from(record.record_type.as_ref()), attribute)
+        .property("target", target, attribute)
+        .property("addedNodes", added_nodes, attribute)
+        .property("removedNodes", removed_nodes, attribute)
+        .property("previousSibling", previous_sibling, attribute)
+        .property("nextSibling", next_sibling, attribute)
+        .property("attributeName", attribute_name, attribute)
+        .property("attributeNamespace", JsValue::Null, attribute)
+        .property("oldValue", old_value, attribute)
+        .build();
+
+    Ok(JsValue::Object(object))
+}
+
+/// Create the platform object of the `node`, which is `null` for nodes that
+/// don't have platform objects yet, i.e. nodes that aren't elements or the
+/// document.
+fn create_node_object(node: Node, context: &mut Context) -> JsResult<JsValue> {
+    if node.is_document() {
+        return context.global_object().get("document", context);
+    }
+
+    if node.is_element() {
+        return HTMLElement::create_object(node, context).map(JsValue::Object);
+    }
+
+    Ok(JsValue::Null)
+}
+
+/// Create the array of the platform objects of the `nodes`, leaving out the
+/// nodes without one.
+fn create_node_list(nodes: Vec<Node>, context: &mut Context) -> JsResult<JsValue> {
+    let mut objects = Vec::with_capacity(nodes.len());
+    for node in nodes {
+        let object = create_node_object(node, context)?;
+        if !object.is_null() {
+            objects.push(object);
+        }
+    }
+    Ok(JsValue::Object(JsArray::from_iter(objects, context).into()))
+}
+
+impl Class for MutationObserver {
+    const NAME: &'static str = "MutationObserver";
+    const LENGTH: usize = 1;
+    const ATTRIBUTES: Attribute = Attribute::empty();
+
+    /// [DOM Living Standard § 4.3.1 `new MutationObserver(callback)`](https://dom.spec.whatwg.org/#dom-mutationobserver-mutationobserver)
+    fn constructor(_: &JsValue, args: &[JsValue], context: &mut Context<'_>) -> JsResult<Self> {
+        let callback = args.first()
+            .and_then(JsValue::as_callable)
+            .cloned()
+            .and_then(JsFunction::from_object)
+            .ok_or_else(|| JsNativeError::typ().with_message("The callback is not a function"))?;
+
+        let event_queue = context.with_window(|window| window.event_queue().clone());
+
+        Ok(Self {
+            observer: retina_dom::MutationObserver::new(event_queue),
+            callback,
+        })
+    }
+
+    fn init(class: &mut ClassBuilder<'_, '_>) -> JsResult<()> {
+        class.method("disconnect", 0, NativeFunction::from_fn_ptr(Self::disconnect));
+        class.method("observe", 1, NativeFunction::from_fn_ptr(Self::observe));
+        class.method("takeRecords", 0, NativeFunction::from_fn_ptr(Self::take_records));
+        Ok(())
+    }
+}
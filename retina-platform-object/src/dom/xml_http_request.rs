@@ -0,0 +1,965 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    time::Duration,
+};
+
+use boa_engine::prelude::*;
+
+use boa_engine::class::{Class, ClassBuilder};
+use boa_engine::object::{FunctionObjectBuilder, ObjectInitializer};
+use boa_engine::object::builtins::{JsArrayBuffer, JsFunction};
+use boa_engine::property::Attribute;
+use boa_gc::{Finalize, GcRefCell, Trace, custom_trace};
+use log::{error, warn};
+use retina_fetch::{
+    HeaderMap,
+    HeaderName,
+    HeaderValue,
+    Method,
+    RequestCredentialsMode,
+    RequestDestination,
+    RequestInitiator,
+    RequestMode,
+    RequestReferrer,
+    Url,
+};
+
+use crate::blob::Blob;
+use crate::fetch::{
+    self,
+    CompletedResponse,
+    FetchHandler,
+    FetchId,
+    FetchOutcome,
+};
+use crate::prelude::*;
+
+/// The events that are fired at an `XMLHttpRequest`, which can be handled
+/// using the `on<type>` event handler attributes.
+const EVENT_TYPES: [&str; 8] = [
+    "abort",
+    "error",
+    "load",
+    "loadend",
+    "loadstart",
+    "progress",
+    "readystatechange",
+    "timeout",
+];
+
+/// The [state](https://xhr.spec.whatwg.org/#concept-xmlhttprequest-state) of
+/// an `XMLHttpRequest`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+enum ReadyState {
+    #[default]
+    Unsent = 0,
+    Opened = 1,
+    HeadersReceived = 2,
+    Loading = 3,
+    Done = 4,
+}
+
+/// The [response type](https://xhr.spec.whatwg.org/#response-type) of an
+/// `XMLHttpRequest`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum ResponseType {
+    #[default]
+    Empty,
+    ArrayBuffer,
+    Blob,
+    Document,
+    Json,
+    Text,
+}
+
+impl ResponseType {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "" => Some(Self::Empty),
+            "arraybuffer" => Some(Self::ArrayBuffer),
+            "blob" => Some(Self::Blob),
+            "document" => Some(Self::Document),
+            "json" => Some(Self::Json),
+            "text" => Some(Self::Text),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Empty => "",
+            Self::ArrayBuffer => "arraybuffer",
+            Self::Blob => "blob",
+            Self::Document => "document",
+            Self::Json => "json",
+            Self::Text => "text",
+        }
+    }
+}
+
+/// The associated concepts of an `XMLHttpRequest` that aren't JavaScript
+/// values.
+#[derive(Default)]
+struct State {
+    ready_state: ReadyState,
+    send_flag: bool,
+    synchronous: bool,
+
+    request_method: Option<Method>,
+    request_url: Option<Url>,
+    author_request_headers: HeaderMap,
+
+    timeout: u32,
+    with_credentials: bool,
+    response_type: ResponseType,
+    override_mime_type: Option<String>,
+
+    /// The [response](https://xhr.spec.whatwg.org/#response), which is
+    /// [`None`] for a network error.
+    response: Option<CompletedResponse>,
+
+    /// The request that is being fetched in the background.
+    fetch_id: Option<FetchId>,
+}
+
+impl State {
+    /// The [final MIME type](https://xhr.spec.whatwg.org/#final-mime-type).
+    fn final_mime_type(&self) -> String {
+        if let Some(override_mime_type) = &self.override_mime_type {
+            return override_mime_type.clone();
+        }
+
+        self.response.as_ref()
+            .map_or_else(|| String::from("text/xml"), |response| response.content_type.clone())
+    }
+
+    /// The [text response](https://xhr.spec.whatwg.org/#text-response).
+    fn text_response(&self) -> String {
+        self.response.as_ref()
+            .map(|response| String::from_utf8_lossy(&response.body).into_owned())
+            .unwrap_or_default()
+    }
+}
+
+/// The [Interface `XMLHttpRequest`](https://xhr.spec.whatwg.org/#interface-xmlhttprequest)
+/// platform object.
+#[derive(Finalize)]
+pub struct XmlHttpRequest {
+    state: RefCell<State>,
+
+    /// The functions of the `on<type>` event handler attributes, by event
+    /// type.
+    event_handlers: GcRefCell<HashMap<&'static str, JsFunction>>,
+
+    /// The [response object](https://xhr.spec.whatwg.org/#response-object)
+    /// of the `"arraybuffer"`, `"blob"` and `"json"` response types, which
+    /// is created once the response is requested.
+    response_object: GcRefCell<Option<JsValue>>,
+}
+
+unsafe impl Trace for XmlHttpRequest {
+    custom_trace!(this, {
+        mark(&this.event_handlers);
+        mark(&this.response_object);
+    });
+}
+
+impl XmlHttpRequest {
+    fn object_of(this: &JsValue) -> JsResult<JsObject> {
+        this.as_object()
+            .filter(|object| object.is::<Self>())
+            .cloned()
+            .ok_or_else(|| JsError::from_opaque("Value is not `XMLHttpRequest`".into()))
+    }
+
+    fn with_xhr<T>(this: &JsValue, f: impl FnOnce(&Self) -> T) -> JsResult<T> {
+        let this = this.as_object()
+            .and_then(|obj| obj.downcast_ref::<Self>())
+            .ok_or_else(|| JsError::from_opaque("Value is not `XMLHttpRequest`".into()))?;
+        Ok(f(&this))
+    }
+
+    fn with_state<T>(this: &JsValue, f: impl FnOnce(&mut State) -> T) -> JsResult<T> {
+        Self::with_xhr(this, |xhr| f(&mut xhr.state.borrow_mut()))
+    }
+
+    /// Fire an event of `event_type` at the `object`, invoking its event
+    /// handler. The `progress` is the number of bytes loaded and the total
+    /// number of bytes, for `ProgressEvent`s.
+    fn fire_event(object: &JsObject, event_type: &'static str, progress: Option<(usize, usize)>, context: &mut Context) {
+        let Some(handler) = object.downcast_ref::<Self>()
+            .and_then(|xhr| xhr.event_handlers.borrow().get(event_type).cloned()) else {
+            return;
+        };
+
+        let mut event = ObjectInitializer::new(context);
+        event.property("type", JsString::from(event_type), Attribute::READONLY | Attribute::ENUMERABLE)
+            .property("target", object.clone(), Attribute::READONLY | Attribute::ENUMERABLE)
+            .property("currentTarget", object.clone(), Attribute::READONLY | Attribute::ENUMERABLE);
+        if let Some((loaded, total)) = progress {
+            event.property("lengthComputable", total != 0, Attribute::READONLY | Attribute::ENUMERABLE)
+                .property("loaded", loaded as f64, Attribute::READONLY | Attribute::ENUMERABLE)
+                .property("total", total as f64, Attribute::READONLY | Attribute::ENUMERABLE);
+        }
+        let event = event.build();
+
+        if let Err(e) = handler.call(&JsValue::Object(object.clone()), &[event.into()], context) {
+            error!("[XMLHttpRequest] Uncaught exception in on{event_type} handler: {e}");
+        }
+    }
+
+    fn fire_progress_event(object: &JsObject, event_type: &'static str, context: &mut Context) {
+        let length = object.downcast_ref::<Self>()
+            .and_then(|xhr| xhr.state.borrow().response.as_ref().map(|response| response.body.len()))
+            .unwrap_or(0);
+        Self::fire_event(object, event_type, Some((length, length)), context);
+    }
+
+    fn set_ready_state(object: &JsObject, ready_state: ReadyState) {
+        if let Some(xhr) = object.downcast_ref::<Self>() {
+            xhr.state.borrow_mut().ready_state = ready_state;
+        }
+    }
+
+    /// Notify the `XMLHttpRequest` of the `object` that its request
+    /// completed, running the steps of [handle response end-of-body](https://xhr.spec.whatwg.org/#handle-response-end-of-body),
+    /// or the [request error steps](https://xhr.spec.whatwg.org/#request-error-steps).
+    pub(crate) fn process_outcome(object: &JsObject, outcome: FetchOutcome, context: &mut Context) -> JsResult<()> {
+        let Some(synchronous) = object.downcast_ref::<Self>().and_then(|xhr| {
+            let mut state = xhr.state.borrow_mut();
+            state.fetch_id = None;
+            state.send_flag.then_some(state.synchronous)
+        }) else {
+            // The request was aborted, or opened again.
+            return Ok(());
+        };
+
+        let response = match outcome {
+            FetchOutcome::Response(response) => response,
+            FetchOutcome::Error(e) => {
+                warn!("[XMLHttpRequest] Failed to fetch: {e}");
+                return Self::run_request_error_steps(object, "error", "NetworkError", context);
+            }
+            FetchOutcome::TimedOut => {
+                return Self::run_request_error_steps(object, "timeout", "TimeoutError", context);
+            }
+        };
+
+        if let Some(xhr) = object.downcast_ref::<Self>() {
+            xhr.state.borrow_mut().response = Some(response);
+        }
+
+        if !synchronous {
+            // Process response: set this’s state to headers received, and
+            // fire an event named readystatechange at this.
+            Self::set_ready_state(object, ReadyState::HeadersReceived);
+            Self::fire_event(object, "readystatechange", None, context);
+
+            // Process the body: set this’s state to loading, fire an event
+            // named readystatechange at this, and fire a progress event
+            // named progress at this.
+            Self::set_ready_state(object, ReadyState::Loading);
+            Self::fire_event(object, "readystatechange", None, context);
+            Self::fire_progress_event(object, "progress", context);
+        }
+
+        // 6. Set this’s state to done.
+        // 7. Unset this’s send() flag.
+        if let Some(xhr) = object.downcast_ref::<Self>() {
+            let mut state = xhr.state.borrow_mut();
+            state.ready_state = ReadyState::Done;
+            state.send_flag = false;
+        }
+
+        // 8. Fire an event named readystatechange at this.
+        Self::fire_event(object, "readystatechange", None, context);
+
+        // 9. Fire a progress event named load at this with transmitted and
+        //    length.
+        Self::fire_progress_event(object, "load", context);
+
+        // 10. Fire a progress event named loadend at this with transmitted
+        //     and length.
+        Self::fire_progress_event(object, "loadend", context);
+
+        Ok(())
+    }
+
+    /// The [request error steps](https://xhr.spec.whatwg.org/#request-error-steps)
+    /// for the `event_type`, which throw an `exception` for synchronous
+    /// requests.
+    fn run_request_error_steps(
+        object: &JsObject,
+        event_type: &'static str,
+        exception: &str,
+        context: &mut Context,
+    ) -> JsResult<()> {
+        let synchronous = object.downcast_ref::<Self>().is_some_and(|xhr| {
+            // 1. Set xhr’s state to done.
+            // 2. Unset xhr’s send() flag.
+            // 3. Set xhr’s response to a network error.
+            let mut state = xhr.state.borrow_mut();
+            state.ready_state = ReadyState::Done;
+            state.send_flag = false;
+            state.response = None;
+            state.synchronous
+        });
+
+        // 4. If xhr’s synchronous flag is set, then throw exception.
+        if synchronous {
+            return Err(dom_exception(exception, "The request failed", context));
+        }
+
+        // 5. Fire an event named readystatechange at xhr.
+        Self::fire_event(object, "readystatechange", None, context);
+
+        // 7. Fire a progress event named event at xhr with 0 and 0.
+        Self::fire_event(object, event_type, Some((0, 0)), context);
+
+        // 8. Fire a progress event named loadend at xhr with 0 and 0.
+        Self::fire_event(object, "loadend", Some((0, 0)), context);
+
+        Ok(())
+    }
+
+    /// [XMLHttpRequest Living Standard § 3.5 `open()`](https://xhr.spec.whatwg.org/#the-open()-method)
+    fn open(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let method = args.first().cloned().unwrap_or_default().to_string(context)?.to_std_string_escaped();
+        let url = args.get(1).cloned().unwrap_or_default().to_string(context)?.to_std_string_escaped();
+
+        // 3. If method is not a method, then throw a "SyntaxError"
+        //    DOMException.
+        // 4. If method is a forbidden method, then throw a "SecurityError"
+        //    DOMException.
+        // 5. Normalize method.
+        let method = fetch::normalize_method(&method)?;
+
+        // 6. Let parsedURL be the result of parsing url with this’s relevant
+        //    settings object’s API base URL and this’s relevant settings
+        //    object’s API URL character encoding.
+        let base_url = context.with_window(|window| window.base_url());
+        let Ok(mut url) = Url::options().base_url(Some(&base_url)).parse(&url) else {
+            return Err(dom_exception("SyntaxError", &format!("Failed to parse URL \"{url}\""), context));
+        };
+
+        // 8. If the async argument is omitted, set async to true, and set
+        //    username and password to null.
+        let asynchronous = args.get(2).is_none_or(JsValue::to_boolean);
+
+        // 9. If parsedURL’s host is non-null, then:
+        if url.has_host() {
+            // 9.1. If the username argument is not null, set the username
+            //      given parsedURL and username.
+            if let Some(username) = args.get(3).filter(|value| !value.is_null_or_undefined()) {
+                let username = username.to_string(context)?.to_std_string_escaped();
+                _ = url.set_username(&username);
+            }
+
+            // 9.2. If the password argument is not null, set the password
+            //      given parsedURL and password.
+            if let Some(password) = args.get(4).filter(|value| !value.is_null_or_undefined()) {
+                let password = password.to_string(context)?.to_std_string_escaped();
+                _ = url.set_password(Some(&password));
+            }
+        }
+
+        // 10. If async is false, the current global object is a Window
+        //     object, and either this’s timeout is not 0 or this’s response
+        //     type is not the empty string, then throw an
+        //     "InvalidAccessError" DOMException.
+        let (timeout, response_type) = Self::with_state(this, |state| (state.timeout, state.response_type))?;
+        if !asynchronous && (timeout != 0 || response_type != ResponseType::Empty) {
+            return Err(dom_exception(
+                "InvalidAccessError",
+                "A synchronous request can't have a timeout or a response type",
+                context,
+            ));
+        }
+
+        // 11. Terminate this’s fetch controller.
+        if let Some(id) = Self::with_state(this, |state| state.fetch_id.take())? {
+            fetch::cancel_fetch(id, context);
+        }
+
+        // 12. Set variables associated with the object as follows: [...]
+        let changed = Self::with_xhr(this, |xhr| {
+            *xhr.response_object.borrow_mut() = None;
+
+            let mut state = xhr.state.borrow_mut();
+            state.send_flag = false;
+            state.request_method = Some(method);
+            state.request_url = Some(url);
+            state.synchronous = !asynchronous;
+            state.author_request_headers.clear();
+            state.response = None;
+
+            // 13. If this’s state is not opened, then:
+            // 13.1. Set this’s state to opened.
+            let changed = state.ready_state != ReadyState::Opened;
+            state.ready_state = ReadyState::Opened;
+            changed
+        })?;
+
+        // 13.2. Fire an event named readystatechange at this.
+        if changed {
+            Self::fire_event(&Self::object_of(this)?, "readystatechange", None, context);
+        }
+
+        Ok(JsValue::Undefined)
+    }
+
+    /// [XMLHttpRequest Living Standard § 3.5.2 `setRequestHeader()`](https://xhr.spec.whatwg.org/#the-setrequestheader()-method)
+    fn set_request_header(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let name = args.first().cloned().unwrap_or_default().to_string(context)?.to_std_string_escaped();
+        let value = args.get(1).cloned().unwrap_or_default().to_string(context)?.to_std_string_escaped();
+
+        // 1. If this’s state is not opened, then throw an
+        //    "InvalidStateError" DOMException.
+        // 2. If this’s send() flag is set, then throw an "InvalidStateError"
+        //    DOMException.
+        let (ready_state, send_flag) = Self::with_state(this, |state| (state.ready_state, state.send_flag))?;
+        if ready_state != ReadyState::Opened || send_flag {
+            return Err(dom_exception("InvalidStateError", "The request isn't opened, or was already sent", context));
+        }
+
+        // 3. Normalize value.
+        // 4. If name is not a header name or value is not a header value,
+        //    then throw a "SyntaxError" DOMException.
+        let (Ok(name), Ok(value)) = (HeaderName::from_bytes(name.as_bytes()), HeaderValue::from_str(value.trim())) else {
+            return Err(dom_exception("SyntaxError", &format!("Invalid header \"{name}\""), context));
+        };
+
+        // 5. If (name, value) is a forbidden request-header, then return.
+        if is_forbidden_request_header(&name) {
+            return Ok(JsValue::Undefined);
+        }
+
+        // 6. Combine (name, value) in this’s author request headers.
+        Self::with_state(this, |state| {
+            let headers = &mut state.author_request_headers;
+            let value = match headers.get(&name) {
+                Some(existing) => {
+                    let mut combined = existing.as_bytes().to_vec();
+                    combined.extend_from_slice(b", ");
+                    combined.extend_from_slice(value.as_bytes());
+                    HeaderValue::from_bytes(&combined).unwrap_or(value)
+                }
+                None => value,
+            };
+            headers.insert(name, value);
+        })?;
+
+        Ok(JsValue::Undefined)
+    }
+
+    /// [XMLHttpRequest Living Standard § 3.5.6 `send()`](https://xhr.spec.whatwg.org/#the-send()-method)
+    fn send(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let object = Self::object_of(this)?;
+
+        // 1. If this’s state is not opened, then throw an
+        //    "InvalidStateError" DOMException.
+        // 2. If this’s send() flag is set, then throw an "InvalidStateError"
+        //    DOMException.
+        let (ready_state, send_flag) = Self::with_state(this, |state| (state.ready_state, state.send_flag))?;
+        if ready_state != ReadyState::Opened || send_flag {
+            return Err(dom_exception("InvalidStateError", "The request isn't opened, or was already sent", context));
+        }
+
+        let (method, url, headers, with_credentials, timeout, synchronous) = Self::with_state(this, |state| (
+            state.request_method.clone().unwrap_or(Method::GET),
+            state.request_url.clone(),
+            state.author_request_headers.clone(),
+            state.with_credentials,
+            state.timeout,
+            state.synchronous,
+        ))?;
+        let Some(url) = url else {
+            return Err(dom_exception("InvalidStateError", "The request isn't opened", context));
+        };
+
+        let base_url = context.with_window(|window| window.base_url());
+        let mut request = retina_fetch::Request::new(
+            url,
+            RequestInitiator::None,
+            RequestDestination::None,
+            RequestMode::Cors,
+            RequestReferrer::Url(base_url),
+        );
+
+        // 3. If this’s request method is `GET` or `HEAD`, then set body to
+        //    null.
+        // 4. If body is not null, then: [...] extract body.
+        let body = args.first().cloned().unwrap_or_default();
+        if !body.is_null_or_undefined() && !matches!(method, Method::GET | Method::HEAD) {
+            request.set_body(fetch::extract_body(&body, context)?);
+        }
+
+        request.set_method(method);
+        *request.headers_mut() = headers;
+        request.set_credentials_mode(if with_credentials {
+            RequestCredentialsMode::Include
+        } else {
+            RequestCredentialsMode::SameOrigin
+        });
+
+        let timeout = (timeout != 0).then(|| Duration::from_millis(timeout as u64));
+
+        // 10. Unset this’s upload complete flag.
+        // 11. Unset this’s timed out flag.
+        // 14. Set this’s send() flag.
+        Self::with_xhr(this, |xhr| {
+            *xhr.response_object.borrow_mut() = None;
+
+            let mut state = xhr.state.borrow_mut();
+            state.response = None;
+            state.send_flag = true;
+        })?;
+
+        // 12. If this’s synchronous flag is unset, then:
+        if !synchronous {
+            // 12.1. Fire a progress event named loadstart at this with 0 and
+            //       0.
+            Self::fire_event(&object, "loadstart", Some((0, 0)), context);
+
+            // 12.3. If this’s state is not opened or this’s send() flag is
+            //       unset, then return.
+            let still_sending = Self::with_state(this, |state| state.ready_state == ReadyState::Opened && state.send_flag)?;
+            if !still_sending {
+                return Ok(JsValue::Undefined);
+            }
+
+            // 12.4. Set this’s fetch controller to the result of fetching
+            //       req with [...]
+            let id = fetch::start_fetch(request, FetchHandler::XmlHttpRequest(object), timeout, context);
+            Self::with_state(this, |state| state.fetch_id = Some(id))?;
+            return Ok(JsValue::Undefined);
+        }
+
+        // 13. Otherwise, if this’s synchronous flag is set: [...] pause until
+        //     either processedResponse is true or this’s timeout is not 0
+        //     and this’s timeout milliseconds have passed.
+        let outcome = fetch::fetch_blocking(request, timeout, context);
+        Self::process_outcome(&object, outcome, context)?;
+        Ok(JsValue::Undefined)
+    }
+
+    /// [XMLHttpRequest Living Standard § 3.5.7 `abort()`](https://xhr.spec.whatwg.org/#the-abort()-method)
+    fn abort(this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let object = Self::object_of(this)?;
+
+        // 1. Abort this’s fetch controller.
+        if let Some(id) = Self::with_state(this, |state| state.fetch_id.take())? {
+            fetch::cancel_fetch(id, context);
+        }
+
+        // 2. If this’s state is opened with this’s send() flag set, headers
+        //    received, or loading, then run the request error steps for this
+        //    and abort.
+        let (ready_state, send_flag) = Self::with_state(this, |state| (state.ready_state, state.send_flag))?;
+        let is_in_progress = match ready_state {
+            ReadyState::Opened => send_flag,
+            ReadyState::HeadersReceived | ReadyState::Loading => true,
+            ReadyState::Unsent | ReadyState::Done => false,
+        };
+        if is_in_progress {
+            Self::run_request_error_steps(&object, "abort", "AbortError", context)?;
+        }
+
+        // 3. If this’s state is done, then set this’s state to unsent and
+        //    this’s response to a network error.
+        Self::with_state(this, |state| {
+            if state.ready_state == ReadyState::Done {
+                state.ready_state = ReadyState::Unsent;
+                state.response = None;
+            }
+        })?;
+
+        Ok(JsValue::Undefined)
+    }
+
+    /// [XMLHttpRequest Living Standard § 3.6.4 `getResponseHeader()`](https://xhr.spec.whatwg.org/#the-getresponseheader()-method)
+    fn get_response_header(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let name = args.first().cloned().unwrap_or_default().to_string(context)?.to_std_string_escaped();
+
+        let value = Self::with_state(this, |state| {
+            let headers = &state.response.as_ref()?.headers;
+            let values: Vec<_> = headers.get_all(name.as_str())
+                .iter()
+                .map(|value| String::from_utf8_lossy(value.as_bytes()).into_owned())
+                .collect();
+            (!values.is_empty()).then(|| values.join(", "))
+        })?;
+
+        Ok(value.map_or(JsValue::Null, |value| JsString::from(value).into()))
+    }
+
+    /// [XMLHttpRequest Living Standard § 3.6.5 `getAllResponseHeaders()`](https://xhr.spec.whatwg.org/#the-getallresponseheaders()-method)
+    fn get_all_response_headers(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        let output = Self::with_state(this, |state| {
+            let Some(response) = &state.response else {
+                return String::new();
+            };
+
+            // 3. Let headers be the result of sorting initialHeaders in
+            //    ascending order, with a being less than b if a’s name is
+            //    legacy-uppercased-byte less than b’s name.
+            let mut names: Vec<&HeaderName> = response.headers.keys().collect();
+            names.sort_by_key(|name| name.as_str().to_ascii_uppercase());
+
+            // 4. For each header in headers, append header’s name, followed
+            //    by a 0x3A 0x20 byte pair, followed by header’s value,
+            //    followed by a 0x0D 0x0A byte pair, to output.
+            let mut output = String::new();
+            for name in names {
+                let values: Vec<_> = response.headers.get_all(name)
+                    .iter()
+                    .map(|value| String::from_utf8_lossy(value.as_bytes()).into_owned())
+                    .collect();
+                output.push_str(&format!("{}: {}\r\n", name.as_str(), values.join(", ")));
+            }
+            output
+        })?;
+
+        Ok(JsString::from(output).into())
+    }
+
+    /// [XMLHttpRequest Living Standard § 3.6.7 `overrideMimeType()`](https://xhr.spec.whatwg.org/#the-overridemimetype()-method)
+    fn override_mime_type(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let mime = args.first().cloned().unwrap_or_default().to_string(context)?.to_std_string_escaped();
+
+        // 1. If this’s state is loading or done, then throw an
+        //    "InvalidStateError" DOMException.
+        if Self::with_state(this, |state| state.ready_state >= ReadyState::Loading)? {
+            return Err(dom_exception("InvalidStateError", "The response is already loading", context));
+        }
+
+        // 2. Set this’s override MIME type to the result of parsing mime.
+        // 3. If this’s override MIME type is failure, then set this’s
+        //    override MIME type to application/octet-stream.
+        let mime = mime.parse::<retina_fetch::mime::Mime>()
+            .map_or_else(|_| retina_fetch::mime::APPLICATION_OCTET_STREAM.to_string(), |mime| mime.to_string());
+        Self::with_state(this, |state| state.override_mime_type = Some(mime))?;
+
+        Ok(JsValue::Undefined)
+    }
+
+    /// [XMLHttpRequest Living Standard § 3.6.1 `readyState`](https://xhr.spec.whatwg.org/#dom-xmlhttprequest-readystate)
+    fn get_ready_state(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_state(this, |state| JsValue::new(state.ready_state as u8))
+    }
+
+    /// [XMLHttpRequest Living Standard § 3.6.2 `status`](https://xhr.spec.whatwg.org/#the-status-attribute)
+    fn get_status(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_state(this, |state| JsValue::new(state.response.as_ref().map_or(0, |response| response.status)))
+    }
+
+    /// [XMLHttpRequest Living Standard § 3.6.3 `statusText`](https://xhr.spec.whatwg.org/#the-statustext-attribute)
+    fn get_status_text(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_state(this, |state| {
+            let status_text = state.response.as_ref().map_or("", |response| response.status_text.as_str());
+            JsString::from(status_text).into()
+        })
+    }
+
+    /// [XMLHttpRequest Living Standard § 3.6.1 `responseURL`](https://xhr.spec.whatwg.org/#the-responseurl-attribute)
+    fn get_response_url(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_state(this, |state| {
+            // Return the empty string if this’s response’s URL is null;
+            // otherwise its serialization with the exclude fragment flag set.
+            let url = state.response.as_ref()
+                .and_then(|response| Url::parse(&response.url).ok())
+                .map(|mut url| {
+                    url.set_fragment(None);
+                    url.to_string()
+                })
+                .unwrap_or_default();
+            JsString::from(url).into()
+        })
+    }
+
+    /// [XMLHttpRequest Living Standard § 3.6.8 `responseType`](https://xhr.spec.whatwg.org/#the-responsetype-attribute)
+    fn get_response_type(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_state(this, |state| JsString::from(state.response_type.as_str()).into())
+    }
+
+    /// [XMLHttpRequest Living Standard § 3.6.8 `responseType`](https://xhr.spec.whatwg.org/#the-responsetype-attribute)
+    fn set_response_type(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let value = args.first().cloned().unwrap_or_default().to_string(context)?.to_std_string_escaped();
+
+        // 1. If the current global object is not a Window object and the
+        //    given value is "document", then return.
+        // 2. If this’s state is loading or done, then throw an
+        //    "InvalidStateError" DOMException.
+        let (ready_state, synchronous) = Self::with_state(this, |state| (state.ready_state, state.synchronous))?;
+        if ready_state >= ReadyState::Loading {
+            return Err(dom_exception("InvalidStateError", "The response is already loading", context));
+        }
+
+        // 3. If the current global object is a Window object and this’s
+        //    synchronous flag is set, then throw an "InvalidAccessError"
+        //    DOMException.
+        if synchronous {
+            return Err(dom_exception("InvalidAccessError", "A synchronous request can't have a response type", context));
+        }
+
+        // Invalid values are ignored, as per the IDL enumeration.
+        if let Some(response_type) = ResponseType::parse(&value) {
+            if response_type == ResponseType::Document {
+                warn!("[XMLHttpRequest] The \"document\" response type isn't supported");
+            }
+
+            // 4. Set this’s response type to the given value.
+            Self::with_state(this, |state| state.response_type = response_type)?;
+        }
+
+        Ok(JsValue::Undefined)
+    }
+
+    /// [XMLHttpRequest Living Standard § 3.6.9 `response`](https://xhr.spec.whatwg.org/#the-response-attribute)
+    fn get_response(this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let (response_type, ready_state) = Self::with_state(this, |state| (state.response_type, state.ready_state))?;
+
+        // 1. If this’s response type is the empty string or "text", then:
+        if matches!(response_type, ResponseType::Empty | ResponseType::Text) {
+            return Self::get_response_text(this, &[], context);
+        }
+
+        // 2. If this’s state is not done, then return null.
+        if ready_state != ReadyState::Done {
+            return Ok(JsValue::Null);
+        }
+
+        // 3. If this’s response object is failure, then return null.
+        // 4. If this’s response object is non-null, then return it.
+        if let Some(response_object) = Self::with_xhr(this, |xhr| xhr.response_object.borrow().clone())? {
+            return Ok(response_object);
+        }
+
+        let Some((body, mime_type)) = Self::with_state(this, |state| {
+            state.response.as_ref().map(|response| (response.body.clone(), state.final_mime_type()))
+        })? else {
+            return Ok(JsValue::Null);
+        };
+
+        let response_object = match response_type {
+            // 5. If this’s response type is "arraybuffer", then set this’s
+            //    response object to a new ArrayBuffer object representing
+            //    this’s received bytes.
+            ResponseType::ArrayBuffer => JsArrayBuffer::from_byte_block(body, context)?.into(),
+
+            // 6. Otherwise, if this’s response type is "blob", set this’s
+            //    response object to a new Blob object representing this’s
+            //    received bytes with type set to the result of get a final
+            //    MIME type for this.
+            ResponseType::Blob => Blob::new(body, mime_type).create_object(context)?.into(),
+
+            // 8. Otherwise: [...] Let jsonObject be the result of running
+            //    parse JSON from bytes on this’s received bytes. If that
+            //    threw an exception, then return null.
+            ResponseType::Json => {
+                let text = JsString::from(String::from_utf8_lossy(&body).as_ref());
+                match parse_json(text, context) {
+                    Ok(value) => value,
+                    Err(_) => return Ok(JsValue::Null),
+                }
+            }
+
+            ResponseType::Document | ResponseType::Empty | ResponseType::Text => JsValue::Null,
+        };
+
+        Self::with_xhr(this, |xhr| *xhr.response_object.borrow_mut() = Some(response_object.clone()))?;
+        Ok(response_object)
+    }
+
+    /// [XMLHttpRequest Living Standard § 3.6.10 `responseText`](https://xhr.spec.whatwg.org/#the-responsetext-attribute)
+    fn get_response_text(this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let (response_type, ready_state, text) = Self::with_state(this, |state| {
+            (state.response_type, state.ready_state, state.text_response())
+        })?;
+
+        // 1. If this’s response type is not the empty string or "text", then
+        //    throw an "InvalidStateError" DOMException.
+        if !matches!(response_type, ResponseType::Empty | ResponseType::Text) {
+            return Err(dom_exception("InvalidStateError", "The response type isn't text", context));
+        }
+
+        // 2. If this’s state is not loading or done, then return the empty
+        //    string.
+        if ready_state < ReadyState::Loading {
+            return Ok(JsString::from("").into());
+        }
+
+        // 3. Return the result of getting a text response for this.
+        Ok(JsString::from(text).into())
+    }
+
+    /// [XMLHttpRequest Living Standard § 3.5.3 `timeout`](https://xhr.spec.whatwg.org/#the-timeout-attribute)
+    fn get_timeout(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_state(this, |state| JsValue::new(state.timeout))
+    }
+
+    /// [XMLHttpRequest Living Standard § 3.5.3 `timeout`](https://xhr.spec.whatwg.org/#the-timeout-attribute)
+    fn set_timeout(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let timeout = args.first().cloned().unwrap_or_default().to_u32(context)?;
+
+        // 1. If the current global object is a Window object and this’s
+        //    synchronous flag is set, then throw an "InvalidAccessError"
+        //    DOMException.
+        if Self::with_state(this, |state| state.synchronous)? {
+            return Err(dom_exception("InvalidAccessError", "A synchronous request can't have a timeout", context));
+        }
+
+        // 2. Set this’s timeout to the given value.
+        Self::with_state(this, |state| state.timeout = timeout)?;
+        Ok(JsValue::Undefined)
+    }
+
+    /// [XMLHttpRequest Living Standard § 3.5.4 `withCredentials`](https://xhr.spec.whatwg.org/#the-withcredentials-attribute)
+    fn get_with_credentials(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_state(this, |state| JsValue::Boolean(state.with_credentials))
+    }
+
+    /// [XMLHttpRequest Living Standard § 3.5.4 `withCredentials`](https://xhr.spec.whatwg.org/#the-withcredentials-attribute)
+    fn set_with_credentials(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let with_credentials = args.first().is_some_and(JsValue::to_boolean);
+
+        // 1. If this’s state is not unsent or opened, then throw an
+        //    "InvalidStateError" DOMException.
+        // 2. If this’s send() flag is set, then throw an "InvalidStateError"
+        //    DOMException.
+        let (ready_state, send_flag) = Self::with_state(this, |state| (state.ready_state, state.send_flag))?;
+        if ready_state > ReadyState::Opened || send_flag {
+            return Err(dom_exception("InvalidStateError", "The request was already sent", context));
+        }
+
+        // 3. Set this’s cross-origin credentials to the given value.
+        Self::with_state(this, |state| state.with_credentials = with_credentials)?;
+        Ok(JsValue::Undefined)
+    }
+
+    fn get_event_handler(this: &JsValue, event_type: &'static str) -> JsResult<JsValue> {
+        Self::with_xhr(this, |xhr| {
+            xhr.event_handlers.borrow()
+                .get(event_type)
+                .map_or(JsValue::Null, |handler| handler.clone().into())
+        })
+    }
+
+    /// Set the event handler for `event_type`, which is removed when the
+    /// value isn't callable.
+    fn set_event_handler(this: &JsValue, event_type: &'static str, value: Option<&JsValue>) -> JsResult<JsValue> {
+        let handler = value.and_then(JsValue::as_callable)
+            .cloned()
+            .and_then(JsFunction::from_object);
+
+        Self::with_xhr(this, |xhr| {
+            let mut event_handlers = xhr.event_handlers.borrow_mut();
+            match handler {
+                Some(handler) => event_handlers.insert(event_type, handler),
+                None => event_handlers.remove(event_type),
+            };
+        })?;
+
+        Ok(JsValue::Undefined)
+    }
+}
+
+impl Class for XmlHttpRequest {
+    const NAME: &'static str = "XMLHttpRequest";
+    const LENGTH: usize = 0;
+    const ATTRIBUTES: Attribute = Attribute::empty();
+
+    /// [XMLHttpRequest Living Standard § 3.2 `new XMLHttpRequest()`](https://xhr.spec.whatwg.org/#constructors)
+    fn constructor(_: &JsValue, _: &[JsValue], _: &mut Context<'_>) -> JsResult<Self> {
+        Ok(Self {
+            state: RefCell::new(State::default()),
+            event_handlers: GcRefCell::new(HashMap::new()),
+            response_object: GcRefCell::new(None),
+        })
+    }
+
+    fn init(class: &mut ClassBuilder<'_, '_>) -> JsResult<()> {
+        let states = [
+            ("UNSENT", ReadyState::Unsent),
+            ("OPENED", ReadyState::Opened),
+            ("HEADERS_RECEIVED", ReadyState::HeadersReceived),
+            ("LOADING", ReadyState::Loading),
+            ("DONE", ReadyState::Done),
+        ];
+        for (name, state) in states {
+            class.property(name, state as u8, Attribute::READONLY | Attribute::ENUMERABLE | Attribute::PERMANENT);
+            class.static_property(name, state as u8, Attribute::READONLY | Attribute::ENUMERABLE | Attribute::PERMANENT);
+        }
+
+        class.accessor_with_function_ptr("readyState", Some(Self::get_ready_state), None, Attribute::all());
+        class.accessor_with_function_ptr("response", Some(Self::get_response), None, Attribute::all());
+        class.accessor_with_function_ptr("responseText", Some(Self::get_response_text), None, Attribute::all());
+        class.accessor_with_function_ptr("responseType", Some(Self::get_response_type), Some(Self::set_response_type), Attribute::all());
+        class.accessor_with_function_ptr("responseURL", Some(Self::get_response_url), None, Attribute::all());
+        class.accessor_with_function_ptr("status", Some(Self::get_status), None, Attribute::all());
+        class.accessor_with_function_ptr("statusText", Some(Self::get_status_text), None, Attribute::all());
+        class.accessor_with_function_ptr("timeout", Some(Self::get_timeout), Some(Self::set_timeout), Attribute::all());
+        class.accessor_with_function_ptr("withCredentials", Some(Self::get_with_credentials), Some(Self::set_with_credentials), Attribute::all());
+
+        class.method("abort", 0, NativeFunction::from_fn_ptr(Self::abort));
+        class.method("getAllResponseHeaders", 0, NativeFunction::from_fn_ptr(Self::get_all_response_headers));
+        class.method("getResponseHeader", 1, NativeFunction::from_fn_ptr(Self::get_response_header));
+        class.method("open", 2, NativeFunction::from_fn_ptr(Self::open));
+        class.method("overrideMimeType", 1, NativeFunction::from_fn_ptr(Self::override_mime_type));
+        class.method("send", 0, NativeFunction::from_fn_ptr(Self::send));
+        class.method("setRequestHeader", 2, NativeFunction::from_fn_ptr(Self::set_request_header));
+
+        // The event handler attributes, e.g. `onload`.
+        for event_type in EVENT_TYPES {
+            let get = FunctionObjectBuilder::new(
+                class.context(),
+                NativeFunction::from_copy_closure(move |this, _, _| Self::get_event_handler(this, event_type)),
+            ).build();
+
+            let set = FunctionObjectBuilder::new(
+                class.context(),
+                NativeFunction::from_copy_closure(move |this, args, _| Self::set_event_handler(this, event_type, args.first())),
+            ).build();
+
+            class.accessor(JsString::from(format!("on{event_type}")), Some(get), Some(set), Attribute::all());
+        }
+
+        Ok(())
+    }
+}
+
+/// Whether the `name` is a [forbidden request-header](https://fetch.spec.whatwg.org/#forbidden-request-header),
+/// which can't be set by scripts.
+fn is_forbidden_request_header(name: &HeaderName) -> bool {
+    const FORBIDDEN_NAMES: [&str; 21] = [
+        "accept-charset",
+        "accept-encoding",
+        "access-control-request-headers",
+        "access-control-request-method",
+        "connection",
+        "content-length",
+        "cookie",
+        "cookie2",
+        "date",
+        "dnt",
+        "expect",
+        "host",
+        "keep-alive",
+        "origin",
+        "referer",
+        "set-cookie",
+        "te",
+        "trailer",
+        "transfer-encoding",
+        "upgrade",
+        "via",
+    ];
+
+    // `HeaderName`s are always lowercase.
+    let name = name.as_str();
+    FORBIDDEN_NAMES.contains(&name) || name.starts_with("proxy-") || name.starts_with("sec-")
+}
@@ -0,0 +1,357 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! The [`fetch()`](https://fetch.spec.whatwg.org/#fetch-method) method, with
+//! the `Request` and `Response` interfaces.
+
+mod request;
+mod response;
+
+pub(crate) use request::{extract_body, normalize_method};
+pub use request::Request;
+pub use response::Response;
+
+use std::{
+    sync::mpsc::{channel, Receiver, RecvTimeoutError, TryRecvError},
+    time::{Duration, Instant},
+};
+
+use boa_engine::prelude::*;
+
+use boa_engine::object::builtins::{JsFunction, JsPromise};
+use boa_engine::builtins::promise::ResolvingFunctions;
+use boa_gc::{Finalize, Trace, custom_trace};
+use log::error;
+use retina_fetch::{FetchTask, HeaderMap, InternalError};
+
+use crate::dom::{AbortSignal, XmlHttpRequest};
+use crate::prelude::*;
+
+/// The identifier of a `fetch()` or `XMLHttpRequest` request, which is used
+/// to abort it.
+pub type FetchId = u32;
+
+/// The parts of a [`retina_fetch::Response`] that are sent back from the
+/// background task, after the body was read.
+pub(crate) struct CompletedResponse {
+    pub(crate) status: u16,
+    pub(crate) status_text: String,
+    pub(crate) url: String,
+    pub(crate) headers: HeaderMap,
+    pub(crate) content_type: String,
+    pub(crate) body: Vec<u8>,
+}
+
+impl CompletedResponse {
+    async fn read(mut response: retina_fetch::Response) -> Self {
+        let body = response.body_bytes().await.to_vec();
+        Self {
+            status: response.status().as_u16(),
+            status_text: response.status_text().to_string(),
+            url: response.url().to_string(),
+            headers: response.headers().clone(),
+            content_type: response.content_type().to_string(),
+            body,
+        }
+    }
+}
+
+/// How a request ended.
+pub(crate) enum FetchOutcome {
+    Response(CompletedResponse),
+    Error(retina_fetch::Error),
+    TimedOut,
+}
+
+/// What is notified when the request completes.
+#[derive(Finalize)]
+pub(crate) enum FetchHandler {
+    /// The promise returned by `fetch()`.
+    Promise {
+        resolve: JsFunction,
+        reject: JsFunction,
+    },
+
+    /// The `XMLHttpRequest` object that sent the request.
+    XmlHttpRequest(JsObject),
+}
+
+unsafe impl Trace for FetchHandler {
+    custom_trace!(this, {
+        match this {
+            FetchHandler::Promise { resolve, reject } => {
+                mark(resolve);
+                mark(reject);
+            }
+            FetchHandler::XmlHttpRequest(object) => mark(object),
+        }
+    });
+}
+
+impl From<ResolvingFunctions> for FetchHandler {
+    fn from(value: ResolvingFunctions) -> Self {
+        Self::Promise {
+            resolve: value.resolve,
+            reject: value.reject,
+        }
+    }
+}
+
+/// A request of which the [`FetchHandler`] isn't notified yet.
+#[derive(Finalize)]
+pub struct PendingFetch {
+    id: FetchId,
+    receiver: Receiver<FetchOutcome>,
+    task: FetchTask,
+    handler: FetchHandler,
+
+    /// The time after which the request times out, if it has a timeout.
+    deadline: Option<Instant>,
+}
+
+unsafe impl Trace for PendingFetch {
+    custom_trace!(this, {
+        mark(&this.handler);
+    });
+}
+
+/// The `fetch()` and `XMLHttpRequest` requests of a `Window` that haven't
+/// completed yet.
+#[derive(Finalize, Default)]
+pub struct FetchList {
+    next_id: FetchId,
+    pending: Vec<PendingFetch>,
+}
+
+unsafe impl Trace for FetchList {
+    custom_trace!(this, {
+        mark(&this.pending);
+    });
+}
+
+impl FetchList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    fn insert(
+        &mut self,
+        receiver: Receiver<FetchOutcome>,
+        task: FetchTask,
+        handler: FetchHandler,
+        deadline: Option<Instant>,
+    ) -> FetchId {
+        self.next_id += 1;
+        let id = self.next_id;
+
+        self.pending.push(PendingFetch {
+            id,
+            receiver,
+            task,
+            handler,
+            deadline,
+        });
+
+        id
+    }
+
+    fn remove(&mut self, id: FetchId) -> Option<PendingFetch> {
+        let index = self.pending.iter().position(|fetch| fetch.id == id)?;
+        Some(self.pending.remove(index))
+    }
+
+    /// Take the requests that completed or timed out at `now`, in the order
+    /// they were started.
+    fn take_completed(&mut self, now: Instant) -> Vec<(PendingFetch, FetchOutcome)> {
+        let mut completed = Vec::new();
+        let mut index = 0;
+
+        while index < self.pending.len() {
+            let fetch = &self.pending[index];
+            let outcome = match fetch.receiver.try_recv() {
+                Ok(outcome) => outcome,
+                Err(TryRecvError::Empty) if fetch.deadline.is_some_and(|deadline| deadline <= now) => {
+                    fetch.task.abort();
+                    FetchOutcome::TimedOut
+                }
+                Err(TryRecvError::Empty) => {
+                    index += 1;
+                    continue;
+                }
+                Err(TryRecvError::Disconnected) => FetchOutcome::Error(InternalError::SynchronizationFault.into()),
+            };
+
+            completed.push((self.pending.remove(index), outcome));
+        }
+
+        completed
+    }
+}
+
+/// Start fetching the `request` in the background, returning the receiver of
+/// the outcome and the task that can be used to cancel it.
+fn spawn_fetch(request: retina_fetch::Request, context: &mut Context) -> (Receiver<FetchOutcome>, FetchTask) {
+    let (sender, receiver) = channel();
+    let client = context.with_window(|window| window.fetch().clone());
+    let fetch_promise = client.fetch(request);
+    let task = client.spawn(async move {
+        let outcome = match fetch_promise.await {
+            Ok(response) => FetchOutcome::Response(CompletedResponse::read(response).await),
+            Err(e) => FetchOutcome::Error(e),
+        };
+        _ = sender.send(outcome);
+    });
+    (receiver, task)
+}
+
+/// Start fetching the `request` in the background. The `handler` is notified
+/// by [`run_completed_fetches()`] when the request completes, or when it
+/// didn't complete within the `timeout`.
+pub(crate) fn start_fetch(
+    request: retina_fetch::Request,
+    handler: FetchHandler,
+    timeout: Option<Duration>,
+    context: &mut Context,
+) -> FetchId {
+    let (receiver, task) = spawn_fetch(request, context);
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    context.with_window(|window| window.fetches().borrow_mut().insert(receiver, task, handler, deadline))
+}
+
+/// Fetch the `request`, blocking until it completed, or until it didn't
+/// complete within the `timeout`.
+pub(crate) fn fetch_blocking(request: retina_fetch::Request, timeout: Option<Duration>, context: &mut Context) -> FetchOutcome {
+    let (receiver, task) = spawn_fetch(request, context);
+
+    let outcome = match timeout {
+        Some(timeout) => receiver.recv_timeout(timeout).map_err(|e| e == RecvTimeoutError::Timeout),
+        None => receiver.recv().map_err(|_| false),
+    };
+
+    match outcome {
+        Ok(outcome) => outcome,
+        Err(true) => {
+            task.abort();
+            FetchOutcome::TimedOut
+        }
+        Err(false) => FetchOutcome::Error(InternalError::SynchronizationFault.into()),
+    }
+}
+
+/// Stop the request with `id`, without notifying its handler.
+pub(crate) fn cancel_fetch(id: FetchId, context: &mut Context) {
+    if let Some(fetch) = context.with_window(|window| window.fetches().borrow_mut().remove(id)) {
+        fetch.task.abort();
+    }
+}
+
+/// [Fetch Living Standard § 5.6 `fetch()`](https://fetch.spec.whatwg.org/#dom-global-fetch)
+pub fn fetch(_this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+    let input = args.first().cloned().unwrap_or_default();
+    let init = args.get(1).cloned().unwrap_or_default();
+
+    // 2. Let requestObject be the result of invoking the initial value of
+    //    Request as constructor with input and init as arguments. If this
+    //    throws an exception, reject p with it and return p.
+    let request = match Request::create(&input, &init, context) {
+        Ok(request) => request,
+        Err(e) => return Ok(JsPromise::reject(e, context)?.into()),
+    };
+
+    // 4. If requestObject’s signal is aborted, then abort the fetch() call
+    //    with p, request, null, and requestObject’s signal’s abort reason.
+    let signal = request.signal.clone();
+    let reason = signal.as_ref()
+        .and_then(|signal| signal.downcast_ref::<AbortSignal>().and_then(|signal| signal.reason()));
+    if let Some(reason) = reason {
+        return Ok(JsPromise::reject(JsError::from_opaque(reason), context)?.into());
+    }
+
+    // 1. Let p be a new promise.
+    let (promise, functions) = JsPromise::new_pending(context);
+
+    // 12. Set controller to the result of calling fetch given request and
+    //     processResponse given response being these steps: [...]
+    let id = start_fetch(request.request, functions.into(), None, context);
+
+    // 11. Add the following abort steps to requestObject’s signal: [...]
+    if let Some(signal) = signal.as_ref().and_then(|signal| signal.downcast_ref::<AbortSignal>()) {
+        signal.add_fetch(id);
+    }
+
+    // 13. Return p.
+    Ok(promise.into())
+}
+
+/// [Abort the `fetch()` call](https://fetch.spec.whatwg.org/#abort-fetch)
+/// with `id`, rejecting its promise with the `reason`. Nothing happens if the
+/// request already completed.
+pub fn abort_fetch(id: FetchId, reason: JsValue, context: &mut Context) {
+    let Some(fetch) = context.with_window(|window| window.fetches().borrow_mut().remove(id)) else {
+        return;
+    };
+
+    fetch.task.abort();
+
+    // 1. Reject promise with error.
+    if let FetchHandler::Promise { reject, .. } = &fetch.handler {
+        if let Err(e) = reject.call(&JsValue::Undefined, &[reason], context) {
+            error!("[fetch] Failed to reject the promise of fetch {id}: {e}");
+        }
+    }
+}
+
+/// Notify the handlers of the `fetch()` and `XMLHttpRequest` requests that
+/// completed or timed out in the background. A microtask checkpoint is
+/// performed after each request. Returns the number of requests that
+/// completed.
+pub fn run_completed_fetches(context: &mut Context) -> usize {
+    let now = Instant::now();
+    let completed = context.with_window(|window| window.fetches().borrow_mut().take_completed(now));
+    let count = completed.len();
+
+    for (fetch, outcome) in completed {
+        let result = match fetch.handler {
+            FetchHandler::Promise { resolve, reject } => settle_promise(resolve, reject, outcome, context),
+            FetchHandler::XmlHttpRequest(object) => XmlHttpRequest::process_outcome(&object, outcome, context),
+        };
+
+        if let Err(e) = result {
+            error!("[fetch] Uncaught exception whilst completing fetch {}: {e}", fetch.id);
+        }
+
+        context.run_jobs();
+    }
+
+    count
+}
+
+/// Resolve the promise of `fetch()` with the `Response`, or reject it with a
+/// TypeError.
+fn settle_promise(resolve: JsFunction, reject: JsFunction, outcome: FetchOutcome, context: &mut Context) -> JsResult<()> {
+    let error = match outcome {
+        FetchOutcome::Response(response) => {
+            let response = Response::new(
+                response.status,
+                response.status_text,
+                response.url,
+                response.content_type,
+                response.body,
+            ).create_object(context)?;
+            resolve.call(&JsValue::Undefined, &[response.into()], context)?;
+            return Ok(());
+        }
+        FetchOutcome::Error(e) => format!("Failed to fetch: {e}"),
+        FetchOutcome::TimedOut => String::from("Failed to fetch: timed out"),
+    };
+
+    let error = JsNativeError::typ()
+        .with_message(error)
+        .to_opaque(context);
+    reject.call(&JsValue::Undefined, &[error.into()], context)?;
+    Ok(())
+}
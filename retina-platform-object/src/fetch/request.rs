@@ -0,0 +1,339 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use boa_engine::prelude::*;
+
+use boa_engine::class::{Class, ClassBuilder};
+use boa_engine::object::builtins::JsArray;
+use boa_engine::property::Attribute;
+use boa_gc::{Finalize, Trace, custom_trace};
+use retina_fetch::{
+    mime,
+    HeaderName,
+    HeaderValue,
+    Method,
+    RequestBody,
+    RequestCredentialsMode,
+    RequestDestination,
+    RequestInitiator,
+    RequestMode,
+    RequestReferrer,
+    Url,
+};
+
+use crate::blob::{array_buffer_bytes, Blob};
+use crate::dom::AbortSignal;
+use crate::prelude::*;
+
+/// The [Interface `Request`](https://fetch.spec.whatwg.org/#request-class)
+/// platform object.
+#[derive(Finalize)]
+pub struct Request {
+    pub(crate) request: retina_fetch::Request,
+
+    /// The [`AbortSignal`] object that aborts the fetch, if one was given.
+    pub(crate) signal: Option<JsObject>,
+}
+
+unsafe impl Trace for Request {
+    custom_trace!(this, {
+        if let Some(signal) = &this.signal {
+            mark(signal);
+        }
+    });
+}
+
+impl Request {
+    /// Create a request from the `input` and `init` arguments, as is done by
+    /// both the [constructor](https://fetch.spec.whatwg.org/#dom-request) and
+    /// `fetch()`.
+    pub fn create(input: &JsValue, init: &JsValue, context: &mut Context) -> JsResult<Self> {
+        // 5. If input is a string, then:
+        // 6. Otherwise:
+        let mut this = match input.as_object().and_then(|object| object.downcast_ref::<Self>()) {
+            Some(input) => Self {
+                request: input.request.clone(),
+                signal: input.signal.clone(),
+            },
+            None => {
+                let input = input.to_string(context)?.to_std_string_escaped();
+
+                // 5.2. Let parsedURL be the result of parsing input with
+                //      baseURL.
+                let base_url = context.with_window(|window| window.base_url());
+                let url = Url::options()
+                    .base_url(Some(&base_url))
+                    .parse(&input)
+                    .map_err(|e| JsNativeError::typ().with_message(format!("Failed to parse URL \"{input}\": {e}")))?;
+
+                // 5.4. If parsedURL includes credentials, then throw a
+                //      TypeError.
+                if !url.username().is_empty() || url.password().is_some() {
+                    return Err(JsNativeError::typ()
+                        .with_message("The URL includes credentials")
+                        .into());
+                }
+
+                // 5.7. Set fallbackMode to "cors".
+                let mut request = retina_fetch::Request::new(
+                    url,
+                    RequestInitiator::None,
+                    RequestDestination::None,
+                    RequestMode::Cors,
+                    RequestReferrer::Url(base_url),
+                );
+
+                // 5.8. Set fallbackCredentials to "same-origin".
+                request.set_credentials_mode(RequestCredentialsMode::SameOrigin);
+
+                Self {
+                    request,
+                    signal: None,
+                }
+            }
+        };
+
+        if let Some(init) = init.as_object() {
+            this.apply_init(init, context)?;
+        }
+
+        Ok(this)
+    }
+
+    /// Apply the members of the [`RequestInit`](https://fetch.spec.whatwg.org/#requestinit)
+    /// dictionary.
+    fn apply_init(&mut self, init: &JsObject, context: &mut Context) -> JsResult<()> {
+        // 23. If init["mode"] exists, then:
+        if let Some(mode) = string_member(init, "mode", context)? {
+            let mode = match mode.as_str() {
+                "cors" => RequestMode::Cors,
+                "no-cors" => RequestMode::NoCors,
+                "same-origin" => RequestMode::SameOrigin,
+                // 23.1. If mode is "navigate", then throw a TypeError.
+                _ => return Err(JsNativeError::typ()
+                    .with_message(format!("Invalid request mode: \"{mode}\""))
+                    .into()),
+            };
+            self.request.set_mode(mode);
+        }
+
+        // 24. If init["credentials"] exists, then set request’s credentials
+        //     mode to it.
+        if let Some(credentials) = string_member(init, "credentials", context)? {
+            let credentials = match credentials.as_str() {
+                "omit" => RequestCredentialsMode::Omit,
+                "same-origin" => RequestCredentialsMode::SameOrigin,
+                "include" => RequestCredentialsMode::Include,
+                _ => return Err(JsNativeError::typ()
+                    .with_message(format!("Invalid credentials mode: \"{credentials}\""))
+                    .into()),
+            };
+            self.request.set_credentials_mode(credentials);
+        }
+
+        // 25. If init["method"] exists, then:
+        if let Some(method) = string_member(init, "method", context)? {
+            self.request.set_method(normalize_method(&method)?);
+        }
+
+        // 29. If init["signal"] exists, then set signal to it.
+        let signal = init.get("signal", context)?;
+        if !signal.is_undefined() {
+            self.signal = match signal.as_object() {
+                Some(signal) if signal.is::<AbortSignal>() => Some(signal.clone()),
+                None if signal.is_null() => None,
+                _ => return Err(JsNativeError::typ()
+                    .with_message("`signal` is not an `AbortSignal`")
+                    .into()),
+            };
+        }
+
+        // 32. If init is not empty, then:
+        let headers = init.get("headers", context)?;
+        if !headers.is_undefined() {
+            // 32.3. Empty this’s headers’s header list.
+            // 32.4. If headers is a Headers object, then for each header of
+            //       its header list, append header to this’s headers.
+            // 32.5. Otherwise, fill this’s headers with headers.
+            self.request.headers_mut().clear();
+            for (name, value) in header_pairs(&headers, context)? {
+                let name = HeaderName::from_bytes(name.as_bytes())
+                    .map_err(|_| JsNativeError::typ().with_message(format!("Invalid header name: \"{name}\"")))?;
+                let value = HeaderValue::from_str(value.trim())
+                    .map_err(|_| JsNativeError::typ().with_message(format!("Invalid value of header \"{name}\"")))?;
+                self.request.headers_mut().append(name, value);
+            }
+        }
+
+        // 34. If either init["body"] exists and is non-null or inputBody is
+        //     non-null, and request’s method is `GET` or `HEAD`, then throw a
+        //     TypeError.
+        let body = init.get("body", context)?;
+        if body.is_null_or_undefined() {
+            return Ok(());
+        }
+
+        if matches!(*self.request.method(), Method::GET | Method::HEAD) {
+            return Err(JsNativeError::typ()
+                .with_message("A request with the GET or HEAD method cannot have a body")
+                .into());
+        }
+
+        // 36. If init["body"] exists and is non-null, then let Content-Type
+        //     be the result of extracting init["body"].
+        self.request.set_body(extract_body(&body, context)?);
+        Ok(())
+    }
+
+    fn with_request<T>(this: &JsValue, f: impl FnOnce(&Self) -> T) -> JsResult<T> {
+        let this = this.as_object()
+            .and_then(|obj| obj.downcast_ref::<Self>())
+            .ok_or_else(|| JsError::from_opaque("Value is not `Request`".into()))?;
+        Ok(f(&this))
+    }
+
+    /// [Fetch Living Standard § 6.3 `method`](https://fetch.spec.whatwg.org/#dom-request-method)
+    fn get_method(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_request(this, |request| JsString::from(request.request.method().as_str()).into())
+    }
+
+    /// [Fetch Living Standard § 6.3 `url`](https://fetch.spec.whatwg.org/#dom-request-url)
+    fn get_url(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_request(this, |request| JsString::from(request.request.url().as_str()).into())
+    }
+
+    /// [Fetch Living Standard § 6.3 `mode`](https://fetch.spec.whatwg.org/#dom-request-mode)
+    fn get_mode(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_request(this, |request| JsString::from(request.request.mode().as_str()).into())
+    }
+
+    /// [Fetch Living Standard § 6.3 `credentials`](https://fetch.spec.whatwg.org/#dom-request-credentials)
+    fn get_credentials(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_request(this, |request| JsString::from(request.request.credentials_mode().as_str()).into())
+    }
+}
+
+impl Class for Request {
+    const NAME: &'static str = "Request";
+    const LENGTH: usize = 1;
+    const ATTRIBUTES: Attribute = Attribute::empty();
+
+    /// [Fetch Living Standard § 6.3 `new Request()`](https://fetch.spec.whatwg.org/#dom-request)
+    fn constructor(_this: &JsValue, args: &[JsValue], context: &mut Context<'_>) -> JsResult<Self> {
+        let input = args.first().cloned().unwrap_or_default();
+        let init = args.get(1).cloned().unwrap_or_default();
+        Self::create(&input, &init, context)
+    }
+
+    fn init(class: &mut ClassBuilder<'_, '_>) -> JsResult<()> {
+        class.accessor_with_function_ptr("credentials", Some(Self::get_credentials), None, Attribute::all());
+        class.accessor_with_function_ptr("method", Some(Self::get_method), None, Attribute::all());
+        class.accessor_with_function_ptr("mode", Some(Self::get_mode), None, Attribute::all());
+        class.accessor_with_function_ptr("url", Some(Self::get_url), None, Attribute::all());
+        Ok(())
+    }
+}
+
+/// Get the member `name` of the `dictionary` as a string, if it exists.
+fn string_member(dictionary: &JsObject, name: &str, context: &mut Context) -> JsResult<Option<String>> {
+    let value = dictionary.get(name, context)?;
+    if value.is_undefined() {
+        return Ok(None);
+    }
+
+    Ok(Some(value.to_string(context)?.to_std_string_escaped()))
+}
+
+/// Validate and [normalize](https://fetch.spec.whatwg.org/#concept-method-normalize)
+/// the `method`.
+pub(crate) fn normalize_method(method: &str) -> JsResult<Method> {
+    // 25.2. If method is not a method or method is a forbidden method, then
+    //       throw a TypeError.
+    let is_forbidden = ["CONNECT", "TRACE", "TRACK"].iter()
+        .any(|forbidden| forbidden.eq_ignore_ascii_case(method));
+    if is_forbidden {
+        return Err(JsNativeError::typ()
+            .with_message(format!("Forbidden method: \"{method}\""))
+            .into());
+    }
+
+    // 25.3. Normalize method.
+    let normalized = ["DELETE", "GET", "HEAD", "OPTIONS", "POST", "PUT"].iter()
+        .find(|normalized| normalized.eq_ignore_ascii_case(method))
+        .map_or_else(|| method.to_string(), |normalized| normalized.to_string());
+
+    Method::from_bytes(normalized.as_bytes())
+        .map_err(|_| JsNativeError::typ().with_message(format!("Invalid method: \"{method}\"")).into())
+}
+
+/// Get the name-value pairs of the [`HeadersInit`](https://fetch.spec.whatwg.org/#typedefdef-headersinit),
+/// which is either a sequence of pairs, or a record.
+fn header_pairs(headers: &JsValue, context: &mut Context) -> JsResult<Vec<(String, String)>> {
+    let Some(object) = headers.as_object().cloned() else {
+        return Err(JsNativeError::typ().with_message("`headers` is not an object").into());
+    };
+
+    // A record is converted to a sequence of pairs using `Object.entries()`.
+    let pairs = if object.is_array() {
+        JsArray::from_object(object)?
+    } else {
+        let entries = context.global_object()
+            .get("Object", context)?
+            .as_object()
+            .cloned()
+            .ok_or_else(|| JsError::from_opaque("`Object` is not an object".into()))?
+            .get("entries", context)?;
+        let entries = entries.as_callable()
+            .ok_or_else(|| JsError::from_opaque("`Object.entries` is not a function".into()))?
+            .call(&JsValue::Undefined, &[JsValue::Object(object)], context)?;
+        JsArray::from_object(entries.as_object().cloned().unwrap_or_default())?
+    };
+
+    let mut result = Vec::new();
+    for index in 0..pairs.length(context)? {
+        let pair = pairs.get(index, context)?;
+        let pair = pair.as_object()
+            .filter(|pair| pair.is_array())
+            .cloned()
+            .ok_or_else(|| JsNativeError::typ().with_message("A header is not a sequence"))?;
+        let pair = JsArray::from_object(pair)?;
+
+        // If header’s size is not 2, then throw a TypeError.
+        if pair.length(context)? != 2 {
+            return Err(JsNativeError::typ()
+                .with_message("A header must consist of a name and a value")
+                .into());
+        }
+
+        let name = pair.get(0, context)?.to_string(context)?.to_std_string_escaped();
+        let value = pair.get(1, context)?.to_string(context)?.to_std_string_escaped();
+        result.push((name, value));
+    }
+
+    Ok(result)
+}
+
+/// [Extract](https://fetch.spec.whatwg.org/#concept-bodyinit-extract) the
+/// body and its `Content-Type`.
+pub(crate) fn extract_body(body: &JsValue, context: &mut Context) -> JsResult<RequestBody> {
+    if let Some(object) = body.as_object() {
+        if let Some(bytes) = array_buffer_bytes(object) {
+            return Ok(RequestBody::Bytes {
+                content_type: mime::APPLICATION_OCTET_STREAM,
+                bytes,
+            });
+        }
+
+        if let Some(blob) = object.downcast_ref::<Blob>() {
+            return Ok(RequestBody::Bytes {
+                content_type: blob.content_type().parse().unwrap_or(mime::APPLICATION_OCTET_STREAM),
+                bytes: blob.bytes().to_vec(),
+            });
+        }
+    }
+
+    Ok(RequestBody::Bytes {
+        content_type: mime::TEXT_PLAIN_UTF_8,
+        bytes: body.to_string(context)?.to_std_string_escaped().into_bytes(),
+    })
+}
@@ -0,0 +1,161 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use std::cell::RefCell;
+
+use boa_engine::prelude::*;
+
+use boa_engine::class::{Class, ClassBuilder};
+use boa_engine::object::ObjectData;
+use boa_engine::object::builtins::{JsArrayBuffer, JsPromise};
+use boa_engine::property::Attribute;
+use boa_gc::{Finalize, Trace, empty_trace};
+
+use crate::blob::Blob;
+use crate::prelude::*;
+
+/// The [Interface `Response`](https://fetch.spec.whatwg.org/#response-class)
+/// platform object. Since the body is read completely before `fetch()`
+/// resolves, it isn't a stream.
+#[derive(Finalize)]
+pub struct Response {
+    status: u16,
+    status_text: String,
+    url: String,
+    content_type: String,
+
+    /// The body of the response, which is [`None`] once it is used.
+    body: RefCell<Option<Vec<u8>>>,
+}
+
+unsafe impl Trace for Response {
+    empty_trace!();
+}
+
+impl Response {
+    pub fn new(status: u16, status_text: String, url: String, content_type: String, body: Vec<u8>) -> Self {
+        Self {
+            status,
+            status_text,
+            url,
+            content_type,
+            body: RefCell::new(Some(body)),
+        }
+    }
+
+    pub fn create_object(self, context: &mut Context) -> JsResult<JsObject> {
+        Ok(JsObject::from_proto_and_data(
+            Some(class_prototype::<Self>(context)?),
+            ObjectData::native_object(self),
+        ))
+    }
+
+    fn with_response<T>(this: &JsValue, f: impl FnOnce(&Self) -> T) -> JsResult<T> {
+        let this = this.as_object()
+            .and_then(|obj| obj.downcast_ref::<Self>())
+            .ok_or_else(|| JsError::from_opaque("Value is not `Response`".into()))?;
+        Ok(f(&this))
+    }
+
+    /// [Consume the body](https://fetch.spec.whatwg.org/#concept-body-consume-body)
+    /// of the response, converting it using `convert`. The returned promise
+    /// is rejected if the body was already used, or the conversion failed.
+    fn consume_body(
+        this: &JsValue,
+        context: &mut Context,
+        convert: impl FnOnce(Vec<u8>, String, &mut Context) -> JsResult<JsValue>,
+    ) -> JsResult<JsValue> {
+        let (body, content_type) = Self::with_response(this, |response| {
+            (response.body.borrow_mut().take(), response.content_type.clone())
+        })?;
+
+        // 1. If object is unusable, then return a promise rejected with a
+        //    TypeError.
+        let Some(body) = body else {
+            let error = JsNativeError::typ().with_message("The body of the response is already used");
+            return Ok(JsPromise::reject(error, context)?.into());
+        };
+
+        let promise = match convert(body, content_type, context) {
+            Ok(value) => JsPromise::resolve(value, context)?,
+            Err(e) => JsPromise::reject(e, context)?,
+        };
+        Ok(promise.into())
+    }
+
+    /// [Fetch Living Standard § 5 `text()`](https://fetch.spec.whatwg.org/#dom-body-text)
+    fn text(this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::consume_body(this, context, |body, _, _| {
+            Ok(JsString::from(String::from_utf8_lossy(&body).as_ref()).into())
+        })
+    }
+
+    /// [Fetch Living Standard § 5 `json()`](https://fetch.spec.whatwg.org/#dom-body-json)
+    fn json(this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::consume_body(this, context, |body, _, context| {
+            parse_json(JsString::from(String::from_utf8_lossy(&body).as_ref()), context)
+        })
+    }
+
+    /// [Fetch Living Standard § 5 `arrayBuffer()`](https://fetch.spec.whatwg.org/#dom-body-arraybuffer)
+    fn array_buffer(this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::consume_body(this, context, |body, _, context| {
+            Ok(JsArrayBuffer::from_byte_block(body, context)?.into())
+        })
+    }
+
+    /// [Fetch Living Standard § 5 `blob()`](https://fetch.spec.whatwg.org/#dom-body-blob)
+    fn blob(this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::consume_body(this, context, |body, content_type, context| {
+            Ok(Blob::new(body, content_type).create_object(context)?.into())
+        })
+    }
+
+    /// [Fetch Living Standard § 6.4 `bodyUsed`](https://fetch.spec.whatwg.org/#dom-body-bodyused)
+    fn get_body_used(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_response(this, |response| JsValue::Boolean(response.body.borrow().is_none()))
+    }
+
+    /// [Fetch Living Standard § 6.4 `ok`](https://fetch.spec.whatwg.org/#dom-response-ok)
+    fn get_ok(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_response(this, |response| JsValue::Boolean((200..=299).contains(&response.status)))
+    }
+
+    /// [Fetch Living Standard § 6.4 `status`](https://fetch.spec.whatwg.org/#dom-response-status)
+    fn get_status(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_response(this, |response| JsValue::new(response.status))
+    }
+
+    /// [Fetch Living Standard § 6.4 `statusText`](https://fetch.spec.whatwg.org/#dom-response-statustext)
+    fn get_status_text(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_response(this, |response| JsString::from(response.status_text.as_str()).into())
+    }
+
+    /// [Fetch Living Standard § 6.4 `url`](https://fetch.spec.whatwg.org/#dom-response-url)
+    fn get_url(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_response(this, |response| JsString::from(response.url.as_str()).into())
+    }
+}
+
+impl Class for Response {
+    const NAME: &'static str = "Response";
+    const LENGTH: usize = 0;
+    const ATTRIBUTES: Attribute = Attribute::empty();
+
+    fn constructor(_: &JsValue, _: &[JsValue], _: &mut Context<'_>) -> JsResult<Self> {
+        illegal_constructor()
+    }
+
+    fn init(class: &mut ClassBuilder<'_, '_>) -> JsResult<()> {
+        class.accessor_with_function_ptr("bodyUsed", Some(Self::get_body_used), None, Attribute::all());
+        class.accessor_with_function_ptr("ok", Some(Self::get_ok), None, Attribute::all());
+        class.accessor_with_function_ptr("status", Some(Self::get_status), None, Attribute::all());
+        class.accessor_with_function_ptr("statusText", Some(Self::get_status_text), None, Attribute::all());
+        class.accessor_with_function_ptr("url", Some(Self::get_url), None, Attribute::all());
+        class.method("arrayBuffer", 0, NativeFunction::from_fn_ptr(Self::array_buffer));
+        class.method("blob", 0, NativeFunction::from_fn_ptr(Self::blob));
+        class.method("json", 0, NativeFunction::from_fn_ptr(Self::json));
+        class.method("text", 0, NativeFunction::from_fn_ptr(Self::text));
+        Ok(())
+    }
+}
@@ -3,21 +3,64 @@
 
 use boa_engine::class::Class;
 
+pub mod animation_frames;
+pub mod blob;
+pub mod canvas;
+pub mod clipboard;
+pub mod console;
 pub mod dom;
 pub mod event_target;
+pub mod fetch;
 pub mod nav_history;
+pub mod performance;
+pub mod permissions;
 pub mod prelude;
+pub mod storage;
+pub mod timers;
+pub mod url;
 
 pub trait PlatformObject: Class {
 
 }
 
 pub fn register_all(context: &mut boa_engine::Context) -> Result<(), boa_engine::JsError> {
+    use blob::*;
+    use canvas::*;
+    use clipboard::*;
+    use console::*;
     use nav_history::*;
     use dom::*;
+    use fetch::*;
+    use performance::*;
+    use permissions::*;
+    use storage::*;
+    use url::*;
 
+    context.register_global_class::<AbortController>()?;
+    context.register_global_class::<AbortSignal>()?;
+    context.register_global_class::<Blob>()?;
+    context.register_global_class::<CanvasRenderingContext2D>()?;
+    context.register_global_class::<Clipboard>()?;
+    context.register_global_class::<Console>()?;
     context.register_global_class::<Document>()?;
+    context.register_global_class::<HTMLCanvasElement>()?;
+    context.register_global_class::<HTMLElement>()?;
+    context.register_global_class::<Location>()?;
+    context.register_global_class::<MutationObserver>()?;
+    context.register_global_class::<Performance>()?;
+    context.register_global_class::<PerformanceEntry>()?;
+    context.register_global_class::<PerformanceObserver>()?;
+    context.register_global_class::<PerformanceObserverEntryList>()?;
+    context.register_global_class::<Permissions>()?;
+    context.register_global_class::<Request>()?;
+    context.register_global_class::<Response>()?;
+    context.register_global_class::<Storage>()?;
+    context.register_global_class::<Url>()?;
+    context.register_global_class::<UrlSearchParams>()?;
     context.register_global_class::<Window>()?;
+    context.register_global_class::<XmlHttpRequest>()?;
+
+    prelude::inherit_class::<HTMLCanvasElement, HTMLElement>(context)?;
 
     Ok(())
 }
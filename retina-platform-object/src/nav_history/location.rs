@@ -0,0 +1,253 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use boa_engine::prelude::*;
+
+use boa_engine::class::{Class, ClassBuilder};
+use boa_engine::object::ObjectData;
+use boa_engine::property::Attribute;
+use boa_gc::{Finalize, Trace, empty_trace};
+use retina_fetch::Url;
+
+use crate::prelude::*;
+
+/// How a navigation affects the [session history](https://html.spec.whatwg.org/multipage/document-sequences.html#tn-session-history-entries).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HistoryHandling {
+    /// A new entry is added after the current one.
+    Push,
+
+    /// The current entry is replaced.
+    Replace,
+}
+
+/// A navigation requested by a script, which is performed by the page after
+/// the script ran.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NavigationRequest {
+    /// Navigate to the `url`. Navigations that only change the fragment
+    /// don't load the document again, but scroll to the indicated part.
+    Navigate {
+        url: Url,
+        history_handling: HistoryHandling,
+    },
+
+    /// Load the current URL again.
+    Reload,
+}
+
+/// Whether navigating from `current` to `url` only changes the fragment,
+/// which means the document doesn't have to be loaded again.
+pub fn is_fragment_navigation(current: &Url, url: &Url) -> bool {
+    if url.fragment().is_none() {
+        return false;
+    }
+
+    let mut current = current.clone();
+    current.set_fragment(None);
+
+    let mut url = url.clone();
+    url.set_fragment(None);
+
+    current == url
+}
+
+/// The [Interface `Location`](https://html.spec.whatwg.org/multipage/nav-history-apis.html#the-location-interface)
+/// platform object, available as `window.location`, which reflects the URL
+/// of the document.
+#[derive(Finalize)]
+pub struct Location;
+
+unsafe impl Trace for Location {
+    empty_trace!();
+}
+
+impl Location {
+    pub fn create_object(self, context: &mut Context) -> JsResult<JsObject> {
+        Ok(JsObject::from_proto_and_data(
+            Some(class_prototype::<Self>(context)?),
+            ObjectData::native_object(self),
+        ))
+    }
+
+    fn url(context: &mut Context) -> Url {
+        context.with_window(|window| window.url())
+    }
+
+    /// Parse the `url` against the document base URL, which throws a
+    /// "SyntaxError" when it is invalid.
+    fn parse_url(url: &JsValue, context: &mut Context) -> JsResult<Url> {
+        let url = url.to_string(context)?.to_std_string_escaped();
+        let base_url = context.with_window(|window| window.base_url());
+
+        Url::options()
+            .base_url(Some(&base_url))
+            .parse(&url)
+            .map_err(|e| dom_exception("SyntaxError", &format!("Invalid URL \"{url}\": {e}"), context))
+    }
+
+    /// [Location-object navigate](https://html.spec.whatwg.org/multipage/nav-history-apis.html#location-object-navigate)
+    /// to the `url`.
+    fn navigate(url: Url, history_handling: HistoryHandling, context: &mut Context) {
+        context.with_window(|window| {
+            // Navigating to a fragment happens synchronously, such that the
+            // location reflects the new URL immediately.
+            if is_fragment_navigation(&window.url(), &url) {
+                window.set_url(url.clone());
+            }
+
+            window.request_navigation(NavigationRequest::Navigate {
+                url,
+                history_handling,
+            });
+        });
+    }
+
+    /// [HTML Living Standard § 7.2.4 `href`](https://html.spec.whatwg.org/multipage/nav-history-apis.html#dom-location-href)
+    fn get_href(_this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Ok(JsString::from(Self::url(context).as_str()).into())
+    }
+
+    /// [HTML Living Standard § 7.2.4 `href` setter](https://html.spec.whatwg.org/multipage/nav-history-apis.html#the-location-interface:dom-location-href-2)
+    fn set_href(_this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let url = Self::parse_url(&args.first().cloned().unwrap_or_default(), context)?;
+        Self::navigate(url, HistoryHandling::Push, context);
+        Ok(JsValue::Undefined)
+    }
+
+    /// [HTML Living Standard § 7.2.4 `origin`](https://html.spec.whatwg.org/multipage/nav-history-apis.html#dom-location-origin)
+    fn get_origin(_this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Ok(JsString::from(Self::url(context).origin().ascii_serialization()).into())
+    }
+
+    /// [HTML Living Standard § 7.2.4 `protocol`](https://html.spec.whatwg.org/multipage/nav-history-apis.html#dom-location-protocol)
+    fn get_protocol(_this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Ok(JsString::from(format!("{}:", Self::url(context).scheme())).into())
+    }
+
+    /// [HTML Living Standard § 7.2.4 `host`](https://html.spec.whatwg.org/multipage/nav-history-apis.html#dom-location-host)
+    fn get_host(_this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let url = Self::url(context);
+        let host = match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{host}:{port}"),
+            (Some(host), None) => host.to_string(),
+            (None, _) => String::new(),
+        };
+        Ok(JsString::from(host).into())
+    }
+
+    /// [HTML Living Standard § 7.2.4 `hostname`](https://html.spec.whatwg.org/multipage/nav-history-apis.html#dom-location-hostname)
+    fn get_hostname(_this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Ok(JsString::from(Self::url(context).host_str().unwrap_or_default()).into())
+    }
+
+    /// [HTML Living Standard § 7.2.4 `port`](https://html.spec.whatwg.org/multipage/nav-history-apis.html#dom-location-port)
+    fn get_port(_this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let port = Self::url(context).port().map(|port| port.to_string()).unwrap_or_default();
+        Ok(JsString::from(port).into())
+    }
+
+    /// [HTML Living Standard § 7.2.4 `pathname`](https://html.spec.whatwg.org/multipage/nav-history-apis.html#dom-location-pathname)
+    fn get_pathname(_this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Ok(JsString::from(Self::url(context).path()).into())
+    }
+
+    /// [HTML Living Standard § 7.2.4 `search`](https://html.spec.whatwg.org/multipage/nav-history-apis.html#dom-location-search)
+    fn get_search(_this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let search = match Self::url(context).query() {
+            Some(query) if !query.is_empty() => format!("?{query}"),
+            _ => String::new(),
+        };
+        Ok(JsString::from(search).into())
+    }
+
+    /// [HTML Living Standard § 7.2.4 `hash`](https://html.spec.whatwg.org/multipage/nav-history-apis.html#dom-location-hash)
+    fn get_hash(_this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let hash = match Self::url(context).fragment() {
+            Some(fragment) if !fragment.is_empty() => format!("#{fragment}"),
+            _ => String::new(),
+        };
+        Ok(JsString::from(hash).into())
+    }
+
+    /// [HTML Living Standard § 7.2.4 `hash` setter](https://html.spec.whatwg.org/multipage/nav-history-apis.html#the-location-interface:dom-location-hash-2)
+    fn set_hash(_this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let value = args.first().cloned().unwrap_or_default()
+            .to_string(context)?
+            .to_std_string_escaped();
+
+        // 3. Let copyURL be a copy of this's url.
+        let url = Self::url(context);
+        let mut copy_url = url.clone();
+
+        // 4. Let thisURLFragment be copyURL's fragment if it is non-null;
+        //    otherwise the empty string.
+        let this_url_fragment = copy_url.fragment().unwrap_or_default().to_string();
+
+        // 5. Let input be the given value with a single leading "#" removed,
+        //    if any.
+        let input = value.strip_prefix('#').unwrap_or(&value);
+
+        // 6. Set copyURL's fragment to the empty string.
+        // 7. Basic URL parse input, with copyURL as url and fragment state as
+        //    state override.
+        copy_url.set_fragment(Some(input));
+
+        // 8. If copyURL's fragment is thisURLFragment, then return.
+        if copy_url.fragment() == Some(this_url_fragment.as_str()) {
+            return Ok(JsValue::Undefined);
+        }
+
+        // 9. Location-object navigate this to copyURL.
+        Self::navigate(copy_url, HistoryHandling::Push, context);
+        Ok(JsValue::Undefined)
+    }
+
+    /// [HTML Living Standard § 7.2.4 `assign()`](https://html.spec.whatwg.org/multipage/nav-history-apis.html#dom-location-assign)
+    fn assign(_this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let url = Self::parse_url(&args.first().cloned().unwrap_or_default(), context)?;
+        Self::navigate(url, HistoryHandling::Push, context);
+        Ok(JsValue::Undefined)
+    }
+
+    /// [HTML Living Standard § 7.2.4 `replace()`](https://html.spec.whatwg.org/multipage/nav-history-apis.html#dom-location-replace)
+    fn replace(_this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let url = Self::parse_url(&args.first().cloned().unwrap_or_default(), context)?;
+        Self::navigate(url, HistoryHandling::Replace, context);
+        Ok(JsValue::Undefined)
+    }
+
+    /// [HTML Living Standard § 7.2.4 `reload()`](https://html.spec.whatwg.org/multipage/nav-history-apis.html#dom-location-reload)
+    fn reload(_this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        context.with_window(|window| window.request_navigation(NavigationRequest::Reload));
+        Ok(JsValue::Undefined)
+    }
+}
+
+impl Class for Location {
+    const NAME: &'static str = "Location";
+    const LENGTH: usize = 0;
+    const ATTRIBUTES: Attribute = Attribute::empty();
+
+    fn constructor(_: &JsValue, _: &[JsValue], _: &mut Context<'_>) -> JsResult<Self> {
+        illegal_constructor()
+    }
+
+    fn init(class: &mut ClassBuilder<'_, '_>) -> JsResult<()> {
+        class.accessor_with_function_ptr("href", Some(Self::get_href), Some(Self::set_href), Attribute::all());
+        class.accessor_with_function_ptr("origin", Some(Self::get_origin), None, Attribute::all());
+        class.accessor_with_function_ptr("protocol", Some(Self::get_protocol), None, Attribute::all());
+        class.accessor_with_function_ptr("host", Some(Self::get_host), None, Attribute::all());
+        class.accessor_with_function_ptr("hostname", Some(Self::get_hostname), None, Attribute::all());
+        class.accessor_with_function_ptr("port", Some(Self::get_port), None, Attribute::all());
+        class.accessor_with_function_ptr("pathname", Some(Self::get_pathname), None, Attribute::all());
+        class.accessor_with_function_ptr("search", Some(Self::get_search), None, Attribute::all());
+        class.accessor_with_function_ptr("hash", Some(Self::get_hash), Some(Self::set_hash), Attribute::all());
+
+        class.method("assign", 1, NativeFunction::from_fn_ptr(Self::assign));
+        class.method("replace", 1, NativeFunction::from_fn_ptr(Self::replace));
+        class.method("reload", 0, NativeFunction::from_fn_ptr(Self::reload));
+        class.method("toString", 0, NativeFunction::from_fn_ptr(Self::get_href));
+        Ok(())
+    }
+}
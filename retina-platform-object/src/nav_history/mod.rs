@@ -5,6 +5,8 @@
 //!
 //! [spec]: https://html.spec.whatwg.org/multipage/nav-history-apis.html
 
+pub mod location;
 pub mod window;
 
+pub use location::{is_fragment_navigation, HistoryHandling, Location, NavigationRequest};
 pub use window::Window;
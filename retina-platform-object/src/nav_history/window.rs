@@ -1,29 +1,113 @@
 // Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
 // All Rights Reserved.
 
+use std::cell::RefCell;
+use std::sync::{Arc, RwLock};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
 use boa_engine::prelude::*;
 
 use boa_engine::class::{Class, ClassBuilder, ClassConstructor};
+use boa_engine::object::ObjectInitializer;
 use boa_engine::property::Attribute;
-use boa_gc::{Finalize, Trace};
-use retina_dom::Node;
+use boa_gc::{Finalize, GcRefCell, Trace, custom_trace};
+use retina_dom::{event::queue::EventQueue, Node};
+use retina_fetch::{Fetch, Url};
+use retina_user_agent::permission::{Origin, PermissionStore};
+use retina_user_agent::storage::LocalStorage;
 
+use crate::animation_frames::{self, AnimationFrameCallbacks};
+use crate::clipboard::Clipboard;
+use crate::console::Console;
 use crate::dom::Document;
-use crate::prelude::illegal_constructor;
+use crate::fetch::{self, FetchList};
+use crate::nav_history::{Location, NavigationRequest};
+use crate::performance::{Performance, PerformanceTimeline};
+use crate::permissions::Permissions;
+use crate::prelude::{illegal_constructor, ContextExt};
+use crate::storage::Storage;
+use crate::timers::{self, TimerList};
 
 #[derive(Finalize)]
 pub struct Window {
     document_node: Node,
+    event_queue: EventQueue,
+
+    /// The [URL](https://html.spec.whatwg.org/multipage/nav-history-apis.html#concept-document-url)
+    /// of the document, which changes when navigating to a fragment.
+    url: RefCell<Url>,
+
+    /// The navigation requested by a script, e.g. using `location.assign()`,
+    /// which is performed by the page after the script ran.
+    navigation_request: RefCell<Option<NavigationRequest>>,
+
+    /// The client that is used for `fetch()`.
+    fetch: Fetch,
+
+    /// The requests of `fetch()` that haven't completed yet.
+    fetches: GcRefCell<FetchList>,
+
+    /// The [mutation observers](https://dom.spec.whatwg.org/#mutation-observer-list)
+    /// that observe a node, which are notified when the
+    /// [`Microtask::NotifyMutationObservers`](retina_dom::event::queue::Microtask::NotifyMutationObservers)
+    /// is run.
+    mutation_observers: GcRefCell<Vec<JsObject>>,
+
+    /// The timers of `setTimeout()` and `setInterval()`.
+    timers: GcRefCell<TimerList>,
+
+    /// The callbacks of `requestAnimationFrame()`.
+    animation_frame_callbacks: GcRefCell<AnimationFrameCallbacks>,
+
+    /// The `Storage` object of `localStorage`, which receives the changes
+    /// made by other browsing contexts.
+    local_storage: GcRefCell<Option<JsObject>>,
+
+    /// The [time origin](https://w3c.github.io/hr-time/#dfn-time-origin),
+    /// against which high resolution timestamps are measured.
+    time_origin: Instant,
+
+    /// The time origin as the number of milliseconds since the Unix epoch,
+    /// which is the value of `performance.timeOrigin`.
+    time_origin_timestamp: f64,
+
+    /// The marks, measures and `PerformanceObserver`s of `performance`.
+    performance_timeline: GcRefCell<PerformanceTimeline>,
 }
 
 unsafe impl Trace for Window {
-    boa_gc::empty_trace!();
+    custom_trace!(this, {
+        mark(&this.mutation_observers);
+        mark(&this.fetches);
+        mark(&this.timers);
+        mark(&this.animation_frame_callbacks);
+        mark(&this.local_storage);
+        mark(&this.performance_timeline);
+    });
 }
 
 impl Window {
-    pub fn new(document_node: Node) -> Self {
+    pub fn new(document_node: Node, url: Url, event_queue: EventQueue, fetch: Fetch, time_origin: Instant) -> Self {
+        let time_origin_timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .saturating_sub(time_origin.elapsed())
+            .as_secs_f64() * 1000.0;
+
         Self {
             document_node,
+            event_queue,
+            url: RefCell::new(url),
+            navigation_request: RefCell::new(None),
+            fetch,
+            fetches: GcRefCell::new(FetchList::new()),
+            mutation_observers: GcRefCell::new(Vec::new()),
+            timers: GcRefCell::new(TimerList::new()),
+            animation_frame_callbacks: GcRefCell::new(AnimationFrameCallbacks::new()),
+            local_storage: GcRefCell::new(None),
+            time_origin,
+            time_origin_timestamp,
+            performance_timeline: GcRefCell::new(PerformanceTimeline::new()),
         }
     }
 
@@ -31,8 +115,91 @@ impl Window {
         &self.document_node
     }
 
+    pub fn event_queue(&self) -> &EventQueue {
+        &self.event_queue
+    }
+
+    pub fn url(&self) -> Url {
+        self.url.borrow().clone()
+    }
+
+    pub fn set_url(&self, url: Url) {
+        *self.url.borrow_mut() = url;
+    }
+
+    /// Request the page to navigate after the script ran. This replaces the
+    /// navigation that was requested before, if any.
+    pub fn request_navigation(&self, request: NavigationRequest) {
+        *self.navigation_request.borrow_mut() = Some(request);
+    }
+
+    pub fn take_navigation_request(&self) -> Option<NavigationRequest> {
+        self.navigation_request.borrow_mut().take()
+    }
+
+    /// The [document base URL](https://html.spec.whatwg.org/multipage/urls-and-fetching.html#document-base-url),
+    /// against which relative URLs are resolved.
+    pub fn base_url(&self) -> Url {
+        self.document_node.as_document()
+            .and_then(|document| document.data().base_url().cloned())
+            .unwrap_or_else(|| self.url())
+    }
+
+    pub fn fetch(&self) -> &Fetch {
+        &self.fetch
+    }
+
+    pub fn fetches(&self) -> &GcRefCell<FetchList> {
+        &self.fetches
+    }
+
+    /// Add the `MutationObserver` object to the list of observers that are
+    /// notified, if it isn't in the list already.
+    pub fn add_mutation_observer(&self, observer: &JsObject) {
+        let mut mutation_observers = self.mutation_observers.borrow_mut();
+        if !mutation_observers.iter().any(|object| JsObject::equals(object, observer)) {
+            mutation_observers.push(observer.clone());
+        }
+    }
+
+    pub fn mutation_observers(&self) -> Vec<JsObject> {
+        self.mutation_observers.borrow().clone()
+    }
+
+    pub fn timers(&self) -> &GcRefCell<TimerList> {
+        &self.timers
+    }
+
+    pub fn animation_frame_callbacks(&self) -> &GcRefCell<AnimationFrameCallbacks> {
+        &self.animation_frame_callbacks
+    }
+
+    pub fn local_storage(&self) -> Option<JsObject> {
+        self.local_storage.borrow().clone()
+    }
+
+    /// The [relative high resolution time](https://w3c.github.io/hr-time/#dfn-relative-high-resolution-time)
+    /// of `time`, i.e. the number of milliseconds since the time origin.
+    pub fn relative_high_resolution_time(&self, time: Instant) -> f64 {
+        time.saturating_duration_since(self.time_origin).as_secs_f64() * 1000.0
+    }
+
+    pub fn time_origin_timestamp(&self) -> f64 {
+        self.time_origin_timestamp
+    }
+
+    pub fn performance_timeline(&self) -> &GcRefCell<PerformanceTimeline> {
+        &self.performance_timeline
+    }
+
     /// When [`Window`] is the global object, use this method to initialize the properties.
-    pub fn initialize_global(context: &mut Context, document: Node) -> JsResult<()> {
+    pub fn initialize_global(
+        context: &mut Context,
+        document: Node,
+        permission_store: Arc<RwLock<PermissionStore>>,
+        local_storage: Arc<RwLock<LocalStorage>>,
+        origin: Origin,
+    ) -> JsResult<()> {
         context.register_global_property("window", context.global_object(), Attribute::empty())?;
 
         // let window_object = context.global_object();
@@ -44,7 +211,40 @@ impl Window {
         let document = Document::raw_constructor(&JsValue::Object(JsObject::default()), &[], context)?;
         context.register_global_property("document", document, Attribute::all())?;
 
+        let location = Location.create_object(context)?;
+        context.register_global_property("location", location, Attribute::READONLY | Attribute::PERMANENT)?;
+
+        let local_storage = Storage::new_local(local_storage, origin.clone()).create_object(context)?;
+        context.with_window(|window| *window.local_storage.borrow_mut() = Some(local_storage.clone()));
+        context.register_global_property("localStorage", local_storage, Attribute::READONLY | Attribute::ENUMERABLE)?;
+
+        let session_storage = Storage::new_session().create_object(context)?;
+        context.register_global_property("sessionStorage", session_storage, Attribute::READONLY | Attribute::ENUMERABLE)?;
+        context.register_global_property("onstorage", JsValue::Null, Attribute::all())?;
+
+        let permissions = Permissions::new(permission_store, origin).create_object(context)?;
+        let clipboard = Clipboard.create_object(context)?;
+        let navigator = ObjectInitializer::new(context)
+            .property("clipboard", clipboard, Attribute::READONLY | Attribute::ENUMERABLE)
+            .property("permissions", permissions, Attribute::READONLY | Attribute::ENUMERABLE)
+            .build();
+        context.register_global_property("navigator", navigator, Attribute::all())?;
+
+        let performance = Performance.create_object(context)?;
+        context.register_global_property("performance", performance, Attribute::READONLY | Attribute::ENUMERABLE)?;
+
+        let console = Console::new().create_object(context)?;
+        context.register_global_property("console", console, Attribute::READONLY | Attribute::PERMANENT)?;
+
+        context.register_global_builtin_callable("setTimeout", 1, NativeFunction::from_fn_ptr(timers::set_timeout))?;
+        context.register_global_builtin_callable("setInterval", 1, NativeFunction::from_fn_ptr(timers::set_interval))?;
+        context.register_global_builtin_callable("clearTimeout", 0, NativeFunction::from_fn_ptr(timers::clear_timer))?;
+        context.register_global_builtin_callable("clearInterval", 0, NativeFunction::from_fn_ptr(timers::clear_timer))?;
+
+        context.register_global_builtin_callable("fetch", 1, NativeFunction::from_fn_ptr(fetch::fetch))?;
 
+        context.register_global_builtin_callable("requestAnimationFrame", 1, NativeFunction::from_fn_ptr(animation_frames::request_animation_frame))?;
+        context.register_global_builtin_callable("cancelAnimationFrame", 1, NativeFunction::from_fn_ptr(animation_frames::cancel_animation_frame))?;
 
         Ok(())
     }
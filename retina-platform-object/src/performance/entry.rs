@@ -0,0 +1,205 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use boa_engine::prelude::*;
+
+use boa_engine::class::{Class, ClassBuilder};
+use boa_engine::object::{builtins::JsArray, ObjectData, ObjectInitializer};
+use boa_engine::property::Attribute;
+use boa_gc::{Finalize, Trace, custom_trace};
+
+use crate::prelude::*;
+
+/// The [entry types](https://w3c.github.io/timing-entrytypes-registry/#registry)
+/// that are supported.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EntryType {
+    /// A `PerformanceMark`, created by `performance.mark()`.
+    Mark,
+
+    /// A `PerformanceMeasure`, created by `performance.measure()`.
+    Measure,
+}
+
+impl EntryType {
+    /// The entry types in alphabetical order, which is the value of
+    /// `PerformanceObserver.supportedEntryTypes`.
+    pub const ALL: [EntryType; 2] = [EntryType::Mark, EntryType::Measure];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|entry_type| entry_type.as_str() == value)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Mark => "mark",
+            Self::Measure => "measure",
+        }
+    }
+}
+
+/// The [Interface `PerformanceEntry`](https://w3c.github.io/performance-timeline/#the-performanceentry-interface)
+/// platform object, which is the performance metric of a mark or a measure.
+#[derive(Clone, Finalize)]
+pub struct PerformanceEntry {
+    name: String,
+    entry_type: EntryType,
+    start_time: f64,
+    duration: f64,
+
+    /// The `detail` given to `performance.mark()` or `performance.measure()`.
+    detail: JsValue,
+}
+
+unsafe impl Trace for PerformanceEntry {
+    custom_trace!(this, {
+        mark(&this.detail);
+    });
+}
+
+impl PerformanceEntry {
+    pub fn new(name: String, entry_type: EntryType, start_time: f64, duration: f64, detail: JsValue) -> Self {
+        Self {
+            name,
+            entry_type,
+            start_time,
+            duration,
+            detail,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn entry_type(&self) -> EntryType {
+        self.entry_type
+    }
+
+    pub fn start_time(&self) -> f64 {
+        self.start_time
+    }
+
+    pub fn create_object(self, context: &mut Context) -> JsResult<JsObject> {
+        Ok(JsObject::from_proto_and_data(
+            Some(class_prototype::<Self>(context)?),
+            ObjectData::native_object(self),
+        ))
+    }
+
+    fn with_entry<T>(this: &JsValue, f: impl FnOnce(&Self) -> T) -> JsResult<T> {
+        let this = this.as_object()
+            .and_then(|obj| obj.downcast_ref::<Self>())
+            .ok_or_else(|| JsError::from_opaque("Value is not `PerformanceEntry`".into()))?;
+        Ok(f(&this))
+    }
+
+    /// [Performance Timeline § 5.1 `name`](https://w3c.github.io/performance-timeline/#dom-performanceentry-name)
+    fn get_name(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_entry(this, |entry| JsString::from(entry.name.as_str()).into())
+    }
+
+    /// [Performance Timeline § 5.1 `entryType`](https://w3c.github.io/performance-timeline/#dom-performanceentry-entrytype)
+    fn get_entry_type(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_entry(this, |entry| JsString::from(entry.entry_type.as_str()).into())
+    }
+
+    /// [Performance Timeline § 5.1 `startTime`](https://w3c.github.io/performance-timeline/#dom-performanceentry-starttime)
+    fn get_start_time(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_entry(this, |entry| JsValue::new(entry.start_time))
+    }
+
+    /// [Performance Timeline § 5.1 `duration`](https://w3c.github.io/performance-timeline/#dom-performanceentry-duration)
+    fn get_duration(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_entry(this, |entry| JsValue::new(entry.duration))
+    }
+
+    /// [User Timing § 4.1 `detail`](https://w3c.github.io/user-timing/#dom-performancemark-detail)
+    fn get_detail(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_entry(this, |entry| entry.detail.clone())
+    }
+
+    /// [Performance Timeline § 5.1 `toJSON()`](https://w3c.github.io/performance-timeline/#dom-performanceentry-tojson)
+    fn to_json(this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let entry = Self::with_entry(this, Self::clone)?;
+        Ok(ObjectInitializer::new(context)
+            .property("name", JsString::from(entry.name), Attribute::all())
+            .property("entryType", JsString::from(entry.entry_type.as_str()), Attribute::all())
+            .property("startTime", entry.start_time, Attribute::all())
+            .property("duration", entry.duration, Attribute::all())
+            .property("detail", entry.detail, Attribute::all())
+            .build()
+            .into())
+    }
+}
+
+impl Class for PerformanceEntry {
+    const NAME: &'static str = "PerformanceEntry";
+    const LENGTH: usize = 0;
+    const ATTRIBUTES: Attribute = Attribute::empty();
+
+    fn constructor(_: &JsValue, _: &[JsValue], _: &mut Context<'_>) -> JsResult<Self> {
+        illegal_constructor()
+    }
+
+    fn init(class: &mut ClassBuilder<'_, '_>) -> JsResult<()> {
+        class.accessor_with_function_ptr("name", Some(Self::get_name), None, Attribute::all());
+        class.accessor_with_function_ptr("entryType", Some(Self::get_entry_type), None, Attribute::all());
+        class.accessor_with_function_ptr("startTime", Some(Self::get_start_time), None, Attribute::all());
+        class.accessor_with_function_ptr("duration", Some(Self::get_duration), None, Attribute::all());
+        class.accessor_with_function_ptr("detail", Some(Self::get_detail), None, Attribute::all());
+        class.method("toJSON", 0, NativeFunction::from_fn_ptr(Self::to_json));
+        Ok(())
+    }
+}
+
+/// Get the entries that match the `name` and `entry_type` if given, sorted
+/// chronologically by their start time, which is the result of
+/// [filter buffer by name and type](https://w3c.github.io/performance-timeline/#filter-buffer-by-name-and-type).
+pub(crate) fn filter_entries(
+    entries: &[PerformanceEntry],
+    name: Option<&str>,
+    entry_type: Option<&str>,
+) -> Vec<PerformanceEntry> {
+    let mut result: Vec<PerformanceEntry> = entries.iter()
+        .filter(|entry| name.is_none_or(|name| entry.name == name))
+        .filter(|entry| entry_type.is_none_or(|entry_type| entry.entry_type.as_str() == entry_type))
+        .cloned()
+        .collect();
+
+    // The sort is stable, so entries with the same start time keep the order
+    // in which they were recorded.
+    result.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+    result
+}
+
+/// Create the array of `PerformanceEntry` objects that is returned by the
+/// `getEntries()` methods.
+pub(crate) fn create_entries_array(entries: Vec<PerformanceEntry>, context: &mut Context) -> JsResult<JsValue> {
+    let objects = entries.into_iter()
+        .map(|entry| entry.create_object(context).map(JsValue::from))
+        .collect::<JsResult<Vec<_>>>()?;
+    Ok(JsArray::from_iter(objects, context).into())
+}
+
+/// The shared steps of `getEntriesByType(type)`, which `Performance` and
+/// `PerformanceObserverEntryList` run on their buffers.
+pub(crate) fn get_entries_by_type(entries: &[PerformanceEntry], args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+    let entry_type = args.first().cloned().unwrap_or_default()
+        .to_string(context)?
+        .to_std_string_escaped();
+    create_entries_array(filter_entries(entries, None, Some(&entry_type)), context)
+}
+
+/// The shared steps of `getEntriesByName(name, type)`, which `Performance`
+/// and `PerformanceObserverEntryList` run on their buffers.
+pub(crate) fn get_entries_by_name(entries: &[PerformanceEntry], args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+    let name = args.first().cloned().unwrap_or_default()
+        .to_string(context)?
+        .to_std_string_escaped();
+    let entry_type = match args.get(1) {
+        None | Some(JsValue::Undefined) => None,
+        Some(value) => Some(value.to_string(context)?.to_std_string_escaped()),
+    };
+    create_entries_array(filter_entries(entries, Some(&name), entry_type.as_deref()), context)
+}
@@ -0,0 +1,541 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! The [High Resolution Time](https://w3c.github.io/hr-time/),
+//! [Performance Timeline](https://w3c.github.io/performance-timeline/) and
+//! [User Timing](https://w3c.github.io/user-timing/) APIs, i.e.
+//! `performance.now()`, `performance.mark()` and `PerformanceObserver`.
+
+mod entry;
+mod observer;
+
+pub use entry::{EntryType, PerformanceEntry};
+pub use observer::{PerformanceObserver, PerformanceObserverEntryList};
+
+use std::time::Instant;
+
+use boa_engine::prelude::*;
+
+use boa_engine::class::{Class, ClassBuilder};
+use boa_engine::object::{ObjectData, ObjectInitializer};
+use boa_engine::property::Attribute;
+use boa_gc::{Finalize, Trace, custom_trace, empty_trace};
+use log::error;
+
+use crate::prelude::*;
+
+/// The attributes of the [`PerformanceTiming`](https://w3c.github.io/navigation-timing/#the-performancetiming-interface)
+/// interface, which can't be used as the name of a mark.
+const PERFORMANCE_TIMING_ATTRIBUTES: [&str; 21] = [
+    "navigationStart",
+    "unloadEventStart",
+    "unloadEventEnd",
+    "redirectStart",
+    "redirectEnd",
+    "fetchStart",
+    "domainLookupStart",
+    "domainLookupEnd",
+    "connectStart",
+    "connectEnd",
+    "secureConnectionStart",
+    "requestStart",
+    "responseStart",
+    "responseEnd",
+    "domLoading",
+    "domInteractive",
+    "domContentLoadedEventStart",
+    "domContentLoadedEventEnd",
+    "domComplete",
+    "loadEventStart",
+    "loadEventEnd",
+];
+
+/// The performance timeline of a `Window`, with the marks and measures that
+/// were recorded, and the `PerformanceObserver`s that are notified of them.
+#[derive(Finalize, Default)]
+pub struct PerformanceTimeline {
+    /// The [performance entry buffer](https://w3c.github.io/performance-timeline/#dfn-performance-entry-buffer).
+    entries: Vec<PerformanceEntry>,
+
+    /// The [list of registered performance observer objects](https://w3c.github.io/performance-timeline/#list-of-registered-performance-observer-objects).
+    observers: Vec<JsObject>,
+
+    /// The [performance observer task queued flag](https://w3c.github.io/performance-timeline/#dfn-performance-observer-task-queued-flag).
+    observer_task_queued: bool,
+}
+
+unsafe impl Trace for PerformanceTimeline {
+    custom_trace!(this, {
+        mark(&this.entries);
+        mark(&this.observers);
+    });
+}
+
+impl PerformanceTimeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn entries(&self) -> &[PerformanceEntry] {
+        &self.entries
+    }
+
+    /// Whether the `PerformanceObserver` task must run, because entries
+    /// were queued for the observers.
+    pub fn is_observer_task_queued(&self) -> bool {
+        self.observer_task_queued
+    }
+
+    fn register_observer(&mut self, observer: &JsObject) {
+        if !self.observers.iter().any(|object| JsObject::equals(object, observer)) {
+            self.observers.push(observer.clone());
+        }
+    }
+
+    fn unregister_observer(&mut self, observer: &JsObject) {
+        self.observers.retain(|object| !JsObject::equals(object, observer));
+    }
+}
+
+/// [Queue a `PerformanceEntry`](https://w3c.github.io/performance-timeline/#queue-a-performanceentry),
+/// which adds it to the buffers of the interested observers, and to the
+/// performance entry buffer.
+fn queue_entry(entry: PerformanceEntry, context: &mut Context) {
+    let observers = context.with_window(|window| window.performance_timeline().borrow().observers.clone());
+
+    // 6. For each registered performance observer regObs in relevantGlobal's
+    //    list of registered performance observer objects: [...] If
+    //    regObsOptionsList contains a PerformanceObserverInit options whose
+    //    entryTypes member includes entryType or whose type member equals to
+    //    entryType, append regObs's observer to interested observers.
+    // 8. For each observer in interested observers: Append newEntry to
+    //    observer's observer buffer.
+    let mut has_interested_observers = false;
+    for observer in observers {
+        if let Some(observer) = observer.downcast_ref::<PerformanceObserver>() {
+            has_interested_observers |= observer.append_if_observed(&entry);
+        }
+    }
+
+    context.with_window(|window| {
+        let mut timeline = window.performance_timeline().borrow_mut();
+
+        // 11. If shouldAdd is true [...] add newEntry to the performance
+        //     entry buffer.
+        timeline.entries.push(entry);
+
+        // 12. Queue the PerformanceObserver task with relevantGlobal as input.
+        timeline.observer_task_queued |= has_interested_observers;
+    });
+}
+
+/// Run the [PerformanceObserver task](https://w3c.github.io/performance-timeline/#queue-the-performanceobserver-task),
+/// which invokes the callbacks of the observers with the entries in their
+/// buffers. Returns the number of callbacks that were invoked.
+pub fn run_performance_observer_task(context: &mut Context) -> usize {
+    // 2. Set the performance observer task queued flag of relevantGlobal to
+    //    false.
+    let (queued, observers) = context.with_window(|window| {
+        let mut timeline = window.performance_timeline().borrow_mut();
+        let queued = std::mem::take(&mut timeline.observer_task_queued);
+        (queued, timeline.observers.clone())
+    });
+
+    if !queued {
+        return 0;
+    }
+
+    let mut count = 0;
+
+    // 3. Let notifyList be a copy of relevantGlobal's list of registered
+    //    performance observer objects.
+    // 4. For each registered performance observer object registeredObserver
+    //    in notifyList, run these steps:
+    for observer in observers {
+        let Some((entries, callback)) = observer.downcast_ref::<PerformanceObserver>()
+            .map(|observer| (observer.take_buffer(), observer.callback())) else {
+            continue;
+        };
+
+        // 4.4. If entries is empty, return.
+        if entries.is_empty() {
+            continue;
+        }
+
+        // 4.5. Let observerEntryList be a new PerformanceObserverEntryList,
+        //      with its entry list set to entries.
+        let entry_list = match PerformanceObserverEntryList::new(entries).create_object(context) {
+            Ok(entry_list) => entry_list,
+            Err(e) => {
+                error!("[performance] Failed to create the PerformanceObserverEntryList: {e}");
+                continue;
+            }
+        };
+
+        // 4.8. Call po’s observer callback with observerEntryList as the first
+        //      argument, po as the second argument and as callback this
+        //      value.
+        let this = JsValue::Object(observer.clone());
+        if let Err(e) = callback.call(&this, &[entry_list.into(), this.clone()], context) {
+            error!("[performance] Uncaught exception in PerformanceObserver callback: {e}");
+        }
+
+        context.run_jobs();
+        count += 1;
+    }
+
+    count
+}
+
+/// The [Interface `Performance`](https://w3c.github.io/hr-time/#sec-performance)
+/// platform object, available as `window.performance`.
+#[derive(Finalize)]
+pub struct Performance;
+
+unsafe impl Trace for Performance {
+    empty_trace!();
+}
+
+impl Performance {
+    pub fn create_object(self, context: &mut Context) -> JsResult<JsObject> {
+        Ok(JsObject::from_proto_and_data(
+            Some(class_prototype::<Self>(context)?),
+            ObjectData::native_object(self),
+        ))
+    }
+
+    fn current_high_resolution_time(context: &mut Context) -> f64 {
+        context.with_window(|window| window.relative_high_resolution_time(Instant::now()))
+    }
+
+    /// The value of the `PerformanceTiming` attribute. Only the start of the
+    /// navigation is known, and the other attributes are zero, which means
+    /// the event didn't occur.
+    fn timing_attribute(name: &str, context: &mut Context) -> f64 {
+        match name {
+            "navigationStart" | "fetchStart" => context.with_window(|window| window.time_origin_timestamp()).round(),
+            _ => 0.0,
+        }
+    }
+
+    /// [HR-Time § 8.1 `now()`](https://w3c.github.io/hr-time/#dom-performance-now)
+    fn now(_this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Ok(JsValue::new(Self::current_high_resolution_time(context)))
+    }
+
+    /// [HR-Time § 8.2 `timeOrigin`](https://w3c.github.io/hr-time/#dom-performance-timeorigin)
+    fn get_time_origin(_this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Ok(JsValue::new(context.with_window(|window| window.time_origin_timestamp())))
+    }
+
+    /// [Navigation Timing § 5.1 `timing`](https://w3c.github.io/navigation-timing/#dom-performance-timing)
+    fn get_timing(_this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let values = PERFORMANCE_TIMING_ATTRIBUTES.map(|name| (name, Self::timing_attribute(name, context)));
+
+        let mut timing = ObjectInitializer::new(context);
+        for (name, value) in values {
+            timing.property(name, value, Attribute::READONLY | Attribute::ENUMERABLE);
+        }
+        Ok(timing.build().into())
+    }
+
+    /// [HR-Time § 8.3 `toJSON()`](https://w3c.github.io/hr-time/#dom-performance-tojson)
+    fn to_json(_this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let time_origin = context.with_window(|window| window.time_origin_timestamp());
+        Ok(ObjectInitializer::new(context)
+            .property("timeOrigin", time_origin, Attribute::all())
+            .build()
+            .into())
+    }
+
+    /// [Performance Timeline § 4.1 `getEntries()`](https://w3c.github.io/performance-timeline/#dom-performance-getentries)
+    fn get_entries(_this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let entries = context.with_window(|window| {
+            entry::filter_entries(window.performance_timeline().borrow().entries(), None, None)
+        });
+        entry::create_entries_array(entries, context)
+    }
+
+    /// [Performance Timeline § 4.2 `getEntriesByType()`](https://w3c.github.io/performance-timeline/#dom-performance-getentriesbytype)
+    fn get_entries_by_type(_this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let entries = context.with_window(|window| window.performance_timeline().borrow().entries().to_vec());
+        entry::get_entries_by_type(&entries, args, context)
+    }
+
+    /// [Performance Timeline § 4.3 `getEntriesByName()`](https://w3c.github.io/performance-timeline/#dom-performance-getentriesbyname)
+    fn get_entries_by_name(_this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let entries = context.with_window(|window| window.performance_timeline().borrow().entries().to_vec());
+        entry::get_entries_by_name(&entries, args, context)
+    }
+
+    /// [User Timing § 3.1.1 `mark()`](https://w3c.github.io/user-timing/#dom-performance-mark)
+    fn mark(_this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let name = args.first().cloned().unwrap_or_default()
+            .to_string(context)?
+            .to_std_string_escaped();
+
+        // 1. If the current global object is a Window object and markName
+        //    uses the same name as a read only attribute in the
+        //    PerformanceTiming interface, throw a SyntaxError.
+        if PERFORMANCE_TIMING_ATTRIBUTES.contains(&name.as_str()) {
+            return Err(dom_exception("SyntaxError", &format!("\"{name}\" is a PerformanceTiming attribute"), context));
+        }
+
+        let options = args.get(1).and_then(JsValue::as_object).cloned();
+        let start_time = match &options {
+            Some(options) => options.get("startTime", context)?,
+            None => JsValue::Undefined,
+        };
+
+        // 5. If markOptions's startTime member is present, then:
+        let start_time = if start_time.is_undefined() {
+            // 6. Otherwise, set it to the value that would be returned by the
+            //    Performance object's now() method.
+            Self::current_high_resolution_time(context)
+        } else {
+            // 5.1. If markOptions's startTime is negative, throw a TypeError.
+            let start_time = start_time.to_number(context)?;
+            if start_time < 0.0 {
+                return Err(JsNativeError::typ().with_message("The startTime must not be negative").into());
+            }
+
+            // 5.2. Otherwise, set entry's startTime to the value of
+            //      markOptions's startTime.
+            start_time
+        };
+
+        // 8. Set entry's detail to the result of calling the
+        //    StructuredSerialize algorithm on markOptions's detail.
+        let detail = match &options {
+            Some(options) => options.get("detail", context)?,
+            None => JsValue::Null,
+        };
+        let detail = if detail.is_undefined() { JsValue::Null } else { detail };
+
+        let entry = PerformanceEntry::new(name, EntryType::Mark, start_time, 0.0, detail);
+        queue_entry(entry.clone(), context);
+        Ok(entry.create_object(context)?.into())
+    }
+
+    /// [User Timing § 3.1.3 `measure()`](https://w3c.github.io/user-timing/#dom-performance-measure)
+    fn measure(_this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let name = args.first().cloned().unwrap_or_default()
+            .to_string(context)?
+            .to_std_string_escaped();
+
+        let start_or_options = args.get(1).cloned().unwrap_or_default();
+        let end_mark = args.get(2).cloned().filter(|value| !value.is_undefined());
+
+        let mut start = None;
+        let mut end = None;
+        let mut duration = None;
+        let mut detail = JsValue::Null;
+        let mut is_options = false;
+
+        if let Some(options) = start_or_options.as_object() {
+            let get = |key: &str, context: &mut Context| -> JsResult<Option<JsValue>> {
+                let value = options.get(key, context)?;
+                Ok((!value.is_undefined()).then_some(value))
+            };
+            start = get("start", context)?;
+            end = get("end", context)?;
+            duration = get("duration", context)?;
+            let options_detail = get("detail", context)?;
+
+            // 1. If startOrMeasureOptions is a PerformanceMeasureOptions
+            //    object and at least one of start, end, duration, and detail
+            //    are present, run the following checks:
+            is_options = start.is_some() || end.is_some() || duration.is_some() || options_detail.is_some();
+            if is_options {
+                // 1.1. If endMark is given, throw a TypeError.
+                if end_mark.is_some() {
+                    return Err(JsNativeError::typ().with_message("The endMark can't be given together with options").into());
+                }
+
+                // 1.2. If startOrMeasureOptions's start and end members are
+                //      both omitted, throw a TypeError.
+                if start.is_none() && end.is_none() {
+                    return Err(JsNativeError::typ().with_message("Either the start or end must be given").into());
+                }
+
+                // 1.3. If startOrMeasureOptions's start, duration, and end
+                //      members are all present, throw a TypeError.
+                if start.is_some() && duration.is_some() && end.is_some() {
+                    return Err(JsNativeError::typ().with_message("The start, end and duration can't all be given").into());
+                }
+            }
+
+            detail = options_detail.unwrap_or(JsValue::Null);
+        }
+
+        // 2. Compute end time as follows:
+        let end_time = if let Some(end_mark) = end_mark {
+            // 2.1. If endMark is given, let end time be the value returned by
+            //      running the convert a mark to a timestamp algorithm passing
+            //      in endMark.
+            Self::convert_mark_to_timestamp(&end_mark, context)?
+        } else if let Some(end) = &end {
+            // 2.2. Otherwise, if startOrMeasureOptions is a
+            //      PerformanceMeasureOptions object, and if its end member is
+            //      present, let end time be the value returned by running the
+            //      convert a mark to a timestamp algorithm passing in
+            //      startOrMeasureOptions's end.
+            Self::convert_mark_to_timestamp(end, context)?
+        } else if let (Some(start), Some(duration)) = (&start, &duration) {
+            // 2.3. Otherwise, if startOrMeasureOptions is a
+            //      PerformanceMeasureOptions object, and if its start and
+            //      duration members are both present: [...] Let end time be
+            //      start plus duration.
+            Self::convert_mark_to_timestamp(start, context)? + Self::convert_mark_to_timestamp(duration, context)?
+        } else {
+            // 2.4. Otherwise, let end time be the value that would be returned
+            //      by the Performance object's now() method.
+            Self::current_high_resolution_time(context)
+        };
+
+        // 3. Compute start time as follows:
+        let start_time = if let Some(start) = &start {
+            // 3.1. If startOrMeasureOptions is a PerformanceMeasureOptions
+            //      object, and if its start member is present, let start time
+            //      be the value returned by running the convert a mark to a
+            //      timestamp algorithm passing in startOrMeasureOptions's
+            //      start.
+            Self::convert_mark_to_timestamp(start, context)?
+        } else if let (Some(duration), Some(_)) = (&duration, &end) {
+            // 3.2. Otherwise, if startOrMeasureOptions is a
+            //      PerformanceMeasureOptions object, and if its duration and
+            //      end members are both present: [...] Let start time be end
+            //      minus duration.
+            end_time - Self::convert_mark_to_timestamp(duration, context)?
+        } else if !is_options && !start_or_options.is_undefined() && !start_or_options.is_null() && !start_or_options.is_object() {
+            // 3.3. Otherwise, if startOrMeasureOptions is a DOMString, let
+            //      start time be the value returned by running the convert a
+            //      mark to a timestamp algorithm passing in
+            //      startOrMeasureOptions.
+            let start_mark = JsValue::from(start_or_options.to_string(context)?);
+            Self::convert_mark_to_timestamp(&start_mark, context)?
+        } else {
+            // 3.4. Otherwise, let start time be 0.
+            0.0
+        };
+
+        let entry = PerformanceEntry::new(name, EntryType::Measure, start_time, end_time - start_time, detail);
+        queue_entry(entry.clone(), context);
+        Ok(entry.create_object(context)?.into())
+    }
+
+    /// [Convert a mark to a timestamp](https://w3c.github.io/user-timing/#convert-a-mark-to-a-timestamp),
+    /// which is either the name of a mark, or a timestamp.
+    fn convert_mark_to_timestamp(mark: &JsValue, context: &mut Context) -> JsResult<f64> {
+        // 2. If mark is a DOMHighResTimeStamp, the following steps are taken:
+        if let Some(timestamp) = mark.as_number() {
+            // 2.1. If mark is negative, throw a TypeError.
+            if timestamp < 0.0 {
+                return Err(JsNativeError::typ().with_message("The timestamp must not be negative").into());
+            }
+
+            // 2.2. Otherwise, return mark.
+            return Ok(timestamp);
+        }
+
+        let name = mark.to_string(context)?.to_std_string_escaped();
+
+        // 1. If mark is a DOMString and it has the same name as a read only
+        //    attribute in the PerformanceTiming interface, let end time be
+        //    the value returned by running the convert a name to a timestamp
+        //    algorithm with name set to the value of mark.
+        if PERFORMANCE_TIMING_ATTRIBUTES.contains(&name.as_str()) {
+            // 2. If name is navigationStart, return 0.
+            if name == "navigationStart" {
+                return Ok(0.0);
+            }
+
+            // 3. Let startTime be the value of navigationStart in the
+            //    PerformanceTiming interface.
+            // 4. Let endTime be the value of name in the PerformanceTiming
+            //    interface.
+            let start_time = Self::timing_attribute("navigationStart", context);
+            let end_time = Self::timing_attribute(&name, context);
+
+            // 5. If endTime is 0, throw an InvalidAccessError.
+            if end_time == 0.0 {
+                return Err(dom_exception("InvalidAccessError", &format!("The \"{name}\" event didn't occur"), context));
+            }
+
+            // 6. Return result of subtracting startTime from endTime.
+            return Ok(end_time - start_time);
+        }
+
+        // 2. Otherwise, if mark is a DOMString, let end time be the value of
+        //    the startTime attribute from the most recent occurrence of a
+        //    PerformanceMark object in the performance entry buffer whose
+        //    name is mark. If no matching entry is found, throw a SyntaxError.
+        let start_time = context.with_window(|window| {
+            window.performance_timeline().borrow()
+                .entries()
+                .iter()
+                .rev()
+                .find(|entry| entry.entry_type() == EntryType::Mark && entry.name() == name)
+                .map(PerformanceEntry::start_time)
+        });
+
+        match start_time {
+            Some(start_time) => Ok(start_time),
+            None => Err(dom_exception("SyntaxError", &format!("There is no mark named \"{name}\""), context)),
+        }
+    }
+
+    /// Remove the entries of the `entry_type`, which only have the given
+    /// name, if any.
+    fn clear_entries(entry_type: EntryType, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let name = match args.first() {
+            None | Some(JsValue::Undefined) => None,
+            Some(name) => Some(name.to_string(context)?.to_std_string_escaped()),
+        };
+
+        context.with_window(|window| {
+            window.performance_timeline().borrow_mut().entries.retain(|entry| {
+                entry.entry_type() != entry_type || name.as_ref().is_some_and(|name| entry.name() != name)
+            });
+        });
+
+        Ok(JsValue::Undefined)
+    }
+
+    /// [User Timing § 3.1.2 `clearMarks()`](https://w3c.github.io/user-timing/#dom-performance-clearmarks)
+    fn clear_marks(_this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::clear_entries(EntryType::Mark, args, context)
+    }
+
+    /// [User Timing § 3.1.4 `clearMeasures()`](https://w3c.github.io/user-timing/#dom-performance-clearmeasures)
+    fn clear_measures(_this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::clear_entries(EntryType::Measure, args, context)
+    }
+}
+
+impl Class for Performance {
+    const NAME: &'static str = "Performance";
+    const LENGTH: usize = 0;
+    const ATTRIBUTES: Attribute = Attribute::empty();
+
+    fn constructor(_: &JsValue, _: &[JsValue], _: &mut Context<'_>) -> JsResult<Self> {
+        illegal_constructor()
+    }
+
+    fn init(class: &mut ClassBuilder<'_, '_>) -> JsResult<()> {
+        class.method("now", 0, NativeFunction::from_fn_ptr(Self::now));
+        class.accessor_with_function_ptr("timeOrigin", Some(Self::get_time_origin), None, Attribute::all());
+        class.accessor_with_function_ptr("timing", Some(Self::get_timing), None, Attribute::all());
+        class.method("toJSON", 0, NativeFunction::from_fn_ptr(Self::to_json));
+
+        class.method("getEntries", 0, NativeFunction::from_fn_ptr(Self::get_entries));
+        class.method("getEntriesByType", 1, NativeFunction::from_fn_ptr(Self::get_entries_by_type));
+        class.method("getEntriesByName", 1, NativeFunction::from_fn_ptr(Self::get_entries_by_name));
+
+        class.method("mark", 1, NativeFunction::from_fn_ptr(Self::mark));
+        class.method("measure", 1, NativeFunction::from_fn_ptr(Self::measure));
+        class.method("clearMarks", 0, NativeFunction::from_fn_ptr(Self::clear_marks));
+        class.method("clearMeasures", 0, NativeFunction::from_fn_ptr(Self::clear_measures));
+        Ok(())
+    }
+}
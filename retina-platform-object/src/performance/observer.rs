@@ -0,0 +1,366 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use std::cell::{Cell, RefCell};
+
+use boa_engine::prelude::*;
+
+use boa_engine::class::{Class, ClassBuilder};
+use boa_engine::object::{IntegrityLevel, ObjectData};
+use boa_engine::object::builtins::{JsArray, JsFunction};
+use boa_engine::property::Attribute;
+use boa_gc::{Finalize, GcRefCell, Trace, custom_trace};
+use log::warn;
+
+use crate::prelude::*;
+
+use super::entry::{self, EntryType, PerformanceEntry};
+
+/// The [observer type](https://w3c.github.io/performance-timeline/#dfn-observer-type)
+/// of a `PerformanceObserver`, which is decided by the first call to
+/// `observe()`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum ObserverType {
+    /// Observing a single type, using `observe({ type })`.
+    Single,
+
+    /// Observing multiple types, using `observe({ entryTypes })`.
+    Multiple,
+}
+
+/// The [Interface `PerformanceObserver`](https://w3c.github.io/performance-timeline/#the-performanceobserver-interface)
+/// platform object, which is notified of new marks and measures.
+#[derive(Finalize)]
+pub struct PerformanceObserver {
+    callback: JsFunction,
+    observer_type: Cell<Option<ObserverType>>,
+    entry_types: RefCell<Vec<EntryType>>,
+    buffer: GcRefCell<Vec<PerformanceEntry>>,
+}
+
+unsafe impl Trace for PerformanceObserver {
+    custom_trace!(this, {
+        mark(&this.callback);
+        mark(&this.buffer);
+    });
+}
+
+impl PerformanceObserver {
+    pub(super) fn callback(&self) -> JsFunction {
+        self.callback.clone()
+    }
+
+    /// Append the `entry` to the observer buffer if the observer is
+    /// interested in its type, returning whether it was.
+    pub(super) fn append_if_observed(&self, entry: &PerformanceEntry) -> bool {
+        if !self.entry_types.borrow().contains(&entry.entry_type()) {
+            return false;
+        }
+
+        self.buffer.borrow_mut().push(entry.clone());
+        true
+    }
+
+    pub(super) fn take_buffer(&self) -> Vec<PerformanceEntry> {
+        std::mem::take(&mut *self.buffer.borrow_mut())
+    }
+
+    fn with_observer<T>(this: &JsValue, f: impl FnOnce(&Self) -> T) -> JsResult<T> {
+        let this = this.as_object()
+            .and_then(|obj| obj.downcast_ref::<Self>())
+            .ok_or_else(|| JsError::from_opaque("Value is not `PerformanceObserver`".into()))?;
+        Ok(f(&this))
+    }
+
+    /// Read the `entryTypes` member of the `PerformanceObserverInit`.
+    fn convert_entry_types(value: &JsValue, context: &mut Context) -> JsResult<Vec<String>> {
+        let array = value.as_object()
+            .cloned()
+            .and_then(|object| JsArray::from_object(object).ok())
+            .ok_or_else(|| JsNativeError::typ().with_message("`entryTypes` is not a sequence"))?;
+
+        let mut entry_types = Vec::new();
+        for index in 0..array.length(context)? {
+            entry_types.push(array.get(index, context)?.to_string(context)?.to_std_string_escaped());
+        }
+        Ok(entry_types)
+    }
+
+    /// [Performance Timeline § 6.2 `observe()`](https://w3c.github.io/performance-timeline/#observe-method)
+    fn observe(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let object = this.as_object()
+            .cloned()
+            .ok_or_else(|| JsError::from_opaque("Value is not `PerformanceObserver`".into()))?;
+
+        let mut entry_types = None;
+        let mut entry_type = None;
+        let mut buffered = None;
+        if let Some(options) = args.first().and_then(JsValue::as_object) {
+            let value = options.get("entryTypes", context)?;
+            if !value.is_undefined() {
+                entry_types = Some(Self::convert_entry_types(&value, context)?);
+            }
+
+            let value = options.get("type", context)?;
+            if !value.is_undefined() {
+                entry_type = Some(value.to_string(context)?.to_std_string_escaped());
+            }
+
+            let value = options.get("buffered", context)?;
+            if !value.is_undefined() {
+                buffered = Some(value.to_boolean());
+            }
+        }
+
+        // 2. If options's entryTypes and type members are both omitted, then
+        //    throw a "TypeError".
+        if entry_types.is_none() && entry_type.is_none() {
+            return Err(JsNativeError::typ().with_message("Either `entryTypes` or `type` must be given").into());
+        }
+
+        // 3. If options's entryTypes is present and any other member is also
+        //    present, then throw a "TypeError".
+        if entry_types.is_some() && (entry_type.is_some() || buffered.is_some()) {
+            return Err(JsNativeError::typ().with_message("`entryTypes` can't be combined with other options").into());
+        }
+
+        let observer_type = Self::with_observer(this, |observer| {
+            // 4. Update or check this's observer type by running these steps:
+            match observer.observer_type.get() {
+                // 4.1. If this's observer type is "undefined":
+                None => {
+                    let observer_type = if entry_types.is_some() {
+                        ObserverType::Multiple
+                    } else {
+                        ObserverType::Single
+                    };
+                    observer.observer_type.set(Some(observer_type));
+                    Ok(observer_type)
+                }
+
+                // 4.2. If this's observer type is "single" and options's
+                //      entryTypes member is present, then throw an
+                //      "InvalidModificationError".
+                Some(ObserverType::Single) if entry_types.is_some() => Err(()),
+
+                // 4.3. If this's observer type is "multiple" and options's
+                //      type member is present, then throw an
+                //      "InvalidModificationError".
+                Some(ObserverType::Multiple) if entry_type.is_some() => Err(()),
+
+                Some(observer_type) => Ok(observer_type),
+            }
+        })?;
+
+        let Ok(observer_type) = observer_type else {
+            return Err(dom_exception("InvalidModificationError", "The observer type of the PerformanceObserver can't be changed", context));
+        };
+
+        match observer_type {
+            // 6. If this's observer type is "multiple", run the following steps:
+            ObserverType::Multiple => {
+                // 6.1. Let entry types be options's entryTypes sequence.
+                // 6.2. Remove all types from entry types that are not
+                //      contained in relevantGlobal's frozen array of supported
+                //      entry types.
+                let entry_types: Vec<EntryType> = entry_types.unwrap_or_default()
+                    .iter()
+                    .filter_map(|entry_type| EntryType::parse(entry_type))
+                    .collect();
+
+                // 6.3. If the resulting entry types sequence is an empty
+                //      sequence, abort these steps. The user agent SHOULD
+                //      notify developers when the steps are aborted.
+                if entry_types.is_empty() {
+                    warn!("[performance] PerformanceObserver.observe() called without supported entry types");
+                    return Ok(JsValue::Undefined);
+                }
+
+                // 6.4. If the list of registered performance observer objects
+                //      of relevantGlobal contains a registered performance
+                //      observer whose observer is this, replace its options
+                //      list with a list containing options as its only item.
+                // 6.5. Otherwise, create and append a registered performance
+                //      observer object to the list of registered performance
+                //      observer objects of relevantGlobal.
+                Self::with_observer(this, |observer| *observer.entry_types.borrow_mut() = entry_types)?;
+                context.with_window(|window| window.performance_timeline().borrow_mut().register_observer(&object));
+            }
+
+            // 7. Otherwise, run the following steps:
+            ObserverType::Single => {
+                // 7.2. If options's type is not contained in the
+                //      relevantGlobal's frozen array of supported entry types,
+                //      abort these steps.
+                let Some(entry_type) = entry_type.as_deref().and_then(EntryType::parse) else {
+                    warn!("[performance] PerformanceObserver.observe() called with an unsupported type: {entry_type:?}");
+                    return Ok(JsValue::Undefined);
+                };
+
+                // 7.3. If the list of registered performance observer objects
+                //      of relevantGlobal contains a registered performance
+                //      observer obs whose observer is this: [...] append
+                //      options to obs's options list.
+                // 7.4. Otherwise, create and append a registered performance
+                //      observer object to the list of registered performance
+                //      observer objects of relevantGlobal.
+                Self::with_observer(this, |observer| {
+                    let mut entry_types = observer.entry_types.borrow_mut();
+                    if !entry_types.contains(&entry_type) {
+                        entry_types.push(entry_type);
+                    }
+                })?;
+                context.with_window(|window| window.performance_timeline().borrow_mut().register_observer(&object));
+
+                // 7.5. If options's buffered flag is set:
+                if buffered == Some(true) {
+                    // 7.5.2. For each entry in tuple's performance entry
+                    //        buffer: [...] append entry to this's observer
+                    //        buffer.
+                    context.with_window(|window| {
+                        let mut timeline = window.performance_timeline().borrow_mut();
+
+                        let entries = entry::filter_entries(timeline.entries(), None, Some(entry_type.as_str()));
+                        if entries.is_empty() {
+                            return;
+                        }
+
+                        if let Some(observer) = object.downcast_ref::<Self>() {
+                            observer.buffer.borrow_mut().extend(entries);
+                        }
+
+                        // 7.5.3. Queue the PerformanceObserver task with
+                        //        relevantGlobal as input.
+                        timeline.observer_task_queued = true;
+                    });
+                }
+            }
+        }
+
+        Ok(JsValue::Undefined)
+    }
+
+    /// [Performance Timeline § 6.3 `disconnect()`](https://w3c.github.io/performance-timeline/#disconnect-method)
+    fn disconnect(this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::with_observer(this, |observer| {
+            observer.buffer.borrow_mut().clear();
+            observer.entry_types.borrow_mut().clear();
+        })?;
+
+        if let Some(object) = this.as_object() {
+            context.with_window(|window| window.performance_timeline().borrow_mut().unregister_observer(object));
+        }
+
+        Ok(JsValue::Undefined)
+    }
+
+    /// [Performance Timeline § 6.4 `takeRecords()`](https://w3c.github.io/performance-timeline/#takerecords-method)
+    fn take_records(this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let entries = Self::with_observer(this, Self::take_buffer)?;
+        entry::create_entries_array(entries, context)
+    }
+}
+
+impl Class for PerformanceObserver {
+    const NAME: &'static str = "PerformanceObserver";
+    const LENGTH: usize = 1;
+    const ATTRIBUTES: Attribute = Attribute::empty();
+
+    /// [Performance Timeline § 6.1 `new PerformanceObserver(callback)`](https://w3c.github.io/performance-timeline/#the-performanceobserver-interface)
+    fn constructor(_: &JsValue, args: &[JsValue], _: &mut Context<'_>) -> JsResult<Self> {
+        let callback = args.first()
+            .and_then(JsValue::as_callable)
+            .cloned()
+            .and_then(JsFunction::from_object)
+            .ok_or_else(|| JsNativeError::typ().with_message("The callback is not a function"))?;
+
+        Ok(Self {
+            callback,
+            observer_type: Cell::new(None),
+            entry_types: RefCell::new(Vec::new()),
+            buffer: GcRefCell::new(Vec::new()),
+        })
+    }
+
+    fn init(class: &mut ClassBuilder<'_, '_>) -> JsResult<()> {
+        class.method("disconnect", 0, NativeFunction::from_fn_ptr(Self::disconnect));
+        class.method("observe", 0, NativeFunction::from_fn_ptr(Self::observe));
+        class.method("takeRecords", 0, NativeFunction::from_fn_ptr(Self::take_records));
+
+        // [Performance Timeline § 6.5 `supportedEntryTypes`](https://w3c.github.io/performance-timeline/#supportedentrytypes-attribute)
+        let supported_entry_types = EntryType::ALL.map(|entry_type| JsString::from(entry_type.as_str()).into());
+        let supported_entry_types = JsArray::from_iter(supported_entry_types, class.context());
+        supported_entry_types.set_integrity_level(IntegrityLevel::Frozen, class.context())?;
+        class.static_property("supportedEntryTypes", supported_entry_types, Attribute::READONLY | Attribute::ENUMERABLE | Attribute::PERMANENT);
+        Ok(())
+    }
+}
+
+/// The [Interface `PerformanceObserverEntryList`](https://w3c.github.io/performance-timeline/#performanceobserverentrylist-interface)
+/// platform object, which contains the entries given to the callback of a
+/// `PerformanceObserver`.
+#[derive(Finalize)]
+pub struct PerformanceObserverEntryList {
+    entries: Vec<PerformanceEntry>,
+}
+
+unsafe impl Trace for PerformanceObserverEntryList {
+    custom_trace!(this, {
+        mark(&this.entries);
+    });
+}
+
+impl PerformanceObserverEntryList {
+    pub fn new(entries: Vec<PerformanceEntry>) -> Self {
+        Self { entries }
+    }
+
+    pub fn create_object(self, context: &mut Context) -> JsResult<JsObject> {
+        Ok(JsObject::from_proto_and_data(
+            Some(class_prototype::<Self>(context)?),
+            ObjectData::native_object(self),
+        ))
+    }
+
+    fn with_entries<T>(this: &JsValue, f: impl FnOnce(&[PerformanceEntry]) -> T) -> JsResult<T> {
+        let this = this.as_object()
+            .and_then(|obj| obj.downcast_ref::<Self>())
+            .ok_or_else(|| JsError::from_opaque("Value is not `PerformanceObserverEntryList`".into()))?;
+        Ok(f(&this.entries))
+    }
+
+    /// [Performance Timeline § 6.6.1 `getEntries()`](https://w3c.github.io/performance-timeline/#getentries-method)
+    fn get_entries(this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let entries = Self::with_entries(this, |entries| entry::filter_entries(entries, None, None))?;
+        entry::create_entries_array(entries, context)
+    }
+
+    /// [Performance Timeline § 6.6.2 `getEntriesByType()`](https://w3c.github.io/performance-timeline/#getentriesbytype-method)
+    fn get_entries_by_type(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let entries = Self::with_entries(this, <[PerformanceEntry]>::to_vec)?;
+        entry::get_entries_by_type(&entries, args, context)
+    }
+
+    /// [Performance Timeline § 6.6.3 `getEntriesByName()`](https://w3c.github.io/performance-timeline/#getentriesbyname-method)
+    fn get_entries_by_name(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let entries = Self::with_entries(this, <[PerformanceEntry]>::to_vec)?;
+        entry::get_entries_by_name(&entries, args, context)
+    }
+}
+
+impl Class for PerformanceObserverEntryList {
+    const NAME: &'static str = "PerformanceObserverEntryList";
+    const LENGTH: usize = 0;
+    const ATTRIBUTES: Attribute = Attribute::empty();
+
+    fn constructor(_: &JsValue, _: &[JsValue], _: &mut Context<'_>) -> JsResult<Self> {
+        illegal_constructor()
+    }
+
+    fn init(class: &mut ClassBuilder<'_, '_>) -> JsResult<()> {
+        class.method("getEntries", 0, NativeFunction::from_fn_ptr(Self::get_entries));
+        class.method("getEntriesByType", 1, NativeFunction::from_fn_ptr(Self::get_entries_by_type));
+        class.method("getEntriesByName", 1, NativeFunction::from_fn_ptr(Self::get_entries_by_name));
+        Ok(())
+    }
+}
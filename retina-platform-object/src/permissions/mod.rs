@@ -0,0 +1,100 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! The [Permissions API](https://w3c.github.io/permissions/).
+
+use std::{
+    str::FromStr,
+    sync::{Arc, RwLock},
+};
+
+use boa_engine::prelude::*;
+
+use boa_engine::class::{Class, ClassBuilder};
+use boa_engine::object::{builtins::JsPromise, ObjectData, ObjectInitializer};
+use boa_engine::property::Attribute;
+use boa_gc::{Finalize, Trace, empty_trace};
+use retina_user_agent::permission::{Origin, PermissionName, PermissionStore};
+
+use crate::prelude::{class_prototype, illegal_constructor};
+
+/// The [Interface `Permissions`](https://w3c.github.io/permissions/#permissions-interface)
+/// platform object, available as `navigator.permissions`.
+#[derive(Finalize)]
+pub struct Permissions {
+    store: Arc<RwLock<PermissionStore>>,
+    origin: Origin,
+}
+
+impl Permissions {
+    pub fn new(store: Arc<RwLock<PermissionStore>>, origin: Origin) -> Self {
+        Self {
+            store,
+            origin,
+        }
+    }
+
+    /// Create a JavaScript object, using the prototype of the registered
+    /// `Permissions` class.
+    pub fn create_object(self, context: &mut Context) -> JsResult<JsObject> {
+        Ok(JsObject::from_proto_and_data(
+            Some(class_prototype::<Self>(context)?),
+            ObjectData::native_object(self),
+        ))
+    }
+
+    /// [Permissions § 6.1 `query()`](https://w3c.github.io/permissions/#dom-permissions-query)
+    pub fn query(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let descriptor = args.get(0)
+            .and_then(JsValue::as_object)
+            .cloned()
+            .ok_or_else(|| JsNativeError::typ().with_message("The permission descriptor must be an object"))?;
+
+        let name = descriptor.get("name", context)?
+            .to_string(context)?
+            .to_std_string_escaped();
+
+        let Ok(name) = PermissionName::from_str(&name) else {
+            let error = JsNativeError::typ()
+                .with_message(format!("Unknown permission: \"{name}\""));
+            return Ok(JsPromise::reject(error, context)?.into());
+        };
+
+        // The guard of the store must be dropped before the borrow of `this`
+        // ends, so the state is assigned in a statement of its own.
+        let state;
+        {
+            let this = this.as_object()
+                .and_then(|obj| obj.downcast_ref::<Self>())
+                .ok_or_else(|| JsError::from_opaque("Value is not `Permissions`".into()))?;
+
+            state = this.store.read().unwrap().query(&this.origin, name);
+        }
+
+        // TODO: PermissionStatus should be an EventTarget that fires `change`
+        //       events.
+        let status = ObjectInitializer::new(context)
+            .property("name", JsString::from(name.as_str()), Attribute::READONLY | Attribute::ENUMERABLE)
+            .property("state", JsString::from(state.as_str()), Attribute::READONLY | Attribute::ENUMERABLE)
+            .build();
+
+        Ok(JsPromise::resolve(status, context)?.into())
+    }
+}
+
+impl Class for Permissions {
+    const NAME: &'static str = "Permissions";
+    const LENGTH: usize = 0;
+    const ATTRIBUTES: Attribute = Attribute::empty();
+
+    fn constructor(_: &JsValue, _: &[JsValue], _: &mut Context<'_>) -> JsResult<Self> {
+        illegal_constructor()
+    }
+
+    fn init(class: &mut ClassBuilder<'_, '_>) -> JsResult<()> {
+        class.method("query", 1, NativeFunction::from_fn_ptr(Self::query));
+        Ok(())
+    }
+}
+
+unsafe impl Trace for Permissions { empty_trace!(); }
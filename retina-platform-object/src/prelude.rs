@@ -2,7 +2,7 @@
 // All Rights Reserved.
 
 use boa_engine::{
-    class::ClassBuilder,
+    class::{Class, ClassBuilder},
     native_function::NativeFunctionPointer,
     object::FunctionObjectBuilder,
     property::{Attribute, PropertyKey},
@@ -17,6 +17,16 @@ pub fn illegal_constructor<T>() -> JsResult<T> {
     JsResult::Err(JsError::from_native(native_error))
 }
 
+/// Create an `Error` with the `name` of a [`DOMException`](https://webidl.spec.whatwg.org/#idl-DOMException),
+/// e.g. `"InvalidStateError"`, since that interface isn't implemented yet.
+pub fn dom_exception(name: &str, message: &str, context: &mut Context) -> JsError {
+    let error = JsNativeError::error()
+        .with_message(message)
+        .to_opaque(context);
+    _ = error.set("name", JsString::from(name), false, context);
+    JsError::from_opaque(error.into())
+}
+
 pub trait ClassBuilderExt {
     fn accessor_with_function_ptr<K>(
         &mut self,
@@ -69,3 +79,56 @@ impl<'host> ContextExt for Context<'host> {
         f(&self.global_object().downcast_ref::<Window>().unwrap())
     }
 }
+
+/// Get the `prototype` of the registered class `C`.
+pub fn class_prototype<C: Class>(context: &mut Context) -> JsResult<JsObject> {
+    let constructor = context.global_object().get(C::NAME, context)?;
+    let prototype = constructor.as_object()
+        .ok_or_else(|| JsError::from_opaque(format!("`{}` is not registered", C::NAME).into()))?
+        .get("prototype", context)?;
+
+    prototype.as_object()
+        .cloned()
+        .ok_or_else(|| JsError::from_opaque(format!("`{}` has no prototype", C::NAME).into()))
+}
+
+/// Make the registered class `Derived` inherit the properties of the
+/// registered class `Base`, by chaining their prototypes.
+pub fn inherit_class<Derived: Class, Base: Class>(context: &mut Context) -> JsResult<()> {
+    let derived = class_prototype::<Derived>(context)?;
+    let base = class_prototype::<Base>(context)?;
+    derived.set_prototype(Some(base));
+    Ok(())
+}
+
+/// Convert the arguments to numbers, returning [`None`] if any of them isn't
+/// finite, since most drawing methods ignore such calls.
+pub fn finite_number_arguments<const N: usize>(args: &[JsValue], context: &mut Context) -> JsResult<Option<[f32; N]>> {
+    let mut numbers = [0.0; N];
+    for (index, number) in numbers.iter_mut().enumerate() {
+        let value = args.get(index)
+            .cloned()
+            .unwrap_or_default()
+            .to_number(context)?;
+
+        if !value.is_finite() {
+            return Ok(None);
+        }
+
+        *number = value as f32;
+    }
+    Ok(Some(numbers))
+}
+
+/// Parse the `text` as JSON, using the global `JSON.parse()`.
+pub fn parse_json(text: JsString, context: &mut Context) -> JsResult<JsValue> {
+    let parse = context.global_object()
+        .get("JSON", context)?
+        .as_object()
+        .cloned()
+        .ok_or_else(|| JsError::from_opaque("`JSON` is not an object".into()))?
+        .get("parse", context)?;
+    parse.as_callable()
+        .ok_or_else(|| JsError::from_opaque("`JSON.parse` is not a function".into()))?
+        .call(&JsValue::Undefined, &[text.into()], context)
+}
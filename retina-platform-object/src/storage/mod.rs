@@ -0,0 +1,279 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! The [Web Storage API](https://html.spec.whatwg.org/multipage/webstorage.html),
+//! i.e. `localStorage` and `sessionStorage`.
+
+use std::{
+    cell::RefCell,
+    sync::{mpsc::Receiver, Arc, RwLock},
+};
+
+use boa_engine::prelude::*;
+
+use boa_engine::class::{Class, ClassBuilder};
+use boa_engine::object::{ObjectData, ObjectInitializer};
+use boa_engine::property::Attribute;
+use boa_gc::{Finalize, Trace, empty_trace};
+use log::error;
+use retina_user_agent::permission::Origin;
+use retina_user_agent::storage::{
+    LocalStorage,
+    QuotaExceededError,
+    StorageArea,
+    StorageChange,
+    StorageListenerId,
+    StorageNotification,
+};
+
+use crate::prelude::*;
+
+/// The [Interface `Storage`](https://html.spec.whatwg.org/multipage/webstorage.html#the-storage-interface)
+/// platform object, available as `localStorage` and `sessionStorage`.
+#[derive(Finalize)]
+pub struct Storage {
+    kind: StorageKind,
+}
+
+unsafe impl Trace for Storage {
+    empty_trace!();
+}
+
+enum StorageKind {
+    /// The area of the origin in the [`LocalStorage`], which is shared with
+    /// the other browsing contexts.
+    Local {
+        store: Arc<RwLock<LocalStorage>>,
+        origin: Origin,
+        listener: StorageListenerId,
+
+        /// The changes made by the other browsing contexts of the origin.
+        notifications: Receiver<StorageNotification>,
+    },
+
+    /// The area of `sessionStorage`, which belongs to this browsing context.
+    Session(RefCell<StorageArea>),
+}
+
+impl Storage {
+    /// Create the `localStorage` of the `origin`, which is notified of the
+    /// changes made by other browsing contexts.
+    pub fn new_local(store: Arc<RwLock<LocalStorage>>, origin: Origin) -> Self {
+        let (listener, notifications) = store.write().unwrap().subscribe(origin.clone());
+        Self {
+            kind: StorageKind::Local {
+                store,
+                origin,
+                listener,
+                notifications,
+            },
+        }
+    }
+
+    /// Create an empty `sessionStorage`.
+    pub fn new_session() -> Self {
+        Self {
+            kind: StorageKind::Session(RefCell::new(StorageArea::new())),
+        }
+    }
+
+    pub fn create_object(self, context: &mut Context) -> JsResult<JsObject> {
+        Ok(JsObject::from_proto_and_data(
+            Some(class_prototype::<Self>(context)?),
+            ObjectData::native_object(self),
+        ))
+    }
+
+    /// Take the changes of `localStorage` made by other browsing contexts.
+    /// This is always empty for `sessionStorage`.
+    pub fn take_notifications(&self) -> Vec<StorageNotification> {
+        match &self.kind {
+            StorageKind::Local { notifications, .. } => notifications.try_iter().collect(),
+            StorageKind::Session(..) => Vec::new(),
+        }
+    }
+
+    /// Stop receiving the changes of `localStorage` made by other browsing
+    /// contexts, which must be done when the browsing context is discarded.
+    pub fn unsubscribe(&self) {
+        if let StorageKind::Local { store, listener, .. } = &self.kind {
+            store.write().unwrap().unsubscribe(*listener);
+        }
+    }
+
+    fn read<T>(&self, f: impl FnOnce(Option<&StorageArea>) -> T) -> T {
+        match &self.kind {
+            StorageKind::Local { store, origin, .. } => f(store.read().unwrap().area(origin)),
+            StorageKind::Session(area) => f(Some(&area.borrow())),
+        }
+    }
+
+    fn with_storage<T>(this: &JsValue, f: impl FnOnce(&Self) -> T) -> JsResult<T> {
+        let this = this.as_object()
+            .and_then(|obj| obj.downcast_ref::<Self>())
+            .ok_or_else(|| JsError::from_opaque("Value is not `Storage`".into()))?;
+        Ok(f(&this))
+    }
+
+    fn string_argument(args: &[JsValue], index: usize, context: &mut Context) -> JsResult<String> {
+        let Some(value) = args.get(index) else {
+            return Err(JsNativeError::typ()
+                .with_message(format!("Expected at least {} arguments", index + 1))
+                .into());
+        };
+        Ok(value.to_string(context)?.to_std_string_escaped())
+    }
+
+    fn optional_string(value: Option<&str>) -> JsValue {
+        value.map_or(JsValue::Null, |value| JsString::from(value).into())
+    }
+
+    /// [HTML Living Standard § 12.2.1 `length`](https://html.spec.whatwg.org/multipage/webstorage.html#dom-storage-length)
+    fn get_length(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_storage(this, |storage| {
+            storage.read(|area| JsValue::new(area.map_or(0, StorageArea::length)))
+        })
+    }
+
+    /// [HTML Living Standard § 12.2.1 `key()`](https://html.spec.whatwg.org/multipage/webstorage.html#dom-storage-key)
+    fn key(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let Some(index) = args.first() else {
+            return Err(JsNativeError::typ().with_message("Expected at least 1 argument").into());
+        };
+        let index = index.to_u32(context)? as usize;
+
+        Self::with_storage(this, |storage| {
+            storage.read(|area| Self::optional_string(area.and_then(|area| area.key(index))))
+        })
+    }
+
+    /// [HTML Living Standard § 12.2.1 `getItem()`](https://html.spec.whatwg.org/multipage/webstorage.html#dom-storage-getitem)
+    fn get_item(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let key = Self::string_argument(args, 0, context)?;
+        Self::with_storage(this, |storage| {
+            storage.read(|area| Self::optional_string(area.and_then(|area| area.get_item(&key))))
+        })
+    }
+
+    /// [HTML Living Standard § 12.2.1 `setItem()`](https://html.spec.whatwg.org/multipage/webstorage.html#dom-storage-setitem)
+    fn set_item(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let key = Self::string_argument(args, 0, context)?;
+        let value = Self::string_argument(args, 1, context)?;
+        let url = context.with_window(|window| window.url().to_string());
+
+        let result = Self::with_storage(this, |storage| match &storage.kind {
+            StorageKind::Local { store, origin, listener, .. } => {
+                store.write().unwrap().set_item(origin, &key, &value, *listener, &url)
+            }
+            StorageKind::Session(area) => area.borrow_mut().set_item(&key, &value).map(|_| ()),
+        })?;
+
+        // 4. If this's map's size exceeds the quota, then throw a
+        //    "QuotaExceededError" DOMException.
+        if let Err(QuotaExceededError) = result {
+            return Err(dom_exception("QuotaExceededError", "The storage quota has been exceeded.", context));
+        }
+
+        Ok(JsValue::Undefined)
+    }
+
+    /// [HTML Living Standard § 12.2.1 `removeItem()`](https://html.spec.whatwg.org/multipage/webstorage.html#dom-storage-removeitem)
+    fn remove_item(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let key = Self::string_argument(args, 0, context)?;
+        let url = context.with_window(|window| window.url().to_string());
+
+        Self::with_storage(this, |storage| match &storage.kind {
+            StorageKind::Local { store, origin, listener, .. } => {
+                store.write().unwrap().remove_item(origin, &key, *listener, &url);
+            }
+            StorageKind::Session(area) => {
+                area.borrow_mut().remove_item(&key);
+            }
+        })?;
+
+        Ok(JsValue::Undefined)
+    }
+
+    /// [HTML Living Standard § 12.2.1 `clear()`](https://html.spec.whatwg.org/multipage/webstorage.html#dom-storage-clear)
+    fn clear(this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let url = context.with_window(|window| window.url().to_string());
+
+        Self::with_storage(this, |storage| match &storage.kind {
+            StorageKind::Local { store, origin, listener, .. } => {
+                store.write().unwrap().clear(origin, *listener, &url);
+            }
+            StorageKind::Session(area) => {
+                area.borrow_mut().clear();
+            }
+        })?;
+
+        Ok(JsValue::Undefined)
+    }
+}
+
+impl Class for Storage {
+    const NAME: &'static str = "Storage";
+    const LENGTH: usize = 0;
+    const ATTRIBUTES: Attribute = Attribute::empty();
+
+    fn constructor(_: &JsValue, _: &[JsValue], _: &mut Context<'_>) -> JsResult<Self> {
+        illegal_constructor()
+    }
+
+    fn init(class: &mut ClassBuilder<'_, '_>) -> JsResult<()> {
+        class.accessor_with_function_ptr("length", Some(Self::get_length), None, Attribute::all());
+        class.method("key", 1, NativeFunction::from_fn_ptr(Self::key));
+        class.method("getItem", 1, NativeFunction::from_fn_ptr(Self::get_item));
+        class.method("setItem", 2, NativeFunction::from_fn_ptr(Self::set_item));
+        class.method("removeItem", 1, NativeFunction::from_fn_ptr(Self::remove_item));
+        class.method("clear", 0, NativeFunction::from_fn_ptr(Self::clear));
+        Ok(())
+    }
+}
+
+/// Fire a [`storage` event](https://html.spec.whatwg.org/multipage/webstorage.html#the-storageevent-interface)
+/// at the `Window` for each change of `localStorage` that other browsing
+/// contexts of the same origin made. Returns the number of events fired.
+pub fn run_storage_notifications(context: &mut Context) -> usize {
+    let Some(storage_area) = context.with_window(|window| window.local_storage()) else {
+        return 0;
+    };
+
+    let notifications = storage_area.downcast_ref::<Storage>()
+        .map(|storage| storage.take_notifications())
+        .unwrap_or_default();
+    let count = notifications.len();
+
+    for notification in notifications {
+        if let Err(e) = fire_storage_event(&storage_area, notification, context) {
+            error!("[storage] Uncaught exception in onstorage handler: {e}");
+        }
+
+        context.run_jobs();
+    }
+
+    count
+}
+
+fn fire_storage_event(storage_area: &JsObject, notification: StorageNotification, context: &mut Context) -> JsResult<()> {
+    let window = context.global_object();
+    let handler = window.get("onstorage", context)?;
+    let Some(handler) = handler.as_callable() else {
+        return Ok(());
+    };
+
+    let StorageChange { key, old_value, new_value } = notification.change;
+    let event = ObjectInitializer::new(context)
+        .property("type", JsString::from("storage"), Attribute::READONLY | Attribute::ENUMERABLE)
+        .property("target", window.clone(), Attribute::READONLY | Attribute::ENUMERABLE)
+        .property("currentTarget", window.clone(), Attribute::READONLY | Attribute::ENUMERABLE)
+        .property("key", Storage::optional_string(key.as_deref()), Attribute::READONLY | Attribute::ENUMERABLE)
+        .property("oldValue", Storage::optional_string(old_value.as_deref()), Attribute::READONLY | Attribute::ENUMERABLE)
+        .property("newValue", Storage::optional_string(new_value.as_deref()), Attribute::READONLY | Attribute::ENUMERABLE)
+        .property("url", JsString::from(notification.url), Attribute::READONLY | Attribute::ENUMERABLE)
+        .property("storageArea", storage_area.clone(), Attribute::READONLY | Attribute::ENUMERABLE)
+        .build();
+
+    handler.call(&window.into(), &[event.into()], context)?;
+    Ok(())
+}
@@ -0,0 +1,278 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! The [timers](https://html.spec.whatwg.org/multipage/timers-and-user-prompts.html#timers)
+//! of the `WindowOrWorkerGlobalScope` mixin, i.e. `setTimeout()` and
+//! `setInterval()`.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    time::{Duration, Instant},
+};
+
+use boa_engine::prelude::*;
+
+use boa_engine::object::builtins::JsFunction;
+use boa_gc::{Finalize, Trace, custom_trace};
+use log::error;
+
+use crate::prelude::*;
+
+/// The identifier of a timer, which is returned by `setTimeout()` and
+/// `setInterval()`, and can be passed to `clearTimeout()` and
+/// `clearInterval()`.
+pub type TimerId = u32;
+
+/// After this many nested timers, the timeout is clamped to at least
+/// [`MINIMUM_NESTED_TIMEOUT`].
+const MAXIMUM_NESTING_LEVEL: u32 = 5;
+
+/// The minimum timeout of deeply nested timers, which makes sure a page can't
+/// keep the event loop busy with e.g. `setInterval(f, 0)`.
+const MINIMUM_NESTED_TIMEOUT: Duration = Duration::from_millis(4);
+
+/// The function or script that is run when a timer expires.
+#[derive(Finalize)]
+pub enum TimerHandler {
+    Callback(JsFunction),
+    Script(String),
+}
+
+unsafe impl Trace for TimerHandler {
+    custom_trace!(this, {
+        if let TimerHandler::Callback(callback) = this {
+            mark(callback);
+        }
+    });
+}
+
+/// A timer that is scheduled to run in the [`TimerList`].
+#[derive(Finalize)]
+pub struct Timer {
+    id: TimerId,
+    handler: TimerHandler,
+    arguments: Vec<JsValue>,
+    timeout: Duration,
+    repeat: bool,
+    nesting_level: u32,
+}
+
+unsafe impl Trace for Timer {
+    custom_trace!(this, {
+        mark(&this.handler);
+        mark(&this.arguments);
+    });
+}
+
+impl Timer {
+    /// Run the handler of this timer, with the global object as `this`.
+    fn run(&self, context: &mut Context) -> JsResult<()> {
+        match &self.handler {
+            TimerHandler::Callback(callback) => {
+                let this = JsValue::Object(context.global_object());
+                callback.call(&this, &self.arguments, context)?;
+            }
+            TimerHandler::Script(script) => {
+                context.eval(Source::from_bytes(script))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The timers of a `Window`, which are ordered by the time they expire.
+#[derive(Finalize, Default)]
+pub struct TimerList {
+    next_id: TimerId,
+
+    /// The [timer nesting level](https://html.spec.whatwg.org/multipage/timers-and-user-prompts.html#timer-nesting-level)
+    /// of the timer that is currently running, or zero.
+    nesting_level: u32,
+
+    /// The [map of active timers](https://html.spec.whatwg.org/multipage/timers-and-user-prompts.html#map-of-active-timers).
+    active_timers: HashSet<TimerId>,
+
+    /// The timers that haven't expired yet, by the time they expire.
+    schedule: BTreeMap<Instant, Vec<Timer>>,
+}
+
+unsafe impl Trace for TimerList {
+    custom_trace!(this, {
+        for timers in this.schedule.values() {
+            mark(timers);
+        }
+    });
+}
+
+impl TimerList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a new timer, returning its [`TimerId`].
+    ///
+    /// [HTML Living Standard § 8.6 Timer initialization steps](https://html.spec.whatwg.org/multipage/timers-and-user-prompts.html#timer-initialisation-steps)
+    pub fn insert(&mut self, handler: TimerHandler, arguments: Vec<JsValue>, timeout: Duration, repeat: bool) -> TimerId {
+        // 2. If previousId was given, let id be previousId; otherwise, let id
+        //    be an implementation-defined integer that is greater than zero
+        //    and does not already exist in global's map of active timers.
+        self.next_id += 1;
+        let id = self.next_id;
+        self.active_timers.insert(id);
+
+        self.schedule(Timer {
+            id,
+            handler,
+            arguments,
+            timeout,
+            repeat,
+            // 3. If the surrounding agent's event loop's currently running
+            //    task is a task that was created by this algorithm, then let
+            //    nesting level be the task's timer nesting level. Otherwise,
+            //    let nesting level be zero.
+            nesting_level: self.nesting_level,
+        });
+
+        id
+    }
+
+    /// [Clear the timer](https://html.spec.whatwg.org/multipage/timers-and-user-prompts.html#dom-cleartimeout)
+    /// with the given `id`, if there is one.
+    pub fn clear(&mut self, id: TimerId) {
+        if !self.active_timers.remove(&id) {
+            return;
+        }
+
+        for timers in self.schedule.values_mut() {
+            timers.retain(|timer| timer.id != id);
+        }
+        self.schedule.retain(|_, timers| !timers.is_empty());
+    }
+
+    fn schedule(&mut self, mut timer: Timer) {
+        // 11. If nesting level is greater than 5, and timeout is less than 4,
+        //     then set timeout to 4.
+        if timer.nesting_level > MAXIMUM_NESTING_LEVEL && timer.timeout < MINIMUM_NESTED_TIMEOUT {
+            timer.timeout = MINIMUM_NESTED_TIMEOUT;
+        }
+
+        // 13. Set task's timer nesting level to nesting level + 1.
+        timer.nesting_level += 1;
+
+        self.schedule.entry(Instant::now() + timer.timeout)
+            .or_default()
+            .push(timer);
+    }
+
+    /// Take the first timer that expired at or before `now`.
+    fn pop_expired(&mut self, now: Instant) -> Option<Timer> {
+        let mut entry = self.schedule.first_entry()?;
+        if *entry.key() > now {
+            return None;
+        }
+
+        let timer = entry.get_mut().remove(0);
+        if entry.get().is_empty() {
+            entry.remove();
+        }
+
+        Some(timer)
+    }
+}
+
+/// [HTML Living Standard § 8.6 `setTimeout()`](https://html.spec.whatwg.org/multipage/timers-and-user-prompts.html#dom-settimeout)
+pub fn set_timeout(_this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+    initialize_timer(args, false, context)
+}
+
+/// [HTML Living Standard § 8.6 `setInterval()`](https://html.spec.whatwg.org/multipage/timers-and-user-prompts.html#dom-setinterval)
+pub fn set_interval(_this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+    initialize_timer(args, true, context)
+}
+
+/// [HTML Living Standard § 8.6 `clearTimeout()`](https://html.spec.whatwg.org/multipage/timers-and-user-prompts.html#dom-cleartimeout)
+/// and [`clearInterval()`](https://html.spec.whatwg.org/multipage/timers-and-user-prompts.html#dom-clearinterval),
+/// which share the same list of timers.
+pub fn clear_timer(_this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+    let id = args.first()
+        .cloned()
+        .unwrap_or_default()
+        .to_number(context)?;
+
+    if id.is_finite() && id >= 1.0 && id <= TimerId::MAX as f64 {
+        context.with_window(|window| window.timers().borrow_mut().clear(id as TimerId));
+    }
+
+    Ok(JsValue::Undefined)
+}
+
+/// The [timer initialization steps](https://html.spec.whatwg.org/multipage/timers-and-user-prompts.html#timer-initialisation-steps)
+/// of `setTimeout(handler, timeout, ...arguments)` and
+/// `setInterval(handler, timeout, ...arguments)`.
+fn initialize_timer(args: &[JsValue], repeat: bool, context: &mut Context) -> JsResult<JsValue> {
+    // A handler that isn't a function is converted to a string, which is run
+    // as a script.
+    let handler = match args.first().and_then(JsValue::as_callable).cloned().and_then(JsFunction::from_object) {
+        Some(callback) => TimerHandler::Callback(callback),
+        None => TimerHandler::Script(
+            args.first()
+                .cloned()
+                .unwrap_or_default()
+                .to_string(context)?
+                .to_std_string_escaped()
+        ),
+    };
+
+    // 4. If timeout is less than 0, then set timeout to 0.
+    let timeout = args.get(1)
+        .cloned()
+        .unwrap_or_default()
+        .to_number(context)?;
+    let timeout = if timeout.is_finite() && timeout > 0.0 {
+        Duration::from_millis(timeout.min(i32::MAX as f64) as u64)
+    } else {
+        Duration::ZERO
+    };
+
+    let arguments = args.get(2..).unwrap_or_default().to_vec();
+
+    let id = context.with_window(|window| {
+        window.timers().borrow_mut().insert(handler, arguments, timeout, repeat)
+    });
+
+    Ok(JsValue::new(id))
+}
+
+/// Run the timers that expired, in the order of their expiry. A microtask
+/// checkpoint is performed after each timer. Returns the number of timers
+/// that ran.
+pub fn run_expired_timers(context: &mut Context) -> usize {
+    let now = Instant::now();
+    let mut count = 0;
+
+    while let Some(timer) = context.with_window(|window| window.timers().borrow_mut().pop_expired(now)) {
+        context.with_window(|window| window.timers().borrow_mut().nesting_level = timer.nesting_level);
+
+        if let Err(e) = timer.run(context) {
+            error!("[timers] Uncaught exception in timer {}: {e}", timer.id);
+        }
+
+        context.run_jobs();
+        count += 1;
+
+        context.with_window(|window| {
+            let mut timers = window.timers().borrow_mut();
+            timers.nesting_level = 0;
+
+            // Intervals are scheduled again, unless they were cleared whilst
+            // running. Timeouts are removed from the map of active timers.
+            if timer.repeat && timers.active_timers.contains(&timer.id) {
+                timers.schedule(timer);
+            } else {
+                timers.active_timers.remove(&timer.id);
+            }
+        });
+    }
+
+    count
+}
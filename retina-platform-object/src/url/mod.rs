@@ -0,0 +1,352 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! The [`URL`](https://url.spec.whatwg.org/#url-class) and
+//! [`URLSearchParams`](https://url.spec.whatwg.org/#interface-urlsearchparams)
+//! interfaces of the URL Standard.
+
+mod search_params;
+
+pub use search_params::UrlSearchParams;
+
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use boa_engine::prelude::*;
+
+use boa_engine::class::{Class, ClassBuilder};
+use boa_engine::object::ObjectData;
+use boa_engine::property::Attribute;
+use boa_gc::{Finalize, GcRefCell, Trace, custom_trace};
+use retina_fetch::url::quirks;
+
+use crate::blob::Blob;
+use crate::prelude::*;
+
+/// The parsed URL, which is called a [URL record](https://url.spec.whatwg.org/#concept-url)
+/// by the specification, as opposed to the `URL` platform object.
+type UrlRecord = retina_fetch::Url;
+
+/// The [Interface `URL`](https://url.spec.whatwg.org/#url-class) platform
+/// object.
+#[derive(Finalize)]
+pub struct Url {
+    url: RefCell<UrlRecord>,
+
+    /// The [query object](https://url.spec.whatwg.org/#concept-url-query-object),
+    /// which is created when `searchParams` is first accessed.
+    query_object: GcRefCell<Option<JsObject>>,
+}
+
+unsafe impl Trace for Url {
+    custom_trace!(this, {
+        mark(&this.query_object);
+    });
+}
+
+impl Url {
+    pub fn new(url: UrlRecord) -> Self {
+        Self {
+            url: RefCell::new(url),
+            query_object: GcRefCell::new(None),
+        }
+    }
+
+    pub fn url(&self) -> UrlRecord {
+        self.url.borrow().clone()
+    }
+
+    pub fn create_object(self, context: &mut Context) -> JsResult<JsObject> {
+        Ok(JsObject::from_proto_and_data(
+            Some(class_prototype::<Self>(context)?),
+            ObjectData::native_object(self),
+        ))
+    }
+
+    /// Set the query of the URL, after the `URLSearchParams` was changed.
+    fn set_query(&self, query: Option<&str>) {
+        self.url.borrow_mut().set_query(query);
+    }
+
+    /// Set the list of the query object to the query of the URL, after the
+    /// URL was changed.
+    fn update_query_object(&self) {
+        let Some(query_object) = self.query_object.borrow().clone() else {
+            return;
+        };
+
+        let list = search_params::parse_query(self.url.borrow().query().unwrap_or_default());
+        if let Some(query_object) = query_object.downcast_ref::<UrlSearchParams>() {
+            query_object.set_list(list);
+        };
+    }
+
+    fn with_url<T>(this: &JsValue, f: impl FnOnce(&Self) -> T) -> JsResult<T> {
+        let this = this.as_object()
+            .and_then(|obj| obj.downcast_ref::<Self>())
+            .ok_or_else(|| JsError::from_opaque("Value is not `URL`".into()))?;
+        Ok(f(&this))
+    }
+
+    /// Get the component of the URL using one of the [`quirks`] getters.
+    fn get_component(this: &JsValue, getter: fn(&UrlRecord) -> &str) -> JsResult<JsValue> {
+        Self::with_url(this, |url| JsString::from(getter(&url.url.borrow())).into())
+    }
+
+    /// Set the component of the URL using one of the [`quirks`] setters.
+    /// Invalid values are ignored, as the specification requires.
+    fn set_component<E>(this: &JsValue, args: &[JsValue], context: &mut Context, setter: fn(&mut UrlRecord, &str) -> Result<(), E>) -> JsResult<JsValue> {
+        let value = args.first().cloned().unwrap_or_default()
+            .to_string(context)?
+            .to_std_string_escaped();
+
+        Self::with_url(this, |url| _ = setter(&mut url.url.borrow_mut(), &value))?;
+        Ok(JsValue::Undefined)
+    }
+
+    /// Parse the `url` against the `base`, which is the
+    /// [API URL parser](https://url.spec.whatwg.org/#api-url-parser).
+    fn parse(url: &JsValue, base: Option<&JsValue>, context: &mut Context) -> JsResult<UrlRecord> {
+        let url = url.to_string(context)?.to_std_string_escaped();
+
+        // 1. Let parsedBase be null.
+        // 2. If base is non-null:
+        let base = match base {
+            None | Some(JsValue::Undefined) => None,
+            Some(base) => {
+                // 2.1. Set parsedBase to the result of running the basic URL
+                //      parser on base.
+                // 2.2. If parsedBase is failure, then return failure.
+                let base = base.to_string(context)?.to_std_string_escaped();
+                let base = UrlRecord::parse(&base)
+                    .map_err(|e| JsNativeError::typ().with_message(format!("Invalid base URL \"{base}\": {e}")))?;
+                Some(base)
+            }
+        };
+
+        // 3. Return the result of running the basic URL parser on url with
+        //    parsedBase.
+        UrlRecord::options()
+            .base_url(base.as_ref())
+            .parse(&url)
+            .map_err(|e| JsNativeError::typ().with_message(format!("Invalid URL \"{url}\": {e}")).into())
+    }
+
+    /// [URL Standard § 6.1 `href`](https://url.spec.whatwg.org/#dom-url-href)
+    fn get_href(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::get_component(this, quirks::href)
+    }
+
+    /// [URL Standard § 6.1 `href` setter](https://url.spec.whatwg.org/#ref-for-dom-url-href②)
+    fn set_href(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 1. Let parsedURL be the result of running the basic URL parser on
+        //    the given value.
+        // 2. If parsedURL is failure, then throw a TypeError.
+        let url = Self::parse(&args.first().cloned().unwrap_or_default(), None, context)?;
+
+        Self::with_url(this, |this| {
+            // 3. Set this's URL to parsedURL.
+            *this.url.borrow_mut() = url;
+
+            // 4. Empty this's query object's list.
+            // 5. Let query be this's URL's query.
+            // 6. If query is non-null, then set this's query object's list to
+            //    the result of parsing query.
+            this.update_query_object();
+        })?;
+        Ok(JsValue::Undefined)
+    }
+
+    /// [URL Standard § 6.1 `origin`](https://url.spec.whatwg.org/#dom-url-origin)
+    fn get_origin(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_url(this, |url| JsString::from(quirks::origin(&url.url.borrow())).into())
+    }
+
+    /// [URL Standard § 6.1 `protocol`](https://url.spec.whatwg.org/#dom-url-protocol)
+    fn get_protocol(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::get_component(this, quirks::protocol)
+    }
+
+    fn set_protocol(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::set_component(this, args, context, quirks::set_protocol)
+    }
+
+    /// [URL Standard § 6.1 `username`](https://url.spec.whatwg.org/#dom-url-username)
+    fn get_username(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::get_component(this, quirks::username)
+    }
+
+    fn set_username(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::set_component(this, args, context, quirks::set_username)
+    }
+
+    /// [URL Standard § 6.1 `password`](https://url.spec.whatwg.org/#dom-url-password)
+    fn get_password(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::get_component(this, quirks::password)
+    }
+
+    fn set_password(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::set_component(this, args, context, quirks::set_password)
+    }
+
+    /// [URL Standard § 6.1 `host`](https://url.spec.whatwg.org/#dom-url-host)
+    fn get_host(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::get_component(this, quirks::host)
+    }
+
+    fn set_host(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::set_component(this, args, context, quirks::set_host)
+    }
+
+    /// [URL Standard § 6.1 `hostname`](https://url.spec.whatwg.org/#dom-url-hostname)
+    fn get_hostname(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::get_component(this, quirks::hostname)
+    }
+
+    fn set_hostname(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::set_component(this, args, context, quirks::set_hostname)
+    }
+
+    /// [URL Standard § 6.1 `port`](https://url.spec.whatwg.org/#dom-url-port)
+    fn get_port(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::get_component(this, quirks::port)
+    }
+
+    fn set_port(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::set_component(this, args, context, quirks::set_port)
+    }
+
+    /// [URL Standard § 6.1 `pathname`](https://url.spec.whatwg.org/#dom-url-pathname)
+    fn get_pathname(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::get_component(this, quirks::pathname)
+    }
+
+    fn set_pathname(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::set_component(this, args, context, |url, value| {
+            quirks::set_pathname(url, value);
+            Ok::<_, ()>(())
+        })
+    }
+
+    /// [URL Standard § 6.1 `search`](https://url.spec.whatwg.org/#dom-url-search)
+    fn get_search(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::get_component(this, quirks::search)
+    }
+
+    /// [URL Standard § 6.1 `search` setter](https://url.spec.whatwg.org/#ref-for-dom-url-search②)
+    fn set_search(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::set_component(this, args, context, |url, value| {
+            quirks::set_search(url, value);
+            Ok::<_, ()>(())
+        })?;
+
+        // Set this's query object's list to the result of parsing input.
+        Self::with_url(this, Self::update_query_object)?;
+        Ok(JsValue::Undefined)
+    }
+
+    /// [URL Standard § 6.1 `searchParams`](https://url.spec.whatwg.org/#dom-url-searchparams),
+    /// which returns the same object every time.
+    fn get_search_params(this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let object = this.as_object()
+            .cloned()
+            .ok_or_else(|| JsError::from_opaque("Value is not `URL`".into()))?;
+
+        let (query_object, list) = Self::with_url(this, |url| {
+            let list = search_params::parse_query(url.url.borrow().query().unwrap_or_default());
+            (url.query_object.borrow().clone(), list)
+        })?;
+
+        if let Some(query_object) = query_object {
+            return Ok(query_object.into());
+        }
+
+        let query_object = UrlSearchParams::new_for_url(list, object).create_object(context)?;
+        Self::with_url(this, |url| *url.query_object.borrow_mut() = Some(query_object.clone()))?;
+        Ok(query_object.into())
+    }
+
+    /// [URL Standard § 6.1 `hash`](https://url.spec.whatwg.org/#dom-url-hash)
+    fn get_hash(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::get_component(this, quirks::hash)
+    }
+
+    fn set_hash(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::set_component(this, args, context, |url, value| {
+            quirks::set_hash(url, value);
+            Ok::<_, ()>(())
+        })
+    }
+
+    /// [URL Standard § 6.1 `canParse()`](https://url.spec.whatwg.org/#dom-url-canparse)
+    fn can_parse(_this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let url = args.first().cloned().unwrap_or_default();
+        Ok(Self::parse(&url, args.get(1), context).is_ok().into())
+    }
+
+    /// [File API § 8.3.1 `createObjectURL()`](https://w3c.github.io/FileAPI/#dfn-createObjectURL)
+    ///
+    /// TODO: the `Blob` isn't added to a blob URL store, which means the
+    /// returned URL can't be fetched.
+    fn create_object_url(_this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+        let is_blob = args.first()
+            .and_then(JsValue::as_object)
+            .is_some_and(|object| object.downcast_ref::<Blob>().is_some());
+        if !is_blob {
+            return Err(JsNativeError::typ().with_message("The object is not a `Blob`").into());
+        }
+
+        // 2. Let serialized be the ASCII serialization of environment's
+        //    origin.
+        let serialized = context.with_window(|window| window.url().origin().ascii_serialization());
+
+        // 5. Append the result of generating a UUID as a string to result.
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        Ok(JsString::from(format!("blob:{serialized}/{id}")).into())
+    }
+
+    /// [File API § 8.3.2 `revokeObjectURL()`](https://w3c.github.io/FileAPI/#dfn-revokeObjectURL)
+    ///
+    /// TODO: this does nothing, since `createObjectURL()` doesn't add entries
+    /// to a blob URL store.
+    fn revoke_object_url(_this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Ok(JsValue::Undefined)
+    }
+}
+
+impl Class for Url {
+    const NAME: &'static str = "URL";
+    const LENGTH: usize = 1;
+    const ATTRIBUTES: Attribute = Attribute::empty();
+
+    /// [URL Standard § 6.1 `new URL(url, base)`](https://url.spec.whatwg.org/#dom-url-url)
+    fn constructor(_: &JsValue, args: &[JsValue], context: &mut Context<'_>) -> JsResult<Self> {
+        let url = Self::parse(&args.first().cloned().unwrap_or_default(), args.get(1), context)?;
+        Ok(Self::new(url))
+    }
+
+    fn init(class: &mut ClassBuilder<'_, '_>) -> JsResult<()> {
+        class.accessor_with_function_ptr("href", Some(Self::get_href), Some(Self::set_href), Attribute::all());
+        class.accessor_with_function_ptr("origin", Some(Self::get_origin), None, Attribute::all());
+        class.accessor_with_function_ptr("protocol", Some(Self::get_protocol), Some(Self::set_protocol), Attribute::all());
+        class.accessor_with_function_ptr("username", Some(Self::get_username), Some(Self::set_username), Attribute::all());
+        class.accessor_with_function_ptr("password", Some(Self::get_password), Some(Self::set_password), Attribute::all());
+        class.accessor_with_function_ptr("host", Some(Self::get_host), Some(Self::set_host), Attribute::all());
+        class.accessor_with_function_ptr("hostname", Some(Self::get_hostname), Some(Self::set_hostname), Attribute::all());
+        class.accessor_with_function_ptr("port", Some(Self::get_port), Some(Self::set_port), Attribute::all());
+        class.accessor_with_function_ptr("pathname", Some(Self::get_pathname), Some(Self::set_pathname), Attribute::all());
+        class.accessor_with_function_ptr("search", Some(Self::get_search), Some(Self::set_search), Attribute::all());
+        class.accessor_with_function_ptr("searchParams", Some(Self::get_search_params), None, Attribute::all());
+        class.accessor_with_function_ptr("hash", Some(Self::get_hash), Some(Self::set_hash), Attribute::all());
+
+        class.method("toJSON", 0, NativeFunction::from_fn_ptr(Self::get_href));
+        class.method("toString", 0, NativeFunction::from_fn_ptr(Self::get_href));
+
+        class.static_method("canParse", 1, NativeFunction::from_fn_ptr(Self::can_parse));
+        class.static_method("createObjectURL", 1, NativeFunction::from_fn_ptr(Self::create_object_url));
+        class.static_method("revokeObjectURL", 1, NativeFunction::from_fn_ptr(Self::revoke_object_url));
+        Ok(())
+    }
+}
@@ -0,0 +1,421 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use std::cell::RefCell;
+
+use boa_engine::prelude::*;
+
+use boa_engine::class::{Class, ClassBuilder};
+use boa_engine::object::{FunctionObjectBuilder, ObjectData};
+use boa_engine::object::builtins::JsArray;
+use boa_engine::property::Attribute;
+use boa_engine::symbol::JsSymbol;
+use boa_gc::{Finalize, GcRefCell, Trace, custom_trace};
+use retina_fetch::url::form_urlencoded;
+
+use crate::prelude::*;
+
+use super::Url;
+
+/// The [Interface `URLSearchParams`](https://url.spec.whatwg.org/#interface-urlsearchparams)
+/// platform object, which is the list of name-value pairs of a query.
+#[derive(Finalize)]
+pub struct UrlSearchParams {
+    list: RefCell<Vec<(String, String)>>,
+
+    /// The `URL` object whose query is updated when the list changes, if
+    /// this object is the `searchParams` of a `URL`.
+    url_object: GcRefCell<Option<JsObject>>,
+}
+
+unsafe impl Trace for UrlSearchParams {
+    custom_trace!(this, {
+        mark(&this.url_object);
+    });
+}
+
+/// Get the well-known `Symbol.iterator`, which isn't exposed by the engine.
+fn symbol_iterator(context: &mut Context) -> JsResult<JsSymbol> {
+    context.intrinsics().constructors().symbol().constructor()
+        .get("iterator", context)?
+        .as_symbol()
+        .ok_or_else(|| JsNativeError::typ().with_message("`Symbol.iterator` is not a symbol").into())
+}
+
+/// Parse the `query` using the [`application/x-www-form-urlencoded` parser](https://url.spec.whatwg.org/#concept-urlencoded-parser).
+pub(super) fn parse_query(query: &str) -> Vec<(String, String)> {
+    form_urlencoded::parse(query.as_bytes())
+        .into_owned()
+        .collect()
+}
+
+impl UrlSearchParams {
+    pub fn new(list: Vec<(String, String)>) -> Self {
+        Self {
+            list: RefCell::new(list),
+            url_object: GcRefCell::new(None),
+        }
+    }
+
+    pub(super) fn new_for_url(list: Vec<(String, String)>, url_object: JsObject) -> Self {
+        Self {
+            list: RefCell::new(list),
+            url_object: GcRefCell::new(Some(url_object)),
+        }
+    }
+
+    pub fn create_object(self, context: &mut Context) -> JsResult<JsObject> {
+        Ok(JsObject::from_proto_and_data(
+            Some(class_prototype::<Self>(context)?),
+            ObjectData::native_object(self),
+        ))
+    }
+
+    /// Replace the list, when the query of the associated `URL` changed.
+    pub(super) fn set_list(&self, list: Vec<(String, String)>) {
+        *self.list.borrow_mut() = list;
+    }
+
+    /// The [`application/x-www-form-urlencoded` serializer](https://url.spec.whatwg.org/#concept-urlencoded-serializer)
+    /// of the list.
+    fn serialize(&self) -> String {
+        form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(self.list.borrow().iter())
+            .finish()
+    }
+
+    /// [Update](https://url.spec.whatwg.org/#concept-urlsearchparams-update)
+    /// the query of the associated `URL`.
+    fn update(&self) {
+        // 1. If query's URL object is null, then return.
+        let Some(url_object) = self.url_object.borrow().clone() else {
+            return;
+        };
+
+        // 2. Let serializedQuery be the serialization of query's list.
+        // 3. If serializedQuery is the empty string, then set serializedQuery
+        //    to null.
+        let serialized_query = self.serialize();
+        let serialized_query = (!serialized_query.is_empty()).then_some(serialized_query);
+
+        // 4. Set query's URL object's URL's query to serializedQuery.
+        if let Some(url) = url_object.downcast_ref::<Url>() {
+            url.set_query(serialized_query.as_deref());
+        };
+    }
+
+    fn with_params<T>(this: &JsValue, f: impl FnOnce(&Self) -> T) -> JsResult<T> {
+        let this = this.as_object()
+            .and_then(|obj| obj.downcast_ref::<Self>())
+            .ok_or_else(|| JsError::from_opaque("Value is not `URLSearchParams`".into()))?;
+        Ok(f(&this))
+    }
+
+    fn string_argument(args: &[JsValue], index: usize, context: &mut Context) -> JsResult<String> {
+        Ok(args.get(index).cloned().unwrap_or_default()
+            .to_string(context)?
+            .to_std_string_escaped())
+    }
+
+    /// The optional `value` argument of `delete()` and `has()`.
+    fn optional_value_argument(args: &[JsValue], context: &mut Context) -> JsResult<Option<String>> {
+        match args.get(1) {
+            None | Some(JsValue::Undefined) => Ok(None),
+            Some(value) => Ok(Some(value.to_string(context)?.to_std_string_escaped())),
+        }
+    }
+
+    /// Convert the `init` argument of the constructor, which is either a
+    /// sequence of pairs, a record, or a string.
+    fn convert_init(init: &JsValue, context: &mut Context) -> JsResult<Vec<(String, String)>> {
+        let Some(object) = init.as_object() else {
+            // 1. If init is a string and starts with U+003F (?), then remove
+            //    the first code point from init.
+            if init.is_undefined() {
+                return Ok(Vec::new());
+            }
+
+            let init = init.to_string(context)?.to_std_string_escaped();
+            let init = init.strip_prefix('?').unwrap_or(&init);
+            return Ok(parse_query(init));
+        };
+
+        // If init is a sequence, then for each innerSequence of init:
+        let iterator = object.get(symbol_iterator(context)?, context)?;
+        if iterator.is_callable() {
+            let array_from = context.intrinsics().constructors().array().constructor().get("from", context)?;
+            let array_from = array_from.as_callable()
+                .ok_or_else(|| JsNativeError::typ().with_message("`Array.from` is not callable"))?;
+            let sequence = array_from.call(&JsValue::Undefined, std::slice::from_ref(init), context)?;
+            return Self::convert_sequence(&sequence, context);
+        }
+
+        // Otherwise, if init is a record, then for each name → value of init,
+        // append (name, value) to query's list.
+        let object_entries = context.intrinsics().constructors().object().constructor().get("entries", context)?;
+        let object_entries = object_entries.as_callable()
+            .ok_or_else(|| JsNativeError::typ().with_message("`Object.entries` is not callable"))?;
+        let entries = object_entries.call(&JsValue::Undefined, std::slice::from_ref(init), context)?;
+        Self::convert_sequence(&entries, context)
+    }
+
+    fn convert_sequence(sequence: &JsValue, context: &mut Context) -> JsResult<Vec<(String, String)>> {
+        let sequence = sequence.as_object()
+            .cloned()
+            .and_then(|object| JsArray::from_object(object).ok())
+            .ok_or_else(|| JsNativeError::typ().with_message("The init is not a sequence"))?;
+
+        let mut list = Vec::new();
+        for index in 0..sequence.length(context)? {
+            let pair = sequence.get(index, context)?;
+            let pair = pair.as_object()
+                .cloned()
+                .and_then(|object| JsArray::from_object(object).ok())
+                .ok_or_else(|| JsNativeError::typ().with_message("The pair is not a sequence"))?;
+
+            // 1. If innerSequence's size is not 2, then throw a TypeError.
+            if pair.length(context)? != 2 {
+                return Err(JsNativeError::typ().with_message("The pair doesn't contain exactly two items").into());
+            }
+
+            // 2. Append (innerSequence[0], innerSequence[1]) to query's list.
+            let name = pair.get(0, context)?.to_string(context)?.to_std_string_escaped();
+            let value = pair.get(1, context)?.to_string(context)?.to_std_string_escaped();
+            list.push((name, value));
+        }
+        Ok(list)
+    }
+
+    /// [URL Standard § 6.2 `size`](https://url.spec.whatwg.org/#dom-urlsearchparams-size)
+    fn get_size(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_params(this, |params| JsValue::new(params.list.borrow().len()))
+    }
+
+    /// [URL Standard § 6.2 `append()`](https://url.spec.whatwg.org/#dom-urlsearchparams-append)
+    fn append(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let name = Self::string_argument(args, 0, context)?;
+        let value = Self::string_argument(args, 1, context)?;
+
+        Self::with_params(this, |params| {
+            params.list.borrow_mut().push((name, value));
+            params.update();
+        })?;
+        Ok(JsValue::Undefined)
+    }
+
+    /// [URL Standard § 6.2 `delete()`](https://url.spec.whatwg.org/#dom-urlsearchparams-delete)
+    fn delete(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let name = Self::string_argument(args, 0, context)?;
+        let value = Self::optional_value_argument(args, context)?;
+
+        Self::with_params(this, |params| {
+            // 1. If value is given, then remove all tuples whose name is name
+            //    and value is value from this's list.
+            // 2. Otherwise, remove all tuples whose name is name from this's
+            //    list.
+            params.list.borrow_mut().retain(|(n, v)| {
+                *n != name || value.as_ref().is_some_and(|value| v != value)
+            });
+
+            // 3. Update this.
+            params.update();
+        })?;
+        Ok(JsValue::Undefined)
+    }
+
+    /// [URL Standard § 6.2 `get()`](https://url.spec.whatwg.org/#dom-urlsearchparams-get)
+    fn get(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let name = Self::string_argument(args, 0, context)?;
+
+        Self::with_params(this, |params| {
+            params.list.borrow()
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map_or(JsValue::Null, |(_, value)| JsString::from(value.as_str()).into())
+        })
+    }
+
+    /// [URL Standard § 6.2 `getAll()`](https://url.spec.whatwg.org/#dom-urlsearchparams-getall)
+    fn get_all(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let name = Self::string_argument(args, 0, context)?;
+
+        let values: Vec<JsValue> = Self::with_params(this, |params| {
+            params.list.borrow()
+                .iter()
+                .filter(|(n, _)| *n == name)
+                .map(|(_, value)| JsString::from(value.as_str()).into())
+                .collect()
+        })?;
+        Ok(JsArray::from_iter(values, context).into())
+    }
+
+    /// [URL Standard § 6.2 `has()`](https://url.spec.whatwg.org/#dom-urlsearchparams-has)
+    fn has(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let name = Self::string_argument(args, 0, context)?;
+        let value = Self::optional_value_argument(args, context)?;
+
+        Self::with_params(this, |params| {
+            params.list.borrow()
+                .iter()
+                .any(|(n, v)| *n == name && value.as_ref().is_none_or(|value| v == value))
+                .into()
+        })
+    }
+
+    /// [URL Standard § 6.2 `set()`](https://url.spec.whatwg.org/#dom-urlsearchparams-set)
+    fn set(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let name = Self::string_argument(args, 0, context)?;
+        let value = Self::string_argument(args, 1, context)?;
+
+        Self::with_params(this, |params| {
+            let mut list = params.list.borrow_mut();
+
+            // 1. If this's list contains a tuple whose name is name, then set
+            //    the value of the first such tuple to value and remove the
+            //    others.
+            if let Some(index) = list.iter().position(|(n, _)| *n == name) {
+                list[index].1 = value;
+
+                let mut position = 0;
+                list.retain(|(n, _)| {
+                    let keep = *n != name || position == index;
+                    position += 1;
+                    keep
+                });
+            } else {
+                // 2. Otherwise, append (name, value) to this's list.
+                list.push((name, value));
+            }
+
+            drop(list);
+
+            // 3. Update this.
+            params.update();
+        })?;
+        Ok(JsValue::Undefined)
+    }
+
+    /// [URL Standard § 6.2 `sort()`](https://url.spec.whatwg.org/#dom-urlsearchparams-sort)
+    fn sort(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_params(this, |params| {
+            // 1. Sort all tuples in this's list, if any, by their names. Sorting
+            //    must be done by comparison of code units. The relative order
+            //    between tuples with equal names must be preserved.
+            params.list.borrow_mut().sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+
+            // 2. Update this.
+            params.update();
+        })?;
+        Ok(JsValue::Undefined)
+    }
+
+    /// [URL Standard § 6.2 stringification behavior](https://url.spec.whatwg.org/#urlsearchparams-stringification-behavior)
+    fn to_string(this: &JsValue, _args: &[JsValue], _context: &mut Context) -> JsResult<JsValue> {
+        Self::with_params(this, |params| JsString::from(params.serialize()).into())
+    }
+
+    /// [URL Standard § 6.2 `forEach()`](https://webidl.spec.whatwg.org/#es-forEach)
+    fn for_each(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let callback = args.first()
+            .and_then(JsValue::as_callable)
+            .cloned()
+            .ok_or_else(|| JsNativeError::typ().with_message("The callback is not a function"))?;
+        let this_arg = args.get(1).cloned().unwrap_or_default();
+
+        // The list is read again after every callback, since the callback
+        // might modify it.
+        let mut index = 0;
+        while let Some((name, value)) = Self::with_params(this, |params| params.list.borrow().get(index).cloned())? {
+            let args = [JsString::from(value).into(), JsString::from(name).into(), this.clone()];
+            callback.call(&this_arg, &args, context)?;
+            index += 1;
+        }
+
+        Ok(JsValue::Undefined)
+    }
+
+    /// Create an iterator over the `values`.
+    ///
+    /// TODO: the iterator should reflect the changes made to the list whilst
+    /// iterating, instead of iterating over a snapshot.
+    fn create_iterator(values: Vec<JsValue>, context: &mut Context) -> JsResult<JsValue> {
+        let array = JsArray::from_iter(values, context);
+        let values = array.get("values", context)?;
+        let values = values.as_callable()
+            .ok_or_else(|| JsNativeError::typ().with_message("`Array.prototype.values` is not callable"))?;
+        values.call(&array.into(), &[], context)
+    }
+
+    /// [WebIDL § 3.7.10.2 `entries()`](https://webidl.spec.whatwg.org/#es-iterable-entries)
+    fn entries(this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let list = Self::with_params(this, |params| params.list.borrow().clone())?;
+        let entries = list.into_iter()
+            .map(|(name, value)| {
+                let pair = [JsString::from(name).into(), JsString::from(value).into()];
+                JsArray::from_iter(pair, context).into()
+            })
+            .collect();
+        Self::create_iterator(entries, context)
+    }
+
+    /// [WebIDL § 3.7.10.3 `keys()`](https://webidl.spec.whatwg.org/#es-iterable-keys)
+    fn keys(this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let keys = Self::with_params(this, |params| {
+            params.list.borrow()
+                .iter()
+                .map(|(name, _)| JsString::from(name.as_str()).into())
+                .collect()
+        })?;
+        Self::create_iterator(keys, context)
+    }
+
+    /// [WebIDL § 3.7.10.4 `values()`](https://webidl.spec.whatwg.org/#es-iterable-values)
+    fn values(this: &JsValue, _args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let values = Self::with_params(this, |params| {
+            params.list.borrow()
+                .iter()
+                .map(|(_, value)| JsString::from(value.as_str()).into())
+                .collect()
+        })?;
+        Self::create_iterator(values, context)
+    }
+}
+
+impl Class for UrlSearchParams {
+    const NAME: &'static str = "URLSearchParams";
+    const LENGTH: usize = 0;
+    const ATTRIBUTES: Attribute = Attribute::empty();
+
+    /// [URL Standard § 6.2 `new URLSearchParams(init)`](https://url.spec.whatwg.org/#dom-urlsearchparams-urlsearchparams)
+    fn constructor(_: &JsValue, args: &[JsValue], context: &mut Context<'_>) -> JsResult<Self> {
+        let list = Self::convert_init(&args.first().cloned().unwrap_or_default(), context)?;
+        Ok(Self::new(list))
+    }
+
+    fn init(class: &mut ClassBuilder<'_, '_>) -> JsResult<()> {
+        class.accessor_with_function_ptr("size", Some(Self::get_size), None, Attribute::all());
+
+        class.method("append", 2, NativeFunction::from_fn_ptr(Self::append));
+        class.method("delete", 1, NativeFunction::from_fn_ptr(Self::delete));
+        class.method("get", 1, NativeFunction::from_fn_ptr(Self::get));
+        class.method("getAll", 1, NativeFunction::from_fn_ptr(Self::get_all));
+        class.method("has", 1, NativeFunction::from_fn_ptr(Self::has));
+        class.method("set", 2, NativeFunction::from_fn_ptr(Self::set));
+        class.method("sort", 0, NativeFunction::from_fn_ptr(Self::sort));
+        class.method("toString", 0, NativeFunction::from_fn_ptr(Self::to_string));
+
+        class.method("forEach", 1, NativeFunction::from_fn_ptr(Self::for_each));
+        class.method("keys", 0, NativeFunction::from_fn_ptr(Self::keys));
+        class.method("values", 0, NativeFunction::from_fn_ptr(Self::values));
+
+        // The `@@iterator` method is the same function object as `entries()`.
+        let symbol_iterator = symbol_iterator(class.context())?;
+        let entries = FunctionObjectBuilder::new(class.context(), NativeFunction::from_fn_ptr(Self::entries))
+            .name("entries")
+            .length(0)
+            .build();
+        class.property("entries", entries.clone(), Attribute::all());
+        class.property(symbol_iterator, entries, Attribute::WRITABLE | Attribute::CONFIGURABLE);
+        Ok(())
+    }
+}
@@ -1,21 +1,32 @@
 // Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
 // All Rights Reserved.
 
+use std::time::Instant;
+
 use boa_engine::context::intrinsics::Intrinsics;
 use boa_engine::object::ObjectData;
 use boa_engine::prelude::*;
 use boa_engine::context::HostHooks;
-use retina_dom::Node;
+use retina_dom::{event::queue::EventQueue, Node};
+use retina_fetch::{Fetch, Url};
 use retina_platform_object::nav_history::Window;
 
 pub struct ScritturaHostHooks {
     document: Node,
+    url: Url,
+    event_queue: EventQueue,
+    fetch: Fetch,
+    time_origin: Instant,
 }
 
 impl ScritturaHostHooks {
-    pub fn new(document: Node) -> Self {
+    pub fn new(document: Node, url: Url, event_queue: EventQueue, fetch: Fetch, time_origin: Instant) -> Self {
         Self {
             document,
+            url,
+            event_queue,
+            fetch,
+            time_origin,
         }
     }
 }
@@ -26,7 +37,13 @@ impl ScritturaHostHooks {
             // TODO: `WindowPrototype`
             intrinsics.constructors().object().prototype(),
 
-            ObjectData::native_object(Window::new(self.document.clone()))
+            ObjectData::native_object(Window::new(
+                self.document.clone(),
+                self.url.clone(),
+                self.event_queue.clone(),
+                self.fetch.clone(),
+                self.time_origin,
+            ))
         )
     }
 }
@@ -1,19 +1,33 @@
 // Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
 // All Rights Reserved.
 
-use boa_engine::job::JobQueue;
-use retina_dom::event::queue::EventQueue;
+use std::{cell::RefCell, collections::VecDeque};
+
+use boa_engine::job::{JobQueue, NativeJob};
+use log::error;
+use retina_dom::event::queue::{EventQueue, Microtask};
+use retina_platform_object::dom::MutationObserver;
 
 pub struct ScritturaJobQueue {
     event_queue: EventQueue,
+
+    /// The reaction jobs of settled promises, which are microtasks as well,
+    /// but can't be sent to the [`EventQueue`], since they refer to
+    /// JavaScript values.
+    promise_jobs: RefCell<VecDeque<NativeJob>>,
 }
 
 impl ScritturaJobQueue {
     pub fn new(event_queue: EventQueue) -> Self {
         Self {
             event_queue,
+            promise_jobs: RefCell::new(VecDeque::new()),
         }
     }
+
+    fn pop_promise_job(&self) -> Option<NativeJob> {
+        self.promise_jobs.borrow_mut().pop_front()
+    }
 }
 
 impl JobQueue for ScritturaJobQueue {
@@ -23,14 +37,29 @@ impl JobQueue for ScritturaJobQueue {
         todo!();
     }
 
-    fn enqueue_promise_job(&self, job: boa_engine::job::NativeJob, context: &mut boa_engine::Context<'_>) {
-        _ = job;
-        _ = context;
-        todo!();
+    fn enqueue_promise_job(&self, job: NativeJob, _context: &mut boa_engine::Context<'_>) {
+        self.promise_jobs.borrow_mut().push_back(job);
     }
 
+    /// [Perform a microtask checkpoint](https://html.spec.whatwg.org/multipage/webappapis.html#perform-a-microtask-checkpoint),
+    /// which runs until both the promise jobs and the other microtasks are
+    /// exhausted, since they can queue each other.
     fn run_jobs(&self, context: &mut boa_engine::Context<'_>) {
-        _ = context;
-        _ = self.event_queue;
+        loop {
+            if let Some(job) = self.pop_promise_job() {
+                if let Err(e) = job.call(context) {
+                    error!("[job-queue] Uncaught exception in promise job: {e}");
+                }
+                continue;
+            }
+
+            let Some(microtask) = self.event_queue.pop_microtask() else {
+                break;
+            };
+
+            match microtask {
+                Microtask::NotifyMutationObservers => MutationObserver::notify_mutation_observers(context),
+            }
+        }
     }
 }
@@ -7,14 +7,29 @@ mod job_queue;
 pub mod prelude;
 pub mod test_helper;
 
-use std::rc::Rc;
+use std::{
+    rc::Rc,
+    sync::{Arc, RwLock},
+    time::Instant,
+};
 
 use boa_engine::{prelude::*, context::{MaybeShared, HostHooks}, job::JobQueue};
 use retina_dom::{
     event::queue::EventQueue,
     Node,
 };
-use retina_platform_object::nav_history::Window;
+use retina_fetch::{Fetch, Url};
+use retina_platform_object::{
+    nav_history::Window,
+    prelude::ContextExt,
+    storage::Storage,
+};
+use retina_user_agent::{
+    permission::{Origin, PermissionStore},
+    storage::LocalStorage,
+};
+
+pub use retina_platform_object::nav_history::{is_fragment_navigation, HistoryHandling, NavigationRequest};
 
 use self::{
     host_hooks::ScritturaHostHooks,
@@ -28,9 +43,22 @@ pub struct BrowsingContext {
 impl BrowsingContext {
     pub fn new(
         document: Node,
+        url: Url,
         event_queue: EventQueue,
+        fetch: Fetch,
+        permission_store: Arc<RwLock<PermissionStore>>,
+        local_storage: Arc<RwLock<LocalStorage>>,
+        time_origin: Instant,
     ) -> Self {
-        let hooks: Rc<dyn HostHooks> = Rc::new(ScritturaHostHooks::new(document.clone()));
+        let origin: Origin = url.origin().ascii_serialization();
+
+        let hooks: Rc<dyn HostHooks> = Rc::new(ScritturaHostHooks::new(
+            document.clone(),
+            url,
+            event_queue.clone(),
+            fetch,
+            time_origin,
+        ));
         let hooks = MaybeShared::Shared(hooks);
 
         let job_queue: Rc<dyn JobQueue> = Rc::new(ScritturaJobQueue::new(event_queue));
@@ -45,7 +73,7 @@ impl BrowsingContext {
         retina_platform_object::register_all(&mut context)
             .expect("failed to register platform objects");
 
-        Window::initialize_global(&mut context, document)
+        Window::initialize_global(&mut context, document, permission_store, local_storage, origin)
             .expect("failed to register global properties and methods of `Window`");
 
         Self {
@@ -69,7 +97,87 @@ impl BrowsingContext {
         )).expect("failed to register `assert`");
     }
 
+    /// Runs the script, followed by a [microtask checkpoint][spec].
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/webappapis.html#perform-a-microtask-checkpoint
     pub fn run_script_from_string_source(&mut self, source: &str) -> Result<JsValue, JsError> {
-        self.context.eval(Source::from_bytes(source))
+        let result = self.context.eval(Source::from_bytes(source));
+        self.context.run_jobs();
+        result
+    }
+
+    /// Runs the `setTimeout()` and `setInterval()` callbacks that expired,
+    /// returning the number of callbacks that ran.
+    pub fn run_expired_timers(&mut self) -> usize {
+        retina_platform_object::timers::run_expired_timers(&mut self.context)
+    }
+
+    /// Settles the promises of the `fetch()` requests and fires the events of
+    /// the `XMLHttpRequest`s that completed in the background, returning the
+    /// number of requests that completed.
+    pub fn run_completed_fetches(&mut self) -> usize {
+        retina_platform_object::fetch::run_completed_fetches(&mut self.context)
+    }
+
+    /// Fires the `storage` events for the changes to `localStorage` made by
+    /// other browsing contexts, returning the number of events fired.
+    pub fn run_storage_notifications(&mut self) -> usize {
+        retina_platform_object::storage::run_storage_notifications(&mut self.context)
+    }
+
+    /// Invokes the `PerformanceObserver` callbacks with the marks and
+    /// measures that were recorded, returning the number of callbacks that
+    /// ran.
+    pub fn run_performance_observer_task(&mut self) -> usize {
+        retina_platform_object::performance::run_performance_observer_task(&mut self.context)
+    }
+
+    /// Takes the navigation that a script requested, e.g. using
+    /// `location.assign()`, which the page must perform.
+    pub fn take_navigation_request(&self) -> Option<NavigationRequest> {
+        self.context.with_window(Window::take_navigation_request)
+    }
+
+    /// Whether there are `requestAnimationFrame()` callbacks waiting for the
+    /// next frame.
+    pub fn has_animation_frame_callbacks(&self) -> bool {
+        self.context.with_window(|window| !window.animation_frame_callbacks().borrow().is_empty())
+    }
+
+    /// Runs the `requestAnimationFrame()` callbacks with the timestamp of
+    /// `now`, which must happen before the frame is rendered. Returns the
+    /// number of callbacks that ran.
+    pub fn run_animation_frame_callbacks(&mut self, now: Instant) -> usize {
+        retina_platform_object::animation_frames::run_animation_frame_callbacks(now, &mut self.context)
+    }
+
+    /// Runs the script and serializes its completion value to JSON. Errors
+    /// are converted to their string representation.
+    pub fn run_script_from_string_source_to_json(&mut self, source: &str) -> Result<String, String> {
+        let value = self.run_script_from_string_source(source)
+            .map_err(|e| e.to_string())?;
+
+        if value.is_undefined() {
+            return Ok(String::from("null"));
+        }
+
+        value.to_json(&mut self.context)
+            .map(|json| json.to_string())
+            .map_err(|e| e.to_string())
+    }
+}
+
+impl Drop for BrowsingContext {
+    fn drop(&mut self) {
+        // The objects of the context aren't necessarily collected, so the
+        // `localStorage` would otherwise still receive the changes of other
+        // browsing contexts.
+        let Some(local_storage) = self.context.with_window(|window| window.local_storage()) else {
+            return;
+        };
+
+        if let Some(storage) = local_storage.downcast_ref::<Storage>() {
+            storage.unsubscribe();
+        };
     }
 }
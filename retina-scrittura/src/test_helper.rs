@@ -1,15 +1,36 @@
 // Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
 // All Rights Reserved.
 
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
+
 use retina_dom::event::queue::EventQueue;
+use retina_fetch::{Fetch, Url};
+use retina_user_agent::{
+    permission::PermissionStore,
+    storage::LocalStorage,
+};
 use crate::BrowsingContext;
 
 /// Create a simple [`BrowsingContext`] from the `test/html/empty/index.html`
 /// document in the repository root.
 pub fn create_simple_context_and_document() -> BrowsingContext {
     let document = retina_dom::Parser::parse(include_str!("../../test/html/empty/index.html"));
+    let url = Url::from_directory_path(env!("CARGO_MANIFEST_DIR"))
+        .and_then(|directory| directory.join("../test/html/empty/index.html").map_err(|_| ()))
+        .expect("the manifest directory should be an absolute path");
 
-    let mut context = BrowsingContext::new(document, EventQueue::new());
+    let permission_store = Arc::new(RwLock::new(PermissionStore::new_in_memory()));
+    let local_storage = Arc::new(RwLock::new(LocalStorage::new_in_memory()));
+    let mut context = BrowsingContext::new(
+        document,
+        url,
+        EventQueue::new(),
+        Fetch::new(),
+        permission_store,
+        local_storage,
+        Instant::now(),
+    );
     context.attach_assertion_module();
 
     context
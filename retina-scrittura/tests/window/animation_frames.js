@@ -0,0 +1,23 @@
+assert(typeof requestAnimationFrame === "function", "requestAnimationFrame is not a function");
+assert(typeof cancelAnimationFrame === "function", "cancelAnimationFrame is not a function");
+
+let ran = false;
+const first = requestAnimationFrame(() => ran = true);
+const second = requestAnimationFrame(() => {});
+assert(Number.isInteger(first) && first > 0, `the handle isn't a positive integer: ${first}`);
+assert(second > first, `the handles aren't increasing: ${first}, ${second}`);
+
+// Callbacks only run before the next frame is painted.
+assert(!ran, "the callback ran synchronously");
+
+cancelAnimationFrame(second);
+cancelAnimationFrame(12345);
+cancelAnimationFrame();
+
+let threw = false;
+try {
+    requestAnimationFrame("not a function");
+} catch (e) {
+    threw = e instanceof TypeError;
+}
+assert(threw, "requestAnimationFrame didn't throw a TypeError for a non-function");
@@ -0,0 +1,64 @@
+document.querySelector("body").insertAdjacentHTML("beforeend", `<canvas id="canvas" width="40" height="20"></canvas>`);
+
+const canvas = document.getElementById("canvas");
+assert(canvas instanceof HTMLCanvasElement, "canvas is not an HTMLCanvasElement");
+assert(canvas instanceof HTMLElement, "canvas is not an HTMLElement");
+assert(canvas.width === 40, `canvas.width is not 40: ${canvas.width}`);
+assert(canvas.height === 20, `canvas.height is not 20: ${canvas.height}`);
+assert(canvas.getContext("webgl") === null, "canvas.getContext(\"webgl\") is not null");
+
+const context = canvas.getContext("2d");
+assert(context instanceof CanvasRenderingContext2D, "context is not a CanvasRenderingContext2D");
+assert(context.canvas instanceof HTMLCanvasElement, "context.canvas is not an HTMLCanvasElement");
+
+assert(context.fillStyle === "#000000", `fillStyle is not black: ${context.fillStyle}`);
+context.fillStyle = "red";
+assert(context.fillStyle === "#ff0000", `fillStyle is not red: ${context.fillStyle}`);
+context.fillStyle = "not a color";
+assert(context.fillStyle === "#ff0000", `invalid fillStyle wasn't ignored: ${context.fillStyle}`);
+context.strokeStyle = "rgba(0, 0, 255, 0.5)";
+assert(context.strokeStyle === "rgba(0, 0, 255, 0.5)", `strokeStyle is wrong: ${context.strokeStyle}`);
+
+assert(context.lineWidth === 1, `lineWidth is not 1: ${context.lineWidth}`);
+context.lineWidth = 2.5;
+context.lineWidth = -1;
+assert(context.lineWidth === 2.5, `lineWidth is not 2.5: ${context.lineWidth}`);
+
+assert(context.font === "10px sans-serif", `font is not the default: ${context.font}`);
+context.font = "bold 16px serif";
+context.font = "invalid";
+assert(context.font === "bold 16px serif", `font is wrong: ${context.font}`);
+
+context.save();
+context.fillStyle = "blue";
+context.restore();
+assert(context.fillStyle === "#ff0000", `fillStyle wasn't restored: ${context.fillStyle}`);
+
+context.fillRect(0, 0, 10, 10);
+context.strokeRect(10, 0, 10, 10);
+context.clearRect(0, 0, 5, 5);
+context.translate(5, 5);
+context.rotate(Math.PI / 4);
+context.scale(2, 2);
+context.transform(1, 0, 0, 1, 0, 0);
+context.beginPath();
+context.moveTo(0, 0);
+context.lineTo(5, 0);
+context.arc(0, 0, 5, 0, Math.PI, true);
+context.closePath();
+context.fill();
+context.fill("evenodd");
+context.stroke();
+context.fillText("Hello", 0, 10);
+context.strokeText("World", 0, 10, 100);
+context.drawImage(canvas, 0, 0);
+context.drawImage(canvas, 0, 0, 10, 10);
+context.drawImage(canvas, 0, 0, 5, 5, 0, 0, 10, 10);
+
+let threw = false;
+try {
+    context.arc(0, 0, -1, 0, 1);
+} catch {
+    threw = true;
+}
+assert(threw, "arc() with a negative radius didn't throw");
@@ -0,0 +1,9 @@
+assert(typeof navigator.clipboard === "object", `navigator.clipboard is not an object: ${typeof navigator.clipboard}`);
+assert(navigator.clipboard.constructor.name == "Clipboard", `navigator.clipboard.constructor.name is not 'Clipboard': '${navigator.clipboard.constructor.name}'`);
+
+assert(typeof navigator.clipboard.readText === "function", "navigator.clipboard.readText is not a function");
+assert(typeof navigator.clipboard.writeText === "function", "navigator.clipboard.writeText is not a function");
+
+// The system clipboard might not be available, in which case the promise is
+// rejected, but it must be a promise regardless.
+assert(navigator.clipboard.readText() instanceof Promise, "navigator.clipboard.readText() did not return a Promise");
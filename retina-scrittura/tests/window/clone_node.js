@@ -0,0 +1,16 @@
+const body = document.querySelector("body");
+body.insertAdjacentHTML("beforeend", `<div id="original" class="box"><p>Hello <b>world</b></p></div>`);
+
+const original = document.getElementById("original");
+
+const shallow = original.cloneNode();
+assert(shallow !== original, "the shallow clone is the original");
+assert(shallow.innerHTML === "", `the shallow clone has children: ${shallow.innerHTML}`);
+assert(shallow.matches("div#original.box"), "the attributes weren't cloned");
+
+const deep = original.cloneNode(true);
+assert(deep.innerHTML === original.innerHTML, `the deep clone has different children: ${deep.innerHTML}`);
+assert(deep.closest("body") === null, "the clone has a parent");
+
+deep.innerHTML = "changed";
+assert(original.innerHTML === "<p>Hello <b>world</b></p>", `the original was changed: ${original.innerHTML}`);
@@ -0,0 +1,53 @@
+assert(typeof console === "object", `console is not an object: ${typeof console}`);
+
+const methods = [
+    "assert", "count", "countReset", "debug", "error", "group", "groupCollapsed", "groupEnd",
+    "info", "log", "table", "time", "timeEnd", "timeLog", "warn",
+];
+for (const method of methods) {
+    assert(typeof console[method] === "function", `console.${method} is not a function`);
+}
+
+assert(console.log("%s is %d years and %f%% done %o %c", "retina", 1.5, 99.5, { a: 1 }, "color: red") === undefined, "log() returned a value");
+assert(console.info() === undefined, "info() without arguments returned a value");
+console.debug("debug", 1, true, null, undefined, Symbol("s"), [1, 2]);
+console.warn("%d", "42px");
+console.error(new Error("an error"));
+
+console.assert(true, "not printed");
+console.assert(false);
+console.assert(false, "with %s", "substitution");
+console.assert(false, { a: 1 });
+
+console.group("group");
+console.groupCollapsed();
+console.log("nested");
+console.groupEnd();
+console.groupEnd();
+console.groupEnd();
+
+console.count();
+console.count("label");
+console.countReset("label");
+console.countReset("missing");
+
+console.time();
+console.time();
+console.timeLog(undefined, "data");
+console.timeEnd();
+console.timeEnd();
+
+console.table([{ a: 1, b: "x" }, { b: 2, c: 3 }, 4]);
+console.table({ first: { a: 1 }, second: { a: 2, b: 3 } }, ["a"]);
+console.table("not tabular");
+
+// The methods can be called without a `this` value.
+const { log } = console;
+log("called without this");
+[1, 2].forEach(console.log);
+
+// The property is non-writable and non-configurable.
+const original = console;
+window.console = null;
+assert(console === original, "console was overwritten");
+assert(delete window.console === false, "console was deleted");
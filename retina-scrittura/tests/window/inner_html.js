@@ -0,0 +1,38 @@
+const body = document.querySelector("body");
+body.insertAdjacentHTML("beforeend", `<div id="container"><p class="greeting">Hello &amp; <b>welcome</b></p><br></div>`);
+
+const container = document.getElementById("container");
+assert(typeof container.innerHTML === "string", `innerHTML is not a string: ${typeof container.innerHTML}`);
+assert(container.innerHTML === `<p class="greeting">Hello &amp; <b>welcome</b></p><br>`, `innerHTML is wrong: ${container.innerHTML}`);
+
+container.innerHTML = `<span id="replaced">1 < 2</span><img src="x.png">`;
+assert(container.innerHTML === `<span id="replaced">1 &lt; 2</span><img src="x.png">`, `innerHTML wasn't replaced: ${container.innerHTML}`);
+assert(document.getElementById("replaced") !== null, "the new element isn't in the document");
+assert(document.querySelector(".greeting") === null, "the old element is still in the document");
+
+// No <html>, <head> or <body> elements are created for the fragment.
+container.innerHTML = "<html><body>text</body></html>";
+assert(container.innerHTML === "text", `the fragment wasn't parsed in context: ${container.innerHTML}`);
+
+container.innerHTML = "";
+assert(container.innerHTML === "", `innerHTML isn't empty: ${container.innerHTML}`);
+
+const observer = new MutationObserver(() => {});
+observer.observe(container, { childList: true });
+container.innerHTML = "<i></i><i></i>";
+const records = observer.takeRecords();
+assert(records.length === 1, `expected a single record, got ${records.length}`);
+assert(records[0].addedNodes.length === 2, `expected 2 added nodes, got ${records[0].addedNodes.length}`);
+
+function insertAdjacentHTMLError(element, position) {
+    try {
+        element.insertAdjacentHTML(position, "<b></b>");
+    } catch (e) {
+        return e.name;
+    }
+    return null;
+}
+
+assert(insertAdjacentHTMLError(container, "middle") === "SyntaxError", "an invalid position isn't a SyntaxError");
+const html = document.querySelector("html");
+assert(insertAdjacentHTMLError(html, "afterend") === "NoModificationAllowedError", "inserting next to the root isn't a NoModificationAllowedError");
@@ -0,0 +1,47 @@
+assert(typeof Location === "function", "Location is not a function");
+assert(location instanceof Location, "location is not a Location");
+assert(window.location === location, "window.location isn't location");
+
+const initialHref = location.href;
+assert(initialHref.startsWith("file:///"), `invalid href: ${initialHref}`);
+assert(initialHref.endsWith("/test/html/empty/index.html"), `invalid href: ${initialHref}`);
+assert(location.toString() === initialHref, "toString() isn't the href");
+assert(`${location}` === initialHref, "the string conversion isn't the href");
+assert(location.protocol === "file:", `invalid protocol: ${location.protocol}`);
+assert(location.host === "", `invalid host: ${location.host}`);
+assert(location.hostname === "", `invalid hostname: ${location.hostname}`);
+assert(location.port === "", `invalid port: ${location.port}`);
+assert(location.pathname.endsWith("/index.html"), `invalid pathname: ${location.pathname}`);
+assert(location.search === "", `invalid search: ${location.search}`);
+assert(location.hash === "", `invalid hash: ${location.hash}`);
+assert(location.origin === "null", `invalid origin: ${location.origin}`);
+
+function throwsNamed(name, f) {
+    try {
+        f();
+    } catch (e) {
+        return e.name === name;
+    }
+    return false;
+}
+
+assert(throwsNamed("TypeError", () => new Location()), "the Location constructor didn't throw");
+assert(throwsNamed("SyntaxError", () => location.assign("http://[")), "assign() with an invalid URL didn't throw");
+assert(throwsNamed("SyntaxError", () => location.href = "http://["), "setting an invalid href didn't throw");
+
+// Navigating to a fragment changes the URL immediately.
+location.hash = "#anchor";
+assert(location.hash === "#anchor", `the hash wasn't changed: ${location.hash}`);
+assert(location.href === `${initialHref}#anchor`, `the href wasn't changed: ${location.href}`);
+
+location.hash = "other";
+assert(location.hash === "#other", `the hash without a leading "#" wasn't changed: ${location.hash}`);
+
+location.href = "#last";
+assert(location.hash === "#last", `setting the href to a fragment didn't change the hash: ${location.hash}`);
+
+// Other navigations only change the URL once the new document is loaded.
+location.assign("other.html");
+location.replace("other.html?query");
+location.reload();
+assert(location.href === `${initialHref}#last`, `the href was changed by a navigation: ${location.href}`);
@@ -0,0 +1,71 @@
+const body = document.querySelector("body");
+body.insertAdjacentHTML("beforeend", `<div id="observed"><p id="first"></p></div>`);
+
+const observed = document.getElementById("observed");
+
+let threw = false;
+try {
+    new MutationObserver(42);
+} catch (e) {
+    threw = e instanceof TypeError;
+}
+assert(threw, "the constructor didn't throw a TypeError for a callback that isn't a function");
+
+const observer = new MutationObserver(() => {});
+assert(observer instanceof MutationObserver, "observer is not a MutationObserver");
+
+threw = false;
+try {
+    observer.observe(observed, {});
+} catch (e) {
+    threw = e instanceof TypeError;
+}
+assert(threw, "observe() didn't throw a TypeError for empty options");
+
+threw = false;
+try {
+    observer.observe(observed, { childList: true, attributes: false, attributeOldValue: true });
+} catch (e) {
+    threw = e instanceof TypeError;
+}
+assert(threw, "observe() didn't throw a TypeError for attributeOldValue without attributes");
+
+observer.observe(observed, { childList: true });
+observed.insertAdjacentHTML("beforeend", `<span id="second"></span>`);
+
+// The parent of `observed` isn't observed.
+body.insertAdjacentHTML("beforeend", `<span></span>`);
+
+let records = observer.takeRecords();
+assert(records.length === 1, `expected 1 record, got ${records.length}`);
+assert(records[0].type === "childList", `the type is not childList: ${records[0].type}`);
+assert(records[0].target.matches("#observed"), "the target is not #observed");
+assert(records[0].addedNodes.length === 1, `expected 1 added node, got ${records[0].addedNodes.length}`);
+assert(records[0].addedNodes[0].matches("#second"), "the added node is not #second");
+assert(records[0].removedNodes.length === 0, "there are removed nodes");
+assert(records[0].previousSibling.matches("#first"), "the previous sibling is not #first");
+assert(records[0].nextSibling === null, "the next sibling is not null");
+assert(records[0].attributeName === null, "the attribute name is not null");
+assert(records[0].oldValue === null, "the old value is not null");
+assert(observer.takeRecords().length === 0, "the records weren't taken");
+
+// Observing the subtree also records the mutations of the descendants.
+observer.observe(observed, { childList: true, subtree: true });
+document.getElementById("first").insertAdjacentHTML("afterbegin", `<b></b>`);
+records = observer.takeRecords();
+assert(records.length === 1, `expected 1 record of the subtree, got ${records.length}`);
+assert(records[0].target.matches("#first"), "the target is not #first");
+
+observer.disconnect();
+observed.insertAdjacentHTML("beforeend", `<span></span>`);
+assert(observer.takeRecords().length === 0, "records were queued after disconnect()");
+
+// The callback is invoked after this script, in a microtask.
+let callbackRecords = null;
+const callbackObserver = new MutationObserver((records, mo) => {
+    assert(mo === callbackObserver, "the second argument is not the observer");
+    callbackRecords = records;
+});
+callbackObserver.observe(document, { childList: true, subtree: true });
+observed.insertAdjacentHTML("beforeend", `<span></span>`);
+assert(callbackRecords === null, "the callback was invoked synchronously");
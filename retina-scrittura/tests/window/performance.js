@@ -0,0 +1,101 @@
+assert(typeof Performance === "function", "Performance is not a function");
+assert(performance instanceof Performance, "performance is not a Performance");
+assert(window.performance === performance, "window.performance isn't performance");
+
+function throwsNamed(name, f) {
+    try {
+        f();
+    } catch (e) {
+        return e.name === name;
+    }
+    return false;
+}
+
+assert(throwsNamed("TypeError", () => new Performance()), "the Performance constructor didn't throw");
+
+const first = performance.now();
+const second = performance.now();
+assert(typeof first === "number", `now() isn't a number: ${first}`);
+assert(first >= 0, `now() is negative: ${first}`);
+assert(second >= first, `now() isn't monotonic: ${first} > ${second}`);
+assert(performance.timeOrigin > 1600000000000, `invalid timeOrigin: ${performance.timeOrigin}`);
+assert(performance.toJSON().timeOrigin === performance.timeOrigin, "toJSON() doesn't contain the timeOrigin");
+assert(performance.timing.navigationStart === Math.round(performance.timeOrigin), `invalid navigationStart: ${performance.timing.navigationStart}`);
+
+// Marks
+const mark = performance.mark("start", { detail: { step: 1 } });
+assert(mark instanceof PerformanceEntry, "mark() didn't return a PerformanceEntry");
+assert(mark.name === "start", `invalid name: ${mark.name}`);
+assert(mark.entryType === "mark", `invalid entryType: ${mark.entryType}`);
+assert(mark.duration === 0, `invalid duration: ${mark.duration}`);
+assert(mark.detail.step === 1, "the detail wasn't stored");
+assert(mark.toJSON().name === "start", "toJSON() doesn't contain the name");
+
+performance.mark("early", { startTime: 1 });
+performance.mark("late", { startTime: 1000000 });
+assert(performance.getEntriesByName("early")[0].startTime === 1, "the startTime option wasn't used");
+assert(performance.getEntriesByName("early")[0].detail === null, "the detail isn't null by default");
+
+assert(throwsNamed("SyntaxError", () => performance.mark("navigationStart")), "a PerformanceTiming name didn't throw");
+assert(throwsNamed("TypeError", () => performance.mark("negative", { startTime: -1 })), "a negative startTime didn't throw");
+
+// Measures
+const measure = performance.measure("between", "early", "late");
+assert(measure.entryType === "measure", `invalid entryType: ${measure.entryType}`);
+assert(measure.startTime === 1, `invalid startTime: ${measure.startTime}`);
+assert(measure.duration === 999999, `invalid duration: ${measure.duration}`);
+
+const fromOrigin = performance.measure("from-origin");
+assert(fromOrigin.startTime === 0, `a measure without a start doesn't start at 0: ${fromOrigin.startTime}`);
+
+const withOptions = performance.measure("options", { start: 10, duration: 5, detail: "x" });
+assert(withOptions.startTime === 10 && withOptions.duration === 5, "the start and duration options weren't used");
+assert(withOptions.detail === "x", "the detail option wasn't used");
+
+assert(performance.measure("navigation", "navigationStart", "late").startTime === 0, "navigationStart isn't 0");
+assert(throwsNamed("SyntaxError", () => performance.measure("missing", "missing")), "an unknown mark didn't throw");
+assert(throwsNamed("InvalidAccessError", () => performance.measure("unload", "unloadEventStart")), "an event that didn't occur didn't throw");
+assert(throwsNamed("TypeError", () => performance.measure("both", { start: 1 }, "late")), "options with an endMark didn't throw");
+assert(throwsNamed("TypeError", () => performance.measure("all", { start: 1, end: 2, duration: 1 })), "start, end and duration together didn't throw");
+
+// Querying
+const entries = performance.getEntries();
+for (let i = 1; i < entries.length; i += 1) {
+    assert(entries[i - 1].startTime <= entries[i].startTime, "getEntries() isn't sorted chronologically");
+}
+assert(performance.getEntriesByType("mark").length === 3, `invalid number of marks: ${performance.getEntriesByType("mark").length}`);
+assert(performance.getEntriesByType("measure").length === 4, `invalid number of measures: ${performance.getEntriesByType("measure").length}`);
+assert(performance.getEntriesByName("start", "measure").length === 0, "getEntriesByName() ignored the type");
+
+performance.clearMarks("early");
+assert(performance.getEntriesByName("early").length === 0, "clearMarks(name) didn't remove the mark");
+assert(performance.getEntriesByType("mark").length === 2, "clearMarks(name) removed other marks");
+performance.clearMeasures();
+assert(performance.getEntriesByType("measure").length === 0, "clearMeasures() didn't remove the measures");
+assert(performance.getEntriesByType("mark").length === 2, "clearMeasures() removed the marks");
+
+// PerformanceObserver
+assert(PerformanceObserver.supportedEntryTypes.join() === "mark,measure", `invalid supportedEntryTypes: ${PerformanceObserver.supportedEntryTypes}`);
+assert(throwsNamed("TypeError", () => new PerformanceObserver()), "the constructor without a callback didn't throw");
+
+const observer = new PerformanceObserver(() => {});
+assert(throwsNamed("TypeError", () => observer.observe({})), "observe() without types didn't throw");
+assert(throwsNamed("TypeError", () => observer.observe({ entryTypes: ["mark"], buffered: true })), "entryTypes with buffered didn't throw");
+
+observer.observe({ entryTypes: ["mark"] });
+assert(throwsNamed("InvalidModificationError", () => observer.observe({ type: "mark" })), "changing the observer type didn't throw");
+
+performance.mark("observed");
+performance.measure("ignored");
+const records = observer.takeRecords();
+assert(records.length === 1, `invalid number of records: ${records.length}`);
+assert(records[0].name === "observed", `invalid record: ${records[0].name}`);
+assert(observer.takeRecords().length === 0, "takeRecords() didn't empty the buffer");
+
+observer.disconnect();
+performance.mark("after-disconnect");
+assert(observer.takeRecords().length === 0, "the observer received entries after disconnect()");
+
+const buffered = new PerformanceObserver(() => {});
+buffered.observe({ type: "mark", buffered: true });
+assert(buffered.takeRecords().length === 4, "the buffered marks weren't delivered");
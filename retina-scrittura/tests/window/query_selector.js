@@ -0,0 +1,25 @@
+const body = document.querySelector("body");
+body.insertAdjacentHTML("beforeend", `<div id="list"><p class="item"></p><p class="item"></p></div>`);
+
+const list = document.getElementById("list");
+assert(document.querySelectorAll(".item").length === 2, "the document doesn't find both items");
+assert(list.querySelector("p.item") !== null, "the element doesn't find its descendant");
+assert(list.querySelector("div") === null, "the element finds itself");
+
+function selectorError(target, method, selectors) {
+    try {
+        target[method](selectors);
+    } catch (e) {
+        return e.name;
+    }
+    return null;
+}
+
+for (const target of [document, list]) {
+    for (const method of ["querySelector", "querySelectorAll"]) {
+        for (const selectors of ["p {", "p,", ""]) {
+            const error = selectorError(target, method, selectors);
+            assert(error === "SyntaxError", `${method}("${selectors}") threw ${error} instead of a SyntaxError`);
+        }
+    }
+}
@@ -0,0 +1,49 @@
+assert(typeof Storage === "function", "Storage is not a function");
+assert(localStorage instanceof Storage, "localStorage is not a Storage");
+assert(sessionStorage instanceof Storage, "sessionStorage is not a Storage");
+assert(window.localStorage === localStorage, "window.localStorage isn't localStorage");
+assert(localStorage !== sessionStorage, "localStorage and sessionStorage are the same object");
+assert(onstorage === null, "onstorage isn't null initially");
+
+function throwsNamed(name, f) {
+    try {
+        f();
+    } catch (e) {
+        return e.name === name;
+    }
+    return false;
+}
+
+assert(throwsNamed("TypeError", () => new Storage()), "the Storage constructor didn't throw");
+
+for (const storage of [localStorage, sessionStorage]) {
+    storage.clear();
+    assert(storage.length === 0, `the storage isn't empty: ${storage.length}`);
+    assert(storage.getItem("missing") === null, "getItem() of a missing key isn't null");
+    assert(storage.key(0) === null, "key() of an empty storage isn't null");
+
+    storage.setItem("number", 42);
+    assert(storage.getItem("number") === "42", `the value isn't converted to a string: ${storage.getItem("number")}`);
+    assert(storage.length === 1, `invalid length: ${storage.length}`);
+    assert(storage.key(0) === "number", `invalid key: ${storage.key(0)}`);
+
+    storage.setItem("number", "43");
+    storage.setItem("other", "value");
+    assert(storage.getItem("number") === "43", "setItem() didn't overwrite the value");
+    assert(storage.length === 2, `invalid length: ${storage.length}`);
+
+    storage.removeItem("number");
+    storage.removeItem("missing");
+    assert(storage.getItem("number") === null, "removeItem() didn't remove the item");
+    assert(storage.length === 1, `invalid length after removeItem(): ${storage.length}`);
+
+    assert(throwsNamed("TypeError", () => storage.setItem("key")), "setItem() with one argument didn't throw");
+    assert(throwsNamed("QuotaExceededError", () => storage.setItem("large", "a".repeat(6 * 1024 * 1024))), "the quota wasn't enforced");
+
+    storage.clear();
+    assert(storage.length === 0, "clear() didn't remove the items");
+}
+
+localStorage.setItem("shared", "local");
+assert(sessionStorage.getItem("shared") === null, "sessionStorage shares the items of localStorage");
+localStorage.clear();
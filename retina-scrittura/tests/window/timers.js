@@ -0,0 +1,23 @@
+for (const name of ["setTimeout", "setInterval", "clearTimeout", "clearInterval"]) {
+    assert(typeof window[name] === "function", `${name} is not a function`);
+}
+
+const first = setTimeout(() => {}, 10);
+const second = setInterval(() => {}, 10, "argument");
+const third = setTimeout("window.fromString = true;");
+assert(Number.isInteger(first) && first > 0, `the timer ID isn't a positive integer: ${first}`);
+assert(second !== first && third !== second, `the timer IDs aren't unique: ${first}, ${second}, ${third}`);
+
+// Timers don't run synchronously.
+let ran = false;
+setTimeout(() => ran = true, 0);
+assert(!ran, "the timeout ran synchronously");
+
+clearTimeout(first);
+clearInterval(second);
+clearTimeout(third);
+
+// Clearing unknown timers is allowed.
+clearTimeout();
+clearTimeout(12345);
+clearInterval("not a number");
@@ -0,0 +1,83 @@
+assert(typeof XMLHttpRequest === "function", "XMLHttpRequest is not a function");
+assert(XMLHttpRequest.DONE === 4 && XMLHttpRequest.UNSENT === 0, "the state constants are missing");
+
+const xhr = new XMLHttpRequest();
+assert(xhr.readyState === XMLHttpRequest.UNSENT, `invalid initial state: ${xhr.readyState}`);
+assert(xhr.status === 0, `invalid initial status: ${xhr.status}`);
+assert(xhr.statusText === "", `invalid initial statusText: ${xhr.statusText}`);
+assert(xhr.responseText === "", `invalid initial responseText: ${xhr.responseText}`);
+assert(xhr.responseURL === "", `invalid initial responseURL: ${xhr.responseURL}`);
+assert(xhr.responseType === "", `invalid initial responseType: ${xhr.responseType}`);
+assert(xhr.timeout === 0, `invalid initial timeout: ${xhr.timeout}`);
+assert(xhr.withCredentials === false, "withCredentials is set initially");
+assert(xhr.onload === null, "onload isn't null initially");
+assert(xhr.getResponseHeader("Content-Type") === null, "a response header exists initially");
+assert(xhr.getAllResponseHeaders() === "", "response headers exist initially");
+
+function throwsNamed(name, f) {
+    try {
+        f();
+    } catch (e) {
+        return e.name === name;
+    }
+    return false;
+}
+
+assert(throwsNamed("InvalidStateError", () => xhr.send()), "send() before open() didn't throw");
+assert(throwsNamed("InvalidStateError", () => xhr.setRequestHeader("X-Test", "a")), "setRequestHeader() before open() didn't throw");
+
+const states = [];
+xhr.onreadystatechange = () => states.push(xhr.readyState);
+assert(typeof xhr.onreadystatechange === "function", "the event handler isn't stored");
+
+xhr.open("get", "index.html");
+assert(xhr.readyState === XMLHttpRequest.OPENED, `open() didn't change the state: ${xhr.readyState}`);
+assert(states.join() === "1", `readystatechange wasn't fired once: ${states}`);
+
+xhr.setRequestHeader("X-Test", "a");
+xhr.setRequestHeader("X-Test", "b");
+assert(throwsNamed("SyntaxError", () => xhr.setRequestHeader("Invalid Name", "a")), "an invalid header name didn't throw");
+
+xhr.responseType = "json";
+assert(xhr.responseType === "json", `responseType wasn't set: ${xhr.responseType}`);
+xhr.responseType = "invalid";
+assert(xhr.responseType === "json", "an invalid responseType wasn't ignored");
+assert(throwsNamed("InvalidStateError", () => xhr.responseText), "responseText didn't throw for the JSON response type");
+xhr.responseType = "";
+
+xhr.timeout = 1000;
+xhr.withCredentials = true;
+xhr.overrideMimeType("text/plain");
+
+let loadStarted = false;
+xhr.onloadstart = () => loadStarted = true;
+xhr.send();
+assert(loadStarted, "loadstart wasn't fired");
+assert(throwsNamed("InvalidStateError", () => xhr.send()), "send() twice didn't throw");
+assert(throwsNamed("InvalidStateError", () => xhr.withCredentials = false), "withCredentials could be changed after send()");
+
+let aborted = false;
+let loadEnded = false;
+xhr.onabort = (event) => aborted = event.type === "abort" && event.target === xhr;
+xhr.onloadend = () => loadEnded = true;
+xhr.abort();
+assert(aborted && loadEnded, "abort and loadend weren't fired");
+assert(xhr.readyState === XMLHttpRequest.UNSENT, `abort() didn't reset the state: ${xhr.readyState}`);
+assert(xhr.status === 0, "an aborted request has a status");
+
+const sync = new XMLHttpRequest();
+sync.open("GET", "index.html", false);
+assert(throwsNamed("InvalidAccessError", () => sync.timeout = 10), "a synchronous request accepted a timeout");
+sync.send();
+assert(sync.readyState === XMLHttpRequest.DONE, `the synchronous request didn't complete: ${sync.readyState}`);
+assert(sync.status === 200, `invalid status: ${sync.status}`);
+assert(sync.responseText.includes("<html"), "invalid responseText");
+assert(sync.response === sync.responseText, "the text response isn't returned");
+assert(sync.responseURL.endsWith("/index.html"), `invalid responseURL: ${sync.responseURL}`);
+assert(sync.getResponseHeader("content-type").startsWith("text/html"), `invalid Content-Type: ${sync.getResponseHeader("content-type")}`);
+assert(sync.getAllResponseHeaders().includes("content-type: text/html"), `invalid headers: ${sync.getAllResponseHeaders()}`);
+
+const missing = new XMLHttpRequest();
+missing.open("GET", "does-not-exist.html", false);
+assert(throwsNamed("NetworkError", () => missing.send()), "a failed synchronous request didn't throw");
+assert(missing.readyState === XMLHttpRequest.DONE, "the failed request isn't done");
@@ -0,0 +1,232 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! Bloom filters over the class names and IDs used by the selectors of a set
+//! of stylesheets. These allow the [`StyleCollector`](crate::StyleCollector)
+//! to skip selectors that require a class or ID the element can't have,
+//! without running the full selector matching algorithm.
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+use retina_dom::NodeKind;
+use retina_style::{
+    CompoundSelector,
+    Rule,
+    Selector,
+    SimpleSelector,
+    Stylesheet,
+};
+
+const BLOOM_FILTER_WORDS: usize = 64;
+const BLOOM_FILTER_BITS: u64 = (BLOOM_FILTER_WORDS * u64::BITS as usize) as u64;
+
+/// A fixed-size [Bloom filter](https://en.wikipedia.org/wiki/Bloom_filter)
+/// over strings. A negative answer of [`Self::may_contain`] is definitive,
+/// a positive answer might be a false positive.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BloomFilter {
+    bits: [u64; BLOOM_FILTER_WORDS],
+}
+
+impl BloomFilter {
+    pub fn new() -> Self {
+        Self {
+            bits: [0; BLOOM_FILTER_WORDS],
+        }
+    }
+
+    pub fn insert(&mut self, value: &str) {
+        for bit in Self::bit_indices(value) {
+            self.bits[(bit / 64) as usize] |= 1 << (bit % 64);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|word| *word == 0)
+    }
+
+    pub fn may_contain(&self, value: &str) -> bool {
+        Self::bit_indices(value)
+            .into_iter()
+            .all(|bit| self.bits[(bit / 64) as usize] & (1 << (bit % 64)) != 0)
+    }
+
+    /// Derives the two bit positions of the value from the halves of a single
+    /// 64-bit hash.
+    fn bit_indices(value: &str) -> [u64; 2] {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        [
+            (hash & 0xFFFF_FFFF) % BLOOM_FILTER_BITS,
+            (hash >> 32) % BLOOM_FILTER_BITS,
+        ]
+    }
+}
+
+impl Default for BloomFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The Bloom filter containing all class names used in selectors.
+pub type ClassBloomFilter = BloomFilter;
+
+/// The Bloom filter containing all IDs used in selectors.
+pub type IdBloomFilter = BloomFilter;
+
+/// The class and ID filters of a set of stylesheets.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SelectorBloomFilters {
+    pub(crate) classes: ClassBloomFilter,
+    pub(crate) ids: IdBloomFilter,
+}
+
+impl SelectorBloomFilters {
+    pub fn new(stylesheets: &[Stylesheet]) -> Self {
+        let mut filters = Self::default();
+        for stylesheet in stylesheets {
+            filters.insert_stylesheet(stylesheet);
+        }
+        filters
+    }
+
+    fn insert_stylesheet(&mut self, stylesheet: &Stylesheet) {
+        for rule in stylesheet.rules() {
+            match rule {
+                Rule::AtFontFace(..) | Rule::AtKeyframes(..) | Rule::AtLayerStatement(..) => (),
+                Rule::AtImport(import) => {
+                    if let Some(stylesheet) = &import.stylesheet {
+                        self.insert_stylesheet(stylesheet);
+                    }
+                }
+                Rule::AtLayer(layer) => self.insert_stylesheet(&layer.stylesheet),
+                Rule::AtMedia(media) => self.insert_stylesheet(&media.stylesheet),
+                Rule::AtSupports(supports) => self.insert_stylesheet(&supports.stylesheet),
+                Rule::Style(rule) => {
+                    for selector in &rule.selector_list.items {
+                        self.insert_selector(selector);
+                    }
+                }
+            }
+        }
+    }
+
+    fn insert_selector(&mut self, selector: &Selector) {
+        match selector {
+            Selector::Complex(complex) => {
+                self.insert_compound(&complex.topmost);
+                for (_, compound) in &complex.combinators {
+                    self.insert_compound(compound);
+                }
+            }
+            Selector::Compound(compound) => self.insert_compound(compound),
+            Selector::Simple(simple) => self.insert_simple(simple),
+        }
+    }
+
+    fn insert_compound(&mut self, compound: &CompoundSelector) {
+        for simple in &compound.0 {
+            self.insert_simple(simple);
+        }
+    }
+
+    fn insert_simple(&mut self, simple: &SimpleSelector) {
+        match simple {
+            SimpleSelector::Class(class) => self.classes.insert(class.as_ref()),
+            SimpleSelector::Id(id) => self.ids.insert(id.as_ref()),
+            _ => (),
+        }
+    }
+
+    /// Computes which kinds of selectors could possibly match the given node.
+    pub fn hints_for(&self, node: &NodeKind) -> SelectorBloomHints {
+        let Some(element) = node.as_dom_element() else {
+            return SelectorBloomHints::default();
+        };
+
+        let id = element.id();
+        SelectorBloomHints {
+            may_match_class: element.class_list().any(|class| self.classes.may_contain(class)),
+            may_match_id: !id.is_empty() && self.ids.may_contain(id),
+        }
+    }
+}
+
+/// The result of checking the classes and the ID of an element against the
+/// [`SelectorBloomFilters`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SelectorBloomHints {
+    may_match_class: bool,
+    may_match_id: bool,
+}
+
+impl SelectorBloomHints {
+    /// Returns `false` if the subject of the selector requires a class or ID
+    /// that the element definitely doesn't have, meaning the selector can't
+    /// match.
+    pub fn may_match(&self, selector: &Selector) -> bool {
+        let subject = match selector {
+            Selector::Complex(complex) => match complex.combinators.last() {
+                Some((_, compound)) => compound,
+                None => &complex.topmost,
+            },
+            Selector::Compound(compound) => compound,
+            Selector::Simple(simple) => return self.may_match_simple(simple),
+        };
+
+        subject.0.iter().all(|simple| self.may_match_simple(simple))
+    }
+
+    fn may_match_simple(&self, simple: &SimpleSelector) -> bool {
+        match simple {
+            SimpleSelector::Class(..) => self.may_match_class,
+            SimpleSelector::Id(..) => self.may_match_id,
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use retina_style::CascadeOrigin;
+    use retina_style_parser::CssParsable;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("header")]
+    #[case("nav-item")]
+    #[case("a")]
+    fn bloom_filter_contains_inserted(#[case] value: &str) {
+        let mut filter = BloomFilter::new();
+        assert!(filter.is_empty());
+        assert!(!filter.may_contain(value));
+
+        filter.insert(value);
+        assert!(!filter.is_empty());
+        assert!(filter.may_contain(value));
+    }
+
+    #[test]
+    fn filters_from_stylesheet() {
+        let stylesheets = &[
+            Stylesheet::parse(CascadeOrigin::Author, "
+                .button { color: red; }
+                @media screen {
+                    div > #main { color: blue; }
+                }
+            ")
+        ];
+
+        let filters = SelectorBloomFilters::new(stylesheets);
+        assert!(filters.classes.may_contain("button"));
+        assert!(filters.ids.may_contain("main"));
+        assert!(!filters.ids.may_contain("button"));
+    }
+}
@@ -1,6 +1,8 @@
 // Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
 // All Rights Reserved.
 
+use std::collections::{HashMap, HashSet};
+
 use log::warn;
 
 use retina_dom::{
@@ -14,6 +16,7 @@ use retina_style::{
     Rule,
 };
 
+use retina_common::StrTendril;
 use retina_style_parser::CssAttributeStrExtensions;
 
 use crate::{
@@ -28,7 +31,12 @@ fn cascade_normal_declarations_for_origin(
     origin: CascadeOrigin,
 ) {
     let mut applicable_rules: Vec<_> = applicable_rules.to_vec();
-    applicable_rules.sort_by(|a, b| a.specificity.cmp(&b.specificity));
+
+    // Within an origin, the cascade layer takes precedence over specificity.
+    // The sort is stable, so the order of appearance is maintained.
+    applicable_rules.sort_by(|a, b| {
+        a.layer.cmp(&b.layer).then_with(|| a.specificity.cmp(&b.specificity))
+    });
 
     for applicable_rule in applicable_rules.iter() {
         if applicable_rule.rule.cascade_origin != origin {
@@ -59,7 +67,9 @@ fn cascade_styles_from_attribute(
                 &[
                     ApplicableRule {
                         specificity: crate::SelectorSpecificity::new_for_style_attribute(),
+                        layer: crate::CascadeLayerOrder::unlayered(),
                         rule: &style_rule,
+                        index: crate::RuleIndex::STYLE_ATTRIBUTE,
                     }
                 ],
                 CascadeOrigin::Author,
@@ -150,6 +160,21 @@ fn compute_relative_properties(property_map: &mut PropertyMap, parent: Option<&P
     let font_size_multiplier = match property_map.font_size() {
         CssLength::FontSize(size) => size,
         CssLength::Percentage(percentage) => percentage,
+
+        // Both `em` and percentages of `font-size` refer to the font size of
+        // the parent, so e.g. `calc(1em + 2px)` can be computed here, unless
+        // it contains other relative units.
+        CssLength::Calc(calc) => {
+            let CssLength::Pixels(parent_absolute_size) = parent_font_size else { return };
+            if calc.root_font_size != 0.0 || calc.viewport_height != 0.0 || calc.viewport_width != 0.0 {
+                return;
+            }
+
+            let size = calc.pixels + (calc.font_size + calc.percentage) * parent_absolute_size;
+            property_map.font_size = Some(CssLength::Pixels(size));
+            return;
+        }
+
         _ => return,
     };
 
@@ -168,8 +193,13 @@ fn inherit_property<T>(target: &mut Option<T>, source: &Option<T>)
 }
 
 fn inherit_properties(property_map: &mut PropertyMap, parent: &PropertyMap) {
+    inherit_property(&mut property_map.border_collapse, &parent.border_collapse);
+    inherit_property(&mut property_map.border_spacing, &parent.border_spacing);
+    inherit_property(&mut property_map.caption_side, &parent.caption_side);
     inherit_property(&mut property_map.color, &parent.color);
     inherit_property(&mut property_map.cursor, &parent.cursor);
+    inherit_property(&mut property_map.direction, &parent.direction);
+    property_map.custom = parent.custom.clone();
     inherit_property(&mut property_map.font_family_list, &parent.font_family_list);
     inherit_property(&mut property_map.font_kerning, &parent.font_kerning);
     inherit_property(&mut property_map.font_size, &parent.font_size);
@@ -178,8 +208,15 @@ fn inherit_properties(property_map: &mut PropertyMap, parent: &PropertyMap) {
     inherit_property(&mut property_map.font_variant_ligatures, &parent.font_variant_ligatures);
     inherit_property(&mut property_map.font_variant_position, &parent.font_variant_position);
     inherit_property(&mut property_map.font_weight, &parent.font_weight);
+    inherit_property(&mut property_map.image_rendering, &parent.image_rendering);
+    inherit_property(&mut property_map.letter_spacing, &parent.letter_spacing);
+    inherit_property(&mut property_map.overflow_wrap, &parent.overflow_wrap);
+    inherit_property(&mut property_map.text_indent, &parent.text_indent);
     inherit_property(&mut property_map.text_transform, &parent.text_transform);
     inherit_property(&mut property_map.white_space, &parent.white_space);
+    inherit_property(&mut property_map.word_break, &parent.word_break);
+    inherit_property(&mut property_map.word_spacing, &parent.word_spacing);
+    inherit_property(&mut property_map.writing_mode, &parent.writing_mode);
 
     // This is incorrect, but I'm not sure what the spec means by inheritance
     // through the box tree...
@@ -191,6 +228,98 @@ fn inherit_properties(property_map: &mut PropertyMap, parent: &PropertyMap) {
     inherit_property(&mut property_map.text_decoration_style, &parent.text_decoration_style);
 }
 
+/// Resolves the `var()` references in the values of the custom properties,
+/// detecting [dependency cycles][spec].
+///
+/// [spec]: https://drafts.csswg.org/css-variables/#cycles
+struct CustomPropertyResolver<'map> {
+    declared: &'map HashMap<String, StrTendril>,
+
+    /// The substituted values, where `None` is the guaranteed-invalid value.
+    resolved: HashMap<String, Option<StrTendril>>,
+
+    /// The properties that are currently being resolved, in order.
+    stack: Vec<String>,
+
+    /// The properties that are part of a cycle.
+    cyclic: HashSet<String>,
+}
+
+impl<'map> CustomPropertyResolver<'map> {
+    fn new(declared: &'map HashMap<String, StrTendril>) -> Self {
+        Self {
+            declared,
+            resolved: HashMap::new(),
+            stack: Vec::new(),
+            cyclic: HashSet::new(),
+        }
+    }
+
+    fn resolve(&mut self, name: &str) -> Option<StrTendril> {
+        if let Some(value) = self.resolved.get(name) {
+            return value.clone();
+        }
+
+        let declared = self.declared;
+        let value = declared.get(name)?;
+
+        // > If there is a cycle in the dependency graph, all the custom
+        // > properties in the cycle are invalid at computed-value time.
+        if let Some(index) = self.stack.iter().position(|entry| entry == name) {
+            self.cyclic.extend(self.stack[index..].iter().cloned());
+            return None;
+        }
+
+        self.stack.push(name.to_string());
+        let substituted = retina_style_parser::substitute_var_functions(value, &mut |name| {
+            self.resolve(name).map(|value| value.to_string())
+        });
+        self.stack.pop();
+
+        let value = substituted
+            .filter(|_| !self.cyclic.contains(name))
+            .map(StrTendril::from);
+
+        self.resolved.insert(name.to_string(), value.clone());
+        value
+    }
+}
+
+fn resolve_custom_properties(property_map: &mut PropertyMap) {
+    let declared = std::mem::take(&mut property_map.custom);
+
+    let mut resolver = CustomPropertyResolver::new(&declared);
+    for name in declared.keys() {
+        resolver.resolve(name);
+    }
+
+    property_map.custom = resolver.resolved
+        .into_iter()
+        .filter_map(|(name, value)| Some((name, value?)))
+        .collect();
+}
+
+/// Parse the declarations containing `var()` references, now that the
+/// custom properties are known.
+fn apply_pending_substitutions(property_map: &mut PropertyMap) {
+    for (property, value) in std::mem::take(&mut property_map.pending_substitutions) {
+        let custom = &property_map.custom;
+        let substituted = retina_style_parser::substitute_var_functions(&value, &mut |name| {
+            custom.get(name).map(|value| value.to_string())
+        });
+
+        match substituted.and_then(|value| retina_style_parser::parse_property_value(property, &value)) {
+            Some(value) => {
+                property_map.apply_property(property, value);
+            }
+
+            // TODO: the declaration is invalid at computed-value time, which
+            //       should make the property `unset`.
+            None => warn!("[cascade] Invalid at computed-value time: {}: {value}", property.as_ref()),
+        }
+    }
+}
+
 pub trait Cascade {
     fn cascade(
         &self,
@@ -246,6 +375,9 @@ impl<'stylesheets> Cascade for CollectedStyles<'stylesheets> {
         // 1. Transition declarations [css-transitions-1]
         // TODO
 
+        resolve_custom_properties(&mut property_map);
+        apply_pending_substitutions(&mut property_map);
+
         compute_relative_properties(&mut property_map, parent);
 
         property_map
@@ -254,12 +386,11 @@ impl<'stylesheets> Cascade for CollectedStyles<'stylesheets> {
 
 #[cfg(test)]
 mod tests {
-
-
     use retina_dom::{NodeKind, Text, Document, Node};
     use retina_style::*;
     use retina_style_parser::CssParsable;
     use retina_common::StrTendril;
+    use rstest::rstest;
 
     use crate::*;
     use super::*;
@@ -350,4 +481,162 @@ mod tests {
         });
     }
 
+    #[test]
+    fn most_specific_selector_of_list() {
+        // The rule applies with the specificity of #x, which is higher than
+        // that of .c, even though p comes after it in the selector list.
+        let stylesheets = [
+            Stylesheet::parse(CascadeOrigin::Author, "
+                #x, p { color: red; }
+                .c { color: blue; }
+            "),
+        ];
+
+        let document = retina_dom::Parser::parse("<p id=x class=c>Text</p>");
+        let mut paragraph = None;
+        document.for_each_child_node_recursive_handle(&mut |node| {
+            if node.tag_name() == Some("p") {
+                paragraph = Some(node.clone());
+            }
+        });
+
+        let cascaded_style = StyleCollector::new(&stylesheets).collect(&paragraph.unwrap()).cascade(None, None);
+        assert_eq!(cascaded_style.color, Some(CssNamedColor::RED));
+    }
+
+    #[test]
+    fn cascade_layers_in_declaration_order() {
+        let stylesheets = [
+            Stylesheet::parse(CascadeOrigin::Author, "
+                @layer base, theme;
+
+                @layer theme {
+                    * { color: blue; }
+                }
+
+                @layer base {
+                    * { color: green; }
+                }
+            "),
+        ];
+
+        let node = &NodeKind::Text(Text::new(StrTendril::new()));
+        let cascaded_style = StyleCollector::new(&stylesheets).collect(node).cascade(None, None);
+
+        assert_eq!(cascaded_style.color, Some(CssNamedColor::BLUE));
+    }
+
+    #[test]
+    fn cascade_layers_unlayered_wins() {
+        let stylesheets = [
+            Stylesheet::parse(CascadeOrigin::Author, "
+                * { color: green; }
+
+                @layer theme {
+                    * { color: blue; }
+                }
+            "),
+        ];
+
+        let node = &NodeKind::Text(Text::new(StrTendril::new()));
+        let cascaded_style = StyleCollector::new(&stylesheets).collect(node).cascade(None, None);
+
+        assert_eq!(cascaded_style.color, Some(CssNamedColor::GREEN));
+    }
+
+    #[test]
+    fn cascade_layers_sub_layers_lose_from_parent() {
+        let stylesheets = [
+            Stylesheet::parse(CascadeOrigin::Author, "
+                @layer framework {
+                    * { color: green; }
+
+                    @layer theme {
+                        * { color: blue; }
+                    }
+                }
+            "),
+        ];
+
+        let node = &NodeKind::Text(Text::new(StrTendril::new()));
+        let cascaded_style = StyleCollector::new(&stylesheets).collect(node).cascade(None, None);
+
+        assert_eq!(cascaded_style.color, Some(CssNamedColor::GREEN));
+    }
+
+    #[rstest]
+    #[case("margin: 1px", [1.0, 1.0, 1.0, 1.0])]
+    #[case("margin: 1px 2px", [1.0, 2.0, 1.0, 2.0])]
+    #[case("margin: 1px 2px 3px", [1.0, 2.0, 3.0, 2.0])]
+    #[case("margin: 1px 2px 3px 4px", [1.0, 2.0, 3.0, 4.0])]
+    #[case("padding: 1px", [1.0, 1.0, 1.0, 1.0])]
+    #[case("padding: 1px 2px", [1.0, 2.0, 1.0, 2.0])]
+    #[case("padding: 1px 2px 3px", [1.0, 2.0, 3.0, 2.0])]
+    #[case("padding: 1px 2px 3px 4px", [1.0, 2.0, 3.0, 4.0])]
+    fn box_shorthand_expansion(#[case] declaration: &str, #[case] expected: [CssDecimal; 4]) {
+        let stylesheets = [
+            Stylesheet::parse(CascadeOrigin::Author, &format!("* {{ {declaration} }}")),
+        ];
+
+        let node = &NodeKind::Text(Text::new(StrTendril::new()));
+        let style = StyleCollector::new(&stylesheets).collect(node).cascade(None, None);
+
+        let actual = if declaration.starts_with("margin") {
+            [style.margin_top(), style.margin_right(), style.margin_bottom(), style.margin_left()]
+        } else {
+            [style.padding_top(), style.padding_right(), style.padding_bottom(), style.padding_left()]
+        };
+
+        assert_eq!(actual, expected.map(CssLength::Pixels));
+    }
+
+    fn cascade_author_style(style: &str, parent: Option<&PropertyMap>) -> PropertyMap {
+        let stylesheets = [
+            Stylesheet::parse(CascadeOrigin::Author, &format!("* {{ {style} }}")),
+        ];
+
+        let node = &NodeKind::Text(Text::new(StrTendril::new()));
+        StyleCollector::new(&stylesheets).collect(node).cascade(None, parent)
+    }
+
+    #[rstest]
+    #[case("--accent: blue; color: var(--accent)", Some(CssNamedColor::BLUE))]
+    #[case("color: var(--accent, green)", Some(CssNamedColor::GREEN))]
+    #[case("--a: var(--b); --b: blue; color: var(--a)", Some(CssNamedColor::BLUE))]
+    #[case("color: var(--accent); color: green", Some(CssNamedColor::GREEN))]
+    #[case("--a: var(--b, red); --b: var(--a, red); color: var(--a, green)", Some(CssNamedColor::GREEN))]
+    #[case("--a: var(--a); --c: var(--a, green); color: var(--c)", Some(CssNamedColor::GREEN))]
+    fn custom_properties(#[case] style: &str, #[case] expected: Option<CssColor>) {
+        let cascaded_style = cascade_author_style(style, None);
+        assert_eq!(cascaded_style.color, expected);
+    }
+
+    #[rstest]
+    #[case("font-size: calc(1em + 2px)", CssLength::Pixels(18.0))]
+    #[case("font-size: calc(50% * 3)", CssLength::Pixels(24.0))]
+    #[case("font-size: calc(1rem + 2px)", CssLength::Calc(CssCalcLength { root_font_size: 1.0, pixels: 2.0, ..Default::default() }))]
+    fn calc_font_size(#[case] style: &str, #[case] expected: CssLength) {
+        let cascaded_style = cascade_author_style(style, None);
+        assert_eq!(cascaded_style.font_size(), expected);
+    }
+
+    #[test]
+    fn custom_properties_cycles_are_guaranteed_invalid() {
+        let cascaded_style = cascade_author_style("--a: var(--b); --b: var(--a); --c: 1px", None);
+
+        assert_eq!(cascaded_style.custom.get("--a"), None);
+        assert_eq!(cascaded_style.custom.get("--b"), None);
+        assert_eq!(cascaded_style.custom.get("--c").map(|value| value.as_ref()), Some("1px"));
+    }
+
+    #[test]
+    fn custom_properties_are_inherited() {
+        let parent = cascade_author_style("--accent: blue", None);
+        let child = CollectedStyles::new().cascade(None, Some(&parent));
+        assert_eq!(child.custom, parent.custom);
+
+        let child = cascade_author_style("--border: 2px; margin-top: var(--border)", Some(&parent));
+        assert_eq!(child.margin_top(), CssLength::Pixels(2.0));
+        assert_eq!(child.custom.get("--accent").map(|value| value.as_ref()), Some("blue"));
+    }
 }
@@ -0,0 +1,103 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use std::collections::HashMap;
+
+/// The position of a rule in the [cascade layer order][spec]. Rules in
+/// earlier layers lose from rules in later layers, and rules that aren't in
+/// any layer win from all layered rules.
+///
+/// The order is stored as the path of sibling indices from the outermost
+/// layer to the innermost one, terminated by [`u32::MAX`]. Comparing these
+/// paths lexicographically gives the correct order, since the declarations
+/// directly inside a layer come after the declarations of its sub-layers.
+///
+/// [spec]: https://drafts.csswg.org/css-cascade-5/#layer-ordering
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CascadeLayerOrder(Vec<u32>);
+
+impl CascadeLayerOrder {
+    /// The order of rules that aren't in any layer.
+    pub fn unlayered() -> Self {
+        Self(vec![u32::MAX])
+    }
+}
+
+impl Default for CascadeLayerOrder {
+    fn default() -> Self {
+        Self::unlayered()
+    }
+}
+
+/// The layer some rule is declared in, whilst walking the style sheets.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct CascadeLayer {
+    /// The fully qualified name, e.g. `framework.theme`. This is empty for
+    /// the implicit outer layer.
+    name: String,
+    path: Vec<u32>,
+}
+
+impl CascadeLayer {
+    pub(crate) fn order(&self) -> CascadeLayerOrder {
+        let mut path = self.path.clone();
+        path.push(u32::MAX);
+        CascadeLayerOrder(path)
+    }
+}
+
+/// Keeps track of the layers in the order they are first declared.
+#[derive(Debug, Default)]
+pub(crate) struct CascadeLayerRegistry {
+    indices: HashMap<String, u32>,
+    sub_layer_counts: HashMap<String, u32>,
+    anonymous_layer_count: usize,
+}
+
+impl CascadeLayerRegistry {
+    /// Declare the (possibly dotted) layer name inside the `parent` layer,
+    /// returning the declared layer. Declaring a layer multiple times doesn't
+    /// change its position.
+    pub(crate) fn declare(&mut self, parent: &CascadeLayer, name: &str) -> CascadeLayer {
+        let mut layer = parent.clone();
+        for segment in name.split('.') {
+            layer = self.declare_segment(&layer, segment);
+        }
+        layer
+    }
+
+    /// Declare a new anonymous layer, which is always distinct from every
+    /// other layer.
+    pub(crate) fn declare_anonymous(&mut self, parent: &CascadeLayer) -> CascadeLayer {
+        self.anonymous_layer_count += 1;
+
+        // Layer names are identifiers, which can't start with a U+0000.
+        let name = format!("\0anonymous-{}", self.anonymous_layer_count);
+        self.declare_segment(parent, &name)
+    }
+
+    fn declare_segment(&mut self, parent: &CascadeLayer, segment: &str) -> CascadeLayer {
+        let name = if parent.name.is_empty() {
+            segment.to_string()
+        } else {
+            format!("{}.{segment}", parent.name)
+        };
+
+        let index = match self.indices.get(&name) {
+            Some(index) => *index,
+            None => {
+                let count = self.sub_layer_counts.entry(parent.name.clone()).or_default();
+                let index = *count;
+                *count += 1;
+
+                self.indices.insert(name.clone(), index);
+                index
+            }
+        };
+
+        let mut path = parent.path.clone();
+        path.push(index);
+
+        CascadeLayer { name, path }
+    }
+}
@@ -4,6 +4,8 @@
 use retina_dom::NodeKind;
 
 use retina_style::{
+    AtImportLayer,
+    CssPseudoElement,
     MediaQuery,
     MediaType,
     Rule,
@@ -11,17 +13,39 @@ use retina_style::{
     StyleRule,
 };
 
-use crate::{SelectorMatcher, SelectorSpecificity, selector_specificity::CalculateSpecificity};
+use crate::{
+    bloom_filter::{
+        SelectorBloomFilters,
+        SelectorBloomHints,
+    },
+    cascade_layer::{
+        CascadeLayer,
+        CascadeLayerRegistry,
+    },
+    CascadeLayerOrder,
+    RuleIndex,
+    SelectorSpecificity,
+    selector_match::{
+        HasMatchCache,
+        matches_selector_with_pseudo_element,
+    },
+    selector_specificity::CalculateSpecificity,
+};
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct ApplicableRule<'stylesheet> {
     pub(crate) rule: &'stylesheet StyleRule,
+    pub(crate) index: RuleIndex,
+    pub(crate) layer: CascadeLayerOrder,
     pub(crate) specificity: SelectorSpecificity,
 }
 
 impl<'stylesheet> PartialOrd for ApplicableRule<'stylesheet> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.specificity.partial_cmp(&other.specificity)
+        match self.layer.cmp(&other.layer) {
+            std::cmp::Ordering::Equal => self.specificity.partial_cmp(&other.specificity),
+            ordering => Some(ordering),
+        }
     }
 }
 
@@ -45,21 +69,55 @@ impl<'stylesheets> CollectedStyles<'stylesheets> {
 
 pub struct StyleCollector<'stylesheets> {
     stylesheets: &'stylesheets [Stylesheet],
+    bloom_filters: SelectorBloomFilters,
+    has_match_cache: HasMatchCache,
 }
 
 impl<'stylesheets> StyleCollector<'stylesheets> {
+    /// Creates a collector for the given stylesheets. This hashes the class
+    /// names and IDs of all selectors, so the collector should be reused for
+    /// all nodes of a document. The results of `:has()` are cached for the
+    /// lifetime of the collector, so the DOM shouldn't change in the
+    /// meantime.
     pub fn new(stylesheets: &'stylesheets [Stylesheet]) -> Self {
         Self {
-            stylesheets
+            stylesheets,
+            bloom_filters: SelectorBloomFilters::new(stylesheets),
+            has_match_cache: HasMatchCache::default(),
         }
     }
 
     pub fn collect(&self, node: &NodeKind) -> CollectedStyles<'stylesheets> {
+        self.collect_impl(node, None)
+    }
+
+    /// Collects the rules that apply to the given pseudo-element of the node,
+    /// e.g. the rules with a `p::before` selector for the `::before` of a
+    /// `<p>` element.
+    pub fn collect_for_pseudo_element(
+        &self,
+        node: &NodeKind,
+        pseudo_element: CssPseudoElement,
+    ) -> CollectedStyles<'stylesheets> {
+        self.collect_impl(node, Some(pseudo_element))
+    }
+
+    fn collect_impl(
+        &self,
+        node: &NodeKind,
+        pseudo_element: Option<CssPseudoElement>,
+    ) -> CollectedStyles<'stylesheets> {
         let mut collected_styles = CollectedStyles::new();
+        let mut layers = CascadeLayerRegistry::default();
+        let layer = CascadeLayer::default();
+        let hints = self.bloom_filters.hints_for(node);
+        let mut next_rule_index = 0;
 
-        for sheet in self.stylesheets {
-            self.collect_for_style_sheet(node, sheet, &mut collected_styles);
-        }
+        self.has_match_cache.scope(|| {
+            for sheet in self.stylesheets {
+                self.collect_for_style_sheet(node, pseudo_element, &hints, sheet, &mut collected_styles, &mut layers, &layer, &mut next_rule_index);
+            }
+        });
 
         collected_styles
     }
@@ -67,23 +125,73 @@ impl<'stylesheets> StyleCollector<'stylesheets> {
     fn collect_for_style_sheet(
         &self,
         node: &NodeKind,
+        pseudo_element: Option<CssPseudoElement>,
+        hints: &SelectorBloomHints,
         stylesheet: &'stylesheets Stylesheet,
-        collected_styles: &mut CollectedStyles<'stylesheets>
+        collected_styles: &mut CollectedStyles<'stylesheets>,
+        layers: &mut CascadeLayerRegistry,
+        layer: &CascadeLayer,
+        next_rule_index: &mut usize,
     ) {
         for rule in stylesheet.rules() {
             match rule {
-                Rule::AtFontFace(..) => continue,
+                Rule::AtFontFace(..) | Rule::AtKeyframes(..) => continue,
+
+                Rule::AtImport(import) => {
+                    let Some(imported_stylesheet) = &import.stylesheet else { continue };
+                    if !media_query_list_matches(&import.media) {
+                        continue;
+                    }
+
+                    let import_layer = match &import.layer {
+                        AtImportLayer::None => layer.clone(),
+                        AtImportLayer::Anonymous => layers.declare_anonymous(layer),
+                        AtImportLayer::Named(name) => layers.declare(layer, name),
+                    };
+
+                    self.collect_for_style_sheet(node, pseudo_element, hints, imported_stylesheet, collected_styles, layers, &import_layer, next_rule_index);
+                }
+
+                Rule::AtLayer(layer_rule) => {
+                    let inner_layer = match &layer_rule.name {
+                        Some(name) => layers.declare(layer, name),
+                        None => layers.declare_anonymous(layer),
+                    };
+
+                    self.collect_for_style_sheet(node, pseudo_element, hints, &layer_rule.stylesheet, collected_styles, layers, &inner_layer, next_rule_index);
+                }
+
+                Rule::AtLayerStatement(statement) => {
+                    for name in &statement.names {
+                        layers.declare(layer, name);
+                    }
+                }
 
                 Rule::AtMedia(media) => {
-                    if media.media_query_list[0] != MediaQuery::Type(MediaType::Print) {
-                        self.collect_for_style_sheet(node, &media.stylesheet, collected_styles);
+                    if media_query_list_matches(&media.media_query_list) {
+                        self.collect_for_style_sheet(node, pseudo_element, hints, &media.stylesheet, collected_styles, layers, layer, next_rule_index);
                     }
                 }
 
+                // The condition was evaluated when the rule was parsed.
+                Rule::AtSupports(supports) => {
+                    self.collect_for_style_sheet(node, pseudo_element, hints, &supports.stylesheet, collected_styles, layers, layer, next_rule_index);
+                }
+
                 Rule::Style(rule) => {
-                    if let Some(selector) = rule.selector_list.most_specific_match(node) {
+                    let index = RuleIndex(*next_rule_index);
+                    *next_rule_index += 1;
+
+                    let selector = rule.selector_list.items.iter()
+                        .filter(|selector| hints.may_match(selector))
+                        .filter(|selector| matches_selector_with_pseudo_element(selector, node, pseudo_element))
+                        .max_by_key(|selector| selector.calculate_specificity());
+
+                    if let Some(selector) = selector {
                         collected_styles.applicable_rules.push(ApplicableRule {
                             rule,
+                            index,
+                            layer: layer.order(),
                             specificity: selector.calculate_specificity()
                         });
                     }
@@ -93,6 +201,13 @@ impl<'stylesheets> StyleCollector<'stylesheets> {
     }
 }
 
+/// Checks if the media query list applies to the screen we're rendering to.
+/// An empty list always matches.
+fn media_query_list_matches(media_query_list: &[MediaQuery]) -> bool {
+    media_query_list.is_empty()
+        || media_query_list.iter().any(|query| *query != MediaQuery::Type(MediaType::Print))
+}
+
 #[cfg(test)]
 mod tests {
     use retina_dom::Text;
@@ -102,6 +217,58 @@ mod tests {
     use super::*;
     use retina_style_parser::CssParsable;
 
+    #[test]
+    fn bloom_filter_skips_unknown_class() {
+        let stylesheets = &[
+            Stylesheet::parse(CascadeOrigin::Author, ".known, #known {
+                color: white;
+            }")
+        ];
+
+        let document = retina_dom::Parser::parse("<p class=unknown id=known></p><b class=other></b>");
+
+        let mut elements = Vec::new();
+        document.for_each_child_node_recursive_handle(&mut |node| {
+            if matches!(node.tag_name(), Some("p" | "b")) {
+                elements.push(node.clone());
+            }
+        });
+
+        let collector = StyleCollector::new(stylesheets);
+        assert_eq!(collector.collect(&elements[0]).applicable_rules().len(), 1);
+        assert_eq!(collector.collect(&elements[1]).applicable_rules().len(), 0);
+    }
+
+    #[test]
+    fn pseudo_element_rules_are_collected_separately() {
+        let stylesheets = &[
+            Stylesheet::parse(CascadeOrigin::Author, "p { color: red; }
+            p::before { content: 'a'; }
+            p::after, div::before { content: 'b'; }")
+        ];
+
+        let document = retina_dom::Parser::parse("<p></p>");
+
+        let mut paragraph = None;
+        document.for_each_child_node_recursive_handle(&mut |node| {
+            if node.tag_name() == Some("p") {
+                paragraph = Some(node.clone());
+            }
+        });
+        let paragraph = paragraph.unwrap();
+
+        let collector = StyleCollector::new(stylesheets);
+        assert_eq!(collector.collect(&paragraph).applicable_rules().len(), 1);
+
+        let before = collector.collect_for_pseudo_element(&paragraph, CssPseudoElement::Before);
+        assert_eq!(before.applicable_rules().len(), 1);
+        assert_eq!(before.applicable_rules()[0].index, RuleIndex(1));
+
+        let after = collector.collect_for_pseudo_element(&paragraph, CssPseudoElement::After);
+        assert_eq!(after.applicable_rules().len(), 1);
+        assert_eq!(after.applicable_rules()[0].index, RuleIndex(2));
+    }
+
     #[test]
     fn stylesheet_single_rule_single_declaration_text_node() {
         let stylesheets = &[
@@ -117,6 +284,8 @@ mod tests {
             applicable_rules: vec![
                 ApplicableRule {
                     rule: stylesheets[0].rules()[0].try_as_style().unwrap(),
+                    index: RuleIndex(0),
+                    layer: CascadeLayerOrder::unlayered(),
                     specificity: Default::default(),
                 }
             ]
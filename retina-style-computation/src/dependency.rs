@@ -0,0 +1,320 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! Bookkeeping of which style rules apply to which node, such that a DOM
+//! mutation only has to restyle the nodes it can affect, instead of the whole
+//! document.
+
+use std::collections::{hash_map::Entry, HashMap};
+
+use retina_dom::{Node, NodeKind};
+use retina_style::CssPseudoElement;
+
+use crate::{CollectedStyles, PropertyMap};
+
+/// Identifies a node by its [`id`][retina_dom::node::NodeInterface::id], which
+/// isn't reused after the node is dropped, unlike its address.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NodeId(u64);
+
+impl NodeId {
+    pub fn of(node: &NodeKind) -> Self {
+        Self(node.as_node().id())
+    }
+}
+
+/// The index of a style rule, in the order the [`StyleCollector`] visits them
+/// across all stylesheets.
+///
+/// [`StyleCollector`]: crate::StyleCollector
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RuleIndex(pub usize);
+
+impl RuleIndex {
+    /// The index used for the declarations of the `style` attribute, which
+    /// aren't part of any stylesheet.
+    pub const STYLE_ATTRIBUTE: Self = Self(usize::MAX);
+}
+
+/// What is recorded of a single node.
+#[derive(Clone, Debug)]
+struct NodeDependencies {
+    rules: Vec<RuleIndex>,
+
+    /// The style after the cascade, which can be reused until the node is
+    /// invalidated.
+    cascaded_style: PropertyMap,
+
+    /// The cascaded styles of the pseudo-elements of the node.
+    pseudo_element_styles: Vec<(CssPseudoElement, PropertyMap)>,
+
+    /// The final style of the node, from which its children inherit. This is
+    /// kept after invalidation, to find out whether the children have to be
+    /// restyled as well.
+    computed_style: Option<PropertyMap>,
+
+    is_valid: bool,
+}
+
+/// Records the rules that apply to each node, and the styles resulting from
+/// them, such that only the invalidated nodes have to be restyled.
+#[derive(Clone, Debug, Default)]
+pub struct DependencyMap {
+    dependencies_by_node: HashMap<NodeId, NodeDependencies>,
+}
+
+impl DependencyMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the rules collected for the node, and the style cascaded from
+    /// them. Returns `true` if these rules differ from the rules previously
+    /// recorded.
+    pub fn record(&mut self, node: &NodeKind, collected_styles: &CollectedStyles, cascaded_style: PropertyMap) -> bool {
+        let rules: Vec<RuleIndex> = collected_styles.applicable_rules()
+            .iter()
+            .map(|rule| rule.index)
+            .collect();
+
+        match self.dependencies_by_node.entry(NodeId::of(node)) {
+            Entry::Occupied(mut entry) => {
+                let dependencies = entry.get_mut();
+                let changed = dependencies.rules != rules;
+                dependencies.rules = rules;
+                dependencies.cascaded_style = cascaded_style;
+                dependencies.pseudo_element_styles.clear();
+                dependencies.is_valid = true;
+                changed
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(NodeDependencies {
+                    rules,
+                    cascaded_style,
+                    pseudo_element_styles: Vec::new(),
+                    computed_style: None,
+                    is_valid: true,
+                });
+                true
+            }
+        }
+    }
+
+    /// Records the cascaded style of a pseudo-element of the node, which must
+    /// be recorded itself.
+    pub fn record_pseudo_element(&mut self, node: &NodeKind, pseudo_element: CssPseudoElement, cascaded_style: PropertyMap) {
+        if let Some(dependencies) = self.valid_dependencies_mut(node) {
+            dependencies.pseudo_element_styles.push((pseudo_element, cascaded_style));
+        }
+    }
+
+    /// Records the final style of the node, i.e. after it was adjusted for
+    /// e.g. transitions. Returns `true` if it differs from the previous final
+    /// style, which means the children inheriting from it must be restyled.
+    pub fn record_computed_style(&mut self, node: &NodeKind, computed_style: &PropertyMap) -> bool {
+        let Some(dependencies) = self.valid_dependencies_mut(node) else {
+            return true;
+        };
+
+        if dependencies.computed_style.as_ref() == Some(computed_style) {
+            return false;
+        }
+
+        dependencies.computed_style = Some(computed_style.clone());
+        true
+    }
+
+    /// Get the rules recorded for the node, if it was styled before and
+    /// hasn't been invalidated since.
+    pub fn rules_for(&self, node: &NodeKind) -> Option<&[RuleIndex]> {
+        self.valid_dependencies(node).map(|dependencies| dependencies.rules.as_slice())
+    }
+
+    /// Get the cascaded style of the node, if it is still valid.
+    pub fn cascaded_style(&self, node: &NodeKind) -> Option<&PropertyMap> {
+        self.valid_dependencies(node).map(|dependencies| &dependencies.cascaded_style)
+    }
+
+    /// Get the cascaded style of a pseudo-element of the node, if it is still
+    /// valid.
+    pub fn cascaded_pseudo_element_style(&self, node: &NodeKind, pseudo_element: CssPseudoElement) -> Option<&PropertyMap> {
+        self.valid_dependencies(node)?
+            .pseudo_element_styles
+            .iter()
+            .find_map(|(candidate, style)| (*candidate == pseudo_element).then_some(style))
+    }
+
+    /// Forgets the node and its descendants, e.g. after they were removed from
+    /// the document.
+    pub fn remove(&mut self, node: &Node) {
+        self.dependencies_by_node.remove(&NodeId::of(node));
+        node.for_each_child_node_recursive_handle(&mut |child| {
+            self.dependencies_by_node.remove(&NodeId::of(child));
+        });
+    }
+
+    /// Forgets all nodes, e.g. when the stylesheets changed, or the document
+    /// was mutated in a way that isn't tracked.
+    pub fn clear(&mut self) {
+        self.dependencies_by_node.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.dependencies_by_node.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dependencies_by_node.is_empty()
+    }
+
+    /// Invalidates the recorded rules of the nodes that have to be restyled
+    /// after `changed_node` was mutated, and returns these nodes. When the
+    /// mutation could affect inherited properties (or selectors with
+    /// combinators matching descendants), the descendants of the node must be
+    /// restyled as well.
+    pub fn invalidate(&mut self, changed_node: &Node, affects_descendants: bool) -> Vec<Node> {
+        let mut nodes = vec![Node::clone(changed_node)];
+
+        if affects_descendants {
+            changed_node.for_each_child_node_recursive_handle(&mut |child| {
+                nodes.push(Node::clone(child));
+            });
+        }
+
+        for node in &nodes {
+            if let Some(dependencies) = self.dependencies_by_node.get_mut(&NodeId::of(node)) {
+                dependencies.is_valid = false;
+            }
+        }
+
+        nodes
+    }
+
+    fn valid_dependencies(&self, node: &NodeKind) -> Option<&NodeDependencies> {
+        self.dependencies_by_node.get(&NodeId::of(node))
+            .filter(|dependencies| dependencies.is_valid)
+    }
+
+    fn valid_dependencies_mut(&mut self, node: &NodeKind) -> Option<&mut NodeDependencies> {
+        self.dependencies_by_node.get_mut(&NodeId::of(node))
+            .filter(|dependencies| dependencies.is_valid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use retina_dom::Parser;
+    use retina_style::{CascadeOrigin, Stylesheet};
+    use retina_style_parser::CssParsable;
+
+    use super::*;
+    use crate::{Cascade, StyleCollector};
+
+    fn find(document: &Node, tag_name: &str) -> Node {
+        let mut found = None;
+        document.for_each_child_node_recursive_handle(&mut |node| {
+            if found.is_none() && node.tag_name() == Some(tag_name) {
+                found = Some(Node::clone(node));
+            }
+        });
+        found.unwrap()
+    }
+
+    fn record(map: &mut DependencyMap, collector: &StyleCollector, node: &Node) -> bool {
+        let collected_styles = collector.collect(node);
+        let cascaded_style = collected_styles.cascade(Some(node), None);
+        map.record(node, &collected_styles, cascaded_style)
+    }
+
+    #[test]
+    fn record_detects_changes() {
+        let stylesheets = &[
+            Stylesheet::parse(CascadeOrigin::Author, "p { color: red; } .a { color: blue; }")
+        ];
+        let collector = StyleCollector::new(stylesheets);
+        let document = Parser::parse("<p class=a></p>");
+        let paragraph = find(&document, "p");
+
+        let mut map = DependencyMap::new();
+        assert!(record(&mut map, &collector, &paragraph));
+        assert_eq!(map.rules_for(&paragraph), Some(&[RuleIndex(0), RuleIndex(1)][..]));
+
+        assert!(!record(&mut map, &collector, &paragraph));
+    }
+
+    #[test]
+    fn invalidate() {
+        let stylesheets = &[
+            Stylesheet::parse(CascadeOrigin::Author, "span { color: red; }")
+        ];
+        let collector = StyleCollector::new(stylesheets);
+        let document = Parser::parse("<div><p><span></span></p></div>");
+        let div = find(&document, "div");
+        let span = find(&document, "span");
+
+        let mut map = DependencyMap::new();
+        record(&mut map, &collector, &span);
+
+        assert_eq!(map.invalidate(&div, false).len(), 1);
+        assert!(map.rules_for(&span).is_some());
+
+        assert_eq!(map.invalidate(&div, true).len(), 3);
+        assert!(map.rules_for(&span).is_none());
+    }
+
+    #[test]
+    fn cascaded_style_until_invalidated() {
+        let stylesheets = &[
+            Stylesheet::parse(CascadeOrigin::Author, "p { color: red; } p::before { content: 'a'; }")
+        ];
+        let collector = StyleCollector::new(stylesheets);
+        let document = Parser::parse("<p></p>");
+        let paragraph = find(&document, "p");
+
+        let mut map = DependencyMap::new();
+        assert!(map.cascaded_style(&paragraph).is_none());
+
+        record(&mut map, &collector, &paragraph);
+        let before = collector.collect_for_pseudo_element(&paragraph, CssPseudoElement::Before).cascade(None, None);
+        map.record_pseudo_element(&paragraph, CssPseudoElement::Before, before.clone());
+
+        assert!(map.cascaded_style(&paragraph).is_some());
+        assert_eq!(map.cascaded_pseudo_element_style(&paragraph, CssPseudoElement::Before), Some(&before));
+        assert!(map.cascaded_pseudo_element_style(&paragraph, CssPseudoElement::After).is_none());
+
+        map.invalidate(&paragraph, false);
+        assert!(map.cascaded_style(&paragraph).is_none());
+        assert!(map.cascaded_pseudo_element_style(&paragraph, CssPseudoElement::Before).is_none());
+    }
+
+    #[test]
+    fn record_computed_style_detects_changes() {
+        let stylesheets = &[
+            Stylesheet::parse(CascadeOrigin::Author, "p { color: red; }")
+        ];
+        let collector = StyleCollector::new(stylesheets);
+        let document = Parser::parse("<p></p>");
+        let paragraph = find(&document, "p");
+
+        let mut map = DependencyMap::new();
+        assert!(map.record_computed_style(&paragraph, &PropertyMap::default()), "unrecorded nodes are always changed");
+
+        record(&mut map, &collector, &paragraph);
+        let style = map.cascaded_style(&paragraph).unwrap().clone();
+        assert!(map.record_computed_style(&paragraph, &style));
+        assert!(!map.record_computed_style(&paragraph, &style));
+
+        // The computed style is kept after invalidation, so restyling the
+        // node to the same style doesn't affect its children.
+        map.invalidate(&paragraph, false);
+        record(&mut map, &collector, &paragraph);
+        assert!(!map.record_computed_style(&paragraph, &style));
+    }
+
+    #[test]
+    fn node_ids_are_not_reused() {
+        let first = NodeId::of(&Parser::parse("<p></p>"));
+        let second = NodeId::of(&Parser::parse("<p></p>"));
+        assert_ne!(first, second);
+    }
+}
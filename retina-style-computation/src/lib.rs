@@ -1,16 +1,22 @@
 // Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
 // All Rights Reserved.
 
+pub(crate) mod bloom_filter;
 pub(crate) mod cascade;
+pub(crate) mod cascade_layer;
 pub(crate) mod collect;
+pub(crate) mod dependency;
 pub(crate) mod property_map;
 pub(crate) mod selector_match;
 pub(crate) mod selector_specificity;
 
 pub(crate) use self::collect::ApplicableRule;
 
+pub use bloom_filter::{BloomFilter, ClassBloomFilter, IdBloomFilter, SelectorBloomFilters, SelectorBloomHints};
 pub use collect::{CollectedStyles, StyleCollector};
 pub use cascade::Cascade;
-pub use property_map::{BorderProperties, PropertyMap};
-pub use selector_match::SelectorMatcher;
+pub use cascade_layer::CascadeLayerOrder;
+pub use dependency::{DependencyMap, NodeId, RuleIndex};
+pub use property_map::{BorderProperties, OutlineProperties, PropertyMap, INTERPOLATABLE_PROPERTIES};
+pub use selector_match::{ElementSelectorExt, InvalidSelectorError, SelectorMatcher};
 pub use selector_specificity::SelectorSpecificity;
@@ -1,7 +1,9 @@
 // Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
 // All Rights Reserved.
 
-use retina_common::Color;
+use std::collections::HashMap;
+
+use retina_common::{Color, StrTendril};
 use retina_style::*;
 
 /// The initial value of the [`border-color`][spec-color].
@@ -14,6 +16,42 @@ pub const INITIAL_BORDER_COLOR: CssColor = CssColor::Color(Color::BLACK);
 /// [spec-width]: https://drafts.csswg.org/css-backgrounds/#typedef-line-width
 pub const INITIAL_BORDER_WIDTH: CssLength = CssLength::Pixels(3 as _);
 
+/// The properties of which the computed values can be interpolated, which
+/// are the ones that can be transitioned.
+///
+/// [spec]: https://drafts.csswg.org/css-transitions/#transitionable
+pub const INTERPOLATABLE_PROPERTIES: &[Property] = &[
+    Property::BackgroundColor,
+    Property::Bottom,
+    Property::Color,
+    Property::FlexGrow,
+    Property::FlexShrink,
+    Property::Height,
+    Property::Left,
+    Property::MarginBottom,
+    Property::MarginLeft,
+    Property::MarginRight,
+    Property::MarginTop,
+    Property::MaxHeight,
+    Property::MaxWidth,
+    Property::MinHeight,
+    Property::MinWidth,
+    Property::Opacity,
+    Property::OutlineColor,
+    Property::OutlineOffset,
+    Property::OutlineWidth,
+    Property::PaddingBottom,
+    Property::PaddingLeft,
+    Property::PaddingRight,
+    Property::PaddingTop,
+    Property::Right,
+    Property::TextDecorationColor,
+    Property::TextIndent,
+    Property::Top,
+    Property::Transform,
+    Property::Width,
+];
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct BorderProperties {
     pub color: CssColor,
@@ -70,30 +108,101 @@ impl TryFrom<Value> for BorderProperties {
     }
 }
 
+/// The computed `outline-*` properties.
+///
+/// [spec]: https://drafts.csswg.org/css-ui/#outline-props
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct OutlineProperties {
+    pub color: CssColor,
+    pub offset: CssLength,
+    pub style: CssOutlineStyle,
+    pub width: CssLength,
+}
+
+impl Default for OutlineProperties {
+    fn default() -> Self {
+        Self {
+            color: CssColor::CurrentColor,
+            offset: CssLength::Pixels(0.0),
+            style: CssOutlineStyle::default(),
+            width: INITIAL_BORDER_WIDTH,
+        }
+    }
+}
+
+impl From<CssOutline> for OutlineProperties {
+    fn from(value: CssOutline) -> Self {
+        let mut outline = Self::default();
+
+        if let Some(color) = value.color { outline.color = color }
+        if let Some(style) = value.style { outline.style = style }
+        if let Some(width) = value.width { outline.width = width }
+
+        outline
+    }
+}
+
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct PropertyMap {
+    pub align_content: Option<CssAlignContent>,
+    pub align_items: Option<CssAlignItems>,
+    pub align_self: Option<CssAlignSelf>,
+
+    pub animation: Option<Vec<CssAnimation>>,
+    pub aspect_ratio: Option<CssAspectRatio>,
+
     pub background_color: Option<CssColor>,
     pub background_image: Option<CssImage>,
     pub background_repeat: Option<CssBackgroundRepeat>,
 
+    pub box_shadow: Option<Vec<CssBoxShadow>>,
+
     pub border_bottom: BorderProperties,
     pub border_left: BorderProperties,
     pub border_right: BorderProperties,
     pub border_top: BorderProperties,
 
+    pub border_collapse: Option<CssBorderCollapse>,
+    pub border_spacing: Option<CssBorderSpacing>,
+
     pub margin_bottom: Option<CssLength>,
     pub margin_left: Option<CssLength>,
     pub margin_right: Option<CssLength>,
     pub margin_top: Option<CssLength>,
 
+    /// The `max-height` and `max-width` properties, of which `none` is
+    /// stored as [`CssLength::Auto`].
+    pub max_height: Option<CssLength>,
+    pub max_width: Option<CssLength>,
+    pub min_height: Option<CssLength>,
+    pub min_width: Option<CssLength>,
+
     pub padding_bottom: Option<CssLength>,
     pub padding_left: Option<CssLength>,
     pub padding_right: Option<CssLength>,
     pub padding_top: Option<CssLength>,
 
+    pub caption_side: Option<CssCaptionSide>,
+    pub clear: Option<CssClear>,
     pub color: Option<CssColor>,
+    pub column_span: Option<CssColumnSpan>,
+    pub content: Option<CssContent>,
     pub cursor: Option<CssCursor>,
+
+    /// The [custom properties][spec], by their name including the `--`
+    /// prefix. These are inherited, and after the cascade their `var()`
+    /// references are substituted.
+    ///
+    /// [spec]: https://drafts.csswg.org/css-variables/#custom-property
+    pub custom: HashMap<String, StrTendril>,
+
+    pub direction: Option<CssDirection>,
     pub display: Option<CssDisplay>,
+    pub flex_basis: Option<CssFlexBasis>,
+    pub flex_direction: Option<CssFlexDirection>,
+    pub flex_grow: Option<CssDecimal>,
+    pub flex_shrink: Option<CssDecimal>,
+    pub flex_wrap: Option<CssFlexWrap>,
     pub float: Option<CssFloatValue>,
     pub font_kerning: Option<CssFontKerning>,
     pub font_family_list: Option<Vec<CssFontFamilyName>>,
@@ -104,14 +213,50 @@ pub struct PropertyMap {
     pub font_variant_ligatures: Option<CssFontVariantLigatures>,
     pub font_variant_position: Option<CssFontVariantPosition>,
     pub font_weight: Option<CssDecimal>,
+    pub grid_column: Option<CssGridPlacement>,
+    pub grid_row: Option<CssGridPlacement>,
+    pub grid_template_areas: Option<CssGridTemplateAreas>,
+    pub grid_template_columns: Option<CssGridTrackList>,
+    pub grid_template_rows: Option<CssGridTrackList>,
     pub height: Option<CssLength>,
+    pub image_rendering: Option<CssImageRendering>,
+    pub justify_content: Option<CssJustifyContent>,
+    pub letter_spacing: Option<CssLength>,
     pub line_height: Option<CssLength>,
+    pub opacity: Option<CssOpacity>,
+    pub order: Option<i32>,
+    pub outline: OutlineProperties,
+    pub overflow_wrap: Option<CssOverflowWrap>,
+    pub overflow_x: Option<CssOverflow>,
+    pub overflow_y: Option<CssOverflow>,
+    pub position: Option<CssPosition>,
+    pub table_layout: Option<CssTableLayout>,
+
+    pub inset_bottom: Option<CssLength>,
+    pub inset_left: Option<CssLength>,
+    pub inset_right: Option<CssLength>,
+    pub inset_top: Option<CssLength>,
+
     pub text_decoration_color: Option<CssColor>,
     pub text_decoration_line: Option<CssTextDecorationLine>,
     pub text_decoration_style: Option<CssTextDecorationStyle>,
+    pub text_indent: Option<CssLength>,
+    pub text_overflow: Option<CssTextOverflow>,
     pub text_transform: Option<CssTextTransform>,
+    pub transform: Option<CssTransform>,
+    pub transition: Option<Vec<CssTransition>>,
     pub width: Option<CssLength>,
     pub white_space: Option<CssWhiteSpace>,
+    pub word_break: Option<CssWordBreak>,
+    pub word_spacing: Option<CssLength>,
+    pub writing_mode: Option<CssWritingMode>,
+    pub z_index: Option<CssZIndex>,
+
+    /// The declarations containing `var()` references, which are parsed once
+    /// the custom properties are known, at the end of the cascade. This is
+    /// `pub` to allow the struct update syntax outside of this crate.
+    #[doc(hidden)]
+    pub pending_substitutions: Vec<(Property, StrTendril)>,
 }
 
 impl PropertyMap {
@@ -120,9 +265,60 @@ impl PropertyMap {
     }
 
     pub fn apply_property(&mut self, property: Property, value: Value) -> PropertyMapDidApply {
+        // A later declaration of the property wins over an earlier one that
+        // is still awaiting substitution.
+        self.pending_substitutions.retain(|(pending, _)| *pending != property);
+
+        if let Value::VariableReference(value) = value {
+            self.pending_substitutions.push((property, value));
+            return PropertyMapDidApply::Yes;
+        }
+
         match property {
             Property::Invalid => PropertyMapDidApply::NoBecauseOfAnInvalidProperty,
 
+            Property::Custom => if let Value::CustomProperty(custom) = value {
+                self.custom.insert(custom.name, custom.value);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::AlignContent => if let Value::AlignContent(align_content) = value {
+                self.align_content = Some(align_content);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::AlignItems => if let Value::AlignItems(align_items) = value {
+                self.align_items = Some(align_items);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::AlignSelf => if let Value::AlignSelf(align_self) = value {
+                self.align_self = Some(align_self);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::Animation => if let Value::Animation(animation) = value {
+                self.animation = Some(animation);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::AspectRatio => if let Value::AspectRatio(aspect_ratio) = value {
+                self.aspect_ratio = Some(aspect_ratio);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
             Property::BackgroundColor => if let Value::Color(color) = value {
                 self.background_color = Some(color);
                 PropertyMapDidApply::Yes
@@ -297,6 +493,41 @@ impl PropertyMap {
                 PropertyMapDidApply::NoBecauseOfAnInvalidValue
             }
 
+            Property::BorderCollapse => if let Value::BorderCollapse(border_collapse) = value {
+                self.border_collapse = Some(border_collapse);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::BorderSpacing => if let Value::BorderSpacing(border_spacing) = value {
+                self.border_spacing = Some(border_spacing);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::BoxShadow => if let Value::BoxShadow(box_shadow) = value {
+                self.box_shadow = Some(box_shadow);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::CaptionSide => if let Value::CaptionSide(caption_side) = value {
+                self.caption_side = Some(caption_side);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::Clear => if let Value::Clear(clear) = value {
+                self.clear = Some(clear);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
             Property::Color => if let Value::Color(color) = value {
                 self.color = Some(color);
                 PropertyMapDidApply::Yes
@@ -304,6 +535,20 @@ impl PropertyMap {
                 PropertyMapDidApply::NoBecauseOfAnInvalidValue
             }
 
+            Property::ColumnSpan => if let Value::ColumnSpan(column_span) = value {
+                self.column_span = Some(column_span);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::Content => if let Value::Content(content) = value {
+                self.content = Some(content);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
             Property::Cursor => if let Value::Cursor(cursor) = value {
                 self.cursor = Some(cursor);
                 PropertyMapDidApply::Yes
@@ -311,6 +556,13 @@ impl PropertyMap {
                 PropertyMapDidApply::NoBecauseOfAnInvalidValue
             }
 
+            Property::Direction => if let Value::Direction(direction) = value {
+                self.direction = Some(direction);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
             Property::Display => if let Value::Display(display) = value {
                 self.display = Some(display);
                 PropertyMapDidApply::Yes
@@ -397,6 +649,76 @@ impl PropertyMap {
                 PropertyMapDidApply::NoBecauseOfAnInvalidValue
             }
 
+            Property::GridColumn => if let Value::GridPlacement(placement) = value {
+                self.grid_column = Some(placement);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::GridRow => if let Value::GridPlacement(placement) = value {
+                self.grid_row = Some(placement);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::GridTemplateAreas => if let Value::GridTemplateAreas(areas) = value {
+                self.grid_template_areas = Some(areas);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::FlexBasis => if let Value::FlexBasis(basis) = value {
+                self.flex_basis = Some(basis);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::FlexDirection => if let Value::FlexDirection(direction) = value {
+                self.flex_direction = Some(direction);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::FlexGrow => if let Value::Number(factor) = value {
+                self.flex_grow = Some(factor);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::FlexShrink => if let Value::Number(factor) = value {
+                self.flex_shrink = Some(factor);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::FlexWrap => if let Value::FlexWrap(wrap) = value {
+                self.flex_wrap = Some(wrap);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::GridTemplateColumns => if let Value::GridTrackList(tracks) = value {
+                self.grid_template_columns = Some(tracks);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::GridTemplateRows => if let Value::GridTrackList(tracks) = value {
+                self.grid_template_rows = Some(tracks);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
             Property::FontSize => if let Value::Length(length) = value {
                 self.font_size = Some(length);
                 PropertyMapDidApply::Yes
@@ -411,9 +733,154 @@ impl PropertyMap {
                 PropertyMapDidApply::NoBecauseOfAnInvalidValue
             }
 
+            Property::ImageRendering => if let Value::ImageRendering(image_rendering) = value {
+                self.image_rendering = Some(image_rendering);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::JustifyContent => if let Value::JustifyContent(justify_content) = value {
+                self.justify_content = Some(justify_content);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::LetterSpacing => if let Value::Length(length) = value {
+                self.letter_spacing = Some(length);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::Bottom => if let Value::Length(length) = value {
+                self.inset_bottom = Some(length);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::Left => if let Value::Length(length) = value {
+                self.inset_left = Some(length);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::Right => if let Value::Length(length) = value {
+                self.inset_right = Some(length);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::Top => if let Value::Length(length) = value {
+                self.inset_top = Some(length);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::Opacity => if let Value::Opacity(opacity) = value {
+                self.opacity = Some(opacity.computed());
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::Order => if let Value::Integer(order) = value {
+                self.order = Some(order);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::Outline => if let Value::Outline(outline) = value {
+                // `outline-offset` isn't part of the shorthand, so it is kept.
+                self.outline = OutlineProperties {
+                    offset: self.outline.offset,
+                    ..outline.into()
+                };
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::OutlineColor => if let Value::Color(color) = value {
+                self.outline.color = color;
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::OutlineOffset => if let Value::Length(offset) = value {
+                self.outline.offset = offset;
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::OutlineStyle => if let Value::OutlineStyle(style) = value {
+                self.outline.style = style;
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::OutlineWidth => if let Value::Length(width) = value {
+                self.outline.width = width;
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::Overflow => if let Value::OverflowShorthand(overflow) = value {
+                self.overflow_x = Some(overflow.x);
+                self.overflow_y = Some(overflow.y);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::OverflowWrap => if let Value::OverflowWrap(overflow_wrap) = value {
+                self.overflow_wrap = Some(overflow_wrap);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::OverflowX => if let Value::Overflow(overflow) = value {
+                self.overflow_x = Some(overflow);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::OverflowY => if let Value::Overflow(overflow) = value {
+                self.overflow_y = Some(overflow);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::Position => if let Value::Position(position) = value {
+                self.position = Some(position);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::TableLayout => if let Value::TableLayout(table_layout) = value {
+                self.table_layout = Some(table_layout);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
             Property::Margin => {
                 match value.into_length_percentage_longhand() {
-                    Some((bottom, left, right, top)) => {
+                    Some((top, right, bottom, left)) => {
                         self.margin_bottom = Some(bottom);
                         self.margin_left = Some(left);
                         self.margin_right = Some(right);
@@ -510,9 +977,37 @@ impl PropertyMap {
                 PropertyMapDidApply::NoBecauseOfAnInvalidValue
             }
 
+            Property::MaxHeight => if let Value::Length(length) = value {
+                self.max_height = Some(length);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::MaxWidth => if let Value::Length(length) = value {
+                self.max_width = Some(length);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::MinHeight => if let Value::Length(length) = value {
+                self.min_height = Some(length);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::MinWidth => if let Value::Length(length) = value {
+                self.min_width = Some(length);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
             Property::Padding => {
                 match value.into_length_percentage_longhand() {
-                    Some((bottom, left, right, top)) => {
+                    Some((top, right, bottom, left)) => {
                         self.padding_bottom = Some(bottom);
                         self.padding_left = Some(left);
                         self.padding_right = Some(right);
@@ -589,6 +1084,20 @@ impl PropertyMap {
                 PropertyMapDidApply::NoBecauseOfAnInvalidValue
             }
 
+            Property::TextIndent => if let Value::Length(length) = value {
+                self.text_indent = Some(length);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::TextOverflow => if let Value::TextOverflow(text_overflow) = value {
+                self.text_overflow = Some(text_overflow);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
             Property::TextTransform => if let Value::TextTransform(transform) = value {
                 self.text_transform = Some(transform);
                 PropertyMapDidApply::Yes
@@ -596,6 +1105,20 @@ impl PropertyMap {
                 PropertyMapDidApply::NoBecauseOfAnInvalidValue
             }
 
+            Property::Transform => if let Value::Transform(transform) = value {
+                self.transform = Some(transform);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::Transition => if let Value::Transition(transition) = value {
+                self.transition = Some(transition);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
             Property::Width => if let Value::Length(length) = value {
                 self.width = Some(length);
                 PropertyMapDidApply::Yes
@@ -609,6 +1132,34 @@ impl PropertyMap {
             } else {
                 PropertyMapDidApply::NoBecauseOfAnInvalidValue
             }
+
+            Property::WordBreak => if let Value::WordBreak(word_break) = value {
+                self.word_break = Some(word_break);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::WordSpacing => if let Value::Length(length) = value {
+                self.word_spacing = Some(length);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::WritingMode => if let Value::WritingMode(writing_mode) = value {
+                self.writing_mode = Some(writing_mode);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
+
+            Property::ZIndex => if let Value::ZIndex(z_index) = value {
+                self.z_index = Some(z_index);
+                PropertyMapDidApply::Yes
+            } else {
+                PropertyMapDidApply::NoBecauseOfAnInvalidValue
+            }
         }
     }
 
@@ -647,11 +1198,38 @@ impl PropertyMap {
         self.background_color.unwrap_or(CssNamedColor::TRANSPARENT)
     }
 
+    pub fn border_collapse(&self) -> CssBorderCollapse {
+        self.border_collapse.unwrap_or_default()
+    }
+
+    pub fn border_spacing(&self) -> CssBorderSpacing {
+        self.border_spacing.unwrap_or(CssBorderSpacing {
+            horizontal: CssLength::Pixels(0.0),
+            vertical: CssLength::Pixels(0.0),
+        })
+    }
+
+    pub fn caption_side(&self) -> CssCaptionSide {
+        self.caption_side.unwrap_or_default()
+    }
+
+    pub fn clear(&self) -> CssClear {
+        self.clear.unwrap_or_default()
+    }
+
     pub fn color(&self) -> CssColor {
         // The initial value is implementation-defined.
         self.color.unwrap_or(CssNamedColor::BLACK)
     }
 
+    pub fn float(&self) -> CssFloatValue {
+        self.float.unwrap_or(CssFloatValue::None)
+    }
+
+    pub fn direction(&self) -> CssDirection {
+        self.direction.unwrap_or_default()
+    }
+
     pub fn display(&self) -> CssDisplay {
         self.display.unwrap_or(CssDisplay::Normal {
             inside: CssDisplayInside::Flow,
@@ -660,6 +1238,38 @@ impl PropertyMap {
         })
     }
 
+    pub fn align_content(&self) -> CssAlignContent {
+        self.align_content.unwrap_or_default()
+    }
+
+    pub fn align_items(&self) -> CssAlignItems {
+        self.align_items.unwrap_or_default()
+    }
+
+    pub fn align_self(&self) -> CssAlignSelf {
+        self.align_self.unwrap_or_default()
+    }
+
+    pub fn flex_basis(&self) -> CssFlexBasis {
+        self.flex_basis.unwrap_or_default()
+    }
+
+    pub fn flex_direction(&self) -> CssFlexDirection {
+        self.flex_direction.unwrap_or_default()
+    }
+
+    pub fn flex_grow(&self) -> CssDecimal {
+        self.flex_grow.unwrap_or(0.0)
+    }
+
+    pub fn flex_shrink(&self) -> CssDecimal {
+        self.flex_shrink.unwrap_or(1.0)
+    }
+
+    pub fn flex_wrap(&self) -> CssFlexWrap {
+        self.flex_wrap.unwrap_or_default()
+    }
+
     pub fn font_size(&self) -> CssLength {
         self.font_size.unwrap_or(CssLength::Pixels(16.0))
     }
@@ -675,10 +1285,42 @@ impl PropertyMap {
             && self.font_weight == other.font_weight
     }
 
+    pub fn grid_column(&self) -> &CssGridPlacement {
+        const AUTO: &CssGridPlacement = &CssGridPlacement {
+            start: CssGridLine::Auto,
+            end: CssGridLine::Auto,
+        };
+        self.grid_column.as_ref().unwrap_or(AUTO)
+    }
+
+    pub fn grid_row(&self) -> &CssGridPlacement {
+        const AUTO: &CssGridPlacement = &CssGridPlacement {
+            start: CssGridLine::Auto,
+            end: CssGridLine::Auto,
+        };
+        self.grid_row.as_ref().unwrap_or(AUTO)
+    }
+
+    pub fn grid_template_areas(&self) -> &CssGridTemplateAreas {
+        self.grid_template_areas.as_ref().unwrap_or(&CssGridTemplateAreas::None)
+    }
+
+    pub fn grid_template_columns(&self) -> &CssGridTrackList {
+        self.grid_template_columns.as_ref().unwrap_or(&CssGridTrackList::None)
+    }
+
+    pub fn grid_template_rows(&self) -> &CssGridTrackList {
+        self.grid_template_rows.as_ref().unwrap_or(&CssGridTrackList::None)
+    }
+
     pub fn height(&self) -> CssLength {
         self.height.unwrap_or(CssLength::Auto)
     }
 
+    pub fn justify_content(&self) -> CssJustifyContent {
+        self.justify_content.unwrap_or_default()
+    }
+
     pub fn margin_bottom(&self) -> CssLength {
         self.margin_bottom.unwrap_or(CssLength::Pixels(0.0))
     }
@@ -695,6 +1337,68 @@ impl PropertyMap {
         self.margin_top.unwrap_or(CssLength::Pixels(0.0))
     }
 
+    /// Get the `max-height`, which is [`CssLength::Auto`] for `none`.
+    pub fn max_height(&self) -> CssLength {
+        self.max_height.unwrap_or(CssLength::Auto)
+    }
+
+    /// Get the `max-width`, which is [`CssLength::Auto`] for `none`.
+    pub fn max_width(&self) -> CssLength {
+        self.max_width.unwrap_or(CssLength::Auto)
+    }
+
+    pub fn min_height(&self) -> CssLength {
+        self.min_height.unwrap_or(CssLength::Auto)
+    }
+
+    pub fn min_width(&self) -> CssLength {
+        self.min_width.unwrap_or(CssLength::Auto)
+    }
+
+    pub fn opacity(&self) -> CssOpacity {
+        self.opacity.unwrap_or_default()
+    }
+
+    pub fn order(&self) -> i32 {
+        self.order.unwrap_or(0)
+    }
+
+    pub fn overflow_wrap(&self) -> CssOverflowWrap {
+        self.overflow_wrap.unwrap_or_default()
+    }
+
+    pub fn overflow_x(&self) -> CssOverflow {
+        computed_overflow(self.overflow_x, self.overflow_y)
+    }
+
+    pub fn overflow_y(&self) -> CssOverflow {
+        computed_overflow(self.overflow_y, self.overflow_x)
+    }
+
+    pub fn position(&self) -> CssPosition {
+        self.position.unwrap_or_default()
+    }
+
+    pub fn table_layout(&self) -> CssTableLayout {
+        self.table_layout.unwrap_or_default()
+    }
+
+    pub fn inset_bottom(&self) -> CssLength {
+        self.inset_bottom.unwrap_or(CssLength::Auto)
+    }
+
+    pub fn inset_left(&self) -> CssLength {
+        self.inset_left.unwrap_or(CssLength::Auto)
+    }
+
+    pub fn inset_right(&self) -> CssLength {
+        self.inset_right.unwrap_or(CssLength::Auto)
+    }
+
+    pub fn inset_top(&self) -> CssLength {
+        self.inset_top.unwrap_or(CssLength::Auto)
+    }
+
     pub fn padding_bottom(&self) -> CssLength {
         self.padding_bottom.unwrap_or(CssLength::Pixels(0.0))
     }
@@ -711,13 +1415,122 @@ impl PropertyMap {
         self.padding_top.unwrap_or(CssLength::Pixels(0.0))
     }
 
+    pub fn column_span(&self) -> CssColumnSpan {
+        self.column_span.unwrap_or_default()
+    }
+
+    pub fn content(&self) -> &CssContent {
+        self.content.as_ref().unwrap_or(&CssContent::Normal)
+    }
+
+    pub fn image_rendering(&self) -> CssImageRendering {
+        self.image_rendering.unwrap_or_default()
+    }
+
+    /// The additional spacing between characters, or [`None`] for `normal`.
+    pub fn letter_spacing(&self) -> Option<CssLength> {
+        self.letter_spacing
+    }
+
+    pub fn animation(&self) -> &[CssAnimation] {
+        self.animation.as_deref().unwrap_or_default()
+    }
+
+    pub fn aspect_ratio(&self) -> CssAspectRatio {
+        self.aspect_ratio.unwrap_or_default()
+    }
+
+    pub fn box_shadow(&self) -> &[CssBoxShadow] {
+        self.box_shadow.as_deref().unwrap_or_default()
+    }
+
+    pub fn text_indent(&self) -> CssLength {
+        self.text_indent.unwrap_or(CssLength::Pixels(0.0))
+    }
+
+    pub fn text_overflow(&self) -> CssTextOverflow {
+        self.text_overflow.unwrap_or_default()
+    }
+
+    pub fn transform(&self) -> &CssTransform {
+        self.transform.as_ref().unwrap_or(&CssTransform::None)
+    }
+
+    /// Get the computed value of one of the [`INTERPOLATABLE_PROPERTIES`],
+    /// in the form [`PropertyMap::apply_property()`] accepts.
+    pub fn interpolatable_value(&self, property: Property) -> Option<Value> {
+        Some(match property {
+            Property::BackgroundColor => Value::Color(self.background_color()),
+            Property::Color => Value::Color(self.color()),
+            Property::OutlineColor => Value::Color(self.outline.color),
+            Property::TextDecorationColor => Value::Color(self.text_decoration_color.unwrap_or(CssColor::CurrentColor)),
+
+            Property::FlexGrow => Value::Number(self.flex_grow()),
+            Property::FlexShrink => Value::Number(self.flex_shrink()),
+
+            Property::Height => Value::Length(self.height()),
+            Property::Width => Value::Length(self.width()),
+
+            Property::Bottom => Value::Length(self.inset_bottom()),
+            Property::Left => Value::Length(self.inset_left()),
+            Property::Right => Value::Length(self.inset_right()),
+            Property::Top => Value::Length(self.inset_top()),
+
+            Property::MarginBottom => Value::Length(self.margin_bottom()),
+            Property::MarginLeft => Value::Length(self.margin_left()),
+            Property::MarginRight => Value::Length(self.margin_right()),
+            Property::MarginTop => Value::Length(self.margin_top()),
+
+            Property::MaxHeight => Value::Length(self.max_height()),
+            Property::MaxWidth => Value::Length(self.max_width()),
+            Property::MinHeight => Value::Length(self.min_height()),
+            Property::MinWidth => Value::Length(self.min_width()),
+
+            Property::PaddingBottom => Value::Length(self.padding_bottom()),
+            Property::PaddingLeft => Value::Length(self.padding_left()),
+            Property::PaddingRight => Value::Length(self.padding_right()),
+            Property::PaddingTop => Value::Length(self.padding_top()),
+
+            Property::TextIndent => Value::Length(self.text_indent()),
+
+            Property::OutlineOffset => Value::Length(self.outline.offset),
+            Property::OutlineWidth => Value::Length(self.outline.width),
+
+            Property::Opacity => Value::Opacity(self.opacity()),
+            Property::Transform => Value::Transform(self.transform().clone()),
+
+            _ => return None,
+        })
+    }
+
+    pub fn transition(&self) -> &[CssTransition] {
+        self.transition.as_deref().unwrap_or_default()
+    }
+
     pub fn white_space(&self) -> CssWhiteSpace {
         self.white_space.unwrap_or(CssWhiteSpace::Normal)
     }
 
+    pub fn word_break(&self) -> CssWordBreak {
+        self.word_break.unwrap_or_default()
+    }
+
+    /// The additional spacing between words, or [`None`] for `normal`.
+    pub fn word_spacing(&self) -> Option<CssLength> {
+        self.word_spacing
+    }
+
+    pub fn writing_mode(&self) -> CssWritingMode {
+        self.writing_mode.unwrap_or_default()
+    }
+
     pub fn width(&self) -> CssLength {
         self.width.unwrap_or(CssLength::Auto)
     }
+
+    pub fn z_index(&self) -> CssZIndex {
+        self.z_index.unwrap_or_default()
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -727,3 +1540,28 @@ pub enum PropertyMapDidApply {
     NoBecauseOfAnUnsupportedFeature,
     Yes,
 }
+
+/// Compute the value of `overflow-x` or `overflow-y`, given the specified
+/// value of the `other` axis.
+///
+/// # References
+/// * [CSS Overflow Module Level 3 § 3.1. Scrolling and Clipping Overflow][spec]
+///
+/// [spec]: https://drafts.csswg.org/css-overflow/#overflow-properties
+fn computed_overflow(value: Option<CssOverflow>, other: Option<CssOverflow>) -> CssOverflow {
+    let value = value.unwrap_or_default();
+    let other = other.unwrap_or_default();
+
+    // > as specified, except with visible/clip computing to auto/hidden
+    // > (respectively) if one of overflow-x or overflow-y is neither visible
+    // > nor clip
+    if matches!(other, CssOverflow::Visible | CssOverflow::Clip) {
+        return value;
+    }
+
+    match value {
+        CssOverflow::Visible => CssOverflow::Auto,
+        CssOverflow::Clip => CssOverflow::Hidden,
+        value => value,
+    }
+}
@@ -1,27 +1,40 @@
 // Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
 // All Rights Reserved.
 
-use std::sync::Weak;
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    sync::Weak,
+};
 
 use retina_dom::{
     Element,
+    HtmlElementKind,
+    HtmlInputElement,
     Node,
     NodeKind,
 };
 
 use retina_style::{
+    AnPlusB,
     AttributeSelector,
     AttributeSelectorKind,
     AttributeSelectorCaseSensitivity,
     ComplexSelector,
     CompoundSelector,
+    CssPseudoElement,
+    FunctionalPseudoClassSelectorKind,
     PseudoClassSelectorKind,
+    RelativeSelector,
+    RelativeSelectorList,
     Selector,
     SelectorCombinator,
     SelectorList,
     SimpleSelector,
 };
 
+use crate::selector_specificity::CalculateSpecificity;
+
 fn matches_attribute_value(
     selector_value: &str,
     actual_value: &str,
@@ -126,8 +139,12 @@ fn matches_attribute_selector(
 
 fn matches_pseudo_class_selector(
     pseudo_class_selector: PseudoClassSelectorKind,
-    element: &Element,
+    node: &NodeKind,
 ) -> bool {
+    let (Some(element), Some(html_element)) = (node.as_dom_element(), node.as_html_element_kind()) else {
+        return false;
+    };
+
     match pseudo_class_selector {
         PseudoClassSelectorKind::AnyLink | PseudoClassSelectorKind::Link => {
             if element.attributes().find_by_str("href").is_none() {
@@ -140,21 +157,13 @@ fn matches_pseudo_class_selector(
 
         // <https://drafts.csswg.org/selectors/#checked>
         PseudoClassSelectorKind::Checked => {
-            if !element.qualified_name().local.eq_str_ignore_ascii_case("input") {
-                return false;
-            }
-
-            let Some(ty) = element.attributes().find_by_str("type") else {
-                return false;
-            };
-
-            if !ty.eq_ignore_ascii_case("checkbox") && !ty.eq_ignore_ascii_case("radio") {
-                return false;
-            }
-
-            element.attributes().find_by_str("checked").is_some()
+            html_element.as_input_element().is_some_and(HtmlInputElement::is_checked)
         }
 
+        // <https://drafts.csswg.org/selectors/#enableddisabled>
+        PseudoClassSelectorKind::Disabled => form_control_is_disabled(html_element) == Some(true),
+        PseudoClassSelectorKind::Enabled => form_control_is_disabled(html_element) == Some(false),
+
         // Matches if the element has no children, or all children are text with
         // only whitespace.
         PseudoClassSelectorKind::Empty => {
@@ -164,7 +173,17 @@ fn matches_pseudo_class_selector(
                 .all(|node| node.is_text_with_only_whitespace())
         }
 
-        // <https://drafts.csswg.org/selectors/#checked>
+        // <https://drafts.csswg.org/selectors/#the-focus-pseudo>
+        PseudoClassSelectorKind::Focus => element.is_focused(),
+
+        // <https://drafts.csswg.org/selectors/#the-hover-pseudo>
+        PseudoClassSelectorKind::Hover => element.is_hovered(),
+
+        // <https://drafts.csswg.org/selectors/#opt-pseudos>
+        PseudoClassSelectorKind::Optional => form_control_is_required(html_element) == Some(false),
+        PseudoClassSelectorKind::Required => form_control_is_required(html_element) == Some(true),
+
+        // <https://drafts.csswg.org/selectors/#placeholder>
         PseudoClassSelectorKind::PlaceholderShown => {
             if !element.qualified_name().local.eq_str_ignore_ascii_case("input") {
                 return false;
@@ -196,17 +215,86 @@ fn matches_pseudo_class_selector(
     }
 }
 
+/// Whether the form control is disabled, or `None` if the element isn't a form
+/// control that can be disabled.
+///
+/// TODO: descendants of a disabled `<fieldset>` are disabled as well.
+fn form_control_is_disabled(html_element: &HtmlElementKind) -> Option<bool> {
+    match html_element {
+        HtmlElementKind::Button(button) => Some(button.is_disabled()),
+        HtmlElementKind::Input(input) => Some(input.is_disabled()),
+        HtmlElementKind::Select(select) => Some(select.is_disabled()),
+        _ => None,
+    }
+}
+
+/// Whether the form control is required, or `None` if the element isn't a
+/// form control that can be required.
+fn form_control_is_required(html_element: &HtmlElementKind) -> Option<bool> {
+    match html_element {
+        HtmlElementKind::Input(input) if input.supports_required() => Some(input.is_required()),
+        HtmlElementKind::Select(select) => Some(select.is_required()),
+        _ => None,
+    }
+}
+
 /// Checks whether or not the given node matches the selector.
 pub fn matches_selector(selector: &Selector, node: &NodeKind) -> bool {
+    matches_selector_with_pseudo_element(selector, node, None)
+}
+
+/// Checks whether or not the given pseudo-element of the node is matched by
+/// the selector. When `pseudo_element` is `None`, the selector is matched
+/// against the node itself, and selectors targeting a pseudo-element never
+/// match.
+///
+/// # References
+/// * [CSS - Selectors Level 4 - 3.6](https://drafts.csswg.org/selectors/#pseudo-elements)
+pub fn matches_selector_with_pseudo_element(
+    selector: &Selector,
+    node: &NodeKind,
+    pseudo_element: Option<CssPseudoElement>,
+) -> bool {
     match selector {
-        Selector::Complex(selector) => matches_selector_complex(selector, node),
-        Selector::Compound(selectors) => matches_selector_compound(selectors, node),
-        Selector::Simple(simple_selector) => matches_selector_simple(simple_selector, node),
+        Selector::Complex(selector) => matches_selector_complex(selector, node, pseudo_element),
+        Selector::Compound(selectors) => matches_selector_subject(&selectors.0, node, pseudo_element),
+        Selector::Simple(simple_selector) => {
+            matches_selector_subject(std::slice::from_ref(simple_selector), node, pseudo_element)
+        }
     }
 }
 
-fn matches_selector_complex(selector: &ComplexSelector, node: &NodeKind) -> bool {
-    matches_selector_complex_inner(&selector.topmost, &selector.combinators, node)
+fn matches_selector_complex(
+    selector: &ComplexSelector,
+    node: &NodeKind,
+    pseudo_element: Option<CssPseudoElement>,
+) -> bool {
+    let Some(((combinator, subject), rest)) = selector.combinators.split_last() else {
+        return matches_selector_subject(&selector.topmost.0, node, pseudo_element);
+    };
+
+    matches_selector_subject(&subject.0, node, pseudo_element)
+        && matches_selector_combinator(&selector.topmost, rest, *combinator, node)
+}
+
+/// Matches the rightmost compound selector, the only place where a
+/// pseudo-element may appear.
+fn matches_selector_subject(
+    selectors: &[SimpleSelector],
+    node: &NodeKind,
+    pseudo_element: Option<CssPseudoElement>,
+) -> bool {
+    let mut found_pseudo_element = None;
+
+    for selector in selectors {
+        if let SimpleSelector::PseudoElement(pseudo) = selector {
+            found_pseudo_element = Some(*pseudo);
+        } else if !matches_selector_simple(selector, node) {
+            return false;
+        }
+    }
+
+    found_pseudo_element == pseudo_element
 }
 
 /// Recursively go up the list of selectors.
@@ -247,16 +335,22 @@ fn matches_selector_complex_inner(
     rest: &[(SelectorCombinator, CompoundSelector)],
     node: &NodeKind,
 ) -> bool {
-    let Some((combinator, last)) = rest.last() else {
+    let Some(((combinator, last), rest)) = rest.split_last() else {
         return matches_selector_compound(topmost, node);
     };
 
-    if !matches_selector_compound(last, node) {
-        return false;
-    }
-
-    let rest = &rest[0..rest.len() - 1];
+    matches_selector_compound(last, node)
+        && matches_selector_combinator(topmost, rest, *combinator, node)
+}
 
+/// Checks the relation described by the `combinator` between the `node` and
+/// the elements matching the rest of the complex selector.
+fn matches_selector_combinator(
+    topmost: &CompoundSelector,
+    rest: &[(SelectorCombinator, CompoundSelector)],
+    combinator: SelectorCombinator,
+    node: &NodeKind,
+) -> bool {
     match combinator {
         SelectorCombinator::Child => {
             if let Some(parent) = node.as_node().parent().as_ref().and_then(Weak::upgrade) {
@@ -300,7 +394,7 @@ fn matches_selector_complex_inner(
 
             let pred = |child: &Node| matches_selector_complex_inner(topmost, rest, child);
 
-            if *combinator == SelectorCombinator::NextSibling {
+            if combinator == SelectorCombinator::NextSibling {
                 iter.take(1).any(pred)
             } else {
                 iter.any(pred)
@@ -310,7 +404,18 @@ fn matches_selector_complex_inner(
 }
 
 fn matches_selector_compound(selectors: &CompoundSelector, node: &NodeKind) -> bool {
-    selectors.0.iter().all(|selector| matches_selector_simple(selector, node))
+    // `:has()` is evaluated last, since it has to walk the subtree or the
+    // siblings of the node, which is expensive.
+    let is_relational = |selector: &&SimpleSelector| {
+        matches!(selector, SimpleSelector::FunctionalPseudoClass(FunctionalPseudoClassSelectorKind::Has(..)))
+    };
+
+    selectors.0.iter()
+        .filter(|selector| !is_relational(selector))
+        .all(|selector| matches_selector_simple(selector, node))
+        && selectors.0.iter()
+            .filter(is_relational)
+            .all(|selector| matches_selector_simple(selector, node))
 }
 
 fn matches_selector_simple(simple_selector: &SimpleSelector, node: &NodeKind) -> bool {
@@ -338,9 +443,17 @@ fn matches_selector_simple(simple_selector: &SimpleSelector, node: &NodeKind) ->
         }
 
         SimpleSelector::PseudoClass(pseudo_class_selector) => {
-            node.as_dom_element().is_some_and(|element| matches_pseudo_class_selector(*pseudo_class_selector, element))
+            matches_pseudo_class_selector(*pseudo_class_selector, node)
+        }
+
+        SimpleSelector::FunctionalPseudoClass(functional) => {
+            node.as_dom_element().is_some() && matches_functional_pseudo_class_selector(functional, node)
         }
 
+        // Pseudo-elements are only matched as part of the subject, see
+        // `matches_selector_subject`.
+        SimpleSelector::PseudoElement(..) => false,
+
         SimpleSelector::TypeSelector(ty) => {
             node.tag_name().is_some_and(|name| name.eq_ignore_ascii_case(ty))
         }
@@ -349,6 +462,291 @@ fn matches_selector_simple(simple_selector: &SimpleSelector, node: &NodeKind) ->
     }
 }
 
+fn matches_functional_pseudo_class_selector(
+    functional: &FunctionalPseudoClassSelectorKind,
+    node: &NodeKind,
+) -> bool {
+    let matches_any = |lists: &[SelectorList]| lists.iter().any(|list| list.matches(node));
+
+    match functional {
+        FunctionalPseudoClassSelectorKind::Has(list) => matches_has(list, node),
+
+        FunctionalPseudoClassSelectorKind::Is(lists) => matches_any(lists),
+        FunctionalPseudoClassSelectorKind::Not(lists) => !matches_any(lists),
+
+        FunctionalPseudoClassSelectorKind::NthChild(an_plus_b, of) => {
+            matches_nth_child(*an_plus_b, of.as_ref(), node, false)
+        }
+
+        FunctionalPseudoClassSelectorKind::NthLastChild(an_plus_b, of) => {
+            matches_nth_child(*an_plus_b, of.as_ref(), node, true)
+        }
+
+        FunctionalPseudoClassSelectorKind::NthLastOfType(an_plus_b) => {
+            matches_nth_of_type(*an_plus_b, node, true)
+        }
+
+        FunctionalPseudoClassSelectorKind::NthOfType(an_plus_b) => {
+            matches_nth_of_type(*an_plus_b, node, false)
+        }
+
+        FunctionalPseudoClassSelectorKind::Where(lists) => matches_any(lists),
+    }
+}
+
+thread_local! {
+    /// The cache of the [`HasMatchCache`] that is currently in scope.
+    static HAS_MATCH_CACHE: RefCell<Option<HashMap<(usize, usize), bool>>> = const { RefCell::new(None) };
+}
+
+/// Caches the results of `:has()` per node and selector list, because
+/// matching it means walking the subtree or the siblings of the node. The
+/// results are only valid as long as the DOM doesn't change, so the cache is
+/// owned by the [`StyleCollector`](crate::StyleCollector) of a single pass.
+#[derive(Debug, Default)]
+pub(crate) struct HasMatchCache {
+    results: RefCell<HashMap<(usize, usize), bool>>,
+}
+
+impl HasMatchCache {
+    /// Use this cache for all `:has()` matching done by `f`.
+    pub(crate) fn scope<R>(&self, f: impl FnOnce() -> R) -> R {
+        let results = std::mem::take(&mut *self.results.borrow_mut());
+        let previous = HAS_MATCH_CACHE.with(|cache| cache.replace(Some(results)));
+
+        let result = f();
+
+        let results = HAS_MATCH_CACHE.with(|cache| cache.replace(previous));
+        *self.results.borrow_mut() = results.unwrap_or_default();
+        result
+    }
+}
+
+/// Match the `:has()` pseudo-class, which matches if any element relative to
+/// the `anchor` matches one of the relative selectors.
+///
+/// [Selectors Level 4 § 4.5](https://drafts.csswg.org/selectors/#relational)
+fn matches_has(list: &RelativeSelectorList, anchor: &NodeKind) -> bool {
+    let key = (anchor as *const NodeKind as usize, list as *const RelativeSelectorList as usize);
+
+    let cached = HAS_MATCH_CACHE.with(|cache| {
+        cache.borrow().as_ref().and_then(|results| results.get(&key).copied())
+    });
+    if let Some(cached) = cached {
+        return cached;
+    }
+
+    let result = list.items.iter().any(|relative| matches_relative_selector(relative, anchor));
+
+    HAS_MATCH_CACHE.with(|cache| {
+        if let Some(results) = cache.borrow_mut().as_mut() {
+            results.insert(key, result);
+        }
+    });
+
+    result
+}
+
+fn matches_relative_selector(relative: &RelativeSelector, anchor: &NodeKind) -> bool {
+    let matches_subject = |node: &NodeKind| {
+        matches_relative_selector_inner(
+            anchor,
+            relative.combinator,
+            &relative.selector.topmost,
+            &relative.selector.combinators,
+            node,
+        )
+    };
+
+    match relative.combinator {
+        // The subject is always in the subtree of the anchor.
+        SelectorCombinator::Child | SelectorCombinator::Descendant => {
+            any_descendant(anchor, &matches_subject)
+        }
+
+        // The subject is a following sibling, or is in the subtree of one.
+        SelectorCombinator::NextSibling | SelectorCombinator::SubsequentSibling => {
+            following_element_siblings(anchor).iter().any(|sibling| {
+                matches_subject(sibling.as_ref()) || any_descendant(sibling, &matches_subject)
+            })
+        }
+    }
+}
+
+/// Like [`matches_selector_complex_inner`], but the element matching the
+/// `topmost` compound selector must also have the relation described by the
+/// `leading` combinator to the `anchor`.
+fn matches_relative_selector_inner(
+    anchor: &NodeKind,
+    leading: SelectorCombinator,
+    topmost: &CompoundSelector,
+    rest: &[(SelectorCombinator, CompoundSelector)],
+    node: &NodeKind,
+) -> bool {
+    let Some(((combinator, last), rest)) = rest.split_last() else {
+        return matches_selector_compound(topmost, node) && is_related_to(anchor, leading, node);
+    };
+
+    if !matches_selector_compound(last, node) {
+        return false;
+    }
+
+    let matches_rest = |node: &NodeKind| matches_relative_selector_inner(anchor, leading, topmost, rest, node);
+    match combinator {
+        SelectorCombinator::Child => parent_of(node).is_some_and(|parent| matches_rest(parent.as_ref())),
+        SelectorCombinator::Descendant => ancestors_of(node).iter().any(|ancestor| matches_rest(ancestor.as_ref())),
+        SelectorCombinator::NextSibling => preceding_element_siblings(node).first().is_some_and(|sibling| matches_rest(sibling.as_ref())),
+        SelectorCombinator::SubsequentSibling => preceding_element_siblings(node).iter().any(|sibling| matches_rest(sibling.as_ref())),
+    }
+}
+
+/// Check whether the `node` has the relation described by the `combinator`
+/// to the `anchor`, e.g. for `>` if the `anchor` is the parent of `node`.
+fn is_related_to(anchor: &NodeKind, combinator: SelectorCombinator, node: &NodeKind) -> bool {
+    let is_anchor = |candidate: &Node| std::ptr::eq(candidate.as_ref(), anchor);
+
+    match combinator {
+        SelectorCombinator::Child => parent_of(node).as_ref().is_some_and(is_anchor),
+        SelectorCombinator::Descendant => ancestors_of(node).iter().any(is_anchor),
+        SelectorCombinator::NextSibling => preceding_element_siblings(node).first().is_some_and(is_anchor),
+        SelectorCombinator::SubsequentSibling => preceding_element_siblings(node).iter().any(is_anchor),
+    }
+}
+
+fn parent_of(node: &NodeKind) -> Option<Node> {
+    node.as_node().parent().as_ref().and_then(Weak::upgrade).map(Node::from)
+}
+
+/// Get the ancestors of the `node`, starting with its parent.
+fn ancestors_of(node: &NodeKind) -> Vec<Node> {
+    let mut ancestors = Vec::new();
+    let mut current = parent_of(node);
+    while let Some(ancestor) = current {
+        current = parent_of(&ancestor);
+        ancestors.push(ancestor);
+    }
+    ancestors
+}
+
+/// Get the element siblings before the `node`, starting with the closest.
+fn preceding_element_siblings(node: &NodeKind) -> Vec<Node> {
+    let Some(parent) = parent_of(node) else {
+        return Vec::new();
+    };
+
+    let children = parent
+        .as_parent_node()
+        .unwrap()
+        .children();
+
+    let mut siblings: Vec<Node> = children.iter()
+        .take_while(|child| !std::ptr::eq(child.as_ref(), node))
+        .filter(|child| child.is_element())
+        .cloned()
+        .collect();
+    siblings.reverse();
+    siblings
+}
+
+/// Get the element siblings after the `node`, starting with the closest.
+fn following_element_siblings(node: &NodeKind) -> Vec<Node> {
+    let Some(parent) = parent_of(node) else {
+        return Vec::new();
+    };
+
+    let children = parent
+        .as_parent_node()
+        .unwrap()
+        .children();
+
+    children.iter()
+        .skip_while(|child| !std::ptr::eq(child.as_ref(), node))
+        .skip(1)
+        .filter(|child| child.is_element())
+        .cloned()
+        .collect()
+}
+
+/// Check whether any element descendant of the `node` satisfies the
+/// `predicate`, in tree order.
+fn any_descendant(node: &NodeKind, predicate: &dyn Fn(&NodeKind) -> bool) -> bool {
+    let Some(parent) = node.as_parent_node() else {
+        return false;
+    };
+
+    let children = parent.children();
+    children.iter()
+        .filter(|child| child.is_element())
+        .any(|child| predicate(child.as_ref()) || any_descendant(child, predicate))
+}
+
+/// Match the `:nth-child()` and `:nth-last-child()` pseudo-classes. When the
+/// `of S` argument is given, only the siblings matching `S` are counted, and
+/// the element itself must match `S` as well.
+///
+/// [Selectors Level 4 § 14.4.1](https://drafts.csswg.org/selectors/#the-nth-child-pseudo)
+fn matches_nth_child(
+    an_plus_b: AnPlusB,
+    of: Option<&SelectorList>,
+    node: &NodeKind,
+    from_end: bool,
+) -> bool {
+    let Some(of) = of else {
+        return matches_nth_sibling(an_plus_b, node, from_end, |_| true);
+    };
+
+    of.matches(node) && matches_nth_sibling(an_plus_b, node, from_end, |sibling| of.matches(sibling))
+}
+
+/// Match the `:nth-of-type()` and `:nth-last-of-type()` pseudo-classes, which
+/// only count the siblings with the same element type.
+///
+/// [Selectors Level 4 § 14.4.3](https://drafts.csswg.org/selectors/#the-nth-of-type-pseudo)
+fn matches_nth_of_type(an_plus_b: AnPlusB, node: &NodeKind, from_end: bool) -> bool {
+    let Some(tag_name) = node.tag_name() else {
+        return false;
+    };
+
+    matches_nth_sibling(an_plus_b, node, from_end, |sibling| sibling.tag_name() == Some(tag_name))
+}
+
+/// Check whether the 1-based index of the `node` amongst its element siblings
+/// that satisfy the `predicate` is represented by `an_plus_b`, counting from
+/// the last sibling if `from_end` is set.
+fn matches_nth_sibling(
+    an_plus_b: AnPlusB,
+    node: &NodeKind,
+    from_end: bool,
+    predicate: impl Fn(&NodeKind) -> bool,
+) -> bool {
+    let Some(parent) = node.as_node().parent().as_ref().and_then(Weak::upgrade) else {
+        return false;
+    };
+
+    let children = parent
+        .as_parent_node()
+        .unwrap()
+        .children();
+
+    let mut index = 0;
+    let mut find_index = |child: &Node| {
+        if !child.is_element() || !predicate(child) {
+            return false;
+        }
+
+        index += 1;
+        std::ptr::eq(child.as_ref(), node)
+    };
+
+    let found = if from_end {
+        children.iter().rev().any(&mut find_index)
+    } else {
+        children.iter().any(&mut find_index)
+    };
+
+    found && an_plus_b.matches(index)
+}
+
 /// A simple extension trait to be able to call `Selector::matches`.
 pub trait SelectorMatcher {
     /// Checks whether or not the given node matches the selector.
@@ -357,20 +755,146 @@ pub trait SelectorMatcher {
     }
 
     fn most_specific_match(&self, node: &NodeKind) -> Option<&Selector>;
+
+    /// Like [`SelectorMatcher::most_specific_match`], but for the given
+    /// pseudo-element of the node, e.g. `p::before`.
+    fn most_specific_pseudo_element_match(
+        &self,
+        node: &NodeKind,
+        pseudo_element: CssPseudoElement,
+    ) -> Option<&Selector>;
 }
 
 impl SelectorMatcher for Selector {
     fn most_specific_match(&self, node: &NodeKind) -> Option<&Selector> {
         matches_selector(self, node).then_some(self)
     }
+
+    fn most_specific_pseudo_element_match(
+        &self,
+        node: &NodeKind,
+        pseudo_element: CssPseudoElement,
+    ) -> Option<&Selector> {
+        matches_selector_with_pseudo_element(self, node, Some(pseudo_element)).then_some(self)
+    }
 }
 
 impl SelectorMatcher for SelectorList {
     fn most_specific_match(&self, node: &NodeKind) -> Option<&Selector> {
-        self.items.iter().filter(|selector| selector.matches(node)).max()
+        self.items.iter()
+            .filter(|selector| selector.matches(node))
+            .max_by_key(|selector| selector.calculate_specificity())
+    }
+
+    fn most_specific_pseudo_element_match(
+        &self,
+        node: &NodeKind,
+        pseudo_element: CssPseudoElement,
+    ) -> Option<&Selector> {
+        self.items.iter()
+            .filter_map(|selector| selector.most_specific_pseudo_element_match(node, pseudo_element))
+            .max_by_key(|selector| selector.calculate_specificity())
+    }
+}
+
+/// The selector-based methods of the [Interface `Element`][spec]. These can't
+/// live in `retina-dom` itself, since selector matching is part of the style
+/// system, which depends on the DOM.
+///
+/// [spec]: https://dom.spec.whatwg.org/#interface-element
+pub trait ElementSelectorExt {
+    /// Returns `true` if the element matches any of the `selectors`. Invalid
+    /// selectors and non-element nodes never match.
+    ///
+    /// [DOM Living Standard § 4.9 `matches()`](https://dom.spec.whatwg.org/#dom-element-matches)
+    fn matches(&self, selectors: &str) -> bool;
+
+    /// Returns the first inclusive ancestor element that matches any of the
+    /// `selectors`, walking up until the root of the tree.
+    ///
+    /// [DOM Living Standard § 4.9 `closest()`](https://dom.spec.whatwg.org/#dom-element-closest)
+    fn closest(&self, selectors: &str) -> Option<Node>;
+
+    /// Returns the first descendant element in tree order that matches any of
+    /// the `selectors`. Works for both document and element nodes.
+    ///
+    /// [DOM Living Standard § 4.2.6 `querySelector()`](https://dom.spec.whatwg.org/#dom-parentnode-queryselector)
+    fn query_selector(&self, selectors: &str) -> Result<Option<Node>, InvalidSelectorError>;
+
+    /// Returns all descendant elements in tree order that match any of the
+    /// `selectors`.
+    ///
+    /// [DOM Living Standard § 4.2.6 `querySelectorAll()`](https://dom.spec.whatwg.org/#dom-parentnode-queryselectorall)
+    fn query_selector_all(&self, selectors: &str) -> Result<Vec<Node>, InvalidSelectorError>;
+}
+
+/// The `selectors` given to an [`ElementSelectorExt`] method couldn't be
+/// parsed, which corresponds to a `SyntaxError` `DOMException`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct InvalidSelectorError;
+
+impl ElementSelectorExt for Node {
+    fn matches(&self, selectors: &str) -> bool {
+        if !self.is_element() {
+            return false;
+        }
+
+        retina_style_parser::parse_selectors(selectors)
+            .ok()
+            .is_some_and(|list| list.matches(self))
+    }
+
+    fn closest(&self, selectors: &str) -> Option<Node> {
+        let list = retina_style_parser::parse_selectors(selectors).ok()?;
+
+        let mut node = self.clone();
+        loop {
+            if node.is_element() && list.matches(&node) {
+                return Some(node);
+            }
+
+            let parent = node.as_node().parent().as_ref().and_then(Weak::upgrade)?;
+            node = Node::from(parent);
+        }
+    }
+
+    fn query_selector(&self, selectors: &str) -> Result<Option<Node>, InvalidSelectorError> {
+        let list = retina_style_parser::parse_selectors(selectors)
+            .map_err(|_| InvalidSelectorError)?;
+        Ok(find_descendant_matching(self, &list))
+    }
+
+    fn query_selector_all(&self, selectors: &str) -> Result<Vec<Node>, InvalidSelectorError> {
+        let list = retina_style_parser::parse_selectors(selectors)
+            .map_err(|_| InvalidSelectorError)?;
+
+        let mut nodes = Vec::new();
+        self.for_each_child_node_recursive_handle(&mut |node| {
+            if node.is_element() && list.matches(node) {
+                nodes.push(node.clone());
+            }
+        });
+        Ok(nodes)
     }
 }
 
+/// Depth-first search for the first descendant element matching `list`,
+/// stopping as soon as one is found.
+fn find_descendant_matching(node: &NodeKind, list: &SelectorList) -> Option<Node> {
+    let parent = node.as_parent_node()?;
+    for child in parent.children().iter() {
+        if child.is_element() && list.matches(child) {
+            return Some(child.clone());
+        }
+
+        if let Some(found) = find_descendant_matching(child, list) {
+            return Some(found);
+        }
+    }
+
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -610,6 +1134,255 @@ mod tests {
         assert!(universal_selector_in_selector_list.matches(&node) == should_match);
     }
 
+    #[rstest]
+    #[case("p", true)]
+    #[case("div", false)]
+    #[case("div, p", true)]
+    #[case(".intro", true)]
+    #[case("p.intro#first", true)]
+    #[case("p.outro", false)]
+    #[case("[data-x]", false)]
+    #[case("", false)]
+    #[case("p {", false)]
+    #[case("p,", false)]
+    fn element_matches_selectors(#[case] selectors: &str, #[case] should_match: bool) {
+        let mut element = HtmlElementKind::Unknown(HtmlUnknownElement::new(qual_name("p")));
+        element.as_dom_element_mut().attributes_mut().set("class", "intro".into());
+        element.as_dom_element_mut().attributes_mut().set("id", "first".into());
+
+        let node = Node::new(NodeKind::HtmlElement(element));
+        assert_eq!(ElementSelectorExt::matches(&node, selectors), should_match);
+    }
+
+    #[test]
+    fn user_action_pseudo_classes() {
+        let element = HtmlElementKind::Unknown(HtmlUnknownElement::new(qual_name("a")));
+        let node = NodeKind::HtmlElement(element);
+        let hover = Selector::Simple(SimpleSelector::PseudoClass(PseudoClassSelectorKind::Hover));
+        let focus = Selector::Simple(SimpleSelector::PseudoClass(PseudoClassSelectorKind::Focus));
+
+        assert!(!hover.matches(&node));
+        assert!(!focus.matches(&node));
+
+        node.as_dom_element().unwrap().set_hovered(true);
+        assert!(hover.matches(&node));
+        assert!(!focus.matches(&node));
+
+        node.as_dom_element().unwrap().set_hovered(false);
+        node.as_dom_element().unwrap().set_focused(true);
+        assert!(!hover.matches(&node));
+        assert!(focus.matches(&node));
+    }
+
+    #[rstest]
+    #[case("p::before", None, false)]
+    #[case("p::before", Some(CssPseudoElement::Before), true)]
+    #[case("p::before", Some(CssPseudoElement::After), false)]
+    #[case("p:after", Some(CssPseudoElement::After), true)]
+    #[case("div::before", Some(CssPseudoElement::Before), false)]
+    #[case(".intro::after", Some(CssPseudoElement::After), true)]
+    #[case("p", Some(CssPseudoElement::Before), false)]
+    fn match_pseudo_element(
+        #[case] selectors: &str,
+        #[case] pseudo_element: Option<CssPseudoElement>,
+        #[case] should_match: bool,
+    ) {
+        let mut element = HtmlElementKind::Unknown(HtmlUnknownElement::new(qual_name("p")));
+        element.as_dom_element_mut().attributes_mut().set("class", "intro".into());
+        let node = NodeKind::HtmlElement(element);
+
+        let list = retina_style_parser::parse_selectors(selectors).unwrap();
+        let matches = list.items.iter()
+            .any(|selector| matches_selector_with_pseudo_element(selector, &node, pseudo_element));
+        assert_eq!(matches, should_match);
+    }
+
+    #[rstest]
+    #[case("span", Some("span"))]
+    #[case("p", Some("p"))]
+    #[case(".container", Some("div"))]
+    #[case("div > p", Some("p"))]
+    #[case("section", None)]
+    #[case("p {", None)]
+    fn element_closest(#[case] selectors: &str, #[case] expected_tag_name: Option<&str>) {
+        let document = Parser::parse("<div class=container><p><span id=start>Hello</span></p></div>");
+
+        let mut start = None;
+        document.for_each_child_node_recursive_handle(&mut |node| {
+            if node.as_dom_element().is_some_and(|element| element.id() == "start") {
+                start = Some(node.clone());
+            }
+        });
+
+        let closest = start.unwrap().closest(selectors);
+        assert_eq!(closest.as_ref().and_then(|node| node.tag_name()), expected_tag_name);
+    }
+
+    #[rstest]
+    #[case("p", Some("a"))]
+    #[case(".x", Some("b"))]
+    #[case("div > span", Some("c"))]
+    #[case("section p", None)]
+    fn document_query_selector(#[case] selectors: &str, #[case] expected_id: Option<&str>) {
+        let document = Parser::parse("<div><p id=a><span id=b class=x></span></p><span id=c class=x></span></div>");
+
+        let found = document.query_selector(selectors).unwrap();
+        assert_eq!(found.as_ref().and_then(|node| node.as_dom_element()).map(|element| element.id()), expected_id);
+    }
+
+    #[rstest]
+    #[case("span", &["b", "c"])]
+    #[case(".x", &["b", "c"])]
+    #[case("p span", &["b"])]
+    #[case("p, span", &["a", "b", "c"])]
+    fn document_query_selector_all(#[case] selectors: &str, #[case] expected_ids: &[&str]) {
+        let document = Parser::parse("<div><p id=a><span id=b class=x></span></p><span id=c class=x></span></div>");
+
+        let ids: Vec<_> = document.query_selector_all(selectors)
+            .unwrap()
+            .iter()
+            .map(|node| node.as_dom_element().unwrap().id().to_string())
+            .collect();
+        assert_eq!(ids, expected_ids);
+    }
+
+    #[test]
+    fn element_query_selector_is_scoped() {
+        let document = Parser::parse("<div><p id=a><span id=b></span></p><span id=c></span></div>");
+        let paragraph = document.as_document().unwrap().get_element_by_id("a").unwrap();
+
+        let ids: Vec<_> = paragraph.query_selector_all("span")
+            .unwrap()
+            .iter()
+            .map(|node| node.as_dom_element().unwrap().id().to_string())
+            .collect();
+        assert_eq!(ids, ["b"]);
+        assert!(paragraph.query_selector("p").unwrap().is_none());
+        assert!(paragraph.as_dom_element().unwrap().get_element_by_id("c").is_none());
+    }
+
+    #[rstest]
+    #[case("p {")]
+    #[case("p,")]
+    #[case("")]
+    fn query_selector_invalid(#[case] selectors: &str) {
+        let document = Parser::parse("<p></p>");
+
+        assert_eq!(document.query_selector(selectors), Err(InvalidSelectorError));
+        assert_eq!(document.query_selector_all(selectors), Err(InvalidSelectorError));
+    }
+
+    #[rstest]
+    #[case(":nth-child(2n+1)", &["a", "c", "e"])]
+    #[case(":nth-child(odd)", &["a", "c", "e"])]
+    #[case(":nth-child(even)", &["b", "d"])]
+    #[case(":nth-child(-n+2)", &["a", "b"])]
+    #[case(":nth-child(3)", &["c"])]
+    #[case(":nth-child(2 of .x)", &["d"])]
+    #[case(":nth-child(odd of li)", &["a", "d"])]
+    #[case(":nth-last-child(1)", &["e"])]
+    #[case(":nth-last-child(2 of .x)", &["b"])]
+    #[case("li:nth-of-type(3)", &["d"])]
+    #[case("[id]:nth-last-of-type(1)", &["c", "e"])]
+    fn nth_child(#[case] selectors: &str, #[case] expected_ids: &[&str]) {
+        let document = Parser::parse("<ul><li id=a></li><li id=b class=x></li><p id=c></p><li id=d class=x></li><li id=e></li></ul>");
+
+        let mut ids = Vec::new();
+        document.for_each_child_node_recursive_handle(&mut |node| {
+            let Some(element) = node.as_dom_element() else {
+                return;
+            };
+
+            if !element.id().is_empty() && ElementSelectorExt::matches(node, selectors) {
+                ids.push(element.id().to_string());
+            }
+        });
+
+        assert_eq!(ids, expected_ids);
+    }
+
+    #[rstest]
+    #[case(":checked", &["checkbox"])]
+    #[case(":disabled", &["text", "button"])]
+    #[case(":enabled", &["checkbox", "radio", "hidden", "select"])]
+    #[case(":required", &["checkbox", "select"])]
+    #[case(":optional", &["text", "radio"])]
+    fn form_pseudo_classes(#[case] selectors: &str, #[case] expected_ids: &[&str]) {
+        let document = Parser::parse(concat!(
+            "<form id=form>",
+            "<input id=text disabled>",
+            "<input id=checkbox type=checkbox checked required>",
+            "<input id=radio type=radio>",
+            "<input id=hidden type=hidden checked required>",
+            "<select id=select required></select>",
+            "<button id=button disabled></button>",
+            "</form>",
+        ));
+
+        let mut ids = Vec::new();
+        document.for_each_child_node_recursive_handle(&mut |node| {
+            let Some(element) = node.as_dom_element() else {
+                return;
+            };
+
+            if !element.id().is_empty() && ElementSelectorExt::matches(node, selectors) {
+                ids.push(element.id().to_string());
+            }
+        });
+
+        assert_eq!(ids, expected_ids);
+    }
+
+    #[rstest]
+    #[case("div:has(p)", &["outer", "inner"])]
+    #[case("div:has(> p)", &["inner"])]
+    #[case("div:has(> span)", &["outer"])]
+    #[case("div:has(span img)", &["outer"])]
+    #[case("div:has(> span > img)", &["outer"])]
+    #[case("div:has(+ section)", &["outer"])]
+    #[case("div:has(~ section .note)", &["outer"])]
+    #[case("div:has(~ p)", &[])]
+    #[case("div:has(.missing, img)", &["outer"])]
+    #[case(":has(> .note)", &["after"])]
+    fn has(#[case] selectors: &str, #[case] expected_ids: &[&str]) {
+        let document = Parser::parse(concat!(
+            "<div id=outer><span><img></span><div id=inner><p></p></div></div>",
+            "<section id=after><b class=note></b></section>",
+        ));
+
+        let mut ids = Vec::new();
+        document.for_each_child_node_recursive_handle(&mut |node| {
+            let Some(element) = node.as_dom_element() else {
+                return;
+            };
+
+            if !element.id().is_empty() && ElementSelectorExt::matches(node, selectors) {
+                ids.push(element.id().to_string());
+            }
+        });
+
+        assert_eq!(ids, expected_ids);
+    }
+
+    #[test]
+    fn has_match_cache() {
+        let document = Parser::parse("<div id=outer><p></p></div>");
+        let list = retina_style_parser::parse_selectors("div:has(p)").unwrap();
+        let cache = HasMatchCache::default();
+
+        let mut outer = None;
+        document.for_each_child_node_recursive_handle(&mut |node| {
+            if node.as_dom_element().is_some_and(|element| element.id() == "outer") {
+                outer = Some(node.clone());
+            }
+        });
+        let outer = outer.unwrap();
+
+        assert!(cache.scope(|| list.matches(&outer)));
+        assert_eq!(cache.results.borrow().len(), 1);
+        assert!(cache.scope(|| list.matches(&outer)));
+    }
+
     fn impl_matches_attribute_selector_for_element(
         attr_name: &str,
         attr_value: &str,
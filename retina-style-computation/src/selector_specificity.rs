@@ -10,6 +10,7 @@ use std::{
 use retina_style::{
     ComplexSelector,
     CompoundSelector,
+    FunctionalPseudoClassSelectorKind,
     Selector,
     SimpleSelector,
 };
@@ -142,7 +143,11 @@ impl CalculateSpecificity for SimpleSelector {
                 ..Default::default()
             },
 
-            SimpleSelector::TypeSelector(..) => SelectorSpecificity {
+            SimpleSelector::FunctionalPseudoClass(functional) => functional.calculate_specificity(),
+
+            SimpleSelector::PseudoElement(..)
+                | SimpleSelector::TypeSelector(..)
+            => SelectorSpecificity {
                 type_and_pseudo_element_selectors: 1,
                 ..Default::default()
             },
@@ -152,9 +157,65 @@ impl CalculateSpecificity for SimpleSelector {
     }
 }
 
+impl CalculateSpecificity for FunctionalPseudoClassSelectorKind {
+    /// > The specificity of an :is(), :not(), or :has() pseudo-class is
+    /// > replaced by the specificity of the most specific complex selector in
+    /// > its selector list argument.
+    /// >
+    /// > The specificity of an :nth-child() or :nth-last-child() selector is
+    /// > the specificity of the pseudo class itself (counting as one
+    /// > pseudo-class selector) plus the specificity of the most specific
+    /// > complex selector in its selector list argument (if any).
+    /// >
+    /// > The specificity of a :where() pseudo-class is replaced by zero.
+    ///
+    /// [Selectors Level 4 § 17](https://drafts.csswg.org/selectors/#specificity-rules)
+    fn calculate_specificity(&self) -> SelectorSpecificity {
+        let pseudo_class = SelectorSpecificity::new(0, 1, 0);
+
+        let lists = match self {
+            Self::Has(list) => {
+                return list.items.iter()
+                    .map(|relative| relative.selector.calculate_specificity())
+                    .max()
+                    .unwrap_or_default();
+            }
+
+            Self::Is(lists) | Self::Not(lists) => lists,
+            Self::Where(..) => return SelectorSpecificity::default(),
+
+            Self::NthChild(_, of) | Self::NthLastChild(_, of) => {
+                let of = of.iter()
+                    .flat_map(|list| list.items.iter())
+                    .map(CalculateSpecificity::calculate_specificity)
+                    .max()
+                    .unwrap_or_default();
+                return pseudo_class + of;
+            }
+
+            Self::NthLastOfType(..) | Self::NthOfType(..) => return pseudo_class,
+        };
+
+        lists.iter()
+            .flat_map(|list| list.items.iter())
+            .map(CalculateSpecificity::calculate_specificity)
+            .max()
+            .unwrap_or_default()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use retina_style::{AttributeSelector, AttributeSelectorCaseSensitivity, AttributeSelectorKind};
+    use retina_style::{
+        AnPlusB,
+        AttributeSelector,
+        AttributeSelectorCaseSensitivity,
+        AttributeSelectorKind,
+        RelativeSelector,
+        RelativeSelectorList,
+        SelectorCombinator,
+        SelectorList,
+    };
     use rstest::rstest;
     use super::*;
     use pretty_assertions::assert_eq;
@@ -164,6 +225,17 @@ mod tests {
     #[case(selector(class("my-post")), SelectorSpecificity::new(0, 1, 0))]
     #[case(selector(attr_name("my-data")), SelectorSpecificity::new(0, 1, 0))]
     #[case(selector(ty("article")), SelectorSpecificity::new(0, 0, 1))]
+    #[case(compound(&[ty("a"), class("b"), id("c"), attr_name("d")]), SelectorSpecificity::new(1, 2, 1))]
+    #[case(compound(&[ty("a"), not(&[id("b")])]), SelectorSpecificity::new(1, 0, 1))]
+    #[case(selector(is(&[ty("ol"), ty("ul"), class("menu")])), SelectorSpecificity::new(0, 1, 0))]
+    #[case(compound(&[class("a"), where_(&[id("b"), class("c")])]), SelectorSpecificity::new(0, 1, 0))]
+    #[case(compound(&[ty("div"), has(&[class("a"), id("b")])]), SelectorSpecificity::new(1, 0, 1))]
+    #[case(compound(&[ty("li"), nth_child(None)]), SelectorSpecificity::new(0, 1, 1))]
+    #[case(selector(nth_child(Some(&[ty("li"), id("a")]))), SelectorSpecificity::new(1, 1, 0))]
+    #[case(Selector::Complex(ComplexSelector {
+        topmost: CompoundSelector(vec![ty("ul"), class("list")]),
+        combinators: vec![(SelectorCombinator::Child, CompoundSelector(vec![ty("li"), id("first")]))],
+    }), SelectorSpecificity::new(1, 1, 2))]
     fn specificity(#[case] selector: Selector, #[case] spec: SelectorSpecificity) {
         assert_eq!(selector.calculate_specificity(), spec);
     }
@@ -214,6 +286,46 @@ mod tests {
         Selector::Simple(simple)
     }
 
+    fn compound(selectors: &[SimpleSelector]) -> Selector {
+        Selector::Compound(CompoundSelector(selectors.to_vec()))
+    }
+
+    fn selector_list(selectors: &[SimpleSelector]) -> Vec<SelectorList> {
+        vec![SelectorList {
+            items: selectors.iter().cloned().map(Selector::Simple).collect(),
+        }]
+    }
+
+    fn is(selectors: &[SimpleSelector]) -> SimpleSelector {
+        SimpleSelector::FunctionalPseudoClass(FunctionalPseudoClassSelectorKind::Is(selector_list(selectors)))
+    }
+
+    fn not(selectors: &[SimpleSelector]) -> SimpleSelector {
+        SimpleSelector::FunctionalPseudoClass(FunctionalPseudoClassSelectorKind::Not(selector_list(selectors)))
+    }
+
+    fn where_(selectors: &[SimpleSelector]) -> SimpleSelector {
+        SimpleSelector::FunctionalPseudoClass(FunctionalPseudoClassSelectorKind::Where(selector_list(selectors)))
+    }
+
+    fn has(selectors: &[SimpleSelector]) -> SimpleSelector {
+        let items = selectors.iter()
+            .map(|selector| RelativeSelector {
+                combinator: SelectorCombinator::Child,
+                selector: ComplexSelector {
+                    topmost: CompoundSelector(vec![selector.clone()]),
+                    combinators: Vec::new(),
+                },
+            })
+            .collect();
+        SimpleSelector::FunctionalPseudoClass(FunctionalPseudoClassSelectorKind::Has(RelativeSelectorList { items }))
+    }
+
+    fn nth_child(of: Option<&[SimpleSelector]>) -> SimpleSelector {
+        let of = of.map(|selectors| selector_list(selectors).remove(0));
+        SimpleSelector::FunctionalPseudoClass(FunctionalPseudoClassSelectorKind::NthChild(AnPlusB::new(2, 1), of))
+    }
+
     fn ty(name: &str) -> SimpleSelector {
         SimpleSelector::TypeSelector(name.into())
     }
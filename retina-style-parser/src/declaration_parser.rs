@@ -4,13 +4,20 @@
 use cssparser::Parser;
 
 use retina_style::{
+    CssCustomProperty,
     Declaration,
     Property,
+    Value,
 };
 
 use super::{
     parse_value,
     RetinaStyleParseError,
+    variable::{
+        consume_raw_value,
+        contains_var_function,
+        is_custom_property_name,
+    },
 };
 
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
@@ -25,10 +32,29 @@ impl<'i> cssparser::DeclarationParser<'i> for DeclarationParser {
         name: cssparser::CowRcStr<'i>,
         input: &mut Parser<'i, 't>,
     ) -> Result<Self::Declaration, cssparser::ParseError<'i, Self::Error>> {
-        let Some(property) = Property::parse(name.as_ref()) else {
+        if is_custom_property_name(&name) {
+            let value = Value::CustomProperty(CssCustomProperty {
+                name: name.to_string(),
+                value: consume_raw_value(input).into(),
+            });
+
+            return Ok(Declaration::new(Property::Custom, value));
+        }
+
+        let Some(property) = Property::parse(name.as_ref()).filter(|property| *property != Property::Custom) else {
             return Err(input.new_custom_error(RetinaStyleParseError::UnknownProperty(name)));
         };
 
+        // Values with `var()` references can only be parsed once the custom
+        // properties are known, which is at computed-value time.
+        let start = input.state();
+        if contains_var_function(input) {
+            input.reset(&start);
+            let value = consume_raw_value(input);
+            return Ok(Declaration::new(property, Value::VariableReference(value.into())));
+        }
+        input.reset(&start);
+
         parse_value(input, property).map(|value| Declaration::new(property, value))
     }
 }
@@ -18,24 +18,66 @@ const REDUCE_CONTEXT_SUFFIX: usize = 20;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum RetinaStyleParseError<'i> {
+    AngleUnknownUnit(CowRcStr<'i>),
+
+    BorderSpacingInvalidLength,
+
+    BoxShadowInvalidLength,
+    BoxShadowMissingOffsets,
+    BoxShadowNegativeBlurRadius,
+
+    CalcInvalidExpression,
+    CalcUnexpectedToken(Token<'i>),
+
+    ColorMixCurrentColorNotSupported,
+    ColorMixPercentagesSumToZero,
+    ColorMixUnknownColorSpace(CowRcStr<'i>),
     ColorUnknownValue(cssparser::Color),
 
     ComponentListUnknownKinds(Vec<Value>),
 
     ExpectedIdentifierAsPropertyValue,
 
+    EasingFunctionUnknownFunction(CowRcStr<'i>),
+    EasingFunctionUnknownKeyword(CowRcStr<'i>),
+
+    FlexBasisNegative,
+    FlexFactorNegative,
+
     FloatUnknownKeyword(CowRcStr<'i>),
 
+    AspectRatioNegative,
+
     AtFontFaceInvalidProperty(CowRcStr<'i>),
 
+    AtImportUnexpectedBlock,
+
+    AtKeyframesInvalidName(CowRcStr<'i>),
+
+    AtLayerBlockWithMultipleNames,
+
     FontKerningUnknownKeyword(CowRcStr<'i>),
     FontSizeUnknownKeyword(CowRcStr<'i>),
     FontStyleUnknownKeyword(CowRcStr<'i>),
     FontVariantLigaturesUnknownKeyword(CowRcStr<'i>),
 
+    GradientTooFewColorStops,
+    GradientUnknownSideKeyword(CowRcStr<'i>),
+
+    GridLineInvalidSpan,
+    GridRepeatInvalidCount,
+    GridTemplateAreasInvalidString,
+    GridTemplateAreasNonRectangularArea,
+    GridTemplateAreasUnequalColumnCounts,
+    GridTrackBreadthNegative,
+    GridTrackListEmpty,
+    GridTrackSizeFlexibleMinimum,
+
     ImageUnexpectedFunction(CowRcStr<'i>),
     ImageUnexpectedToken(Token<'i>),
 
+    KeyframeSelectorUnknownKeyword(CowRcStr<'i>),
+
     LengthUnexpectedToken(Token<'i>),
     LengthUnknownIdentifier(CowRcStr<'i>),
     LengthUnknownUnit(CowRcStr<'i>),
@@ -46,6 +88,24 @@ pub enum RetinaStyleParseError<'i> {
 
     MediaPreludeUnknownType(CowRcStr<'i>),
 
+    OutlineStyleHidden,
+
+    PositionInvalidKeywordCombination,
+    PositionUnknownKeyword(CowRcStr<'i>),
+
+    SizeConstraintNegative,
+    SizeConstraintUnexpectedAuto,
+
+    SpacingUnexpectedAuto,
+
+    TextIndentUnexpectedAuto,
+
+    TimeUnknownUnit(CowRcStr<'i>),
+
+    TransformUnknownFunction(CowRcStr<'i>),
+
+    TransitionUnknownProperty(CowRcStr<'i>),
+
     UnexpectedEofBasicColorKeyword,
 
     UnknownAtRule(CowRcStr<'i>),
@@ -55,9 +115,13 @@ pub enum RetinaStyleParseError<'i> {
     UnknownProperty(CowRcStr<'i>),
     UnknownSelector(Token<'i>),
     UnknownSelectorPseudoClass(CowRcStr<'i>),
+    UnknownSelectorPseudoElement(CowRcStr<'i>),
     UnknownValue(Option<Token<'i>>),
     UnknownWhiteSpaceKeyword,
 
+    VarFunctionInvalidName(CowRcStr<'i>),
+    VarFunctionUndefinedVariable(CowRcStr<'i>),
+
     WideKeywordsNotYetSupported,
 
     AttributeSelectorExpectedIdentifierAsAttributeName(Token<'i>),
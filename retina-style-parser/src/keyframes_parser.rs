@@ -0,0 +1,106 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! Parsing of the keyframe blocks inside of the `@keyframes` rule.
+//!
+//! # References
+//! * [CSS Animations Level 1 § 3. Declaring Keyframes](https://drafts.csswg.org/css-animations/#keyframes)
+
+use cssparser::{
+    Parser,
+    RuleBodyParser,
+    Token,
+};
+use retina_style::{
+    CssDecimal,
+    Keyframe,
+};
+
+use crate::{
+    Context,
+    ParseError,
+    RetinaStyleParseError,
+    declaration_parser::DeclarationParser,
+};
+
+#[derive(Debug)]
+pub(crate) struct KeyframesParser<'context> {
+    pub(crate) context: &'context mut Context,
+}
+
+impl<'i, 'context> cssparser::QualifiedRuleParser<'i> for KeyframesParser<'context> {
+    type Prelude = Vec<CssDecimal>;
+    type QualifiedRule = Keyframe;
+    type Error = RetinaStyleParseError<'i>;
+
+    fn parse_prelude<'t>(
+        &mut self,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self::Prelude, ParseError<'i>> {
+        input.parse_comma_separated(parse_keyframe_selector)
+    }
+
+    fn parse_block<'t>(
+        &mut self,
+        offsets: Self::Prelude,
+        _start: &cssparser::ParserState,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self::QualifiedRule, ParseError<'i>> {
+        let mut declarations = Vec::new();
+
+        let mut declaration_parser = DeclarationParser {};
+        let mut declaration_parser = RuleBodyParser::new(input, &mut declaration_parser);
+
+        while let Some(result) = declaration_parser.next() {
+            match result {
+                Ok(declaration) => declarations.push(declaration),
+                Err(e) => self.context.parse_error(declaration_parser.input, "keyframe declaration", e),
+            }
+        }
+
+        Ok(Keyframe {
+            offsets,
+            declarations,
+        })
+    }
+}
+
+impl<'i, 'context> cssparser::RuleBodyItemParser<'i, Keyframe, RetinaStyleParseError<'i>> for KeyframesParser<'context> {
+    fn parse_declarations(&self) -> bool {
+        false
+    }
+
+    fn parse_qualified(&self) -> bool {
+        true
+    }
+}
+
+impl<'i, 'context> cssparser::AtRuleParser<'i> for KeyframesParser<'context> {
+    type Prelude = ();
+    type AtRule = Keyframe;
+    type Error = RetinaStyleParseError<'i>;
+    // ignored / errors upon
+}
+
+impl<'i, 'context> cssparser::DeclarationParser<'i> for KeyframesParser<'context> {
+    type Declaration = Keyframe;
+    type Error = RetinaStyleParseError<'i>;
+    // ignored / errors upon
+}
+
+/// Parses a [`<keyframe-selector>`][spec], which is `from`, `to` or a
+/// percentage between `0%` and `100%` inclusive.
+///
+/// [spec]: https://drafts.csswg.org/css-animations/#typedef-keyframe-selector
+fn parse_keyframe_selector<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<CssDecimal, ParseError<'i>> {
+    let location = input.current_source_location();
+    match input.next()?.clone() {
+        Token::Ident(ident) if ident.eq_ignore_ascii_case("from") => Ok(0.0),
+        Token::Ident(ident) if ident.eq_ignore_ascii_case("to") => Ok(1.0),
+        Token::Ident(ident) => Err(location.new_custom_error(RetinaStyleParseError::KeyframeSelectorUnknownKeyword(ident))),
+        Token::Percentage { unit_value, .. } if (0.0..=1.0).contains(&unit_value) => Ok(unit_value as CssDecimal),
+        token => Err(location.new_unexpected_token_error(token)),
+    }
+}
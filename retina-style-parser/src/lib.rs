@@ -5,10 +5,13 @@ mod context;
 mod declaration_parser;
 mod error;
 mod font_face_parser;
+mod keyframes_parser;
 mod rule_parser;
 mod selector_parser;
+mod supports_parser;
 mod util;
 mod value_parser;
+mod variable;
 
 pub use self::error::RetinaStyleParseError;
 pub use self::util::{
@@ -20,6 +23,7 @@ pub(crate) use self::context::Context;
 pub(crate) use self::rule_parser::RuleParser;
 pub(crate) use self::selector_parser::parse_selector_list;
 pub(crate) use self::value_parser::parse_value;
+pub use self::variable::substitute_var_functions;
 
 pub(self) type ParseError<'i> = cssparser::ParseError<'i, RetinaStyleParseError<'i>>;
 
@@ -30,14 +34,16 @@ use cssparser::{
     StyleSheetParser,
 };
 
-use log::error;
+use log::{error, warn};
 use retina_style::{
     CascadeOrigin,
     CssColor,
     CssLength,
+    Property,
     Rule,
     SelectorList,
     Stylesheet,
+    Value,
 };
 
 /// Parses the [`style`][attr] attribute according to the rules of
@@ -80,11 +86,40 @@ pub(crate) fn parse_stylesheet_contents(cascade_origin: CascadeOrigin, parser: &
 
     let mut stylesheet = Stylesheet::new();
 
+    // `@import` rules must precede all other rules (except `@charset` and
+    // `@layer` statements), otherwise they are invalid.
+    // <https://drafts.csswg.org/css-cascade-5/#at-import>
+    let mut may_import = true;
+
     while !stylesheet_parser.input.is_exhausted() {
         stylesheet_parser.input.skip_whitespace();
         let Some(rule) = stylesheet_parser.next() else { continue };
         match rule {
             Ok(rule) => {
+                match &rule {
+                    Rule::AtImport(import) => {
+                        if !may_import {
+                            warn!("[CssParser] Ignoring @import of \"{}\" after other rules", import.url);
+                            continue;
+                        }
+                    }
+                    Rule::AtLayerStatement(..) => (),
+                    _ => may_import = false,
+                }
+
+                // The rules inside of an `@supports` rule of which the
+                // condition doesn't hold are discarded.
+                if let Rule::AtSupports(supports) = &rule {
+                    if !supports.condition.matches() {
+                        continue;
+                    }
+                }
+
+                if let Rule::AtKeyframes(keyframes) = rule {
+                    stylesheet.insert_keyframes(keyframes);
+                    continue;
+                }
+
                 if let Rule::Style(style_rule) = &rule {
                     if style_rule.declarations.is_empty() {
                         if cascade_origin == CascadeOrigin::UserAgent {
@@ -106,6 +141,16 @@ pub(crate) fn parse_stylesheet_contents(cascade_origin: CascadeOrigin, parser: &
     stylesheet
 }
 
+/// Parses a [selector list][spec], as given to e.g. `Element.matches()` and
+/// `Document.querySelector()`.
+///
+/// [spec]: https://drafts.csswg.org/selectors/#selector-list
+pub fn parse_selectors(input: &str) -> Result<SelectorList, ParseError<'_>> {
+    let mut input = ParserInput::new(input);
+    let mut parser = Parser::new(&mut input);
+    parser.parse_entirely(parse_selector_list)
+}
+
 pub fn parse_value_color(input: &str) -> Option<CssColor> {
     let mut input = ParserInput::new(input);
     let mut parser = Parser::new(&mut input);
@@ -118,6 +163,16 @@ pub fn parse_value_length(input: &str) -> Option<CssLength> {
     value_parser::parse_length(&mut parser).ok()
 }
 
+/// Parses the value of a property, e.g. after [substituting][spec] the
+/// `var()` functions of a declaration.
+///
+/// [spec]: https://drafts.csswg.org/css-variables/#substitute-a-var
+pub fn parse_property_value(property: Property, input: &str) -> Option<Value> {
+    let mut input = ParserInput::new(input);
+    let mut parser = Parser::new(&mut input);
+    parser.parse_entirely(|parser| parse_value(parser, property)).ok()
+}
+
 #[cfg(test)]
 mod tests {
     use retina_style::*;
@@ -151,4 +206,27 @@ mod tests {
             rule
         ]);
     }
+
+    #[test]
+    fn custom_properties_and_var_references() {
+        let input = "
+            * {
+                --accent: #0b7285 ;
+                color: var(--accent, red);
+            }
+        ";
+
+        let stylesheet = Stylesheet::parse(CascadeOrigin::Author, input);
+        let Some(Rule::Style(rule)) = stylesheet.rules().first() else {
+            panic!("expected a style rule: {stylesheet:#?}");
+        };
+
+        assert_eq!(rule.declarations, vec![
+            Declaration::new(Property::Custom, Value::CustomProperty(CssCustomProperty {
+                name: "--accent".into(),
+                value: "#0b7285".into(),
+            })),
+            Declaration::new(Property::Color, Value::VariableReference("var(--accent, red)".into())),
+        ]);
+    }
 }
@@ -6,10 +6,17 @@ use cssparser::{
     RuleBodyParser,
     ParseError,
     ParseErrorKind,
+    Token,
 };
 
 use retina_style::{
+    AtImportLayer,
+    AtImportRule,
+    AtKeyframesRule,
+    AtLayerRule,
+    AtLayerStatementRule,
     AtMediaRule,
+    AtSupportsRule,
     CascadeOrigin,
     CssFontFaceAtRule,
     MediaQuery,
@@ -17,9 +24,16 @@ use retina_style::{
     Rule,
     SelectorList,
     StyleRule,
+    Stylesheet,
+    SupportsCondition,
 };
 
-use crate::{Context, font_face_parser::FontFaceParser};
+use crate::{
+    Context,
+    font_face_parser::FontFaceParser,
+    keyframes_parser::KeyframesParser,
+    supports_parser::parse_supports_condition,
+};
 
 use super::{
     RetinaStyleParseError,
@@ -28,7 +42,11 @@ use super::{
 
 pub enum AtRulePrelude {
     FontFace,
+    Import(AtImportRule),
+    Keyframes(String),
+    Layer(Vec<String>),
     Media(Vec<MediaQuery>),
+    Supports(SupportsCondition),
 }
 
 #[derive(Debug)]
@@ -67,6 +85,45 @@ impl<'context> RuleParser<'context> {
         Ok(Rule::AtFontFace(rule))
     }
 
+    fn parse_at_keyframes_block<'i, 't>(
+        &mut self,
+        name: String,
+        input: &mut Parser<'i, 't>
+    ) -> Result<Rule, ParseError<'i, RetinaStyleParseError<'i>>> {
+        let mut rule = AtKeyframesRule {
+            name,
+            keyframes: Vec::new(),
+        };
+
+        let mut parser = KeyframesParser { context: self.context };
+        let mut parser = RuleBodyParser::new(input, &mut parser);
+        while let Some(keyframe) = parser.next() {
+            match keyframe {
+                Ok(keyframe) => rule.keyframes.push(keyframe),
+                Err(error) => {
+                    parser.parser.context.parse_error(parser.input, "keyframe", error);
+                }
+            }
+        }
+
+        Ok(Rule::AtKeyframes(rule))
+    }
+
+    fn parse_at_layer_block<'i, 't>(
+        &mut self,
+        mut names: Vec<String>,
+        input: &mut Parser<'i, 't>
+    ) -> Result<Rule, ParseError<'i, RetinaStyleParseError<'i>>> {
+        if names.len() > 1 {
+            return Err(input.new_custom_error(RetinaStyleParseError::AtLayerBlockWithMultipleNames));
+        }
+
+        Ok(Rule::AtLayer(AtLayerRule {
+            name: names.pop(),
+            stylesheet: crate::parse_stylesheet_contents(self.cascade_origin, input),
+        }))
+    }
+
     fn parse_at_media_block<'i, 't>(
         &mut self,
         media_query_list: Vec<MediaQuery>,
@@ -78,39 +135,160 @@ impl<'context> RuleParser<'context> {
         }))
     }
 
-    fn parse_at_media_prelude<'i, 't>(
+    /// Parse the block of the `@supports` rule, which is skipped when the
+    /// condition doesn't hold, since the rule is discarded anyway.
+    fn parse_at_supports_block<'i, 't>(
+        &mut self,
+        condition: SupportsCondition,
+        input: &mut Parser<'i, 't>
+    ) -> Result<Rule, ParseError<'i, RetinaStyleParseError<'i>>> {
+        let stylesheet = if condition.matches() {
+            crate::parse_stylesheet_contents(self.cascade_origin, input)
+        } else {
+            Stylesheet::new()
+        };
+
+        Ok(Rule::AtSupports(AtSupportsRule {
+            condition,
+            stylesheet,
+        }))
+    }
+
+    /// Parse the prelude of the `@import` rule.
+    ///
+    /// ```text
+    /// @import [ <url> | <string> ]
+    ///         [ layer | layer(<layer-name>) ]?
+    ///         <import-conditions> ;
+    /// ```
+    fn parse_at_import_prelude<'i, 't>(
         &mut self,
         input: &mut Parser<'i, 't>
     ) -> Result<AtRulePrelude, ParseError<'i, RetinaStyleParseError<'i>>> {
-        let location = input.current_source_location();
+        let url = input.expect_url_or_string()?.as_ref().to_string();
 
-        let ty = input.expect_ident().map_err(|e| ParseError {
-            location,
-            kind: ParseErrorKind::Basic(e.kind),
-        })?;
+        let layer = if input.try_parse(|input| input.expect_ident_matching("layer")).is_ok() {
+            AtImportLayer::Anonymous
+        } else if input.try_parse(|input| input.expect_function_matching("layer")).is_ok() {
+            AtImportLayer::Named(input.parse_nested_block(parse_layer_name)?)
+        } else {
+            AtImportLayer::None
+        };
 
-        if ty.eq_ignore_ascii_case("all") {
-            let query = MediaQuery::Type(MediaType::All);
-            return Ok(AtRulePrelude::Media(vec![query]));
-        }
+        let media = if input.is_exhausted() {
+            Vec::new()
+        } else {
+            input.parse_comma_separated(parse_media_query)?
+        };
+
+        Ok(AtRulePrelude::Import(AtImportRule {
+            url,
+            layer,
+            media,
+            stylesheet: None,
+        }))
+    }
 
-        if ty.eq_ignore_ascii_case("print") {
-            let query = MediaQuery::Type(MediaType::Print);
-            return Ok(AtRulePrelude::Media(vec![query]));
+    /// Parse the prelude of the `@keyframes` rule, which is the name of the
+    /// animation as a [`<custom-ident>`][ident] or a `<string>`.
+    ///
+    /// [ident]: https://drafts.csswg.org/css-values-4/#custom-idents
+    fn parse_at_keyframes_prelude<'i, 't>(
+        &mut self,
+        input: &mut Parser<'i, 't>
+    ) -> Result<AtRulePrelude, ParseError<'i, RetinaStyleParseError<'i>>> {
+        let location = input.current_source_location();
+        match input.next()?.clone() {
+            // > The <custom-ident> additionally excludes the none keyword.
+            Token::Ident(name) if name.eq_ignore_ascii_case("none") => {
+                Err(location.new_custom_error(RetinaStyleParseError::AtKeyframesInvalidName(name)))
+            }
+            Token::Ident(name) | Token::QuotedString(name) => Ok(AtRulePrelude::Keyframes(name.as_ref().to_string())),
+            token => Err(location.new_unexpected_token_error(token)),
         }
+    }
 
-        if ty.eq_ignore_ascii_case("screen") {
-            let query = MediaQuery::Type(MediaType::Screen);
-            return Ok(AtRulePrelude::Media(vec![query]));
+    /// Parse the prelude of the `@layer` rule, which is an optional list of
+    /// layer names.
+    fn parse_at_layer_prelude<'i, 't>(
+        &mut self,
+        input: &mut Parser<'i, 't>
+    ) -> Result<AtRulePrelude, ParseError<'i, RetinaStyleParseError<'i>>> {
+        if input.is_exhausted() {
+            return Ok(AtRulePrelude::Layer(Vec::new()));
         }
 
-        Err(ParseError {
-            location,
-            kind: ParseErrorKind::Custom(RetinaStyleParseError::MediaPreludeUnknownType(ty.clone())),
-        })
+        Ok(AtRulePrelude::Layer(input.parse_comma_separated(parse_layer_name)?))
+    }
+
+    fn parse_at_media_prelude<'i, 't>(
+        &mut self,
+        input: &mut Parser<'i, 't>
+    ) -> Result<AtRulePrelude, ParseError<'i, RetinaStyleParseError<'i>>> {
+        let query = parse_media_query(input)?;
+        Ok(AtRulePrelude::Media(vec![query]))
+    }
+
+    fn parse_at_supports_prelude<'i, 't>(
+        &mut self,
+        input: &mut Parser<'i, 't>
+    ) -> Result<AtRulePrelude, ParseError<'i, RetinaStyleParseError<'i>>> {
+        let condition = parse_supports_condition(input)?;
+        input.expect_exhausted()?;
+        Ok(AtRulePrelude::Supports(condition))
     }
 }
 
+/// Parse a (dotted) [`<layer-name>`][spec], e.g. `framework.theme`.
+///
+/// [spec]: https://drafts.csswg.org/css-cascade-5/#typedef-layer-name
+pub(crate) fn parse_layer_name<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<String, ParseError<'i, RetinaStyleParseError<'i>>> {
+    let mut name = input.expect_ident()?.as_ref().to_string();
+
+    loop {
+        let result = input.try_parse(|input| {
+            input.expect_delim('.')?;
+            input.expect_ident().cloned()
+        });
+
+        let Ok(part) = result else { break };
+        name.push('.');
+        name.push_str(&part);
+    }
+
+    Ok(name)
+}
+
+fn parse_media_query<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<MediaQuery, ParseError<'i, RetinaStyleParseError<'i>>> {
+    let location = input.current_source_location();
+
+    let ty = input.expect_ident().map_err(|e| ParseError {
+        location,
+        kind: ParseErrorKind::Basic(e.kind),
+    })?;
+
+    if ty.eq_ignore_ascii_case("all") {
+        return Ok(MediaQuery::Type(MediaType::All));
+    }
+
+    if ty.eq_ignore_ascii_case("print") {
+        return Ok(MediaQuery::Type(MediaType::Print));
+    }
+
+    if ty.eq_ignore_ascii_case("screen") {
+        return Ok(MediaQuery::Type(MediaType::Screen));
+    }
+
+    Err(ParseError {
+        location,
+        kind: ParseErrorKind::Custom(RetinaStyleParseError::MediaPreludeUnknownType(ty.clone())),
+    })
+}
+
 impl<'i, 'context> cssparser::AtRuleParser<'i> for RuleParser<'context> {
     type Prelude = AtRulePrelude;
     type AtRule = Rule;
@@ -123,6 +301,14 @@ impl<'i, 'context> cssparser::AtRuleParser<'i> for RuleParser<'context> {
     ) -> Result<Self::Prelude, cssparser::ParseError<'i, Self::Error>> {
         if name.eq_ignore_ascii_case("media") {
             self.parse_at_media_prelude(input)
+        } else if name.eq_ignore_ascii_case("import") {
+            self.parse_at_import_prelude(input)
+        } else if name.eq_ignore_ascii_case("keyframes") {
+            self.parse_at_keyframes_prelude(input)
+        } else if name.eq_ignore_ascii_case("layer") {
+            self.parse_at_layer_prelude(input)
+        } else if name.eq_ignore_ascii_case("supports") {
+            self.parse_at_supports_prelude(input)
         } else if name.eq_ignore_ascii_case("font-face") {
             Ok(AtRulePrelude::FontFace)
         } else {
@@ -141,7 +327,25 @@ impl<'i, 'context> cssparser::AtRuleParser<'i> for RuleParser<'context> {
     ) -> Result<Self::AtRule, ParseError<'i, Self::Error>> {
         match prelude {
             AtRulePrelude::FontFace => self.parse_at_font_face_block(input),
+            AtRulePrelude::Import(..) => Err(input.new_custom_error(RetinaStyleParseError::AtImportUnexpectedBlock)),
+            AtRulePrelude::Keyframes(name) => self.parse_at_keyframes_block(name, input),
+            AtRulePrelude::Layer(names) => self.parse_at_layer_block(names, input),
             AtRulePrelude::Media(media) => self.parse_at_media_block(media, input),
+            AtRulePrelude::Supports(condition) => self.parse_at_supports_block(condition, input),
+        }
+    }
+
+    fn rule_without_block(
+        &mut self,
+        prelude: Self::Prelude,
+        _start: &cssparser::ParserState,
+    ) -> Result<Self::AtRule, ()> {
+        match prelude {
+            AtRulePrelude::Import(import) => Ok(Rule::AtImport(import)),
+            AtRulePrelude::Layer(names) if !names.is_empty() => {
+                Ok(Rule::AtLayerStatement(AtLayerStatementRule { names }))
+            }
+            _ => Err(()),
         }
     }
 }
@@ -223,4 +427,165 @@ mod tests {
             rule
         ]);
     }
+
+    #[rstest]
+    #[case("@import \"theme.css\";", "theme.css", AtImportLayer::None, vec![])]
+    #[case("@import url(theme.css);", "theme.css", AtImportLayer::None, vec![])]
+    #[case("@import url(\"theme.css\") screen;", "theme.css", AtImportLayer::None, vec![MediaQuery::Type(MediaType::Screen)])]
+    #[case("@import \"theme.css\" print, screen;", "theme.css", AtImportLayer::None, vec![MediaQuery::Type(MediaType::Print), MediaQuery::Type(MediaType::Screen)])]
+    #[case("@import \"theme.css\" layer;", "theme.css", AtImportLayer::Anonymous, vec![])]
+    #[case("@import \"theme.css\" layer(framework.theme);", "theme.css", AtImportLayer::Named("framework.theme".into()), vec![])]
+    #[test]
+    fn at_import(
+        #[case] input: &str,
+        #[case] url: &str,
+        #[case] layer: AtImportLayer,
+        #[case] media: Vec<MediaQuery>,
+    ) {
+        let stylesheet = Stylesheet::parse(CascadeOrigin::Author, input);
+
+        assert_eq!(stylesheet.rules(), &[
+            Rule::AtImport(AtImportRule {
+                url: url.to_string(),
+                layer,
+                media,
+                stylesheet: None,
+            })
+        ]);
+    }
+
+    #[test]
+    fn at_import_after_layer_statement() {
+        let stylesheet = Stylesheet::parse(CascadeOrigin::Author, "
+            @layer base, theme;
+            @import \"theme.css\" layer(theme);
+        ");
+
+        assert_eq!(stylesheet.rules().len(), 2);
+        assert!(matches!(stylesheet.rules()[1], Rule::AtImport(..)));
+    }
+
+    #[test]
+    fn at_import_after_style_rule_is_ignored() {
+        let stylesheet = Stylesheet::parse(CascadeOrigin::Author, "
+            * { color: red }
+            @import \"theme.css\";
+        ");
+
+        assert_eq!(stylesheet.rules().len(), 1);
+        assert!(stylesheet.rules()[0].try_as_style().is_some());
+    }
+
+    #[test]
+    fn at_supports() {
+        let stylesheet = Stylesheet::parse(CascadeOrigin::Author, "
+            @supports (display: grid) and (not (display: bogus)) {
+                * { color: red }
+            }
+            @supports (display: bogus) {
+                * { color: blue }
+            }
+        ");
+
+        assert_eq!(stylesheet.rules().len(), 1);
+        let Rule::AtSupports(supports) = &stylesheet.rules()[0] else {
+            panic!("expected an @supports rule, got: {:#?}", stylesheet.rules()[0]);
+        };
+
+        assert!(supports.condition.matches());
+        assert_eq!(supports.stylesheet.rules().len(), 1);
+    }
+
+    #[test]
+    fn at_keyframes() {
+        let stylesheet = Stylesheet::parse(CascadeOrigin::Author, "
+            @keyframes fade {
+                from { color: red }
+                50%, 75% { color: blue }
+                to { color: red }
+            }
+        ");
+
+        assert!(stylesheet.rules().is_empty());
+        assert_eq!(stylesheet.keyframes("fade"), Some(&AtKeyframesRule {
+            name: "fade".into(),
+            keyframes: vec![
+                Keyframe {
+                    offsets: vec![0.0],
+                    declarations: vec![Declaration::new(Property::Color, CssNamedColor::RED.into())],
+                },
+                Keyframe {
+                    offsets: vec![0.5, 0.75],
+                    declarations: vec![Declaration::new(Property::Color, CssNamedColor::BLUE.into())],
+                },
+                Keyframe {
+                    offsets: vec![1.0],
+                    declarations: vec![Declaration::new(Property::Color, CssNamedColor::RED.into())],
+                },
+            ],
+        }));
+    }
+
+    #[rstest]
+    #[case("@keyframes none { from { color: red } }")]
+    #[case("@keyframes fade { 150% { color: red } }")]
+    #[case("@keyframes fade { halfway { color: red } }")]
+    fn at_keyframes_invalid(#[case] input: &str) {
+        let stylesheet = Stylesheet::parse(CascadeOrigin::Author, input);
+
+        let keyframe_count = ["none", "fade"].iter()
+            .filter_map(|name| stylesheet.keyframes(name))
+            .map(|rule| rule.keyframes.len())
+            .sum::<usize>();
+        assert_eq!(keyframe_count, 0);
+    }
+
+    #[test]
+    fn at_keyframes_last_rule_wins() {
+        let stylesheet = Stylesheet::parse(CascadeOrigin::Author, "
+            @keyframes fade { from { color: red } }
+            @keyframes fade { to { color: blue } }
+        ");
+
+        let rule = stylesheet.keyframes("fade").unwrap();
+        assert_eq!(rule.keyframes.len(), 1);
+        assert_eq!(rule.keyframes[0].offsets, vec![1.0]);
+    }
+
+    #[rstest]
+    #[case("@layer theme { * { color: red } }", Some("theme"))]
+    #[case("@layer framework.theme { * { color: red } }", Some("framework.theme"))]
+    #[case("@layer { * { color: red } }", None)]
+    #[test]
+    fn at_layer_block(#[case] input: &str, #[case] name: Option<&str>) {
+        let stylesheet = Stylesheet::parse(CascadeOrigin::Author, input);
+
+        let [Rule::AtLayer(layer)] = stylesheet.rules() else {
+            panic!("expected a single @layer rule, got: {:#?}", stylesheet.rules());
+        };
+
+        assert_eq!(layer.name.as_deref(), name);
+        assert_eq!(layer.stylesheet.rules().len(), 1);
+    }
+
+    #[test]
+    fn at_layer_statement() {
+        let stylesheet = Stylesheet::parse(CascadeOrigin::Author, "@layer reset, framework.base, theme;");
+
+        assert_eq!(stylesheet.rules(), &[
+            Rule::AtLayerStatement(AtLayerStatementRule {
+                names: vec![
+                    "reset".into(),
+                    "framework.base".into(),
+                    "theme".into(),
+                ],
+            })
+        ]);
+    }
+
+    #[test]
+    fn at_layer_block_with_multiple_names_is_invalid() {
+        let stylesheet = Stylesheet::parse(CascadeOrigin::Author, "@layer a, b { * { color: red } }");
+        assert!(stylesheet.rules().is_empty());
+    }
 }
@@ -3,6 +3,7 @@
 
 use cssparser::{
     BasicParseErrorKind,
+    CowRcStr,
     Parser,
     Token,
 };
@@ -10,12 +11,17 @@ use cssparser::{
 use retina_common::StrTendril;
 
 use retina_style::{
+    AnPlusB,
     AttributeSelector,
     AttributeSelectorCaseSensitivity,
     AttributeSelectorKind,
     ComplexSelector,
     CompoundSelector,
+    CssPseudoElement,
+    FunctionalPseudoClassSelectorKind,
     PseudoClassSelectorKind,
+    RelativeSelector,
+    RelativeSelectorList,
     Selector,
     SelectorCombinator,
     SelectorList,
@@ -93,6 +99,12 @@ fn parse_attribute_selector_name<'i, 't>(
 fn parse_selector<'i, 't>(
     input: &mut Parser<'i, 't>
 ) -> Result<Selector, ParseError<'i>> {
+    parse_complex_selector(input).map(parse_selector_try_simplify)
+}
+
+fn parse_complex_selector<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<ComplexSelector, ParseError<'i>> {
     let mut complex = ComplexSelector {
         topmost: parse_compound_selector(input)?,
         combinators: Vec::new(),
@@ -140,7 +152,40 @@ fn parse_selector<'i, 't>(
         ));
     }
 
-    Ok(parse_selector_try_simplify(complex))
+    Ok(complex)
+}
+
+/// Parse a [`<relative-selector>`][spec], which is a complex selector that
+/// may start with a combinator, e.g. `> p`. The descendant combinator is
+/// implied if it is omitted.
+///
+/// [spec]: https://drafts.csswg.org/selectors/#typedef-relative-selector
+fn parse_relative_selector<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<RelativeSelector, ParseError<'i>> {
+    input.skip_whitespace();
+
+    let combinator = if input.try_parse(|input| input.expect_delim('>')).is_ok() {
+        SelectorCombinator::Child
+    } else if input.try_parse(|input| input.expect_delim('+')).is_ok() {
+        SelectorCombinator::NextSibling
+    } else if input.try_parse(|input| input.expect_delim('~')).is_ok() {
+        SelectorCombinator::SubsequentSibling
+    } else {
+        SelectorCombinator::Descendant
+    };
+
+    Ok(RelativeSelector {
+        combinator,
+        selector: parse_complex_selector(input)?,
+    })
+}
+
+fn parse_relative_selector_list<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<RelativeSelectorList, ParseError<'i>> {
+    input.parse_comma_separated(parse_relative_selector)
+        .map(|items| RelativeSelectorList { items })
 }
 
 fn parse_selector_try_simplify(complex_selector: ComplexSelector) -> Selector {
@@ -183,7 +228,25 @@ fn parse_simple_selector<'i, 't>(
     let first_token = input.next_including_whitespace()?;
     Ok(match first_token {
         Token::Colon => {
+            if input.try_parse(|input| input.expect_colon()).is_ok() {
+                let name = input.expect_ident_cloned()?;
+                let pseudo = CssPseudoElement::parse(name.as_ref())
+                    .ok_or_else(|| input.new_custom_error(RetinaStyleParseError::UnknownSelectorPseudoElement(name)))?;
+                return Ok(SimpleSelector::PseudoElement(pseudo));
+            }
+
+            if let Ok(name) = input.try_parse(|input| input.expect_function().cloned()) {
+                return parse_functional_pseudo_class(input, name);
+            }
+
             let pseudo_class = input.expect_ident_cloned()?;
+
+            // The legacy single-colon syntax of the CSS 2 pseudo-elements.
+            // <https://drafts.csswg.org/selectors/#pseudo-element-syntax>
+            if let Some(pseudo) = CssPseudoElement::parse(pseudo_class.as_ref()) {
+                return Ok(SimpleSelector::PseudoElement(pseudo));
+            }
+
             let pseudo = PseudoClassSelectorKind::parse(pseudo_class.as_ref())
                 .ok_or_else(|| input.new_custom_error(RetinaStyleParseError::UnknownSelectorPseudoClass(pseudo_class)))?;
             SimpleSelector::PseudoClass(pseudo)
@@ -204,6 +267,62 @@ fn parse_simple_selector<'i, 't>(
     })
 }
 
+/// Parses the selector list argument of a [functional pseudo-class][spec], of
+/// which the function token is already consumed.
+///
+/// [spec]: https://drafts.csswg.org/selectors/#functional-pseudo-class
+fn parse_functional_pseudo_class<'i, 't>(
+    input: &mut Parser<'i, 't>,
+    name: CowRcStr<'i>,
+) -> Result<SimpleSelector, ParseError<'i>> {
+    let kind = match name.to_ascii_lowercase().as_str() {
+        "has" => FunctionalPseudoClassSelectorKind::Has(input.parse_nested_block(parse_relative_selector_list)?),
+        "is" => FunctionalPseudoClassSelectorKind::Is(vec![input.parse_nested_block(parse_selector_list)?]),
+        "not" => FunctionalPseudoClassSelectorKind::Not(vec![input.parse_nested_block(parse_selector_list)?]),
+        "where" => FunctionalPseudoClassSelectorKind::Where(vec![input.parse_nested_block(parse_selector_list)?]),
+
+        "nth-child" => {
+            let (an_plus_b, of) = input.parse_nested_block(parse_nth_child_arguments)?;
+            FunctionalPseudoClassSelectorKind::NthChild(an_plus_b, of)
+        }
+        "nth-last-child" => {
+            let (an_plus_b, of) = input.parse_nested_block(parse_nth_child_arguments)?;
+            FunctionalPseudoClassSelectorKind::NthLastChild(an_plus_b, of)
+        }
+        "nth-last-of-type" => FunctionalPseudoClassSelectorKind::NthLastOfType(input.parse_nested_block(parse_an_plus_b)?),
+        "nth-of-type" => FunctionalPseudoClassSelectorKind::NthOfType(input.parse_nested_block(parse_an_plus_b)?),
+
+        _ => return Err(input.new_custom_error(RetinaStyleParseError::UnknownSelectorPseudoClass(name))),
+    };
+
+    Ok(SimpleSelector::FunctionalPseudoClass(kind))
+}
+
+/// Parse the [`An+B`][spec] notation, including the `odd` and `even`
+/// keywords.
+///
+/// [spec]: https://drafts.csswg.org/css-syntax/#anb-microsyntax
+fn parse_an_plus_b<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<AnPlusB, ParseError<'i>> {
+    let (a, b) = cssparser::parse_nth(input)?;
+    Ok(AnPlusB::new(a, b))
+}
+
+/// Parse the arguments of `:nth-child()` and `:nth-last-child()`, which are
+/// `<an+b> [of <complex-real-selector-list>]?`.
+fn parse_nth_child_arguments<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<(AnPlusB, Option<SelectorList>), ParseError<'i>> {
+    let an_plus_b = parse_an_plus_b(input)?;
+
+    if input.try_parse(|input| input.expect_ident_matching("of")).is_err() {
+        return Ok((an_plus_b, None));
+    }
+
+    Ok((an_plus_b, Some(parse_selector_list(input)?)))
+}
+
 pub fn parse_selector_list<'i, 't>(
     input: &mut Parser<'i, 't>
 ) -> Result<SelectorList, ParseError<'i>> {
@@ -247,6 +366,53 @@ mod tests {
     #[case("[attr*=val]", Selector::Simple(SimpleSelector::Attribute(AttributeSelector::new("attr".into(), AttributeSelectorCaseSensitivity::Default, AttributeSelectorKind::Contains("val".into())))))]
     #[case("[attr*='my value']", Selector::Simple(SimpleSelector::Attribute(AttributeSelector::new("attr".into(), AttributeSelectorCaseSensitivity::Default, AttributeSelectorKind::Contains("my value".into())))))]
     #[case("[attr*=\"my value\"]", Selector::Simple(SimpleSelector::Attribute(AttributeSelector::new("attr".into(), AttributeSelectorCaseSensitivity::Default, AttributeSelectorKind::Contains("my value".into())))))]
+    #[case(":is(ol, ul)", Selector::Simple(SimpleSelector::FunctionalPseudoClass(FunctionalPseudoClassSelectorKind::Is(vec![SelectorList { items: vec![
+        Selector::Simple(SimpleSelector::TypeSelector("ol".into())),
+        Selector::Simple(SimpleSelector::TypeSelector("ul".into())),
+    ] }]))))]
+    #[case(":not(#id)", Selector::Simple(SimpleSelector::FunctionalPseudoClass(FunctionalPseudoClassSelectorKind::Not(vec![SelectorList { items: vec![
+        Selector::Simple(SimpleSelector::Id("id".into())),
+    ] }]))))]
+    #[case(":where(.a)", Selector::Simple(SimpleSelector::FunctionalPseudoClass(FunctionalPseudoClassSelectorKind::Where(vec![SelectorList { items: vec![
+        Selector::Simple(SimpleSelector::Class("a".into())),
+    ] }]))))]
+    #[case(":has(> p)", Selector::Simple(SimpleSelector::FunctionalPseudoClass(FunctionalPseudoClassSelectorKind::Has(RelativeSelectorList { items: vec![
+        RelativeSelector {
+            combinator: SelectorCombinator::Child,
+            selector: ComplexSelector { topmost: CompoundSelector(vec![SimpleSelector::TypeSelector("p".into())]), combinators: vec![] },
+        },
+    ] }))))]
+    #[case(":has(img, + .a b)", Selector::Simple(SimpleSelector::FunctionalPseudoClass(FunctionalPseudoClassSelectorKind::Has(RelativeSelectorList { items: vec![
+        RelativeSelector {
+            combinator: SelectorCombinator::Descendant,
+            selector: ComplexSelector { topmost: CompoundSelector(vec![SimpleSelector::TypeSelector("img".into())]), combinators: vec![] },
+        },
+        RelativeSelector {
+            combinator: SelectorCombinator::NextSibling,
+            selector: ComplexSelector {
+                topmost: CompoundSelector(vec![SimpleSelector::Class("a".into())]),
+                combinators: vec![(SelectorCombinator::Descendant, CompoundSelector(vec![SimpleSelector::TypeSelector("b".into())]))],
+            },
+        },
+    ] }))))]
+    #[case(":nth-child(2n+1)", Selector::Simple(SimpleSelector::FunctionalPseudoClass(FunctionalPseudoClassSelectorKind::NthChild(AnPlusB::new(2, 1), None))))]
+    #[case(":nth-child(odd)", Selector::Simple(SimpleSelector::FunctionalPseudoClass(FunctionalPseudoClassSelectorKind::NthChild(AnPlusB::new(2, 1), None))))]
+    #[case(":nth-child(even)", Selector::Simple(SimpleSelector::FunctionalPseudoClass(FunctionalPseudoClassSelectorKind::NthChild(AnPlusB::new(2, 0), None))))]
+    #[case(":nth-child(-n + 3)", Selector::Simple(SimpleSelector::FunctionalPseudoClass(FunctionalPseudoClassSelectorKind::NthChild(AnPlusB::new(-1, 3), None))))]
+    #[case(":nth-child(2 of .a, p)", Selector::Simple(SimpleSelector::FunctionalPseudoClass(FunctionalPseudoClassSelectorKind::NthChild(AnPlusB::new(0, 2), Some(SelectorList { items: vec![
+        Selector::Simple(SimpleSelector::Class("a".into())),
+        Selector::Simple(SimpleSelector::TypeSelector("p".into())),
+    ] })))))]
+    #[case(":nth-last-child(3n)", Selector::Simple(SimpleSelector::FunctionalPseudoClass(FunctionalPseudoClassSelectorKind::NthLastChild(AnPlusB::new(3, 0), None))))]
+    #[case(":nth-of-type(even)", Selector::Simple(SimpleSelector::FunctionalPseudoClass(FunctionalPseudoClassSelectorKind::NthOfType(AnPlusB::new(2, 0)))))]
+    #[case(":nth-last-of-type(1)", Selector::Simple(SimpleSelector::FunctionalPseudoClass(FunctionalPseudoClassSelectorKind::NthLastOfType(AnPlusB::new(0, 1)))))]
+    #[case("::before", Selector::Simple(SimpleSelector::PseudoElement(CssPseudoElement::Before)))]
+    #[case("::after", Selector::Simple(SimpleSelector::PseudoElement(CssPseudoElement::After)))]
+    #[case(":before", Selector::Simple(SimpleSelector::PseudoElement(CssPseudoElement::Before)))]
+    #[case("p::after", Selector::Compound(CompoundSelector(vec![
+        SimpleSelector::TypeSelector("p".into()),
+        SimpleSelector::PseudoElement(CssPseudoElement::After),
+    ])))]
     fn single_selector(#[case] input: &str, #[case] expected: Selector) {
         let mut input = cssparser::ParserInput::new(input);
         let input = &mut cssparser::Parser::new(&mut input);
@@ -0,0 +1,170 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! Parsing and evaluation of the condition of the `@supports` rule.
+//!
+//! # References
+//! * [CSS Conditional Rules Module Level 3 § 6. Feature Queries: the `@supports` rule](https://drafts.csswg.org/css-conditional-3/#at-supports)
+
+use cssparser::{
+    DeclarationParser as _,
+    Parser,
+    Token,
+};
+use retina_style::{
+    Declaration,
+    SupportsCondition,
+};
+
+use crate::{
+    ParseError,
+    declaration_parser::DeclarationParser,
+};
+
+/// Parse a [`<supports-condition>`][spec]:
+///
+/// ```text
+/// not <supports-in-parens>
+/// | <supports-in-parens> [ and <supports-in-parens> ]*
+/// | <supports-in-parens> [ or <supports-in-parens> ]*
+/// ```
+///
+/// [spec]: https://drafts.csswg.org/css-conditional-3/#typedef-supports-condition
+pub(crate) fn parse_supports_condition<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<SupportsCondition, ParseError<'i>> {
+    if input.try_parse(|input| input.expect_ident_matching("not")).is_ok() {
+        return Ok(SupportsCondition::Not(Box::new(parse_supports_in_parens(input)?)));
+    }
+
+    let first = parse_supports_in_parens(input)?;
+
+    let location = input.current_source_location();
+    let Ok(keyword) = input.try_parse(|input| input.expect_ident_cloned()) else {
+        return Ok(first);
+    };
+
+    let is_conjunction = if keyword.eq_ignore_ascii_case("and") {
+        true
+    } else if keyword.eq_ignore_ascii_case("or") {
+        false
+    } else {
+        return Err(location.new_unexpected_token_error(Token::Ident(keyword)));
+    };
+
+    // The keywords can't be mixed without parentheses, e.g. `(a) and (b) or
+    // (c)` is invalid.
+    let mut conditions = vec![first, parse_supports_in_parens(input)?];
+    while input.try_parse(|input| input.expect_ident_matching(&keyword)).is_ok() {
+        conditions.push(parse_supports_in_parens(input)?);
+    }
+
+    Ok(if is_conjunction {
+        SupportsCondition::And(conditions)
+    } else {
+        SupportsCondition::Or(conditions)
+    })
+}
+
+/// Parse a [`<supports-in-parens>`][spec], which is a nested condition, a
+/// declaration, or a `<general-enclosed>` that is unknown.
+///
+/// [spec]: https://drafts.csswg.org/css-conditional-3/#typedef-supports-in-parens
+fn parse_supports_in_parens<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<SupportsCondition, ParseError<'i>> {
+    let location = input.current_source_location();
+    match input.next()?.clone() {
+        Token::ParenthesisBlock => input.parse_nested_block(|input| {
+            let condition: Result<_, ParseError<'i>> = input.try_parse(|input| {
+                let condition = parse_supports_condition(input)?;
+                input.expect_exhausted()?;
+                Ok(condition)
+            });
+
+            if let Ok(condition) = condition {
+                return Ok(condition);
+            }
+
+            Ok(SupportsCondition::Declaration(parse_supports_declaration(input)))
+        }),
+
+        // e.g. `selector(...)`, of which the contents are skipped.
+        Token::Function(..) => {
+            input.parse_nested_block(|input| {
+                while input.next().is_ok() {}
+                Ok::<_, ParseError<'i>>(())
+            })?;
+            Ok(SupportsCondition::Unknown)
+        }
+
+        token => Err(location.new_unexpected_token_error(token)),
+    }
+}
+
+/// Parse the `property: value` of a [`<supports-decl>`][spec] the same way
+/// as a declaration in a style rule, which is [`None`] if that fails, i.e. the
+/// declaration isn't supported.
+///
+/// [spec]: https://drafts.csswg.org/css-conditional-3/#typedef-supports-decl
+fn parse_supports_declaration<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Option<Declaration> {
+    let declaration = input.try_parse(|input| {
+        let name = input.expect_ident_cloned()?;
+        input.expect_colon()?;
+
+        let declaration = DeclarationParser.parse_value(name, input)?;
+        input.expect_exhausted()?;
+        Ok::<_, ParseError<'i>>(declaration)
+    });
+
+    // Skip the remainder of an unsupported declaration, since the whole
+    // block must be consumed.
+    while input.next().is_ok() {}
+    declaration.ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("(color: red)", true)]
+    #[case("(display: grid)", true)]
+    #[case("(colour: red)", false)]
+    #[case("(color: 10px)", false)]
+    #[case("not (color: 10px)", true)]
+    #[case("not (color: red)", false)]
+    #[case("(color: red) and (display: flex)", true)]
+    #[case("(color: red) and (display: bogus)", false)]
+    #[case("(color: bogus) or (display: flex)", true)]
+    #[case("(color: bogus) or (display: bogus)", false)]
+    #[case("(color: red blue) or (display: flex)", true)]
+    #[case("(display: grid 10px) and (color: red)", false)]
+    #[case("not (display: flex flex)", true)]
+    #[case("((color: red) and (display: flex)) or (foo: bar)", true)]
+    #[case("selector(a > b)", false)]
+    #[case("not selector(a > b)", true)]
+    fn supports_condition(#[case] input: &str, #[case] expected: bool) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        let condition = parse_supports_condition(input).expect("valid condition");
+        assert!(input.is_exhausted());
+        assert_eq!(condition.matches(), expected);
+    }
+
+    #[rstest]
+    #[case("color: red")]
+    #[case("(color: red) and (display: flex) or (color: blue)")]
+    #[case("(color: red) (display: flex)")]
+    fn supports_condition_invalid(#[case] input: &str) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        let result = parse_supports_condition(input).and_then(|_| input.expect_exhausted().map_err(Into::into));
+        assert!(result.is_err());
+    }
+}
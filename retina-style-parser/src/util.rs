@@ -208,10 +208,92 @@ pub fn convert_color(value: cssparser::Color) -> Option<CssColor> {
     match value {
         cssparser::Color::CurrentColor => Some(CssColor::CurrentColor),
         cssparser::Color::Rgba(rgba) => Some(convert_rgba(rgba)),
+        cssparser::Color::Lab(lab) => Some(convert_lab(lab)),
+        cssparser::Color::Lch(lch) => Some(convert_lch(lch)),
+        cssparser::Color::Oklab(oklab) => Some(convert_oklab(oklab)),
+        cssparser::Color::Oklch(oklch) => Some(convert_oklch(oklch)),
         _ => None,
     }
 }
 
+/// Converts the CIE Lab color, which is relative to the D50 white point, to
+/// sRGB, which is relative to D65.
+///
+/// <https://drafts.csswg.org/css-color-4/#specifying-lab-lch>
+pub fn convert_lab(value: cssparser::Lab) -> CssColor {
+    use palette::{chromatic_adaptation::AdaptIntoUnclamped, white_point::{D50, D65}, FromColor};
+
+    let lab = palette::Lab::<D50, f32>::new(
+        value.lightness.unwrap_or(0.0),
+        value.a.unwrap_or(0.0),
+        value.b.unwrap_or(0.0),
+    );
+
+    let xyz: palette::Xyz<D65, f32> = palette::Xyz::<D50, f32>::from_color(lab).adapt_into_unclamped();
+    convert_srgb(palette::Srgb::from_color(xyz), value.alpha)
+}
+
+/// Converts the CIE LCH color to sRGB, by way of [`convert_lab`].
+///
+/// <https://drafts.csswg.org/css-color-4/#specifying-lab-lch>
+pub fn convert_lch(value: cssparser::Lch) -> CssColor {
+    use palette::{white_point::D50, FromColor};
+
+    let lch = palette::Lch::<D50, f32>::new(
+        value.lightness.unwrap_or(0.0),
+        value.chroma.unwrap_or(0.0),
+        value.hue.unwrap_or(0.0),
+    );
+
+    let lab = palette::Lab::<D50, f32>::from_color(lch);
+    convert_lab(cssparser::Lab {
+        lightness: Some(lab.l),
+        a: Some(lab.a),
+        b: Some(lab.b),
+        alpha: value.alpha,
+    })
+}
+
+/// <https://drafts.csswg.org/css-color-4/#specifying-oklab-oklch>
+pub fn convert_oklab(value: cssparser::Oklab) -> CssColor {
+    use palette::FromColor;
+
+    let oklab = palette::Oklab::<f32>::new(
+        value.lightness.unwrap_or(0.0),
+        value.a.unwrap_or(0.0),
+        value.b.unwrap_or(0.0),
+    );
+
+    convert_srgb(palette::Srgb::from_color(oklab), value.alpha)
+}
+
+/// <https://drafts.csswg.org/css-color-4/#specifying-oklab-oklch>
+pub fn convert_oklch(value: cssparser::Oklch) -> CssColor {
+    use palette::FromColor;
+
+    let oklch = palette::Oklch::<f32>::new(
+        value.lightness.unwrap_or(0.0),
+        value.chroma.unwrap_or(0.0),
+        value.hue.unwrap_or(0.0),
+    );
+
+    convert_srgb(palette::Srgb::from_color(oklch), value.alpha)
+}
+
+/// Clamps the color to the sRGB gamut, since colors in the perceptual color
+/// spaces can lie outside of it.
+fn convert_srgb(value: palette::Srgb<f32>, alpha: Option<f32>) -> CssColor {
+    use palette::Clamp;
+
+    let value = value.clamp();
+    CssColor::Color(Color::rgba(
+        value.red as f64,
+        value.green as f64,
+        value.blue as f64,
+        alpha.unwrap_or(1.0) as f64,
+    ))
+}
+
 pub fn convert_rgba(value: cssparser::RGBA) -> retina_style::CssColor {
     let mut color = retina_common::Color::rgb_bytes(
         value.red.unwrap_or(0),
@@ -49,6 +49,32 @@ mod util {
     }
 }
 
+/// Parses an [`<angle>`][spec]. A unitless zero is accepted as well, which
+/// is allowed by the transform functions.
+///
+/// [spec]: https://drafts.csswg.org/css-values-4/#angles
+pub(crate) fn parse_angle<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssAngle, ParseError<'i>> {
+    let location = input.current_source_location();
+    match input.next()?.clone() {
+        Token::Dimension { value, unit, .. } => {
+            let value = value as CssDecimal;
+            match unit.to_ascii_lowercase().as_str() {
+                "deg" => Ok(CssAngle::Degrees(value)),
+                "grad" => Ok(CssAngle::Gradians(value)),
+                "rad" => Ok(CssAngle::Radians(value)),
+                "turn" => Ok(CssAngle::Turns(value)),
+                _ => Err(location.new_custom_error(RetinaStyleParseError::AngleUnknownUnit(unit))),
+            }
+        }
+
+        Token::Number { value, .. } if value == 0.0 => Ok(CssAngle::default()),
+
+        token => Err(location.new_unexpected_token_error(token)),
+    }
+}
+
 pub(crate) fn parse_background_repeat<'i, 't>(
     input: &mut Parser<'i, 't>
 ) -> Result<CssBackgroundRepeat, ParseError<'i>> {
@@ -99,9 +125,152 @@ pub(crate) fn parse_background_repeat<'i, 't>(
     Ok(CssBackgroundRepeat { horizontal: first, vertical: second })
 }
 
+/// Parses the value of the [`box-shadow`][spec] property, which is `none` or
+/// a comma-separated list of shadows.
+///
+/// [spec]: https://drafts.csswg.org/css-backgrounds/#box-shadow
+/// Parses the [`border-spacing`][spec] property, which is one or two
+/// non-negative lengths. A single length is used for both directions.
+///
+/// [spec]: https://drafts.csswg.org/css2/#propdef-border-spacing
+pub(crate) fn parse_border_spacing<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssBorderSpacing, ParseError<'i>> {
+    let horizontal = parse_border_spacing_length(input)?;
+    let vertical = input.try_parse(parse_border_spacing_length).unwrap_or(horizontal);
+    Ok(CssBorderSpacing { horizontal, vertical })
+}
+
+fn parse_border_spacing_length<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssLength, ParseError<'i>> {
+    let location = input.current_source_location();
+    match parse_length(input)? {
+        CssLength::Auto | CssLength::Percentage(..) => {
+            Err(location.new_custom_error(RetinaStyleParseError::BorderSpacingInvalidLength))
+        }
+
+        CssLength::FontSize(value)
+            | CssLength::FontSizeOfRootElement(value)
+            | CssLength::Pixels(value)
+            | CssLength::UaDefaultViewportHeightPercentage(value)
+            | CssLength::UaDefaultViewportMaxPercentage(value)
+            | CssLength::UaDefaultViewportMinPercentage(value)
+            | CssLength::UaDefaultViewportWidthPercentage(value) if value < 0.0 => {
+            Err(location.new_custom_error(RetinaStyleParseError::BorderSpacingInvalidLength))
+        }
+
+        length => Ok(length),
+    }
+}
+
+pub(crate) fn parse_box_shadow<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<Vec<CssBoxShadow>, ParseError<'i>> {
+    if input.try_parse(|input| input.expect_ident_matching("none")).is_ok() {
+        return Ok(Vec::new());
+    }
+
+    input.parse_comma_separated(parse_single_box_shadow)
+}
+
+/// Parses a single [`<shadow>`][spec], of which the color, the lengths and
+/// the `inset` keyword can be specified in any order.
+///
+/// [spec]: https://drafts.csswg.org/css-backgrounds/#typedef-shadow
+fn parse_single_box_shadow<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssBoxShadow, ParseError<'i>> {
+    let mut color = None;
+    let mut lengths = None;
+    let mut inset = false;
+
+    loop {
+        if !inset && input.try_parse(|input| input.expect_ident_matching("inset")).is_ok() {
+            inset = true;
+            continue;
+        }
+
+        if color.is_none() {
+            if let Ok(value) = input.try_parse(parse_color) {
+                color = Some(value);
+                continue;
+            }
+        }
+
+        if lengths.is_none() {
+            if let Ok(value) = input.try_parse(parse_box_shadow_lengths) {
+                lengths = Some(value);
+                continue;
+            }
+        }
+
+        break;
+    }
+
+    let Some([offset_x, offset_y, blur_radius, spread_radius]) = lengths else {
+        return Err(input.new_custom_error(RetinaStyleParseError::BoxShadowMissingOffsets));
+    };
+
+    Ok(CssBoxShadow {
+        color: color.unwrap_or(CssColor::CurrentColor),
+        offset_x,
+        offset_y,
+        blur_radius,
+        spread_radius,
+        inset,
+    })
+}
+
+/// Parses the offsets, and the optional blur and spread radius of a
+/// `<shadow>`.
+fn parse_box_shadow_lengths<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<[CssLength; 4], ParseError<'i>> {
+    let offset_x = parse_box_shadow_length(input)?;
+    let offset_y = parse_box_shadow_length(input)?;
+
+    let location = input.current_source_location();
+    let Ok(blur_radius) = input.try_parse(parse_box_shadow_length) else {
+        return Ok([offset_x, offset_y, CssLength::Pixels(0.0), CssLength::Pixels(0.0)]);
+    };
+
+    if let CssLength::FontSize(value)
+        | CssLength::FontSizeOfRootElement(value)
+        | CssLength::Pixels(value)
+        | CssLength::UaDefaultViewportHeightPercentage(value)
+        | CssLength::UaDefaultViewportMaxPercentage(value)
+        | CssLength::UaDefaultViewportMinPercentage(value)
+        | CssLength::UaDefaultViewportWidthPercentage(value) = blur_radius {
+        if value < 0.0 {
+            return Err(location.new_custom_error(RetinaStyleParseError::BoxShadowNegativeBlurRadius));
+        }
+    }
+
+    let spread_radius = input.try_parse(parse_box_shadow_length).unwrap_or(CssLength::Pixels(0.0));
+    Ok([offset_x, offset_y, blur_radius, spread_radius])
+}
+
+/// Parses a length of a `<shadow>`, which can't be a percentage.
+fn parse_box_shadow_length<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssLength, ParseError<'i>> {
+    let location = input.current_source_location();
+    match parse_length(input)? {
+        CssLength::Auto | CssLength::Percentage(..) => {
+            Err(location.new_custom_error(RetinaStyleParseError::BoxShadowInvalidLength))
+        }
+        length => Ok(length),
+    }
+}
+
 pub(crate) fn parse_color<'i, 't>(
     input: &mut Parser<'i, 't>
 ) -> Result<CssColor, ParseError<'i>> {
+    if input.try_parse(|input| input.expect_function_matching("color-mix")).is_ok() {
+        return input.parse_nested_block(parse_color_mix_arguments);
+    }
+
     let location = input.current_source_location();
     let color = Color::parse(input).map_err(|e| e.basic())?;
 
@@ -117,6 +286,145 @@ pub(crate) fn parse_color<'i, 't>(
     }
 }
 
+/// The color spaces supported by [`parse_color_mix_arguments`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum ColorMixSpace {
+    Srgb,
+    SrgbLinear,
+}
+
+impl ColorMixSpace {
+    fn convert_from_srgb(&self, component: f64) -> f64 {
+        match self {
+            Self::Srgb => component,
+            Self::SrgbLinear => if component <= 0.04045 {
+                component / 12.92
+            } else {
+                ((component + 0.055) / 1.055).powf(2.4)
+            },
+        }
+    }
+
+    fn convert_to_srgb(&self, component: f64) -> f64 {
+        match self {
+            Self::Srgb => component,
+            Self::SrgbLinear => if component <= 0.0031308 {
+                component * 12.92
+            } else {
+                1.055 * component.powf(1.0 / 2.4) - 0.055
+            },
+        }
+    }
+}
+
+/// Parses the arguments of the [`color-mix()`][spec] function, and mixes the
+/// colors eagerly, since both of them are known at parse time.
+///
+/// [spec]: https://drafts.csswg.org/css-color-5/#color-mix
+fn parse_color_mix_arguments<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssColor, ParseError<'i>> {
+    input.expect_ident_matching("in")?;
+
+    let location = input.current_source_location();
+    let space = input.expect_ident_cloned()?;
+    let space = match space.to_ascii_lowercase().as_str() {
+        "srgb" => ColorMixSpace::Srgb,
+        "srgb-linear" => ColorMixSpace::SrgbLinear,
+        _ => return Err(ParseError {
+            kind: ParseErrorKind::Custom(
+                RetinaStyleParseError::ColorMixUnknownColorSpace(space)
+            ),
+            location,
+        }),
+    };
+
+    input.expect_comma()?;
+    let (first, first_percentage) = parse_color_mix_component(input)?;
+    input.expect_comma()?;
+    let (second, second_percentage) = parse_color_mix_component(input)?;
+
+    // https://drafts.csswg.org/css-color-5/#color-mix-percent-norm
+    let (first_percentage, second_percentage) = match (first_percentage, second_percentage) {
+        (None, None) => (0.5, 0.5),
+        (Some(first), None) => (first, 1.0 - first),
+        (None, Some(second)) => (1.0 - second, second),
+        (Some(first), Some(second)) => (first, second),
+    };
+
+    let sum = first_percentage + second_percentage;
+    if sum <= 0.0 {
+        return Err(ParseError {
+            kind: ParseErrorKind::Custom(RetinaStyleParseError::ColorMixPercentagesSumToZero),
+            location,
+        });
+    }
+
+    let alpha_multiplier = sum.min(1.0);
+    let first_percentage = first_percentage / sum;
+    let second_percentage = second_percentage / sum;
+
+    // Interpolate using premultiplied alpha, such that a transparent color
+    // doesn't darken the other color.
+    let alpha = first.alpha() * first_percentage + second.alpha() * second_percentage;
+    let mix = |first_component: f64, second_component: f64| {
+        let premultiplied = space.convert_from_srgb(first_component) * first.alpha() * first_percentage
+            + space.convert_from_srgb(second_component) * second.alpha() * second_percentage;
+
+        if alpha == 0.0 {
+            0.0
+        } else {
+            space.convert_to_srgb(premultiplied / alpha)
+        }
+    };
+
+    Ok(CssColor::Color(retina_common::Color::rgba(
+        mix(first.red(), second.red()),
+        mix(first.green(), second.green()),
+        mix(first.blue(), second.blue()),
+        alpha * alpha_multiplier,
+    )))
+}
+
+/// Parses `<color> && <percentage [0,100]>?`, where the percentage is returned
+/// as a fraction.
+fn parse_color_mix_component<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<(retina_common::Color, Option<f64>), ParseError<'i>> {
+    let mut percentage = input.try_parse(parse_color_mix_percentage).ok();
+
+    let location = input.current_source_location();
+    let color = match parse_color(input)? {
+        CssColor::Color(color) => color,
+        CssColor::CurrentColor => return Err(ParseError {
+            kind: ParseErrorKind::Custom(RetinaStyleParseError::ColorMixCurrentColorNotSupported),
+            location,
+        }),
+    };
+
+    if percentage.is_none() {
+        percentage = input.try_parse(parse_color_mix_percentage).ok();
+    }
+
+    Ok((color, percentage))
+}
+
+fn parse_color_mix_percentage<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<f64, ParseError<'i>> {
+    let location = input.current_source_location();
+    let percentage = input.expect_percentage()?;
+    if !(0.0..=1.0).contains(&percentage) {
+        return Err(location.new_unexpected_token_error(Token::Percentage {
+            has_sign: false,
+            unit_value: percentage,
+            int_value: None,
+        }));
+    }
+
+    Ok(percentage as f64)
+}
+
 pub(crate) fn parse_display<'i, 't>(
     input: &mut Parser<'i, 't>
 ) -> Result<CssDisplay, ParseError<'i>> {
@@ -144,12 +452,193 @@ pub(crate) fn parse_display<'i, 't>(
             outside: CssDisplayOutside::Inline,
             is_list_item: false,
         },
+        "flow-root" => CssDisplay::Normal {
+            inside: CssDisplayInside::FlowRoot,
+            outside: CssDisplayOutside::Block,
+            is_list_item: false,
+        },
+        "flex" => CssDisplay::Normal {
+            inside: CssDisplayInside::Flex,
+            outside: CssDisplayOutside::Block,
+            is_list_item: false,
+        },
+        "grid" => CssDisplay::Normal {
+            inside: CssDisplayInside::Grid,
+            outside: CssDisplayOutside::Block,
+            is_list_item: false,
+        },
+        "inline-flex" => CssDisplay::Normal {
+            inside: CssDisplayInside::Flex,
+            outside: CssDisplayOutside::Inline,
+            is_list_item: false,
+        },
+        "inline-grid" => CssDisplay::Normal {
+            inside: CssDisplayInside::Grid,
+            outside: CssDisplayOutside::Inline,
+            is_list_item: false,
+        },
+        "table" => CssDisplay::Normal {
+            inside: CssDisplayInside::Table,
+            outside: CssDisplayOutside::Block,
+            is_list_item: false,
+        },
+        "inline-table" => CssDisplay::Normal {
+            inside: CssDisplayInside::Table,
+            outside: CssDisplayOutside::Inline,
+            is_list_item: false,
+        },
+        "table-row-group" => CssDisplay::Internal(CssDisplayInternal::TableRowGroup),
+        "table-header-group" => CssDisplay::Internal(CssDisplayInternal::TableHeaderGroup),
+        "table-footer-group" => CssDisplay::Internal(CssDisplayInternal::TableFooterGroup),
+        "table-row" => CssDisplay::Internal(CssDisplayInternal::TableRow),
+        "table-cell" => CssDisplay::Internal(CssDisplayInternal::TableCell),
+        "table-column-group" => CssDisplay::Internal(CssDisplayInternal::TableColumnGroup),
+        "table-column" => CssDisplay::Internal(CssDisplayInternal::TableColumn),
+        "table-caption" => CssDisplay::Internal(CssDisplayInternal::TableCaption),
         "none" => CssDisplay::Box(CssDisplayBox::None),
         "contents" => CssDisplay::Box(CssDisplayBox::Contents),
         _ => return Err(input.new_custom_error(RetinaStyleParseError::UnknownBasicColorKeyword)),
     })
 }
 
+/// Parses the [`content`][spec] property. Only `<string>` and `counter()`
+/// values are supported in the content list.
+///
+/// [spec]: https://drafts.csswg.org/css-content/#content-property
+pub(crate) fn parse_content<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssContent, ParseError<'i>> {
+    if input.try_parse(|input| input.expect_ident_matching("normal")).is_ok() {
+        return Ok(CssContent::Normal);
+    }
+
+    if input.try_parse(|input| input.expect_ident_matching("none")).is_ok() {
+        return Ok(CssContent::None);
+    }
+
+    let mut items = Vec::new();
+    while !input.is_exhausted() {
+        if let Ok(string) = input.try_parse(|input| input.expect_string_cloned()) {
+            items.push(CssContentItem::String(string.as_ref().into()));
+            continue;
+        }
+
+        input.expect_function_matching("counter")?;
+        let name = input.parse_nested_block(|input| {
+            let name = input.expect_ident_cloned()?;
+            // The optional `<counter-style>` argument is ignored.
+            if input.try_parse(Parser::expect_comma).is_ok() {
+                input.expect_ident()?;
+            }
+            Ok(name)
+        })?;
+        items.push(CssContentItem::Counter(name.as_ref().into()));
+    }
+
+    if items.is_empty() {
+        return Err(input.new_error_for_next_token());
+    }
+
+    Ok(CssContent::Items(items))
+}
+
+/// Parses the [`aspect-ratio`][spec] property, which is `auto || <ratio>`.
+///
+/// [spec]: https://drafts.csswg.org/css-sizing-4/#aspect-ratio
+pub(crate) fn parse_aspect_ratio<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssAspectRatio, ParseError<'i>> {
+    let mut auto = input.try_parse(|input| input.expect_ident_matching("auto")).is_ok();
+
+    let Ok(ratio) = input.try_parse(parse_ratio) else {
+        if auto {
+            return Ok(CssAspectRatio::Auto);
+        }
+        return Err(input.new_error_for_next_token());
+    };
+
+    if !auto {
+        auto = input.try_parse(|input| input.expect_ident_matching("auto")).is_ok();
+    }
+
+    Ok(if auto {
+        CssAspectRatio::AutoOrRatio(ratio)
+    } else {
+        CssAspectRatio::Ratio(ratio)
+    })
+}
+
+/// Parses a [`<ratio>`][spec], which is `<number [0,∞]> [ / <number [0,∞]> ]?`.
+/// The denominator is `1` when omitted.
+///
+/// [spec]: https://drafts.csswg.org/css-values-4/#ratio-value
+pub(crate) fn parse_ratio<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssRatio, ParseError<'i>> {
+    let location = input.current_source_location();
+    let numerator = input.expect_number()? as CssDecimal;
+
+    let denominator = if input.try_parse(|input| input.expect_delim('/')).is_ok() {
+        input.expect_number()? as CssDecimal
+    } else {
+        1.0
+    };
+
+    if numerator < 0.0 || denominator < 0.0 {
+        return Err(location.new_custom_error(RetinaStyleParseError::AspectRatioNegative));
+    }
+
+    Ok(CssRatio { numerator, denominator })
+}
+
+/// Parses the [`flex-basis`][spec] property.
+///
+/// [spec]: https://drafts.csswg.org/css-flexbox/#flex-basis-property
+pub(crate) fn parse_flex_basis<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssFlexBasis, ParseError<'i>> {
+    if input.try_parse(|input| input.expect_ident_matching("auto")).is_ok() {
+        return Ok(CssFlexBasis::Auto);
+    }
+
+    if input.try_parse(|input| input.expect_ident_matching("content")).is_ok() {
+        return Ok(CssFlexBasis::Content);
+    }
+
+    let location = input.current_source_location();
+    match parse_length(input)? {
+        CssLength::FontSize(value)
+            | CssLength::FontSizeOfRootElement(value)
+            | CssLength::Percentage(value)
+            | CssLength::Pixels(value)
+            | CssLength::UaDefaultViewportHeightPercentage(value)
+            | CssLength::UaDefaultViewportMaxPercentage(value)
+            | CssLength::UaDefaultViewportMinPercentage(value)
+            | CssLength::UaDefaultViewportWidthPercentage(value) if value < 0.0 => {
+            Err(location.new_custom_error(RetinaStyleParseError::FlexBasisNegative))
+        }
+
+        length => Ok(CssFlexBasis::Length(length)),
+    }
+}
+
+/// Parses the [`flex-grow`][grow] and [`flex-shrink`][shrink] properties,
+/// which are non-negative `<number>`s.
+///
+/// [grow]: https://drafts.csswg.org/css-flexbox/#flex-grow-property
+/// [shrink]: https://drafts.csswg.org/css-flexbox/#flex-shrink-property
+pub(crate) fn parse_flex_factor<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssDecimal, ParseError<'i>> {
+    let location = input.current_source_location();
+    let factor = input.expect_number()?;
+    if factor < 0.0 {
+        return Err(location.new_custom_error(RetinaStyleParseError::FlexFactorNegative));
+    }
+
+    Ok(factor as CssDecimal)
+}
+
 pub(crate) fn parse_float<'i, 't>(
     input: &mut Parser<'i, 't>
 ) -> Result<CssFloatValue, ParseError<'i>> {
@@ -480,601 +969,2717 @@ pub(crate) fn parse_font_weight<'i, 't>(
     Err(input.new_error_for_next_token())
 }
 
-pub(crate) fn parse_image<'i, 't>(
+/// Parse the value of the `grid-column` and `grid-row` shorthands.
+///
+/// ## References
+/// * [CSS Grid Layout Module Level 2 § 8.4. Placement Shorthands](https://drafts.csswg.org/css-grid/#placement-shorthands)
+pub(crate) fn parse_grid_placement<'i, 't>(
     input: &mut Parser<'i, 't>
-) -> Result<CssImage, ParseError<'i>> {
+) -> Result<CssGridPlacement, ParseError<'i>> {
+    let start = parse_grid_line(input)?;
+
+    let end = if input.try_parse(|input| input.expect_delim('/')).is_ok() {
+        parse_grid_line(input)?
+    } else if let CssGridLine::Area(name) = &start {
+        // When the start is a `<custom-ident>`, the end is set to it as well.
+        CssGridLine::Area(name.clone())
+    } else {
+        CssGridLine::Auto
+    };
+
+    Ok(CssGridPlacement { start, end })
+}
+
+fn parse_grid_line<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssGridLine, ParseError<'i>> {
     let location = input.current_source_location();
-    let token = input.next()?;
+    match input.next()?.clone() {
+        Token::Ident(ident) if ident.eq_ignore_ascii_case("auto") => Ok(CssGridLine::Auto),
 
-    match token {
-        Token::UnquotedUrl(url) => Ok(CssImage::Url(url.to_string())),
-        Token::Function(function_name) => {
-            if function_name.eq_ignore_ascii_case("url") || function_name.eq_ignore_ascii_case("src") {
-                let url = input.parse_nested_block(|parser| {
-                    parser.expect_string_cloned().map_err(Into::into)
-                })?;
-                Ok(CssImage::Url(url.to_string()))
-            } else {
-                Err(ParseError {
-                    location,
-                    kind: ParseErrorKind::Custom(
-                        RetinaStyleParseError::ImageUnexpectedFunction(
-                            function_name.to_owned()
-                        )
-                    )
-                })
+        Token::Ident(ident) if ident.eq_ignore_ascii_case("span") => {
+            let count = input.try_parse(|input| input.expect_integer()).unwrap_or(1);
+            if count <= 0 {
+                return Err(location.new_custom_error(RetinaStyleParseError::GridLineInvalidSpan));
             }
+
+            Ok(CssGridLine::Span(count as u32))
         }
 
-        _ => Err(ParseError {
-            location,
-            kind: ParseErrorKind::Custom(
-                RetinaStyleParseError::ImageUnexpectedToken(token.clone())
-            )
-        })
+        Token::Ident(ident) => Ok(CssGridLine::Area(StrTendril::from(ident.as_ref()))),
+
+        Token::Number { int_value: Some(line), .. } if line != 0 => Ok(CssGridLine::Line(line)),
+
+        token => Err(location.new_unexpected_token_error(token)),
     }
 }
 
-pub(crate) fn parse_length<'i, 't>(
+/// Parse the value of the `grid-template-areas` property.
+///
+/// ## References
+/// * [CSS Grid Layout Module Level 2 § 7.3. Named Areas](https://drafts.csswg.org/css-grid/#grid-template-areas-property)
+pub(crate) fn parse_grid_template_areas<'i, 't>(
     input: &mut Parser<'i, 't>
-) -> Result<CssLength, ParseError<'i>> {
-    let token_location = input.current_source_location();
-    let token = input.next()
-        .cloned()
-        .map_err(|_| input.new_custom_error(RetinaStyleParseError::UnexpectedEofBasicColorKeyword))?;
+) -> Result<CssGridTemplateAreas, ParseError<'i>> {
+    if input.try_parse(|input| input.expect_ident_matching("none")).is_ok() {
+        return Ok(CssGridTemplateAreas::None);
+    }
 
-    match token {
-        Token::Ident(ident) => {
-            if ident == "auto" {
-                Ok(CssLength::Auto)
-            } else {
-                Err(ParseError {
-                    kind: ParseErrorKind::Custom(RetinaStyleParseError::LengthUnknownIdentifier(ident)),
-                    location: token_location,
-                })
-            }
+    let location = input.current_source_location();
+    let mut rows = Vec::new();
+    loop {
+        let row_location = input.current_source_location();
+        let Ok(row) = input.try_parse(|input| input.expect_string_cloned()) else {
+            break;
+        };
+
+        match parse_grid_template_areas_row(&row) {
+            Some(cells) if !cells.is_empty() => rows.push(cells),
+            _ => return Err(row_location.new_custom_error(RetinaStyleParseError::GridTemplateAreasInvalidString)),
         }
+    }
 
-        Token::Dimension { value, unit, .. } => {
-            match unit.as_ref() {
-                "em" => Ok(CssLength::FontSize(value as _)),
-                "px" => Ok(CssLength::Pixels(value as _)),
-                "rem" => Ok(CssLength::FontSizeOfRootElement(value as _)),
-                "vh" => Ok(CssLength::UaDefaultViewportHeightPercentage(value as _)),
-                "vw" => Ok(CssLength::UaDefaultViewportWidthPercentage(value as _)),
+    if rows.is_empty() {
+        return Err(input.new_error_for_next_token());
+    }
 
-                "cm" => Ok(CssLength::Pixels(value as CssDecimal * PIXELS_PER_CENTIMETER)),
-                "mm" => Ok(CssLength::Pixels(value as CssDecimal * PIXELS_PER_MILLIMETER)),
-                "Q" => Ok(CssLength::Pixels(value as CssDecimal * PIXELS_PER_QUARTER_MILLIMETER)),
-                "in" => Ok(CssLength::Pixels(value as CssDecimal * PIXELS_PER_INCH)),
-                "pc" => Ok(CssLength::Pixels(value as CssDecimal * PIXELS_PER_PICA)),
-                "pt" => Ok(CssLength::Pixels(value as CssDecimal * PIXELS_PER_POINT)),
+    if rows.iter().any(|row| row.len() != rows[0].len()) {
+        return Err(location.new_custom_error(RetinaStyleParseError::GridTemplateAreasUnequalColumnCounts));
+    }
 
-                _ => Err(ParseError {
-                    kind: ParseErrorKind::Custom(RetinaStyleParseError::LengthUnknownUnit(unit)),
-                    location: token_location,
-                }),
-            }
-        }
+    let areas = CssGridTemplateAreas::Areas(rows);
 
-        Token::Number { int_value, .. } if int_value == Some(0) => {
-            Ok(CssLength::Pixels(0.0))
-        }
+    // Every named area must be a single filled-in rectangle.
+    let CssGridTemplateAreas::Areas(rows) = &areas else { unreachable!() };
+    for name in rows.iter().flatten().flatten() {
+        let area = areas.find_area(name).unwrap();
 
-        Token::Percentage { unit_value, .. } => Ok(CssLength::Percentage(unit_value as _)),
+        let is_rectangle = (area.row_start..area.row_end).all(|row| {
+            rows[row][area.column_start..area.column_end]
+                .iter()
+                .all(|cell| cell.as_ref() == Some(name))
+        });
 
-        _ => Err(ParseError {
-            kind: ParseErrorKind::Custom(RetinaStyleParseError::LengthUnexpectedToken(token)),
-            location: token_location,
-        })
+        let cell_count = rows.iter().flatten().flatten().filter(|cell| *cell == name).count();
+        let area_size = (area.row_end - area.row_start) * (area.column_end - area.column_start);
+
+        if !is_rectangle || cell_count != area_size {
+            return Err(location.new_custom_error(RetinaStyleParseError::GridTemplateAreasNonRectangularArea));
+        }
     }
+
+    Ok(areas)
 }
 
-pub(crate) fn parse_line_height<'i, 't>(
-    input: &mut Parser<'i, 't>
-) -> Result<CssLength, ParseError<'i>> {
-    let result = input.try_parse(parse_length);
+/// Tokenize a string of `grid-template-areas` into its cells. Returns `None`
+/// when the string contains a trash token.
+///
+/// ## References
+/// * [CSS Grid Layout Module Level 2 § 7.3.1. Serialization Of Template Strings](https://drafts.csswg.org/css-grid/#valdef-grid-template-areas-string)
+fn parse_grid_template_areas_row(row: &str) -> Option<Vec<Option<StrTendril>>> {
+    fn is_ident_code_point(c: char) -> bool {
+        c.is_ascii_alphanumeric() || c == '-' || c == '_' || !c.is_ascii()
+    }
 
-    if result.is_err() {
-        if let Ok(number) = input.expect_number() {
-            return Ok(CssLength::FontSize(number as _));
+    let mut cells = Vec::new();
+    let mut chars = row.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        if c.is_ascii_whitespace() {
+            continue;
+        }
+
+        // A sequence of one or more `.` is a null cell token.
+        if c == '.' {
+            while chars.next_if(|(_, c)| *c == '.').is_some() {}
+            cells.push(None);
+            continue;
+        }
+
+        if !is_ident_code_point(c) {
+            return None;
+        }
+
+        let mut end = index + c.len_utf8();
+        while let Some((next_index, next)) = chars.next_if(|(_, c)| is_ident_code_point(*c)) {
+            end = next_index + next.len_utf8();
         }
+
+        cells.push(Some(StrTendril::from(&row[index..end])));
     }
 
-    result
+    Some(cells)
 }
 
-pub(crate) fn parse_line_style<'i, 't>(
+/// Parse the value of the `grid-template-columns` and `grid-template-rows`
+/// properties. Line names are accepted, but not stored.
+///
+/// ## References
+/// * [CSS Grid Layout Module Level 2 § 7.2. Explicit Track Sizing](https://drafts.csswg.org/css-grid/#track-sizing)
+pub(crate) fn parse_grid_track_list<'i, 't>(
     input: &mut Parser<'i, 't>
-) -> Result<CssLineStyle, ParseError<'i>> {
-    let token = input.next()
-        .cloned()
-        .map_err(|_| input.new_custom_error(RetinaStyleParseError::LineStyleUnknownKeyword))?;
+) -> Result<CssGridTrackList, ParseError<'i>> {
+    if input.try_parse(|input| input.expect_ident_matching("none")).is_ok() {
+        return Ok(CssGridTrackList::None);
+    }
 
-    let Token::Ident(ident) = token else {
-        return Err(input.new_custom_error(RetinaStyleParseError::LineStyleExpectedKeyword));
-    };
+    if input.try_parse(|input| input.expect_ident_matching("subgrid")).is_ok() {
+        while input.try_parse(skip_grid_line_names).is_ok() {}
+        return Ok(CssGridTrackList::Subgrid);
+    }
 
-    CssLineStyle::from_str(ident.as_ref())
-        .ok_or_else(|| input.new_custom_error(RetinaStyleParseError::LineStyleUnknownKeyword))
-}
+    let location = input.current_source_location();
+    let mut tracks = Vec::new();
+    loop {
+        _ = input.try_parse(skip_grid_line_names);
 
+        if input.try_parse(|input| input.expect_function_matching("repeat")).is_ok() {
+            input.parse_nested_block(|input| parse_grid_track_repeat(input, &mut tracks))?;
+            continue;
+        }
 
-pub(crate) fn parse_single_value<'i, 't>(input: &mut Parser<'i, 't>) -> Result<Value, ParseError<'i>> {
-    if let Ok(color) = input.try_parse(parse_color) {
-        return Ok(Value::Color(color));
+        match input.try_parse(parse_grid_track_size) {
+            Ok(track) => tracks.push(track),
+            Err(..) => break,
+        }
     }
 
-    if let Ok(display) = input.try_parse(parse_display) {
-        return Ok(Value::Display(display));
+    if tracks.is_empty() {
+        return Err(location.new_custom_error(RetinaStyleParseError::GridTrackListEmpty));
     }
 
-    if let Ok(image) = input.try_parse(parse_image) {
-        return Ok(Value::Image(image));
+    Ok(CssGridTrackList::Tracks(tracks))
+}
+
+/// Parse the arguments of `repeat(<integer>, <track-size>+)`, and append the
+/// repeated tracks to `tracks`.
+fn parse_grid_track_repeat<'i, 't>(
+    input: &mut Parser<'i, 't>,
+    tracks: &mut Vec<CssGridTrackSize>,
+) -> Result<(), ParseError<'i>> {
+    /// Prevents absurd amounts of tracks, which the specification allows
+    /// user agents to clamp.
+    const MAXIMUM_REPEAT_COUNT: i32 = 1000;
+
+    let location = input.current_source_location();
+    let count = input.expect_integer()?;
+    if count <= 0 {
+        return Err(location.new_custom_error(RetinaStyleParseError::GridRepeatInvalidCount));
     }
 
-    if let Ok(length) = input.try_parse(parse_length) {
-        return Ok(Value::Length(length));
+    input.expect_comma()?;
+
+    let mut repeated = Vec::new();
+    loop {
+        _ = input.try_parse(skip_grid_line_names);
+        match input.try_parse(parse_grid_track_size) {
+            Ok(track) => repeated.push(track),
+            Err(..) => break,
+        }
     }
 
-    if let Ok(line_style) = input.try_parse(parse_line_style) {
-        return Ok(Value::LineStyle(line_style));
+    if repeated.is_empty() {
+        return Err(input.new_error_for_next_token());
     }
 
-    if let Ok(white_space) = input.try_parse(parse_white_space) {
-        return Ok(Value::WhiteSpace(white_space));
+    for _ in 0..count.min(MAXIMUM_REPEAT_COUNT) {
+        tracks.extend_from_slice(&repeated);
     }
 
-    let token = input.next().ok().cloned();
-    Err(input.new_custom_error(RetinaStyleParseError::UnknownValue(token)))
+    Ok(())
 }
 
-pub(crate) fn parse_text_decoration<'i, 't>(
+fn parse_grid_track_size<'i, 't>(
     input: &mut Parser<'i, 't>
-) -> Result<CssTextDecoration, ParseError<'i>> {
-    let mut result = CssTextDecoration {
-        color: None,
-        line: None,
-        style: None,
-    };
-
-    while !input.is_exhausted() {
-        if result.color.is_none() {
-            if let Ok(color) = input.try_parse(parse_color) {
-                result.color = Some(color);
-                continue;
+) -> Result<CssGridTrackSize, ParseError<'i>> {
+    if input.try_parse(|input| input.expect_function_matching("minmax")).is_ok() {
+        return input.parse_nested_block(|input| {
+            let location = input.current_source_location();
+            let min = parse_grid_track_breadth(input)?;
+            if let CssGridTrackBreadth::Flex(..) = min {
+                return Err(location.new_custom_error(RetinaStyleParseError::GridTrackSizeFlexibleMinimum));
             }
-        }
 
-        if result.line.is_none() {
-            if let Ok(line) = input.try_parse(parse_text_decoration_line) {
-                result.line = Some(line);
-                continue;
-            }
-        }
+            input.expect_comma()?;
+            let max = parse_grid_track_breadth(input)?;
 
-        if result.style.is_none() {
-            if let Ok(style) = input.try_parse(parse_text_decoration_style) {
-                result.style = Some(style);
-                continue;
+            Ok(CssGridTrackSize::MinMax { min, max })
+        });
+    }
+
+    parse_grid_track_breadth(input).map(CssGridTrackSize::Breadth)
+}
+
+fn parse_grid_track_breadth<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssGridTrackBreadth, ParseError<'i>> {
+    let location = input.current_source_location();
+    let state = input.state();
+
+    match input.next()?.clone() {
+        Token::Ident(ident) if ident.eq_ignore_ascii_case("auto") => return Ok(CssGridTrackBreadth::Auto),
+        Token::Ident(ident) if ident.eq_ignore_ascii_case("max-content") => return Ok(CssGridTrackBreadth::MaxContent),
+        Token::Ident(ident) if ident.eq_ignore_ascii_case("min-content") => return Ok(CssGridTrackBreadth::MinContent),
+
+        Token::Dimension { value, unit, .. } if unit.eq_ignore_ascii_case("fr") => {
+            if value < 0.0 {
+                return Err(location.new_custom_error(RetinaStyleParseError::GridTrackBreadthNegative));
             }
+
+            return Ok(CssGridTrackBreadth::Flex(value as _));
         }
 
-        return Err(input.new_error_for_next_token());
+        _ => input.reset(&state),
     }
 
-    Ok(result)
+    let length = parse_length(input)?;
+    match length {
+        CssLength::FontSize(value)
+            | CssLength::FontSizeOfRootElement(value)
+            | CssLength::Percentage(value)
+            | CssLength::Pixels(value)
+            | CssLength::UaDefaultViewportHeightPercentage(value)
+            | CssLength::UaDefaultViewportMaxPercentage(value)
+            | CssLength::UaDefaultViewportMinPercentage(value)
+            | CssLength::UaDefaultViewportWidthPercentage(value) if value < 0.0 => {
+            Err(location.new_custom_error(RetinaStyleParseError::GridTrackBreadthNegative))
+        }
+
+        length => Ok(CssGridTrackBreadth::Length(length)),
+    }
 }
 
-pub(crate) fn parse_text_decoration_line<'i, 't>(
+/// Skip a `[<line-names>]` block.
+///
+/// TODO: store the line names, such that items can be placed against them.
+fn skip_grid_line_names<'i, 't>(
     input: &mut Parser<'i, 't>
-) -> Result<CssTextDecorationLine, ParseError<'i>> {
-    let location = input.current_source_location();
-    let keyword = input.expect_ident()?;
-    CssTextDecorationLine::iter()
-        .find(|style| {
-            style.as_ref().eq_ignore_ascii_case(&keyword)
-        })
-        .ok_or_else(|| ParseError {
-            kind: ParseErrorKind::Custom(
-                RetinaStyleParseError::UnknownKeyword(keyword.clone())
-            ),
-            location,
-        })
+) -> Result<(), ParseError<'i>> {
+    input.expect_square_bracket_block()?;
+    input.parse_nested_block(|input| {
+        while input.next().is_ok() {}
+        Ok(())
+    })
 }
 
-pub(crate) fn parse_text_decoration_style<'i, 't>(
+pub(crate) fn parse_image<'i, 't>(
     input: &mut Parser<'i, 't>
-) -> Result<CssTextDecorationStyle, ParseError<'i>> {
+) -> Result<CssImage, ParseError<'i>> {
     let location = input.current_source_location();
-    let keyword = input.expect_ident()?;
-    CssTextDecorationStyle::iter()
-        .find(|style| {
-            style.as_ref().eq_ignore_ascii_case(&keyword)
-        })
-        .ok_or_else(|| ParseError {
-            kind: ParseErrorKind::Custom(
-                RetinaStyleParseError::UnknownKeyword(keyword.clone())
-            ),
+    let token = input.next()?;
+
+    match token {
+        Token::UnquotedUrl(url) => Ok(CssImage::Url(url.to_string())),
+        Token::Function(function_name) => {
+            if function_name.eq_ignore_ascii_case("url") || function_name.eq_ignore_ascii_case("src") {
+                let url = input.parse_nested_block(|parser| {
+                    parser.expect_string_cloned().map_err(Into::into)
+                })?;
+                Ok(CssImage::Url(url.to_string()))
+            } else if function_name.eq_ignore_ascii_case("linear-gradient") {
+                let gradient = input.parse_nested_block(parse_linear_gradient)?;
+                Ok(CssImage::Gradient(CssGradient::Linear(gradient)))
+            } else if function_name.eq_ignore_ascii_case("radial-gradient") {
+                let gradient = input.parse_nested_block(parse_radial_gradient)?;
+                Ok(CssImage::Gradient(CssGradient::Radial(gradient)))
+            } else {
+                Err(ParseError {
+                    location,
+                    kind: ParseErrorKind::Custom(
+                        RetinaStyleParseError::ImageUnexpectedFunction(
+                            function_name.to_owned()
+                        )
+                    )
+                })
+            }
+        }
+
+        _ => Err(ParseError {
             location,
+            kind: ParseErrorKind::Custom(
+                RetinaStyleParseError::ImageUnexpectedToken(token.clone())
+            )
         })
+    }
 }
 
-pub(crate) fn parse_text_transform<'i, 't>(
+/// Parses the arguments of [`linear-gradient()`][spec], e.g.
+/// `to right, red, blue 80%`.
+///
+/// [spec]: https://drafts.csswg.org/css-images-3/#linear-gradient-syntax
+fn parse_linear_gradient<'i, 't>(
     input: &mut Parser<'i, 't>
-) -> Result<CssTextTransform, ParseError<'i>> {
-    let location = input.current_source_location();
-    let keyword = input.expect_ident()?;
-    CssTextTransform::iter()
-        .find(|style| {
-            style.as_ref().eq_ignore_ascii_case(&keyword)
-        })
-        .ok_or_else(|| ParseError {
-            kind: ParseErrorKind::Custom(
-                RetinaStyleParseError::UnknownKeyword(keyword.clone())
-            ),
-            location,
-        })
+) -> Result<CssLinearGradient, ParseError<'i>> {
+    let direction = if let Ok(angle) = input.try_parse(parse_angle) {
+        input.expect_comma()?;
+        CssLinearGradientDirection::Angle(angle)
+    } else if input.try_parse(|input| input.expect_ident_matching("to")).is_ok() {
+        let direction = parse_side_or_corner(input)?;
+        input.expect_comma()?;
+        direction
+    } else {
+        CssLinearGradientDirection::default()
+    };
+
+    Ok(CssLinearGradient {
+        direction,
+        color_stops: parse_color_stop_list(input)?,
+    })
 }
 
-fn parse_specific_value<'i, 't>(
-    input: &mut Parser<'i, 't>,
-    property: Property,
-) -> Option<Result<Value, ParseError<'i>>> {
-    match property {
-        Property::BackgroundRepeat => Some(parse_background_repeat(input).map(|value| Value::BackgroundRepeat(value))),
-        Property::Cursor => Some(util::parse_enum(input).map(|value| Value::Cursor(value))),
-        Property::Float => Some(parse_float(input).map(|float| Value::Float(float))),
-        Property::Font => Some(parse_font_shorthand(input).map(|shorthand| Value::FontShorthand(shorthand))),
-        Property::FontFamily => Some(parse_font_families(input).map(|families| Value::FontFamily(families))),
-        Property::FontKerning => Some(parse_font_kerning(input).map(|kerning| Value::FontKerning(kerning))),
-        Property::FontSize => Some(parse_font_size(input)),
-        Property::FontStyle => Some(parse_font_style(input).map(|style| Value::FontStyle(style))),
-        Property::FontVariantCaps => Some(parse_font_variant_caps(input).map(|ligatures| Value::FontVariantCaps(ligatures))),
-        Property::FontVariantEastAsian => Some(parse_font_variant_east_asian(input).map(|value| Value::FontVariantEastAsian(value))),
-        Property::FontVariantLigatures => Some(parse_font_variant_ligatures(input).map(|ligatures| Value::FontVariantLigatures(ligatures))),
-        Property::FontVariantPosition => Some(parse_font_variant_position(input).map(|value| Value::FontVariantPosition(value))),
-        Property::FontWeight => Some(parse_font_weight(input).map(|value| Value::FontWeight(value))),
-        Property::TextDecoration => Some(parse_text_decoration(input).map(|value| Value::TextDecoration(value))),
-        Property::TextDecorationLine => Some(parse_text_decoration_line(input).map(|value| Value::TextDecorationLine(value))),
-        Property::TextDecorationStyle => Some(parse_text_decoration_style(input).map(|value| Value::TextDecorationStyle(value))),
-        Property::TextTransform => Some(parse_text_transform(input).map(|value| Value::TextTransform(value))),
+/// Parses the `<side-or-corner>` after the `to` keyword, which is a
+/// horizontal and/or a vertical side, in any order.
+fn parse_side_or_corner<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssLinearGradientDirection, ParseError<'i>> {
+    let mut horizontal = None;
+    let mut vertical = None;
 
-        _ => None,
-    }
-}
+    loop {
+        let location = input.current_source_location();
+        let Ok(ident) = input.try_parse(|input| input.expect_ident().cloned()) else {
+            break;
+        };
 
-pub(crate) fn parse_value<'i, 't>(input: &mut Parser<'i, 't>, property: Property) -> Result<Value, ParseError<'i>> {
-    if property == Property::Invalid {
-        return Err(input.new_error_for_next_token());
+        match ident.to_ascii_lowercase().as_str() {
+            "left" if horizontal.is_none() => horizontal = Some(CssHorizontalSide::Left),
+            "right" if horizontal.is_none() => horizontal = Some(CssHorizontalSide::Right),
+            "top" if vertical.is_none() => vertical = Some(CssVerticalSide::Top),
+            "bottom" if vertical.is_none() => vertical = Some(CssVerticalSide::Bottom),
+            _ => return Err(location.new_custom_error(RetinaStyleParseError::GradientUnknownSideKeyword(ident))),
+        }
     }
 
-    if let Some(result) = parse_wide_keywords(input) {
-        return result;
+    if horizontal.is_none() && vertical.is_none() {
+        let token = input.next()?.clone();
+        return Err(input.new_unexpected_token_error(token));
     }
 
-    if let Some(result) = parse_specific_value(input, property) {
-        return result;
-    }
+    Ok(CssLinearGradientDirection::ToSideOrCorner { horizontal, vertical })
+}
 
-    let value = parse_single_value(input)?;
-    if input.is_exhausted() {
-        return Ok(value);
-    }
+/// Parses the arguments of [`radial-gradient()`][spec], e.g.
+/// `circle at top left, red, blue`.
+///
+/// [spec]: https://drafts.csswg.org/css-images-3/#radial-gradient-syntax
+fn parse_radial_gradient<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssRadialGradient, ParseError<'i>> {
+    let shape = if input.try_parse(|input| input.expect_ident_matching("circle")).is_ok() {
+        Some(CssRadialGradientShape::Circle)
+    } else if input.try_parse(|input| input.expect_ident_matching("ellipse")).is_ok() {
+        Some(CssRadialGradientShape::Ellipse)
+    } else {
+        None
+    };
 
-    let mut values = Vec::with_capacity(4);
-    values.push(value);
+    let center = if input.try_parse(|input| input.expect_ident_matching("at")).is_ok() {
+        Some(parse_position(input)?)
+    } else {
+        None
+    };
 
-    while !input.is_exhausted() {
-        values.push(parse_single_value(input)?);
+    if shape.is_some() || center.is_some() {
+        input.expect_comma()?;
     }
 
-    assert_ne!(values.len(), 1);
-
-    match &values[..] {
-        //
-        // Border
-        //
-        &[Value::Length(length), Value::LineStyle(style)] => Ok(Value::BorderLonghand(
-            CssBorderLonghand {
-                width: Some(length),
-                style: Some(style),
-                color: None,
+    Ok(CssRadialGradient {
+        shape: shape.unwrap_or_default(),
+        center: center.unwrap_or((CssLength::Percentage(0.5), CssLength::Percentage(0.5))),
+        color_stops: parse_color_stop_list(input)?,
+    })
+}
+
+/// Parses a `<color-stop-list>`, which consists of at least two stops. A
+/// stop with two positions, e.g. `red 10% 20%`, is a shorthand for two stops
+/// of the same color.
+///
+/// TODO: color interpolation hints, e.g. the `30%` of `red, 30%, blue`,
+///       aren't supported.
+///
+/// [spec]: https://drafts.csswg.org/css-images-4/#typedef-color-stop-list
+fn parse_color_stop_list<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<Vec<CssColorStop>, ParseError<'i>> {
+    let color_stops: Vec<CssColorStop> = input.parse_comma_separated(|input| {
+        let color = parse_color(input)?;
+
+        let mut stops = vec![CssColorStop {
+            color,
+            position: input.try_parse(parse_length).ok(),
+        }];
+
+        if stops[0].position.is_some() {
+            if let Ok(position) = input.try_parse(parse_length) {
+                stops.push(CssColorStop { color, position: Some(position) });
             }
-        )),
+        }
 
-        &[Value::Length(length), Value::LineStyle(style), Value::Color(color)] => Ok(Value::BorderLonghand(
-            CssBorderLonghand {
-                width: Some(length),
-                style: Some(style),
-                color: Some(color),
+        Ok(stops)
+    })?.into_iter().flatten().collect();
+
+    if color_stops.len() < 2 {
+        return Err(input.new_custom_error(RetinaStyleParseError::GradientTooFewColorStops));
+    }
+
+    Ok(color_stops)
+}
+
+/// A component of a `<position>`, which can be either a keyword of a
+/// specific axis, or a keyword or length that applies to either axis.
+enum PositionComponent {
+    Horizontal(CssLength),
+    Vertical(CssLength),
+    Either(CssLength),
+}
+
+/// Parses a [`<position>`][spec] of one or two components, e.g. `center`,
+/// `right top` or `25% 75%`, into the horizontal and vertical offsets.
+///
+/// TODO: the syntax with offsets from the sides, e.g.
+///       `right 10px bottom 20px`, isn't supported.
+///
+/// [spec]: https://drafts.csswg.org/css-values-4/#position
+pub(crate) fn parse_position<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<(CssLength, CssLength), ParseError<'i>> {
+    use PositionComponent::*;
+
+    let location = input.current_source_location();
+    let first = parse_position_component(input)?;
+    let second = input.try_parse(parse_position_component).ok();
+
+    match (first, second) {
+        (Vertical(y), None) => Ok((CssLength::Percentage(0.5), y)),
+        (Horizontal(x) | Either(x), None) => Ok((x, CssLength::Percentage(0.5))),
+        (Horizontal(x) | Either(x), Some(Vertical(y) | Either(y))) => Ok((x, y)),
+        (Vertical(y) | Either(y), Some(Horizontal(x))) => Ok((x, y)),
+        (Vertical(y), Some(Either(x))) => Ok((x, y)),
+        _ => Err(location.new_custom_error(RetinaStyleParseError::PositionInvalidKeywordCombination)),
+    }
+}
+
+fn parse_position_component<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<PositionComponent, ParseError<'i>> {
+    let location = input.current_source_location();
+    let Ok(ident) = input.try_parse(|input| input.expect_ident().cloned()) else {
+        return Ok(PositionComponent::Either(parse_length(input)?));
+    };
+
+    match ident.to_ascii_lowercase().as_str() {
+        "center" => Ok(PositionComponent::Either(CssLength::Percentage(0.5))),
+        "left" => Ok(PositionComponent::Horizontal(CssLength::Percentage(0.0))),
+        "right" => Ok(PositionComponent::Horizontal(CssLength::Percentage(1.0))),
+        "top" => Ok(PositionComponent::Vertical(CssLength::Percentage(0.0))),
+        "bottom" => Ok(PositionComponent::Vertical(CssLength::Percentage(1.0))),
+        _ => Err(location.new_custom_error(RetinaStyleParseError::PositionUnknownKeyword(ident))),
+    }
+}
+
+pub(crate) fn parse_length<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssLength, ParseError<'i>> {
+    let token_location = input.current_source_location();
+    let token = input.next()
+        .cloned()
+        .map_err(|_| input.new_custom_error(RetinaStyleParseError::UnexpectedEofBasicColorKeyword))?;
+
+    match token {
+        Token::Ident(ident) => {
+            if ident == "auto" {
+                Ok(CssLength::Auto)
+            } else {
+                Err(ParseError {
+                    kind: ParseErrorKind::Custom(RetinaStyleParseError::LengthUnknownIdentifier(ident)),
+                    location: token_location,
+                })
             }
-        )),
+        }
 
-        &[Value::LineStyle(style), Value::Color(color)] => Ok(Value::BorderLonghand(
-            CssBorderLonghand {
-                width: None,
-                style: Some(style),
-                color: Some(color),
+        Token::Dimension { value, unit, .. } => {
+            match unit.as_ref() {
+                "em" => Ok(CssLength::FontSize(value as _)),
+                "px" => Ok(CssLength::Pixels(value as _)),
+                "rem" => Ok(CssLength::FontSizeOfRootElement(value as _)),
+
+                // We don't have any retractable UI, so the small, large and
+                // dynamic viewport sizes are all equal to the default one.
+                "vh" | "svh" | "lvh" | "dvh" => Ok(CssLength::UaDefaultViewportHeightPercentage(value as _)),
+                "vw" | "svw" | "lvw" | "dvw" => Ok(CssLength::UaDefaultViewportWidthPercentage(value as _)),
+                "vmin" | "svmin" | "lvmin" | "dvmin" => Ok(CssLength::UaDefaultViewportMinPercentage(value as _)),
+                "vmax" | "svmax" | "lvmax" | "dvmax" => Ok(CssLength::UaDefaultViewportMaxPercentage(value as _)),
+
+                "cm" => Ok(CssLength::Pixels(value as CssDecimal * PIXELS_PER_CENTIMETER)),
+                "mm" => Ok(CssLength::Pixels(value as CssDecimal * PIXELS_PER_MILLIMETER)),
+                "Q" => Ok(CssLength::Pixels(value as CssDecimal * PIXELS_PER_QUARTER_MILLIMETER)),
+                "in" => Ok(CssLength::Pixels(value as CssDecimal * PIXELS_PER_INCH)),
+                "pc" => Ok(CssLength::Pixels(value as CssDecimal * PIXELS_PER_PICA)),
+                "pt" => Ok(CssLength::Pixels(value as CssDecimal * PIXELS_PER_POINT)),
+
+                _ => Err(ParseError {
+                    kind: ParseErrorKind::Custom(RetinaStyleParseError::LengthUnknownUnit(unit)),
+                    location: token_location,
+                }),
             }
-        )),
+        }
+
+        Token::Number { int_value, .. } if int_value == Some(0) => {
+            Ok(CssLength::Pixels(0.0))
+        }
+
+        Token::Percentage { unit_value, .. } => Ok(CssLength::Percentage(unit_value as _)),
+
+        Token::Function(name) if is_math_function(&name) => {
+            let expression = input.parse_nested_block(|input| parse_math_function(&name, input))?;
+            expression.to_length()
+                .ok_or_else(|| token_location.new_custom_error(RetinaStyleParseError::CalcInvalidExpression))
+        }
+
+        _ => Err(ParseError {
+            kind: ParseErrorKind::Custom(RetinaStyleParseError::LengthUnexpectedToken(token)),
+            location: token_location,
+        })
+    }
+}
+
+/// Parses the [`min-width`, `min-height`][min], [`max-width` and
+/// `max-height`][max] properties. The keyword that removes the constraint,
+/// which is `auto` for the former and `none` for the latter, is stored as
+/// [`CssLength::Auto`].
+///
+/// [min]: https://drafts.csswg.org/css-sizing-3/#min-size-properties
+/// [max]: https://drafts.csswg.org/css-sizing-3/#max-size-properties
+pub(crate) fn parse_size_constraint<'i, 't>(
+    input: &mut Parser<'i, 't>,
+    unconstrained_keyword: &'static str,
+) -> Result<CssLength, ParseError<'i>> {
+    if input.try_parse(|input| input.expect_ident_matching(unconstrained_keyword)).is_ok() {
+        return Ok(CssLength::Auto);
+    }
+
+    if input.try_parse(|input| input.expect_ident_matching("min-content")).is_ok() {
+        return Ok(CssLength::MinContent);
+    }
+
+    if input.try_parse(|input| input.expect_ident_matching("max-content")).is_ok() {
+        return Ok(CssLength::MaxContent);
+    }
+
+    let location = input.current_source_location();
+    match parse_length(input)? {
+        CssLength::Auto => {
+            Err(location.new_custom_error(RetinaStyleParseError::SizeConstraintUnexpectedAuto))
+        }
+
+        CssLength::FontSize(value)
+            | CssLength::FontSizeOfRootElement(value)
+            | CssLength::Percentage(value)
+            | CssLength::Pixels(value)
+            | CssLength::UaDefaultViewportHeightPercentage(value)
+            | CssLength::UaDefaultViewportMaxPercentage(value)
+            | CssLength::UaDefaultViewportMinPercentage(value)
+            | CssLength::UaDefaultViewportWidthPercentage(value) if value < 0.0 => {
+            Err(location.new_custom_error(RetinaStyleParseError::SizeConstraintNegative))
+        }
+
+        length => Ok(length),
+    }
+}
+
+pub(crate) fn parse_line_height<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssLength, ParseError<'i>> {
+    let result = input.try_parse(parse_length);
+
+    if result.is_err() {
+        if let Ok(number) = input.expect_number() {
+            return Ok(CssLength::FontSize(number as _));
+        }
+    }
+
+    result
+}
+
+/// Parse the `letter-spacing` and `word-spacing` properties, which are
+/// `normal | <length-percentage>`, where `normal` means no additional spacing.
+///
+/// # References
+/// * [CSS Text Module Level 4 § 8.1. Word Spacing: the `word-spacing` property](https://drafts.csswg.org/css-text-4/#word-spacing-property)
+/// * [CSS Text Module Level 4 § 8.2. Tracking: the `letter-spacing` property](https://drafts.csswg.org/css-text-4/#letter-spacing-property)
+pub(crate) fn parse_spacing<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssLength, ParseError<'i>> {
+    if input.try_parse(|input| input.expect_ident_matching("normal")).is_ok() {
+        return Ok(CssLength::Pixels(0.0));
+    }
+
+    let location = input.current_source_location();
+    match parse_length(input)? {
+        CssLength::Auto => Err(location.new_custom_error(RetinaStyleParseError::SpacingUnexpectedAuto)),
+        length => Ok(length),
+    }
+}
+
+pub(crate) fn parse_line_style<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssLineStyle, ParseError<'i>> {
+    let token = input.next()
+        .cloned()
+        .map_err(|_| input.new_custom_error(RetinaStyleParseError::LineStyleUnknownKeyword))?;
+
+    let Token::Ident(ident) = token else {
+        return Err(input.new_custom_error(RetinaStyleParseError::LineStyleExpectedKeyword));
+    };
+
+    CssLineStyle::from_str(ident.as_ref())
+        .ok_or_else(|| input.new_custom_error(RetinaStyleParseError::LineStyleUnknownKeyword))
+}
+
+/// Parse the `outline` shorthand, which is `<outline-color> ||
+/// <outline-style> || <outline-width>` in any order.
+///
+/// # References
+/// * [CSS Basic User Interface Module Level 4 § 5.1. Outlines Shorthand: the `outline` property](https://drafts.csswg.org/css-ui/#outline)
+pub(crate) fn parse_outline<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssOutline, ParseError<'i>> {
+    let mut result = CssOutline::default();
+
+    while !input.is_exhausted() {
+        if result.color.is_none() {
+            if let Ok(color) = input.try_parse(parse_color) {
+                result.color = Some(color);
+                continue;
+            }
+        }
+
+        // The style is tried before the width, since `auto` would otherwise
+        // be parsed as a length.
+        if result.style.is_none() {
+            if let Ok(style) = input.try_parse(parse_outline_style) {
+                result.style = Some(style);
+                continue;
+            }
+        }
+
+        if result.width.is_none() {
+            if let Ok(width) = input.try_parse(parse_length) {
+                result.width = Some(width);
+                continue;
+            }
+        }
+
+        return Err(input.new_error_for_next_token());
+    }
+
+    Ok(result)
+}
+
+/// Parse the `outline-style` property, which is `auto` or a line style other
+/// than `hidden`.
+///
+/// # References
+/// * [CSS Basic User Interface Module Level 4 § 5.3. Outline Patterns: the `outline-style` property](https://drafts.csswg.org/css-ui/#outline-style)
+pub(crate) fn parse_outline_style<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssOutlineStyle, ParseError<'i>> {
+    if input.try_parse(|input| input.expect_ident_matching("auto")).is_ok() {
+        return Ok(CssOutlineStyle::Auto);
+    }
+
+    let location = input.current_source_location();
+    match parse_line_style(input)? {
+        CssLineStyle::Hidden => Err(location.new_custom_error(RetinaStyleParseError::OutlineStyleHidden)),
+        style => Ok(CssOutlineStyle::LineStyle(style)),
+    }
+}
+
+
+fn is_math_function(name: &str) -> bool {
+    ["calc", "clamp", "max", "min"].iter().any(|function| name.eq_ignore_ascii_case(function))
+}
+
+/// Parses the arguments of the [math function][spec] with the given name.
+///
+/// [spec]: https://drafts.csswg.org/css-values-4/#math
+fn parse_math_function<'i, 't>(
+    name: &str,
+    input: &mut Parser<'i, 't>,
+) -> Result<CalcExpression, ParseError<'i>> {
+    if name.eq_ignore_ascii_case("calc") {
+        return parse_calc_expression(input);
+    }
+
+    let arguments = input.parse_comma_separated(parse_calc_expression)?;
+
+    if name.eq_ignore_ascii_case("min") {
+        return Ok(CalcExpression::Min(arguments));
+    }
+
+    if name.eq_ignore_ascii_case("max") {
+        return Ok(CalcExpression::Max(arguments));
+    }
+
+    let Ok([min, value, max]) = <[CalcExpression; 3]>::try_from(arguments) else {
+        return Err(input.new_custom_error(RetinaStyleParseError::CalcInvalidExpression));
+    };
+
+    Ok(CalcExpression::Clamp {
+        min: Box::new(min),
+        value: Box::new(value),
+        max: Box::new(max),
+    })
+}
+
+/// Parses a [`<calc-sum>`][spec].
+///
+/// [spec]: https://drafts.csswg.org/css-values-4/#typedef-calc-sum
+fn parse_calc_expression<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CalcExpression, ParseError<'i>> {
+    let mut expression = parse_calc_product(input)?;
+
+    loop {
+        let state = input.state();
+        match input.next().cloned() {
+            Ok(Token::Delim('+')) => {
+                let rhs = parse_calc_product(input)?;
+                expression = CalcExpression::Sum(Box::new(expression), Box::new(rhs));
+            }
+
+            Ok(Token::Delim('-')) => {
+                let rhs = parse_calc_product(input)?;
+                expression = CalcExpression::Difference(Box::new(expression), Box::new(rhs));
+            }
+
+            _ => {
+                input.reset(&state);
+                return Ok(expression);
+            }
+        }
+    }
+}
+
+/// Parses a [`<calc-product>`][spec].
+///
+/// [spec]: https://drafts.csswg.org/css-values-4/#typedef-calc-product
+fn parse_calc_product<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CalcExpression, ParseError<'i>> {
+    let mut expression = parse_calc_value(input)?;
+
+    loop {
+        let state = input.state();
+        match input.next().cloned() {
+            Ok(Token::Delim('*')) => {
+                let rhs = parse_calc_value(input)?;
+                expression = CalcExpression::Product(Box::new(expression), Box::new(rhs));
+            }
+
+            Ok(Token::Delim('/')) => {
+                let rhs = parse_calc_value(input)?;
+                expression = CalcExpression::Quotient(Box::new(expression), Box::new(rhs));
+            }
+
+            _ => {
+                input.reset(&state);
+                return Ok(expression);
+            }
+        }
+    }
+}
+
+/// Parses a [`<calc-value>`][spec], which is a number, length, percentage,
+/// parenthesized expression or nested math function.
+///
+/// [spec]: https://drafts.csswg.org/css-values-4/#typedef-calc-value
+fn parse_calc_value<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CalcExpression, ParseError<'i>> {
+    let location = input.current_source_location();
+    let state = input.state();
+
+    match input.next()?.clone() {
+        Token::Number { value, .. } => return Ok(CalcExpression::Number(value as _)),
+        Token::ParenthesisBlock => return input.parse_nested_block(parse_calc_expression),
+        Token::Function(name) if is_math_function(&name) => {
+            return input.parse_nested_block(|input| parse_math_function(&name, input));
+        }
+
+        Token::Dimension { .. } | Token::Percentage { .. } => input.reset(&state),
+        token => return Err(location.new_custom_error(RetinaStyleParseError::CalcUnexpectedToken(token))),
+    }
+
+    parse_length(input).map(CalcExpression::Length)
+}
+
+pub(crate) fn parse_single_value<'i, 't>(input: &mut Parser<'i, 't>) -> Result<Value, ParseError<'i>> {
+    if let Ok(color) = input.try_parse(parse_color) {
+        return Ok(Value::Color(color));
+    }
+
+    if let Ok(display) = input.try_parse(parse_display) {
+        return Ok(Value::Display(display));
+    }
+
+    if let Ok(image) = input.try_parse(parse_image) {
+        return Ok(Value::Image(image));
+    }
+
+    if let Ok(length) = input.try_parse(parse_length) {
+        return Ok(Value::Length(length));
+    }
+
+    if let Ok(line_style) = input.try_parse(parse_line_style) {
+        return Ok(Value::LineStyle(line_style));
+    }
+
+    if let Ok(white_space) = input.try_parse(parse_white_space) {
+        return Ok(Value::WhiteSpace(white_space));
+    }
+
+    let token = input.next().ok().cloned();
+    Err(input.new_custom_error(RetinaStyleParseError::UnknownValue(token)))
+}
+
+pub(crate) fn parse_text_decoration<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssTextDecoration, ParseError<'i>> {
+    let mut result = CssTextDecoration {
+        color: None,
+        line: None,
+        style: None,
+    };
+
+    while !input.is_exhausted() {
+        if result.color.is_none() {
+            if let Ok(color) = input.try_parse(parse_color) {
+                result.color = Some(color);
+                continue;
+            }
+        }
+
+        if result.line.is_none() {
+            if let Ok(line) = input.try_parse(parse_text_decoration_line) {
+                result.line = Some(line);
+                continue;
+            }
+        }
+
+        if result.style.is_none() {
+            if let Ok(style) = input.try_parse(parse_text_decoration_style) {
+                result.style = Some(style);
+                continue;
+            }
+        }
+
+        return Err(input.new_error_for_next_token());
+    }
+
+    Ok(result)
+}
+
+pub(crate) fn parse_text_decoration_line<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssTextDecorationLine, ParseError<'i>> {
+    let location = input.current_source_location();
+    let keyword = input.expect_ident()?;
+    CssTextDecorationLine::iter()
+        .find(|style| {
+            style.as_ref().eq_ignore_ascii_case(&keyword)
+        })
+        .ok_or_else(|| ParseError {
+            kind: ParseErrorKind::Custom(
+                RetinaStyleParseError::UnknownKeyword(keyword.clone())
+            ),
+            location,
+        })
+}
+
+pub(crate) fn parse_text_decoration_style<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssTextDecorationStyle, ParseError<'i>> {
+    let location = input.current_source_location();
+    let keyword = input.expect_ident()?;
+    CssTextDecorationStyle::iter()
+        .find(|style| {
+            style.as_ref().eq_ignore_ascii_case(&keyword)
+        })
+        .ok_or_else(|| ParseError {
+            kind: ParseErrorKind::Custom(
+                RetinaStyleParseError::UnknownKeyword(keyword.clone())
+            ),
+            location,
+        })
+}
+
+/// Parse the `text-indent` property, which is a `<length-percentage>`.
+///
+/// TODO: the `hanging` and `each-line` keywords aren't supported yet.
+///
+/// # References
+/// * [CSS Text Module Level 3 § 8.1. First Line Indentation: the `text-indent` property](https://drafts.csswg.org/css-text/#text-indent-property)
+pub(crate) fn parse_text_indent<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssLength, ParseError<'i>> {
+    let location = input.current_source_location();
+    match parse_length(input)? {
+        CssLength::Auto => Err(location.new_custom_error(RetinaStyleParseError::TextIndentUnexpectedAuto)),
+        length => Ok(length),
+    }
+}
+
+pub(crate) fn parse_text_overflow<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssTextOverflow, ParseError<'i>> {
+    let location = input.current_source_location();
+    let keyword = input.expect_ident()?;
+    CssTextOverflow::iter()
+        .find(|value| {
+            value.as_ref().eq_ignore_ascii_case(&keyword)
+        })
+        .ok_or_else(|| ParseError {
+            kind: ParseErrorKind::Custom(
+                RetinaStyleParseError::UnknownKeyword(keyword.clone())
+            ),
+            location,
+        })
+}
+
+pub(crate) fn parse_text_transform<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssTextTransform, ParseError<'i>> {
+    let location = input.current_source_location();
+    let keyword = input.expect_ident()?;
+    CssTextTransform::iter()
+        .find(|style| {
+            style.as_ref().eq_ignore_ascii_case(&keyword)
+        })
+        .ok_or_else(|| ParseError {
+            kind: ParseErrorKind::Custom(
+                RetinaStyleParseError::UnknownKeyword(keyword.clone())
+            ),
+            location,
+        })
+}
+
+/// Parses a [`<time>`][spec] into a [`Duration`](std::time::Duration).
+/// Negative times aren't representable, and are therefore rejected.
+///
+/// [spec]: https://drafts.csswg.org/css-values/#time
+/// Parses the value of the [`transform`][spec] property.
+///
+/// [spec]: https://drafts.csswg.org/css-transforms/#transform-property
+pub(crate) fn parse_transform<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssTransform, ParseError<'i>> {
+    if input.try_parse(|input| input.expect_ident_matching("none")).is_ok() {
+        return Ok(CssTransform::None);
+    }
+
+    let mut functions = vec![parse_transform_function(input)?];
+    while !input.is_exhausted() {
+        functions.push(parse_transform_function(input)?);
+    }
+
+    Ok(CssTransform::List(functions))
+}
+
+/// Parses a [`<transform-function>`][spec].
+///
+/// [spec]: https://drafts.csswg.org/css-transforms/#transform-functions
+fn parse_transform_function<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssTransformFunction, ParseError<'i>> {
+    let location = input.current_source_location();
+    let name = input.expect_function()?.clone();
+
+    input.parse_nested_block(|input| {
+        match name.to_ascii_lowercase().as_str() {
+            "matrix" => Ok(CssTransformFunction::Matrix(parse_transform_numbers(input)?)),
+            "matrix3d" => Ok(CssTransformFunction::Matrix3d(parse_transform_numbers(input)?)),
+
+            "perspective" => {
+                if input.try_parse(|input| input.expect_ident_matching("none")).is_ok() {
+                    Ok(CssTransformFunction::Perspective(None))
+                } else {
+                    Ok(CssTransformFunction::Perspective(Some(parse_length(input)?)))
+                }
+            }
+
+            "rotate" | "rotatez" => Ok(CssTransformFunction::Rotate(parse_angle(input)?)),
+
+            "scale" => {
+                let x = input.expect_number()? as CssDecimal;
+                let y = match input.try_parse(|input| input.expect_comma()) {
+                    Ok(()) => input.expect_number()? as CssDecimal,
+                    Err(..) => x,
+                };
+                Ok(CssTransformFunction::Scale(x, y))
+            }
+            "scalex" => Ok(CssTransformFunction::Scale(input.expect_number()? as CssDecimal, 1.0)),
+            "scaley" => Ok(CssTransformFunction::Scale(1.0, input.expect_number()? as CssDecimal)),
+
+            "skew" => {
+                let x = parse_angle(input)?;
+                let y = match input.try_parse(|input| input.expect_comma()) {
+                    Ok(()) => parse_angle(input)?,
+                    Err(..) => CssAngle::default(),
+                };
+                Ok(CssTransformFunction::Skew(x, y))
+            }
+            "skewx" => Ok(CssTransformFunction::Skew(parse_angle(input)?, CssAngle::default())),
+            "skewy" => Ok(CssTransformFunction::Skew(CssAngle::default(), parse_angle(input)?)),
+
+            "translate" => {
+                let x = parse_length(input)?;
+                let y = match input.try_parse(|input| input.expect_comma()) {
+                    Ok(()) => parse_length(input)?,
+                    Err(..) => CssLength::Pixels(0.0),
+                };
+                Ok(CssTransformFunction::Translate(x, y))
+            }
+            "translatex" => Ok(CssTransformFunction::Translate(parse_length(input)?, CssLength::Pixels(0.0))),
+            "translatey" => Ok(CssTransformFunction::Translate(CssLength::Pixels(0.0), parse_length(input)?)),
+
+            _ => Err(location.new_custom_error(RetinaStyleParseError::TransformUnknownFunction(name.clone()))),
+        }
+    })
+}
+
+/// Parses the comma-separated numbers of `matrix()` and `matrix3d()`.
+fn parse_transform_numbers<'i, 't, const N: usize>(
+    input: &mut Parser<'i, 't>
+) -> Result<[CssDecimal; N], ParseError<'i>> {
+    let mut values = [0.0; N];
+    for (index, value) in values.iter_mut().enumerate() {
+        if index != 0 {
+            input.expect_comma()?;
+        }
+        *value = input.expect_number()? as CssDecimal;
+    }
+    Ok(values)
+}
+
+pub(crate) fn parse_time<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<std::time::Duration, ParseError<'i>> {
+    let location = input.current_source_location();
+    let (value, unit) = match input.next()?.clone() {
+        Token::Dimension { value, unit, .. } if value >= 0.0 => (value, unit),
+        token => return Err(location.new_unexpected_token_error(token)),
+    };
+
+    let seconds = match unit.to_ascii_lowercase().as_str() {
+        "s" => value as f64,
+        "ms" => value as f64 / 1000.0,
+        _ => return Err(location.new_custom_error(RetinaStyleParseError::TimeUnknownUnit(unit))),
+    };
+
+    // Round to microseconds, to account for the imprecision of the `f32`.
+    Ok(std::time::Duration::from_micros((seconds * 1_000_000.0).round() as u64))
+}
+
+/// Parses an [`<easing-function>`][spec].
+///
+/// [spec]: https://drafts.csswg.org/css-easing/#typedef-easing-function
+pub(crate) fn parse_easing_function<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssEasingFunction, ParseError<'i>> {
+    let location = input.current_source_location();
+    let token = input.next()?.clone();
+    match token {
+        Token::Ident(ident) => match ident.to_ascii_lowercase().as_str() {
+            "linear" => Ok(CssEasingFunction::Linear),
+            "ease" => Ok(CssEasingFunction::Ease),
+            "ease-in" => Ok(CssEasingFunction::EaseIn),
+            "ease-out" => Ok(CssEasingFunction::EaseOut),
+            "ease-in-out" => Ok(CssEasingFunction::EaseInOut),
+            _ => Err(location.new_custom_error(RetinaStyleParseError::EasingFunctionUnknownKeyword(ident))),
+        },
+
+        Token::Function(name) if name.eq_ignore_ascii_case("cubic-bezier") => {
+            input.parse_nested_block(|input| {
+                let x1 = parse_cubic_bezier_x(input)?;
+                input.expect_comma()?;
+                let y1 = input.expect_number()? as CssDecimal;
+                input.expect_comma()?;
+                let x2 = parse_cubic_bezier_x(input)?;
+                input.expect_comma()?;
+                let y2 = input.expect_number()? as CssDecimal;
+                Ok(CssEasingFunction::CubicBezier(x1, y1, x2, y2))
+            })
+        }
+
+        Token::Function(name) if name.eq_ignore_ascii_case("steps") => {
+            input.parse_nested_block(|input| {
+                let location = input.current_source_location();
+                let count = input.expect_integer()?;
+                if count < 1 {
+                    return Err(location.new_unexpected_token_error(Token::Number {
+                        has_sign: count < 0,
+                        value: count as f32,
+                        int_value: Some(count),
+                    }));
+                }
+
+                let position = if input.try_parse(|input| input.expect_comma()).is_ok() {
+                    util::parse_enum(input)?
+                } else {
+                    CssStepPosition::default()
+                };
+
+                Ok(CssEasingFunction::Steps(count as u32, position))
+            })
+        }
+
+        Token::Function(name) => Err(location.new_custom_error(RetinaStyleParseError::EasingFunctionUnknownFunction(name))),
+
+        token => Err(location.new_unexpected_token_error(token)),
+    }
+}
+
+/// The x-coordinates of the control points must be in the range [0, 1].
+fn parse_cubic_bezier_x<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssDecimal, ParseError<'i>> {
+    let location = input.current_source_location();
+    let value = input.expect_number()?;
+    if !(0.0..=1.0).contains(&value) {
+        return Err(location.new_unexpected_token_error(Token::Number {
+            has_sign: value < 0.0,
+            value,
+            int_value: None,
+        }));
+    }
+
+    Ok(value as CssDecimal)
+}
+
+/// Parses the [`transition`][spec] shorthand, which is a comma-separated list
+/// of `<single-transition>`s.
+///
+/// [spec]: https://drafts.csswg.org/css-transitions/#transition-shorthand-property
+pub(crate) fn parse_transition<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<Vec<CssTransition>, ParseError<'i>> {
+    input.parse_comma_separated(parse_single_transition)
+}
+
+/// Parses a `<single-transition>`, of which the components can be specified
+/// in any order. The first `<time>` is the duration, the second the delay.
+fn parse_single_transition<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssTransition, ParseError<'i>> {
+    let mut property = None;
+    let mut duration = None;
+    let mut timing_function = None;
+    let mut delay = None;
+
+    while !input.is_exhausted() {
+        if let Ok(time) = input.try_parse(parse_time) {
+            if duration.is_none() {
+                duration = Some(time);
+                continue;
+            }
+
+            if delay.is_none() {
+                delay = Some(time);
+                continue;
+            }
+
+            return Err(input.new_error_for_next_token());
+        }
+
+        if timing_function.is_none() {
+            if let Ok(function) = input.try_parse(parse_easing_function) {
+                timing_function = Some(function);
+                continue;
+            }
+        }
+
+        if property.is_some() {
+            return Err(input.new_error_for_next_token());
+        }
+
+        let location = input.current_source_location();
+        let ident = input.expect_ident_cloned()?;
+        property = Some(match ident.to_ascii_lowercase().as_str() {
+            "all" => CssTransitionProperty::All,
+            "none" => CssTransitionProperty::None,
+            name => match Property::parse(name) {
+                Some(property) => CssTransitionProperty::Property(property),
+                None => return Err(location.new_custom_error(RetinaStyleParseError::TransitionUnknownProperty(ident))),
+            },
+        });
+    }
+
+    let default = CssTransition::default();
+    Ok(CssTransition {
+        property: property.unwrap_or(default.property),
+        duration: duration.unwrap_or(default.duration),
+        timing_function: timing_function.unwrap_or(default.timing_function),
+        delay: delay.unwrap_or(default.delay),
+    })
+}
+
+/// Parses the [`animation`][spec] shorthand, which is a comma-separated list
+/// of `<single-animation>`s.
+///
+/// [spec]: https://drafts.csswg.org/css-animations/#animation
+pub(crate) fn parse_animation<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<Vec<CssAnimation>, ParseError<'i>> {
+    input.parse_comma_separated(parse_single_animation)
+}
+
+/// Parses a `<single-animation>`, of which the components can be specified in
+/// any order. Keywords are matched against the other longhands first, such
+/// that e.g. `none` is the `animation-fill-mode` and not the name.
+fn parse_single_animation<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssAnimation, ParseError<'i>> {
+    let mut animation = CssAnimation::default();
+
+    let mut has_name = false;
+    let mut has_duration = false;
+    let mut has_timing_function = false;
+    let mut has_delay = false;
+    let mut has_iteration_count = false;
+    let mut has_direction = false;
+    let mut has_fill_mode = false;
+    let mut has_play_state = false;
+
+    while !input.is_exhausted() {
+        if let Ok(time) = input.try_parse(parse_time) {
+            if !has_duration {
+                animation.duration = time;
+                has_duration = true;
+                continue;
+            }
+
+            if !has_delay {
+                animation.delay = time;
+                has_delay = true;
+                continue;
+            }
+
+            return Err(input.new_error_for_next_token());
+        }
+
+        if !has_timing_function {
+            if let Ok(function) = input.try_parse(parse_easing_function) {
+                animation.timing_function = function;
+                has_timing_function = true;
+                continue;
+            }
+        }
+
+        if !has_iteration_count {
+            if let Ok(count) = input.try_parse(parse_animation_iteration_count) {
+                animation.iteration_count = count;
+                has_iteration_count = true;
+                continue;
+            }
+        }
+
+        if !has_direction {
+            if let Ok(direction) = input.try_parse(util::parse_enum) {
+                animation.direction = direction;
+                has_direction = true;
+                continue;
+            }
+        }
+
+        if !has_fill_mode {
+            if let Ok(fill_mode) = input.try_parse(util::parse_enum) {
+                animation.fill_mode = fill_mode;
+                has_fill_mode = true;
+                continue;
+            }
+        }
+
+        if !has_play_state {
+            if let Ok(play_state) = input.try_parse(util::parse_enum) {
+                animation.play_state = play_state;
+                has_play_state = true;
+                continue;
+            }
+        }
+
+        if has_name {
+            return Err(input.new_error_for_next_token());
+        }
+
+        let location = input.current_source_location();
+        animation.name = match input.next()?.clone() {
+            Token::Ident(ident) if ident.eq_ignore_ascii_case("none") => None,
+            Token::Ident(ident) | Token::QuotedString(ident) => Some(StrTendril::from(ident.as_ref())),
+            token => return Err(location.new_unexpected_token_error(token)),
+        };
+        has_name = true;
+    }
+
+    Ok(animation)
+}
+
+/// Parses an [`<single-animation-iteration-count>`][spec].
+///
+/// [spec]: https://drafts.csswg.org/css-animations/#typedef-single-animation-iteration-count
+fn parse_animation_iteration_count<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssAnimationIterationCount, ParseError<'i>> {
+    if input.try_parse(|input| input.expect_ident_matching("infinite")).is_ok() {
+        return Ok(CssAnimationIterationCount::Infinite);
+    }
+
+    let location = input.current_source_location();
+    let count = input.expect_number()?;
+    if count < 0.0 {
+        return Err(location.new_unexpected_token_error(Token::Number {
+            has_sign: true,
+            value: count,
+            int_value: None,
+        }));
+    }
+
+    Ok(CssAnimationIterationCount::Count(count as CssDecimal))
+}
+
+fn parse_specific_value<'i, 't>(
+    input: &mut Parser<'i, 't>,
+    property: Property,
+) -> Option<Result<Value, ParseError<'i>>> {
+    match property {
+        Property::AlignContent => Some(util::parse_enum(input).map(|value| Value::AlignContent(value))),
+        Property::AlignItems => Some(util::parse_enum(input).map(|value| Value::AlignItems(value))),
+        Property::AlignSelf => Some(util::parse_enum(input).map(|value| Value::AlignSelf(value))),
+        Property::Animation => Some(parse_animation(input).map(|value| Value::Animation(value))),
+        Property::AspectRatio => Some(parse_aspect_ratio(input).map(|value| Value::AspectRatio(value))),
+        Property::BackgroundRepeat => Some(parse_background_repeat(input).map(|value| Value::BackgroundRepeat(value))),
+        Property::BorderCollapse => Some(util::parse_enum(input).map(|value| Value::BorderCollapse(value))),
+        Property::BorderSpacing => Some(parse_border_spacing(input).map(|value| Value::BorderSpacing(value))),
+        Property::BoxShadow => Some(parse_box_shadow(input).map(|value| Value::BoxShadow(value))),
+        Property::CaptionSide => Some(util::parse_enum(input).map(|value| Value::CaptionSide(value))),
+        Property::Clear => Some(util::parse_enum(input).map(|value| Value::Clear(value))),
+        Property::Color => Some(parse_color(input).map(|value| Value::Color(value))),
+        Property::ColumnSpan => Some(util::parse_enum(input).map(|value| Value::ColumnSpan(value))),
+        Property::Content => Some(parse_content(input).map(|value| Value::Content(value))),
+        Property::Cursor => Some(util::parse_enum(input).map(|value| Value::Cursor(value))),
+        Property::Direction => Some(util::parse_enum(input).map(|value| Value::Direction(value))),
+        Property::Display => Some(parse_display(input).map(|value| Value::Display(value))),
+        Property::FlexBasis => Some(parse_flex_basis(input).map(|value| Value::FlexBasis(value))),
+        Property::FlexDirection => Some(util::parse_enum(input).map(|value| Value::FlexDirection(value))),
+        Property::FlexGrow => Some(parse_flex_factor(input).map(|value| Value::Number(value))),
+        Property::FlexShrink => Some(parse_flex_factor(input).map(|value| Value::Number(value))),
+        Property::FlexWrap => Some(util::parse_enum(input).map(|value| Value::FlexWrap(value))),
+        Property::Float => Some(parse_float(input).map(|float| Value::Float(float))),
+        Property::Font => Some(parse_font_shorthand(input).map(|shorthand| Value::FontShorthand(shorthand))),
+        Property::FontFamily => Some(parse_font_families(input).map(|families| Value::FontFamily(families))),
+        Property::FontKerning => Some(parse_font_kerning(input).map(|kerning| Value::FontKerning(kerning))),
+        Property::FontSize => Some(parse_font_size(input)),
+        Property::FontStyle => Some(parse_font_style(input).map(|style| Value::FontStyle(style))),
+        Property::FontVariantCaps => Some(parse_font_variant_caps(input).map(|ligatures| Value::FontVariantCaps(ligatures))),
+        Property::FontVariantEastAsian => Some(parse_font_variant_east_asian(input).map(|value| Value::FontVariantEastAsian(value))),
+        Property::FontVariantLigatures => Some(parse_font_variant_ligatures(input).map(|ligatures| Value::FontVariantLigatures(ligatures))),
+        Property::FontVariantPosition => Some(parse_font_variant_position(input).map(|value| Value::FontVariantPosition(value))),
+        Property::FontWeight => Some(parse_font_weight(input).map(|value| Value::FontWeight(value))),
+        Property::GridColumn => Some(parse_grid_placement(input).map(|value| Value::GridPlacement(value))),
+        Property::GridRow => Some(parse_grid_placement(input).map(|value| Value::GridPlacement(value))),
+        Property::GridTemplateAreas => Some(parse_grid_template_areas(input).map(|value| Value::GridTemplateAreas(value))),
+        Property::GridTemplateColumns => Some(parse_grid_track_list(input).map(|value| Value::GridTrackList(value))),
+        Property::GridTemplateRows => Some(parse_grid_track_list(input).map(|value| Value::GridTrackList(value))),
+        Property::ImageRendering => Some(util::parse_enum(input).map(|value| Value::ImageRendering(value))),
+        Property::JustifyContent => Some(util::parse_enum(input).map(|value| Value::JustifyContent(value))),
+        Property::LetterSpacing | Property::WordSpacing => Some(parse_spacing(input).map(|value| Value::Length(value))),
+        Property::MaxHeight | Property::MaxWidth => Some(parse_size_constraint(input, "none").map(|value| Value::Length(value))),
+        Property::MinHeight | Property::MinWidth => Some(parse_size_constraint(input, "auto").map(|value| Value::Length(value))),
+        Property::Opacity => Some(parse_opacity(input).map(|value| Value::Opacity(value))),
+        Property::Order => Some(input.expect_integer().map(|value| Value::Integer(value)).map_err(Into::into)),
+        Property::Outline => Some(parse_outline(input).map(|value| Value::Outline(value))),
+        Property::OutlineStyle => Some(parse_outline_style(input).map(|value| Value::OutlineStyle(value))),
+        Property::Overflow => Some(parse_overflow_shorthand(input).map(|value| Value::OverflowShorthand(value))),
+        Property::OverflowWrap => Some(util::parse_enum(input).map(|value| Value::OverflowWrap(value))),
+        Property::OverflowX | Property::OverflowY => Some(util::parse_enum(input).map(|value| Value::Overflow(value))),
+        Property::Position => Some(util::parse_enum(input).map(|value| Value::Position(value))),
+        Property::TableLayout => Some(util::parse_enum(input).map(|value| Value::TableLayout(value))),
+        Property::TextDecoration => Some(parse_text_decoration(input).map(|value| Value::TextDecoration(value))),
+        Property::TextDecorationLine => Some(parse_text_decoration_line(input).map(|value| Value::TextDecorationLine(value))),
+        Property::TextDecorationStyle => Some(parse_text_decoration_style(input).map(|value| Value::TextDecorationStyle(value))),
+        Property::TextIndent => Some(parse_text_indent(input).map(|value| Value::Length(value))),
+        Property::TextOverflow => Some(parse_text_overflow(input).map(|value| Value::TextOverflow(value))),
+        Property::TextTransform => Some(parse_text_transform(input).map(|value| Value::TextTransform(value))),
+        Property::Transform => Some(parse_transform(input).map(|value| Value::Transform(value))),
+        Property::Transition => Some(parse_transition(input).map(|value| Value::Transition(value))),
+        Property::WordBreak => Some(util::parse_enum(input).map(|value| Value::WordBreak(value))),
+        Property::WritingMode => Some(util::parse_enum(input).map(|value| Value::WritingMode(value))),
+        Property::ZIndex => Some(parse_z_index(input).map(|value| Value::ZIndex(value))),
+
+        _ => None,
+    }
+}
+
+/// Parse the `opacity` property, which is an `<alpha-value>`: a `<number>` or
+/// a `<percentage>`. Values outside of `0..1` are valid, but clamped when
+/// computed.
+///
+/// # References
+/// * [CSS Color Module Level 4 § 13.2. Transparency: the `opacity` property](https://drafts.csswg.org/css-color/#transparency)
+pub(crate) fn parse_opacity<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssOpacity, ParseError<'i>> {
+    let location = input.current_source_location();
+    match input.next()? {
+        Token::Number { value, .. } => Ok(CssOpacity(*value as _)),
+        Token::Percentage { unit_value, .. } => Ok(CssOpacity(*unit_value as _)),
+        token => Err(location.new_unexpected_token_error(token.clone())),
+    }
+}
+
+/// Parse the `overflow` shorthand, of which the first value is used for
+/// `overflow-x` and the second for `overflow-y`. If the second value is
+/// omitted, the first value is used for both.
+///
+/// [spec]: https://drafts.csswg.org/css-overflow/#propdef-overflow
+pub(crate) fn parse_overflow_shorthand<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssOverflowShorthand, ParseError<'i>> {
+    let x: CssOverflow = util::parse_enum(input)?;
+    let y = input.try_parse(util::parse_enum).unwrap_or(x);
+    Ok(CssOverflowShorthand { x, y })
+}
+
+pub(crate) fn parse_value<'i, 't>(input: &mut Parser<'i, 't>, property: Property) -> Result<Value, ParseError<'i>> {
+    if property == Property::Invalid {
+        return Err(input.new_error_for_next_token());
+    }
+
+    if let Some(result) = parse_wide_keywords(input) {
+        return result;
+    }
+
+    if let Some(result) = parse_specific_value(input, property) {
+        return result;
+    }
+
+    let value = parse_single_value(input)?;
+    if input.is_exhausted() {
+        return Ok(value);
+    }
+
+    let mut values = Vec::with_capacity(4);
+    values.push(value);
+
+    while !input.is_exhausted() {
+        values.push(parse_single_value(input)?);
+    }
+
+    assert_ne!(values.len(), 1);
+
+    match &values[..] {
+        //
+        // Border
+        //
+        &[Value::Length(length), Value::LineStyle(style)] => Ok(Value::BorderLonghand(
+            CssBorderLonghand {
+                width: Some(length),
+                style: Some(style),
+                color: None,
+            }
+        )),
+
+        &[Value::Length(length), Value::LineStyle(style), Value::Color(color)] => Ok(Value::BorderLonghand(
+            CssBorderLonghand {
+                width: Some(length),
+                style: Some(style),
+                color: Some(color),
+            }
+        )),
+
+        &[Value::LineStyle(style), Value::Color(color)] => Ok(Value::BorderLonghand(
+            CssBorderLonghand {
+                width: None,
+                style: Some(style),
+                color: Some(color),
+            }
+        )),
+
+        //
+        // Colors
+        //
+        &[Value::Color(a), Value::Color(b)] => Ok(Value::ComponentList(
+            ValueComponentList::TwoColors([a, b]),
+        )),
+
+        &[Value::Color(a), Value::Color(b), Value::Color(c)] => Ok(Value::ComponentList(
+            ValueComponentList::ThreeColors([a, b, c]),
+        )),
+
+        &[Value::Color(a), Value::Color(b), Value::Color(c), Value::Color(d)] => Ok(Value::ComponentList(
+            ValueComponentList::FourColors([a, b, c, d]),
+        )),
+
+        //
+        // Lengths
+        //
+        &[Value::Length(a), Value::Length(b)] => Ok(Value::ComponentList(
+            ValueComponentList::TwoLengths([a, b]),
+        )),
+
+        &[Value::Length(a), Value::Length(b), Value::Length(c)] => Ok(Value::ComponentList(
+            ValueComponentList::ThreeLengths([a, b, c]),
+        )),
+
+        &[Value::Length(a), Value::Length(b), Value::Length(c), Value::Length(d)] => Ok(Value::ComponentList(
+            ValueComponentList::FourLengths([a, b, c, d]),
+        )),
+
+        _ => Err(input.new_custom_error(RetinaStyleParseError::ComponentListUnknownKinds(values))),
+    }
+}
+
+fn parse_wide_keywords<'i, 't>(input: &mut Parser<'i, 't>) -> Option<Result<Value, ParseError<'i>>> {
+    let reset_point = input.state();
+
+    let Ok(ident) = input.expect_ident_cloned() else {
+        input.reset(&reset_point);
+        return None;
+    };
+
+    if ident.eq_ignore_ascii_case("inherit") {
+        return parse_wide_keywords_inner(input, &ident, reset_point);
+    }
+
+    if ident.eq_ignore_ascii_case("initial") {
+        return parse_wide_keywords_inner(input, &ident, reset_point);
+    }
+
+    if ident.eq_ignore_ascii_case("reset") {
+        return parse_wide_keywords_inner(input, &ident, reset_point);
+    }
+
+    input.reset(&reset_point);
+    None
+}
+
+fn parse_wide_keywords_inner<'i, 't>(
+    input: &mut Parser<'i, 't>,
+    ident: &str,
+    reset_point: cssparser::ParserState
+) -> Option<Result<Value, ParseError<'i>>> {
+    if let Err(e) = input.expect_exhausted() {
+        return Some(Err(e.into()));
+    }
+
+    _ = ident;
+
+    let after_token_state = input.state();
+
+    input.reset(&reset_point);
+    let todo_error = input.new_custom_error(
+        RetinaStyleParseError::WideKeywordsNotYetSupported
+    );
+
+    input.reset(&after_token_state);
+    Some(Err(todo_error))
+}
+
+pub(crate) fn parse_white_space<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssWhiteSpace, ParseError<'i>> {
+    let token = input.next()
+        .cloned()
+        .map_err(|_| input.new_custom_error(RetinaStyleParseError::UnexpectedEofBasicColorKeyword))?;
+
+    let Token::Ident(ident) = token else {
+        return Err(input.new_custom_error(RetinaStyleParseError::ExpectedIdentifierAsPropertyValue));
+    };
+
+    CssWhiteSpace::iter()
+        .find(|keyword| keyword.as_ref().eq_ignore_ascii_case(ident.as_ref()))
+        .ok_or_else(|| input.new_custom_error(RetinaStyleParseError::UnknownWhiteSpaceKeyword))
+}
+
+pub(crate) fn parse_z_index<'i, 't>(
+    input: &mut Parser<'i, 't>
+) -> Result<CssZIndex, ParseError<'i>> {
+    if input.try_parse(|input| input.expect_ident_matching("auto")).is_ok() {
+        return Ok(CssZIndex::Auto);
+    }
+
+    Ok(CssZIndex::Integer(input.expect_integer()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use retina_style::{CssColor, CssNamedColor};
+
+    use super::*;
+
+    #[rstest]
+    #[case("repeat", CssBackgroundRepeat {
+        horizontal: CssBackgroundRepeatStyle::Repeat,
+        vertical: CssBackgroundRepeatStyle::Repeat,
+    })]
+    fn value_background_repeat(#[case] input: &str, #[case] background_repeat: CssBackgroundRepeat) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        let result = parse_value(input, Property::BackgroundRepeat);
+        let expected = Ok(Value::BackgroundRepeat(background_repeat));
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("red", CssNamedColor::RED)]
+    #[case("RED", CssNamedColor::RED)]
+    #[case("rEd", CssNamedColor::RED)]
+    #[case("Red", CssNamedColor::RED)]
+    #[case("green", CssNamedColor::GREEN)]
+    #[case("greeN", CssNamedColor::GREEN)]
+    #[case("blue", CssNamedColor::BLUE)]
+    fn value_color(#[case] input: &str, #[case] color: CssColor) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        let result = parse_value(input, Property::Color);
+        let expected = Ok(color.into());
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("color-mix(in srgb, red, blue)", retina_common::Color::rgb(0.5, 0.0, 0.5))]
+    #[case("color-mix(in srgb, red 25%, blue)", retina_common::Color::rgb(0.25, 0.0, 0.75))]
+    #[case("color-mix(in srgb, 25% red, blue)", retina_common::Color::rgb(0.25, 0.0, 0.75))]
+    #[case("color-mix(in srgb, red, blue 75%)", retina_common::Color::rgb(0.25, 0.0, 0.75))]
+    #[case("color-mix(in srgb, red 25%, blue 25%)", retina_common::Color::rgba(0.5, 0.0, 0.5, 0.5))]
+    #[case("color-mix(in srgb, transparent, red)", retina_common::Color::rgba(1.0, 0.0, 0.0, 0.5))]
+    #[case("color-mix(in srgb-linear, black, transparent)", retina_common::Color::rgba(0.0, 0.0, 0.0, 0.5))]
+    fn value_color_mix(#[case] input: &str, #[case] color: retina_common::Color) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        let result = parse_value(input, Property::Color);
+        let expected = Ok(CssColor::Color(color).into());
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("lab(0 0 0)", retina_common::Color::BLACK)]
+    #[case("lab(100 0 0)", retina_common::Color::WHITE)]
+    #[case("lab(54.29 80.8 69.89)", retina_common::Color::RED)]
+    #[case("lch(54.29 106.84 40.85)", retina_common::Color::RED)]
+    #[case("oklab(1 0 0)", retina_common::Color::WHITE)]
+    #[case("oklab(0.628 0.2249 0.1258)", retina_common::Color::RED)]
+    #[case("oklch(62.8% 0.2577 29.23)", retina_common::Color::RED)]
+    #[case("oklch(62.8% 0.2577 29.23 / 50%)", retina_common::Color::RED.with_alpha(0.5))]
+    fn value_color_perceptual(#[case] input: &str, #[case] expected: retina_common::Color) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        let Ok(CssColor::Color(color)) = parse_color(input) else {
+            panic!("failed to parse color");
+        };
+
+        for (actual, expected) in [
+            (color.red(), expected.red()),
+            (color.green(), expected.green()),
+            (color.blue(), expected.blue()),
+            (color.alpha(), expected.alpha()),
+        ] {
+            assert!((actual - expected).abs() < 0.01, "{color:?} != {expected:?}");
+        }
+    }
+
+    #[rstest]
+    #[case("color-mix(in hsl, red, blue)")]
+    #[case("color-mix(in srgb, red 0%, blue 0%)")]
+    #[case("color-mix(in srgb, currentcolor, blue)")]
+    fn value_color_mix_invalid(#[case] input: &str) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        assert!(parse_color(input).is_err());
+    }
+
+    #[rstest]
+    #[case("none", CssDisplay::Box(CssDisplayBox::None))]
+    #[case("inline", CssDisplay::Normal { inside: CssDisplayInside::Flow, outside: CssDisplayOutside::Inline, is_list_item: false })]
+    #[case("block", CssDisplay::Normal { inside: CssDisplayInside::Flow, outside: CssDisplayOutside::Block, is_list_item: false })]
+    #[case("inline-block", CssDisplay::Normal { inside: CssDisplayInside::FlowRoot, outside: CssDisplayOutside::Inline, is_list_item: false })]
+    #[case("inline-flex", CssDisplay::Normal { inside: CssDisplayInside::Flex, outside: CssDisplayOutside::Inline, is_list_item: false })]
+    #[case("inline-grid", CssDisplay::Normal { inside: CssDisplayInside::Grid, outside: CssDisplayOutside::Inline, is_list_item: false })]
+    #[case("flow-root", CssDisplay::Normal { inside: CssDisplayInside::FlowRoot, outside: CssDisplayOutside::Block, is_list_item: false })]
+    #[case("flex", CssDisplay::Normal { inside: CssDisplayInside::Flex, outside: CssDisplayOutside::Block, is_list_item: false })]
+    #[case("grid", CssDisplay::Normal { inside: CssDisplayInside::Grid, outside: CssDisplayOutside::Block, is_list_item: false })]
+    #[case("table", CssDisplay::Normal { inside: CssDisplayInside::Table, outside: CssDisplayOutside::Block, is_list_item: false })]
+    #[case("inline-table", CssDisplay::Normal { inside: CssDisplayInside::Table, outside: CssDisplayOutside::Inline, is_list_item: false })]
+    #[case("table-row", CssDisplay::Internal(CssDisplayInternal::TableRow))]
+    #[case("table-cell", CssDisplay::Internal(CssDisplayInternal::TableCell))]
+    #[case("table-caption", CssDisplay::Internal(CssDisplayInternal::TableCaption))]
+    fn value_display(#[case] input: &str, #[case] display: CssDisplay) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        let result = parse_value(input, Property::Display);
+        let expected = Ok(Value::Display(display));
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("10px/1 Verdana, sans-serif", CssFontShorthand {
+        families: vec![CssFontFamilyName::Name("Verdana".into()), CssFontFamilyName::Generic(CssGenericFontFamilyName::SansSerif)],
+        style: None,
+        size: CssLength::Pixels(10.0),
+        line_height: Some(CssLength::FontSize(1.0)),
+        weight: None,
+    })]
+    fn value_font_shorthand(#[case] input: &str, #[case] shorthand: CssFontShorthand) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        let result = parse_value(input, Property::Font);
+        let expected = Ok(Value::FontShorthand(shorthand));
+        pretty_assertions::assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("/1", Some(CssLength::FontSize(1.0)))]
+    fn value_font_shorthand_line_height(#[case] input: &str, #[case] expected: Option<CssLength>) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        let result = parse_font_shorthand_line_height(input);
+        pretty_assertions::assert_eq!(result.as_ref().ok(), expected.as_ref(), "result was: {result:#?}");
+    }
+
+    #[rstest]
+    #[case("auto", CssLength::Auto)]
+    #[case("0", CssLength::Pixels(0.0))]
+    #[case("0px", CssLength::Pixels(0.0))]
+    #[case("616px", CssLength::Pixels(616.0))]
+    #[case("calc(10px + 2 * 3px)", CssLength::Pixels(16.0))]
+    #[case("calc((1em + 1em) / 4)", CssLength::FontSize(0.5))]
+    #[case("calc(100% - 2em)", CssLength::Calc(CssCalcLength { percentage: 1.0, font_size: -2.0, ..Default::default() }))]
+    #[case("calc(50% + calc(8px - 50%))", CssLength::Pixels(8.0))]
+    #[case("min(10px, 4px, 6px)", CssLength::Pixels(4.0))]
+    #[case("max(1rem, 2rem)", CssLength::FontSizeOfRootElement(2.0))]
+    #[case("clamp(10px, 40px, 30px)", CssLength::Pixels(30.0))]
+    #[case("clamp(10px, calc(2px * 3), 30px)", CssLength::Pixels(10.0))]
+    #[case("50vw", CssLength::UaDefaultViewportWidthPercentage(50.0))]
+    #[case("100vh", CssLength::UaDefaultViewportHeightPercentage(100.0))]
+    #[case("100dvh", CssLength::UaDefaultViewportHeightPercentage(100.0))]
+    #[case("100svh", CssLength::UaDefaultViewportHeightPercentage(100.0))]
+    #[case("100lvh", CssLength::UaDefaultViewportHeightPercentage(100.0))]
+    #[case("20vmin", CssLength::UaDefaultViewportMinPercentage(20.0))]
+    #[case("20vmax", CssLength::UaDefaultViewportMaxPercentage(20.0))]
+    #[case("calc(100vh - 2vmin)", CssLength::Calc(CssCalcLength { viewport_height: 100.0, viewport_min: -2.0, ..Default::default() }))]
+    fn value_length(#[case] input: &str, #[case] display: CssLength) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        let result = parse_value(input, Property::Width);
+        let expected = Ok(Value::Length(display));
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("calc(10px + 2)")]
+    #[case("calc(10px * 2px)")]
+    #[case("calc(10px / 0)")]
+    #[case("calc(2 * 3)")]
+    #[case("min(10px, 50%)")]
+    #[case("clamp(1px, 2px)")]
+    fn value_length_invalid_calc(#[case] input: &str) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        assert!(parse_length(input).is_err());
+    }
+
+    #[rstest]
+    #[case("none", CssTextDecoration {
+        color: None,
+        line: Some(CssTextDecorationLine::None),
+        style: None
+    })]
+    #[case("underline", CssTextDecoration {
+        color: None,
+        line: Some(CssTextDecorationLine::Underline),
+        style: None
+    })]
+    #[case("red", CssTextDecoration {
+        color: Some(CssColor::Color(retina_common::Color::RED)),
+        line: None,
+        style: None
+    })]
+    #[case("red underline", CssTextDecoration {
+        color: Some(CssColor::Color(retina_common::Color::RED)),
+        line: Some(CssTextDecorationLine::Underline),
+        style: None
+    })]
+    #[case("red underline wavy", CssTextDecoration {
+        color: Some(CssColor::Color(retina_common::Color::RED)),
+        line: Some(CssTextDecorationLine::Underline),
+        style: Some(CssTextDecorationStyle::Wavy)
+    })]
+    fn value_text_decoration(#[case] input_str: &str, #[case] style: CssTextDecoration) {
+        let mut input = cssparser::ParserInput::new(input_str);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        let result = parse_text_decoration(input);
+        let expected = Ok(style);
+        assert_eq!(result, expected);
+
+
+        let mut input = cssparser::ParserInput::new(input_str);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        let result = parse_value(input, Property::TextDecoration);
+        let expected = Ok(Value::TextDecoration(style));
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("none", CssTextDecorationLine::None)]
+    #[case("blink", CssTextDecorationLine::Blink)]
+    #[case("underline", CssTextDecorationLine::Underline)]
+    #[case("overline", CssTextDecorationLine::Overline)]
+    #[case("line-through", CssTextDecorationLine::LineThrough)]
+    fn value_text_decoration_line(#[case] input: &str, #[case] style: CssTextDecorationLine) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        let result = parse_value(input, Property::TextDecorationLine);
+        let expected = Ok(Value::TextDecorationLine(style));
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("solid", CssTextDecorationStyle::Solid)]
+    #[case("double", CssTextDecorationStyle::Double)]
+    #[case("dotted", CssTextDecorationStyle::Dotted)]
+    #[case("dashed", CssTextDecorationStyle::Dashed)]
+    #[case("wavy", CssTextDecorationStyle::Wavy)]
+    fn value_text_decoration_style(#[case] input: &str, #[case] style: CssTextDecorationStyle) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        let result = parse_value(input, Property::TextDecorationStyle);
+        let expected = Ok(Value::TextDecorationStyle(style));
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("normal", CssLength::Pixels(0.0))]
+    #[case("0", CssLength::Pixels(0.0))]
+    #[case("2px", CssLength::Pixels(2.0))]
+    #[case("-1px", CssLength::Pixels(-1.0))]
+    #[case("0.1em", CssLength::FontSize(0.1))]
+    fn value_spacing(#[case] input: &str, #[case] length: CssLength) {
+        for property in [Property::LetterSpacing, Property::WordSpacing] {
+            let mut input = cssparser::ParserInput::new(input);
+            let input = &mut cssparser::Parser::new(&mut input);
+
+            let result = parse_value(input, property);
+            match (result, length) {
+                (Ok(Value::Length(CssLength::FontSize(actual))), CssLength::FontSize(expected))
+                | (Ok(Value::Length(CssLength::Pixels(actual))), CssLength::Pixels(expected)) => {
+                    // The tokenizer parses numbers as single-precision floats.
+                    assert!((actual - expected).abs() < 1e-6, "property: {property:?}, {actual} != {expected}");
+                }
+                (result, _) => assert_eq!(result, Ok(Value::Length(length)), "property: {property:?}"),
+            }
+        }
+    }
+
+    #[rstest]
+    #[case("auto")]
+    #[case("wide")]
+    fn value_spacing_invalid(#[case] input: &str) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        let result = parse_value(input, Property::LetterSpacing);
+        assert!(result.is_err(), "unexpected: {result:?}");
+    }
+
+    #[rstest]
+    #[case("0", Ok(CssLength::Pixels(0.0)))]
+    #[case("3em", Ok(CssLength::FontSize(3.0)))]
+    #[case("-20px", Ok(CssLength::Pixels(-20.0)))]
+    #[case("50%", Ok(CssLength::Percentage(0.5)))]
+    #[case("auto", Err(()))]
+    #[case("none", Err(()))]
+    fn value_text_indent(#[case] input: &str, #[case] expected: Result<CssLength, ()>) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        let result = parse_value(input, Property::TextIndent)
+            .map_err(|_| ());
+        assert_eq!(result, expected.map(Value::Length));
+    }
+
+    #[rstest]
+    #[case("clip", CssTextOverflow::Clip)]
+    #[case("ellipsis", CssTextOverflow::Ellipsis)]
+    #[case("ELLIPSIS", CssTextOverflow::Ellipsis)]
+    fn value_text_overflow(#[case] input: &str, #[case] text_overflow: CssTextOverflow) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        let result = parse_value(input, Property::TextOverflow);
+        let expected = Ok(Value::TextOverflow(text_overflow));
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("normal", CssWhiteSpace::Normal)]
+    #[case("nowrap", CssWhiteSpace::Nowrap)]
+    #[case("pre", CssWhiteSpace::Pre)]
+    #[case("pre-wrap", CssWhiteSpace::PreWrap)]
+    #[case("pre-line", CssWhiteSpace::PreLine)]
+    #[case("break-spaces", CssWhiteSpace::BreakSpaces)]
+    fn value_white_space(#[case] input: &str, #[case] white_space: CssWhiteSpace) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        let result = parse_value(input, Property::WhiteSpace);
+        let expected = Ok(Value::WhiteSpace(white_space));
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("auto", CssImageRendering::Auto)]
+    #[case("smooth", CssImageRendering::Smooth)]
+    #[case("high-quality", CssImageRendering::HighQuality)]
+    #[case("pixelated", CssImageRendering::Pixelated)]
+    #[case("CRISP-EDGES", CssImageRendering::CrispEdges)]
+    fn value_image_rendering(#[case] input: &str, #[case] image_rendering: CssImageRendering) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        let result = parse_value(input, Property::ImageRendering);
+        let expected = Ok(Value::ImageRendering(image_rendering));
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("2px", Some((CssLength::Pixels(2.0), CssLength::Pixels(2.0))))]
+    #[case("1px 0.5em", Some((CssLength::Pixels(1.0), CssLength::FontSize(0.5))))]
+    #[case("0", Some((CssLength::Pixels(0.0), CssLength::Pixels(0.0))))]
+    #[case("-1px", None)]
+    #[case("10%", None)]
+    #[case("auto", None)]
+    fn value_border_spacing(#[case] input: &str, #[case] expected: Option<(CssLength, CssLength)>) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        let result = parse_value(input, Property::BorderSpacing).ok();
+        let expected = expected.map(|(horizontal, vertical)| Value::BorderSpacing(CssBorderSpacing { horizontal, vertical }));
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case(Property::BorderCollapse, "collapse", Value::BorderCollapse(CssBorderCollapse::Collapse))]
+    #[case(Property::BorderCollapse, "SEPARATE", Value::BorderCollapse(CssBorderCollapse::Separate))]
+    #[case(Property::CaptionSide, "bottom", Value::CaptionSide(CssCaptionSide::Bottom))]
+    #[case(Property::TableLayout, "fixed", Value::TableLayout(CssTableLayout::Fixed))]
+    fn value_table_keywords(#[case] property: Property, #[case] input: &str, #[case] expected: Value) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        assert_eq!(parse_value(input, property), Ok(expected));
+    }
+
+    fn stops(positions: [Option<CssLength>; 2]) -> Vec<CssColorStop> {
+        vec![
+            CssColorStop { color: CssNamedColor::RED, position: positions[0] },
+            CssColorStop { color: CssNamedColor::BLUE, position: positions[1] },
+        ]
+    }
+
+    #[rstest]
+    #[case("linear-gradient(red, blue)", CssLinearGradient {
+        direction: CssLinearGradientDirection::default(),
+        color_stops: stops([None, None]),
+    })]
+    #[case("linear-gradient(45deg, red 10%, blue 20px)", CssLinearGradient {
+        direction: CssLinearGradientDirection::Angle(CssAngle::Degrees(45.0)),
+        color_stops: stops([Some(CssLength::Percentage(0.1)), Some(CssLength::Pixels(20.0))]),
+    })]
+    #[case("linear-gradient(to right, red, blue)", CssLinearGradient {
+        direction: CssLinearGradientDirection::ToSideOrCorner {
+            horizontal: Some(CssHorizontalSide::Right),
+            vertical: None,
+        },
+        color_stops: stops([None, None]),
+    })]
+    #[case("linear-gradient(to top left, red, blue)", CssLinearGradient {
+        direction: CssLinearGradientDirection::ToSideOrCorner {
+            horizontal: Some(CssHorizontalSide::Left),
+            vertical: Some(CssVerticalSide::Top),
+        },
+        color_stops: stops([None, None]),
+    })]
+    #[case("linear-gradient(red 0% 50%, blue)", CssLinearGradient {
+        direction: CssLinearGradientDirection::default(),
+        color_stops: vec![
+            CssColorStop { color: CssNamedColor::RED, position: Some(CssLength::Percentage(0.0)) },
+            CssColorStop { color: CssNamedColor::RED, position: Some(CssLength::Percentage(0.5)) },
+            CssColorStop { color: CssNamedColor::BLUE, position: None },
+        ],
+    })]
+    fn value_linear_gradient(#[case] input: &str, #[case] gradient: CssLinearGradient) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        let Ok(Value::Image(CssImage::Gradient(CssGradient::Linear(result)))) = parse_value(input, Property::BackgroundImage) else {
+            panic!("failed to parse linear gradient");
+        };
+
+        assert_eq!(result.direction, gradient.direction);
+        assert_color_stops_eq(&result.color_stops, &gradient.color_stops);
+    }
+
+    /// Compare the color stops, allowing for the precision lost by the
+    /// single-precision numbers of the tokenizer.
+    fn assert_color_stops_eq(actual: &[CssColorStop], expected: &[CssColorStop]) {
+        assert_eq!(actual.len(), expected.len(), "{actual:?} != {expected:?}");
+
+        for (actual_stop, expected_stop) in actual.iter().zip(expected) {
+            assert_eq!(actual_stop.color, expected_stop.color);
+            match (actual_stop.position, expected_stop.position) {
+                (Some(CssLength::Percentage(actual)), Some(CssLength::Percentage(expected)))
+                | (Some(CssLength::Pixels(actual)), Some(CssLength::Pixels(expected))) => {
+                    assert!((actual - expected).abs() < 1e-6, "{actual_stop:?} != {expected_stop:?}");
+                }
+                (actual, expected) => assert_eq!(actual, expected),
+            }
+        }
+    }
+
+    #[rstest]
+    #[case("radial-gradient(red, blue)", CssRadialGradient {
+        shape: CssRadialGradientShape::Ellipse,
+        center: (CssLength::Percentage(0.5), CssLength::Percentage(0.5)),
+        color_stops: stops([None, None]),
+    })]
+    #[case("radial-gradient(circle, red, blue)", CssRadialGradient {
+        shape: CssRadialGradientShape::Circle,
+        center: (CssLength::Percentage(0.5), CssLength::Percentage(0.5)),
+        color_stops: stops([None, None]),
+    })]
+    #[case("radial-gradient(ellipse at top right, red, blue)", CssRadialGradient {
+        shape: CssRadialGradientShape::Ellipse,
+        center: (CssLength::Percentage(1.0), CssLength::Percentage(0.0)),
+        color_stops: stops([None, None]),
+    })]
+    #[case("radial-gradient(at 10px bottom, red, blue 50%)", CssRadialGradient {
+        shape: CssRadialGradientShape::Ellipse,
+        center: (CssLength::Pixels(10.0), CssLength::Percentage(1.0)),
+        color_stops: stops([None, Some(CssLength::Percentage(0.5))]),
+    })]
+    fn value_radial_gradient(#[case] input: &str, #[case] gradient: CssRadialGradient) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        let result = parse_value(input, Property::BackgroundImage);
+        let expected = Ok(Value::Image(CssImage::Gradient(CssGradient::Radial(gradient))));
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("none", vec![])]
+    #[case("1px 2px", vec![CssBoxShadow {
+        offset_x: CssLength::Pixels(1.0),
+        offset_y: CssLength::Pixels(2.0),
+        ..Default::default()
+    }])]
+    #[case("red 1px 2px 3px 4px inset", vec![CssBoxShadow {
+        color: CssNamedColor::RED,
+        offset_x: CssLength::Pixels(1.0),
+        offset_y: CssLength::Pixels(2.0),
+        blur_radius: CssLength::Pixels(3.0),
+        spread_radius: CssLength::Pixels(4.0),
+        inset: true,
+    }])]
+    #[case("inset 0 0 1em blue, -1px -2px", vec![
+        CssBoxShadow {
+            color: CssNamedColor::BLUE,
+            blur_radius: CssLength::FontSize(1.0),
+            inset: true,
+            ..Default::default()
+        },
+        CssBoxShadow {
+            offset_x: CssLength::Pixels(-1.0),
+            offset_y: CssLength::Pixels(-2.0),
+            ..Default::default()
+        },
+    ])]
+    fn value_box_shadow(#[case] input: &str, #[case] box_shadow: Vec<CssBoxShadow>) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        let result = parse_value(input, Property::BoxShadow);
+        let expected = Ok(Value::BoxShadow(box_shadow));
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("1px")]
+    #[case("1px 2px -3px")]
+    #[case("10% 2px")]
+    #[case("red")]
+    #[case("1px 2px inset inset")]
+    fn value_box_shadow_invalid(#[case] input: &str) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        assert!(parse_value(input, Property::BoxShadow).is_err());
+    }
+
+    #[rstest]
+    #[case("linear-gradient(red)")]
+    #[case("linear-gradient(to, red, blue)")]
+    #[case("linear-gradient(to left right, red, blue)")]
+    #[case("linear-gradient(45deg red, blue)")]
+    #[case("radial-gradient(at left left, red, blue)")]
+    fn value_gradient_invalid(#[case] input: &str) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        assert!(parse_image(input).is_err());
+    }
+
+    #[rstest]
+    #[case(Property::FlexDirection, "row-reverse", Value::FlexDirection(CssFlexDirection::RowReverse))]
+    #[case(Property::FlexWrap, "wrap-reverse", Value::FlexWrap(CssFlexWrap::WrapReverse))]
+    #[case(Property::JustifyContent, "space-between", Value::JustifyContent(CssJustifyContent::SpaceBetween))]
+    #[case(Property::AlignItems, "flex-end", Value::AlignItems(CssAlignItems::FlexEnd))]
+    #[case(Property::AlignSelf, "auto", Value::AlignSelf(CssAlignSelf::Auto))]
+    #[case(Property::AlignContent, "space-evenly", Value::AlignContent(CssAlignContent::SpaceEvenly))]
+    #[case(Property::FlexBasis, "content", Value::FlexBasis(CssFlexBasis::Content))]
+    #[case(Property::FlexBasis, "25%", Value::FlexBasis(CssFlexBasis::Length(CssLength::Percentage(0.25))))]
+    #[case(Property::FlexGrow, "2", Value::Number(2.0))]
+    #[case(Property::FlexShrink, "0.5", Value::Number(0.5))]
+    #[case(Property::Order, "-1", Value::Integer(-1))]
+    fn value_flex(#[case] property: Property, #[case] input: &str, #[case] expected: Value) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        let result = parse_value(input, property);
+        assert_eq!(result, Ok(expected));
+    }
+
+    #[rstest]
+    #[case(Property::MaxWidth, "none", CssLength::Auto)]
+    #[case(Property::MaxWidth, "50%", CssLength::Percentage(0.5))]
+    #[case(Property::MaxHeight, "20em", CssLength::FontSize(20.0))]
+    #[case(Property::MaxHeight, "max-content", CssLength::MaxContent)]
+    #[case(Property::MinWidth, "auto", CssLength::Auto)]
+    #[case(Property::MinWidth, "min-content", CssLength::MinContent)]
+    #[case(Property::MinHeight, "0", CssLength::Pixels(0.0))]
+    #[case(Property::MinHeight, "100px", CssLength::Pixels(100.0))]
+    fn value_size_constraint(#[case] property: Property, #[case] input: &str, #[case] expected: CssLength) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        let result = parse_value(input, property);
+        assert_eq!(result, Ok(Value::Length(expected)));
+    }
+
+    #[rstest]
+    #[case(Property::MaxWidth, "auto")]
+    #[case(Property::MaxWidth, "-10px")]
+    #[case(Property::MinWidth, "none")]
+    #[case(Property::MinHeight, "-5%")]
+    fn value_size_constraint_invalid(#[case] property: Property, #[case] input: &str) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        assert!(parse_value(input, property).is_err());
+    }
+
+    #[rstest]
+    #[case("auto", CssOutline { style: Some(CssOutlineStyle::Auto), ..Default::default() })]
+    #[case("2px solid red", CssOutline {
+        width: Some(CssLength::Pixels(2.0)),
+        style: Some(CssOutlineStyle::LineStyle(CssLineStyle::Solid)),
+        color: Some(CssNamedColor::RED),
+    })]
+    #[case("currentcolor dashed", CssOutline {
+        width: None,
+        style: Some(CssOutlineStyle::LineStyle(CssLineStyle::Dashed)),
+        color: Some(CssColor::CurrentColor),
+    })]
+    #[case("auto 1px", CssOutline {
+        width: Some(CssLength::Pixels(1.0)),
+        style: Some(CssOutlineStyle::Auto),
+        color: None,
+    })]
+    fn value_outline(#[case] input: &str, #[case] expected: CssOutline) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        let result = parse_value(input, Property::Outline);
+        assert_eq!(result, Ok(Value::Outline(expected)));
+    }
+
+    #[rstest]
+    #[case("hidden")]
+    #[case("solid solid")]
+    #[case("1px 2px")]
+    fn value_outline_invalid(#[case] input: &str) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
 
-        //
-        // Colors
-        //
-        &[Value::Color(a), Value::Color(b)] => Ok(Value::ComponentList(
-            ValueComponentList::TwoColors([a, b]),
-        )),
+        assert!(parse_value(input, Property::Outline).is_err());
+    }
 
-        &[Value::Color(a), Value::Color(b), Value::Color(c)] => Ok(Value::ComponentList(
-            ValueComponentList::ThreeColors([a, b, c]),
-        )),
+    #[rstest]
+    #[case("static", CssPosition::Static)]
+    #[case("fixed", CssPosition::Fixed)]
+    #[case("STICKY", CssPosition::Sticky)]
+    fn value_position(#[case] input: &str, #[case] expected: CssPosition) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
 
-        &[Value::Color(a), Value::Color(b), Value::Color(c), Value::Color(d)] => Ok(Value::ComponentList(
-            ValueComponentList::FourColors([a, b, c, d]),
-        )),
+        let result = parse_value(input, Property::Position);
+        assert_eq!(result, Ok(Value::Position(expected)));
+    }
 
-        //
-        // Lengths
-        //
-        &[Value::Length(a), Value::Length(b)] => Ok(Value::ComponentList(
-            ValueComponentList::TwoLengths([a, b]),
-        )),
+    #[rstest]
+    #[case(Property::Overflow, "hidden", Value::OverflowShorthand(CssOverflowShorthand { x: CssOverflow::Hidden, y: CssOverflow::Hidden }))]
+    #[case(Property::Overflow, "auto scroll", Value::OverflowShorthand(CssOverflowShorthand { x: CssOverflow::Auto, y: CssOverflow::Scroll }))]
+    #[case(Property::OverflowX, "clip", Value::Overflow(CssOverflow::Clip))]
+    #[case(Property::OverflowY, "VISIBLE", Value::Overflow(CssOverflow::Visible))]
+    fn value_overflow(#[case] property: Property, #[case] input: &str, #[case] expected: Value) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
 
-        &[Value::Length(a), Value::Length(b), Value::Length(c)] => Ok(Value::ComponentList(
-            ValueComponentList::ThreeLengths([a, b, c]),
-        )),
+        let result = parse_value(input, property);
+        assert_eq!(result, Ok(expected));
+    }
 
-        &[Value::Length(a), Value::Length(b), Value::Length(c), Value::Length(d)] => Ok(Value::ComponentList(
-            ValueComponentList::FourLengths([a, b, c, d]),
-        )),
+    #[rstest]
+    #[case(Property::OverflowWrap, "normal", Value::OverflowWrap(CssOverflowWrap::Normal))]
+    #[case(Property::OverflowWrap, "break-word", Value::OverflowWrap(CssOverflowWrap::BreakWord))]
+    #[case(Property::OverflowWrap, "anywhere", Value::OverflowWrap(CssOverflowWrap::Anywhere))]
+    #[case(Property::WordBreak, "normal", Value::WordBreak(CssWordBreak::Normal))]
+    #[case(Property::WordBreak, "break-all", Value::WordBreak(CssWordBreak::BreakAll))]
+    #[case(Property::WordBreak, "keep-all", Value::WordBreak(CssWordBreak::KeepAll))]
+    #[case(Property::WordBreak, "Break-Word", Value::WordBreak(CssWordBreak::BreakWord))]
+    fn value_word_breaking(#[case] property: Property, #[case] input: &str, #[case] expected: Value) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
 
-        _ => Err(input.new_custom_error(RetinaStyleParseError::ComponentListUnknownKinds(values))),
+        let result = parse_value(input, property);
+        assert_eq!(result, Ok(expected));
     }
-}
 
-fn parse_wide_keywords<'i, 't>(input: &mut Parser<'i, 't>) -> Option<Result<Value, ParseError<'i>>> {
-    let reset_point = input.state();
+    #[rstest]
+    #[case("none", CssTransform::None)]
+    #[case("translate(10px)", CssTransform::List(vec![
+        CssTransformFunction::Translate(CssLength::Pixels(10.0), CssLength::Pixels(0.0)),
+    ]))]
+    #[case("translateY(50%) rotate(0.25turn)", CssTransform::List(vec![
+        CssTransformFunction::Translate(CssLength::Pixels(0.0), CssLength::Percentage(0.5)),
+        CssTransformFunction::Rotate(CssAngle::Turns(0.25)),
+    ]))]
+    #[case("scale(2) skewX(45deg)", CssTransform::List(vec![
+        CssTransformFunction::Scale(2.0, 2.0),
+        CssTransformFunction::Skew(CssAngle::Degrees(45.0), CssAngle::Degrees(0.0)),
+    ]))]
+    #[case("matrix(1, 0, 0, 1, 20, 30)", CssTransform::List(vec![
+        CssTransformFunction::Matrix([1.0, 0.0, 0.0, 1.0, 20.0, 30.0]),
+    ]))]
+    #[case("perspective(none) rotate(0)", CssTransform::List(vec![
+        CssTransformFunction::Perspective(None),
+        CssTransformFunction::Rotate(CssAngle::Degrees(0.0)),
+    ]))]
+    fn value_transform(#[case] input: &str, #[case] expected: CssTransform) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
 
-    let Ok(ident) = input.expect_ident_cloned() else {
-        input.reset(&reset_point);
-        return None;
-    };
+        let result = parse_value(input, Property::Transform);
+        assert_eq!(result, Ok(Value::Transform(expected)));
+    }
 
-    if ident.eq_ignore_ascii_case("inherit") {
-        return parse_wide_keywords_inner(input, &ident, reset_point);
+    #[rstest]
+    #[case("auto", CssZIndex::Auto)]
+    #[case("AUTO", CssZIndex::Auto)]
+    #[case("0", CssZIndex::Integer(0))]
+    #[case("10", CssZIndex::Integer(10))]
+    #[case("-1", CssZIndex::Integer(-1))]
+    fn value_z_index(#[case] input: &str, #[case] expected: CssZIndex) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        let result = parse_value(input, Property::ZIndex);
+        assert_eq!(result, Ok(Value::ZIndex(expected)));
     }
 
-    if ident.eq_ignore_ascii_case("initial") {
-        return parse_wide_keywords_inner(input, &ident, reset_point);
+    #[rstest]
+    #[case("normal", CssContent::Normal)]
+    #[case("none", CssContent::None)]
+    #[case("\"\"", CssContent::Items(vec![CssContentItem::String("".into())]))]
+    #[case("\"Chapter \" counter(chapter)", CssContent::Items(vec![
+        CssContentItem::String("Chapter ".into()),
+        CssContentItem::Counter("chapter".into()),
+    ]))]
+    #[case("counter(item, decimal) '. '", CssContent::Items(vec![
+        CssContentItem::Counter("item".into()),
+        CssContentItem::String(". ".into()),
+    ]))]
+    fn value_content(#[case] input: &str, #[case] expected: CssContent) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        let result = parse_value(input, Property::Content);
+        assert_eq!(result, Ok(Value::Content(expected)));
     }
 
-    if ident.eq_ignore_ascii_case("reset") {
-        return parse_wide_keywords_inner(input, &ident, reset_point);
+    #[rstest]
+    #[case("0", CssOpacity(0.0))]
+    #[case("0.5", CssOpacity(0.5))]
+    #[case("25%", CssOpacity(0.25))]
+    #[case("2", CssOpacity(2.0))]
+    #[case("-1", CssOpacity(-1.0))]
+    fn value_opacity(#[case] input: &str, #[case] expected: CssOpacity) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        let result = parse_value(input, Property::Opacity);
+        assert_eq!(result, Ok(Value::Opacity(expected)));
     }
 
-    input.reset(&reset_point);
-    None
-}
+    #[rstest]
+    #[case("auto")]
+    #[case("10px")]
+    fn value_opacity_invalid(#[case] input: &str) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
 
-fn parse_wide_keywords_inner<'i, 't>(
-    input: &mut Parser<'i, 't>,
-    ident: &str,
-    reset_point: cssparser::ParserState
-) -> Option<Result<Value, ParseError<'i>>> {
-    if let Err(e) = input.expect_exhausted() {
-        return Some(Err(e.into()));
+        assert!(parse_value(input, Property::Opacity).is_err());
     }
 
-    _ = ident;
+    #[rstest]
+    #[case("auto", CssAspectRatio::Auto)]
+    #[case("16 / 9", CssAspectRatio::Ratio(CssRatio { numerator: 16.0, denominator: 9.0 }))]
+    #[case("2", CssAspectRatio::Ratio(CssRatio { numerator: 2.0, denominator: 1.0 }))]
+    #[case("auto 4/3", CssAspectRatio::AutoOrRatio(CssRatio { numerator: 4.0, denominator: 3.0 }))]
+    #[case("0.5 auto", CssAspectRatio::AutoOrRatio(CssRatio { numerator: 0.5, denominator: 1.0 }))]
+    fn value_aspect_ratio(#[case] input: &str, #[case] expected: CssAspectRatio) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
 
-    let after_token_state = input.state();
+        let result = parse_value(input, Property::AspectRatio);
+        assert_eq!(result, Ok(Value::AspectRatio(expected)));
+    }
 
-    input.reset(&reset_point);
-    let todo_error = input.new_custom_error(
-        RetinaStyleParseError::WideKeywordsNotYetSupported
-    );
+    #[rstest]
+    #[case("none")]
+    #[case("-16 / 9")]
+    #[case("16 / -9")]
+    #[case("16px / 9px")]
+    fn value_aspect_ratio_invalid(#[case] input: &str) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
 
-    input.reset(&after_token_state);
-    Some(Err(todo_error))
-}
+        assert!(parse_value(input, Property::AspectRatio).is_err());
+    }
 
-pub(crate) fn parse_white_space<'i, 't>(
-    input: &mut Parser<'i, 't>
-) -> Result<CssWhiteSpace, ParseError<'i>> {
-    let token = input.next()
-        .cloned()
-        .map_err(|_| input.new_custom_error(RetinaStyleParseError::UnexpectedEofBasicColorKeyword))?;
+    #[rstest]
+    #[case(Property::FlexBasis, "-10px")]
+    #[case(Property::FlexGrow, "-1")]
+    #[case(Property::Order, "1.5")]
+    fn value_flex_invalid(#[case] property: Property, #[case] input: &str) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
 
-    let Token::Ident(ident) = token else {
-        return Err(input.new_custom_error(RetinaStyleParseError::ExpectedIdentifierAsPropertyValue));
-    };
+        assert!(parse_value(input, property).is_err());
+    }
 
-    CssWhiteSpace::iter()
-        .find(|keyword| keyword.as_ref().eq_ignore_ascii_case(ident.as_ref()))
-        .ok_or_else(|| input.new_custom_error(RetinaStyleParseError::UnknownWhiteSpaceKeyword))
-}
+    #[rstest]
+    #[case(Property::Color, "red", Value::Color(CssNamedColor::RED))]
+    #[case(Property::Color, "currentcolor", Value::Color(CssColor::CurrentColor))]
+    #[case(Property::Display, "grid", Value::Display(CssDisplay::Normal { inside: CssDisplayInside::Grid, outside: CssDisplayOutside::Block, is_list_item: false }))]
+    #[case(Property::Display, "none", Value::Display(CssDisplay::Box(CssDisplayBox::None)))]
+    fn value_specific(#[case] property: Property, #[case] input: &str, #[case] expected: Value) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
 
-#[cfg(test)]
-mod tests {
-    use rstest::rstest;
-    use retina_style::{CssColor, CssNamedColor};
+        let result = parse_specific_value(input, property);
+        assert_eq!(result, Some(Ok(expected)));
+    }
 
-    use super::*;
+    #[rstest]
+    #[case(Property::Color, "10px")]
+    #[case(Property::Display, "bogus")]
+    fn value_specific_invalid(#[case] property: Property, #[case] input: &str) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        assert!(matches!(parse_specific_value(input, property), Some(Err(_))));
+    }
 
     #[rstest]
-    #[case("repeat", CssBackgroundRepeat {
-        horizontal: CssBackgroundRepeatStyle::Repeat,
-        vertical: CssBackgroundRepeatStyle::Repeat,
-    })]
-    fn value_background_repeat(#[case] input: &str, #[case] background_repeat: CssBackgroundRepeat) {
+    #[case("color 0.3s ease-in-out", vec![CssTransition {
+        property: CssTransitionProperty::Property(Property::Color),
+        duration: std::time::Duration::from_millis(300),
+        timing_function: CssEasingFunction::EaseInOut,
+        delay: std::time::Duration::ZERO,
+    }])]
+    #[case("250ms", vec![CssTransition {
+        duration: std::time::Duration::from_millis(250),
+        ..Default::default()
+    }])]
+    #[case("1s linear 2s width", vec![CssTransition {
+        property: CssTransitionProperty::Property(Property::Width),
+        duration: std::time::Duration::from_secs(1),
+        timing_function: CssEasingFunction::Linear,
+        delay: std::time::Duration::from_secs(2),
+    }])]
+    #[case("none", vec![CssTransition {
+        property: CssTransitionProperty::None,
+        ..Default::default()
+    }])]
+    #[case("color 1s cubic-bezier(0.1, -0.5, 0.9, 1.5), height 2s steps(4, start)", vec![
+        CssTransition {
+            property: CssTransitionProperty::Property(Property::Color),
+            duration: std::time::Duration::from_secs(1),
+            timing_function: CssEasingFunction::CubicBezier(0.1f32 as _, -0.5, 0.9f32 as _, 1.5),
+            delay: std::time::Duration::ZERO,
+        },
+        CssTransition {
+            property: CssTransitionProperty::Property(Property::Height),
+            duration: std::time::Duration::from_secs(2),
+            timing_function: CssEasingFunction::Steps(4, CssStepPosition::Start),
+            delay: std::time::Duration::ZERO,
+        },
+    ])]
+    fn value_transition(#[case] input: &str, #[case] transition: Vec<CssTransition>) {
         let mut input = cssparser::ParserInput::new(input);
         let input = &mut cssparser::Parser::new(&mut input);
 
-        let result = parse_value(input, Property::BackgroundRepeat);
-        let expected = Ok(Value::BackgroundRepeat(background_repeat));
+        let result = parse_value(input, Property::Transition);
+        let expected = Ok(Value::Transition(transition));
         assert_eq!(result, expected);
     }
 
     #[rstest]
-    #[case("red", CssNamedColor::RED)]
-    #[case("RED", CssNamedColor::RED)]
-    #[case("rEd", CssNamedColor::RED)]
-    #[case("Red", CssNamedColor::RED)]
-    #[case("green", CssNamedColor::GREEN)]
-    #[case("greeN", CssNamedColor::GREEN)]
-    #[case("blue", CssNamedColor::BLUE)]
-    fn value_color(#[case] input: &str, #[case] color: CssColor) {
+    #[case("color -1s")]
+    #[case("color 1s 2s 3s")]
+    #[case("color width")]
+    #[case("unknown-property 1s")]
+    #[case("cubic-bezier(2, 0, 0, 1)")]
+    #[case("steps(0)")]
+    fn value_transition_invalid(#[case] input: &str) {
         let mut input = cssparser::ParserInput::new(input);
         let input = &mut cssparser::Parser::new(&mut input);
 
-        let result = parse_value(input, Property::Color);
-        let expected = Ok(color.into());
-        assert_eq!(result, expected);
+        assert!(parse_transition(input).is_err());
     }
 
     #[rstest]
-    #[case("none", CssDisplay::Box(CssDisplayBox::None))]
-    #[case("inline", CssDisplay::Normal { inside: CssDisplayInside::Flow, outside: CssDisplayOutside::Inline, is_list_item: false })]
-    #[case("block", CssDisplay::Normal { inside: CssDisplayInside::Flow, outside: CssDisplayOutside::Block, is_list_item: false })]
-    #[case("inline-block", CssDisplay::Normal { inside: CssDisplayInside::FlowRoot, outside: CssDisplayOutside::Inline, is_list_item: false })]
-    fn value_display(#[case] input: &str, #[case] display: CssDisplay) {
+    #[case("spin 2s linear infinite", vec![CssAnimation {
+        name: Some("spin".into()),
+        duration: std::time::Duration::from_secs(2),
+        timing_function: CssEasingFunction::Linear,
+        iteration_count: CssAnimationIterationCount::Infinite,
+        ..Default::default()
+    }])]
+    #[case("none", vec![CssAnimation::default()])]
+    #[case("1s 500ms 3 alternate-reverse both paused \"fade\"", vec![CssAnimation {
+        name: Some("fade".into()),
+        duration: std::time::Duration::from_secs(1),
+        delay: std::time::Duration::from_millis(500),
+        iteration_count: CssAnimationIterationCount::Count(3.0),
+        direction: CssAnimationDirection::AlternateReverse,
+        fill_mode: CssAnimationFillMode::Both,
+        play_state: CssAnimationPlayState::Paused,
+        ..Default::default()
+    }])]
+    #[case("forwards forwards 1s", vec![CssAnimation {
+        name: Some("forwards".into()),
+        duration: std::time::Duration::from_secs(1),
+        fill_mode: CssAnimationFillMode::Forwards,
+        ..Default::default()
+    }])]
+    #[case("a 1s, b 2s", vec![
+        CssAnimation {
+            name: Some("a".into()),
+            duration: std::time::Duration::from_secs(1),
+            ..Default::default()
+        },
+        CssAnimation {
+            name: Some("b".into()),
+            duration: std::time::Duration::from_secs(2),
+            ..Default::default()
+        },
+    ])]
+    fn value_animation(#[case] input: &str, #[case] animation: Vec<CssAnimation>) {
         let mut input = cssparser::ParserInput::new(input);
         let input = &mut cssparser::Parser::new(&mut input);
 
-        let result = parse_value(input, Property::Display);
-        let expected = Ok(Value::Display(display));
+        let result = parse_value(input, Property::Animation);
+        let expected = Ok(Value::Animation(animation));
         assert_eq!(result, expected);
     }
 
     #[rstest]
-    #[case("10px/1 Verdana, sans-serif", CssFontShorthand {
-        families: vec![CssFontFamilyName::Name("Verdana".into()), CssFontFamilyName::Generic(CssGenericFontFamilyName::SansSerif)],
-        style: None,
-        size: CssLength::Pixels(10.0),
-        line_height: Some(CssLength::FontSize(1.0)),
-        weight: None,
-    })]
-    fn value_font_shorthand(#[case] input: &str, #[case] shorthand: CssFontShorthand) {
+    #[case("none", CssClear::None)]
+    #[case("left", CssClear::Left)]
+    #[case("Both", CssClear::Both)]
+    #[case("inline-end", CssClear::InlineEnd)]
+    fn value_clear(#[case] input: &str, #[case] clear: CssClear) {
         let mut input = cssparser::ParserInput::new(input);
         let input = &mut cssparser::Parser::new(&mut input);
 
-        let result = parse_value(input, Property::Font);
-        let expected = Ok(Value::FontShorthand(shorthand));
-        pretty_assertions::assert_eq!(result, expected);
+        let result = parse_value(input, Property::Clear);
+        let expected = Ok(Value::Clear(clear));
+        assert_eq!(result, expected);
     }
 
     #[rstest]
-    #[case("/1", Some(CssLength::FontSize(1.0)))]
-    fn value_font_shorthand_line_height(#[case] input: &str, #[case] expected: Option<CssLength>) {
+    #[case("ltr", CssDirection::Ltr)]
+    #[case("rtl", CssDirection::Rtl)]
+    #[case("RTL", CssDirection::Rtl)]
+    fn value_direction(#[case] input: &str, #[case] direction: CssDirection) {
         let mut input = cssparser::ParserInput::new(input);
         let input = &mut cssparser::Parser::new(&mut input);
 
-        let result = parse_font_shorthand_line_height(input);
-        pretty_assertions::assert_eq!(result.as_ref().ok(), expected.as_ref(), "result was: {result:#?}");
+        let result = parse_value(input, Property::Direction);
+        let expected = Ok(Value::Direction(direction));
+        assert_eq!(result, expected);
     }
 
     #[rstest]
-    #[case("auto", CssLength::Auto)]
-    #[case("0", CssLength::Pixels(0.0))]
-    #[case("0px", CssLength::Pixels(0.0))]
-    #[case("616px", CssLength::Pixels(616.0))]
-    fn value_length(#[case] input: &str, #[case] display: CssLength) {
+    #[case("horizontal-tb", CssWritingMode::HorizontalTb)]
+    #[case("vertical-rl", CssWritingMode::VerticalRl)]
+    #[case("Vertical-LR", CssWritingMode::VerticalLr)]
+    fn value_writing_mode(#[case] input: &str, #[case] writing_mode: CssWritingMode) {
         let mut input = cssparser::ParserInput::new(input);
         let input = &mut cssparser::Parser::new(&mut input);
 
-        let result = parse_value(input, Property::Width);
-        let expected = Ok(Value::Length(display));
+        let result = parse_value(input, Property::WritingMode);
+        let expected = Ok(Value::WritingMode(writing_mode));
         assert_eq!(result, expected);
     }
 
     #[rstest]
-    #[case("none", CssTextDecoration {
-        color: None,
-        line: Some(CssTextDecorationLine::None),
-        style: None
-    })]
-    #[case("underline", CssTextDecoration {
-        color: None,
-        line: Some(CssTextDecorationLine::Underline),
-        style: None
-    })]
-    #[case("red", CssTextDecoration {
-        color: Some(CssColor::Color(retina_common::Color::RED)),
-        line: None,
-        style: None
-    })]
-    #[case("red underline", CssTextDecoration {
-        color: Some(CssColor::Color(retina_common::Color::RED)),
-        line: Some(CssTextDecorationLine::Underline),
-        style: None
-    })]
-    #[case("red underline wavy", CssTextDecoration {
-        color: Some(CssColor::Color(retina_common::Color::RED)),
-        line: Some(CssTextDecorationLine::Underline),
-        style: Some(CssTextDecorationStyle::Wavy)
-    })]
-    fn value_text_decoration(#[case] input_str: &str, #[case] style: CssTextDecoration) {
-        let mut input = cssparser::ParserInput::new(input_str);
+    #[case("none", CssFloatValue::None)]
+    #[case("right", CssFloatValue::Right)]
+    #[case("inline-start", CssFloatValue::InlineStart)]
+    fn value_float(#[case] input: &str, #[case] float: CssFloatValue) {
+        let mut input = cssparser::ParserInput::new(input);
         let input = &mut cssparser::Parser::new(&mut input);
 
-        let result = parse_text_decoration(input);
-        let expected = Ok(style);
+        let result = parse_value(input, Property::Float);
+        let expected = Ok(Value::Float(float));
         assert_eq!(result, expected);
+    }
 
-
-        let mut input = cssparser::ParserInput::new(input_str);
+    #[rstest]
+    #[case("none", CssColumnSpan::None)]
+    #[case("all", CssColumnSpan::All)]
+    #[case("ALL", CssColumnSpan::All)]
+    fn value_column_span(#[case] input: &str, #[case] column_span: CssColumnSpan) {
+        let mut input = cssparser::ParserInput::new(input);
         let input = &mut cssparser::Parser::new(&mut input);
 
-        let result = parse_value(input, Property::TextDecoration);
-        let expected = Ok(Value::TextDecoration(style));
+        let result = parse_value(input, Property::ColumnSpan);
+        let expected = Ok(Value::ColumnSpan(column_span));
         assert_eq!(result, expected);
     }
 
     #[rstest]
-    #[case("none", CssTextDecorationLine::None)]
-    #[case("blink", CssTextDecorationLine::Blink)]
-    #[case("underline", CssTextDecorationLine::Underline)]
-    #[case("overline", CssTextDecorationLine::Overline)]
-    #[case("line-through", CssTextDecorationLine::LineThrough)]
-    fn value_text_decoration_line(#[case] input: &str, #[case] style: CssTextDecorationLine) {
+    #[case("none", CssGridTrackList::None)]
+    #[case("subgrid", CssGridTrackList::Subgrid)]
+    #[case("100px 1fr", CssGridTrackList::Tracks(vec![
+        CssGridTrackSize::Breadth(CssGridTrackBreadth::Length(CssLength::Pixels(100.0))),
+        CssGridTrackSize::Breadth(CssGridTrackBreadth::Flex(1.0)),
+    ]))]
+    #[case("[main-start] auto minmax(50%, max-content) [main-end]", CssGridTrackList::Tracks(vec![
+        CssGridTrackSize::Breadth(CssGridTrackBreadth::Auto),
+        CssGridTrackSize::MinMax {
+            min: CssGridTrackBreadth::Length(CssLength::Percentage(0.5)),
+            max: CssGridTrackBreadth::MaxContent,
+        },
+    ]))]
+    #[case("repeat(2, 1fr 2em) min-content", CssGridTrackList::Tracks(vec![
+        CssGridTrackSize::Breadth(CssGridTrackBreadth::Flex(1.0)),
+        CssGridTrackSize::Breadth(CssGridTrackBreadth::Length(CssLength::FontSize(2.0))),
+        CssGridTrackSize::Breadth(CssGridTrackBreadth::Flex(1.0)),
+        CssGridTrackSize::Breadth(CssGridTrackBreadth::Length(CssLength::FontSize(2.0))),
+        CssGridTrackSize::Breadth(CssGridTrackBreadth::MinContent),
+    ]))]
+    fn value_grid_template_columns(#[case] input: &str, #[case] track_list: CssGridTrackList) {
         let mut input = cssparser::ParserInput::new(input);
         let input = &mut cssparser::Parser::new(&mut input);
 
-        let result = parse_value(input, Property::TextDecorationLine);
-        let expected = Ok(Value::TextDecorationLine(style));
+        let result = parse_value(input, Property::GridTemplateColumns);
+        let expected = Ok(Value::GridTrackList(track_list));
         assert_eq!(result, expected);
     }
 
     #[rstest]
-    #[case("solid", CssTextDecorationStyle::Solid)]
-    #[case("double", CssTextDecorationStyle::Double)]
-    #[case("dotted", CssTextDecorationStyle::Dotted)]
-    #[case("dashed", CssTextDecorationStyle::Dashed)]
-    #[case("wavy", CssTextDecorationStyle::Wavy)]
-    fn value_text_decoration_style(#[case] input: &str, #[case] style: CssTextDecorationStyle) {
+    #[case("minmax(1fr, 100px)")]
+    #[case("-10px")]
+    #[case("repeat(0, 1fr)")]
+    fn value_grid_template_columns_invalid(#[case] input: &str) {
         let mut input = cssparser::ParserInput::new(input);
         let input = &mut cssparser::Parser::new(&mut input);
 
-        let result = parse_value(input, Property::TextDecorationStyle);
-        let expected = Ok(Value::TextDecorationStyle(style));
-        assert_eq!(result, expected);
+        assert!(parse_value(input, Property::GridTemplateColumns).is_err());
     }
 
     #[rstest]
-    #[case("normal", CssWhiteSpace::Normal)]
-    #[case("nowrap", CssWhiteSpace::Nowrap)]
-    #[case("pre", CssWhiteSpace::Pre)]
-    #[case("pre-wrap", CssWhiteSpace::PreWrap)]
-    #[case("pre-line", CssWhiteSpace::PreLine)]
-    #[case("break-spaces", CssWhiteSpace::BreakSpaces)]
-    fn value_white_space(#[case] input: &str, #[case] white_space: CssWhiteSpace) {
+    #[case("none", CssGridTemplateAreas::None)]
+    #[case("\"head head\" \". main\"", CssGridTemplateAreas::Areas(vec![
+        vec![Some("head".into()), Some("head".into())],
+        vec![None, Some("main".into())],
+    ]))]
+    #[case("\"a ...b\"", CssGridTemplateAreas::Areas(vec![
+        vec![Some("a".into()), None, Some("b".into())],
+    ]))]
+    fn value_grid_template_areas(#[case] input: &str, #[case] areas: CssGridTemplateAreas) {
         let mut input = cssparser::ParserInput::new(input);
         let input = &mut cssparser::Parser::new(&mut input);
 
-        let result = parse_value(input, Property::WhiteSpace);
-        let expected = Ok(Value::WhiteSpace(white_space));
+        let result = parse_value(input, Property::GridTemplateAreas);
+        let expected = Ok(Value::GridTemplateAreas(areas));
         assert_eq!(result, expected);
     }
 
+    #[rstest]
+    #[case("\"a b\" \"a\"")]
+    #[case("\"a b a\"")]
+    #[case("\"a b\" \"b a\"")]
+    #[case("\"a $\"")]
+    fn value_grid_template_areas_invalid(#[case] input: &str) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        assert!(parse_value(input, Property::GridTemplateAreas).is_err());
+    }
+
+    #[rstest]
+    #[case("auto", CssGridLine::Auto, CssGridLine::Auto)]
+    #[case("2", CssGridLine::Line(2), CssGridLine::Auto)]
+    #[case("1 / -1", CssGridLine::Line(1), CssGridLine::Line(-1))]
+    #[case("span 2 / 5", CssGridLine::Span(2), CssGridLine::Line(5))]
+    #[case("3 / span 2", CssGridLine::Line(3), CssGridLine::Span(2))]
+    #[case("main", CssGridLine::Area("main".into()), CssGridLine::Area("main".into()))]
+    fn value_grid_column(#[case] input: &str, #[case] start: CssGridLine, #[case] end: CssGridLine) {
+        let mut input = cssparser::ParserInput::new(input);
+        let input = &mut cssparser::Parser::new(&mut input);
+
+        let result = parse_value(input, Property::GridColumn);
+        let expected = Ok(Value::GridPlacement(CssGridPlacement { start, end }));
+        assert_eq!(result, expected);
+    }
 }
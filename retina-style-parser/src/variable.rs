@@ -0,0 +1,176 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! Parsing of [custom properties][spec] and the `var()` function.
+//!
+//! [spec]: https://drafts.csswg.org/css-variables/
+
+use cssparser::{Parser, ParserInput, SourcePosition, Token};
+
+use crate::{ParseError, RetinaStyleParseError};
+
+/// Whether or not the name is that of a [custom property][spec], i.e. it
+/// starts with two dashes.
+///
+/// [spec]: https://drafts.csswg.org/css-variables/#custom-property
+pub(crate) fn is_custom_property_name(name: &str) -> bool {
+    name.starts_with("--")
+}
+
+/// Consume the rest of the input, returning the unparsed text.
+pub(crate) fn consume_raw_value<'i, 't>(input: &mut Parser<'i, 't>) -> &'i str {
+    let start = input.position();
+    while input.next_including_whitespace_and_comments().is_ok() {}
+    input.slice_from(start).trim()
+}
+
+/// Whether or not the rest of the input contains a `var()` function, also
+/// when nested in other functions or blocks.
+pub(crate) fn contains_var_function<'i, 't>(input: &mut Parser<'i, 't>) -> bool {
+    while let Ok(token) = input.next() {
+        match token.clone() {
+            Token::Function(name) if name.eq_ignore_ascii_case("var") => return true,
+
+            Token::Function(..)
+                | Token::ParenthesisBlock
+                | Token::SquareBracketBlock
+                | Token::CurlyBracketBlock => {
+                let nested = input.parse_nested_block(|input| {
+                    Ok::<_, ParseError<'i>>(contains_var_function(input))
+                });
+
+                if nested.unwrap_or(false) {
+                    return true;
+                }
+            }
+
+            _ => (),
+        }
+    }
+
+    false
+}
+
+/// [Substitute the `var()` functions][spec] in the value. The `lookup`
+/// returns the value of a custom property, or `None` when the property isn't
+/// defined or is the guaranteed-invalid value, in which case the fallback is
+/// used.
+///
+/// Returns `None` when a `var()` function can't be substituted, which makes
+/// the declaration invalid at computed-value time.
+///
+/// [spec]: https://drafts.csswg.org/css-variables/#substitute-a-var
+pub fn substitute_var_functions(
+    value: &str,
+    lookup: &mut dyn FnMut(&str) -> Option<String>,
+) -> Option<String> {
+    let mut input = ParserInput::new(value);
+    let mut parser = Parser::new(&mut input);
+
+    let mut output = String::with_capacity(value.len());
+    let mut last = parser.position();
+
+    substitute_in_block(&mut parser, &mut output, &mut last, lookup).ok()?;
+
+    output.push_str(parser.slice_from(last));
+    Some(output)
+}
+
+/// Substitute the `var()` functions in the current block, appending the text
+/// up to each function to the `output`.
+fn substitute_in_block<'i, 't>(
+    input: &mut Parser<'i, 't>,
+    output: &mut String,
+    last: &mut SourcePosition,
+    lookup: &mut dyn FnMut(&str) -> Option<String>,
+) -> Result<(), ParseError<'i>> {
+    loop {
+        let start = input.position();
+        let Ok(token) = input.next_including_whitespace_and_comments() else {
+            return Ok(());
+        };
+
+        match token.clone() {
+            Token::Function(name) if name.eq_ignore_ascii_case("var") => {
+                output.push_str(input.slice(*last..start));
+                let value = input.parse_nested_block(|input| substitute_var_function(input, lookup))?;
+                output.push_str(&value);
+                *last = input.position();
+            }
+
+            Token::Function(..)
+                | Token::ParenthesisBlock
+                | Token::SquareBracketBlock
+                | Token::CurlyBracketBlock => {
+                input.parse_nested_block(|input| substitute_in_block(input, output, last, lookup))?;
+            }
+
+            _ => (),
+        }
+    }
+}
+
+/// Resolve the arguments of a `var()` function, i.e.
+/// `var( <custom-property-name> , <declaration-value>? )`.
+fn substitute_var_function<'i, 't>(
+    input: &mut Parser<'i, 't>,
+    lookup: &mut dyn FnMut(&str) -> Option<String>,
+) -> Result<String, ParseError<'i>> {
+    let location = input.current_source_location();
+    let name = input.expect_ident_cloned()?;
+    if !is_custom_property_name(&name) {
+        return Err(location.new_custom_error(RetinaStyleParseError::VarFunctionInvalidName(name)));
+    }
+
+    let fallback = if input.try_parse(|input| input.expect_comma()).is_ok() {
+        Some(consume_raw_value(input))
+    } else {
+        None
+    };
+
+    if let Some(value) = lookup(&name) {
+        return Ok(value);
+    }
+
+    fallback.and_then(|fallback| substitute_var_functions(fallback, lookup))
+        .ok_or_else(|| location.new_custom_error(RetinaStyleParseError::VarFunctionUndefinedVariable(name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("var(--color)", Some("red"))]
+    #[case("1px solid var(--color)", Some("1px solid red"))]
+    #[case("calc(var(--size) * 2)", Some("calc(10px * 2)"))]
+    #[case("var(--undefined, blue)", Some("blue"))]
+    #[case("var(--undefined, var(--color))", Some("red"))]
+    #[case("var(--color, blue)", Some("red"))]
+    #[case("var(--undefined)", None)]
+    #[case("var(color)", None)]
+    fn substitute(#[case] input: &str, #[case] expected: Option<&str>) {
+        let variables = HashMap::from([
+            ("--color", "red"),
+            ("--size", "10px"),
+        ]);
+
+        let result = substitute_var_functions(input, &mut |name| variables.get(name).map(|value| value.to_string()));
+        assert_eq!(result.as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case("var(--color)", true)]
+    #[case("1px calc(2px + var(--size))", true)]
+    #[case("1px solid red", false)]
+    #[case("\"var(--color)\"", false)]
+    fn contains_var(#[case] input: &str, #[case] expected: bool) {
+        let mut input = ParserInput::new(input);
+        let mut parser = Parser::new(&mut input);
+        assert_eq!(contains_var_function(&mut parser), expected);
+    }
+}
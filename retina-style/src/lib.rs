@@ -8,6 +8,7 @@ mod media;
 mod rule;
 mod selector;
 mod stylesheet;
+mod supports;
 mod value;
 
 pub use cascade_origin::CascadeOrigin;
@@ -17,4 +18,5 @@ pub use property::Property;
 pub use rule::*;
 pub use selector::*;
 pub use stylesheet::Stylesheet;
+pub use supports::SupportsCondition;
 pub use value::*;
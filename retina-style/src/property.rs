@@ -11,6 +11,14 @@ pub enum Property {
     #[strum(disabled)]
     Invalid,
 
+    AlignContent,
+    AlignItems,
+    AlignSelf,
+
+    Animation,
+
+    AspectRatio,
+
     BackgroundColor,
     BackgroundImage,
     BackgroundRepeat,
@@ -40,10 +48,35 @@ pub enum Property {
     BorderTopStyle,
     BorderTopWidth,
 
+    BorderCollapse,
+    BorderSpacing,
+
+    Bottom,
+
+    BoxShadow,
+
+    CaptionSide,
+    Clear,
     Cursor,
     Color,
+    ColumnSpan,
+    Content,
+
+    /// A [custom property][spec], of which the name is part of the
+    /// [`Value::CustomProperty`](crate::Value::CustomProperty).
+    ///
+    /// [spec]: https://drafts.csswg.org/css-variables/#custom-property
+    #[strum(disabled)]
+    Custom,
+
+    Direction,
     Display,
 
+    FlexBasis,
+    FlexDirection,
+    FlexGrow,
+    FlexShrink,
+    FlexWrap,
     Float,
     Font,
     FontFamily,
@@ -58,8 +91,22 @@ pub enum Property {
     FontVariantPosition,
     FontWeight,
 
+    GridColumn,
+    GridRow,
+    GridTemplateAreas,
+    GridTemplateColumns,
+    GridTemplateRows,
+
     Height,
 
+    ImageRendering,
+
+    JustifyContent,
+
+    Left,
+
+    LetterSpacing,
+
     Margin,
     MarginBlock,
     MarginBlockEnd,
@@ -72,20 +119,57 @@ pub enum Property {
     MarginRight,
     MarginTop,
 
+    MaxHeight,
+    MaxWidth,
+    MinHeight,
+    MinWidth,
+
+    Opacity,
+    Order,
+
+    Outline,
+    OutlineColor,
+    OutlineOffset,
+    OutlineStyle,
+    OutlineWidth,
+
+    Overflow,
+    OverflowWrap,
+    OverflowX,
+    OverflowY,
+
     Padding,
     PaddingBottom,
     PaddingLeft,
     PaddingRight,
     PaddingTop,
 
+    Position,
+
+    Right,
+
+    TableLayout,
+
     TextDecoration,
     TextDecorationColor,
     TextDecorationLine,
     TextDecorationStyle,
+    TextIndent,
+    TextOverflow,
     TextTransform,
 
+    Top,
+
+    Transform,
+    Transition,
+
     Width,
     WhiteSpace,
+    WordBreak,
+    WordSpacing,
+    WritingMode,
+
+    ZIndex,
 }
 
 impl Property {
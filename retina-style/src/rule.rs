@@ -3,10 +3,12 @@
 
 use crate::{
     cascade_origin::CascadeOrigin,
+    CssDecimal,
     Declaration,
     MediaQuery,
     SelectorList,
     Stylesheet,
+    SupportsCondition,
 };
 
 pub use self::font_face::*;
@@ -17,8 +19,25 @@ mod font_face;
 pub enum Rule {
     AtFontFace(CssFontFaceAtRule),
 
+    /// `@import`
+    AtImport(AtImportRule),
+
+    /// `@keyframes`, which is stored in the keyframes map of the style sheet
+    /// instead of its list of rules, see [`Stylesheet::keyframes()`].
+    AtKeyframes(AtKeyframesRule),
+
+    /// `@layer name { ... }`
+    AtLayer(AtLayerRule),
+
+    /// `@layer name, other-name;`
+    AtLayerStatement(AtLayerStatementRule),
+
     /// `@rule`
     AtMedia(AtMediaRule),
+
+    /// `@supports`, which is only kept in the style sheet when its condition
+    /// holds.
+    AtSupports(AtSupportsRule),
     Style(StyleRule),
 }
 
@@ -40,8 +59,99 @@ pub struct StyleRule {
     pub declarations: Vec<Declaration>,
 }
 
+/// The block form of the `@layer` rule, which puts the rules inside into a
+/// cascade layer.
+///
+/// # References
+/// * [CSS - Cascading and Inheritance Level 5 § 6.4.2. Declaring Cascade Layers][spec]
+///
+/// [spec]: https://drafts.csswg.org/css-cascade-5/#layering
+#[derive(Clone, Debug, PartialEq)]
+pub struct AtLayerRule {
+    /// The (possibly dotted) name of the layer, or [`None`] if the layer is
+    /// anonymous.
+    pub name: Option<String>,
+    pub stylesheet: Stylesheet,
+}
+
+/// The statement form of the `@layer` rule, which establishes the order of
+/// the named layers without assigning any styles.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AtLayerStatementRule {
+    pub names: Vec<String>,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct AtMediaRule {
     pub media_query_list: Vec<MediaQuery>,
     pub stylesheet: Stylesheet,
 }
+
+/// The `@supports` rule, of which the rules inside only apply when the
+/// features in its condition are supported.
+///
+/// # References
+/// * [CSS Conditional Rules Module Level 3 § 6. Feature Queries: the `@supports` rule][spec]
+///
+/// [spec]: https://drafts.csswg.org/css-conditional-3/#at-supports
+#[derive(Clone, Debug, PartialEq)]
+pub struct AtSupportsRule {
+    pub condition: SupportsCondition,
+    pub stylesheet: Stylesheet,
+}
+
+/// The `@keyframes` rule, which specifies the values of the properties
+/// animated by the `animation` property with the same name.
+///
+/// # References
+/// * [CSS Animations Level 1 § 3. Declaring Keyframes][spec]
+///
+/// [spec]: https://drafts.csswg.org/css-animations/#keyframes
+#[derive(Clone, Debug, PartialEq)]
+pub struct AtKeyframesRule {
+    pub name: String,
+    pub keyframes: Vec<Keyframe>,
+}
+
+/// A keyframe block of the `@keyframes` rule.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Keyframe {
+    /// The offsets of the [`<keyframe-selector>`][spec]s, where `from` is
+    /// `0.0` and `to` is `1.0`.
+    ///
+    /// [spec]: https://drafts.csswg.org/css-animations/#typedef-keyframe-selector
+    pub offsets: Vec<CssDecimal>,
+    pub declarations: Vec<Declaration>,
+}
+
+/// The `@import` rule, which allows importing style rules from other style
+/// sheets.
+///
+/// # References
+/// * [CSS - Cascading and Inheritance Level 5 § 2.1. Importing Style Sheets][spec]
+///
+/// [spec]: https://drafts.csswg.org/css-cascade-5/#at-import
+#[derive(Clone, Debug, PartialEq)]
+pub struct AtImportRule {
+    pub url: String,
+    pub layer: AtImportLayer,
+    pub media: Vec<MediaQuery>,
+
+    /// The style sheet that was loaded from the [`url`][AtImportRule::url].
+    /// This is [`None`] until the style sheet is fetched and parsed.
+    pub stylesheet: Option<Stylesheet>,
+}
+
+/// The cascade layer the rules of an `@import` rule are put into.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum AtImportLayer {
+    /// No `layer` keyword or function was specified.
+    #[default]
+    None,
+
+    /// `layer`
+    Anonymous,
+
+    /// `layer(name)`
+    Named(String),
+}
@@ -9,6 +9,8 @@ use retina_common::StrTendril;
 mod pseudo;
 
 pub use self::pseudo::{
+    AnPlusB,
+    CssPseudoElement,
     FunctionalPseudoClassSelectorKind,
     PseudoClassSelectorKind,
 };
@@ -101,6 +103,25 @@ pub enum Selector {
     Simple(SimpleSelector),
 }
 
+/// A selector that is relative to an anchor element, e.g. the `> p` in
+/// `div:has(> p)`. The `combinator` describes the relation between the
+/// anchor and the topmost compound selector of the `selector`.
+///
+/// # References
+/// * [CSS - Selectors Level 4 - 3.2](https://drafts.csswg.org/selectors/#relative)
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RelativeSelector {
+    pub combinator: SelectorCombinator,
+    pub selector: ComplexSelector,
+}
+
+/// # References
+/// * [CSS - Selectors Level 4 - 3.2](https://drafts.csswg.org/selectors/#typedef-relative-selector-list)
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RelativeSelectorList {
+    pub items: Vec<RelativeSelector>,
+}
+
 /// # References
 /// * [CSS - Selectors Level 4 - 3.1](https://drafts.csswg.org/selectors/#selector-combinator)
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -146,6 +167,19 @@ pub enum SimpleSelector {
 
     PseudoClass(PseudoClassSelectorKind),
 
+    /// A pseudo-class taking arguments, e.g. `:is()` or `:nth-child()`.
+    ///
+    /// # References
+    /// * [CSS - Selectors Level 4 - 3.5](https://drafts.csswg.org/selectors/#functional-pseudo-class)
+    FunctionalPseudoClass(FunctionalPseudoClassSelectorKind),
+
+    /// A pseudo-element, e.g. `::before`. These never match an element
+    /// directly, but the box generated for it.
+    ///
+    /// # References
+    /// * [CSS - Selectors Level 4 - 3.6](https://drafts.csswg.org/selectors/#pseudo-elements)
+    PseudoElement(CssPseudoElement),
+
     /// The type selector selects an element by it's tag name.
     ///
     /// > A ___type selector___ is the name of a document language element type,
@@ -3,14 +3,43 @@
 
 use strum::{AsRefStr, EnumIter};
 
-use crate::SelectorList;
+use crate::{
+    RelativeSelectorList,
+    SelectorList,
+};
+
+/// The [`An+B`][spec] notation, which represents the indices `An+B` for every
+/// non-negative integer `n`, e.g. `2n+1`, `odd` or `3`.
+///
+/// [spec]: https://drafts.csswg.org/css-syntax/#anb-microsyntax
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AnPlusB {
+    pub a: i32,
+    pub b: i32,
+}
+
+impl AnPlusB {
+    pub const fn new(a: i32, b: i32) -> Self {
+        Self { a, b }
+    }
+
+    /// Check whether the (1-based) `index` is represented by this notation.
+    pub fn matches(&self, index: i32) -> bool {
+        let offset = index - self.b;
+        if self.a == 0 {
+            return offset == 0;
+        }
+
+        offset % self.a == 0 && offset / self.a >= 0
+    }
+}
 
 /// # References
 /// * [CSS - Selectors Level 4 - 3.5](https://drafts.csswg.org/selectors/#functional-pseudo-class)
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum FunctionalPseudoClassSelectorKind {
     /// <https://drafts.csswg.org/selectors/#relational>
-    Has(Vec<SelectorList>),
+    Has(RelativeSelectorList),
 
     /// <https://drafts.csswg.org/selectors/#matches>
     Is(Vec<SelectorList>),
@@ -18,10 +47,47 @@ pub enum FunctionalPseudoClassSelectorKind {
     /// <https://drafts.csswg.org/selectors/#negation>
     Not(Vec<SelectorList>),
 
+    /// <https://drafts.csswg.org/selectors/#the-nth-child-pseudo>
+    NthChild(AnPlusB, Option<SelectorList>),
+
+    /// <https://drafts.csswg.org/selectors/#the-nth-last-child-pseudo>
+    NthLastChild(AnPlusB, Option<SelectorList>),
+
+    /// <https://drafts.csswg.org/selectors/#the-nth-last-of-type-pseudo>
+    NthLastOfType(AnPlusB),
+
+    /// <https://drafts.csswg.org/selectors/#the-nth-of-type-pseudo>
+    NthOfType(AnPlusB),
+
     /// <https://drafts.csswg.org/selectors/#zero-matches>
     Where(Vec<SelectorList>),
 }
 
+/// The pseudo-elements that are supported, which represent elements that
+/// aren't part of the document tree, such as generated content.
+///
+/// # References
+/// * [CSS - Selectors Level 4 - 3.6](https://drafts.csswg.org/selectors/#pseudo-elements)
+/// * [CSS Pseudo-Elements Level 4 - 4](https://drafts.csswg.org/css-pseudo/#generated-content)
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(AsRefStr, EnumIter)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CssPseudoElement {
+    /// <https://drafts.csswg.org/css-pseudo/#selectordef-after>
+    After,
+
+    /// <https://drafts.csswg.org/css-pseudo/#selectordef-before>
+    Before,
+}
+
+impl CssPseudoElement {
+    pub fn parse(input: &str) -> Option<Self> {
+        use strum::IntoEnumIterator;
+        Self::iter()
+            .find(|x| x.as_ref().eq_ignore_ascii_case(input))
+    }
+}
+
 /// # References
 /// * [CSS - Selectors Level 4 - 3.5](https://drafts.csswg.org/selectors/#pseudo-classes)
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -1,11 +1,17 @@
 // Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
 // All Rights Reserved.
 
-use crate::Rule;
+use std::collections::HashMap;
+
+use crate::{AtKeyframesRule, Rule};
 
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Stylesheet {
     rules: Vec<Rule>,
+
+    /// The `@keyframes` rules by their name. When multiple rules have the
+    /// same name, the last one wins.
+    keyframes: HashMap<String, AtKeyframesRule>,
 }
 
 impl Stylesheet {
@@ -17,7 +23,37 @@ impl Stylesheet {
         self.rules.push(rule);
     }
 
+    pub fn insert_keyframes(&mut self, rule: AtKeyframesRule) {
+        self.keyframes.insert(rule.name.clone(), rule);
+    }
+
+    /// Find the `@keyframes` rule with the given name, in this style sheet or
+    /// in the ones nested in it (e.g. by `@media` or `@import`), where the
+    /// rules of this style sheet and later rules take precedence.
+    ///
+    /// TODO: the media queries of `@media` and `@import` aren't evaluated.
+    pub fn keyframes(&self, name: &str) -> Option<&AtKeyframesRule> {
+        if let Some(rule) = self.keyframes.get(name) {
+            return Some(rule);
+        }
+
+        self.rules.iter()
+            .rev()
+            .filter_map(|rule| match rule {
+                Rule::AtImport(import) => import.stylesheet.as_ref(),
+                Rule::AtLayer(layer) => Some(&layer.stylesheet),
+                Rule::AtMedia(media) => Some(&media.stylesheet),
+                Rule::AtSupports(supports) => Some(&supports.stylesheet),
+                _ => None,
+            })
+            .find_map(|stylesheet| stylesheet.keyframes(name))
+    }
+
     pub fn rules(&self) -> &[Rule] {
         &self.rules
     }
+
+    pub fn rules_mut(&mut self) -> &mut [Rule] {
+        &mut self.rules
+    }
 }
@@ -0,0 +1,44 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use crate::Declaration;
+
+/// The [`<supports-condition>`][spec] of an `@supports` rule. Whether a
+/// declaration is supported depends on the parser, which is why it evaluates
+/// the declarations when the rule is parsed.
+///
+/// [spec]: https://drafts.csswg.org/css-conditional-3/#typedef-supports-condition
+#[derive(Clone, Debug, PartialEq)]
+pub enum SupportsCondition {
+    /// `(property: value)`, of which the declaration is [`None`] when it
+    /// isn't supported, i.e. the property is unknown or the value invalid.
+    Declaration(Option<Declaration>),
+
+    /// `not <supports-in-parens>`
+    Not(Box<SupportsCondition>),
+
+    /// `<supports-in-parens> [ and <supports-in-parens> ]*`
+    And(Vec<SupportsCondition>),
+
+    /// `<supports-in-parens> [ or <supports-in-parens> ]*`
+    Or(Vec<SupportsCondition>),
+
+    /// A [`<general-enclosed>`][spec], e.g. `selector(...)`, which isn't
+    /// supported and therefore never holds.
+    ///
+    /// [spec]: https://drafts.csswg.org/mediaqueries-4/#typedef-general-enclosed
+    Unknown,
+}
+
+impl SupportsCondition {
+    /// Evaluate the condition.
+    pub fn matches(&self) -> bool {
+        match self {
+            Self::Declaration(declaration) => declaration.is_some(),
+            Self::Not(condition) => !condition.matches(),
+            Self::And(conditions) => conditions.iter().all(Self::matches),
+            Self::Or(conditions) => conditions.iter().any(Self::matches),
+            Self::Unknown => false,
+        }
+    }
+}
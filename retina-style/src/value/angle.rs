@@ -0,0 +1,44 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use super::CssDecimal;
+
+/// An [`<angle>`][spec] value.
+///
+/// # References
+/// * [CSS Values and Units Module Level 4 § 7.1. Angle Units: the `<angle>` type][spec]
+///
+/// [spec]: https://drafts.csswg.org/css-values-4/#angles
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CssAngle {
+    /// `deg`, there are 360 degrees in a full circle.
+    Degrees(CssDecimal),
+
+    /// `grad`, there are 400 gradians in a full circle.
+    Gradians(CssDecimal),
+
+    /// `rad`, there are 2π radians in a full circle.
+    Radians(CssDecimal),
+
+    /// `turn`, there is 1 turn in a full circle.
+    Turns(CssDecimal),
+}
+
+impl CssAngle {
+    pub fn to_radians(&self) -> CssDecimal {
+        use std::f64::consts::TAU;
+
+        match *self {
+            Self::Degrees(degrees) => degrees.to_radians(),
+            Self::Gradians(gradians) => gradians / 400.0 * TAU,
+            Self::Radians(radians) => radians,
+            Self::Turns(turns) => turns * TAU,
+        }
+    }
+}
+
+impl Default for CssAngle {
+    fn default() -> Self {
+        Self::Degrees(0.0)
+    }
+}
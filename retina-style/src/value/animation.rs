@@ -0,0 +1,118 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use std::time::Duration;
+
+use retina_common::StrTendril;
+use strum::{AsRefStr, EnumIter};
+
+use crate::{CssDecimal, CssEasingFunction};
+
+/// A single item of the [`animation`][spec] shorthand.
+///
+/// # References
+/// * [CSS Animations Level 1 § 3.10. The `animation` shorthand property][spec]
+///
+/// [spec]: https://drafts.csswg.org/css-animations/#animation
+#[derive(Clone, Debug, PartialEq)]
+pub struct CssAnimation {
+    /// The name of the `@keyframes` rule, or [`None`] for `none`.
+    pub name: Option<StrTendril>,
+    pub duration: Duration,
+    pub timing_function: CssEasingFunction,
+    pub delay: Duration,
+    pub iteration_count: CssAnimationIterationCount,
+    pub direction: CssAnimationDirection,
+    pub fill_mode: CssAnimationFillMode,
+    pub play_state: CssAnimationPlayState,
+}
+
+impl Default for CssAnimation {
+    fn default() -> Self {
+        Self {
+            name: None,
+            duration: Duration::ZERO,
+            timing_function: CssEasingFunction::default(),
+            delay: Duration::ZERO,
+            iteration_count: CssAnimationIterationCount::default(),
+            direction: CssAnimationDirection::default(),
+            fill_mode: CssAnimationFillMode::default(),
+            play_state: CssAnimationPlayState::default(),
+        }
+    }
+}
+
+/// The value of the [`animation-iteration-count`][spec] property.
+///
+/// [spec]: https://drafts.csswg.org/css-animations/#animation-iteration-count
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CssAnimationIterationCount {
+    /// `infinite`
+    Infinite,
+
+    /// A non-negative number of iterations, which can be fractional.
+    Count(CssDecimal),
+}
+
+impl Default for CssAnimationIterationCount {
+    fn default() -> Self {
+        Self::Count(1.0)
+    }
+}
+
+/// The value of the [`animation-direction`][spec] property.
+///
+/// [spec]: https://drafts.csswg.org/css-animations/#animation-direction
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(AsRefStr, EnumIter)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CssAnimationDirection {
+    /// `normal`
+    #[default]
+    Normal,
+
+    /// `reverse`
+    Reverse,
+
+    /// `alternate`
+    Alternate,
+
+    /// `alternate-reverse`
+    AlternateReverse,
+}
+
+/// The value of the [`animation-fill-mode`][spec] property.
+///
+/// [spec]: https://drafts.csswg.org/css-animations/#animation-fill-mode
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(AsRefStr, EnumIter)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CssAnimationFillMode {
+    /// `none`
+    #[default]
+    None,
+
+    /// `forwards`
+    Forwards,
+
+    /// `backwards`
+    Backwards,
+
+    /// `both`
+    Both,
+}
+
+/// The value of the [`animation-play-state`][spec] property.
+///
+/// [spec]: https://drafts.csswg.org/css-animations/#animation-play-state
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(AsRefStr, EnumIter)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CssAnimationPlayState {
+    /// `running`
+    #[default]
+    Running,
+
+    /// `paused`
+    Paused,
+}
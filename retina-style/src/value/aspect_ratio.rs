@@ -0,0 +1,65 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use super::CssDecimal;
+
+/// The value of the [`aspect-ratio`][spec] property, which is the preferred
+/// ratio of the width to the height of the box, used to compute one of them
+/// when the other is known.
+///
+/// # References
+/// * [CSS Box Sizing Module Level 4 § 2.1. Preferred Aspect Ratios: the `aspect-ratio` property][spec]
+///
+/// [spec]: https://drafts.csswg.org/css-sizing-4/#aspect-ratio
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum CssAspectRatio {
+    /// Replaced elements with a natural aspect ratio use it, and other boxes
+    /// don't have a preferred aspect ratio.
+    #[default]
+    Auto,
+
+    /// `auto && <ratio>`: the natural aspect ratio of replaced elements is
+    /// used, and the given ratio otherwise.
+    AutoOrRatio(CssRatio),
+
+    /// The given ratio is used, even for replaced elements.
+    Ratio(CssRatio),
+}
+
+impl CssAspectRatio {
+    /// Get the preferred aspect ratio, as the width divided by the height,
+    /// given the `natural` aspect ratio of a replaced element.
+    pub fn preferred_ratio(&self, natural: Option<CssDecimal>) -> Option<CssDecimal> {
+        match self {
+            Self::Auto => natural,
+            Self::AutoOrRatio(ratio) => natural.or_else(|| ratio.value()),
+
+            // > If the <ratio> is degenerate, the property instead behaves as
+            // > auto.
+            Self::Ratio(ratio) => ratio.value().or(natural),
+        }
+    }
+}
+
+/// A [`<ratio>`][spec], e.g. `16 / 9`.
+///
+/// [spec]: https://drafts.csswg.org/css-values-4/#ratio-value
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CssRatio {
+    pub numerator: CssDecimal,
+    pub denominator: CssDecimal,
+}
+
+impl CssRatio {
+    /// Get the value of the ratio, which is `None` when it is
+    /// [degenerate][spec], i.e. either of its numbers is zero.
+    ///
+    /// [spec]: https://drafts.csswg.org/css-values-4/#degenerate-ratio
+    pub fn value(&self) -> Option<CssDecimal> {
+        if self.numerator == 0.0 || self.denominator == 0.0 {
+            return None;
+        }
+
+        Some(self.numerator / self.denominator)
+    }
+}
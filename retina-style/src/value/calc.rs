@@ -0,0 +1,286 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! The [math functions][spec] `calc()`, `min()`, `max()` and `clamp()`.
+//!
+//! [spec]: https://drafts.csswg.org/css-values-4/#math
+
+use crate::{CssDecimal, CssLength};
+
+/// The expression tree of a math function.
+///
+/// # References
+/// * [CSS Values and Units Module Level 4 § 10.1. Basic Arithmetic: `calc()`][spec]
+///
+/// [spec]: https://drafts.csswg.org/css-values-4/#calc-func
+#[derive(Clone, Debug, PartialEq)]
+pub enum CalcExpression {
+    /// A `<length>` or `<percentage>`, which is never [`CssLength::Auto`] or
+    /// [`CssLength::Calc`].
+    Length(CssLength),
+
+    /// A `<number>`.
+    Number(CssDecimal),
+
+    Sum(Box<CalcExpression>, Box<CalcExpression>),
+    Difference(Box<CalcExpression>, Box<CalcExpression>),
+    Product(Box<CalcExpression>, Box<CalcExpression>),
+    Quotient(Box<CalcExpression>, Box<CalcExpression>),
+
+    /// `min()`
+    Min(Vec<CalcExpression>),
+
+    /// `max()`
+    Max(Vec<CalcExpression>),
+
+    /// `clamp()`
+    Clamp {
+        min: Box<CalcExpression>,
+        value: Box<CalcExpression>,
+        max: Box<CalcExpression>,
+    },
+}
+
+/// The result of [simplifying][spec] a [`CalcExpression`].
+///
+/// [spec]: https://drafts.csswg.org/css-values-4/#calc-simplification
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CalcValue {
+    Number(CssDecimal),
+    Length(CssCalcLength),
+}
+
+impl CalcExpression {
+    /// Simplify the expression. This is `None` when the types of the operands
+    /// don't match, e.g. when adding a number to a length, or when dividing
+    /// by zero.
+    ///
+    /// TODO: `min()`, `max()` and `clamp()` with arguments of different
+    ///       units, e.g. `min(50%, 10em)`, can only be resolved during layout
+    ///       and aren't supported yet.
+    pub fn simplify(&self) -> Option<CalcValue> {
+        match self {
+            Self::Length(length) => CssCalcLength::from_length(*length).map(CalcValue::Length),
+            Self::Number(number) => Some(CalcValue::Number(*number)),
+
+            Self::Sum(lhs, rhs) => match (lhs.simplify()?, rhs.simplify()?) {
+                (CalcValue::Number(lhs), CalcValue::Number(rhs)) => Some(CalcValue::Number(lhs + rhs)),
+                (CalcValue::Length(lhs), CalcValue::Length(rhs)) => Some(CalcValue::Length(lhs.add(rhs))),
+                _ => None,
+            },
+
+            Self::Difference(lhs, rhs) => match (lhs.simplify()?, rhs.simplify()?) {
+                (CalcValue::Number(lhs), CalcValue::Number(rhs)) => Some(CalcValue::Number(lhs - rhs)),
+                (CalcValue::Length(lhs), CalcValue::Length(rhs)) => Some(CalcValue::Length(lhs.add(rhs.scale(-1.0)))),
+                _ => None,
+            },
+
+            // At least one side of a multiplication must be a number.
+            Self::Product(lhs, rhs) => match (lhs.simplify()?, rhs.simplify()?) {
+                (CalcValue::Number(lhs), CalcValue::Number(rhs)) => Some(CalcValue::Number(lhs * rhs)),
+                (CalcValue::Number(factor), CalcValue::Length(length))
+                    | (CalcValue::Length(length), CalcValue::Number(factor)) => Some(CalcValue::Length(length.scale(factor))),
+                (CalcValue::Length(..), CalcValue::Length(..)) => None,
+            },
+
+            // The right side of a division must be a number.
+            Self::Quotient(lhs, rhs) => match (lhs.simplify()?, rhs.simplify()?) {
+                (_, CalcValue::Number(divisor)) if divisor == 0.0 => None,
+                (CalcValue::Number(lhs), CalcValue::Number(rhs)) => Some(CalcValue::Number(lhs / rhs)),
+                (CalcValue::Length(length), CalcValue::Number(divisor)) => Some(CalcValue::Length(length.scale(1.0 / divisor))),
+                (_, CalcValue::Length(..)) => None,
+            },
+
+            Self::Min(arguments) => compare_arguments(arguments, CssDecimal::min),
+            Self::Max(arguments) => compare_arguments(arguments, CssDecimal::max),
+
+            Self::Clamp { min, value, max } => {
+                let value = Self::Min(vec![(**value).clone(), (**max).clone()]);
+                Self::Max(vec![(**min).clone(), value]).simplify()
+            }
+        }
+    }
+
+    /// Simplify the expression to a `<length-percentage>`, which is `None`
+    /// when the expression is invalid or resolves to a `<number>`.
+    pub fn to_length(&self) -> Option<CssLength> {
+        match self.simplify()? {
+            CalcValue::Length(length) => Some(length.into_length()),
+            CalcValue::Number(..) => None,
+        }
+    }
+}
+
+/// Fold the arguments of `min()` or `max()`, which must all be numbers, or
+/// lengths of the same unit.
+fn compare_arguments(
+    arguments: &[CalcExpression],
+    fold: fn(CssDecimal, CssDecimal) -> CssDecimal,
+) -> Option<CalcValue> {
+    let mut values = arguments.iter().map(CalcExpression::simplify);
+
+    match values.next()?? {
+        CalcValue::Number(first) => values.try_fold(first, |result, value| match value? {
+            CalcValue::Number(number) => Some(fold(result, number)),
+            CalcValue::Length(..) => None,
+        }).map(CalcValue::Number),
+
+        CalcValue::Length(first) => {
+            let (mut unit, first) = first.single_unit()?;
+            let result = values.try_fold(first, |result, value| {
+                let CalcValue::Length(length) = value? else {
+                    return None;
+                };
+
+                let (other_unit, value) = length.single_unit()?;
+                match (unit, other_unit) {
+                    (Some(unit), Some(other_unit)) if unit != other_unit => return None,
+                    (None, other_unit) => unit = other_unit,
+                    _ => (),
+                }
+
+                Some(fold(result, value))
+            })?;
+
+            let mut components = [0.0; CssCalcLength::UNIT_COUNT];
+            components[unit.unwrap_or(0)] = result;
+            Some(CalcValue::Length(CssCalcLength::from_components(components)))
+        }
+    }
+}
+
+/// A `<length-percentage>` that is the sum of lengths with different units,
+/// i.e. the simplified form of a `calc()` expression like `calc(50% - 2em)`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct CssCalcLength {
+    pub pixels: CssDecimal,
+    pub font_size: CssDecimal,
+    pub root_font_size: CssDecimal,
+    pub percentage: CssDecimal,
+    pub viewport_height: CssDecimal,
+    pub viewport_max: CssDecimal,
+    pub viewport_min: CssDecimal,
+    pub viewport_width: CssDecimal,
+}
+
+impl CssCalcLength {
+    const UNIT_COUNT: usize = 8;
+
+    /// Convert a length, which is `None` for `auto` and the intrinsic size
+    /// keywords.
+    pub fn from_length(length: CssLength) -> Option<Self> {
+        let mut result = Self::default();
+        match length {
+            CssLength::Auto | CssLength::MaxContent | CssLength::MinContent => return None,
+            CssLength::Calc(calc) => return Some(calc),
+            CssLength::FontSize(value) => result.font_size = value,
+            CssLength::FontSizeOfRootElement(value) => result.root_font_size = value,
+            CssLength::Percentage(value) => result.percentage = value,
+            CssLength::Pixels(value) => result.pixels = value,
+            CssLength::UaDefaultViewportHeightPercentage(value) => result.viewport_height = value,
+            CssLength::UaDefaultViewportMaxPercentage(value) => result.viewport_max = value,
+            CssLength::UaDefaultViewportMinPercentage(value) => result.viewport_min = value,
+            CssLength::UaDefaultViewportWidthPercentage(value) => result.viewport_width = value,
+        }
+        Some(result)
+    }
+
+    fn from_components(components: [CssDecimal; Self::UNIT_COUNT]) -> Self {
+        let [
+            pixels,
+            font_size,
+            root_font_size,
+            percentage,
+            viewport_height,
+            viewport_max,
+            viewport_min,
+            viewport_width,
+        ] = components;
+
+        Self {
+            pixels,
+            font_size,
+            root_font_size,
+            percentage,
+            viewport_height,
+            viewport_max,
+            viewport_min,
+            viewport_width,
+        }
+    }
+
+    fn components(&self) -> [CssDecimal; Self::UNIT_COUNT] {
+        [
+            self.pixels,
+            self.font_size,
+            self.root_font_size,
+            self.percentage,
+            self.viewport_height,
+            self.viewport_max,
+            self.viewport_min,
+            self.viewport_width,
+        ]
+    }
+
+    /// The non-zero terms of the sum, which are resolved separately and then
+    /// added up.
+    pub fn terms(&self) -> impl Iterator<Item = CssLength> {
+        let constructors: [fn(CssDecimal) -> CssLength; Self::UNIT_COUNT] = [
+            CssLength::Pixels,
+            CssLength::FontSize,
+            CssLength::FontSizeOfRootElement,
+            CssLength::Percentage,
+            CssLength::UaDefaultViewportHeightPercentage,
+            CssLength::UaDefaultViewportMaxPercentage,
+            CssLength::UaDefaultViewportMinPercentage,
+            CssLength::UaDefaultViewportWidthPercentage,
+        ];
+
+        self.components()
+            .into_iter()
+            .zip(constructors)
+            .filter(|(value, _)| *value != 0.0)
+            .map(|(value, constructor)| constructor(value))
+    }
+
+    /// The simplest representation of this length, which is only a
+    /// [`CssLength::Calc`] when it combines multiple units.
+    pub fn into_length(self) -> CssLength {
+        let mut terms = self.terms();
+        match (terms.next(), terms.next()) {
+            (None, _) => CssLength::Pixels(0.0),
+            (Some(term), None) => term,
+            (Some(..), Some(..)) => CssLength::Calc(self),
+        }
+    }
+
+    pub(crate) fn add(self, other: Self) -> Self {
+        let mut components = self.components();
+        for (component, other) in components.iter_mut().zip(other.components()) {
+            *component += other;
+        }
+        Self::from_components(components)
+    }
+
+    pub(crate) fn scale(self, factor: CssDecimal) -> Self {
+        Self::from_components(self.components().map(|component| component * factor))
+    }
+
+    /// The index of the only unit with a non-zero value, and that value. The
+    /// unit is `None` when the length is zero.
+    fn single_unit(&self) -> Option<(Option<usize>, CssDecimal)> {
+        let mut result = (None, 0.0);
+        for (index, value) in self.components().into_iter().enumerate() {
+            if value == 0.0 {
+                continue;
+            }
+
+            if result.0.is_some() {
+                return None;
+            }
+
+            result = (Some(index), value);
+        }
+        Some(result)
+    }
+}
@@ -0,0 +1,23 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use strum::{AsRefStr, EnumIter};
+
+/// The value enum for the [`column-span`][spec] property, which makes an
+/// element in a multi-column container span across all columns.
+///
+/// # References
+/// * [CSS Multi-column Layout Module Level 1 § 6.1. `column-span`][spec]
+///
+/// [spec]: https://drafts.csswg.org/css-multicol/#column-span
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(AsRefStr, EnumIter)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CssColumnSpan {
+    /// `none`
+    #[default]
+    None,
+
+    /// `all`
+    All,
+}
@@ -0,0 +1,45 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use retina_common::StrTendril;
+
+/// The value of the [`content`][spec] property, which describes the generated
+/// content of the `::before` and `::after` pseudo-elements.
+///
+/// # References
+/// * [CSS Generated Content Module Level 3 § 1.1. `content`][spec]
+///
+/// [spec]: https://drafts.csswg.org/css-content/#content-property
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum CssContent {
+    /// `normal`, which computes to `none` for `::before` and `::after`.
+    #[default]
+    Normal,
+
+    /// `none`, the pseudo-element doesn't generate a box.
+    None,
+
+    /// A list of `<string>` and `<counter>` values.
+    Items(Vec<CssContentItem>),
+}
+
+impl CssContent {
+    /// Whether or not a `::before` or `::after` pseudo-element with this
+    /// `content` generates a box.
+    pub fn generates_box(&self) -> bool {
+        matches!(self, Self::Items(..))
+    }
+}
+
+/// A single component of the [`content`][spec] list.
+///
+/// [spec]: https://drafts.csswg.org/css-content/#typedef-content-content-list
+#[derive(Clone, Debug, PartialEq)]
+pub enum CssContentItem {
+    /// A `<string>`, rendered as is.
+    String(StrTendril),
+
+    /// A [`counter(name)`](https://drafts.csswg.org/css-lists/#funcdef-counter)
+    /// function.
+    Counter(StrTendril),
+}
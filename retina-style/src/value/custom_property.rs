@@ -0,0 +1,19 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use retina_common::StrTendril;
+
+/// A declaration of a [custom property][spec], e.g. `--accent: #0b7285`.
+///
+/// # References
+/// * [CSS Custom Properties for Cascading Variables Module Level 1 § 2. Defining Custom Properties][spec]
+///
+/// [spec]: https://drafts.csswg.org/css-variables/#defining-variables
+#[derive(Clone, Debug, PartialEq)]
+pub struct CssCustomProperty {
+    /// The name of the property, including the `--` prefix.
+    pub name: String,
+
+    /// The unparsed value, which may contain `var()` references itself.
+    pub value: StrTendril,
+}
@@ -0,0 +1,239 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! The values of the [CSS Flexible Box Layout Module][spec] and the alignment
+//! properties it uses.
+//!
+//! [spec]: https://drafts.csswg.org/css-flexbox/
+
+use strum::{AsRefStr, EnumIter};
+
+use crate::CssLength;
+
+/// The value enum for the [`flex-direction`][spec] property.
+///
+/// [spec]: https://drafts.csswg.org/css-flexbox/#flex-direction-property
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(AsRefStr, EnumIter)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CssFlexDirection {
+    /// `row`
+    #[default]
+    Row,
+
+    /// `row-reverse`
+    RowReverse,
+
+    /// `column`
+    Column,
+
+    /// `column-reverse`
+    ColumnReverse,
+}
+
+impl CssFlexDirection {
+    /// Whether or not the main axis is the horizontal axis.
+    pub fn is_row(&self) -> bool {
+        matches!(self, Self::Row | Self::RowReverse)
+    }
+
+    /// Whether or not the main-start and main-end directions are swapped.
+    pub fn is_reverse(&self) -> bool {
+        matches!(self, Self::RowReverse | Self::ColumnReverse)
+    }
+}
+
+/// The value enum for the [`flex-wrap`][spec] property.
+///
+/// [spec]: https://drafts.csswg.org/css-flexbox/#flex-wrap-property
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(AsRefStr, EnumIter)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CssFlexWrap {
+    /// `nowrap`
+    #[default]
+    Nowrap,
+
+    /// `wrap`
+    Wrap,
+
+    /// `wrap-reverse`
+    WrapReverse,
+}
+
+/// The value of the [`flex-basis`][spec] property.
+///
+/// [spec]: https://drafts.csswg.org/css-flexbox/#flex-basis-property
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum CssFlexBasis {
+    /// `auto`, which uses the main size property of the item.
+    #[default]
+    Auto,
+
+    /// `content`, which is sized based on the contents of the item.
+    Content,
+
+    /// A `<length-percentage>`.
+    Length(CssLength),
+}
+
+/// The value enum for the [`justify-content`][spec] property.
+///
+/// [spec]: https://drafts.csswg.org/css-align/#propdef-justify-content
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(AsRefStr, EnumIter)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CssJustifyContent {
+    /// `normal`, which behaves as `flex-start` for flex containers.
+    #[default]
+    Normal,
+
+    /// `flex-start`
+    FlexStart,
+
+    /// `flex-end`
+    FlexEnd,
+
+    /// `start`
+    Start,
+
+    /// `end`
+    End,
+
+    /// `center`
+    Center,
+
+    /// `space-between`
+    SpaceBetween,
+
+    /// `space-around`
+    SpaceAround,
+
+    /// `space-evenly`
+    SpaceEvenly,
+}
+
+/// The value enum for the [`align-items`][spec] property.
+///
+/// [spec]: https://drafts.csswg.org/css-align/#propdef-align-items
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(AsRefStr, EnumIter)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CssAlignItems {
+    /// `normal`, which behaves as `stretch` for flex items.
+    #[default]
+    Normal,
+
+    /// `stretch`
+    Stretch,
+
+    /// `flex-start`
+    FlexStart,
+
+    /// `flex-end`
+    FlexEnd,
+
+    /// `start`
+    Start,
+
+    /// `end`
+    End,
+
+    /// `center`
+    Center,
+
+    /// `baseline`
+    Baseline,
+}
+
+/// The value enum for the [`align-self`][spec] property.
+///
+/// [spec]: https://drafts.csswg.org/css-align/#propdef-align-self
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(AsRefStr, EnumIter)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CssAlignSelf {
+    /// `auto`, which uses the `align-items` value of the parent.
+    #[default]
+    Auto,
+
+    /// `normal`
+    Normal,
+
+    /// `stretch`
+    Stretch,
+
+    /// `flex-start`
+    FlexStart,
+
+    /// `flex-end`
+    FlexEnd,
+
+    /// `start`
+    Start,
+
+    /// `end`
+    End,
+
+    /// `center`
+    Center,
+
+    /// `baseline`
+    Baseline,
+}
+
+impl CssAlignSelf {
+    /// Resolve `auto` to the `align-items` value of the parent.
+    pub fn resolve(self, align_items: CssAlignItems) -> CssAlignItems {
+        match self {
+            Self::Auto => align_items,
+            Self::Normal => CssAlignItems::Normal,
+            Self::Stretch => CssAlignItems::Stretch,
+            Self::FlexStart => CssAlignItems::FlexStart,
+            Self::FlexEnd => CssAlignItems::FlexEnd,
+            Self::Start => CssAlignItems::Start,
+            Self::End => CssAlignItems::End,
+            Self::Center => CssAlignItems::Center,
+            Self::Baseline => CssAlignItems::Baseline,
+        }
+    }
+}
+
+/// The value enum for the [`align-content`][spec] property.
+///
+/// [spec]: https://drafts.csswg.org/css-align/#propdef-align-content
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(AsRefStr, EnumIter)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CssAlignContent {
+    /// `normal`, which behaves as `stretch` for flex containers.
+    #[default]
+    Normal,
+
+    /// `stretch`
+    Stretch,
+
+    /// `flex-start`
+    FlexStart,
+
+    /// `flex-end`
+    FlexEnd,
+
+    /// `start`
+    Start,
+
+    /// `end`
+    End,
+
+    /// `center`
+    Center,
+
+    /// `space-between`
+    SpaceBetween,
+
+    /// `space-around`
+    SpaceAround,
+
+    /// `space-evenly`
+    SpaceEvenly,
+}
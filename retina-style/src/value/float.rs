@@ -7,6 +7,7 @@ use strum::{AsRefStr, EnumIter};
 /// * [CSS 2.2 § 9.5.1](https://drafts.csswg.org/css2/#propdef-float)
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 #[derive(AsRefStr, EnumIter)]
+#[strum(serialize_all = "kebab-case")]
 pub enum CssFloatValue {
     None,
     Left,
@@ -14,3 +15,34 @@ pub enum CssFloatValue {
     InlineStart,
     InlineEnd,
 }
+
+/// The value enum for the [`clear`][spec] property, which specifies the
+/// sides of the box that may not be adjacent to an earlier float.
+///
+/// # References
+/// * [CSS 2.2 § 9.5.2 Controlling flow next to floats: the `clear` property][spec]
+///
+/// [spec]: https://drafts.csswg.org/css2/#propdef-clear
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(AsRefStr, EnumIter)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CssClear {
+    /// `none`
+    #[default]
+    None,
+
+    /// `left`
+    Left,
+
+    /// `right`
+    Right,
+
+    /// `both`
+    Both,
+
+    /// `inline-start`
+    InlineStart,
+
+    /// `inline-end`
+    InlineEnd,
+}
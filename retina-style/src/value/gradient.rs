@@ -0,0 +1,113 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use crate::{CssAngle, CssColor, CssLength};
+
+/// A [`<gradient>`][spec] image.
+///
+/// # References
+/// * [CSS Images Module Level 3 § 3. Gradients][spec]
+///
+/// [spec]: https://drafts.csswg.org/css-images-3/#gradients
+#[derive(Clone, Debug, PartialEq)]
+pub enum CssGradient {
+    /// `linear-gradient()`
+    Linear(CssLinearGradient),
+
+    /// `radial-gradient()`
+    Radial(CssRadialGradient),
+}
+
+impl CssGradient {
+    pub fn color_stops(&self) -> &[CssColorStop] {
+        match self {
+            Self::Linear(gradient) => &gradient.color_stops,
+            Self::Radial(gradient) => &gradient.color_stops,
+        }
+    }
+}
+
+/// A [`<linear-color-stop>`][spec], of which the position is [`None`] when
+/// it's omitted, in which case it's placed in between the surrounding stops.
+///
+/// [spec]: https://drafts.csswg.org/css-images-4/#typedef-linear-color-stop
+#[derive(Clone, Debug, PartialEq)]
+pub struct CssColorStop {
+    pub color: CssColor,
+    pub position: Option<CssLength>,
+}
+
+/// The [`linear-gradient()`][spec] function.
+///
+/// [spec]: https://drafts.csswg.org/css-images-3/#linear-gradients
+#[derive(Clone, Debug, PartialEq)]
+pub struct CssLinearGradient {
+    pub direction: CssLinearGradientDirection,
+    pub color_stops: Vec<CssColorStop>,
+}
+
+/// The direction of the gradient line of a `linear-gradient()`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CssLinearGradientDirection {
+    /// An `<angle>`, where `0deg` points upwards and positive angles rotate
+    /// clockwise.
+    Angle(CssAngle),
+
+    /// `to <side-or-corner>`, e.g. `to right` or `to bottom left`.
+    ToSideOrCorner {
+        horizontal: Option<CssHorizontalSide>,
+        vertical: Option<CssVerticalSide>,
+    },
+}
+
+impl Default for CssLinearGradientDirection {
+    /// The default direction is `to bottom`.
+    fn default() -> Self {
+        Self::ToSideOrCorner {
+            horizontal: None,
+            vertical: Some(CssVerticalSide::Bottom),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CssHorizontalSide {
+    Left,
+    Right,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CssVerticalSide {
+    Top,
+    Bottom,
+}
+
+/// The [`radial-gradient()`][spec] function.
+///
+/// TODO: the `<radial-size>` isn't supported, so the gradient always ends at
+///       the `farthest-corner`.
+///
+/// [spec]: https://drafts.csswg.org/css-images-3/#radial-gradients
+#[derive(Clone, Debug, PartialEq)]
+pub struct CssRadialGradient {
+    pub shape: CssRadialGradientShape,
+
+    /// The horizontal and vertical position of the center of the gradient,
+    /// relative to the top left corner of the box.
+    pub center: (CssLength, CssLength),
+
+    pub color_stops: Vec<CssColorStop>,
+}
+
+/// The [`<radial-shape>`][spec] of a `radial-gradient()`.
+///
+/// [spec]: https://drafts.csswg.org/css-images-3/#valdef-radial-gradient-radial-shape
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum CssRadialGradientShape {
+    /// `circle`
+    Circle,
+
+    /// `ellipse`
+    #[default]
+    Ellipse,
+}
@@ -0,0 +1,194 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! The values of the [CSS Grid Layout Module][spec].
+//!
+//! [spec]: https://drafts.csswg.org/css-grid/
+
+use retina_common::StrTendril;
+
+use crate::{CssDecimal, CssLength};
+
+/// The [`<track-breadth>`][spec] of a grid track.
+///
+/// [spec]: https://drafts.csswg.org/css-grid/#typedef-track-breadth
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CssGridTrackBreadth {
+    /// `auto`
+    Auto,
+
+    /// A `<flex>` value, i.e. the `fr` unit.
+    Flex(CssDecimal),
+
+    /// A `<length-percentage>`.
+    Length(CssLength),
+
+    /// `max-content`
+    MaxContent,
+
+    /// `min-content`
+    MinContent,
+}
+
+/// The [`<track-size>`][spec] of a grid track.
+///
+/// [spec]: https://drafts.csswg.org/css-grid/#typedef-track-size
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CssGridTrackSize {
+    Breadth(CssGridTrackBreadth),
+
+    /// `minmax(min, max)`
+    MinMax {
+        min: CssGridTrackBreadth,
+        max: CssGridTrackBreadth,
+    },
+}
+
+impl CssGridTrackSize {
+    /// The [min track sizing function][spec]. A `<flex>` value as the sole
+    /// breadth is treated as `auto`.
+    ///
+    /// [spec]: https://drafts.csswg.org/css-grid/#min-track-sizing-function
+    pub fn min_sizing_function(&self) -> CssGridTrackBreadth {
+        match *self {
+            Self::Breadth(CssGridTrackBreadth::Flex(..)) => CssGridTrackBreadth::Auto,
+            Self::Breadth(breadth) => breadth,
+            Self::MinMax { min, .. } => min,
+        }
+    }
+
+    /// The [max track sizing function][spec].
+    ///
+    /// [spec]: https://drafts.csswg.org/css-grid/#max-track-sizing-function
+    pub fn max_sizing_function(&self) -> CssGridTrackBreadth {
+        match *self {
+            Self::Breadth(breadth) => breadth,
+            Self::MinMax { max, .. } => max,
+        }
+    }
+}
+
+impl Default for CssGridTrackSize {
+    fn default() -> Self {
+        Self::Breadth(CssGridTrackBreadth::Auto)
+    }
+}
+
+/// The value of the [`grid-template-columns`][spec] and `grid-template-rows`
+/// properties.
+///
+/// [spec]: https://drafts.csswg.org/css-grid/#track-sizing
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum CssGridTrackList {
+    /// `none`
+    #[default]
+    None,
+
+    /// The explicit tracks, with `repeat()` already expanded.
+    Tracks(Vec<CssGridTrackSize>),
+
+    /// `subgrid`
+    Subgrid,
+}
+
+impl CssGridTrackList {
+    /// The explicit tracks, which are none for `none` and `subgrid`.
+    pub fn tracks(&self) -> &[CssGridTrackSize] {
+        match self {
+            Self::Tracks(tracks) => tracks,
+            Self::None | Self::Subgrid => &[],
+        }
+    }
+}
+
+/// The value of the [`grid-template-areas`][spec] property.
+///
+/// [spec]: https://drafts.csswg.org/css-grid/#grid-template-areas-property
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum CssGridTemplateAreas {
+    /// `none`
+    #[default]
+    None,
+
+    /// The cells per row, where `None` is a null cell token (`.`). All rows
+    /// have the same amount of columns, and every named area is a rectangle.
+    Areas(Vec<Vec<Option<StrTendril>>>),
+}
+
+impl CssGridTemplateAreas {
+    /// The number of rows and columns defined by the areas.
+    pub fn size(&self) -> (usize, usize) {
+        match self {
+            Self::None => (0, 0),
+            Self::Areas(rows) => (rows.len(), rows.first().map_or(0, Vec::len)),
+        }
+    }
+
+    /// Find the named area, if it exists.
+    pub fn find_area(&self, name: &str) -> Option<CssGridArea> {
+        let Self::Areas(rows) = self else {
+            return None;
+        };
+
+        let mut area: Option<CssGridArea> = None;
+        for (row_index, row) in rows.iter().enumerate() {
+            for (column_index, cell) in row.iter().enumerate() {
+                if cell.as_deref() != Some(name) {
+                    continue;
+                }
+
+                let area = area.get_or_insert(CssGridArea {
+                    row_start: row_index,
+                    row_end: row_index + 1,
+                    column_start: column_index,
+                    column_end: column_index + 1,
+                });
+
+                area.row_end = area.row_end.max(row_index + 1);
+                area.column_end = area.column_end.max(column_index + 1);
+            }
+        }
+
+        area
+    }
+}
+
+/// A named area of `grid-template-areas`, with zero-based track indices. The
+/// ends are exclusive.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CssGridArea {
+    pub row_start: usize,
+    pub row_end: usize,
+    pub column_start: usize,
+    pub column_end: usize,
+}
+
+/// A [`<grid-line>`][spec] value.
+///
+/// [spec]: https://drafts.csswg.org/css-grid/#typedef-grid-row-start-grid-line
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum CssGridLine {
+    /// `auto`
+    #[default]
+    Auto,
+
+    /// The `<integer>` of a line, where negative values count from the end
+    /// of the explicit grid. This is never zero.
+    Line(i32),
+
+    /// `span <integer>`
+    Span(u32),
+
+    /// A `<custom-ident>`, which refers to a named area of
+    /// `grid-template-areas`.
+    Area(StrTendril),
+}
+
+/// The value of the [`grid-column`][spec] and `grid-row` shorthands.
+///
+/// [spec]: https://drafts.csswg.org/css-grid/#placement-shorthands
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CssGridPlacement {
+    pub start: CssGridLine,
+    pub end: CssGridLine,
+}
@@ -1,7 +1,10 @@
 // Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
 // All Rights Reserved.
 
+use crate::CssGradient;
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum CssImage {
     Url(String),
+    Gradient(CssGradient),
 }
@@ -0,0 +1,32 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use strum::{AsRefStr, EnumIter};
+
+/// The value enum for the [`image-rendering`][spec] property, which hints the
+/// algorithm used for scaling images.
+///
+/// # References
+/// * [CSS Images Module Level 3 § 5.3. Determining How To Scale an Image: the `image-rendering` property][spec]
+///
+/// [spec]: https://drafts.csswg.org/css-images/#the-image-rendering
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(AsRefStr, EnumIter)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CssImageRendering {
+    /// `auto`
+    #[default]
+    Auto,
+
+    /// `smooth`
+    Smooth,
+
+    /// `high-quality`
+    HighQuality,
+
+    /// `pixelated`
+    Pixelated,
+
+    /// `crisp-edges`
+    CrispEdges,
+}
@@ -0,0 +1,272 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! Interpolation of computed values, which is used by transitions to compute
+//! the values in between the start and end values.
+//!
+//! # References
+//! * [CSS Values and Units Module Level 4 § 3. Combining Values: Interpolation, Addition, and Accumulation](https://drafts.csswg.org/css-values-4/#combining-values)
+
+use retina_common::Color;
+
+use crate::{
+    CssAngle,
+    CssCalcLength,
+    CssColor,
+    CssDecimal,
+    CssLength,
+    CssOpacity,
+    CssTransform,
+    CssTransformFunction,
+    Value,
+};
+
+/// A computed value that can be interpolated with another value of the same
+/// type.
+pub trait CssInterpolatable: Sized {
+    /// Interpolate between `self` (at a `progress` of `0.0`) and `to` (at
+    /// `1.0`). The progress can be outside of this range for easing functions
+    /// that overshoot, e.g. `cubic-bezier(0.5, 1.5, 0.5, 1.5)`.
+    ///
+    /// Returns `None` when the values can't be interpolated, e.g. `auto` and
+    /// a length, in which case the property doesn't transition.
+    fn interpolate(&self, to: &Self, progress: CssDecimal) -> Option<Self>;
+}
+
+impl CssInterpolatable for CssDecimal {
+    fn interpolate(&self, to: &Self, progress: CssDecimal) -> Option<Self> {
+        Some(self + (to - self) * progress)
+    }
+}
+
+/// Colors are interpolated with premultiplied alpha, such that e.g. the
+/// channels of `transparent` don't tint the color halfway through.
+///
+/// [spec]: https://drafts.csswg.org/css-color-4/#interpolation-alpha
+impl CssInterpolatable for Color {
+    fn interpolate(&self, to: &Self, progress: CssDecimal) -> Option<Self> {
+        let alpha = self.alpha().interpolate(&to.alpha(), progress)?.clamp(0.0, 1.0);
+        if alpha == 0.0 {
+            return Some(Color::TRANSPARENT);
+        }
+
+        let channel = |from_channel: CssDecimal, to_channel: CssDecimal| {
+            let from_premultiplied = from_channel * self.alpha();
+            let to_premultiplied = to_channel * to.alpha();
+            let premultiplied = from_premultiplied.interpolate(&to_premultiplied, progress)?;
+            Some((premultiplied / alpha).clamp(0.0, 1.0))
+        };
+
+        Some(Color::rgba(
+            channel(self.red(), to.red())?,
+            channel(self.green(), to.green())?,
+            channel(self.blue(), to.blue())?,
+            alpha,
+        ))
+    }
+}
+
+impl CssInterpolatable for CssColor {
+    fn interpolate(&self, to: &Self, progress: CssDecimal) -> Option<Self> {
+        match (self, to) {
+            (Self::Color(from), Self::Color(to)) => from.interpolate(to, progress).map(Self::Color),
+
+            // TODO: `currentcolor` should be resolved against the color of
+            //       the element, which isn't known at this point.
+            _ => None,
+        }
+    }
+}
+
+/// Lengths with different units are interpolated as a `calc()` expression,
+/// e.g. halfway between `10px` and `50%` is `calc(5px + 25%)`.
+impl CssInterpolatable for CssLength {
+    fn interpolate(&self, to: &Self, progress: CssDecimal) -> Option<Self> {
+        let from = CssCalcLength::from_length(*self)?;
+        let to = CssCalcLength::from_length(*to)?;
+
+        Some(from.scale(1.0 - progress).add(to.scale(progress)).into_length())
+    }
+}
+
+impl CssInterpolatable for CssAngle {
+    fn interpolate(&self, to: &Self, progress: CssDecimal) -> Option<Self> {
+        match (*self, *to) {
+            (Self::Degrees(from), Self::Degrees(to)) => from.interpolate(&to, progress).map(Self::Degrees),
+            (Self::Gradians(from), Self::Gradians(to)) => from.interpolate(&to, progress).map(Self::Gradians),
+            (Self::Turns(from), Self::Turns(to)) => from.interpolate(&to, progress).map(Self::Turns),
+            (from, to) => from.to_radians().interpolate(&to.to_radians(), progress).map(Self::Radians),
+        }
+    }
+}
+
+impl CssInterpolatable for CssTransformFunction {
+    fn interpolate(&self, to: &Self, progress: CssDecimal) -> Option<Self> {
+        Some(match (self, to) {
+            (Self::Matrix(from), Self::Matrix(to)) => Self::Matrix(interpolate_array(from, to, progress)?),
+            (Self::Matrix3d(from), Self::Matrix3d(to)) => Self::Matrix3d(interpolate_array(from, to, progress)?),
+            (Self::Perspective(Some(from)), Self::Perspective(Some(to))) => {
+                Self::Perspective(Some(from.interpolate(to, progress)?))
+            }
+            (Self::Perspective(None), Self::Perspective(None)) => Self::Perspective(None),
+            (Self::Rotate(from), Self::Rotate(to)) => Self::Rotate(from.interpolate(to, progress)?),
+            (Self::Scale(from_x, from_y), Self::Scale(to_x, to_y)) => Self::Scale(
+                from_x.interpolate(to_x, progress)?,
+                from_y.interpolate(to_y, progress)?,
+            ),
+            (Self::Skew(from_x, from_y), Self::Skew(to_x, to_y)) => Self::Skew(
+                from_x.interpolate(to_x, progress)?,
+                from_y.interpolate(to_y, progress)?,
+            ),
+            (Self::Translate(from_x, from_y), Self::Translate(to_x, to_y)) => Self::Translate(
+                from_x.interpolate(to_x, progress)?,
+                from_y.interpolate(to_y, progress)?,
+            ),
+            _ => return None,
+        })
+    }
+}
+
+/// Transform lists are interpolated per function when the functions of both
+/// lists have the same types, where `none` acts as a list of identity
+/// functions.
+///
+/// TODO: lists with different functions should be interpolated by
+///       decomposing their matrices.
+///
+/// # References
+/// * [CSS Transforms Module Level 1 § 9. Interpolation of Transforms](https://drafts.csswg.org/css-transforms/#interpolation-of-transforms)
+impl CssInterpolatable for CssTransform {
+    fn interpolate(&self, to: &Self, progress: CssDecimal) -> Option<Self> {
+        let (from, to) = match (self, to) {
+            (Self::None, Self::None) => return Some(Self::None),
+            (Self::None, Self::List(to)) => (identity_functions(to), to.clone()),
+            (Self::List(from), Self::None) => (from.clone(), identity_functions(from)),
+            (Self::List(from), Self::List(to)) => (from.clone(), to.clone()),
+        };
+
+        if from.len() != to.len() {
+            return None;
+        }
+
+        from.iter()
+            .zip(&to)
+            .map(|(from, to)| from.interpolate(to, progress))
+            .collect::<Option<Vec<_>>>()
+            .map(Self::List)
+    }
+}
+
+/// The [identity transform functions][spec] with the same types as the given
+/// functions.
+///
+/// [spec]: https://drafts.csswg.org/css-transforms/#identity-transform-function
+fn identity_functions(functions: &[CssTransformFunction]) -> Vec<CssTransformFunction> {
+    functions.iter()
+        .map(|function| match function {
+            CssTransformFunction::Matrix(..) => CssTransformFunction::Matrix([1.0, 0.0, 0.0, 1.0, 0.0, 0.0]),
+            CssTransformFunction::Matrix3d(..) => CssTransformFunction::Matrix3d([
+                1.0, 0.0, 0.0, 0.0,
+                0.0, 1.0, 0.0, 0.0,
+                0.0, 0.0, 1.0, 0.0,
+                0.0, 0.0, 0.0, 1.0,
+            ]),
+            CssTransformFunction::Perspective(..) => CssTransformFunction::Perspective(None),
+            CssTransformFunction::Rotate(..) => CssTransformFunction::Rotate(CssAngle::default()),
+            CssTransformFunction::Scale(..) => CssTransformFunction::Scale(1.0, 1.0),
+            CssTransformFunction::Skew(..) => CssTransformFunction::Skew(CssAngle::default(), CssAngle::default()),
+            CssTransformFunction::Translate(..) => {
+                CssTransformFunction::Translate(CssLength::Pixels(0.0), CssLength::Pixels(0.0))
+            }
+        })
+        .collect()
+}
+
+fn interpolate_array<const N: usize>(
+    from: &[CssDecimal; N],
+    to: &[CssDecimal; N],
+    progress: CssDecimal,
+) -> Option<[CssDecimal; N]> {
+    let mut result = *from;
+    for (value, to) in result.iter_mut().zip(to) {
+        *value = value.interpolate(to, progress)?;
+    }
+    Some(result)
+}
+
+impl CssInterpolatable for CssOpacity {
+    fn interpolate(&self, to: &Self, progress: CssDecimal) -> Option<Self> {
+        self.0.interpolate(&to.0, progress).map(Self)
+    }
+}
+
+impl CssInterpolatable for Value {
+    fn interpolate(&self, to: &Self, progress: CssDecimal) -> Option<Self> {
+        match (self, to) {
+            (Self::Color(from), Self::Color(to)) => from.interpolate(to, progress).map(Self::Color),
+            (Self::Length(from), Self::Length(to)) => from.interpolate(to, progress).map(Self::Length),
+            (Self::Number(from), Self::Number(to)) => from.interpolate(to, progress).map(Self::Number),
+            (Self::Opacity(from), Self::Opacity(to)) => from.interpolate(to, progress).map(Self::Opacity),
+            (Self::Transform(from), Self::Transform(to)) => from.interpolate(to, progress).map(Self::Transform),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(0.0, Color::RED)]
+    #[case(0.5, Color::rgba(1.0, 0.0, 0.0, 0.5))]
+    #[case(1.0, Color::TRANSPARENT)]
+    fn color_to_transparent_keeps_its_channels(#[case] progress: CssDecimal, #[case] expected: Color) {
+        assert_eq!(Color::RED.interpolate(&Color::TRANSPARENT, progress), Some(expected));
+    }
+
+    #[rstest]
+    #[case(0.0, CssLength::Pixels(10.0))]
+    #[case(0.5, CssLength::Calc(CssCalcLength { pixels: 5.0, percentage: 25.0, ..Default::default() }))]
+    #[case(1.0, CssLength::Percentage(50.0))]
+    fn length_with_different_units(#[case] progress: CssDecimal, #[case] expected: CssLength) {
+        let from = CssLength::Pixels(10.0);
+        let to = CssLength::Percentage(50.0);
+        assert_eq!(from.interpolate(&to, progress), Some(expected));
+    }
+
+    #[test]
+    fn length_with_auto() {
+        assert_eq!(CssLength::Auto.interpolate(&CssLength::Pixels(10.0), 0.5), None);
+    }
+
+    #[rstest]
+    #[case(
+        CssTransform::None,
+        CssTransform::List(vec![CssTransformFunction::Translate(CssLength::Pixels(10.0), CssLength::Pixels(20.0))]),
+        Some(CssTransform::List(vec![CssTransformFunction::Translate(CssLength::Pixels(5.0), CssLength::Pixels(10.0))])),
+    )]
+    #[case(
+        CssTransform::List(vec![CssTransformFunction::Scale(3.0, 5.0)]),
+        CssTransform::None,
+        Some(CssTransform::List(vec![CssTransformFunction::Scale(2.0, 3.0)])),
+    )]
+    #[case(
+        CssTransform::List(vec![CssTransformFunction::Scale(2.0, 2.0)]),
+        CssTransform::List(vec![CssTransformFunction::Rotate(CssAngle::Degrees(90.0))]),
+        None,
+    )]
+    #[case(
+        CssTransform::List(vec![CssTransformFunction::Scale(2.0, 2.0)]),
+        CssTransform::List(vec![
+            CssTransformFunction::Scale(2.0, 2.0),
+            CssTransformFunction::Rotate(CssAngle::Degrees(90.0)),
+        ]),
+        None,
+    )]
+    fn transform(#[case] from: CssTransform, #[case] to: CssTransform, #[case] expected: Option<CssTransform>) {
+        assert_eq!(from.interpolate(&to, 0.5), expected);
+    }
+}
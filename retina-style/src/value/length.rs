@@ -1,12 +1,18 @@
 // Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
 // All Rights Reserved.
 
-use crate::CssDecimal;
+use crate::{CssCalcLength, CssDecimal};
 
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
 pub enum CssLength {
     Auto,
 
+    /// A `calc()` expression combining multiple units, e.g.
+    /// `calc(100% - 2em)`.
+    ///
+    /// <https://drafts.csswg.org/css-values-4/#calc-func>
+    Calc(CssCalcLength),
+
     /// The `em` unit.
     ///
     /// <https://drafts.csswg.org/css-values-4/#em>
@@ -17,10 +23,41 @@ pub enum CssLength {
     /// <https://drafts.csswg.org/css-values-4/#rem>
     FontSizeOfRootElement(CssDecimal),
 
+    /// The `max-content` keyword, which is the size of the box when it is
+    /// given infinite available space.
+    ///
+    /// <https://drafts.csswg.org/css-sizing-3/#valdef-width-max-content>
+    MaxContent,
+
+    /// The `min-content` keyword, which is the smallest size the box can take
+    /// without overflowing its content.
+    ///
+    /// <https://drafts.csswg.org/css-sizing-3/#valdef-width-min-content>
+    MinContent,
+
     Percentage(CssDecimal),
 
     Pixels(CssDecimal),
 
+    /// The `vh` unit, and its small, large and dynamic variants (`svh`, `lvh`
+    /// and `dvh`).
+    ///
+    /// <https://drafts.csswg.org/css-values-4/#vh>
     UaDefaultViewportHeightPercentage(CssDecimal),
+
+    /// The `vmax` unit, and its small, large and dynamic variants.
+    ///
+    /// <https://drafts.csswg.org/css-values-4/#vmax>
+    UaDefaultViewportMaxPercentage(CssDecimal),
+
+    /// The `vmin` unit, and its small, large and dynamic variants.
+    ///
+    /// <https://drafts.csswg.org/css-values-4/#vmin>
+    UaDefaultViewportMinPercentage(CssDecimal),
+
+    /// The `vw` unit, and its small, large and dynamic variants (`svw`, `lvw`
+    /// and `dvw`).
+    ///
+    /// <https://drafts.csswg.org/css-values-4/#vw>
     UaDefaultViewportWidthPercentage(CssDecimal),
 }
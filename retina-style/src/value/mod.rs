@@ -1,28 +1,79 @@
 // Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
 // All Rights Reserved.
 
+pub mod angle;
+pub mod animation;
+pub mod aspect_ratio;
 pub mod background;
+pub mod calc;
 pub mod color;
+pub mod column_span;
+pub mod content;
 pub mod cursor;
+pub mod custom_property;
 pub mod display;
+pub mod flex;
 pub mod float;
 pub mod font;
+pub mod gradient;
+pub mod grid;
 pub mod image;
+pub mod image_rendering;
+pub mod interpolate;
 pub mod length;
 pub mod line_style;
+pub mod opacity;
+pub mod outline;
+pub mod overflow;
+pub mod position;
 pub mod reference_pixels;
+pub mod shadow;
+pub mod table;
 pub mod text;
 pub mod text_decoration;
+pub mod transform;
+pub mod transition;
 pub mod white_space;
+pub mod writing_mode;
+
+use retina_common::StrTendril;
 
 pub type CssDecimal = f64;
 
 pub use self::{
+    angle::CssAngle,
+    animation::{
+        CssAnimation,
+        CssAnimationDirection,
+        CssAnimationFillMode,
+        CssAnimationIterationCount,
+        CssAnimationPlayState,
+    },
+    aspect_ratio::{
+        CssAspectRatio,
+        CssRatio,
+    },
     background::{CssBackgroundRepeat, CssBackgroundRepeatStyle},
+    calc::{CalcExpression, CalcValue, CssCalcLength},
     color::{CssColor, CssNamedColor},
+    column_span::CssColumnSpan,
+    content::{CssContent, CssContentItem},
     cursor::CssCursor,
+    custom_property::CssCustomProperty,
     display::{CssDisplay, CssDisplayBox, CssDisplayInside, CssDisplayInternal, CssDisplayOutside},
-    float::CssFloatValue,
+    flex::{
+        CssAlignContent,
+        CssAlignItems,
+        CssAlignSelf,
+        CssFlexBasis,
+        CssFlexDirection,
+        CssFlexWrap,
+        CssJustifyContent,
+    },
+    float::{
+        CssClear,
+        CssFloatValue,
+    },
     font::{
         CssFontFamilyName,
         CssFontKerning,
@@ -37,17 +88,77 @@ pub use self::{
         CssFontWeight,
         CssGenericFontFamilyName,
     },
+    gradient::{
+        CssColorStop,
+        CssGradient,
+        CssHorizontalSide,
+        CssLinearGradient,
+        CssLinearGradientDirection,
+        CssRadialGradient,
+        CssRadialGradientShape,
+        CssVerticalSide,
+    },
+    grid::{
+        CssGridArea,
+        CssGridLine,
+        CssGridPlacement,
+        CssGridTemplateAreas,
+        CssGridTrackBreadth,
+        CssGridTrackList,
+        CssGridTrackSize,
+    },
     image::CssImage,
+    image_rendering::CssImageRendering,
+    interpolate::CssInterpolatable,
     length::CssLength,
     line_style::CssLineStyle,
+    opacity::CssOpacity,
+    outline::{
+        CssOutline,
+        CssOutlineStyle,
+    },
+    overflow::{
+        CssOverflow,
+        CssOverflowShorthand,
+    },
+    position::{
+        CssPosition,
+        CssZIndex,
+    },
     reference_pixels::CssReferencePixels,
-    text::CssTextTransform,
+    shadow::CssBoxShadow,
+    table::{
+        CssBorderCollapse,
+        CssBorderSpacing,
+        CssCaptionSide,
+        CssTableLayout,
+    },
+    text::{
+        CssOverflowWrap,
+        CssTextOverflow,
+        CssTextTransform,
+        CssWordBreak,
+    },
     text_decoration::{
         CssTextDecoration,
         CssTextDecorationLine,
         CssTextDecorationStyle,
     },
+    transform::{
+        CssTransform,
+        CssTransformFunction,
+    },
+    transition::{
+        CssEasingFunction,
+        CssStepPosition,
+        CssTransition,
+        CssTransitionProperty,
+    },
     white_space::CssWhiteSpace,
+    writing_mode::{
+        CssDirection,
+        CssWritingMode,
+    },
 };
 
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
@@ -59,12 +170,29 @@ pub struct CssBorderLonghand {
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Value {
+    AlignContent(CssAlignContent),
+    AlignItems(CssAlignItems),
+    AlignSelf(CssAlignSelf),
+    Animation(Vec<CssAnimation>),
+    AspectRatio(CssAspectRatio),
     BackgroundRepeat(CssBackgroundRepeat),
+    BorderCollapse(CssBorderCollapse),
     BorderLonghand(CssBorderLonghand),
+    BorderSpacing(CssBorderSpacing),
+    BoxShadow(Vec<CssBoxShadow>),
+    CaptionSide(CssCaptionSide),
+    Clear(CssClear),
     Cursor(CssCursor),
     Color(CssColor),
+    ColumnSpan(CssColumnSpan),
     ComponentList(ValueComponentList),
+    Content(CssContent),
+    CustomProperty(CssCustomProperty),
+    Direction(CssDirection),
     Display(CssDisplay),
+    FlexBasis(CssFlexBasis),
+    FlexDirection(CssFlexDirection),
+    FlexWrap(CssFlexWrap),
     Float(CssFloatValue),
     FontFamily(Vec<CssFontFamilyName>),
     FontKerning(CssFontKerning),
@@ -75,30 +203,74 @@ pub enum Value {
     FontVariantLigatures(CssFontVariantLigatures),
     FontVariantPosition(CssFontVariantPosition),
     FontWeight(CssFontWeight),
+    GridPlacement(CssGridPlacement),
+    GridTemplateAreas(CssGridTemplateAreas),
+    GridTrackList(CssGridTrackList),
     Image(CssImage),
+    ImageRendering(CssImageRendering),
+    /// An [`<integer>`](https://drafts.csswg.org/css-values-4/#integers).
+    Integer(i32),
+    JustifyContent(CssJustifyContent),
     Length(CssLength),
     LineStyle(CssLineStyle),
+    /// A [`<number>`](https://drafts.csswg.org/css-values-4/#numbers).
+    Number(CssDecimal),
+    Opacity(CssOpacity),
+    Outline(CssOutline),
+    OutlineStyle(CssOutlineStyle),
+    Overflow(CssOverflow),
+    OverflowShorthand(CssOverflowShorthand),
+    OverflowWrap(CssOverflowWrap),
+    Position(CssPosition),
+    TableLayout(CssTableLayout),
     TextDecoration(CssTextDecoration),
     TextDecorationLine(CssTextDecorationLine),
     TextDecorationStyle(CssTextDecorationStyle),
+    TextOverflow(CssTextOverflow),
     TextTransform(CssTextTransform),
+    Transform(CssTransform),
+    Transition(Vec<CssTransition>),
+
+    /// The unparsed value of a declaration containing `var()` references,
+    /// which is parsed after [substituting][spec] the custom properties.
+    ///
+    /// [spec]: https://drafts.csswg.org/css-variables/#substitute-a-var
+    VariableReference(StrTendril),
     WhiteSpace(CssWhiteSpace),
+    WordBreak(CssWordBreak),
+    WritingMode(CssWritingMode),
+    ZIndex(CssZIndex),
 }
 
 impl Value {
+    /// Expand the value of a box shorthand (e.g. `margin` or `padding`) into
+    /// its longhands, in the order `(top, right, bottom, left)`.
+    ///
+    /// # References
+    /// * [CSS Box Model Module Level 3 § 5.2. Margin Shorthand][spec]
+    ///
+    /// [spec]: https://www.w3.org/TR/css-box-3/#margin-shorthand
     pub fn into_length_percentage_longhand(self) -> Option<(CssLength, CssLength, CssLength, CssLength)> {
         match self {
+            // > If there is only one component value, it applies to all sides.
+            Self::Length(length) =>
+                Some((length, length, length, length)),
+
+            // > If there are two values, the top and bottom are set to the
+            // > first value and the right and left are set to the second.
             Self::ComponentList(ValueComponentList::TwoLengths([vertical, horizontal])) =>
-                Some((vertical, horizontal, horizontal, vertical)),
+                Some((vertical, horizontal, vertical, horizontal)),
 
+            // > If there are three values, the top is set to the first value,
+            // > the left and right are set to the second, and the bottom is
+            // > set to the third.
             Self::ComponentList(ValueComponentList::ThreeLengths([top, horizontal, bottom])) =>
-                Some((bottom, horizontal, horizontal, top)),
+                Some((top, horizontal, bottom, horizontal)),
 
+            // > If there are four values they apply to the top, right, bottom,
+            // > and left, respectively.
             Self::ComponentList(ValueComponentList::FourLengths([top, right, bottom, left])) =>
-                Some((bottom, left, right, top)),
-
-            Self::Length(length) =>
-                Some((length, length, length, length)),
+                Some((top, right, bottom, left)),
 
             _ => None
         }
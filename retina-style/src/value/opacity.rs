@@ -0,0 +1,38 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use super::CssDecimal;
+
+/// The value of the [`opacity`][spec] property, which is the alpha applied
+/// to the box and its descendants as a whole, after they're painted.
+///
+/// The specified value may be outside of the range `0.0..=1.0`, but is
+/// clamped by [`CssOpacity::computed()`].
+///
+/// # References
+/// * [CSS Color Module Level 4 § 13.2. Transparency: the `opacity` property][spec]
+///
+/// [spec]: https://drafts.csswg.org/css-color/#transparency
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CssOpacity(pub CssDecimal);
+
+impl CssOpacity {
+    /// The opacity of a box that is fully opaque, the initial value.
+    pub const OPAQUE: Self = Self(1.0);
+
+    /// > Any values outside the range 0 (fully transparent) to 1 (fully
+    /// > opaque) will be clamped to this range.
+    pub fn computed(self) -> Self {
+        Self(self.0.clamp(0.0, 1.0))
+    }
+
+    pub fn value(&self) -> CssDecimal {
+        self.0
+    }
+}
+
+impl Default for CssOpacity {
+    fn default() -> Self {
+        Self::OPAQUE
+    }
+}
@@ -0,0 +1,36 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use crate::{
+    CssColor,
+    CssLength,
+    CssLineStyle,
+};
+
+/// The [`outline`][spec] shorthand value, of which the omitted components
+/// are reset to their initial values.
+///
+/// [spec]: https://drafts.csswg.org/css-ui/#outline
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct CssOutline {
+    pub width: Option<CssLength>,
+    pub style: Option<CssOutlineStyle>,
+    pub color: Option<CssColor>,
+}
+
+/// The [`outline-style`][spec] property value, which is a line style like
+/// `border-style` (except `hidden`), or `auto`.
+///
+/// [spec]: https://drafts.csswg.org/css-ui/#outline-style
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CssOutlineStyle {
+    /// The user agent chooses the style, which is a solid line.
+    Auto,
+    LineStyle(CssLineStyle),
+}
+
+impl Default for CssOutlineStyle {
+    fn default() -> Self {
+        Self::LineStyle(CssLineStyle::None)
+    }
+}
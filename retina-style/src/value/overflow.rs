@@ -0,0 +1,55 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use strum::{AsRefStr, EnumIter};
+
+/// The value enum for the [`overflow-x`][spec] and [`overflow-y`][spec]
+/// properties, which specify what happens with content that doesn't fit in
+/// the padding box of the box.
+///
+/// # References
+/// * [CSS Overflow Module Level 3 § 3. Scrolling and Clipping Overflow: the `overflow-x`, `overflow-y`, and `overflow` properties][spec]
+///
+/// [spec]: https://drafts.csswg.org/css-overflow/#overflow-properties
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(AsRefStr, EnumIter)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CssOverflow {
+    /// `visible`
+    #[default]
+    Visible,
+
+    /// `hidden`
+    Hidden,
+
+    /// `clip`
+    Clip,
+
+    /// `scroll`
+    Scroll,
+
+    /// `auto`
+    Auto,
+}
+
+impl CssOverflow {
+    /// Whether or not the content overflowing the padding box is clipped.
+    pub fn clips(&self) -> bool {
+        !matches!(self, Self::Visible)
+    }
+
+    /// Whether or not the user can scroll the overflowing content, for
+    /// example using the mouse wheel.
+    pub fn is_user_scrollable(&self) -> bool {
+        matches!(self, Self::Scroll | Self::Auto)
+    }
+}
+
+/// The value of the [`overflow`][spec] shorthand.
+///
+/// [spec]: https://drafts.csswg.org/css-overflow/#propdef-overflow
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CssOverflowShorthand {
+    pub x: CssOverflow,
+    pub y: CssOverflow,
+}
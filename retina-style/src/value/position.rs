@@ -0,0 +1,59 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use strum::{AsRefStr, EnumIter};
+
+/// The value enum for the [`position`][spec] property, which determines the
+/// positioning scheme used to lay out a box.
+///
+/// # References
+/// * [CSS Positioned Layout Module Level 3 § 2. Choosing A Positioning Scheme: `position` property][spec]
+///
+/// [spec]: https://drafts.csswg.org/css-position/#position-property
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(AsRefStr, EnumIter)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CssPosition {
+    /// `static`
+    #[default]
+    Static,
+
+    /// `relative`
+    Relative,
+
+    /// `sticky`
+    Sticky,
+
+    /// `absolute`
+    Absolute,
+
+    /// `fixed`
+    Fixed,
+}
+
+impl CssPosition {
+    /// Whether or not the box is taken out of the normal flow.
+    ///
+    /// [spec]: https://drafts.csswg.org/css-position/#absolute-position
+    pub fn is_absolutely_positioned(&self) -> bool {
+        matches!(self, Self::Absolute | Self::Fixed)
+    }
+}
+
+/// The value enum for the [`z-index`][spec] property, which specifies the
+/// stack level of a positioned box, and whether it establishes a local
+/// stacking context.
+///
+/// # References
+/// * [CSS 2.2 § 9.9.1 Specifying the stack level: the `z-index` property][spec]
+///
+/// [spec]: https://www.w3.org/TR/CSS22/visuren.html#z-index
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum CssZIndex {
+    /// `auto`
+    #[default]
+    Auto,
+
+    /// An `<integer>`
+    Integer(i32),
+}
@@ -0,0 +1,44 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use crate::{CssColor, CssLength};
+
+/// A single shadow of the [`box-shadow`][spec] property, which is a list of
+/// these, or an empty list for `none`.
+///
+/// # References
+/// * [CSS Backgrounds and Borders Module Level 3 § 7.1. Drop Shadows: the `box-shadow` property][spec]
+///
+/// [spec]: https://drafts.csswg.org/css-backgrounds/#box-shadow
+#[derive(Clone, Debug, PartialEq)]
+pub struct CssBoxShadow {
+    /// The color of the shadow, which is `currentcolor` when omitted.
+    pub color: CssColor,
+
+    pub offset_x: CssLength,
+    pub offset_y: CssLength,
+
+    /// The blur radius, which can't be negative.
+    pub blur_radius: CssLength,
+
+    /// How much the shadow is expanded, or shrunk when negative, before the
+    /// blur is applied.
+    pub spread_radius: CssLength,
+
+    /// Whether or not the shadow is drawn inside of the padding box, instead
+    /// of outside of the border box.
+    pub inset: bool,
+}
+
+impl Default for CssBoxShadow {
+    fn default() -> Self {
+        Self {
+            color: CssColor::CurrentColor,
+            offset_x: CssLength::Pixels(0.0),
+            offset_y: CssLength::Pixels(0.0),
+            blur_radius: CssLength::Pixels(0.0),
+            spread_radius: CssLength::Pixels(0.0),
+            inset: false,
+        }
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use strum::{AsRefStr, EnumIter};
+
+use crate::CssLength;
+
+/// The value enum for the [`border-collapse`][spec] property, which selects
+/// the border model of a table.
+///
+/// # References
+/// * [CSS 2.2 § 17.6 Borders](https://drafts.csswg.org/css2/#borders)
+///
+/// [spec]: https://drafts.csswg.org/css2/#propdef-border-collapse
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(AsRefStr, EnumIter)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CssBorderCollapse {
+    /// `separate`, where each cell has its own borders, separated by the
+    /// `border-spacing`.
+    #[default]
+    Separate,
+
+    /// `collapse`, where adjacent cells share their borders.
+    Collapse,
+}
+
+/// The value of the [`border-spacing`][spec] property, which is the distance
+/// between the borders of adjacent cells in the separated border model.
+///
+/// [spec]: https://drafts.csswg.org/css2/#propdef-border-spacing
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CssBorderSpacing {
+    pub horizontal: CssLength,
+    pub vertical: CssLength,
+}
+
+/// The value enum for the [`caption-side`][spec] property.
+///
+/// [spec]: https://drafts.csswg.org/css2/#propdef-caption-side
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(AsRefStr, EnumIter)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CssCaptionSide {
+    /// `top`
+    #[default]
+    Top,
+
+    /// `bottom`
+    Bottom,
+}
+
+/// The value enum for the [`table-layout`][spec] property, which selects the
+/// algorithm used for the widths of the columns.
+///
+/// [spec]: https://drafts.csswg.org/css2/#propdef-table-layout
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(AsRefStr, EnumIter)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CssTableLayout {
+    /// `auto`, where the widths depend on the contents of all the cells.
+    #[default]
+    Auto,
+
+    /// `fixed`, where the widths only depend on the first row.
+    Fixed,
+}
@@ -6,6 +6,34 @@ use strum::{
     EnumIter,
 };
 
+/// The [`overflow-wrap`][spec] property value, which allows breaking within
+/// words that would otherwise overflow the line.
+///
+/// [spec]: https://drafts.csswg.org/css-text/#overflow-wrap-property
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(AsRefStr, EnumIter)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CssOverflowWrap {
+    #[default]
+    Normal,
+
+    BreakWord,
+    Anywhere,
+}
+
+/// The [`text-overflow`][spec] property value.
+///
+/// [spec]: https://drafts.csswg.org/css-overflow/#text-overflow
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(AsRefStr, EnumIter)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CssTextOverflow {
+    #[default]
+    Clip,
+
+    Ellipsis,
+}
+
 /// The [`text-transform`][spec] property value.
 ///
 /// [spec]: https://drafts.csswg.org/css-text-4/#text-transform-property
@@ -20,3 +48,21 @@ pub enum CssTextTransform {
     Uppercase,
     Lowercase,
 }
+
+/// The [`word-break`][spec] property value.
+///
+/// [spec]: https://drafts.csswg.org/css-text/#word-break-property
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(AsRefStr, EnumIter)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CssWordBreak {
+    #[default]
+    Normal,
+
+    BreakAll,
+    KeepAll,
+
+    /// The deprecated `break-word`, which has the same effect as
+    /// `word-break: normal` and `overflow-wrap: anywhere`.
+    BreakWord,
+}
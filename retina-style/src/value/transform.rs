@@ -0,0 +1,50 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use super::{CssAngle, CssDecimal, CssLength};
+
+/// The value of the [`transform`][spec] property.
+///
+/// # References
+/// * [CSS Transforms Module Level 1 § 5. The `transform` Property][spec]
+///
+/// [spec]: https://drafts.csswg.org/css-transforms/#transform-property
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum CssTransform {
+    /// `none`
+    #[default]
+    None,
+
+    /// A `<transform-list>`, of which the functions are applied from left to
+    /// right, i.e. the matrices are multiplied in that order.
+    List(Vec<CssTransformFunction>),
+}
+
+/// A [`<transform-function>`][spec]. The single-axis functions like
+/// `translateX()` are stored as their two-axis equivalents.
+///
+/// [spec]: https://drafts.csswg.org/css-transforms/#transform-functions
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CssTransformFunction {
+    /// `matrix(a, b, c, d, e, f)`
+    Matrix([CssDecimal; 6]),
+
+    /// `matrix3d()`, with the 16 values in column-major order.
+    Matrix3d([CssDecimal; 16]),
+
+    /// `perspective()`, where `None` is the `none` keyword, i.e. an infinite
+    /// perspective.
+    Perspective(Option<CssLength>),
+
+    /// `rotate()` and `rotateZ()`
+    Rotate(CssAngle),
+
+    /// `scale()`, `scaleX()` and `scaleY()`
+    Scale(CssDecimal, CssDecimal),
+
+    /// `skew()`, `skewX()` and `skewY()`
+    Skew(CssAngle, CssAngle),
+
+    /// `translate()`, `translateX()` and `translateY()`
+    Translate(CssLength, CssLength),
+}
@@ -0,0 +1,186 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use std::time::Duration;
+
+use strum::{AsRefStr, EnumIter};
+
+use crate::{CssDecimal, Property};
+
+/// A single item of the [`transition`][spec] shorthand.
+///
+/// # References
+/// * [CSS Transitions § 2.5. The `transition` Shorthand Property][spec]
+///
+/// [spec]: https://drafts.csswg.org/css-transitions/#transition-shorthand-property
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CssTransition {
+    pub property: CssTransitionProperty,
+    pub duration: Duration,
+    pub timing_function: CssEasingFunction,
+    pub delay: Duration,
+}
+
+impl Default for CssTransition {
+    fn default() -> Self {
+        Self {
+            property: CssTransitionProperty::All,
+            duration: Duration::ZERO,
+            timing_function: CssEasingFunction::default(),
+            delay: Duration::ZERO,
+        }
+    }
+}
+
+/// The value of the [`transition-property`][spec] property.
+///
+/// [spec]: https://drafts.csswg.org/css-transitions/#transition-property-property
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CssTransitionProperty {
+    /// `all`
+    All,
+
+    /// `none`
+    None,
+
+    /// A specific property, e.g. `color`.
+    Property(Property),
+}
+
+/// An [easing function][spec], used by e.g. `transition-timing-function`.
+///
+/// # References
+/// * [CSS Easing Functions Level 1][spec]
+///
+/// [spec]: https://drafts.csswg.org/css-easing/#easing-functions
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum CssEasingFunction {
+    /// `linear`
+    Linear,
+
+    /// `ease`, equivalent to `cubic-bezier(0.25, 0.1, 0.25, 1)`.
+    #[default]
+    Ease,
+
+    /// `ease-in`, equivalent to `cubic-bezier(0.42, 0, 1, 1)`.
+    EaseIn,
+
+    /// `ease-out`, equivalent to `cubic-bezier(0, 0, 0.58, 1)`.
+    EaseOut,
+
+    /// `ease-in-out`, equivalent to `cubic-bezier(0.42, 0, 0.58, 1)`.
+    EaseInOut,
+
+    /// `cubic-bezier(x1, y1, x2, y2)`
+    CubicBezier(CssDecimal, CssDecimal, CssDecimal, CssDecimal),
+
+    /// `steps(count, position)`
+    Steps(u32, CssStepPosition),
+}
+
+impl CssEasingFunction {
+    /// Compute the output progress for the given input progress, which is
+    /// between `0.0` and `1.0` inclusive. The output can be outside of that
+    /// range for `cubic-bezier()` functions that overshoot.
+    ///
+    /// # References
+    /// * [CSS Easing Functions Level 1 § 2.2. Cubic Bézier Easing Functions](https://drafts.csswg.org/css-easing/#cubic-bezier-easing-functions)
+    /// * [CSS Easing Functions Level 1 § 2.3. Step Easing Functions](https://drafts.csswg.org/css-easing/#step-easing-functions)
+    pub fn evaluate(&self, progress: CssDecimal) -> CssDecimal {
+        match *self {
+            Self::Linear => progress,
+            Self::Ease => cubic_bezier([0.25, 0.1, 0.25, 1.0], progress),
+            Self::EaseIn => cubic_bezier([0.42, 0.0, 1.0, 1.0], progress),
+            Self::EaseOut => cubic_bezier([0.0, 0.0, 0.58, 1.0], progress),
+            Self::EaseInOut => cubic_bezier([0.42, 0.0, 0.58, 1.0], progress),
+            Self::CubicBezier(x1, y1, x2, y2) => cubic_bezier([x1, y1, x2, y2], progress),
+            Self::Steps(count, position) => steps(count, position, progress),
+        }
+    }
+}
+
+/// Evaluate the cubic Bézier curve from `(0, 0)` to `(1, 1)` with the control
+/// points `(x1, y1)` and `(x2, y2)`, by finding the parameter `t` of which the
+/// x-coordinate is the input `x`.
+fn cubic_bezier([x1, y1, x2, y2]: [CssDecimal; 4], x: CssDecimal) -> CssDecimal {
+    const EPSILON: CssDecimal = 1e-7;
+
+    if x <= 0.0 || x >= 1.0 {
+        return x;
+    }
+
+    let sample = |p1: CssDecimal, p2: CssDecimal, t: CssDecimal| {
+        let u = 1.0 - t;
+        3.0 * u * u * t * p1 + 3.0 * u * t * t * p2 + t * t * t
+    };
+
+    let sample_derivative = |p1: CssDecimal, p2: CssDecimal, t: CssDecimal| {
+        let u = 1.0 - t;
+        3.0 * u * u * p1 + 6.0 * u * t * (p2 - p1) + 3.0 * t * t * (1.0 - p2)
+    };
+
+    // Newton's method converges quickly for most curves...
+    let mut t = x;
+    for _ in 0..8 {
+        let error = sample(x1, x2, t) - x;
+        if error.abs() < EPSILON {
+            return sample(y1, y2, t);
+        }
+
+        let derivative = sample_derivative(x1, x2, t);
+        if derivative.abs() < EPSILON {
+            break;
+        }
+
+        t -= error / derivative;
+    }
+
+    // ...but falls back to bisection for the ones with flat sections, since
+    // x(t) is monotonic for control points with x-coordinates within [0, 1].
+    let (mut low, mut high) = (0.0, 1.0);
+    t = x;
+    while high - low > EPSILON {
+        if sample(x1, x2, t) < x {
+            low = t;
+        } else {
+            high = t;
+        }
+        t = (low + high) / 2.0;
+    }
+
+    sample(y1, y2, t)
+}
+
+fn steps(count: u32, position: CssStepPosition, progress: CssDecimal) -> CssDecimal {
+    let count = count.max(1) as CssDecimal;
+
+    // > 1. Calculate the current step as floor(input progress value × steps).
+    let mut step = (progress * count).floor();
+
+    // > 2. If the step position property is one of: jump-start, jump-both,
+    // >    increment current step by one.
+    if position == CssStepPosition::Start {
+        step += 1.0;
+    }
+
+    // > 5. If input progress value ≥ 0 and current step < 0, let current
+    // >    step be zero.
+    // > 7. If input progress value ≤ 1 and current step > jumps, let current
+    // >    step be jumps.
+    step.clamp(0.0, count) / count
+}
+
+/// The [`<step-position>`][spec] of the `steps()` easing function.
+///
+/// [spec]: https://drafts.csswg.org/css-easing/#typedef-step-position
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(AsRefStr, EnumIter)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CssStepPosition {
+    /// `start`
+    Start,
+
+    /// `end`
+    #[default]
+    End,
+}
@@ -42,4 +42,14 @@ impl CssWhiteSpace {
     pub const fn collapses(&self) -> bool {
         matches!(self, Self::Normal | Self::Nowrap | Self::PreLine)
     }
+
+    /// Determines whether lines may wrap at soft wrap opportunities.
+    ///
+    /// # References
+    /// * [CSS Text Module Level 3 § 3. White Space and Wrapping: the `white-space` property][spec]
+    ///
+    /// [spec]: https://drafts.csswg.org/css-text/#white-space-property
+    pub const fn wraps(&self) -> bool {
+        !matches!(self, Self::Nowrap | Self::Pre)
+    }
 }
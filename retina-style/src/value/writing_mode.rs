@@ -0,0 +1,56 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use strum::{AsRefStr, EnumIter};
+
+/// The value enum for the [`direction`][spec] property, which specifies the
+/// inline base direction of the box.
+///
+/// # References
+/// * [CSS Writing Modes Level 3 § 2.1. Specifying Directionality: the `direction` property][spec]
+///
+/// [spec]: https://drafts.csswg.org/css-writing-modes/#direction
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(AsRefStr, EnumIter)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CssDirection {
+    /// `ltr`: left-to-right
+    #[default]
+    Ltr,
+
+    /// `rtl`: right-to-left
+    Rtl,
+}
+
+/// The value enum for the [`writing-mode`][spec] property, which specifies
+/// whether lines are laid out horizontally or vertically, and the direction
+/// in which the blocks progress.
+///
+/// # References
+/// * [CSS Writing Modes Level 3 § 3.1. Block Flow Direction: the `writing-mode` property][spec]
+///
+/// [spec]: https://drafts.csswg.org/css-writing-modes/#block-flow
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(AsRefStr, EnumIter)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CssWritingMode {
+    /// `horizontal-tb`: the lines are horizontal, and progress from top to
+    /// bottom.
+    #[default]
+    HorizontalTb,
+
+    /// `vertical-rl`: the lines are vertical, and progress from right to
+    /// left, as is traditional for CJK text.
+    VerticalRl,
+
+    /// `vertical-lr`: the lines are vertical, and progress from left to
+    /// right.
+    VerticalLr,
+}
+
+impl CssWritingMode {
+    /// Whether or not the inline axis is vertical.
+    pub const fn is_vertical(&self) -> bool {
+        matches!(self, Self::VerticalRl | Self::VerticalLr)
+    }
+}
@@ -0,0 +1,396 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! Clipping using the `<clipPath>` element.
+//!
+//! ## References
+//! * [SVG 1.1 § 14.3 Clipping paths](https://www.w3.org/TR/SVG11/masking.html#ClippingPaths)
+
+use euclid::default::Transform2D;
+use log::warn;
+use retina_dom::Element;
+
+use crate::{
+    path::{
+        SvgPathCoordinatePair,
+        SvgPathEllipticArcArgument,
+        SvgPathType,
+    },
+    GeometrySink,
+    GeometrySinkFillType,
+    SvgElementTraits,
+    SvgRenderer,
+};
+
+impl<'painter> SvgRenderer<'painter> {
+    /// Push the clipping region of the `clip-path` property of the `element`,
+    /// returning whether a region was pushed, in which case the caller must
+    /// pop it after rendering the element.
+    pub(crate) fn push_clip_path(&mut self, element: &Element) -> bool {
+        let value = element.str_property("clip-path").trim();
+        if value.is_empty() || value.eq_ignore_ascii_case("none") {
+            return false;
+        }
+
+        let Some(reference) = value.strip_prefix("url(").and_then(|value| value.strip_suffix(')')) else {
+            warn!("Unsupported clip-path value: {value}");
+            return false;
+        };
+
+        let reference = reference.trim().trim_matches(['"', '\'']);
+        let clip_path = reference.strip_prefix('#')
+            .and_then(|id| element.find_referenced_element(id))
+            .filter(|node| node.as_dom_element().is_some_and(|element| element.qualified_name().local.as_ref() == "clipPath"));
+
+        // A reference to a non-existent object is an error, which we treat as
+        // if no clipping path was specified.
+        let Some(clip_path) = clip_path else {
+            warn!("clip-path references an unknown <clipPath>: {reference}");
+            return false;
+        };
+        let Some(clip_path) = clip_path.as_dom_element() else { return false };
+
+        // The clipping region is the union of the silhouettes of the children
+        // of the <clipPath> element. An empty region clips away everything.
+        let mut sink = self.painter.create_geometry(GeometrySinkFillType::Filled);
+        for child in clip_path.as_parent_node().children().iter() {
+            let Some(child) = child.as_dom_element() else { continue };
+
+            if !child.str_property("transform").trim().is_empty() {
+                warn!("The transform of a <{}> inside a <clipPath> isn't supported", child.qualified_name().local);
+            }
+
+            if !add_shape_to_sink(sink.as_mut(), child) {
+                warn!("Unsupported element inside <clipPath>: <{}>", child.qualified_name().local);
+            }
+        }
+        let geometry = sink.finish();
+
+        let mut transform = clip_path.property_transform();
+
+        // The coordinates of the children are fractions of the bounding box
+        // of the element that references the <clipPath>.
+        if clip_path.str_property("clipPathUnits").trim() == "objectBoundingBox" {
+            let mut sink = self.painter.create_geometry(GeometrySinkFillType::Filled);
+            if !add_shape_to_sink(sink.as_mut(), element) {
+                warn!("clipPathUnits=\"objectBoundingBox\" is only supported for shapes, not <{}>", element.qualified_name().local);
+                return false;
+            }
+
+            let bounds = sink.finish().bounds();
+            transform = transform.then(
+                &Transform2D::scale(bounds.width(), bounds.height())
+                    .then_translate(bounds.origin.to_vector())
+            );
+        }
+
+        // The clipping geometry is in the user space at the moment it's
+        // pushed, so the transform only has to be applied while pushing.
+        let has_transform = transform != Transform2D::identity();
+        if has_transform {
+            self.painter.push_transform(transform);
+        }
+
+        self.painter.push_clip_geometry(geometry.as_ref());
+
+        if has_transform {
+            self.painter.pop_transform();
+        }
+
+        true
+    }
+}
+
+/// Add the geometry of the basic shape or path `element` to the `sink`,
+/// returning `false` if the element isn't a shape.
+fn add_shape_to_sink(sink: &mut dyn GeometrySink, element: &Element) -> bool {
+    match element.qualified_name().local.as_ref() {
+        "circle" => {
+            let radius = element.property_r();
+            if radius > 0.0 {
+                let center = element.properties_circle_center();
+                add_ellipse_to_sink(sink, center.x, center.y, radius, radius);
+            }
+        }
+
+        "ellipse" => {
+            let radius = element.properties_radii();
+            if radius.x > 0.0 && radius.y > 0.0 {
+                let center = element.properties_circle_center();
+                add_ellipse_to_sink(sink, center.x, center.y, radius.x, radius.y);
+            }
+        }
+
+        // Lines have no interior, and thus don't contribute to the region.
+        "line" => (),
+
+        "path" => {
+            let Some(path_data) = element.attributes().find_by_str("d") else {
+                return true;
+            };
+
+            match crate::path::parse_path(path_data) {
+                Ok((_, path)) => crate::add_path_to_sink(sink, path),
+                Err(e) => warn!("Failed to parse path inside <clipPath>: {e}"),
+            }
+        }
+
+        "polygon" | "polyline" => {
+            let points = element.property_points();
+            let Some((first, rest)) = points.split_first() else {
+                return true;
+            };
+
+            sink.move_to(SvgPathType::Absolute, coordinate(first.x, first.y));
+            for point in rest {
+                sink.line_to(SvgPathType::Absolute, coordinate(point.x, point.y));
+            }
+            sink.close_path();
+        }
+
+        "rect" => add_rect_to_sink(sink, element),
+
+        _ => return false,
+    }
+
+    true
+}
+
+fn add_ellipse_to_sink(sink: &mut dyn GeometrySink, cx: f32, cy: f32, rx: f32, ry: f32) {
+    let arc = |x, y| SvgPathEllipticArcArgument {
+        rx: rx as _,
+        ry: ry as _,
+        x_axis_rotation: 0.0,
+        large_arc_flag: false,
+        sweep_flag: true,
+        coords: coordinate(x, y),
+    };
+
+    sink.move_to(SvgPathType::Absolute, coordinate(cx + rx, cy));
+    sink.elliptic_arc(SvgPathType::Absolute, arc(cx - rx, cy));
+    sink.elliptic_arc(SvgPathType::Absolute, arc(cx + rx, cy));
+    sink.close_path();
+}
+
+/// <https://www.w3.org/TR/SVG11/shapes.html#RectElement>
+fn add_rect_to_sink(sink: &mut dyn GeometrySink, element: &Element) {
+    let x = element.property_x();
+    let y = element.property_y();
+    let width = element.property_width();
+    let height = element.property_height();
+    if width <= 0.0 || height <= 0.0 {
+        return;
+    }
+
+    // If only one of the radii is specified, the other one is the same, and
+    // both are at most half of the width or height respectively.
+    let rx = element.optional_length_property("rx");
+    let ry = element.optional_length_property("ry");
+    let rx = rx.or(ry).unwrap_or_default().clamp(0.0, width / 2.0);
+    let ry = ry.or(Some(rx)).unwrap_or_default().clamp(0.0, height / 2.0);
+
+    if rx == 0.0 || ry == 0.0 {
+        sink.move_to(SvgPathType::Absolute, coordinate(x, y));
+        sink.line_to(SvgPathType::Absolute, coordinate(x + width, y));
+        sink.line_to(SvgPathType::Absolute, coordinate(x + width, y + height));
+        sink.line_to(SvgPathType::Absolute, coordinate(x, y + height));
+        sink.close_path();
+        return;
+    }
+
+    let arc = |x, y| SvgPathEllipticArcArgument {
+        rx: rx as _,
+        ry: ry as _,
+        x_axis_rotation: 0.0,
+        large_arc_flag: false,
+        sweep_flag: true,
+        coords: coordinate(x, y),
+    };
+
+    sink.move_to(SvgPathType::Absolute, coordinate(x + rx, y));
+    sink.line_to(SvgPathType::Absolute, coordinate(x + width - rx, y));
+    sink.elliptic_arc(SvgPathType::Absolute, arc(x + width, y + ry));
+    sink.line_to(SvgPathType::Absolute, coordinate(x + width, y + height - ry));
+    sink.elliptic_arc(SvgPathType::Absolute, arc(x + width - rx, y + height));
+    sink.line_to(SvgPathType::Absolute, coordinate(x + rx, y + height));
+    sink.elliptic_arc(SvgPathType::Absolute, arc(x, y + height - ry));
+    sink.line_to(SvgPathType::Absolute, coordinate(x, y + ry));
+    sink.elliptic_arc(SvgPathType::Absolute, arc(x + rx, y));
+    sink.close_path();
+}
+
+#[inline]
+fn coordinate(x: f32, y: f32) -> SvgPathCoordinatePair {
+    SvgPathCoordinatePair {
+        x: x as _,
+        y: y as _,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use euclid::default::Point2D;
+    use pretty_assertions::assert_eq;
+    use retina_common::Color;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::{
+        test_util::{render, PaintCommand, PathCommand},
+        Material,
+    };
+
+    fn point(x: f32, y: f32) -> Point2D<f32> {
+        Point2D::new(x, y)
+    }
+
+    /// The `<circle>` that is clipped in the tests.
+    const CIRCLE: &str = r#"<circle cx="5" cy="5" r="5" clip-path="url(#c)"/>"#;
+
+    fn circle() -> PaintCommand {
+        PaintCommand::Ellipse {
+            center: point(5.0, 5.0),
+            radius: point(5.0, 5.0),
+            material: Material::Color(Color::BLACK),
+        }
+    }
+
+    /// Get the path of the clipping region of the circle, given the children
+    /// of the `<clipPath>`.
+    fn clip_region(children: &str) -> Vec<PathCommand> {
+        let commands = render(&format!(r#"<svg><clipPath id="c">{children}</clipPath>{CIRCLE}</svg>"#));
+        match &commands[..] {
+            [PaintCommand::PushClip(path), command, PaintCommand::PopClip] if *command == circle() => path.clone(),
+            _ => panic!("unexpected paint commands: {commands:#?}"),
+        }
+    }
+
+    #[test]
+    fn rect() {
+        assert_eq!(clip_region(r#"<rect x="10" y="20" width="30" height="40"/>"#), [
+            PathCommand::MoveTo(point(10.0, 20.0)),
+            PathCommand::LineTo(point(40.0, 20.0)),
+            PathCommand::LineTo(point(40.0, 60.0)),
+            PathCommand::LineTo(point(10.0, 60.0)),
+            PathCommand::Close,
+        ]);
+    }
+
+    #[test]
+    fn rounded_rect() {
+        // The radius is at most half of the height of the rect.
+        let path = clip_region(r#"<rect width="20" height="10" rx="8"/>"#);
+        assert_eq!(path[..3], [
+            PathCommand::MoveTo(point(8.0, 0.0)),
+            PathCommand::LineTo(point(12.0, 0.0)),
+            PathCommand::ArcTo { radius: point(8.0, 5.0), end: point(20.0, 5.0) },
+        ]);
+        assert_eq!(path.len(), 10);
+    }
+
+    #[test]
+    fn circle_and_ellipse() {
+        assert_eq!(clip_region(r#"<circle cx="10" cy="20" r="5"/><ellipse cx="10" cy="20" rx="3" ry="4"/>"#), [
+            PathCommand::MoveTo(point(15.0, 20.0)),
+            PathCommand::ArcTo { radius: point(5.0, 5.0), end: point(5.0, 20.0) },
+            PathCommand::ArcTo { radius: point(5.0, 5.0), end: point(15.0, 20.0) },
+            PathCommand::Close,
+            PathCommand::MoveTo(point(13.0, 20.0)),
+            PathCommand::ArcTo { radius: point(3.0, 4.0), end: point(7.0, 20.0) },
+            PathCommand::ArcTo { radius: point(3.0, 4.0), end: point(13.0, 20.0) },
+            PathCommand::Close,
+        ]);
+    }
+
+    #[test]
+    fn path_and_polygon() {
+        assert_eq!(clip_region(r#"<path d="M0 0 h10 v10 Z"/><polygon points="20,0 30,0 30,10"/>"#), [
+            PathCommand::MoveTo(point(0.0, 0.0)),
+            PathCommand::LineTo(point(10.0, 0.0)),
+            PathCommand::LineTo(point(10.0, 10.0)),
+            PathCommand::Close,
+            PathCommand::MoveTo(point(20.0, 0.0)),
+            PathCommand::LineTo(point(30.0, 0.0)),
+            PathCommand::LineTo(point(30.0, 10.0)),
+            PathCommand::Close,
+        ]);
+    }
+
+    #[rstest]
+    #[case::empty("")]
+    #[case::line(r#"<line x2="10" y2="10"/>"#)]
+    #[case::unsupported_element("<text>Hello</text>")]
+    #[case::rect_without_area(r#"<rect width="10"/>"#)]
+    fn empty_region(#[case] children: &str) {
+        // An empty clipping region clips away everything.
+        assert!(clip_region(children).is_empty());
+    }
+
+    #[rstest]
+    #[case(r#"clip-path="none""#)]
+    #[case(r#"clip-path="url(#unknown)""#)]
+    #[case(r#"clip-path="url(#r)""#)]
+    #[case(r#"clip-path="inset(10px)""#)]
+    fn without_clipping(#[case] clip_path: &str) {
+        let html = format!(r#"<svg><clipPath id="c"><rect width="1" height="1"/></clipPath><defs><rect id="r"/></defs><circle cx="5" cy="5" r="5" {clip_path}/></svg>"#);
+        assert_eq!(render(&html), [circle()]);
+    }
+
+    #[test]
+    fn transform_of_element() {
+        // The clipping path is in the user space of the element.
+        let commands = render(r#"<svg><clipPath id="c"><rect width="1" height="1"/></clipPath><circle cx="5" cy="5" r="5" transform="translate(10, 0)" clip-path="url(#c)"/></svg>"#);
+        assert!(matches!(
+            &commands[..],
+            [
+                PaintCommand::PushTransform(transform),
+                PaintCommand::PushClip(..),
+                PaintCommand::Ellipse { .. },
+                PaintCommand::PopClip,
+                PaintCommand::PopTransform,
+            ] if *transform == Transform2D::translation(10.0, 0.0)
+        ), "unexpected paint commands: {commands:#?}");
+    }
+
+    #[test]
+    fn transform_of_clip_path() {
+        let commands = render(&format!(r#"<svg><clipPath id="c" transform="scale(2, 2)"><rect width="1" height="1"/></clipPath>{CIRCLE}</svg>"#));
+        assert!(matches!(
+            &commands[..],
+            [
+                PaintCommand::PushTransform(transform),
+                PaintCommand::PushClip(..),
+                PaintCommand::PopTransform,
+                PaintCommand::Ellipse { .. },
+                PaintCommand::PopClip,
+            ] if *transform == Transform2D::scale(2.0, 2.0)
+        ), "unexpected paint commands: {commands:#?}");
+    }
+
+    #[test]
+    fn object_bounding_box_units() {
+        // The clipping path covers the left half of the bounding box of the
+        // polygon.
+        let commands = render(r#"<svg><clipPath id="c" clipPathUnits="objectBoundingBox"><rect width="0.5" height="1"/></clipPath><polygon points="10,20 110,20 110,70" clip-path="url(#c)"/></svg>"#);
+        assert_eq!(commands[..3], [
+            PaintCommand::PushTransform(Transform2D::scale(100.0, 50.0).then_translate(euclid::vec2(10.0, 20.0))),
+            PaintCommand::PushClip(vec![
+                PathCommand::MoveTo(point(0.0, 0.0)),
+                PathCommand::LineTo(point(0.5, 0.0)),
+                PathCommand::LineTo(point(0.5, 1.0)),
+                PathCommand::LineTo(point(0.0, 1.0)),
+                PathCommand::Close,
+            ]),
+            PaintCommand::PopTransform,
+        ]);
+        assert!(matches!(&commands[3..], [PaintCommand::Geometry { .. }, PaintCommand::PopClip]));
+    }
+
+    #[test]
+    fn object_bounding_box_units_of_non_shape() {
+        // Only the bounding box of shapes is supported, so a <g> isn't
+        // clipped.
+        let commands = render(r#"<svg><clipPath id="c" clipPathUnits="objectBoundingBox"><rect width="0.5" height="1"/></clipPath><g clip-path="url(#c)"><circle cx="5" cy="5" r="5"/></g></svg>"#);
+        assert_eq!(commands, [circle()]);
+    }
+}
@@ -2,7 +2,9 @@
 // All Rights Reserved.
 
 use raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
+use retina_gfx_font::{FamilyName, FontDescriptor, FontStyle};
 
+use windows::core::HSTRING;
 use windows::Win32::{
     Foundation::HWND,
     Graphics::Direct2D::{
@@ -25,6 +27,21 @@ use windows::Win32::{
         D2D1_CAP_STYLE_FLAT,
         D2D1_CAP_STYLE_ROUND,
         D2D1_CAP_STYLE_SQUARE,
+        D2D1_DASH_STYLE_CUSTOM,
+        D2D1_DASH_STYLE_SOLID,
+    },
+    Graphics::DirectWrite::{
+        DWriteCreateFactory,
+
+        IDWriteFactory,
+        IDWriteTextFormat,
+
+        DWRITE_FACTORY_TYPE_SHARED,
+        DWRITE_FONT_STRETCH_NORMAL,
+        DWRITE_FONT_STYLE_ITALIC,
+        DWRITE_FONT_STYLE_NORMAL,
+        DWRITE_FONT_STYLE_OBLIQUE,
+        DWRITE_FONT_WEIGHT,
     },
 };
 
@@ -36,6 +53,7 @@ use crate::{
 
 pub struct DirectFactory {
     factory: ID2D1Factory,
+    write_factory: IDWriteFactory,
 }
 
 impl DirectFactory {
@@ -50,6 +68,9 @@ impl DirectFactory {
             factory: unsafe {
                 D2D1CreateFactory(D2D1_FACTORY_TYPE_SINGLE_THREADED, Some(&opts)).unwrap()
             },
+            write_factory: unsafe {
+                DWriteCreateFactory(DWRITE_FACTORY_TYPE_SHARED).unwrap()
+            },
         }
     }
 
@@ -78,20 +99,32 @@ impl DirectFactory {
     }
 
     pub fn create_stroke_style(&self, properties: StrokeStyleProperties) -> Box<dyn StrokeStyle> {
+        let style = self.create_direct_stroke_style(&properties, 1.0);
+        Box::new(DirectStrokeStyle { style, properties })
+    }
+
+    /// Create the stroke style for a stroke of the given `width`, since the
+    /// dashes are specified in multiples of the stroke width.
+    pub fn create_direct_stroke_style(&self, properties: &StrokeStyleProperties, width: f32) -> ID2D1StrokeStyle {
         let cap_style = |style| match style {
             CapStyle::Butt => D2D1_CAP_STYLE_FLAT,
             CapStyle::Round => D2D1_CAP_STYLE_ROUND,
             CapStyle::Square => D2D1_CAP_STYLE_SQUARE,
         };
 
+        let dashes: Option<Vec<f32>> = properties.dash_pattern.as_ref().map(|pattern| {
+            pattern.iter().map(|dash| *dash as f32 / width).collect()
+        });
+
         unsafe {
-            let style = self.factory.CreateStrokeStyle(&D2D1_STROKE_STYLE_PROPERTIES {
+            self.factory.CreateStrokeStyle(&D2D1_STROKE_STYLE_PROPERTIES {
                 dashCap: cap_style(properties.cap_style_dash),
                 endCap: cap_style(properties.cap_style_end),
                 startCap: cap_style(properties.cap_style_start),
+                dashStyle: if dashes.is_some() { D2D1_DASH_STYLE_CUSTOM } else { D2D1_DASH_STYLE_SOLID },
+                dashOffset: properties.dash_offset as f32 / width,
                 ..Default::default()
-            }, None).unwrap();
-            Box::new(DirectStrokeStyle { style })
+            }, dashes.as_deref()).unwrap()
         }
     }
 
@@ -100,10 +133,47 @@ impl DirectFactory {
             self.factory.CreatePathGeometry().unwrap()
         }
     }
+
+    pub fn create_text_format(&self, descriptor: &FontDescriptor, font_size: f32) -> IDWriteTextFormat {
+        let family_name = match &descriptor.name {
+            FamilyName::Title(name) => name.as_ref(),
+            FamilyName::Cursive => "Comic Sans MS",
+            FamilyName::Fantasy => "Impact",
+            FamilyName::Monospace | FamilyName::UiMonospace => "Consolas",
+            FamilyName::Emoji => "Segoe UI Emoji",
+            FamilyName::Math => "Cambria Math",
+            FamilyName::SansSerif
+                | FamilyName::SystemUi
+                | FamilyName::UiRounded
+                | FamilyName::UiSansSerif => "Segoe UI",
+            FamilyName::Fangsong
+                | FamilyName::Serif
+                | FamilyName::UiSerif => "Times New Roman",
+        };
+
+        let style = match descriptor.style {
+            FontStyle::Normal => DWRITE_FONT_STYLE_NORMAL,
+            FontStyle::Italic => DWRITE_FONT_STYLE_ITALIC,
+            FontStyle::Oblique => DWRITE_FONT_STYLE_OBLIQUE,
+        };
+
+        unsafe {
+            self.write_factory.CreateTextFormat(
+                &HSTRING::from(family_name),
+                None,
+                DWRITE_FONT_WEIGHT(descriptor.weight.value() as _),
+                style,
+                DWRITE_FONT_STRETCH_NORMAL,
+                font_size,
+                &HSTRING::new(),
+            ).unwrap()
+        }
+    }
 }
 
 pub struct DirectStrokeStyle {
     pub style: ID2D1StrokeStyle,
+    pub properties: StrokeStyleProperties,
 }
 
 impl StrokeStyle for DirectStrokeStyle {
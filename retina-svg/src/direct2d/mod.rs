@@ -3,11 +3,18 @@
 
 mod factory;
 
-use euclid::default::{Box2D, Point2D, Rect, Size2D};
+use std::mem::ManuallyDrop;
+
+use euclid::default::{Box2D, Point2D, Rect, Size2D, Transform2D};
+use image::DynamicImage;
+use log::warn;
+use retina_gfx_font::FontHandle;
 use windows::{
     Foundation::Numerics::Matrix3x2,
     Win32::Graphics::Direct2D::{
         Common::{
+            D2D1_ALPHA_MODE_PREMULTIPLIED,
+            D2D1_PIXEL_FORMAT,
             D2D_RECT_F,
             D2D_POINT_2F,
             D2D1_COLOR_F,
@@ -20,11 +27,25 @@ use windows::{
             D2D_SIZE_U,
             D2D_SIZE_F,
         },
+        D2D1_ANTIALIAS_MODE_PER_PRIMITIVE,
         D2D1_ARC_SEGMENT,
         D2D1_ARC_SIZE_LARGE,
         D2D1_ARC_SIZE_SMALL,
+        D2D1_BITMAP_INTERPOLATION_MODE_LINEAR,
+        D2D1_BITMAP_PROPERTIES,
+        D2D1_BRUSH_PROPERTIES,
+        D2D1_DRAW_TEXT_OPTIONS_NONE,
         D2D1_ELLIPSE,
+        D2D1_EXTEND_MODE_CLAMP,
+        D2D1_EXTEND_MODE_MIRROR,
+        D2D1_EXTEND_MODE_WRAP,
+        D2D1_GAMMA_2_2,
+        D2D1_GRADIENT_STOP,
+        D2D1_LAYER_OPTIONS_NONE,
+        D2D1_LAYER_PARAMETERS,
+        D2D1_LINEAR_GRADIENT_BRUSH_PROPERTIES,
         D2D1_QUADRATIC_BEZIER_SEGMENT,
+        D2D1_RADIAL_GRADIENT_BRUSH_PROPERTIES,
         D2D1_ROUNDED_RECT,
         D2D1_SWEEP_DIRECTION_CLOCKWISE,
         D2D1_SWEEP_DIRECTION_COUNTER_CLOCKWISE,
@@ -32,8 +53,12 @@ use windows::{
         ID2D1Brush,
         ID2D1GeometrySink,
         ID2D1HwndRenderTarget,
+        ID2D1Layer,
         ID2D1PathGeometry,
+        ID2D1StrokeStyle,
     },
+    Win32::Graphics::DirectWrite::DWRITE_MEASURING_MODE_NATURAL,
+    Win32::Graphics::Dxgi::Common::DXGI_FORMAT_R8G8B8A8_UNORM,
 };
 
 use windows::core::ComInterface;
@@ -42,6 +67,8 @@ use crate::{
     Geometry,
     GeometrySink,
     GeometrySinkFillType,
+    GradientKind,
+    GradientSpreadMethod,
     Material,
     Painter,
     path::{
@@ -58,9 +85,10 @@ use crate::{
 use self::factory::{DirectFactory, DirectStrokeStyle};
 
 pub struct DirectContext {
-    #[allow(dead_code)]
     factory: DirectFactory,
     render_target: ID2D1HwndRenderTarget,
+    transform_stack: Vec<Matrix3x2>,
+    layer_stack: Vec<ID2D1Layer>,
 }
 
 impl DirectContext {
@@ -72,6 +100,8 @@ impl DirectContext {
         Self {
             factory,
             render_target,
+            transform_stack: Vec::new(),
+            layer_stack: Vec::new(),
         }
     }
 
@@ -111,9 +141,83 @@ impl DirectContext {
                 ).unwrap();
                 brush.cast().unwrap()
             }
+
+            Material::Gradient(gradient) => unsafe {
+                let stops: Vec<D2D1_GRADIENT_STOP> = gradient.stops.iter()
+                    .map(|stop| D2D1_GRADIENT_STOP {
+                        position: stop.offset,
+                        color: D2D1_COLOR_F {
+                            r: stop.color.red() as _,
+                            g: stop.color.green() as _,
+                            b: stop.color.blue() as _,
+                            a: stop.color.alpha() as _,
+                        },
+                    })
+                    .collect();
+
+                let extend_mode = match gradient.spread_method {
+                    GradientSpreadMethod::Pad => D2D1_EXTEND_MODE_CLAMP,
+                    GradientSpreadMethod::Reflect => D2D1_EXTEND_MODE_MIRROR,
+                    GradientSpreadMethod::Repeat => D2D1_EXTEND_MODE_WRAP,
+                };
+
+                let stop_collection = self.render_target.CreateGradientStopCollection(
+                    &stops,
+                    D2D1_GAMMA_2_2,
+                    extend_mode,
+                ).unwrap();
+
+                let brush_properties = D2D1_BRUSH_PROPERTIES {
+                    opacity: 1.0,
+                    transform: matrix(gradient.transform),
+                };
+
+                match gradient.kind {
+                    GradientKind::Linear { start, end } => {
+                        let brush = self.render_target.CreateLinearGradientBrush(
+                            &D2D1_LINEAR_GRADIENT_BRUSH_PROPERTIES {
+                                startPoint: D2D_POINT_2F { x: start.x, y: start.y },
+                                endPoint: D2D_POINT_2F { x: end.x, y: end.y },
+                            },
+                            Some(&brush_properties),
+                            &stop_collection,
+                        ).unwrap();
+                        brush.cast().unwrap()
+                    }
+
+                    GradientKind::Radial { center, focal_point, radius } => {
+                        let brush = self.render_target.CreateRadialGradientBrush(
+                            &D2D1_RADIAL_GRADIENT_BRUSH_PROPERTIES {
+                                center: D2D_POINT_2F { x: center.x, y: center.y },
+                                gradientOriginOffset: D2D_POINT_2F {
+                                    x: focal_point.x - center.x,
+                                    y: focal_point.y - center.y,
+                                },
+                                radiusX: radius,
+                                radiusY: radius,
+                            },
+                            Some(&brush_properties),
+                            &stop_collection,
+                        ).unwrap();
+                        brush.cast().unwrap()
+                    }
+                }
+            }
         }
     }
 
+    /// Get the Direct2D stroke style for a stroke of the given `width`.
+    /// Direct2D expresses the dash pattern in multiples of the stroke width,
+    /// so the style is recreated when the stroke isn't one unit wide.
+    fn stroke_style(&self, stroke_style: Option<&dyn StrokeStyle>, width: f32) -> Option<ID2D1StrokeStyle> {
+        let stroke_style = stroke_style?.as_any().downcast_ref::<DirectStrokeStyle>()?;
+        if stroke_style.properties.dash_pattern.is_none() || width == 1.0 {
+            return Some(stroke_style.style.clone());
+        }
+
+        Some(self.factory.create_direct_stroke_style(&stroke_style.properties, width))
+    }
+
     fn rect(&self, rect: Box2D<f32>) -> D2D_RECT_F {
         let rect = rect.to_rect();
         D2D_RECT_F {
@@ -184,6 +288,51 @@ impl Painter for DirectContext {
         }
     }
 
+    fn draw_image(&mut self, image: &DynamicImage, source_rect: Rect<f32>, destination_rect: Rect<f32>) {
+        // Direct2D bitmaps have premultiplied alpha.
+        let mut pixels = image.to_rgba8();
+        for pixel in pixels.pixels_mut() {
+            let alpha = pixel[3] as u32;
+            for channel in &mut pixel.0[..3] {
+                *channel = (*channel as u32 * alpha / 255) as u8;
+            }
+        }
+
+        let rect = |rect: Rect<f32>| D2D_RECT_F {
+            left: rect.min_x(),
+            top: rect.min_y(),
+            right: rect.max_x(),
+            bottom: rect.max_y(),
+        };
+
+        unsafe {
+            let bitmap = self.render_target.CreateBitmap(
+                D2D_SIZE_U {
+                    width: pixels.width(),
+                    height: pixels.height(),
+                },
+                Some(pixels.as_ptr() as _),
+                pixels.width() * 4,
+                &D2D1_BITMAP_PROPERTIES {
+                    pixelFormat: D2D1_PIXEL_FORMAT {
+                        format: DXGI_FORMAT_R8G8B8A8_UNORM,
+                        alphaMode: D2D1_ALPHA_MODE_PREMULTIPLIED,
+                    },
+                    dpiX: 96.0,
+                    dpiY: 96.0,
+                },
+            ).unwrap();
+
+            self.render_target.DrawBitmap(
+                &bitmap,
+                Some(&rect(destination_rect)),
+                1.0,
+                D2D1_BITMAP_INTERPOLATION_MODE_LINEAR,
+                Some(&rect(source_rect)),
+            );
+        }
+    }
+
     fn draw_rect(&mut self, rect: euclid::default::Box2D<f32>, material: Material, radius: Point2D<f32>) {
         let material = self.create_material(material);
 
@@ -199,10 +348,29 @@ impl Painter for DirectContext {
         }
     }
 
+    fn draw_text(&mut self, text: &str, position: Point2D<f32>, font: &FontHandle, font_size: f32, material: Material) {
+        let text_format = self.factory.create_text_format(font.descriptor(), font_size);
+        let text: Vec<u16> = text.encode_utf16().collect();
+
+        unsafe {
+            self.render_target.DrawText(
+                &text,
+                &text_format,
+                &D2D_RECT_F {
+                    left: position.x,
+                    top: position.y,
+                    right: f32::MAX,
+                    bottom: f32::MAX,
+                },
+                &self.create_material(material),
+                D2D1_DRAW_TEXT_OPTIONS_NONE,
+                DWRITE_MEASURING_MODE_NATURAL,
+            );
+        }
+    }
+
     fn stroke_ellipse(&mut self, center: Point2D<f32>, radius: Point2D<f32>, material: Material, width: f32, stroke_style: Option<&dyn StrokeStyle>) {
-        let style = stroke_style
-            .and_then(|x| x.as_any().downcast_ref::<DirectStrokeStyle>())
-            .map(|x| &x.style);
+        let style = self.stroke_style(stroke_style, width);
         unsafe {
             self.render_target.DrawEllipse(&D2D1_ELLIPSE {
                     point: D2D_POINT_2F {
@@ -214,7 +382,7 @@ impl Painter for DirectContext {
                 },
                 &self.create_material(material),
                 width,
-                style,
+                style.as_ref(),
             );
         }
     }
@@ -229,31 +397,27 @@ impl Painter for DirectContext {
         let geo = geometry.as_any()
             .downcast_ref::<DirectGeometry>()
             .unwrap();
-        let style = stroke_style
-            .and_then(|x| x.as_any().downcast_ref::<DirectStrokeStyle>())
-            .map(|x| &x.style);
+        let style = self.stroke_style(stroke_style, width);
 
         unsafe {
             self.render_target.DrawGeometry(
                 &geo.geometry,
                 &self.create_material(material),
                 width,
-                style,
+                style.as_ref(),
             )
         }
     }
 
     fn stroke_line(&mut self, start: Point2D<f32>, end: Point2D<f32>, material: Material, width: f32, stroke_style: Option<&dyn StrokeStyle>) {
-        let style = stroke_style
-            .and_then(|x| x.as_any().downcast_ref::<DirectStrokeStyle>())
-            .map(|x| &x.style);
+        let style = self.stroke_style(stroke_style, width);
         unsafe {
             self.render_target.DrawLine(
                 D2D_POINT_2F { x: start.x, y: start.y },
                 D2D_POINT_2F { x: end.x, y: end.y },
                 &self.create_material(material),
                 width,
-                style,
+                style.as_ref(),
         );
         }
     }
@@ -266,9 +430,7 @@ impl Painter for DirectContext {
         width: f32,
         stroke_style: Option<&dyn StrokeStyle>,
     ) {
-        let style = stroke_style
-            .and_then(|x| x.as_any().downcast_ref::<DirectStrokeStyle>())
-            .map(|x| &x.style);
+        let style = self.stroke_style(stroke_style, width);
         unsafe {
             self.render_target.DrawRoundedRectangle(
                 &D2D1_ROUNDED_RECT {
@@ -278,7 +440,7 @@ impl Painter for DirectContext {
                 },
                 &self.create_material(material),
                 width,
-                style,
+                style.as_ref(),
             );
         }
     }
@@ -293,6 +455,69 @@ impl Painter for DirectContext {
         }
     }
 
+    fn push_transform(&mut self, transform: Transform2D<f32>) {
+        unsafe {
+            let mut current = Matrix3x2::default();
+            self.render_target.GetTransform(&mut current);
+            self.transform_stack.push(current);
+
+            self.render_target.SetTransform(&(matrix(transform) * current));
+        }
+    }
+
+    fn pop_transform(&mut self) {
+        let Some(transform) = self.transform_stack.pop() else {
+            warn!("pop_transform() called without a matching push_transform()");
+            return;
+        };
+
+        unsafe {
+            self.render_target.SetTransform(&transform);
+        }
+    }
+
+    fn push_clip_geometry(&mut self, geometry: &dyn Geometry) {
+        let geometry = geometry.as_any()
+            .downcast_ref::<DirectGeometry>()
+            .unwrap();
+
+        unsafe {
+            let layer = self.render_target.CreateLayer(None).unwrap();
+
+            // The geometric mask is transformed by the world transform at the
+            // time the layer is pushed.
+            let mut parameters = D2D1_LAYER_PARAMETERS {
+                contentBounds: D2D_RECT_F {
+                    left: f32::MIN,
+                    top: f32::MIN,
+                    right: f32::MAX,
+                    bottom: f32::MAX,
+                },
+                geometricMask: ManuallyDrop::new(Some(geometry.geometry.cast().unwrap())),
+                maskAntialiasMode: D2D1_ANTIALIAS_MODE_PER_PRIMITIVE,
+                maskTransform: Matrix3x2::identity(),
+                opacity: 1.0,
+                opacityBrush: ManuallyDrop::new(None),
+                layerOptions: D2D1_LAYER_OPTIONS_NONE,
+            };
+            self.render_target.PushLayer(&parameters, &layer);
+            ManuallyDrop::drop(&mut parameters.geometricMask);
+
+            self.layer_stack.push(layer);
+        }
+    }
+
+    fn pop_clip_geometry(&mut self) {
+        if self.layer_stack.pop().is_none() {
+            warn!("pop_clip_geometry() called without a matching push_clip_geometry()");
+            return;
+        }
+
+        unsafe {
+            self.render_target.PopLayer();
+        }
+    }
+
     fn set_size(&self, size: Size2D<f32>) {
         unsafe {
             let mut transform = Matrix3x2::default();
@@ -312,6 +537,17 @@ impl Geometry for DirectGeometry {
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
+
+    fn bounds(&self) -> Rect<f32> {
+        let bounds = unsafe {
+            self.geometry.GetBounds(None).unwrap()
+        };
+
+        Box2D::new(
+            Point2D::new(bounds.left, bounds.top),
+            Point2D::new(bounds.right, bounds.bottom),
+        ).to_rect()
+    }
 }
 
 struct DirectGeometrySink {
@@ -556,7 +792,7 @@ impl GeometrySink for DirectGeometrySink {
     fn finish(&mut self) -> Box<dyn Geometry> {
         log::info!("Finishing...");
 
-        if self.state != DirectGeometrySinkState::Closed {
+        if self.state == DirectGeometrySinkState::Opened {
             // The “closepath” command (Z/z) will close it differently than
             // this implicit one, since it will connect the initial point to
             // the last point, whilst this implicit one doesn't.
@@ -583,6 +819,18 @@ const fn point(value: SvgPathCoordinatePair) -> D2D_POINT_2F {
     }
 }
 
+#[inline]
+const fn matrix(transform: Transform2D<f32>) -> Matrix3x2 {
+    Matrix3x2 {
+        M11: transform.m11,
+        M12: transform.m12,
+        M21: transform.m21,
+        M22: transform.m22,
+        M31: transform.m31,
+        M32: transform.m32,
+    }
+}
+
 #[inline]
 fn reflect_point(point: D2D_POINT_2F, relative_to: D2D_POINT_2F) -> D2D_POINT_2F {
     D2D_POINT_2F {
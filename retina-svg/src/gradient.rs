@@ -0,0 +1,473 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! Resolving the `<linearGradient>` and `<radialGradient>` paint servers.
+//!
+//! ## References
+//! * [SVG 1.1 § 13.2 Gradients](https://www.w3.org/TR/SVG11/pservers.html#Gradients)
+
+use euclid::default::{Point2D, Size2D, Transform2D};
+use log::warn;
+use retina_common::Color;
+use retina_dom::{Element, Node};
+use retina_style::CssColor;
+
+use crate::{
+    Gradient,
+    GradientKind,
+    GradientSpreadMethod,
+    GradientStop,
+    GradientUnits,
+    Material,
+    SvgElementTraits,
+};
+
+/// Resolve the gradient `node`, or [`None`] if it isn't a gradient element.
+pub(crate) fn resolve_gradient(node: &Node) -> Option<Material> {
+    let element = node.as_dom_element()?;
+    let is_linear = match element.qualified_name().local.as_ref() {
+        "linearGradient" => true,
+        "radialGradient" => false,
+        _ => return None,
+    };
+
+    let templates = gradient_templates(node);
+    let templates: Vec<&Element> = templates.iter()
+        .filter_map(|template| template.as_dom_element())
+        .collect();
+
+    // The attributes that aren't defined on this element are inherited from
+    // the referenced gradient (the template), which for the attributes that
+    // are specific to a type of gradient must be of the same type.
+    let attribute = |name: &str, same_type: bool| -> Option<&str> {
+        templates.iter()
+            .filter(|template| !same_type || template.qualified_name().local == element.qualified_name().local)
+            .find_map(|template| template.attributes().find_by_str(name))
+    };
+
+    let units = match attribute("gradientUnits", false) {
+        Some("userSpaceOnUse") => GradientUnits::UserSpaceOnUse,
+        _ => GradientUnits::ObjectBoundingBox,
+    };
+
+    // Percentages are relative to the bounding box (which has the size 1x1
+    // in the gradient coordinate system), or to the viewport.
+    let reference_size = match units {
+        GradientUnits::ObjectBoundingBox => Size2D::new(1.0, 1.0),
+        GradientUnits::UserSpaceOnUse => viewport_size(element),
+    };
+    let reference_diagonal = (reference_size.width.powi(2) + reference_size.height.powi(2)).sqrt() / std::f32::consts::SQRT_2;
+    let coordinate = |name: &str, default: f32, reference: f32| {
+        attribute(name, true)
+            .and_then(|value| parse_gradient_length(name, value, reference))
+            .unwrap_or(default * reference)
+    };
+
+    let stops = templates.iter()
+        .map(|template| parse_stops(template))
+        .find(|stops| !stops.is_empty())
+        .unwrap_or_default();
+
+    // If zero stops are defined, then painting shall occur as if 'none' were
+    // specified as the paint style. If one stop is defined, then painting
+    // shall occur with the solid color fill using the color defined for that
+    // gradient stop.
+    let last_stop_color = match stops[..] {
+        [] => return Some(Material::Color(Color::TRANSPARENT)),
+        [stop] => return Some(Material::Color(stop.color)),
+        [.., stop] => Material::Color(stop.color),
+    };
+
+    let kind = if is_linear {
+        let start = Point2D::new(
+            coordinate("x1", 0.0, reference_size.width),
+            coordinate("y1", 0.0, reference_size.height),
+        );
+        let end = Point2D::new(
+            coordinate("x2", 1.0, reference_size.width),
+            coordinate("y2", 0.0, reference_size.height),
+        );
+
+        // If the start and end point are the same, then the area to be
+        // painted will be painted as a single color using the color and
+        // opacity of the last gradient stop.
+        if start == end {
+            return Some(last_stop_color);
+        }
+
+        GradientKind::Linear { start, end }
+    } else {
+        let center = Point2D::new(
+            coordinate("cx", 0.5, reference_size.width),
+            coordinate("cy", 0.5, reference_size.height),
+        );
+        let radius = coordinate("r", 0.5, reference_diagonal);
+
+        // A negative value is an error. A value of zero will cause the area
+        // to be painted as a single color using the color and opacity of the
+        // last gradient stop.
+        if radius < 0.0 {
+            warn!("Negative radius of <radialGradient>: {radius}");
+            return Some(Material::Color(Color::TRANSPARENT));
+        }
+        if radius == 0.0 {
+            return Some(last_stop_color);
+        }
+
+        let mut focal_point = Point2D::new(
+            attribute("fx", true)
+                .and_then(|value| parse_gradient_length("fx", value, reference_size.width))
+                .unwrap_or(center.x),
+            attribute("fy", true)
+                .and_then(|value| parse_gradient_length("fy", value, reference_size.height))
+                .unwrap_or(center.y),
+        );
+
+        // If the point defined by fx and fy lies outside the circle defined by
+        // cx, cy and r, then the user agent shall set the focal point to the
+        // intersection of the line from (cx, cy) to (fx, fy) with the circle
+        // defined by cx, cy and r.
+        let offset = focal_point - center;
+        if offset.length() > radius {
+            focal_point = center + offset.normalize() * radius;
+        }
+
+        GradientKind::Radial { center, focal_point, radius }
+    };
+
+    let spread_method = match attribute("spreadMethod", false) {
+        Some("reflect") => GradientSpreadMethod::Reflect,
+        Some("repeat") => GradientSpreadMethod::Repeat,
+        _ => GradientSpreadMethod::Pad,
+    };
+
+    let transform = attribute("gradientTransform", false)
+        .map(|value| crate::path::parse_svg_transform(value).cast())
+        .unwrap_or_else(Transform2D::identity);
+
+    Some(Material::Gradient(Gradient {
+        kind,
+        stops,
+        spread_method,
+        transform,
+        units,
+    }))
+}
+
+/// Get the gradient `node`, followed by the gradients it references using the
+/// `href` attribute, of which it inherits the attributes and stops.
+fn gradient_templates(node: &Node) -> Vec<Node> {
+    let mut templates = vec![node.clone()];
+
+    while let Some(element) = templates.last().and_then(|template| template.as_dom_element()) {
+        // Attributes are stored by their local name, so this also finds the
+        // xlink:href attribute.
+        let Some(id) = element.attributes().find_by_str("href").and_then(|href| href.strip_prefix('#')) else {
+            break;
+        };
+
+        let Some(template) = element.find_referenced_element(id) else {
+            warn!("Gradient references an unknown element: #{id}");
+            break;
+        };
+
+        let is_gradient = template.as_dom_element().is_some_and(|template| {
+            matches!(template.qualified_name().local.as_ref(), "linearGradient" | "radialGradient")
+        });
+        if !is_gradient {
+            warn!("Gradient references an element that isn't a gradient: #{id}");
+            break;
+        }
+
+        if templates.iter().any(|existing| Node::ptr_eq(existing, &template)) {
+            warn!("Gradient has a circular reference to: #{id}");
+            break;
+        }
+
+        templates.push(template);
+    }
+
+    templates
+}
+
+/// Parse a coordinate of the gradient, of which a percentage is relative to
+/// the `reference` length.
+fn parse_gradient_length(name: &str, value: &str, reference: f32) -> Option<f32> {
+    match value.trim().strip_suffix('%') {
+        Some(percentage) => percentage.trim().parse::<f32>().ok().map(|percentage| percentage / 100.0 * reference),
+        None => crate::parse_length(name, value.trim()),
+    }
+}
+
+/// Parse the `<stop>` children of the gradient `element`.
+///
+/// <https://www.w3.org/TR/SVG11/pservers.html#StopElement>
+fn parse_stops(element: &Element) -> Vec<GradientStop> {
+    let mut stops = Vec::new();
+    let mut previous_offset = 0.0;
+
+    for child in element.as_parent_node().children().iter() {
+        let Some(stop) = child.as_dom_element() else { continue };
+        if stop.qualified_name().local.as_ref() != "stop" {
+            continue;
+        }
+
+        // Values outside the range 0 to 1 are clamped to this range. If a
+        // gradient stop offset value is less than the offset value of a
+        // previous gradient stop, then the offset value is adjusted to be
+        // equal to the largest of all previous offset values.
+        let offset = stop.attributes().find_by_str("offset")
+            .and_then(|offset| parse_gradient_length("offset", offset, 1.0))
+            .unwrap_or(0.0)
+            .clamp(0.0, 1.0)
+            .max(previous_offset);
+        previous_offset = offset;
+
+        let color = match stop.attributes().find_by_str("stop-color").map(retina_style_parser::parse_value_color) {
+            Some(Some(CssColor::Color(color))) => color,
+            _ => Color::BLACK,
+        };
+
+        let opacity = stop.attributes().find_by_str("stop-opacity")
+            .and_then(|opacity| opacity.trim().parse::<f64>().ok())
+            .unwrap_or(1.0)
+            .clamp(0.0, 1.0);
+
+        stops.push(GradientStop {
+            offset,
+            color: color.with_alpha(color.alpha() * opacity),
+        });
+    }
+
+    stops
+}
+
+/// Get the size of the viewport of the nearest `<svg>` element, to which
+/// percentages in user space are relative.
+fn viewport_size(element: &Element) -> Size2D<f32> {
+    if element.qualified_name().local.eq_str_ignore_ascii_case("svg") {
+        if let Some(view_box) = element.property_view_box() {
+            return view_box.size;
+        }
+
+        return Size2D::new(element.property_width(), element.property_height());
+    }
+
+    element.as_node()
+        .parent()
+        .as_ref()
+        .and_then(std::sync::Weak::upgrade)
+        .and_then(|parent| parent.as_dom_element().map(viewport_size))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::test_util::{materials, render};
+
+    /// The transform of the gradients of the `<rect>` painted by [`fill()`],
+    /// which maps the bounding box units to user space.
+    fn bounding_box() -> Transform2D<f32> {
+        Transform2D::scale(100.0, 50.0)
+    }
+
+    /// Get the fill of a 100x50 `<rect>` that references the gradient with
+    /// the id `g`, which is defined in the `defs`.
+    fn fill(defs: &str) -> Vec<Material> {
+        materials(render(&format!(
+            r#"<svg width="200" height="100"><defs>{defs}</defs><rect width="100" height="50" fill="url(#g)"/></svg>"#
+        )))
+    }
+
+    fn stop(offset: f32, color: Color) -> GradientStop {
+        GradientStop { offset, color }
+    }
+
+    fn red_to_blue() -> Vec<GradientStop> {
+        vec![stop(0.0, Color::RED), stop(1.0, Color::BLUE)]
+    }
+
+    fn gradient(kind: GradientKind) -> Material {
+        Material::Gradient(Gradient {
+            kind,
+            stops: red_to_blue(),
+            spread_method: GradientSpreadMethod::Pad,
+            transform: bounding_box(),
+            units: GradientUnits::UserSpaceOnUse,
+        })
+    }
+
+    const STOPS: &str = r#"<stop offset="0" stop-color="red"/><stop offset="1" stop-color="blue"/>"#;
+
+    #[test]
+    fn linear_bounding_box() {
+        assert_eq!(fill(&format!(r#"<linearGradient id="g">{STOPS}</linearGradient>"#)), [
+            gradient(GradientKind::Linear {
+                start: Point2D::new(0.0, 0.0),
+                end: Point2D::new(1.0, 0.0),
+            }),
+        ]);
+    }
+
+    #[test]
+    fn linear_user_space() {
+        // Percentages are relative to the size of the viewport, i.e. 200x100.
+        let defs = format!(r#"<linearGradient id="g" gradientUnits="userSpaceOnUse" x1="10%" y1="20" x2="50%" y2="100%" gradientTransform="translate(5, 6)">{STOPS}</linearGradient>"#);
+        assert_eq!(fill(&defs), [
+            Material::Gradient(Gradient {
+                kind: GradientKind::Linear {
+                    start: Point2D::new(20.0, 20.0),
+                    end: Point2D::new(100.0, 100.0),
+                },
+                stops: red_to_blue(),
+                spread_method: GradientSpreadMethod::Pad,
+                transform: Transform2D::translation(5.0, 6.0),
+                units: GradientUnits::UserSpaceOnUse,
+            }),
+        ]);
+    }
+
+    #[test]
+    fn radial_bounding_box() {
+        assert_eq!(fill(&format!(r#"<radialGradient id="g">{STOPS}</radialGradient>"#)), [
+            gradient(GradientKind::Radial {
+                center: Point2D::new(0.5, 0.5),
+                focal_point: Point2D::new(0.5, 0.5),
+                radius: 0.5,
+            }),
+        ]);
+    }
+
+    #[test]
+    fn radial_focal_point_outside_circle() {
+        // The focal point is moved onto the edge of the circle.
+        let defs = format!(r#"<radialGradient id="g" r="0.25" fx="1" fy="0.5">{STOPS}</radialGradient>"#);
+        assert_eq!(fill(&defs), [
+            gradient(GradientKind::Radial {
+                center: Point2D::new(0.5, 0.5),
+                focal_point: Point2D::new(0.75, 0.5),
+                radius: 0.25,
+            }),
+        ]);
+    }
+
+    #[rstest]
+    #[case(r#"<linearGradient id="g" x1="0.5" x2="50%">"#)]
+    #[case(r#"<radialGradient id="g" r="0">"#)]
+    fn degenerate_gradient_uses_last_stop(#[case] gradient: &str) {
+        let closing = if gradient.starts_with("<linear") { "</linearGradient>" } else { "</radialGradient>" };
+        assert_eq!(fill(&format!("{gradient}{STOPS}{closing}")), [Material::Color(Color::BLUE)]);
+    }
+
+    #[test]
+    fn negative_radius_disables_painting() {
+        assert!(fill(&format!(r#"<radialGradient id="g" r="-1">{STOPS}</radialGradient>"#)).is_empty());
+    }
+
+    #[test]
+    fn stops() {
+        // The offsets are clamped to 0..=1 and never decrease.
+        let defs = r#"<linearGradient id="g">
+            <stop offset="-1" stop-color="red"/>
+            <stop offset="0.7" stop-color="blue" stop-opacity="0.5"/>
+            <stop offset="50%"/>
+            <stop offset="2" stop-color="red" stop-opacity="3"/>
+        </linearGradient>"#;
+
+        let [Material::Gradient(gradient)] = &fill(defs)[..] else {
+            panic!("expected a single gradient");
+        };
+        assert_eq!(gradient.stops, [
+            stop(0.0, Color::RED),
+            stop(0.7, Color::BLUE.with_alpha(0.5)),
+            stop(0.7, Color::BLACK),
+            stop(1.0, Color::RED),
+        ]);
+    }
+
+    #[test]
+    fn without_stops_disables_painting() {
+        assert!(fill(r#"<linearGradient id="g"/>"#).is_empty());
+    }
+
+    #[test]
+    fn single_stop_is_solid_color() {
+        assert_eq!(
+            fill(r#"<linearGradient id="g"><stop stop-color="blue"/></linearGradient>"#),
+            [Material::Color(Color::BLUE)],
+        );
+    }
+
+    #[rstest]
+    #[case("reflect", GradientSpreadMethod::Reflect)]
+    #[case("repeat", GradientSpreadMethod::Repeat)]
+    #[case("pad", GradientSpreadMethod::Pad)]
+    #[case("bogus", GradientSpreadMethod::Pad)]
+    fn spread_method(#[case] value: &str, #[case] expected: GradientSpreadMethod) {
+        let defs = format!(r#"<linearGradient id="g" spreadMethod="{value}">{STOPS}</linearGradient>"#);
+        let [Material::Gradient(gradient)] = &fill(&defs)[..] else {
+            panic!("expected a single gradient");
+        };
+        assert_eq!(gradient.spread_method, expected);
+    }
+
+    #[test]
+    fn inherits_from_template() {
+        // The stops and the generic attributes are inherited from any
+        // gradient, but the coordinates only from a gradient of the same type.
+        let defs = format!(r##"
+            <linearGradient id="linear" x2="0" y2="1" spreadMethod="repeat">{STOPS}</linearGradient>
+            <radialGradient id="radial" href="#linear" r="0.4"/>
+            <radialGradient id="g" href="#radial" cx="0.2"/>
+        "##);
+        assert_eq!(fill(&defs), [
+            Material::Gradient(Gradient {
+                kind: GradientKind::Radial {
+                    center: Point2D::new(0.2, 0.5),
+                    focal_point: Point2D::new(0.2, 0.5),
+                    radius: 0.4,
+                },
+                stops: red_to_blue(),
+                spread_method: GradientSpreadMethod::Repeat,
+                transform: bounding_box(),
+                units: GradientUnits::UserSpaceOnUse,
+            }),
+        ]);
+    }
+
+    #[test]
+    fn circular_template_reference() {
+        let defs = format!(r##"
+            <linearGradient id="a" href="#g" x2="0" y2="1">{STOPS}</linearGradient>
+            <linearGradient id="g" href="#a"/>
+        "##);
+        assert_eq!(fill(&defs), [
+            gradient(GradientKind::Linear {
+                start: Point2D::new(0.0, 0.0),
+                end: Point2D::new(0.0, 1.0),
+            }),
+        ]);
+    }
+
+    #[rstest]
+    #[case("url(#g) red", vec![Material::Color(Color::BLUE)])]
+    #[case("url(#missing) red", vec![Material::Color(Color::RED)])]
+    #[case("url('#rect') red", vec![Material::Color(Color::RED)])]
+    #[case("url(#missing)", vec![])]
+    fn fallback(#[case] value: &str, #[case] expected: Vec<Material>) {
+        let html = format!(r#"<svg><defs><linearGradient id="g"><stop stop-color="blue"/></linearGradient><rect id="rect"/></defs><rect width="100" height="50" fill="{value}"/></svg>"#);
+        assert_eq!(materials(render(&html)), expected);
+    }
+
+    #[test]
+    fn empty_bounding_box() {
+        // A horizontal line has no height, so a gradient relative to its
+        // bounding box isn't rendered.
+        let html = format!(r#"<svg><defs><linearGradient id="g">{STOPS}</linearGradient></defs><line x2="100" stroke="url(#g)"/></svg>"#);
+        assert!(materials(render(&html)).is_empty());
+    }
+}
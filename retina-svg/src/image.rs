@@ -0,0 +1,360 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! Rendering of the `<image>` element.
+//!
+//! ## References
+//! * [SVG 1.1 § 5.7 The ‘image’ element](https://www.w3.org/TR/SVG11/struct.html#ImageElement)
+
+use base64::Engine;
+use euclid::default::{Point2D, Rect, Size2D};
+use image::DynamicImage;
+use log::warn;
+use retina_dom::{
+    Element,
+    ImageData,
+    ImageDataKind,
+    ImageDataState,
+};
+
+use crate::{
+    SvgElementTraits,
+    SvgRenderer,
+};
+
+/// Provides the images referenced by `<image>` elements, which are fetched
+/// and decoded by the embedder, e.g. using the `ImageProvider` of a page.
+///
+/// Images with a `data:` URL are decoded by the renderer itself.
+pub trait SvgImageProvider {
+    /// Get the image of the `href`. The image isn't rendered if it isn't
+    /// [ready][ImageDataState::Ready], e.g. when it's still loading.
+    fn get(&self, href: &str) -> Option<ImageData>;
+}
+
+impl<'painter> SvgRenderer<'painter> {
+    pub(crate) fn render_image(&mut self, element: &Element) {
+        // Attributes are stored by their local name, so this also finds the
+        // xlink:href attribute.
+        let Some(href) = element.attributes().find_by_str("href") else {
+            warn!("<image> has no \"href\" attribute!");
+            return;
+        };
+
+        // A value of zero for the width or height disables rendering of the
+        // element.
+        let viewport = Rect::new(
+            Point2D::new(element.property_x(), element.property_y()),
+            Size2D::new(element.property_width(), element.property_height()),
+        );
+        if viewport.is_empty() {
+            return;
+        }
+
+        let preserve_aspect_ratio = PreserveAspectRatio::parse(element.str_property("preserveAspectRatio"));
+
+        if let Some(data_url) = href.strip_prefix("data:") {
+            let Some((media_type, data)) = decode_data_url(data_url) else {
+                warn!("<image> has an invalid data URL");
+                return;
+            };
+
+            if media_type.eq_ignore_ascii_case("image/svg+xml") {
+                self.render_svg_image(&String::from_utf8_lossy(&data), viewport, preserve_aspect_ratio);
+                return;
+            }
+
+            match image::load_from_memory(&data) {
+                Ok(bitmap) => self.render_bitmap_image(&bitmap, viewport, preserve_aspect_ratio),
+                Err(e) => warn!("<image> data URL failed to decode: {e}"),
+            }
+            return;
+        }
+
+        let Some(image_provider) = self.resources.image_provider.clone() else {
+            warn!("No image provider was given, skipping <image> element: {href}");
+            return;
+        };
+
+        let Some(image) = image_provider.get(href) else { return };
+        if image.state() != ImageDataState::Ready {
+            return;
+        }
+
+        match &*image.image().read().unwrap() {
+            ImageDataKind::Bitmap(bitmap) => self.render_bitmap_image(bitmap, viewport, preserve_aspect_ratio),
+            ImageDataKind::Animated(animated) => {
+                // TODO animate the image
+                if let Some(frame) = animated.frames().first() {
+                    let bitmap = DynamicImage::ImageRgba8(frame.buffer().clone());
+                    self.render_bitmap_image(&bitmap, viewport, preserve_aspect_ratio);
+                }
+            }
+            ImageDataKind::Uploaded { .. } => warn!("<image> was already uploaded to the GPU: {href}"),
+            ImageDataKind::None => (),
+        };
+    }
+
+    fn render_bitmap_image(&mut self, bitmap: &DynamicImage, viewport: Rect<f32>, preserve_aspect_ratio: PreserveAspectRatio) {
+        let size = Size2D::new(bitmap.width() as f32, bitmap.height() as f32);
+        if size.is_empty() {
+            return;
+        }
+
+        let (source_rect, destination_rect) = preserve_aspect_ratio.map_image(viewport, size);
+        if !destination_rect.is_empty() {
+            self.painter.draw_image(bitmap, source_rect, destination_rect);
+        }
+    }
+
+    fn render_svg_image(&mut self, source: &str, viewport: Rect<f32>, preserve_aspect_ratio: PreserveAspectRatio) {
+        let document = retina_dom::Parser::parse(source);
+
+        let mut root = None;
+        document.for_each_child_node_recursive_handle(&mut |node| {
+            if root.is_none() && node.tag_name() == Some("svg") {
+                root = Some(node.clone());
+            }
+        });
+
+        let Some(root) = root else {
+            warn!("<image> SVG data URL doesn't contain an <svg> element");
+            return;
+        };
+        let Some(element) = root.as_dom_element() else { return };
+
+        let view_box = element.property_view_box().unwrap_or_else(|| {
+            Rect::new(Point2D::zero(), Size2D::new(element.property_width(), element.property_height()))
+        });
+        if view_box.is_empty() {
+            return;
+        }
+
+        // The references of the image are resolved against its own document.
+        let outer_root_node = std::mem::replace(&mut self.root_node, root.clone());
+
+        // TODO clip to the viewport when slicing
+        self.painter.push_transform(crate::view_box_transform(view_box, viewport, preserve_aspect_ratio));
+        for child in element.as_parent_node().children().iter() {
+            self.render_node(child);
+        }
+        self.painter.pop_transform();
+
+        self.root_node = outer_root_node;
+    }
+}
+
+/// Decode the part of a [`data:` URL][spec] after the scheme, returning the
+/// essence of the MIME type and the body.
+///
+/// [spec]: https://fetch.spec.whatwg.org/#data-url-processor
+fn decode_data_url(input: &str) -> Option<(String, Vec<u8>)> {
+    let (media_type, body) = input.split_once(',')?;
+    let body: Vec<u8> = percent_encoding::percent_decode_str(body).collect();
+
+    let media_type = media_type.trim();
+    let (media_type, body) = match media_type.len().checked_sub(";base64".len()) {
+        Some(index) if media_type.get(index..).is_some_and(|end| end.eq_ignore_ascii_case(";base64")) => {
+            let body: Vec<u8> = body.into_iter().filter(|c| !c.is_ascii_whitespace()).collect();
+            let body = base64::engine::general_purpose::STANDARD.decode(body).ok()?;
+            (&media_type[..index], body)
+        }
+        _ => (media_type, body),
+    };
+
+    let essence = media_type.split(';').next().unwrap_or_default().trim();
+    let essence = if essence.is_empty() { "text/plain" } else { essence };
+    Some((essence.to_ascii_lowercase(), body))
+}
+
+/// The alignment of the image on a single axis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum AspectRatioAlign {
+    Min,
+    Mid,
+    Max,
+}
+
+impl AspectRatioAlign {
+    fn offset(self, available: f32, used: f32) -> f32 {
+        match self {
+            Self::Min => 0.0,
+            Self::Mid => (available - used) / 2.0,
+            Self::Max => available - used,
+        }
+    }
+}
+
+/// <https://www.w3.org/TR/SVG11/coords.html#PreserveAspectRatioAttribute>
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct PreserveAspectRatio {
+    /// The alignment on the x- and y-axis, or [`None`] if the image should be
+    /// scaled non-uniformly to fill the viewport.
+    align: Option<(AspectRatioAlign, AspectRatioAlign)>,
+
+    /// Whether the image should cover the viewport (`slice`), instead of
+    /// being fully visible inside it (`meet`).
+    slice: bool,
+}
+
+impl Default for PreserveAspectRatio {
+    fn default() -> Self {
+        Self {
+            align: Some((AspectRatioAlign::Mid, AspectRatioAlign::Mid)),
+            slice: false,
+        }
+    }
+}
+
+impl PreserveAspectRatio {
+    /// Parse the `preserveAspectRatio` attribute, which is
+    /// `[defer] <align> [<meetOrSlice>]`. An invalid value results in the
+    /// default `xMidYMid meet`.
+    pub(crate) fn parse(value: &str) -> Self {
+        let mut parts = value.split_ascii_whitespace().peekable();
+        parts.next_if_eq(&"defer");
+
+        let align = match parts.next() {
+            None => return Self::default(),
+            Some("none") => None,
+            Some(align) => {
+                let axis = |value: &str| match value {
+                    "Min" => Some(AspectRatioAlign::Min),
+                    "Mid" => Some(AspectRatioAlign::Mid),
+                    "Max" => Some(AspectRatioAlign::Max),
+                    _ => None,
+                };
+
+                let Some((x, y)) = align.strip_prefix('x').and_then(|align| align.split_once('Y')) else {
+                    return Self::default();
+                };
+
+                let (Some(x), Some(y)) = (axis(x), axis(y)) else {
+                    return Self::default();
+                };
+                Some((x, y))
+            }
+        };
+
+        let slice = match parts.next() {
+            None | Some("meet") => false,
+            Some("slice") => true,
+            Some(_) => return Self::default(),
+        };
+
+        if parts.next().is_some() {
+            return Self::default();
+        }
+
+        Self { align, slice }
+    }
+
+    /// Get the rectangle in which content of the given `size` is placed,
+    /// inside the `viewport`. When slicing, the rectangle can be larger than
+    /// the viewport.
+    pub(crate) fn fit(&self, viewport: Rect<f32>, size: Size2D<f32>) -> Rect<f32> {
+        let Some((align_x, align_y)) = self.align else {
+            return viewport;
+        };
+
+        let scale_x = viewport.width() / size.width;
+        let scale_y = viewport.height() / size.height;
+        let scale = if self.slice {
+            scale_x.max(scale_y)
+        } else {
+            scale_x.min(scale_y)
+        };
+
+        let size = size * scale;
+        Rect::new(
+            Point2D::new(
+                viewport.min_x() + align_x.offset(viewport.width(), size.width),
+                viewport.min_y() + align_y.offset(viewport.height(), size.height),
+            ),
+            size,
+        )
+    }
+
+    /// Get the part of an image of the given `size` that is visible (the
+    /// source rectangle), and the part of the `viewport` it's drawn into (the
+    /// destination rectangle).
+    pub(crate) fn map_image(&self, viewport: Rect<f32>, size: Size2D<f32>) -> (Rect<f32>, Rect<f32>) {
+        let fitted = self.fit(viewport, size);
+        let destination = fitted.intersection(&viewport).unwrap_or_default();
+
+        let scale_x = fitted.width() / size.width;
+        let scale_y = fitted.height() / size.height;
+        let source = Rect::new(
+            Point2D::new(
+                (destination.min_x() - fitted.min_x()) / scale_x,
+                (destination.min_y() - fitted.min_y()) / scale_y,
+            ),
+            Size2D::new(
+                destination.width() / scale_x,
+                destination.height() / scale_y,
+            ),
+        );
+
+        (source, destination)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use pretty_assertions::assert_eq;
+
+    fn rect(x: f32, y: f32, width: f32, height: f32) -> Rect<f32> {
+        Rect::new(Point2D::new(x, y), Size2D::new(width, height))
+    }
+
+    #[rstest]
+    #[case("", PreserveAspectRatio::default())]
+    #[case("none", PreserveAspectRatio { align: None, slice: false })]
+    #[case("xMinYMax", PreserveAspectRatio { align: Some((AspectRatioAlign::Min, AspectRatioAlign::Max)), slice: false })]
+    #[case("defer xMaxYMin slice", PreserveAspectRatio { align: Some((AspectRatioAlign::Max, AspectRatioAlign::Min)), slice: true })]
+    #[case("  xMidYMid   meet ", PreserveAspectRatio::default())]
+    #[case("xMinYMid slice extra", PreserveAspectRatio::default())]
+    #[case("xMinYTop", PreserveAspectRatio::default())]
+    #[case("xminymin", PreserveAspectRatio::default())]
+    fn parse(#[case] input: &str, #[case] expected: PreserveAspectRatio) {
+        assert_eq!(PreserveAspectRatio::parse(input), expected);
+    }
+
+    #[rstest]
+    #[case("none", rect(10.0, 10.0, 200.0, 100.0))]
+    #[case("xMinYMin meet", rect(10.0, 10.0, 50.0, 100.0))]
+    #[case("xMidYMid meet", rect(85.0, 10.0, 50.0, 100.0))]
+    #[case("xMaxYMax meet", rect(160.0, 10.0, 50.0, 100.0))]
+    #[case("xMinYMin slice", rect(10.0, 10.0, 200.0, 400.0))]
+    #[case("xMidYMid slice", rect(10.0, -140.0, 200.0, 400.0))]
+    #[case("xMaxYMax slice", rect(10.0, -290.0, 200.0, 400.0))]
+    fn fit(#[case] input: &str, #[case] expected: Rect<f32>) {
+        let viewport = rect(10.0, 10.0, 200.0, 100.0);
+        let size = Size2D::new(20.0, 40.0);
+        assert_eq!(PreserveAspectRatio::parse(input).fit(viewport, size), expected);
+    }
+
+    #[rstest]
+    #[case("xMidYMid meet", rect(0.0, 0.0, 20.0, 40.0), rect(85.0, 10.0, 50.0, 100.0))]
+    #[case("xMidYMid slice", rect(0.0, 15.0, 20.0, 10.0), rect(10.0, 10.0, 200.0, 100.0))]
+    #[case("xMinYMax slice", rect(0.0, 30.0, 20.0, 10.0), rect(10.0, 10.0, 200.0, 100.0))]
+    fn map_image(#[case] input: &str, #[case] source: Rect<f32>, #[case] destination: Rect<f32>) {
+        let viewport = rect(10.0, 10.0, 200.0, 100.0);
+        let size = Size2D::new(20.0, 40.0);
+        assert_eq!(PreserveAspectRatio::parse(input).map_image(viewport, size), (source, destination));
+    }
+
+    #[rstest]
+    #[case("image/svg+xml,%3Csvg%3E", Some(("image/svg+xml", b"<svg>".to_vec())))]
+    #[case("image/png;base64,aGVs bG8=", Some(("image/png", b"hello".to_vec())))]
+    #[case("Image/SVG+XML;charset=utf-8;BASE64,PHN2Zz4=", Some(("image/svg+xml", b"<svg>".to_vec())))]
+    #[case(",text", Some(("text/plain", b"text".to_vec())))]
+    #[case("image/png;base64,!!!", None)]
+    #[case("image/png", None)]
+    fn data_url(#[case] input: &str, #[case] expected: Option<(&str, Vec<u8>)>) {
+        let expected = expected.map(|(media_type, body)| (media_type.to_string(), body));
+        assert_eq!(decode_data_url(input), expected);
+    }
+}
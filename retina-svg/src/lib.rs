@@ -9,26 +9,42 @@
 
 #[cfg(windows)]
 pub mod direct2d;
+mod clip_path;
+mod gradient;
+mod image;
 mod painter;
 mod path;
 // mod tesselator;
+#[cfg(test)]
+mod test_util;
+mod text;
+mod use_element;
 
-use std::sync::Weak;
+use std::sync::{Arc, Weak};
 
-use crate::path::SvgPathCommand;
+use crate::{
+    image::PreserveAspectRatio,
+    path::{SvgPath, SvgPathCommand},
+};
 
+pub use self::image::SvgImageProvider;
 pub use self::painter::{
     CapStyle,
     Geometry,
     GeometrySink,
     GeometrySinkFillType,
+    Gradient,
+    GradientKind,
+    GradientSpreadMethod,
+    GradientStop,
+    GradientUnits,
     Material,
     Painter,
     StrokeStyle,
     StrokeStyleProperties,
 };
 
-use euclid::{default::{Box2D, Rect, Size2D}, Point2D, UnknownUnit};
+use euclid::{default::{Box2D, Rect, Size2D, Transform2D}, Point2D, UnknownUnit};
 use log::{error, warn, info};
 
 use lyon::geom::point;
@@ -38,25 +54,76 @@ use retina_dom::{
     Element,
     Node,
 };
+use retina_gfx_font::FontProvider;
 use retina_style::{CssColor, CssLength};
 
+/// The resources of the embedder that are used for rendering.
+#[derive(Clone, Default)]
+pub struct SvgResources {
+    /// The fonts of the text elements, which are skipped if there is no font
+    /// provider.
+    pub font_provider: Option<FontProvider>,
+
+    /// The images of the `<image>` elements that don't have a `data:` URL.
+    pub image_provider: Option<Arc<dyn SvgImageProvider>>,
+}
+
 pub fn render(node: &Node, painter: &mut dyn Painter) {
-    render_inner(node, painter);
+    render_inner(node, painter, SvgResources::default());
 }
 
-fn render_inner(node: &Node, painter: &mut dyn Painter) {
+/// Render the SVG figure, using the `resources` for e.g. the text and image
+/// elements, which are skipped by [`render()`].
+pub fn render_with_resources(node: &Node, painter: &mut dyn Painter, resources: SvgResources) {
+    render_inner(node, painter, resources);
+}
+
+fn render_inner(node: &Node, painter: &mut dyn Painter, resources: SvgResources) {
     let mut renderer = SvgRenderer {
         root_node: node.clone(),
         painter,
+        resources,
+        use_stack: Vec::new(),
     };
 
     renderer.render_node(&node);
 }
 
+/// Find the element with the `id` in the tree of which the `node` is part.
+fn find_element_by_id(node: Node, id: &str) -> Option<Node> {
+    let mut root = node;
+    while let Some(parent) = root.as_node().parent().as_ref().and_then(Weak::upgrade) {
+        root = Node::from(parent);
+    }
+
+    if root.as_dom_element().is_some_and(|element| element.id() == id) {
+        return Some(root);
+    }
+
+    root.as_parent_node()?.get_element_by_id(id)
+}
+
+/// Get the transform that maps the `view_box` onto the `viewport`.
+///
+/// <https://www.w3.org/TR/SVG11/coords.html#ViewBoxAttribute>
+fn view_box_transform(view_box: Rect<f32>, viewport: Rect<f32>, preserve_aspect_ratio: PreserveAspectRatio) -> Transform2D<f32> {
+    let destination_rect = preserve_aspect_ratio.fit(viewport, view_box.size);
+    Transform2D::translation(-view_box.origin.x, -view_box.origin.y)
+        .then_scale(
+            destination_rect.width() / view_box.width(),
+            destination_rect.height() / view_box.height(),
+        )
+        .then_translate(destination_rect.origin.to_vector())
+}
+
 pub struct SvgRenderer<'painter> {
-    #[allow(dead_code)]
     root_node: Node,
     painter: &'painter mut dyn Painter,
+    resources: SvgResources,
+
+    /// The elements that are referenced by the `<use>` elements that are
+    /// being rendered, to detect circular references.
+    use_stack: Vec<Node>,
 }
 
 impl<'painter> SvgRenderer<'painter> {
@@ -64,15 +131,55 @@ impl<'painter> SvgRenderer<'painter> {
         let Some(element) = node.as_dom_element() else { return };
         println!("[SvgRenderer] Rendering node: {}", element.qualified_name().local);
 
+        // These elements are never rendered directly, but only when they're
+        // referenced.
+        if matches!(element.qualified_name().local.as_ref(), "clipPath" | "defs" | "linearGradient" | "radialGradient" | "symbol") {
+            return;
+        }
+
+        // The transform attribute establishes a new user space for the
+        // element and its descendants.
+        let transform = element.property_transform();
+        let has_transform = transform != Transform2D::identity();
+        if has_transform {
+            self.painter.push_transform(transform);
+        }
+
+        // The clipping path is in the user space of the element, i.e. after
+        // its transform is applied.
+        let has_clip_path = self.push_clip_path(element);
+        self.render_element(node, element);
+        if has_clip_path {
+            self.painter.pop_clip_geometry();
+        }
+
+        if has_transform {
+            self.painter.pop_transform();
+        }
+    }
+
+    fn render_element(&mut self, node: &Node, element: &Element) {
         match element.qualified_name().local.as_ref() {
             "circle" => self.render_circle(element),
             "ellipse" => self.render_ellipse(element),
+            "image" => self.render_image(element),
             "line" => self.render_line(element),
             "path" => self.render_path(element),
             "polygon" => self.render_polygon(element),
             "polyline" => self.render_poly_line(element),
             "rect" => self.render_rect(element),
             "svg" => self.render_svg(element),
+            "text" => {
+                // The children of text content elements are rendered as part
+                // of the text.
+                self.render_text(element);
+                return;
+            }
+            "use" => {
+                // The referenced element is rendered instead of the children.
+                self.render_use(node, element);
+                return;
+            }
             _ => (),
         }
 
@@ -93,12 +200,14 @@ impl<'painter> SvgRenderer<'painter> {
         }
         let radius = Point2D::new(radius, radius);
 
-        let fill = element.property_fill();
+        let bounds = Rect::new(center - radius.to_vector(), (radius * 2.0).to_vector().to_size());
+
+        let fill = element.property_fill().resolve_bounding_box(bounds);
         if !fill.is_transparent() {
             self.painter.draw_ellipse(center, radius, fill);
         }
 
-        let stroke = element.property_stroke();
+        let stroke = element.property_stroke().resolve_bounding_box(bounds);
         let stroke_width = element.property_stroke_width();
         if !stroke.is_transparent() && stroke_width > 0.0 {
             let stroke_style = element.stroke_style(self.painter);
@@ -119,12 +228,14 @@ impl<'painter> SvgRenderer<'painter> {
             return;
         }
 
-        let fill = element.property_fill();
+        let bounds = Rect::new(center - radius.to_vector(), (radius * 2.0).to_vector().to_size());
+
+        let fill = element.property_fill().resolve_bounding_box(bounds);
         if !fill.is_transparent() {
             self.painter.draw_ellipse(center, radius, fill);
         }
 
-        let stroke = element.property_stroke();
+        let stroke = element.property_stroke().resolve_bounding_box(bounds);
         let stroke_width = element.property_stroke_width();
         if !stroke.is_transparent() && stroke_width > 0.0 {
             let stroke_style = element.stroke_style(self.painter);
@@ -140,7 +251,8 @@ impl<'painter> SvgRenderer<'painter> {
         // one-dimensional, they have no interior; thus, ‘line’ elements are
         // never filled (see the fill property).
 
-        let stroke = element.property_stroke();
+        let bounds = Box2D::from_points([start, end]).to_rect();
+        let stroke = element.property_stroke().resolve_bounding_box(bounds);
         let stroke_width = element.property_stroke_width();
         if !stroke.is_transparent() && stroke_width > 0.0 {
             let stroke_style = element.stroke_style(self.painter);
@@ -174,53 +286,17 @@ impl<'painter> SvgRenderer<'painter> {
         info!("Parsed path data: {path:#?}");
 
         let mut sink = self.painter.create_geometry(GeometrySinkFillType::Filled);
-
-        for command in path.commands {
-            match command {
-                SvgPathCommand::MoveTo(ty, coords_sequence) => {
-                    sink.move_to(ty, *coords_sequence.0.first().unwrap());
-                    for coords in &coords_sequence.0[1..] {
-                        sink.line_to(ty, *coords);
-                    }
-                }
-                SvgPathCommand::LineTo(ty, coords_sequence) => {
-                    for coords in coords_sequence.0 {
-                        sink.line_to(ty, coords);
-                    }
-                }
-                SvgPathCommand::HorizontalLineTo(ty, lines) => sink.horizontal_lines_to(ty, lines),
-                SvgPathCommand::VerticalLineTo(ty, lines) => sink.vertical_lines_to(ty, lines),
-                SvgPathCommand::SmoothCurveTo(ty, sequence) => {
-                    for double in sequence.0 {
-                        sink.smooth_curve_to(ty, double);
-                    }
-                }
-                SvgPathCommand::CurveTo(ty, sequence) => sink.curve_to(ty, sequence),
-                SvgPathCommand::QuadraticBezierCurveTo(ty, sequence) => {
-                    sink.quadratic_beziers_curve_to(ty, sequence)
-                }
-                SvgPathCommand::SmoothQuadraticBezierCurveTo(ty, sequence) => {
-                    for pair in sequence.0 {
-                        sink.smooth_quadratic_bezier_curve_to(ty, pair)
-                    }
-                }
-                SvgPathCommand::EllipticArc(ty, sequence) => {
-                    for argument in sequence.0 {
-                        sink.elliptic_arc(ty, argument);
-                    }
-                }
-                SvgPathCommand::ClosePath => sink.close_path(),
-            }
-        }
+        add_path_to_sink(sink.as_mut(), path);
 
         let geometry = sink.finish();
+        let bounds = geometry.bounds();
         if !fill.is_transparent() {
-            self.painter.draw_geometry(geometry.as_ref(), fill);
+            self.painter.draw_geometry(geometry.as_ref(), fill.resolve_bounding_box(bounds));
         }
 
         if !stroke.is_transparent() && stroke_width > 0.0 {
             let stroke_style = element.stroke_style(self.painter);
-            self.painter.stroke_geometry(geometry.as_ref(), stroke, stroke_width, stroke_style.as_deref());
+            self.painter.stroke_geometry(geometry.as_ref(), stroke.resolve_bounding_box(bounds), stroke_width, stroke_style.as_deref());
         }
     }
 
@@ -248,13 +324,14 @@ impl<'painter> SvgRenderer<'painter> {
 
         let rect = Box2D::new(min, max);
         let radius = element.properties_radii();
+        let bounds = Rect::new(min, Size2D::new(max.x, max.y));
 
-        let fill = element.property_fill();
+        let fill = element.property_fill().resolve_bounding_box(bounds);
         if !fill.is_transparent() {
             self.painter.draw_rect(rect, fill, radius);
         }
 
-        let stroke = element.property_stroke();
+        let stroke = element.property_stroke().resolve_bounding_box(bounds);
         let stroke_width = element.property_stroke_width();
         if !stroke.is_transparent() && stroke_width > 0.0 {
             let stroke_style = element.stroke_style(self.painter);
@@ -320,31 +397,98 @@ impl<'painter> SvgRenderer<'painter> {
         }
 
         let geometry = geometry.finish();
+        let bounds = geometry.bounds();
         let stroke_style = element.stroke_style(self.painter);
 
         if should_fill {
-            self.painter.draw_geometry(geometry.as_ref(), fill);
+            self.painter.draw_geometry(geometry.as_ref(), fill.resolve_bounding_box(bounds));
         }
 
         if should_stroke {
-            self.painter.stroke_geometry(geometry.as_ref(), stroke, stroke_width, stroke_style.as_deref());
+            self.painter.stroke_geometry(geometry.as_ref(), stroke.resolve_bounding_box(bounds), stroke_width, stroke_style.as_deref());
+        }
+    }
+}
+
+/// Add the commands of the `path` to the `sink`.
+fn add_path_to_sink(sink: &mut dyn GeometrySink, path: SvgPath) {
+    for command in path.commands {
+        match command {
+            SvgPathCommand::MoveTo(ty, coords_sequence) => {
+                sink.move_to(ty, *coords_sequence.0.first().unwrap());
+                for coords in &coords_sequence.0[1..] {
+                    sink.line_to(ty, *coords);
+                }
+            }
+            SvgPathCommand::LineTo(ty, coords_sequence) => {
+                for coords in coords_sequence.0 {
+                    sink.line_to(ty, coords);
+                }
+            }
+            SvgPathCommand::HorizontalLineTo(ty, lines) => sink.horizontal_lines_to(ty, lines),
+            SvgPathCommand::VerticalLineTo(ty, lines) => sink.vertical_lines_to(ty, lines),
+            SvgPathCommand::SmoothCurveTo(ty, sequence) => {
+                for double in sequence.0 {
+                    sink.smooth_curve_to(ty, double);
+                }
+            }
+            SvgPathCommand::CurveTo(ty, sequence) => sink.curve_to(ty, sequence),
+            SvgPathCommand::QuadraticBezierCurveTo(ty, sequence) => {
+                sink.quadratic_beziers_curve_to(ty, sequence)
+            }
+            SvgPathCommand::SmoothQuadraticBezierCurveTo(ty, sequence) => {
+                for pair in sequence.0 {
+                    sink.smooth_quadratic_bezier_curve_to(ty, pair)
+                }
+            }
+            SvgPathCommand::EllipticArc(ty, sequence) => {
+                for argument in sequence.0 {
+                    sink.elliptic_arc(ty, argument);
+                }
+            }
+            SvgPathCommand::ClosePath => sink.close_path(),
+        }
+    }
+}
+
+fn parse_length(name: &str, length: &str) -> Option<f32> {
+    if let Ok(float) = length.parse() {
+        return Some(float);
+    }
+
+    match retina_style_parser::parse_value_length(length) {
+        Some(CssLength::Pixels(pixels)) => Some(pixels as f32),
+
+        unsupported => {
+            warn!("Unsupported length type: {unsupported:?} for property \"{name}\"");
+            None
         }
     }
 }
 
 trait SvgElementTraits {
     fn str_property(&self, name: &str) -> &str;
+
+    /// Get the value of an inherited property, which is looked up on the
+    /// ancestors (up to the `<svg>` element) if it isn't specified on this
+    /// element.
+    fn inherited_str_property(&self, name: &str) -> Option<String>;
+
+    /// Find the element with the `id` in the tree of this element.
+    fn find_referenced_element(&self, id: &str) -> Option<Node>;
     fn length_property_ext(&self, name: &str, default: f32) -> f32;
     fn paint_property_ext(&self, name: &str, default: Material) -> Material;
     fn cap_style_property(&self, name: &str) -> CapStyle {
-        match self.str_property(name) {
-            "butt" => CapStyle::Butt,
-            "round" => CapStyle::Round,
-            "square" => CapStyle::Square,
-            _ => return CapStyle::default(),
+        match self.inherited_str_property(name).as_deref() {
+            Some("butt") => CapStyle::Butt,
+            Some("round") => CapStyle::Round,
+            Some("square") => CapStyle::Square,
+            _ => CapStyle::default(),
         }
     }
 
+    fn optional_length_property(&self, name: &str) -> Option<f32>;
+
     #[inline]
     fn length_property(&self, name: &str) -> f32 {
         self.length_property_ext(name, 0.0)
@@ -366,7 +510,62 @@ trait SvgElementTraits {
     fn property_y(&self) -> f32 { self.length_property("y") }
     fn property_width(&self) -> f32 { self.length_property("width") }
     fn property_height(&self) -> f32 { self.length_property("height") }
-    fn property_stroke_width(&self) -> f32 { self.length_property_ext("stroke-width", 1.0) }
+    fn property_stroke_width(&self) -> f32 {
+        self.inherited_str_property("stroke-width")
+            .and_then(|value| parse_length("stroke-width", &value))
+            .unwrap_or(1.0)
+    }
+
+    /// The lengths of the alternating dashes and gaps, or [`None`] if the
+    /// stroke is solid.
+    ///
+    /// <https://www.w3.org/TR/SVG11/painting.html#StrokeDasharrayProperty>
+    fn stroke_dasharray_property(&self) -> Option<Vec<f64>> {
+        let value = self.inherited_str_property("stroke-dasharray")?;
+        let value = value.trim();
+        if value.is_empty() || value.eq_ignore_ascii_case("none") {
+            return None;
+        }
+
+        let mut dashes = Vec::new();
+        for length in value.split([',', ' ', '\t', '\n', '\r']).filter(|length| !length.is_empty()) {
+            let length = parse_length("stroke-dasharray", length)?;
+
+            // A negative value is an error, which renders the stroke solid.
+            if length < 0.0 {
+                warn!("Negative length in stroke-dasharray: {value}");
+                return None;
+            }
+
+            dashes.push(length as f64);
+        }
+
+        // If the sum of the values is zero, then the stroke is rendered as if
+        // a value of none were specified.
+        if dashes.iter().sum::<f64>() <= 0.0 {
+            return None;
+        }
+
+        // If an odd number of values is provided, then the list of values is
+        // repeated to yield an even number of values.
+        if dashes.len() % 2 == 1 {
+            dashes.extend_from_within(..);
+        }
+
+        Some(dashes)
+    }
+
+    /// <https://www.w3.org/TR/SVG11/painting.html#StrokeDashoffsetProperty>
+    fn stroke_dashoffset_property(&self) -> f64 {
+        self.inherited_str_property("stroke-dashoffset")
+            .and_then(|value| parse_length("stroke-dashoffset", value.trim()))
+            .unwrap_or_default() as f64
+    }
+
+    /// <https://www.w3.org/TR/SVG11/coords.html#TransformAttribute>
+    fn property_transform(&self) -> Transform2D<f32> {
+        path::parse_svg_transform(self.str_property("transform")).cast()
+    }
 
     fn property_view_box(&self) -> Option<Rect<f32>>;
     fn stroke_style(&self, painter: &dyn Painter) -> Option<Box<dyn StrokeStyle>>;
@@ -427,24 +626,37 @@ impl SvgElementTraits for Element {
         self.attributes().find_by_str(name).unwrap_or_default()
     }
 
+    fn inherited_str_property(&self, name: &str) -> Option<String> {
+        if let Some(value) = self.attributes().find_by_str(name) {
+            return Some(value.to_string());
+        }
+
+        // Don't go above the <svg> element, because that is outside the SVG
+        // figure.
+        if self.qualified_name().local.eq_str_ignore_ascii_case("svg") {
+            return None;
+        }
+
+        let parent = self.as_node().parent().as_ref().and_then(Weak::upgrade)?;
+        parent.as_dom_element()?.inherited_str_property(name)
+    }
+
+    fn find_referenced_element(&self, id: &str) -> Option<Node> {
+        let parent = self.as_node().parent().as_ref().and_then(Weak::upgrade)?;
+        find_element_by_id(Node::from(parent), id)
+    }
+
     fn length_property_ext(&self, name: &str, default: f32) -> f32 {
         let Some(length) = self.attributes().find_by_str(name) else {
             println!("[Svg] Attribute \"{name}\" not found on element \"{}\"", self.qualified_name().local);
             return default;
         };
 
-        if let Ok(float) = length.parse() {
-            return float;
-        }
-
-        match retina_style_parser::parse_value_length(length) {
-            Some(CssLength::Pixels(pixels)) => pixels as f32,
+        parse_length(name, length).unwrap_or(default)
+    }
 
-            unsupported => {
-                warn!("Unsupported length type: {unsupported:?} for property \"{name}\"");
-                default
-            }
-        }
+    fn optional_length_property(&self, name: &str) -> Option<f32> {
+        parse_length(name, self.attributes().find_by_str(name)?)
     }
 
     fn paint_property_ext(&self, name: &str, default: Material) -> Material {
@@ -464,6 +676,31 @@ impl SvgElementTraits for Element {
             return default;
         };
 
+        let mut color = color.trim();
+
+        // A reference to a paint server, e.g. a gradient, which is optionally
+        // followed by the fallback value in case the reference is invalid.
+        if let Some(reference) = color.strip_prefix("url(") {
+            let Some((reference, fallback)) = reference.split_once(')') else {
+                warn!("Invalid paint server reference: {color}");
+                return default;
+            };
+
+            let reference = reference.trim().trim_matches(['"', '\'']);
+            let paint_server = reference.strip_prefix('#')
+                .and_then(|id| self.find_referenced_element(id))
+                .and_then(|node| gradient::resolve_gradient(&node));
+            if let Some(paint_server) = paint_server {
+                return paint_server;
+            }
+
+            warn!("Invalid paint server reference: {reference}");
+            color = fallback.trim();
+            if color.is_empty() {
+                return Material::Color(Color::TRANSPARENT);
+            }
+        }
+
         if color.eq_ignore_ascii_case("none") {
             return Material::Color(Color::TRANSPARENT);
         }
@@ -506,6 +743,64 @@ impl SvgElementTraits for Element {
             cap_style_dash: cap_style,
             cap_style_end: cap_style,
             cap_style_start: cap_style,
+            dash_pattern: self.stroke_dasharray_property(),
+            dash_offset: self.stroke_dashoffset_property(),
         }))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use crate::test_util::{render, PaintCommand};
+
+    /// The dash pattern and offset of the strokes of the `svg`.
+    fn dashes(svg: &str) -> Vec<(Option<Vec<f64>>, f64)> {
+        render(svg)
+            .into_iter()
+            .filter_map(|command| match command {
+                PaintCommand::Stroke { dash_pattern, dash_offset, .. } => Some((dash_pattern, dash_offset)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn line(attributes: &str) -> String {
+        format!(r#"<svg><line x1="0" y1="0" x2="100" y2="0" stroke="black" {attributes} /></svg>"#)
+    }
+
+    #[rstest]
+    #[case("", None)]
+    #[case(r#"stroke-dasharray="none""#, None)]
+    #[case(r#"stroke-dasharray="5 10""#, Some(vec![5.0, 10.0]))]
+    #[case(r#"stroke-dasharray="5,10""#, Some(vec![5.0, 10.0]))]
+    #[case(r#"stroke-dasharray=" 5 ,	10
+        15 20 ""#, Some(vec![5.0, 10.0, 15.0, 20.0]))]
+    #[case(r#"stroke-dasharray="5 10 15""#, Some(vec![5.0, 10.0, 15.0, 5.0, 10.0, 15.0]))]
+    #[case(r#"stroke-dasharray="5""#, Some(vec![5.0, 5.0]))]
+    #[case(r#"stroke-dasharray="5 -10""#, None)]
+    #[case(r#"stroke-dasharray="0 0""#, None)]
+    #[case(r#"stroke-dasharray="0""#, None)]
+    fn stroke_dasharray(#[case] attributes: &str, #[case] expected: Option<Vec<f64>>) {
+        assert_eq!(dashes(&line(attributes)), [(expected, 0.0)]);
+    }
+
+    #[rstest]
+    #[case(r#"stroke-dashoffset="3""#, 3.0)]
+    #[case(r#"stroke-dashoffset=" -2.5 ""#, -2.5)]
+    #[case(r#"stroke-dashoffset="invalid""#, 0.0)]
+    fn stroke_dashoffset(#[case] attributes: &str, #[case] expected: f64) {
+        let attributes = format!(r#"stroke-dasharray="5 10" {attributes}"#);
+        assert_eq!(dashes(&line(&attributes)), [(Some(vec![5.0, 10.0]), expected)]);
+    }
+
+    #[test]
+    fn stroke_dasharray_is_inherited() {
+        let svg = r#"<svg><g stroke-dasharray="4" stroke-dashoffset="1">
+            <line x1="0" y1="0" x2="100" y2="0" stroke="black" />
+        </g></svg>"#;
+        assert_eq!(dashes(svg), [(Some(vec![4.0, 4.0]), 1.0)]);
+    }
+}
@@ -3,8 +3,10 @@
 
 use std::any::Any;
 
-use euclid::default::{Box2D, Point2D, Rect, Size2D};
+use euclid::default::{Box2D, Point2D, Rect, Size2D, Transform2D};
+use image::DynamicImage;
 use retina_common::Color;
+use retina_gfx_font::FontHandle;
 
 use crate::path::{
     SvgPathCoordinatePair,
@@ -16,17 +18,100 @@ use crate::path::{
     SvgPathType,
 };
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Material {
     Color(Color),
+    Gradient(Gradient),
 }
 
 impl Material {
     pub fn is_transparent(&self) -> bool {
         match self {
             Self::Color(color) => color.alpha() <= 0.0,
+            Self::Gradient(gradient) => gradient.stops.iter().all(|stop| stop.color.alpha() <= 0.0),
         }
     }
+
+    /// Resolve the gradient coordinates that are relative to the bounding
+    /// box of the painted element, given its `bounds`. The painters only
+    /// receive gradients in [user space][GradientUnits::UserSpaceOnUse].
+    pub fn resolve_bounding_box(self, bounds: Rect<f32>) -> Self {
+        let Self::Gradient(mut gradient) = self else {
+            return self;
+        };
+
+        if gradient.units == GradientUnits::UserSpaceOnUse {
+            return Self::Gradient(gradient);
+        }
+
+        // When the geometry of the applicable element has no width or no
+        // height, such as the case of a horizontal or vertical line, the
+        // gradient will not be rendered.
+        if bounds.is_empty() {
+            return Self::Color(Color::TRANSPARENT);
+        }
+
+        gradient.transform = gradient.transform.then(
+            &Transform2D::scale(bounds.width(), bounds.height())
+                .then_translate(bounds.origin.to_vector())
+        );
+        gradient.units = GradientUnits::UserSpaceOnUse;
+        Self::Gradient(gradient)
+    }
+}
+
+/// A `<linearGradient>` or `<radialGradient>` paint server.
+///
+/// <https://www.w3.org/TR/SVG11/pservers.html>
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    pub kind: GradientKind,
+
+    /// The stops, of which the offsets are in the range `0.0..=1.0` and
+    /// ascending.
+    pub stops: Vec<GradientStop>,
+    pub spread_method: GradientSpreadMethod,
+
+    /// The transform from the gradient coordinate system to the coordinate
+    /// system given by the `units`.
+    pub transform: Transform2D<f32>,
+    pub units: GradientUnits,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GradientKind {
+    Linear {
+        start: Point2D<f32>,
+        end: Point2D<f32>,
+    },
+    Radial {
+        center: Point2D<f32>,
+        focal_point: Point2D<f32>,
+        radius: f32,
+    },
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GradientStop {
+    pub offset: f32,
+    pub color: Color,
+}
+
+/// <https://www.w3.org/TR/SVG11/pservers.html#LinearGradientElementSpreadMethodAttribute>
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum GradientSpreadMethod {
+    #[default]
+    Pad,
+    Reflect,
+    Repeat,
+}
+
+/// <https://www.w3.org/TR/SVG11/pservers.html#LinearGradientElementGradientUnitsAttribute>
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum GradientUnits {
+    UserSpaceOnUse,
+    #[default]
+    ObjectBoundingBox,
 }
 
 pub trait Painter {
@@ -36,10 +121,28 @@ pub trait Painter {
 
     fn push_view_box(&self, view_box: Rect<f32>);
 
+    /// Apply the `transform` to everything that is drawn until the matching
+    /// [`Painter::pop_transform`] call.
+    fn push_transform(&mut self, transform: Transform2D<f32>);
+    fn pop_transform(&mut self);
+
+    /// Only paint inside the filled area of the `geometry` until the matching
+    /// [`Painter::pop_clip_geometry`] call.
+    fn push_clip_geometry(&mut self, geometry: &dyn Geometry);
+    fn pop_clip_geometry(&mut self);
+
     fn draw_ellipse(&mut self, center: Point2D<f32>, radius: Point2D<f32>, material: Material);
     fn draw_geometry(&mut self, geometry: &dyn Geometry, material: Material);
+
+    /// Draw the `source_rect` part of the `image` (in pixels), scaled to the
+    /// `destination_rect`.
+    fn draw_image(&mut self, image: &DynamicImage, source_rect: Rect<f32>, destination_rect: Rect<f32>);
     fn draw_rect(&mut self, rect: Box2D<f32>, material: Material, radius: Point2D<f32>);
 
+    /// Draw the `text` with the `font`, where the `position` is the top-left
+    /// of the text run, like [`retina_gfx_font::Font::paint`].
+    fn draw_text(&mut self, text: &str, position: Point2D<f32>, font: &FontHandle, font_size: f32, material: Material);
+
     fn stroke_ellipse(&mut self, center: Point2D<f32>, radius: Point2D<f32>, material: Material, width: f32, stroke_style: Option<&dyn StrokeStyle>);
     fn stroke_geometry(&mut self, geometry: &dyn Geometry, material: Material, width: f32, stroke_style: Option<&dyn StrokeStyle>);
     fn stroke_line(&mut self, start: Point2D<f32>, end: Point2D<f32>, material: Material, width: f32, stroke_style: Option<&dyn StrokeStyle>);
@@ -55,6 +158,13 @@ pub struct StrokeStyleProperties {
     pub cap_style_dash: CapStyle,
     pub cap_style_start: CapStyle,
     pub cap_style_end: CapStyle,
+
+    /// The lengths of the alternating dashes and gaps in user units, or
+    /// [`None`] for a solid stroke.
+    pub dash_pattern: Option<Vec<f64>>,
+
+    /// The distance into the dash pattern at which the stroke starts.
+    pub dash_offset: f64,
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
@@ -67,6 +177,9 @@ pub enum CapStyle {
 
 pub trait Geometry {
     fn as_any(&self) -> &dyn Any;
+
+    /// The bounding box of the geometry, without the stroke.
+    fn bounds(&self) -> Rect<f32>;
 }
 
 pub trait GeometrySink {
@@ -6,8 +6,10 @@ mod parse_command;
 mod parse_coordinate;
 mod parse_elliptic;
 mod parse_literal;
+mod parse_transform;
 
 pub use parse_command::parse_path;
+pub use parse_transform::parse_svg_transform;
 pub use error::{IResult, PathError};
 
 /// The type of number in our representation.
@@ -0,0 +1,162 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+use euclid::{
+    default::{Transform2D, Vector2D},
+    Angle,
+};
+use log::warn;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::char,
+    combinator::{all_consuming, map_opt},
+    multi::many0,
+    sequence::{delimited, preceded, terminated, tuple},
+};
+
+use super::{
+    IResult,
+    SvgNumber,
+    parse_coordinate::{parse_comma_wsp, parse_coordinate, parse_wsp},
+};
+
+/// Parse the value of the [`transform`][spec] attribute into a single matrix.
+/// An invalid value results in the identity matrix, i.e. the attribute is
+/// ignored.
+///
+/// [spec]: https://www.w3.org/TR/SVG11/coords.html#TransformAttribute
+pub fn parse_svg_transform(input: &str) -> Transform2D<SvgNumber> {
+    match parse_transform_list(input) {
+        Ok((_, transform)) => transform,
+        Err(e) => {
+            warn!("Invalid transform \"{input}\": {e}");
+            Transform2D::identity()
+        }
+    }
+}
+
+/// Parse a list of transform functions, which are applied in the order from
+/// right to left, i.e. the transformation of the last function is applied
+/// first.
+pub fn parse_transform_list(input: &str) -> IResult<&str, Transform2D<SvgNumber>> {
+    let (input, transforms) = all_consuming(preceded(
+        many0(parse_wsp),
+        many0(terminated(
+            parse_transform,
+            many0(parse_comma_wsp),
+        )),
+    ))(input)?;
+
+    let transform = transforms.into_iter()
+        .fold(Transform2D::identity(), |result, transform| transform.then(&result));
+
+    Ok((input, transform))
+}
+
+/// Parse a single transform function.
+pub fn parse_transform(input: &str) -> IResult<&str, Transform2D<SvgNumber>> {
+    alt((
+        map_opt(parse_transform_function("matrix"), |arguments| match arguments[..] {
+            [a, b, c, d, e, f] => Some(Transform2D::new(a, b, c, d, e, f)),
+            _ => None,
+        }),
+        map_opt(parse_transform_function("translate"), |arguments| match arguments[..] {
+            [tx] => Some(Transform2D::translation(tx, 0.0)),
+            [tx, ty] => Some(Transform2D::translation(tx, ty)),
+            _ => None,
+        }),
+        map_opt(parse_transform_function("scale"), |arguments| match arguments[..] {
+            [s] => Some(Transform2D::scale(s, s)),
+            [sx, sy] => Some(Transform2D::scale(sx, sy)),
+            _ => None,
+        }),
+        map_opt(parse_transform_function("rotate"), |arguments| match arguments[..] {
+            [angle] => Some(Transform2D::rotation(Angle::degrees(angle))),
+
+            // The rotation is about the point (cx, cy), which is equivalent to
+            // translate(cx, cy) rotate(angle) translate(-cx, -cy).
+            [angle, cx, cy] => Some(
+                Transform2D::translation(-cx, -cy)
+                    .then_rotate(Angle::degrees(angle))
+                    .then_translate(Vector2D::new(cx, cy))
+            ),
+            _ => None,
+        }),
+        map_opt(parse_transform_function("skewX"), |arguments| match arguments[..] {
+            [angle] => Some(Transform2D::new(1.0, 0.0, Angle::degrees(angle).radians.tan(), 1.0, 0.0, 0.0)),
+            _ => None,
+        }),
+        map_opt(parse_transform_function("skewY"), |arguments| match arguments[..] {
+            [angle] => Some(Transform2D::new(1.0, Angle::degrees(angle).radians.tan(), 0.0, 1.0, 0.0, 0.0)),
+            _ => None,
+        }),
+    ))(input)
+}
+
+/// Parse a transform function with the given `name`, returning its
+/// arguments, which are validated by the caller.
+fn parse_transform_function<'input>(name: &'static str) -> impl FnMut(&'input str) -> IResult<&'input str, Vec<SvgNumber>> {
+    delimited(
+        tuple((
+            tag(name),
+            many0(parse_wsp),
+            char('('),
+            many0(parse_wsp),
+        )),
+        many0(terminated(
+            parse_coordinate,
+            many0(parse_comma_wsp),
+        )),
+        char(')'),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use pretty_assertions::assert_eq;
+
+    #[rstest]
+    #[case("", Transform2D::identity())]
+    #[case("  ", Transform2D::identity())]
+    #[case("matrix(1 2 3 4 5 6)", Transform2D::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0))]
+    #[case("matrix(1,2,3,4,5,6)", Transform2D::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0))]
+    #[case("translate(10)", Transform2D::translation(10.0, 0.0))]
+    #[case("translate(10, -20)", Transform2D::translation(10.0, -20.0))]
+    #[case("translate ( 10 -20 )", Transform2D::translation(10.0, -20.0))]
+    #[case("scale(2)", Transform2D::scale(2.0, 2.0))]
+    #[case("scale(2 .5)", Transform2D::scale(2.0, 0.5))]
+    #[case("translate(10,20) scale(2)", Transform2D::new(2.0, 0.0, 0.0, 2.0, 10.0, 20.0))]
+    #[case("scale(2), translate(10,20)", Transform2D::new(2.0, 0.0, 0.0, 2.0, 20.0, 40.0))]
+    #[case("translate(1)scale(2)", Transform2D::new(2.0, 0.0, 0.0, 2.0, 1.0, 0.0))]
+    fn transform_list(#[case] input: &str, #[case] expected: Transform2D<SvgNumber>) {
+        assert_eq!(parse_transform_list(input), Ok(("", expected)));
+    }
+
+    #[rstest]
+    #[case("rotate(90)", Transform2D::new(0.0, 1.0, -1.0, 0.0, 0.0, 0.0))]
+    #[case("rotate(90 10 20)", Transform2D::new(0.0, 1.0, -1.0, 0.0, 30.0, 10.0))]
+    #[case("skewX(45)", Transform2D::new(1.0, 0.0, 1.0, 1.0, 0.0, 0.0))]
+    #[case("skewY(45)", Transform2D::new(1.0, 1.0, 0.0, 1.0, 0.0, 0.0))]
+    fn transform_list_approximately(#[case] input: &str, #[case] expected: Transform2D<SvgNumber>) {
+        let (remaining, transform) = parse_transform_list(input).unwrap();
+        assert_eq!(remaining, "");
+        assert!(transform.approx_eq(&expected), "{transform:?} != {expected:?}");
+    }
+
+    #[rstest]
+    #[case("translate()")]
+    #[case("translate(1 2 3)")]
+    #[case("matrix(1 2 3 4 5)")]
+    #[case("rotate(1 2)")]
+    #[case("skewX(1, 2)")]
+    #[case("scale(2")]
+    #[case("unknown(2)")]
+    #[case("translate(10) garbage")]
+    fn transform_list_invalid(#[case] input: &str) {
+        assert!(parse_transform_list(input).is_err());
+        assert_eq!(parse_svg_transform(input), Transform2D::identity());
+    }
+}
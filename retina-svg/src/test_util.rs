@@ -0,0 +1,458 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! Utilities for the tests of this crate, which render the figures with a
+//! [`Painter`] that records the paint commands instead of drawing them, such
+//! that the tests don't depend on the painter of a platform.
+
+use std::{any::Any, cell::RefCell, path::Path, sync::Arc};
+
+use euclid::default::{Box2D, Point2D, Rect, Size2D, Transform2D};
+use image::DynamicImage;
+use retina_common::{Color, LoadTime};
+use retina_dom::Node;
+use retina_gfx_font::{
+    Font,
+    FontDescriptor,
+    FontHandle,
+    FontProvider,
+    FontProviderBackend,
+    TextHintingOptions,
+};
+
+use crate::{
+    path::{
+        SvgPathCoordinatePair,
+        SvgPathCoordinatePairDouble,
+        SvgPathCoordinatePairDoubleSequence,
+        SvgPathCoordinatePairTripletSequence,
+        SvgPathCoordinateSequence,
+        SvgPathEllipticArcArgument,
+        SvgPathType,
+    },
+    Geometry,
+    GeometrySink,
+    GeometrySinkFillType,
+    Material,
+    Painter,
+    StrokeStyle,
+    StrokeStyleProperties,
+    SvgResources,
+};
+
+/// A command given to the [`RecordingPainter`].
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum PaintCommand {
+    PushTransform(Transform2D<f32>),
+    PopTransform,
+    PushClip(Vec<PathCommand>),
+    PopClip,
+    Ellipse {
+        center: Point2D<f32>,
+        radius: Point2D<f32>,
+        material: Material,
+    },
+    Geometry {
+        path: Vec<PathCommand>,
+        material: Material,
+    },
+    Image {
+        destination_rect: Rect<f32>,
+    },
+    Rect {
+        rect: Box2D<f32>,
+        material: Material,
+    },
+    Text {
+        text: String,
+        position: Point2D<f32>,
+        font_size: f32,
+        material: Material,
+    },
+    Stroke {
+        material: Material,
+        width: f32,
+        dash_pattern: Option<Vec<f64>>,
+        dash_offset: f64,
+    },
+}
+
+/// A segment of a recorded geometry, in absolute coordinates.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) enum PathCommand {
+    MoveTo(Point2D<f32>),
+    LineTo(Point2D<f32>),
+    ArcTo {
+        radius: Point2D<f32>,
+        end: Point2D<f32>,
+    },
+    CurveTo(Point2D<f32>),
+    Close,
+}
+
+/// Render the first `<svg>` element of the `html` document.
+pub(crate) fn render(html: &str) -> Vec<PaintCommand> {
+    render_with_resources(html, SvgResources::default())
+}
+
+/// Render the first `<svg>` element of the `html` document, with a font
+/// provider of which every font is a [`TestFont`].
+pub(crate) fn render_with_test_font(html: &str) -> Vec<PaintCommand> {
+    render_with_resources(html, SvgResources {
+        font_provider: Some(FontProvider::new(Arc::new(TestFontProvider))),
+        ..Default::default()
+    })
+}
+
+fn render_with_resources(html: &str, resources: SvgResources) -> Vec<PaintCommand> {
+    let document = retina_dom::Parser::parse(html);
+
+    let mut svg = None;
+    document.for_each_child_node_recursive_handle(&mut |node| {
+        if svg.is_none() && node.tag_name() == Some("svg") {
+            svg = Some(node.clone());
+        }
+    });
+    let svg: Node = svg.expect("document has no <svg> element");
+
+    let mut painter = RecordingPainter::default();
+    crate::render_with_resources(&svg, &mut painter, resources);
+    painter.commands.into_inner()
+}
+
+/// The commands that draw something, i.e. without the transform and clip
+/// commands.
+pub(crate) fn drawing_commands(commands: Vec<PaintCommand>) -> Vec<PaintCommand> {
+    commands.into_iter()
+        .filter(|command| !matches!(
+            command,
+            PaintCommand::PushTransform(..) | PaintCommand::PopTransform | PaintCommand::PushClip(..) | PaintCommand::PopClip
+        ))
+        .collect()
+}
+
+/// The materials of the commands that draw something.
+pub(crate) fn materials(commands: Vec<PaintCommand>) -> Vec<Material> {
+    commands.into_iter()
+        .filter_map(|command| match command {
+            PaintCommand::Ellipse { material, .. }
+                | PaintCommand::Geometry { material, .. }
+                | PaintCommand::Rect { material, .. }
+                | PaintCommand::Text { material, .. }
+                | PaintCommand::Stroke { material, .. } => Some(material),
+            _ => None,
+        })
+        .collect()
+}
+
+#[derive(Default)]
+struct RecordingPainter {
+    commands: RefCell<Vec<PaintCommand>>,
+}
+
+impl RecordingPainter {
+    fn push(&self, command: PaintCommand) {
+        self.commands.borrow_mut().push(command);
+    }
+
+    fn push_stroke(&self, material: Material, width: f32, style: Option<&dyn StrokeStyle>) {
+        let style = style.map(|style| {
+            style.as_any()
+                .downcast_ref::<RecordingStrokeStyle>()
+                .expect("stroke style wasn't created by the RecordingPainter")
+        });
+
+        self.push(PaintCommand::Stroke {
+            material,
+            width,
+            dash_pattern: style.and_then(|style| style.dash_pattern.clone()),
+            dash_offset: style.map_or(0.0, |style| style.dash_offset),
+        });
+    }
+}
+
+impl Painter for RecordingPainter {
+    fn set_size(&self, _: Size2D<f32>) {}
+
+    fn create_geometry(&self, _: GeometrySinkFillType) -> Box<dyn GeometrySink> {
+        Box::<RecordingGeometry>::default()
+    }
+
+    fn create_stroke_style(&self, properties: StrokeStyleProperties) -> Box<dyn StrokeStyle> {
+        Box::new(RecordingStrokeStyle {
+            dash_pattern: properties.dash_pattern,
+            dash_offset: properties.dash_offset,
+        })
+    }
+
+    fn push_view_box(&self, _: Rect<f32>) {}
+
+    fn push_transform(&mut self, transform: Transform2D<f32>) {
+        self.push(PaintCommand::PushTransform(transform));
+    }
+
+    fn pop_transform(&mut self) {
+        self.push(PaintCommand::PopTransform);
+    }
+
+    fn push_clip_geometry(&mut self, geometry: &dyn Geometry) {
+        self.push(PaintCommand::PushClip(path_of(geometry)));
+    }
+
+    fn pop_clip_geometry(&mut self) {
+        self.push(PaintCommand::PopClip);
+    }
+
+    fn draw_ellipse(&mut self, center: Point2D<f32>, radius: Point2D<f32>, material: Material) {
+        self.push(PaintCommand::Ellipse { center, radius, material });
+    }
+
+    fn draw_geometry(&mut self, geometry: &dyn Geometry, material: Material) {
+        self.push(PaintCommand::Geometry { path: path_of(geometry), material });
+    }
+
+    fn draw_image(&mut self, _: &DynamicImage, _: Rect<f32>, destination_rect: Rect<f32>) {
+        self.push(PaintCommand::Image { destination_rect });
+    }
+
+    fn draw_rect(&mut self, rect: Box2D<f32>, material: Material, _: Point2D<f32>) {
+        self.push(PaintCommand::Rect { rect, material });
+    }
+
+    fn draw_text(&mut self, text: &str, position: Point2D<f32>, _: &FontHandle, font_size: f32, material: Material) {
+        self.push(PaintCommand::Text {
+            text: text.to_string(),
+            position,
+            font_size,
+            material,
+        });
+    }
+
+    fn stroke_ellipse(&mut self, _: Point2D<f32>, _: Point2D<f32>, material: Material, width: f32, style: Option<&dyn StrokeStyle>) {
+        self.push_stroke(material, width, style);
+    }
+
+    fn stroke_geometry(&mut self, _: &dyn Geometry, material: Material, width: f32, style: Option<&dyn StrokeStyle>) {
+        self.push_stroke(material, width, style);
+    }
+
+    fn stroke_line(&mut self, _: Point2D<f32>, _: Point2D<f32>, material: Material, width: f32, style: Option<&dyn StrokeStyle>) {
+        self.push_stroke(material, width, style);
+    }
+
+    fn stroke_rect(&mut self, _: Box2D<f32>, material: Material, _: Point2D<f32>, width: f32, style: Option<&dyn StrokeStyle>) {
+        self.push_stroke(material, width, style);
+    }
+}
+
+fn path_of(geometry: &dyn Geometry) -> Vec<PathCommand> {
+    geometry.as_any()
+        .downcast_ref::<RecordingGeometry>()
+        .expect("geometry wasn't created by the RecordingPainter")
+        .path
+        .clone()
+}
+
+struct RecordingStrokeStyle {
+    dash_pattern: Option<Vec<f64>>,
+    dash_offset: f64,
+}
+
+impl StrokeStyle for RecordingStrokeStyle {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Records the segments in absolute coordinates. The curves are only
+/// recorded by their end points.
+#[derive(Clone, Default)]
+struct RecordingGeometry {
+    path: Vec<PathCommand>,
+    start: Point2D<f32>,
+    current: Point2D<f32>,
+}
+
+impl RecordingGeometry {
+    fn resolve(&self, ty: SvgPathType, coords: SvgPathCoordinatePair) -> Point2D<f32> {
+        let point = Point2D::new(coords.x as f32, coords.y as f32);
+        match ty {
+            SvgPathType::Absolute => point,
+            SvgPathType::Relative => self.current + point.to_vector(),
+        }
+    }
+
+    fn line_to_point(&mut self, point: Point2D<f32>) {
+        self.path.push(PathCommand::LineTo(point));
+        self.current = point;
+    }
+
+    fn curve_to_point(&mut self, point: Point2D<f32>) {
+        self.path.push(PathCommand::CurveTo(point));
+        self.current = point;
+    }
+}
+
+impl GeometrySink for RecordingGeometry {
+    fn close_path(&mut self) {
+        self.path.push(PathCommand::Close);
+        self.current = self.start;
+    }
+
+    fn line_to(&mut self, ty: SvgPathType, coords: SvgPathCoordinatePair) {
+        let point = self.resolve(ty, coords);
+        self.line_to_point(point);
+    }
+
+    fn horizontal_lines_to(&mut self, ty: SvgPathType, lines: SvgPathCoordinateSequence) {
+        for x in lines.0 {
+            let x = x as f32;
+            let x = if ty == SvgPathType::Relative { self.current.x + x } else { x };
+            self.line_to_point(Point2D::new(x, self.current.y));
+        }
+    }
+
+    fn vertical_lines_to(&mut self, ty: SvgPathType, lines: SvgPathCoordinateSequence) {
+        for y in lines.0 {
+            let y = y as f32;
+            let y = if ty == SvgPathType::Relative { self.current.y + y } else { y };
+            self.line_to_point(Point2D::new(self.current.x, y));
+        }
+    }
+
+    fn move_to(&mut self, ty: SvgPathType, coords: SvgPathCoordinatePair) {
+        let point = self.resolve(ty, coords);
+        self.path.push(PathCommand::MoveTo(point));
+        self.start = point;
+        self.current = point;
+    }
+
+    fn curve_to(&mut self, ty: SvgPathType, sequence: SvgPathCoordinatePairTripletSequence) {
+        for triplet in sequence.0 {
+            let point = self.resolve(ty, triplet.c);
+            self.curve_to_point(point);
+        }
+    }
+
+    fn smooth_curve_to(&mut self, ty: SvgPathType, double: SvgPathCoordinatePairDouble) {
+        let point = self.resolve(ty, double.b);
+        self.curve_to_point(point);
+    }
+
+    fn quadratic_beziers_curve_to(&mut self, ty: SvgPathType, sequence: SvgPathCoordinatePairDoubleSequence) {
+        for double in sequence.0 {
+            let point = self.resolve(ty, double.b);
+            self.curve_to_point(point);
+        }
+    }
+
+    fn smooth_quadratic_bezier_curve_to(&mut self, ty: SvgPathType, coords: SvgPathCoordinatePair) {
+        let point = self.resolve(ty, coords);
+        self.curve_to_point(point);
+    }
+
+    fn elliptic_arc(&mut self, ty: SvgPathType, argument: SvgPathEllipticArcArgument) {
+        let end = self.resolve(ty, argument.coords);
+        self.path.push(PathCommand::ArcTo {
+            radius: Point2D::new(argument.rx as f32, argument.ry as f32),
+            end,
+        });
+        self.current = end;
+    }
+
+    fn finish(&mut self) -> Box<dyn Geometry> {
+        Box::new(self.clone())
+    }
+}
+
+impl Geometry for RecordingGeometry {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    /// The bounds of the end points of the segments, which is exact for
+    /// geometry consisting of lines.
+    fn bounds(&self) -> Rect<f32> {
+        let points = self.path.iter().filter_map(|command| match *command {
+            PathCommand::MoveTo(point) | PathCommand::LineTo(point) | PathCommand::CurveTo(point) => Some(point),
+            PathCommand::ArcTo { end, .. } => Some(end),
+            PathCommand::Close => None,
+        });
+
+        Box2D::from_points(points).to_rect()
+    }
+}
+
+/// Provides a [`TestFont`] for every descriptor.
+struct TestFontProvider;
+
+impl FontProviderBackend for TestFontProvider {
+    fn get(&self, descriptor: &FontDescriptor) -> Option<FontHandle> {
+        Some(FontHandle::new(Arc::new(TestFont {
+            descriptor: descriptor.clone(),
+        })))
+    }
+
+    fn load(&self, _: FontDescriptor, _: Vec<u8>, _: u32) -> bool {
+        false
+    }
+
+    fn load_defaults(&self) {}
+
+    fn load_from_system(&self, _: FontDescriptor) -> bool {
+        false
+    }
+
+    fn load_from_file(&self, _: LoadTime, _: &Path, _: FontDescriptor, _: u32) -> bool {
+        false
+    }
+}
+
+/// A font of which every character is half an em wide and one em high, with
+/// the baseline at 80% of the em.
+struct TestFont {
+    descriptor: FontDescriptor,
+}
+
+impl TestFont {
+    const ADVANCE: f32 = 0.5;
+    const BASELINE: f32 = 0.8;
+}
+
+impl Font for TestFont {
+    fn calculate_size(&self, size: f32, text: &str, _: TextHintingOptions) -> Size2D<f32> {
+        Size2D::new(text.chars().count() as f32 * size * Self::ADVANCE, size)
+    }
+
+    fn calculate_vertical_size(&self, size: f32, text: &str, _: TextHintingOptions) -> Size2D<f32> {
+        Size2D::new(size, text.chars().count() as f32 * size)
+    }
+
+    fn descriptor(&self) -> &FontDescriptor {
+        &self.descriptor
+    }
+
+    fn glyph_bounds(&self, _: char, point_size: f32) -> Rect<f32> {
+        Rect::new(Point2D::zero(), Size2D::new(point_size * Self::ADVANCE, point_size))
+    }
+
+    fn baseline_offset(&self, point_size: f32) -> f32 {
+        point_size * Self::BASELINE
+    }
+
+    fn underline_position(&self, point_size: f32) -> f32 {
+        point_size * 0.9
+    }
+
+    fn underline_thickness(&self, point_size: f32) -> f32 {
+        point_size / 16.0
+    }
+
+    fn paint(&self, _: &str, _: Color, _: Point2D<f32>, _: f32, _: TextHintingOptions, _: &mut retina_gfx::Painter) {
+        unimplemented!("the test font can't be painted")
+    }
+
+    fn paint_vertical(&self, _: &str, _: Color, _: Point2D<f32>, _: f32, _: TextHintingOptions, _: &mut retina_gfx::Painter) {
+        unimplemented!("the test font can't be painted")
+    }
+}
@@ -0,0 +1,537 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! Rendering of the text content elements, i.e. `<text>` and `<tspan>`.
+//!
+//! ## References
+//! * [SVG 1.1 § 10 Text](https://www.w3.org/TR/SVG11/text.html)
+
+use euclid::default::{Point2D, Rect, Size2D};
+use log::warn;
+use retina_dom::Element;
+use retina_gfx_font::{
+    FamilyName,
+    FontDescriptor,
+    FontHandle,
+    FontProvider,
+    FontStyle,
+    FontWeight,
+    TextHintingOptions,
+};
+use retina_style::{
+    CssFontFamilyName,
+    CssFontStyle,
+    CssFontWeight,
+    CssGenericFontFamilyName,
+    Property,
+    Value,
+};
+
+use crate::{
+    Material,
+    SvgElementTraits,
+    SvgRenderer,
+};
+
+/// The initial value of the `font-size` property, which corresponds to the
+/// `medium` keyword.
+const DEFAULT_FONT_SIZE: f32 = 16.0;
+
+impl<'painter> SvgRenderer<'painter> {
+    pub(crate) fn render_text(&mut self, element: &Element) {
+        let Some(font_provider) = self.resources.font_provider.as_ref() else {
+            warn!("No font provider was given, skipping <text> element");
+            return;
+        };
+
+        let mut collector = TextRunCollector {
+            font_provider,
+            runs: Vec::new(),
+            pending_position: TextPosition::default(),
+            previous_was_space: true,
+        };
+        collector.collect(element);
+        let runs = collector.finish();
+
+        // Lay out the runs after each other, starting a new text chunk at
+        // every absolute position.
+        let mut cursor = Point2D::zero();
+        let mut chunk_start = 0;
+        let mut positions = Vec::with_capacity(runs.len());
+        for (index, run) in runs.iter().enumerate() {
+            if run.position.x.is_some() || run.position.y.is_some() {
+                anchor_text_chunk(&runs[chunk_start..index], &mut positions[chunk_start..index], cursor.x);
+                chunk_start = index;
+            }
+
+            cursor.x = run.position.x.unwrap_or(cursor.x) + run.position.dx;
+            cursor.y = run.position.y.unwrap_or(cursor.y) + run.position.dy;
+            positions.push(cursor);
+            cursor.x += run.width;
+        }
+        anchor_text_chunk(&runs[chunk_start..], &mut positions[chunk_start..], cursor.x);
+
+        // The y coordinate is that of the baseline, but the painter expects
+        // the top-left position.
+        let positions: Vec<_> = runs.iter()
+            .zip(positions)
+            .map(|(run, position)| Point2D::new(position.x, position.y - run.font.baseline_offset(run.font_size)))
+            .collect();
+
+        // The bounding box of the whole <text> element, to which gradients
+        // in objectBoundingBox units are relative.
+        let bounds = runs.iter()
+            .zip(&positions)
+            .map(|(run, position)| Rect::new(*position, Size2D::new(run.width, run.font_size)))
+            .reduce(|bounds, rect| bounds.union(&rect))
+            .unwrap_or_default();
+
+        for (run, position) in runs.into_iter().zip(positions) {
+            if run.fill.is_transparent() {
+                continue;
+            }
+
+            let fill = run.fill.resolve_bounding_box(bounds);
+            self.painter.draw_text(&run.text, position, &run.font, run.font_size, fill);
+        }
+    }
+}
+
+/// The text-anchor property aligns a text chunk relative to its starting
+/// point, which requires the advance of the whole chunk, ending at `end_x`.
+fn anchor_text_chunk(runs: &[TextRun], positions: &mut [Point2D<f32>], end_x: f32) {
+    let (Some(run), Some(start)) = (runs.first(), positions.first()) else {
+        return;
+    };
+
+    let offset = run.text_anchor.offset(end_x - start.x);
+    for position in positions {
+        position.x -= offset;
+    }
+}
+
+/// The position attributes of a `<text>` or `<tspan>` element, which apply
+/// to the first character of that element.
+///
+/// <https://www.w3.org/TR/SVG11/text.html#TSpanElementXAttribute>
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct TextPosition {
+    x: Option<f32>,
+    y: Option<f32>,
+    dx: f32,
+    dy: f32,
+}
+
+impl TextPosition {
+    /// Apply the attributes of the `element`, where an absolute position
+    /// overrides the position of the ancestors and relative positions are
+    /// accumulated.
+    fn apply(&mut self, element: &Element) {
+        if let Some(x) = element.optional_length_property("x") {
+            self.x = Some(x);
+        }
+
+        if let Some(y) = element.optional_length_property("y") {
+            self.y = Some(y);
+        }
+
+        self.dx += element.length_property("dx");
+        self.dy += element.length_property("dy");
+    }
+}
+
+/// A run of text of which all characters have the same style.
+struct TextRun {
+    text: String,
+    position: TextPosition,
+    font: FontHandle,
+    font_size: f32,
+    fill: Material,
+    text_anchor: TextAnchor,
+    width: f32,
+}
+
+/// Collects the [`TextRun`]s of a `<text>` element and its `<tspan>`
+/// descendants, in document order.
+struct TextRunCollector<'provider> {
+    font_provider: &'provider FontProvider,
+    runs: Vec<TextRun>,
+    pending_position: TextPosition,
+
+    /// Whether the last collected character was a space, which is used to
+    /// consolidate spaces across runs.
+    previous_was_space: bool,
+}
+
+impl<'provider> TextRunCollector<'provider> {
+    fn collect(&mut self, element: &Element) {
+        self.pending_position.apply(element);
+
+        for child in element.as_parent_node().children().iter() {
+            if let Some(text) = child.as_text() {
+                self.collect_text(element, text.data());
+                continue;
+            }
+
+            let Some(child) = child.as_dom_element() else { continue };
+            if child.qualified_name().local.eq_str_ignore_ascii_case("tspan") {
+                self.collect(child);
+            }
+        }
+    }
+
+    fn collect_text(&mut self, element: &Element, data: &str) {
+        // When xml:space="default", the SVG user agent will do the following
+        // using a copy of the original character data content. First, it will
+        // remove all newline characters. Then it will convert all tab
+        // characters into space characters. Then, it will strip off all
+        // leading and trailing space characters. Then, all contiguous space
+        // characters will be consolidated.
+        let mut text = String::with_capacity(data.len());
+        for c in data.chars() {
+            match c {
+                '\n' => (),
+                ' ' | '\t' => {
+                    if !self.previous_was_space {
+                        text.push(' ');
+                        self.previous_was_space = true;
+                    }
+                }
+                c => {
+                    text.push(c);
+                    self.previous_was_space = false;
+                }
+            }
+        }
+
+        if text.is_empty() {
+            return;
+        }
+
+        let font_size = element.property_font_size();
+        if font_size <= 0.0 {
+            return;
+        }
+
+        let Some(font) = find_font(self.font_provider, element.font_descriptor()) else {
+            warn!("No font available for text: \"{text}\"");
+            return;
+        };
+
+        let width = font.calculate_size(font_size, &text, TextHintingOptions::default()).width;
+        self.runs.push(TextRun {
+            text,
+            position: std::mem::take(&mut self.pending_position),
+            font,
+            font_size,
+            fill: element.property_fill(),
+            text_anchor: element.property_text_anchor(),
+            width,
+        });
+    }
+
+    /// Strips the trailing space of the last run and returns the runs.
+    fn finish(mut self) -> Vec<TextRun> {
+        if let Some(run) = self.runs.last_mut() {
+            if run.text.ends_with(' ') {
+                run.text.pop();
+                run.width = run.font.calculate_size(run.font_size, &run.text, TextHintingOptions::default()).width;
+            }
+
+            if run.text.is_empty() {
+                self.runs.pop();
+            }
+        }
+
+        self.runs
+    }
+}
+
+/// <https://www.w3.org/TR/SVG11/text.html#TextAnchorProperty>
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) enum TextAnchor {
+    #[default]
+    Start,
+    Middle,
+    End,
+}
+
+impl TextAnchor {
+    /// The distance the text run is moved to the left, for a run with the
+    /// given `width`.
+    pub(crate) fn offset(self, width: f32) -> f32 {
+        match self {
+            Self::Start => 0.0,
+            Self::Middle => width / 2.0,
+            Self::End => width,
+        }
+    }
+}
+
+pub(crate) trait SvgTextElementTraits: SvgElementTraits {
+    fn font_descriptor(&self) -> FontDescriptor;
+    fn property_font_size(&self) -> f32;
+    fn property_text_anchor(&self) -> TextAnchor;
+}
+
+impl SvgTextElementTraits for Element {
+    fn font_descriptor(&self) -> FontDescriptor {
+        let name = self.inherited_str_property("font-family")
+            .and_then(|value| match retina_style_parser::parse_property_value(Property::FontFamily, &value) {
+                Some(Value::FontFamily(families)) => families.first().map(convert_font_family),
+                _ => None,
+            })
+            .unwrap_or(FamilyName::Serif);
+
+        let style = self.inherited_str_property("font-style")
+            .and_then(|value| match retina_style_parser::parse_property_value(Property::FontStyle, &value) {
+                Some(Value::FontStyle(style)) => Some(convert_font_style(style)),
+                _ => None,
+            })
+            .unwrap_or_default();
+
+        FontDescriptor {
+            name,
+            style,
+            weight: FontWeight::new(font_weight(self)),
+        }
+    }
+
+    fn property_font_size(&self) -> f32 {
+        self.inherited_str_property("font-size")
+            .and_then(|value| crate::parse_length("font-size", &value))
+            .unwrap_or(DEFAULT_FONT_SIZE)
+    }
+
+    fn property_text_anchor(&self) -> TextAnchor {
+        match self.inherited_str_property("text-anchor").as_deref() {
+            Some("middle") => TextAnchor::Middle,
+            Some("end") => TextAnchor::End,
+            _ => TextAnchor::Start,
+        }
+    }
+}
+
+/// Get the font for the `descriptor`, loading it from the system if it wasn't
+/// loaded before. Falls back to the default serif font.
+fn find_font(font_provider: &FontProvider, descriptor: FontDescriptor) -> Option<FontHandle> {
+    if let Some(font) = font_provider.get(&descriptor) {
+        return Some(font);
+    }
+
+    if font_provider.load_from_system(descriptor.clone()) {
+        if let Some(font) = font_provider.get(&descriptor) {
+            return Some(font);
+        }
+    }
+
+    if descriptor.name == FamilyName::Serif {
+        return None;
+    }
+
+    find_font(font_provider, FontDescriptor {
+        name: FamilyName::Serif,
+        ..descriptor
+    })
+}
+
+/// <https://drafts.csswg.org/css-fonts-4/#relative-weights>
+fn font_weight(element: &Element) -> f32 {
+    let parent_weight = || {
+        if element.qualified_name().local.eq_str_ignore_ascii_case("svg") {
+            return FontWeight::REGULAR.value();
+        }
+
+        element.as_node()
+            .parent()
+            .as_ref()
+            .and_then(std::sync::Weak::upgrade)
+            .and_then(|parent| parent.as_dom_element().map(font_weight))
+            .unwrap_or(FontWeight::REGULAR.value())
+    };
+
+    let Some(value) = element.attributes().find_by_str("font-weight") else {
+        return parent_weight();
+    };
+
+    match retina_style_parser::parse_property_value(Property::FontWeight, value) {
+        Some(Value::FontWeight(CssFontWeight::Absolute(value))) => value as f32,
+        Some(Value::FontWeight(CssFontWeight::Bolder)) => {
+            let parent_weight = parent_weight();
+            if parent_weight < 350.0 {
+                400.0
+            } else if parent_weight <= 550.0 {
+                700.0
+            } else {
+                900.0
+            }
+        }
+        Some(Value::FontWeight(CssFontWeight::Lighter)) => {
+            let parent_weight = parent_weight();
+            if parent_weight < 100.0 {
+                parent_weight
+            } else if parent_weight < 550.0 {
+                100.0
+            } else if parent_weight <= 750.0 {
+                400.0
+            } else {
+                700.0
+            }
+        }
+        _ => parent_weight(),
+    }
+}
+
+fn convert_font_family(value: &CssFontFamilyName) -> FamilyName {
+    match value {
+        CssFontFamilyName::Name(name) => name.clone().into(),
+        CssFontFamilyName::Generic(generic) => match generic {
+            CssGenericFontFamilyName::Cursive => FamilyName::Cursive,
+            CssGenericFontFamilyName::Emoji => FamilyName::Emoji,
+            CssGenericFontFamilyName::Fangsong => FamilyName::Fangsong,
+            CssGenericFontFamilyName::Fantasy => FamilyName::Fantasy,
+            CssGenericFontFamilyName::Math => FamilyName::Math,
+            CssGenericFontFamilyName::Monospace => FamilyName::Monospace,
+            CssGenericFontFamilyName::SansSerif => FamilyName::SansSerif,
+            CssGenericFontFamilyName::Serif => FamilyName::Serif,
+            CssGenericFontFamilyName::SystemUi => FamilyName::SystemUi,
+            CssGenericFontFamilyName::UiMonospace => FamilyName::UiMonospace,
+            CssGenericFontFamilyName::UiRounded => FamilyName::UiRounded,
+            CssGenericFontFamilyName::UiSansSerif => FamilyName::UiSansSerif,
+            CssGenericFontFamilyName::UiSerif => FamilyName::UiSerif,
+        }
+    }
+}
+
+fn convert_font_style(style: CssFontStyle) -> FontStyle {
+    match style {
+        CssFontStyle::Normal => FontStyle::Normal,
+        CssFontStyle::Italic => FontStyle::Italic,
+        CssFontStyle::Oblique => FontStyle::Oblique,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use retina_common::Color;
+    use rstest::rstest;
+
+    use crate::test_util::{drawing_commands, materials, render, render_with_test_font, PaintCommand};
+
+    /// The text runs that are drawn, with their top-left positions.
+    fn text_runs(html: &str) -> Vec<(String, Point2D<f32>)> {
+        drawing_commands(render_with_test_font(html))
+            .into_iter()
+            .map(|command| match command {
+                PaintCommand::Text { text, position, .. } => (text, position),
+                command => panic!("unexpected paint command: {command:?}"),
+            })
+            .collect()
+    }
+
+    fn run(text: &str, x: f32, y: f32) -> (String, Point2D<f32>) {
+        (text.to_string(), Point2D::new(x, y))
+    }
+
+    #[test]
+    fn without_font_provider() {
+        assert!(render("<svg><text x=10 y=20>Hello</text></svg>").is_empty());
+    }
+
+    #[test]
+    fn position_and_font_size() {
+        // The test font is half an em wide per character, and the baseline is
+        // at 80% of the em.
+        let commands = drawing_commands(render_with_test_font(r#"<svg><text x="10" y="20" font-size="10">Hello</text></svg>"#));
+        assert_eq!(commands, [PaintCommand::Text {
+            text: "Hello".into(),
+            position: Point2D::new(10.0, 12.0),
+            font_size: 10.0,
+            material: Material::Color(Color::BLACK),
+        }]);
+    }
+
+    #[test]
+    fn inherited_font_size() {
+        let commands = drawing_commands(render_with_test_font(r#"<svg font-size="20"><g><text y="20">A</text></g></svg>"#));
+        assert!(matches!(&commands[..], [PaintCommand::Text { font_size, .. }] if *font_size == 20.0));
+    }
+
+    #[test]
+    fn whitespace_is_consolidated() {
+        assert_eq!(
+            text_runs("<svg><text y=\"8\" font-size=\"10\">\n  Hello \t  world  \n</text></svg>"),
+            [run("Hello world", 0.0, 0.0)],
+        );
+    }
+
+    #[test]
+    fn tspan_positions() {
+        // The dx of the <tspan> is relative to the end of the previous run,
+        // and the space before "C" is kept, because it's between two runs.
+        assert_eq!(
+            text_runs(r#"<svg><text x="10" y="20" font-size="10">A<tspan dx="5" dy="-2">B</tspan> C<tspan x="100" y="50">D</tspan></text></svg>"#),
+            [
+                run("A", 10.0, 12.0),
+                run("B", 20.0, 10.0),
+                run(" C", 25.0, 10.0),
+                run("D", 100.0, 42.0),
+            ],
+        );
+    }
+
+    #[test]
+    fn tspan_fill() {
+        let commands = render_with_test_font(r#"<svg><text fill="red">A<tspan fill="blue">B</tspan><tspan>C</tspan></text></svg>"#);
+        assert_eq!(
+            materials(commands),
+            [Material::Color(Color::RED), Material::Color(Color::BLUE), Material::Color(Color::RED)],
+        );
+    }
+
+    #[test]
+    fn transparent_text_is_skipped() {
+        assert_eq!(text_runs(r#"<svg><text fill="none">A<tspan fill="red">B</tspan></text></svg>"#).len(), 1);
+    }
+
+    #[rstest]
+    #[case("start", 10.0)]
+    #[case("middle", 0.0)]
+    #[case("end", -10.0)]
+    fn text_anchor(#[case] value: &str, #[case] x: f32) {
+        // The chunk is 20 units wide, including the dx of the <tspan>, and
+        // is aligned using the text-anchor of its first character.
+        let html = format!(r#"<svg><text x="10" y="8" font-size="10" text-anchor="{value}">A<tspan dx="5" text-anchor="end">BC</tspan></text></svg>"#);
+        assert_eq!(text_runs(&html), [run("A", x, 0.0), run("BC", x + 10.0, 0.0)]);
+    }
+
+    #[test]
+    fn text_anchor_per_chunk() {
+        // A new text chunk starts at each absolute position.
+        assert_eq!(
+            text_runs(r#"<svg><text x="50" y="8" font-size="10" text-anchor="middle">AB<tspan x="50" y="28">ABCD</tspan></text></svg>"#),
+            [run("AB", 45.0, 0.0), run("ABCD", 40.0, 20.0)],
+        );
+    }
+
+    #[rstest]
+    #[case(None, FontWeight::REGULAR.value())]
+    #[case(Some("bold"), 700.0)]
+    #[case(Some("bolder"), 700.0)]
+    #[case(Some("lighter"), 100.0)]
+    #[case(Some("300"), 300.0)]
+    fn weight(#[case] value: Option<&str>, #[case] expected: f32) {
+        let attribute = value.map(|value| format!(r#"font-weight="{value}""#)).unwrap_or_default();
+        let document = retina_dom::Parser::parse(&format!("<svg><text {attribute}>A</text></svg>"));
+
+        let mut weight = None;
+        document.for_each_child_node_recursive_handle(&mut |node| {
+            if node.tag_name() == Some("text") {
+                weight = node.as_dom_element().map(font_weight);
+            }
+        });
+        assert_eq!(weight, Some(expected));
+    }
+}
@@ -0,0 +1,228 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! Rendering of the `<use>` element.
+//!
+//! ## References
+//! * [SVG 1.1 § 5.6 The ‘use’ element](https://www.w3.org/TR/SVG11/struct.html#UseElement)
+
+use std::sync::Weak;
+
+use euclid::default::{Point2D, Rect, Size2D, Transform2D};
+use log::{error, warn};
+use retina_dom::{Element, Node};
+
+use crate::{
+    image::PreserveAspectRatio,
+    SvgElementTraits,
+    SvgRenderer,
+};
+
+impl<'painter> SvgRenderer<'painter> {
+    pub(crate) fn render_use(&mut self, node: &Node, element: &Element) {
+        // Attributes are stored by their local name, so this also finds the
+        // xlink:href attribute.
+        let Some(href) = element.attributes().find_by_str("href") else {
+            warn!("<use> has no \"href\" attribute!");
+            return;
+        };
+
+        let Some(id) = href.strip_prefix('#') else {
+            warn!("<use> references an external resource, which isn't supported: {href}");
+            return;
+        };
+
+        let Some(referenced) = crate::find_element_by_id(self.root_node.clone(), id) else {
+            warn!("<use> references an unknown element: {href}");
+            return;
+        };
+
+        if self.use_stack.iter().any(|instance| Node::ptr_eq(instance, &referenced))
+                || is_ancestor(&referenced, element) {
+            error!("<use> has a circular reference to: {href}");
+            return;
+        }
+
+        // The referenced element is rendered as if it were a child of the
+        // <use> element, such that it inherits the properties of the <use>
+        // element, without overriding its own properties. The instance is
+        // created before it's attached to the tree, to avoid notifying the
+        // mutation observers.
+        let instance_parent = node.clone_node(false);
+        let instance = referenced.clone_node(true);
+        instance_parent.append_child(instance.clone());
+        instance_parent.as_node().set_parent(element.as_node().parent());
+
+        let Some(instance_element) = instance.as_dom_element() else { return };
+
+        let mut transform = Transform2D::translation(element.property_x(), element.property_y());
+
+        // The <symbol> and <svg> elements establish a new viewport, of which
+        // the size is given by the <use> element.
+        let establishes_viewport = ["symbol", "svg"].iter()
+            .any(|name| instance_element.qualified_name().local.eq_str_ignore_ascii_case(name));
+        if establishes_viewport {
+            let view_box = instance_element.property_view_box();
+            let width = element.optional_length_property("width")
+                .or_else(|| instance_element.optional_length_property("width"))
+                .or(view_box.map(|view_box| view_box.width()))
+                .unwrap_or_default();
+            let height = element.optional_length_property("height")
+                .or_else(|| instance_element.optional_length_property("height"))
+                .or(view_box.map(|view_box| view_box.height()))
+                .unwrap_or_default();
+
+            // A value of zero disables rendering of the element.
+            let viewport = Rect::new(Point2D::zero(), Size2D::new(width, height));
+            if viewport.is_empty() {
+                return;
+            }
+
+            if let Some(view_box) = view_box {
+                let preserve_aspect_ratio = PreserveAspectRatio::parse(instance_element.str_property("preserveAspectRatio"));
+                transform = crate::view_box_transform(view_box, viewport, preserve_aspect_ratio)
+                    .then(&transform);
+            }
+        }
+
+        self.use_stack.push(referenced);
+        self.painter.push_transform(transform);
+
+        if establishes_viewport {
+            for child in instance_element.as_parent_node().children().iter() {
+                self.render_node(child);
+            }
+        } else {
+            self.render_node(&instance);
+        }
+
+        self.painter.pop_transform();
+        self.use_stack.pop();
+    }
+}
+
+/// Whether the `node` is an ancestor of the `element`.
+fn is_ancestor(node: &Node, element: &Element) -> bool {
+    let mut parent = element.as_node().parent().as_ref().and_then(Weak::upgrade);
+    while let Some(current) = parent {
+        let current = Node::from(current);
+        if Node::ptr_eq(&current, node) {
+            return true;
+        }
+
+        parent = current.as_node().parent().as_ref().and_then(Weak::upgrade);
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use euclid::default::Box2D;
+    use pretty_assertions::assert_eq;
+    use retina_common::Color;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::{
+        test_util::{drawing_commands, render, PaintCommand},
+        Material,
+    };
+
+    fn rect(width: f32, height: f32, color: Color) -> PaintCommand {
+        PaintCommand::Rect {
+            rect: Box2D::new(Point2D::zero(), Point2D::new(width, height)),
+            material: Material::Color(color),
+        }
+    }
+
+    #[rstest]
+    #[case("href")]
+    #[case("xlink:href")]
+    fn translated_by_position(#[case] attribute: &str) {
+        let html = format!(r##"<svg><defs><rect id="r" width="10" height="20" fill="red"/></defs><use {attribute}="#r" x="5" y="6"/></svg>"##);
+        assert_eq!(render(&html), [
+            PaintCommand::PushTransform(Transform2D::translation(5.0, 6.0)),
+            rect(10.0, 20.0, Color::RED),
+            PaintCommand::PopTransform,
+        ]);
+    }
+
+    #[test]
+    fn inherits_properties_of_use_element() {
+        let commands = render(r##"<svg fill="red"><defs><rect id="r" width="10" height="20"/><rect id="blue" width="10" height="20" fill="blue"/></defs><use href="#r" fill="green"/><use href="#blue" fill="green"/></svg>"##);
+        assert_eq!(drawing_commands(commands), [
+            rect(10.0, 20.0, Color::rgb_decimal(0, 128, 0)),
+            rect(10.0, 20.0, Color::BLUE),
+        ]);
+    }
+
+    #[test]
+    fn symbol_establishes_viewport() {
+        let commands = render(r##"<svg><symbol id="s" viewBox="0 0 10 10"><rect width="10" height="10"/></symbol><use href="#s" width="20" height="40"/></svg>"##);
+        assert_eq!(commands, [
+            PaintCommand::PushTransform(Transform2D::scale(2.0, 2.0).then_translate(euclid::vec2(0.0, 10.0))),
+            rect(10.0, 10.0, Color::BLACK),
+            PaintCommand::PopTransform,
+        ]);
+    }
+
+    #[test]
+    fn symbol_without_size() {
+        let commands = render(r##"<svg><symbol id="s" viewBox="0 0 10 10"><rect width="10" height="10"/></symbol><use href="#s" width="0"/></svg>"##);
+        assert!(commands.is_empty());
+    }
+
+    #[rstest]
+    #[case(r##"<use href="#unknown"/>"##)]
+    #[case(r#"<use href="other.svg#r"/>"#)]
+    #[case(r#"<use/>"#)]
+    fn invalid_reference(#[case] use_element: &str) {
+        let html = format!(r#"<svg><defs><rect id="r" width="10" height="20"/></defs>{use_element}</svg>"#);
+        assert!(render(&html).is_empty());
+    }
+
+    #[test]
+    fn reference_to_ancestor() {
+        // The <g> is rendered once, but the <use> inside it isn't, because it
+        // would render the <g> again, infinitely.
+        let commands = render(r##"<svg><g id="g"><rect width="10" height="20"/><use href="#g"/></g></svg>"##);
+        assert_eq!(commands, [rect(10.0, 20.0, Color::BLACK)]);
+    }
+
+    #[test]
+    fn reference_to_self() {
+        // The instance of the <use> element references the original one,
+        // which is being rendered.
+        let commands = render(r##"<svg><use id="u" href="#u"/><rect width="10" height="20"/></svg>"##);
+        assert_eq!(commands, [
+            PaintCommand::PushTransform(Transform2D::identity()),
+            PaintCommand::PopTransform,
+            rect(10.0, 20.0, Color::BLACK),
+        ]);
+    }
+
+    #[test]
+    fn indirect_circular_reference() {
+        // Rendering #a renders #b, which would render #a again.
+        let commands = render(r##"<svg><defs><g id="a"><rect width="10" height="20"/><use href="#b" x="1"/></g><g id="b"><use href="#a" x="2"/></g></defs><use href="#a"/></svg>"##);
+        assert_eq!(commands, [
+            PaintCommand::PushTransform(Transform2D::identity()),
+            rect(10.0, 20.0, Color::BLACK),
+            PaintCommand::PushTransform(Transform2D::translation(1.0, 0.0)),
+            PaintCommand::PopTransform,
+            PaintCommand::PopTransform,
+        ]);
+    }
+
+    #[test]
+    fn nested_references() {
+        // The same element may be used multiple times, as long as it doesn't
+        // reference itself.
+        let commands = render(r##"<svg><defs><rect id="r" width="10" height="20"/><g id="g"><use href="#r"/><use href="#r" y="5"/></g></defs><use href="#g"/></svg>"##);
+        assert_eq!(drawing_commands(commands), [
+            rect(10.0, 20.0, Color::BLACK),
+            rect(10.0, 20.0, Color::BLACK),
+        ]);
+    }
+}
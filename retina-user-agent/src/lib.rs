@@ -4,6 +4,8 @@
 //! This crate provides the User-Agent specific components that aren't
 //! necessarily governed by specifications.
 
+pub mod permission;
+pub mod storage;
 pub mod stylesheet;
 pub mod url_scheme;
 
@@ -0,0 +1,229 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! The storage of the [permissions][spec] the user granted or denied to
+//! origins, which persists between sessions.
+//!
+//! [spec]: https://w3c.github.io/permissions/
+
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+};
+
+use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, EnumString};
+
+/// The default name of the file the permissions are stored in, inside the
+/// configuration directory of the user.
+pub const PERMISSION_STORE_FILE_NAME: &str = "permissions.json";
+
+/// The ASCII serialization of an origin, e.g. `https://example.com`.
+pub type Origin = String;
+
+/// The [powerful features][spec] that are gated behind a permission.
+///
+/// [spec]: https://w3c.github.io/permissions/#dfn-powerful-feature
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(AsRefStr, EnumString, Serialize, Deserialize)]
+#[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum PermissionName {
+    Geolocation,
+    Notifications,
+}
+
+impl PermissionName {
+    pub fn as_str(&self) -> &str {
+        self.as_ref()
+    }
+}
+
+/// The [permission states][spec].
+///
+/// [spec]: https://w3c.github.io/permissions/#dfn-permission-state
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(AsRefStr, EnumString, Serialize, Deserialize)]
+#[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum PermissionState {
+    /// The user (or the User Agent on their behalf) gave express permission.
+    Granted,
+
+    /// The user (or the User Agent on their behalf) denied permission.
+    Denied,
+
+    /// The user wasn't asked yet, so using the feature will prompt them.
+    #[default]
+    Prompt,
+}
+
+impl PermissionState {
+    pub fn as_str(&self) -> &str {
+        self.as_ref()
+    }
+}
+
+/// Stores the [`PermissionState`] per origin and [`PermissionName`], and
+/// persists them to a JSON file.
+#[derive(Clone, Debug, Default)]
+pub struct PermissionStore {
+    path: Option<PathBuf>,
+    permissions: HashMap<Origin, HashMap<PermissionName, PermissionState>>,
+}
+
+impl PermissionStore {
+    /// Creates a store that isn't persisted.
+    pub fn new_in_memory() -> Self {
+        Self::default()
+    }
+
+    /// The path of the [`PERMISSION_STORE_FILE_NAME`] in the configuration
+    /// directory of the user, if it exists on this platform.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs_next::config_dir()
+            .map(|directory| directory.join("retina").join(PERMISSION_STORE_FILE_NAME))
+    }
+
+    /// Opens the store at the given path. A missing or corrupt file results
+    /// in an empty store, which is written to the path when it is modified.
+    pub fn open(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+
+        let permissions = match std::fs::read_to_string(&path) {
+            Ok(contents) => match serde_json::from_str(&contents) {
+                Ok(permissions) => permissions,
+                Err(e) => {
+                    log::warn!("[permissions] Failed to parse \"{}\": {e}", path.display());
+                    HashMap::new()
+                }
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => {
+                log::warn!("[permissions] Failed to read \"{}\": {e}", path.display());
+                HashMap::new()
+            }
+        };
+
+        Self {
+            path: Some(path),
+            permissions,
+        }
+    }
+
+    /// Get the state of the permission for the origin, which is
+    /// [`PermissionState::Prompt`] if the user wasn't asked before.
+    pub fn query(&self, origin: &str, name: PermissionName) -> PermissionState {
+        self.permissions.get(origin)
+            .and_then(|permissions| permissions.get(&name))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Set the state of the permission for the origin, and persist the store.
+    pub fn set(&mut self, origin: &str, name: PermissionName, state: PermissionState) {
+        self.permissions.entry(origin.to_string())
+            .or_default()
+            .insert(name, state);
+
+        self.save();
+    }
+
+    /// [Request permission to use][spec] the powerful feature. The user is
+    /// only prompted when they didn't grant or deny the permission before,
+    /// after which their answer is stored.
+    ///
+    /// [spec]: https://w3c.github.io/permissions/#dfn-request-permission-to-use
+    pub fn request(
+        &mut self,
+        origin: &str,
+        name: PermissionName,
+        prompt: impl FnOnce() -> PermissionState,
+    ) -> PermissionState {
+        let state = self.query(origin, name);
+        if state != PermissionState::Prompt {
+            return state;
+        }
+
+        let state = prompt();
+        if state != PermissionState::Prompt {
+            self.set(origin, name, state);
+        }
+        state
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else { return };
+
+        let contents = match serde_json::to_string_pretty(&self.permissions) {
+            Ok(contents) => contents,
+            Err(e) => {
+                log::warn!("[permissions] Failed to serialize: {e}");
+                return;
+            }
+        };
+
+        if let Some(directory) = path.parent() {
+            if let Err(e) = std::fs::create_dir_all(directory) {
+                log::warn!("[permissions] Failed to create \"{}\": {e}", directory.display());
+                return;
+            }
+        }
+
+        if let Err(e) = std::fs::write(path, contents) {
+            log::warn!("[permissions] Failed to write \"{}\": {e}", path.display());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn query_defaults_to_prompt() {
+        let store = PermissionStore::new_in_memory();
+        assert_eq!(store.query("https://example.com", PermissionName::Notifications), PermissionState::Prompt);
+    }
+
+    #[test]
+    fn request_prompts_only_once() {
+        let mut store = PermissionStore::new_in_memory();
+
+        let state = store.request("https://example.com", PermissionName::Notifications, || PermissionState::Granted);
+        assert_eq!(state, PermissionState::Granted);
+
+        let state = store.request("https://example.com", PermissionName::Notifications, || unreachable!());
+        assert_eq!(state, PermissionState::Granted);
+        assert_eq!(store.query("https://example.com", PermissionName::Notifications), PermissionState::Granted);
+    }
+
+    #[test]
+    fn request_dismissed_prompt_isnt_stored() {
+        let mut store = PermissionStore::new_in_memory();
+
+        let state = store.request("https://example.com", PermissionName::Geolocation, || PermissionState::Prompt);
+        assert_eq!(state, PermissionState::Prompt);
+
+        let state = store.request("https://example.com", PermissionName::Geolocation, || PermissionState::Denied);
+        assert_eq!(state, PermissionState::Denied);
+    }
+
+    #[test]
+    fn persisted_between_opens() {
+        let path = std::env::temp_dir().join(format!("retina-permissions-test-{}.json", std::process::id()));
+        _ = std::fs::remove_file(&path);
+
+        let mut store = PermissionStore::open(&path);
+        store.set("https://example.com", PermissionName::Geolocation, PermissionState::Denied);
+        store.set("https://example.com", PermissionName::Notifications, PermissionState::Granted);
+
+        let store = PermissionStore::open(&path);
+        assert_eq!(store.query("https://example.com", PermissionName::Geolocation), PermissionState::Denied);
+        assert_eq!(store.query("https://example.com", PermissionName::Notifications), PermissionState::Granted);
+        assert_eq!(store.query("https://example.org", PermissionName::Notifications), PermissionState::Prompt);
+
+        _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,374 @@
+// Copyright (C) 2023 Tristan Gerritsen <tristan@thewoosh.org>
+// All Rights Reserved.
+
+//! The [storage areas][spec] of `localStorage` and `sessionStorage`. The
+//! local storage persists between sessions, and notifies the other browsing
+//! contexts of the same origin when it is modified.
+//!
+//! [spec]: https://html.spec.whatwg.org/multipage/webstorage.html
+
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::mpsc::{channel, Receiver, Sender},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::permission::Origin;
+
+/// The default name of the file the local storage is stored in, inside the
+/// application data directory.
+pub const LOCAL_STORAGE_FILE_NAME: &str = "local_storage.json";
+
+/// The maximum number of bytes the keys and values of a storage area may
+/// occupy, which is the [suggested limit][spec] of 5 MiB.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/webstorage.html#disk-space-2
+pub const STORAGE_QUOTA: usize = 5 * 1024 * 1024;
+
+/// The error returned when a storage area would exceed the [`STORAGE_QUOTA`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct QuotaExceededError;
+
+impl std::fmt::Display for QuotaExceededError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("the storage quota has been exceeded")
+    }
+}
+
+impl std::error::Error for QuotaExceededError {}
+
+/// A modification of a [`StorageArea`], which is the information of a
+/// [`StorageEvent`][spec].
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/webstorage.html#the-storageevent-interface
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StorageChange {
+    /// The key that changed, or [`None`] when the area was cleared.
+    pub key: Option<String>,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+/// A [storage map](https://html.spec.whatwg.org/multipage/webstorage.html#concept-storage-map),
+/// i.e. the key-value pairs of a single origin.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct StorageArea {
+    items: HashMap<String, String>,
+}
+
+impl StorageArea {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of key-value pairs.
+    pub fn length(&self) -> usize {
+        self.items.len()
+    }
+
+    /// The `index`th key. The order is arbitrary, but is stable as long as
+    /// the area isn't modified.
+    pub fn key(&self, index: usize) -> Option<&str> {
+        self.items.keys().nth(index).map(String::as_str)
+    }
+
+    pub fn get_item(&self, key: &str) -> Option<&str> {
+        self.items.get(key).map(String::as_str)
+    }
+
+    /// Set the value of `key`, returning the change if the value is
+    /// different from the old value.
+    pub fn set_item(&mut self, key: &str, value: &str) -> Result<Option<StorageChange>, QuotaExceededError> {
+        let old_value = self.items.get(key);
+        if old_value.is_some_and(|old_value| old_value == value) {
+            return Ok(None);
+        }
+
+        let old_size = old_value.map_or(0, |old_value| key.len() + old_value.len());
+        if self.size() - old_size + key.len() + value.len() > STORAGE_QUOTA {
+            return Err(QuotaExceededError);
+        }
+
+        let old_value = self.items.insert(key.to_string(), value.to_string());
+        Ok(Some(StorageChange {
+            key: Some(key.to_string()),
+            old_value,
+            new_value: Some(value.to_string()),
+        }))
+    }
+
+    /// Remove the `key`, returning the change if it existed.
+    pub fn remove_item(&mut self, key: &str) -> Option<StorageChange> {
+        let old_value = self.items.remove(key)?;
+        Some(StorageChange {
+            key: Some(key.to_string()),
+            old_value: Some(old_value),
+            new_value: None,
+        })
+    }
+
+    /// Remove all key-value pairs, returning the change if the area wasn't
+    /// empty.
+    pub fn clear(&mut self) -> Option<StorageChange> {
+        if self.items.is_empty() {
+            return None;
+        }
+
+        self.items.clear();
+        Some(StorageChange::default())
+    }
+
+    fn size(&self) -> usize {
+        self.items.iter()
+            .map(|(key, value)| key.len() + value.len())
+            .sum()
+    }
+}
+
+/// The identifier of a browsing context that subscribed to the changes of
+/// the [`LocalStorage`].
+pub type StorageListenerId = u32;
+
+/// A change of the [`LocalStorage`] made by another browsing context.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StorageNotification {
+    pub change: StorageChange,
+
+    /// The URL of the document that made the change.
+    pub url: String,
+}
+
+#[derive(Debug)]
+struct StorageListener {
+    id: StorageListenerId,
+    origin: Origin,
+    sender: Sender<StorageNotification>,
+}
+
+/// Stores the [`StorageArea`] per origin for `localStorage`, persists them to
+/// a JSON file, and notifies the listeners of the same origin about changes.
+#[derive(Debug, Default)]
+pub struct LocalStorage {
+    path: Option<PathBuf>,
+    areas: HashMap<Origin, StorageArea>,
+    next_listener_id: StorageListenerId,
+    listeners: Vec<StorageListener>,
+}
+
+impl LocalStorage {
+    /// Creates a store that isn't persisted.
+    pub fn new_in_memory() -> Self {
+        Self::default()
+    }
+
+    /// The path of the [`LOCAL_STORAGE_FILE_NAME`] in the application data
+    /// directory of the user, if it exists on this platform.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs_next::data_dir()
+            .map(|directory| directory.join("retina").join(LOCAL_STORAGE_FILE_NAME))
+    }
+
+    /// Opens the store at the given path. A missing or corrupt file results
+    /// in an empty store, which is written to the path when it is modified.
+    pub fn open(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+
+        let areas = match std::fs::read_to_string(&path) {
+            Ok(contents) => match serde_json::from_str(&contents) {
+                Ok(areas) => areas,
+                Err(e) => {
+                    log::warn!("[storage] Failed to parse \"{}\": {e}", path.display());
+                    HashMap::new()
+                }
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => {
+                log::warn!("[storage] Failed to read \"{}\": {e}", path.display());
+                HashMap::new()
+            }
+        };
+
+        Self {
+            path: Some(path),
+            areas,
+            ..Default::default()
+        }
+    }
+
+    /// The storage area of the origin, if anything was stored for it.
+    pub fn area(&self, origin: &str) -> Option<&StorageArea> {
+        self.areas.get(origin)
+    }
+
+    /// Listen to the changes that other listeners make to the storage area
+    /// of the `origin`. The listener is removed when the receiver is dropped.
+    pub fn subscribe(&mut self, origin: Origin) -> (StorageListenerId, Receiver<StorageNotification>) {
+        let (sender, receiver) = channel();
+
+        self.next_listener_id += 1;
+        let id = self.next_listener_id;
+        self.listeners.push(StorageListener { id, origin, sender });
+
+        (id, receiver)
+    }
+
+    /// Stop notifying the listener.
+    pub fn unsubscribe(&mut self, id: StorageListenerId) {
+        self.listeners.retain(|listener| listener.id != id);
+    }
+
+    /// Set the value of `key` on behalf of the `source` listener, whose
+    /// document has the given `url`.
+    pub fn set_item(
+        &mut self,
+        origin: &str,
+        key: &str,
+        value: &str,
+        source: StorageListenerId,
+        url: &str,
+    ) -> Result<(), QuotaExceededError> {
+        let change = self.areas.entry(origin.to_string())
+            .or_default()
+            .set_item(key, value)?;
+        self.commit(origin, change, source, url);
+        Ok(())
+    }
+
+    /// Remove the `key` on behalf of the `source` listener, whose document
+    /// has the given `url`.
+    pub fn remove_item(&mut self, origin: &str, key: &str, source: StorageListenerId, url: &str) {
+        let change = self.areas.get_mut(origin).and_then(|area| area.remove_item(key));
+        self.commit(origin, change, source, url);
+    }
+
+    /// Clear the storage area on behalf of the `source` listener, whose
+    /// document has the given `url`.
+    pub fn clear(&mut self, origin: &str, source: StorageListenerId, url: &str) {
+        let change = self.areas.get_mut(origin).and_then(StorageArea::clear);
+        self.commit(origin, change, source, url);
+    }
+
+    /// Persist the store and notify the other listeners of the origin, if
+    /// the area was changed.
+    fn commit(&mut self, origin: &str, change: Option<StorageChange>, source: StorageListenerId, url: &str) {
+        let Some(change) = change else { return };
+
+        if self.areas.get(origin).is_some_and(|area| area.length() == 0) {
+            self.areas.remove(origin);
+        }
+
+        self.save();
+
+        self.listeners.retain(|listener| {
+            if listener.id == source || listener.origin != origin {
+                return true;
+            }
+
+            listener.sender.send(StorageNotification {
+                change: change.clone(),
+                url: url.to_string(),
+            }).is_ok()
+        });
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else { return };
+
+        let contents = match serde_json::to_string_pretty(&self.areas) {
+            Ok(contents) => contents,
+            Err(e) => {
+                log::warn!("[storage] Failed to serialize: {e}");
+                return;
+            }
+        };
+
+        if let Some(directory) = path.parent() {
+            if let Err(e) = std::fs::create_dir_all(directory) {
+                log::warn!("[storage] Failed to create \"{}\": {e}", directory.display());
+                return;
+            }
+        }
+
+        if let Err(e) = std::fs::write(path, contents) {
+            log::warn!("[storage] Failed to write \"{}\": {e}", path.display());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn area_set_get_remove() {
+        let mut area = StorageArea::new();
+        assert_eq!(area.get_item("key"), None);
+
+        let change = area.set_item("key", "a").unwrap();
+        assert_eq!(change, Some(StorageChange {
+            key: Some("key".into()),
+            old_value: None,
+            new_value: Some("a".into()),
+        }));
+        assert_eq!(area.set_item("key", "a"), Ok(None));
+        assert_eq!(area.get_item("key"), Some("a"));
+        assert_eq!(area.key(0), Some("key"));
+        assert_eq!(area.key(1), None);
+
+        assert_eq!(area.remove_item("key").unwrap().old_value.as_deref(), Some("a"));
+        assert_eq!(area.remove_item("key"), None);
+        assert_eq!(area.length(), 0);
+        assert_eq!(area.clear(), None);
+    }
+
+    #[test]
+    fn area_quota_exceeded() {
+        let mut area = StorageArea::new();
+        let value = "a".repeat(STORAGE_QUOTA);
+        assert_eq!(area.set_item("key", &value), Err(QuotaExceededError));
+        assert_eq!(area.length(), 0);
+    }
+
+    #[test]
+    fn notifies_other_listeners_of_same_origin() {
+        let mut store = LocalStorage::new_in_memory();
+        let (source, source_receiver) = store.subscribe("https://example.com".into());
+        let (_, same_origin) = store.subscribe("https://example.com".into());
+        let (_, other_origin) = store.subscribe("https://example.org".into());
+
+        store.set_item("https://example.com", "key", "value", source, "https://example.com/a").unwrap();
+
+        assert!(source_receiver.try_recv().is_err());
+        assert!(other_origin.try_recv().is_err());
+        let notification = same_origin.try_recv().unwrap();
+        assert_eq!(notification.url, "https://example.com/a");
+        assert_eq!(notification.change.new_value.as_deref(), Some("value"));
+
+        store.clear("https://example.com", source, "https://example.com/a");
+        assert_eq!(same_origin.try_recv().unwrap().change, StorageChange::default());
+        assert!(store.area("https://example.com").is_none());
+
+        store.unsubscribe(2);
+        store.set_item("https://example.com", "key", "value", source, "https://example.com/a").unwrap();
+        assert!(same_origin.try_recv().is_err());
+    }
+
+    #[test]
+    fn persisted_between_opens() {
+        let path = std::env::temp_dir().join(format!("retina-local-storage-test-{}.json", std::process::id()));
+        _ = std::fs::remove_file(&path);
+
+        let mut store = LocalStorage::open(&path);
+        store.set_item("https://example.com", "key", "value", 0, "https://example.com").unwrap();
+
+        let store = LocalStorage::open(&path);
+        assert_eq!(store.area("https://example.com").and_then(|area| area.get_item("key")), Some("value"));
+        assert!(store.area("https://example.org").is_none());
+
+        _ = std::fs::remove_file(&path);
+    }
+}
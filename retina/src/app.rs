@@ -5,7 +5,7 @@ use std::{sync::Arc, time::{Instant, Duration}};
 
 use copypasta::{ClipboardContext, ClipboardProvider};
 
-use log::error;
+use log::{error, info};
 use retina_common::StrTendril;
 use retina_gfx::{
     Color,
@@ -48,7 +48,7 @@ impl Application {
         let url = std::env::var("RETINA_URL")
             .unwrap_or("about:not-found".into());
 
-        let url = retina_fetch::parse_page_url(&url)
+        let url = retina_fetch::parse_page_url(&url, None)
             .expect("failed to parse URL");
 
         window.set_title(&format!("{} — Retina", url.as_str()));
@@ -133,6 +133,17 @@ impl Application {
                 window.set_icon(rgba, width, height);
             }
 
+            PageMessage::ScriptResult { id, result } => {
+                match result {
+                    Ok(json) => info!("Script #{id} completed: {json}"),
+                    Err(e) => error!("Script #{id} failed: {e}"),
+                }
+            }
+
+            PageMessage::PermissionResult { origin, name, state } => {
+                info!("Permission \"{}\" of {origin} is {}", name.as_str(), state.as_str());
+            }
+
             PageMessage::Title { title } => {
                 window.set_title(format!("{title} — Retina").as_str());
                 self.title = Some(title);
@@ -144,6 +155,20 @@ impl Application {
 
             PageMessage::Progress { .. } => (),
 
+            PageMessage::OpenNewPage { url } => {
+                // Every page is opened in a new instance of the browser.
+                let result = std::env::current_exe()
+                    .and_then(|executable| {
+                        std::process::Command::new(executable)
+                            .env("RETINA_URL", &url)
+                            .spawn()
+                    });
+
+                if let Err(e) = result {
+                    error!("Failed to open new page for \"{url}\": {e}");
+                }
+            }
+
             PageMessage::PaintReceived { texture_view, background_color, texture_size } => {
                 self.repaint_requests += 1;
                 self.texture_view = Some(texture_view);
@@ -204,7 +229,12 @@ impl WindowApplication<RetinaEvent> for Application {
     fn on_key_press(&mut self, event: WindowKeyPressEvent) {
         match event.key() {
             VirtualKeyCode::F1 => _ = self.page_send_half.send_command(PageCommand::OpenLayoutTreeView),
-            VirtualKeyCode::F5 => _ = self.page_send_half.send_command(PageCommand::Reload),
+            VirtualKeyCode::F5 => _ = self.page_send_half.send_command(PageCommand::Reload { bypass_cache: false }),
+            VirtualKeyCode::R if event.with_control() => {
+                _ = self.page_send_half.send_command(PageCommand::Reload {
+                    bypass_cache: event.with_shift(),
+                });
+            }
             VirtualKeyCode::F6 => _ = self.page_send_half.send_command(PageCommand::OpenStyleView),
             VirtualKeyCode::F10 => _ = self.page_send_half.send_command(PageCommand::OpenMemoryView),
             VirtualKeyCode::F12 => _ = self.page_send_half.send_command(PageCommand::OpenDomTreeView),
@@ -212,6 +242,15 @@ impl WindowApplication<RetinaEvent> for Application {
             VirtualKeyCode::PageDown => _ = self.page_send_half.send_command(PageCommand::Action(PageCommandAction::PageDown)),
             VirtualKeyCode::PageUp => _ = self.page_send_half.send_command(PageCommand::Action(PageCommandAction::PageUp)),
 
+            VirtualKeyCode::Tab => {
+                let action = if event.with_shift() {
+                    PageCommandAction::FocusPrevious
+                } else {
+                    PageCommandAction::FocusNext
+                };
+                _ = self.page_send_half.send_command(PageCommand::Action(action));
+            }
+
             VirtualKeyCode::Home => _ = self.page_send_half.send_command(PageCommand::Action(PageCommandAction::ScrollToTop)),
             VirtualKeyCode::End => _ = self.page_send_half.send_command(PageCommand::Action(PageCommandAction::ScrollToBottom)),
 